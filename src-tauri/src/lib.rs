@@ -6,8 +6,11 @@ use tauri::image::Image;
 use modules::{
     get_system_overview,
     get_resources,
+    get_gpu_details,
+    get_tpm_info,
     get_os_info,
     get_platform,
+    get_platform_capabilities,
     save_report_file,
     list_processes,
     kill_process,
@@ -17,24 +20,79 @@ use modules::{
     list_startup_apps,
     toggle_apt_repo,
     add_apt_repo,
+    add_apt_repo_deb822,
+    add_ppa,
+    validate_apt_repo,
+    repo_health_check,
+    list_repo_backups,
+    restore_repo_backup,
     delete_apt_repo,
+    refresh_package_index,
+    list_apt_keys,
+    add_apt_key,
+    delete_apt_key,
+    list_packages,
+    get_package_details,
+    list_upgradable_packages,
+    upgrade_packages,
+    get_auto_update_config,
+    set_auto_updates,
     add_startup_app,
     edit_startup_app,
     delete_startup_app,
+    list_installed_applications,
     toggle_startup_app,
     list_devices,
+    get_nvme_info,
+    eject_device,
+    get_fstab_entries,
     list_usb_devices,
+    get_usb_device_details,
     list_network_devices,
+    get_network_device_details,
+    scan_wifi_networks,
+    get_wifi_status,
     list_pci_devices,
     list_input_devices,
+    list_video_devices,
+    list_serial_devices,
+    list_thunderbolt_devices,
+    authorize_thunderbolt_device,
+    list_power_supplies,
+    start_input_test,
+    stop_input_test,
+    list_printers,
+    list_print_jobs,
+    cancel_print_job,
     write_log,
     read_log_file,
+    read_log_entries,
+    read_log_tail,
+    log_file_info,
+    start_log_follow,
+    stop_log_follow,
+    search_log,
     clear_log_file,
     list_scripts,
     add_script,
     remove_script,
     update_script,
+    reorder_scripts,
+    set_script_chain,
+    set_script_notify,
+    list_favorite_scripts,
+    set_script_favorite,
+    set_script_login_shell,
+    set_script_accepts_stdin,
+    set_script_skip_preflight,
     run_script,
+    preview_script,
+    run_script_streaming,
+    cancel_script_run,
+    get_script_history,
+    clear_script_history,
+    export_scripts,
+    import_scripts,
     list_services,
     start_service,
     stop_service,
@@ -43,43 +101,183 @@ use modules::{
     disable_service,
     get_settings,
     set_theme,
+    set_temperature_unit,
+    export_settings,
+    import_settings,
+    reset_settings,
+    reset_window_state,
+    set_module_enabled,
+    update_alert_settings,
+    set_start_at_login,
+    set_start_minimized,
+    set_page_preference,
+    set_repo_backup_keep,
+    set_notifications_enabled,
+    set_script_output_limit,
+    update_settings,
+    list_cron_jobs,
+    add_cron_job,
+    edit_cron_job,
+    delete_cron_job,
+    toggle_cron_job,
+    list_environment_entries,
+    set_environment_entry,
+    delete_environment_entry,
+    get_active_alerts,
+    begin_admin_session,
+    end_admin_session,
+    admin_session_status,
+    open_monitor_widget,
+    close_monitor_widget,
+    set_global_shortcut,
+    list_global_shortcuts,
+    get_last_crash,
+    clear_last_crash,
+    set_metrics_endpoint,
+    get_metrics_endpoint,
+    get_network_top_talkers,
+    get_firewall_status,
+    add_firewall_rule,
+    delete_firewall_rule,
+    get_cleanup_candidates,
+    run_cleanup,
+    get_usage_by_user,
+    export_app_data,
+    import_app_data,
+    get_thermal_history,
+    take_resource_snapshot,
+    list_resource_snapshots,
+    compare_resource_snapshots,
+    get_disk_growth,
+    global_search,
+    set_network_device_state,
+    set_network_device_mtu,
+    get_wol_status,
+    set_wol,
+    send_wol_packet,
+    list_lan_neighbors,
+    ping_host,
+    get_process_detail,
 };
 
+pub fn run_script_cli(target: &str, args: std::collections::HashMap<String, String>) -> i32 {
+    modules::scripts::run_script_cli(target, args)
+}
+
+pub fn run_admin_helper(ttl_secs: u64) -> i32 {
+    modules::admin::run_helper(ttl_secs)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    modules::crash::install_panic_hook();
+
+    let listener = match modules::instance::acquire() {
+        Ok(modules::instance::Instance::Forwarded) => return,
+        Ok(modules::instance::Instance::Primary(listener)) => Some(listener),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    };
+
     tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(modules::shortcuts::handle)
+                .build(),
+        )
         .invoke_handler(tauri::generate_handler![
             get_system_overview,
             get_resources,
+            get_gpu_details,
+            get_tpm_info,
             get_os_info,
             get_platform,
+            get_platform_capabilities,
             save_report_file,
             list_processes,
             kill_process,
             kill_process_group,
             get_processor_info,
             list_devices,
+            get_nvme_info,
+            eject_device,
+            get_fstab_entries,
             list_usb_devices,
+            get_usb_device_details,
             list_network_devices,
+            get_network_device_details,
+            scan_wifi_networks,
+            get_wifi_status,
             list_pci_devices,
             list_input_devices,
+            list_video_devices,
+            list_serial_devices,
+            list_thunderbolt_devices,
+            authorize_thunderbolt_device,
+            list_power_supplies,
+            start_input_test,
+            stop_input_test,
+            list_printers,
+            list_print_jobs,
+            cancel_print_job,
             list_apt_repos,
             list_startup_apps,
             toggle_apt_repo,
             add_apt_repo,
+            add_apt_repo_deb822,
+            add_ppa,
+            validate_apt_repo,
+            repo_health_check,
+            list_repo_backups,
+            restore_repo_backup,
             delete_apt_repo,
+            refresh_package_index,
+            list_apt_keys,
+            add_apt_key,
+            delete_apt_key,
+            list_packages,
+            get_package_details,
+            list_upgradable_packages,
+            upgrade_packages,
+            get_auto_update_config,
+            set_auto_updates,
             add_startup_app,
             edit_startup_app,
             delete_startup_app,
+            list_installed_applications,
             toggle_startup_app,
             write_log,
             read_log_file,
+            read_log_entries,
+            read_log_tail,
+            log_file_info,
+            start_log_follow,
+            stop_log_follow,
+            search_log,
             clear_log_file,
             list_scripts,
             add_script,
             remove_script,
             update_script,
+            reorder_scripts,
+            set_script_chain,
+            set_script_notify,
+            list_favorite_scripts,
+            set_script_favorite,
+            set_script_login_shell,
+            set_script_accepts_stdin,
+            set_script_skip_preflight,
             run_script,
+            preview_script,
+            run_script_streaming,
+            cancel_script_run,
+            get_script_history,
+            clear_script_history,
+            export_scripts,
+            import_scripts,
             list_services,
             start_service,
             stop_service,
@@ -88,6 +286,63 @@ pub fn run() {
             disable_service,
             get_settings,
             set_theme,
+            set_temperature_unit,
+            export_settings,
+            import_settings,
+            reset_settings,
+            reset_window_state,
+            set_module_enabled,
+            update_alert_settings,
+            set_start_at_login,
+            set_start_minimized,
+            set_page_preference,
+            set_repo_backup_keep,
+            set_notifications_enabled,
+            set_script_output_limit,
+            update_settings,
+            list_cron_jobs,
+            add_cron_job,
+            edit_cron_job,
+            delete_cron_job,
+            toggle_cron_job,
+            list_environment_entries,
+            set_environment_entry,
+            delete_environment_entry,
+            get_active_alerts,
+            begin_admin_session,
+            end_admin_session,
+            admin_session_status,
+            open_monitor_widget,
+            close_monitor_widget,
+            set_global_shortcut,
+            list_global_shortcuts,
+            get_last_crash,
+            clear_last_crash,
+            set_metrics_endpoint,
+            get_metrics_endpoint,
+            get_network_top_talkers,
+            get_firewall_status,
+            add_firewall_rule,
+            delete_firewall_rule,
+            get_cleanup_candidates,
+            run_cleanup,
+            get_usage_by_user,
+            export_app_data,
+            import_app_data,
+            get_thermal_history,
+            take_resource_snapshot,
+            list_resource_snapshots,
+            compare_resource_snapshots,
+            get_disk_growth,
+            global_search,
+            set_network_device_state,
+            set_network_device_mtu,
+            get_wol_status,
+            set_wol,
+            send_wol_packet,
+            list_lan_neighbors,
+            ping_host,
+            get_process_detail,
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -103,10 +358,27 @@ pub fn run() {
                 if let Ok(icon) = Image::from_bytes(icon_bytes) {
                     let _ = window.set_icon(icon);
                 }
+                modules::settings::restore_window_state(&window);
+                modules::settings::track_window_state(&window);
+                modules::widget::hide_main_on_close(&window);
+            }
+
+            modules::tasks::exit_on_signals(app.handle().clone());
+            modules::alerts::start_alert_monitor(app.handle().clone());
+            modules::system::start_resource_stream(app.handle().clone());
+            modules::shortcuts::register_all(app.handle());
+            modules::metrics::restart_from_settings();
+            if let Some(listener) = listener {
+                modules::instance::serve(listener, app.handle().clone());
             }
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                modules::tasks::shutdown(app);
+            }
+        });
 }
@@ -5,15 +5,58 @@ fn print_help() {
   println!("A lightweight system management app");
   println!();
   println!("Usage: gantry [OPTIONS]");
+  println!("       gantry run-script <NAME-OR-ID> [--arg KEY=VALUE ...]");
+  println!();
+  println!("Commands:");
+  println!("  run-script     Run a saved script, print its output and exit with its code");
   println!();
   println!("Options:");
   println!("  -h, --help     Print help");
   println!("  -V, --version  Print version");
 }
 
+fn run_script_command(args: &[String]) -> i32 {
+  let mut target = None;
+  let mut values = std::collections::HashMap::new();
+  let mut rest = args.iter();
+  while let Some(arg) = rest.next() {
+    if arg == "--arg" {
+      let Some((key, value)) = rest.next().and_then(|pair| pair.split_once('=')) else {
+        eprintln!("gantry: --arg expects KEY=VALUE");
+        return 2;
+      };
+      values.insert(key.to_string(), value.to_string());
+    } else if target.is_none() {
+      target = Some(arg.as_str());
+    } else {
+      eprintln!("gantry: unexpected argument {}", arg);
+      return 2;
+    }
+  }
+  let Some(target) = target else {
+    eprintln!("Usage: gantry run-script <NAME-OR-ID> [--arg KEY=VALUE ...]");
+    return 2;
+  };
+  gantry_lib::run_script_cli(target, values)
+}
+
 fn main() {
   let args: Vec<String> = std::env::args().collect();
 
+  if args.get(1).map(String::as_str) == Some("run-script") {
+    std::process::exit(run_script_command(&args[2..]));
+  }
+
+  // Started as root by pkexec for an admin session; never opens a window.
+  if args.get(1).map(String::as_str) == Some("admin-helper") {
+    let ttl = args.iter()
+      .position(|a| a == "--ttl")
+      .and_then(|i| args.get(i + 1))
+      .and_then(|t| t.parse().ok())
+      .unwrap_or(300);
+    std::process::exit(gantry_lib::run_admin_helper(ttl));
+  }
+
   let has_flag = |short: &str, long: &str| -> bool {
     args.iter().any(|a| a == short || a == long)
   };
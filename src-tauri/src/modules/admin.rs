@@ -0,0 +1,660 @@
+use super::error::GantryError;
+use super::util::run_tool;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+#[cfg(target_os = "linux")]
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+#[cfg(target_os = "linux")]
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+
+const MAX_FRAME_BYTES: u32 = 1024 * 1024;
+
+// The only directories the root helper writes files into or removes them from.
+const WRITABLE_ROOTS: &[&str] = &["/etc/apt/", "/etc/systemd/"];
+
+const SYSTEMCTL_VERBS: &[&str] = &["start", "stop", "restart", "reload", "enable", "disable", "daemon-reload"];
+
+const HELPER_MAX_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[cfg(target_os = "linux")]
+static SESSION: OnceLock<Mutex<Option<AdminSession>>> = OnceLock::new();
+
+// A frame is a big-endian u32 length, then that many bytes of JSON.
+fn write_frame(w: &mut impl Write, value: &Value) -> std::io::Result<()> {
+    let body = serde_json::to_vec(value)?;
+    if body.len() > MAX_FRAME_BYTES as usize {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Request is too large"));
+    }
+    w.write_all(&(body.len() as u32).to_be_bytes())?;
+    w.write_all(&body)?;
+    w.flush()
+}
+
+fn read_frame(r: &mut impl Read) -> std::io::Result<Value> {
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_FRAME_BYTES {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Frame is too large"));
+    }
+    let mut body = vec![0; len as usize];
+    r.read_exact(&mut body)?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Requests that don't parse as one of these are refused, so the helper never runs a command line it
+// was handed.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum Request {
+    Ping,
+    WriteFile { path: String, content: String },
+    RemoveFile { path: String },
+    Systemctl { verb: String, unit: Option<String> },
+    Sysctl { key: String, value: String },
+    FirewallStatus { backend: String },
+    AddFirewallRule { backend: String, rule: super::firewall::NewRule },
+    DeleteFirewallRule { backend: String, id: String },
+    Cleanup { target: String },
+    SetLink { interface: String, up: Option<bool>, mtu: Option<u32> },
+    SetWol { interface: String, enabled: bool },
+    InstallKeyring { name: String, key: Vec<u8> },
+    WriteEnvironment { content: String },
+    SetAutoUpdates { enabled: bool, with_reboot: bool },
+    WifiScan { interface: String },
+    NvmeHealth { controller: String },
+    AuthorizeThunderbolt { id: String },
+}
+
+// The parent is resolved so a symlinked directory can't lead out of WRITABLE_ROOTS.
+fn checked_path(path: &str) -> Result<PathBuf, String> {
+    let refused = || format!("{} is outside what an admin session may change", path);
+    let p = Path::new(path);
+    let under_root = |s: &str| WRITABLE_ROOTS.iter().any(|root| s.starts_with(root));
+    if !p.is_absolute() || p.components().any(|c| matches!(c, Component::ParentDir)) || !under_root(path) {
+        return Err(refused());
+    }
+    let name = p.file_name().ok_or_else(refused)?;
+    let parent = p.parent().ok_or_else(refused)?.canonicalize().map_err(|e| format!("{}: {}", path, e))?;
+    if !under_root(&format!("{}/", parent.display())) {
+        return Err(refused());
+    }
+    let target = parent.join(name);
+    if fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink()) {
+        return Err(format!("{} is a symlink", path));
+    }
+    Ok(target)
+}
+
+// Never starting with `-`, so a unit can't pass for a systemctl option.
+fn is_valid_unit(unit: &str) -> bool {
+    !unit.is_empty()
+        && !unit.starts_with('-')
+        && unit.chars().all(|c| c.is_ascii_alphanumeric() || "@._:-\\".contains(c))
+}
+
+fn is_valid_sysctl_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(['-', '.', '/'])
+        && !key.contains("..")
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || "._/-".contains(c))
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    let mode = fs::metadata(path).map(|m| m.permissions().mode() & 0o7777).unwrap_or(0o644);
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.gantry-tmp", name));
+    let written = fs::write(&tmp, content)
+        .and_then(|_| fs::set_permissions(&tmp, fs::Permissions::from_mode(mode)))
+        .and_then(|_| fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn run_command(command: &[String]) -> Result<String, String> {
+    let args: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+    run_tool(&command[0], &args).map_err(String::from)
+}
+
+fn run_all(commands: &[Vec<String>]) -> Result<(), String> {
+    for command in commands {
+        run_command(command)?;
+    }
+    Ok(())
+}
+
+fn handle(request: Request) -> Result<Option<String>, String> {
+    match request {
+        Request::Ping => Ok(None),
+        Request::WriteFile { path, content } => write_file(&checked_path(&path)?, content.as_bytes()).map(|_| None),
+        Request::RemoveFile { path } => {
+            fs::remove_file(checked_path(&path)?).map(|_| None).map_err(|e| format!("{}: {}", path, e))
+        }
+        Request::FirewallStatus { backend } => {
+            run_command(&super::firewall::status_command(&backend).map_err(|e| e.message())?).map(Some)
+        }
+        Request::AddFirewallRule { backend, rule } => {
+            run_all(&super::firewall::add_commands(&backend, &rule).map_err(|e| e.message())?).map(|_| None)
+        }
+        Request::DeleteFirewallRule { backend, id } => {
+            run_all(&super::firewall::delete_commands(&backend, &id).map_err(|e| e.message())?).map(|_| None)
+        }
+        #[cfg(target_os = "linux")]
+        Request::Cleanup { target } => {
+            let command = super::maintenance::cleanup_command(&target).map_err(|e| e.message())?;
+            if command.is_empty() {
+                return Ok(None);
+            }
+            run_all(&[command]).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::Cleanup { .. } => Err("System cleanups are only available on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::SetLink { interface, up, mtu } => {
+            run_all(&[super::network::link_command(&interface, up, mtu).map_err(|e| e.message())?]).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::SetLink { .. } => Err("Link changes through the helper are only available on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::SetWol { interface, enabled } => {
+            run_all(&[super::network::wol_command(&interface, enabled).map_err(|e| e.message())?]).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::SetWol { .. } => Err("Wake-on-LAN through the helper is only available on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::InstallKeyring { name, key } => {
+            let path = super::config::keyring_path(&name)?;
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+            }
+            write_file(&checked_path(&path.to_string_lossy())?, &key).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::InstallKeyring { .. } => Err("APT keyrings are only available on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::WriteEnvironment { content } => {
+            write_file(Path::new(super::environment::SYSTEM_ENVIRONMENT_FILE), content.as_bytes()).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::WriteEnvironment { .. } => Err("/etc/environment is only managed on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::SetAutoUpdates { enabled, with_reboot } => {
+            let plan = super::packages::auto_update_plan(enabled, with_reboot)?;
+            write_file(Path::new(plan.target), plan.content.as_bytes())?;
+            run_all(plan.then.as_slice()).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::SetAutoUpdates { .. } => Err("Automatic updates are only managed on Linux".to_string()),
+        #[cfg(target_os = "linux")]
+        Request::WifiScan { interface } => {
+            let command = super::network::wifi_scan_command(&interface).map_err(|e| e.message())?;
+            run_command(&command).map(Some)
+        }
+        #[cfg(target_os = "linux")]
+        Request::NvmeHealth { controller } => {
+            let command = super::storage::nvme_health_command(&controller).map_err(|e| e.message())?;
+            run_command(&command).map(Some)
+        }
+        #[cfg(target_os = "linux")]
+        Request::AuthorizeThunderbolt { id } => {
+            run_all(&[super::devices::thunderbolt_authorize_command(&id).map_err(|e| e.message())?]).map(|_| None)
+        }
+        #[cfg(target_os = "macos")]
+        Request::WifiScan { .. } | Request::NvmeHealth { .. } | Request::AuthorizeThunderbolt { .. } => {
+            Err("This request is only available on Linux".to_string())
+        }
+        Request::Systemctl { verb, unit } => {
+            if !SYSTEMCTL_VERBS.contains(&verb.as_str()) {
+                return Err(format!("systemctl {} is not allowed", verb));
+            }
+            let ran = match (verb.as_str(), unit) {
+                ("daemon-reload", None) => run_tool("systemctl", &["daemon-reload"]).map_err(String::from),
+                ("daemon-reload", Some(_)) => Err("daemon-reload takes no unit".to_string()),
+                (_, Some(unit)) if is_valid_unit(&unit) => run_tool("systemctl", &[&verb, &unit]).map_err(String::from),
+                (_, unit) => Err(format!("Invalid unit name: {}", unit.unwrap_or_default())),
+            };
+            ran.map(|_| None)
+        }
+        Request::Sysctl { key, value } => {
+            if !is_valid_sysctl_key(&key) || value.starts_with('-') || value.contains(['\n', '\r', '\0']) {
+                return Err(format!("Invalid sysctl setting: {}", key));
+            }
+            run_tool("sysctl", &["-w", &format!("{}={}", key, value)]).map(|_| None).map_err(String::from)
+        }
+    }
+}
+
+fn reply_to(request: Value) -> Value {
+    let result = serde_json::from_value::<Request>(request)
+        .map_err(|e| format!("Refused request: {}", e))
+        .and_then(handle);
+    match result {
+        Ok(None) => json!({"ok": true}),
+        Ok(Some(output)) => json!({"ok": true, "output": output}),
+        Err(e) => json!({"ok": false, "error": e}),
+    }
+}
+
+pub fn run_helper(ttl_secs: u64) -> i32 {
+    if unsafe { libc::geteuid() } != 0 {
+        eprintln!("gantry: admin-helper must run as root");
+        return 1;
+    }
+    let ttl = Duration::from_secs(ttl_secs).min(HELPER_MAX_TTL);
+    std::thread::spawn(move || {
+        std::thread::sleep(ttl);
+        std::process::exit(0);
+    });
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+    while let Ok(request) = read_frame(&mut input) {
+        if write_frame(&mut output, &reply_to(request)).is_err() {
+            break;
+        }
+    }
+    0
+}
+
+#[cfg(target_os = "linux")]
+struct AdminSession {
+    child: Child,
+    input: ChildStdin,
+    output: ChildStdout,
+    expires: Instant,
+    expires_at: chrono::DateTime<chrono::Local>,
+}
+
+#[cfg(target_os = "linux")]
+impl AdminSession {
+    fn exchange(&mut self, request: &Value) -> std::io::Result<Value> {
+        write_frame(&mut self.input, request)?;
+        read_frame(&mut self.output)
+    }
+
+    fn close(mut self) {
+        drop(self.input);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn session() -> &'static Mutex<Option<AdminSession>> {
+    SESSION.get_or_init(Default::default)
+}
+
+// Root can't read an AppImage's mount, so pkexec starts the image itself.
+#[cfg(target_os = "linux")]
+fn helper_program() -> Result<PathBuf, GantryError> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    Ok(std::env::current_exe()?)
+}
+
+#[cfg(target_os = "linux")]
+fn routed(request: Value) -> Option<Result<Option<String>, GantryError>> {
+    let mut guard = session().lock().ok()?;
+    if guard.as_ref().is_some_and(|s| Instant::now() >= s.expires) {
+        guard.take()?.close();
+    }
+    let reply = guard.as_mut()?.exchange(&request);
+    match reply {
+        Ok(reply) if reply["ok"] == json!(true) => Some(Ok(reply["output"].as_str().map(String::from))),
+        Ok(reply) => {
+            let error = reply["error"].as_str().unwrap_or("The admin helper refused the request");
+            Some(Err(GantryError::classify(error.to_string())))
+        }
+        Err(_) => {
+            guard.take()?.close();
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pkexec(args: &[&str]) -> Result<String, GantryError> {
+    let output = Command::new("pkexec")
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| GantryError::spawn("pkexec", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(GantryError::from_output("pkexec", &output))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn privileged(action: &str, target: &str, request: Option<Value>, fallback: &[&str]) -> Result<(), GantryError> {
+    let (via, result) = match request.and_then(routed) {
+        Some(result) => ("session", result.map(|_| ())),
+        None => ("pkexec", pkexec(fallback).map(|_| ())),
+    };
+    super::audit::record(action, target, via, &result);
+    result
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn copy_file(source: &Path, dest: &str) -> Result<(), GantryError> {
+    // Unreadable or non-UTF-8 sources go through cp, which handles both.
+    let request = fs::read_to_string(source)
+        .ok()
+        .map(|content| json!({"op": "write_file", "path": dest, "content": content}));
+    privileged("write_file", dest, request, &["cp", &source.to_string_lossy(), dest])
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn remove_file(path: &str) -> Result<(), GantryError> {
+    privileged("remove_file", path, Some(json!({"op": "remove_file", "path": path})), &["rm", path])
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn install_keyring(name: &str, source: &Path) -> Result<(), GantryError> {
+    let dest = super::config::keyring_path(name)?.to_string_lossy().to_string();
+    let request = fs::read(source).ok().map(|key| json!({"op": "install_keyring", "name": name, "key": key}));
+    privileged("install_keyring", &dest, request, &["install", "-D", "-m", "0644", &source.to_string_lossy(), &dest])
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn write_environment(source: &Path) -> Result<(), GantryError> {
+    let dest = super::environment::SYSTEM_ENVIRONMENT_FILE;
+    let request = fs::read_to_string(source).ok().map(|content| json!({"op": "write_environment", "content": content}));
+    privileged("write_file", dest, request, &["cp", &source.to_string_lossy(), dest])
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn systemctl(verb: &str, unit: &str) -> Result<(), GantryError> {
+    let request = json!({"op": "systemctl", "verb": verb, "unit": unit});
+    privileged("systemctl", &format!("{} {}", verb, unit), Some(request), &["systemctl", verb, unit])
+}
+
+// Each command goes in as positional parameters, so nothing in them is parsed by the shell.
+#[cfg(target_os = "linux")]
+fn chain_script(commands: &[Vec<String>]) -> String {
+    let mut n = 0;
+    commands
+        .iter()
+        .map(|command| {
+            command
+                .iter()
+                .map(|_| {
+                    n += 1;
+                    format!("\"${{{}}}\"", n)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+#[cfg(target_os = "linux")]
+fn read_privileged(request: Value, command: &[String]) -> Result<String, GantryError> {
+    match routed(request) {
+        Some(result) => result.map(|output| output.unwrap_or_default()),
+        None => pkexec(&command.iter().map(String::as_str).collect::<Vec<_>>()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn read_firewall(backend: &str, command: &[String]) -> Result<String, GantryError> {
+    read_privileged(json!({"op": "firewall_status", "backend": backend}), command)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn scan_wifi(interface: &str, command: &[String]) -> Result<String, GantryError> {
+    read_privileged(json!({"op": "wifi_scan", "interface": interface}), command)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn read_nvme_health(controller: &str, command: &[String]) -> Result<String, GantryError> {
+    read_privileged(json!({"op": "nvme_health", "controller": controller}), command)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn authorize_thunderbolt(id: &str, command: &[String]) -> Result<(), GantryError> {
+    let fallback: Vec<&str> = command.iter().map(String::as_str).collect();
+    privileged("authorize_thunderbolt", id, Some(json!({"op": "authorize_thunderbolt", "id": id})), &fallback)
+}
+
+#[cfg(target_os = "linux")]
+fn privileged_chain(action: &str, target: &str, request: Option<Value>, commands: &[Vec<String>]) -> Result<(), GantryError> {
+    let script = chain_script(commands);
+    let mut fallback: Vec<&str> = Vec::new();
+    if commands.len() > 1 {
+        fallback.extend(["sh", "-c", &script, "sh"]);
+    }
+    fallback.extend(commands.iter().flatten().map(String::as_str));
+    privileged(action, target, request, &fallback)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn change_firewall(action: &str, target: &str, request: Value, commands: &[Vec<String>]) -> Result<(), GantryError> {
+    privileged_chain(action, target, Some(request), commands)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_auto_updates(
+    enabled: bool,
+    with_reboot: bool,
+    plan: &super::packages::AutoUpdatePlan,
+    source: &Path,
+) -> Result<(), GantryError> {
+    let request = json!({"op": "set_auto_updates", "enabled": enabled, "with_reboot": with_reboot});
+    let mut commands = vec![vec!["cp".to_string(), source.to_string_lossy().to_string(), plan.target.to_string()]];
+    commands.extend(plan.then.clone());
+    privileged_chain("set_auto_updates", plan.target, Some(request), &commands)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn cleanup(target: &str, command: &[String]) -> Result<(), GantryError> {
+    let fallback: Vec<&str> = command.iter().map(String::as_str).collect();
+    privileged("cleanup", target, Some(json!({"op": "cleanup", "target": target})), &fallback)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_link(interface: &str, up: Option<bool>, mtu: Option<u32>, command: &[String]) -> Result<(), GantryError> {
+    let request = json!({"op": "set_link", "interface": interface, "up": up, "mtu": mtu});
+    let fallback: Vec<&str> = command.iter().map(String::as_str).collect();
+    privileged("set_link", &command[4..].join(" "), Some(request), &fallback)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_wol(interface: &str, enabled: bool, command: &[String]) -> Result<(), GantryError> {
+    let request = json!({"op": "set_wol", "interface": interface, "enabled": enabled});
+    let fallback: Vec<&str> = command.iter().map(String::as_str).collect();
+    privileged("set_wol", &command[2..].join(" "), Some(request), &fallback)
+}
+
+#[cfg(target_os = "linux")]
+fn start_failure(mut failed: AdminSession) -> GantryError {
+    drop(failed.input);
+    match failed.child.wait().ok().and_then(|s| s.code()) {
+        Some(126) => GantryError::Cancelled("Authentication was cancelled".into()),
+        Some(127) => GantryError::PermissionDenied("Not authorized to start an admin session".into()),
+        _ => GantryError::Failed("The admin helper did not start".into()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn session_status(guard: &Option<AdminSession>) -> Value {
+    match guard {
+        Some(s) if Instant::now() < s.expires => json!({"active": true, "expires_at": s.expires_at.to_rfc3339()}),
+        _ => json!({"active": false, "expires_at": null}),
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn begin_admin_session() -> Result<Value, GantryError> {
+    if let Some(old) = session().lock().map_err(|e| e.to_string())?.take() {
+        old.close();
+    }
+    let ttl = Duration::from_secs(super::settings::admin_session_minutes() * 60);
+    let mut child = Command::new("pkexec")
+        .arg(helper_program()?)
+        .args(["admin-helper", "--ttl", &ttl.as_secs().to_string()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| GantryError::spawn("pkexec", e))?;
+    let (Some(input), Some(output)) = (child.stdin.take(), child.stdout.take()) else {
+        let _ = child.kill();
+        return Err("Could not talk to the admin helper".into());
+    };
+    let mut new = AdminSession {
+        child,
+        input,
+        output,
+        expires: Instant::now() + ttl,
+        expires_at: chrono::Local::now() + chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::zero()),
+    };
+
+    // The first reply only comes once pkexec has authenticated.
+    let target = format!("{} minutes", ttl.as_secs() / 60);
+    if new.exchange(&json!({"op": "ping"})).is_err() {
+        let err = start_failure(new);
+        super::audit::record("begin_admin_session", &target, "pkexec", &Err(err.clone()));
+        return Err(err);
+    }
+    super::audit::record("begin_admin_session", &target, "pkexec", &Ok(()));
+
+    let mut guard = session().lock().map_err(|e| e.to_string())?;
+    *guard = Some(new);
+    Ok(session_status(&guard))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn end_admin_session() -> Result<Value, GantryError> {
+    if let Some(old) = session().lock().map_err(|e| e.to_string())?.take() {
+        old.close();
+        super::audit::record("end_admin_session", "", "session", &Ok(()));
+    }
+    Ok(json!({"active": false, "expires_at": null}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn admin_session_status() -> Result<Value, GantryError> {
+    let guard = session().lock().map_err(|e| e.to_string())?;
+    Ok(session_status(&guard))
+}
+
+pub(crate) fn close_session() {
+    #[cfg(target_os = "linux")]
+    if let Some(old) = session().lock().ok().and_then(|mut s| s.take()) {
+        old.close();
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn begin_admin_session() -> Result<Value, GantryError> {
+    Err(GantryError::Unsupported("Admin sessions need polkit and are only available on Linux".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn end_admin_session() -> Result<Value, GantryError> {
+    Ok(json!({"active": false, "expires_at": null}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn admin_session_status() -> Result<Value, GantryError> {
+    Ok(json!({"active": false, "expires_at": null}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_round_trip() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, &json!({"op": "ping"})).unwrap();
+        write_frame(&mut buf, &json!({"op": "remove_file", "path": "/etc/apt/x.list"})).unwrap();
+        let mut reader = buf.as_slice();
+        assert_eq!(read_frame(&mut reader).unwrap(), json!({"op": "ping"}));
+        assert_eq!(read_frame(&mut reader).unwrap()["path"], "/etc/apt/x.list");
+        assert!(read_frame(&mut reader).is_err(), "nothing left");
+
+        let oversized = (MAX_FRAME_BYTES + 1).to_be_bytes();
+        assert!(read_frame(&mut oversized.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_requests_outside_the_allowlist_are_refused() {
+        let refused = |request: Value| reply_to(request)["ok"] == json!(false);
+        assert!(refused(json!({"op": "exec", "command": "rm -rf /"})));
+        assert!(refused(json!({"op": "systemctl", "verb": "mask", "unit": "sshd.service"})));
+        assert!(refused(json!({"op": "systemctl", "verb": "start", "unit": "--force"})));
+        assert!(refused(json!({"op": "systemctl", "verb": "start"})));
+        assert!(refused(json!({"op": "sysctl", "key": "../../etc/shadow", "value": "1"})));
+        assert!(refused(json!({"op": "write_file", "path": "/etc/shadow", "content": ""})));
+        assert!(refused(json!({"op": "remove_file", "path": "/etc/apt/../shadow"})));
+        assert!(refused(json!({"op": "firewall_status", "backend": "iptables"})));
+        assert!(refused(json!({"op": "cleanup", "target": "home"})));
+        assert!(refused(json!({"op": "set_link", "interface": "lo", "up": false, "mtu": null})));
+        assert!(refused(json!({"op": "set_link", "interface": "eth0 type bridge", "up": true, "mtu": null})));
+        assert!(refused(json!({"op": "set_link", "interface": "eth0", "up": null, "mtu": 100000})));
+        assert!(refused(json!({"op": "set_wol", "interface": "-eth0", "enabled": true})));
+        assert!(refused(json!({"op": "delete_firewall_rule", "backend": "ufw", "id": "1; reboot"})));
+        let rule = json!({"action": "allow", "direction": "in", "protocol": "tcp", "port": "22 --dry-run", "source": null});
+        assert!(refused(json!({"op": "add_firewall_rule", "backend": "ufw", "rule": rule})));
+        assert!(refused(json!({"op": "install_keyring", "name": "../../shadow", "key": [0]})));
+        assert!(refused(json!({"op": "write_environment", "content": "", "path": "/etc/shadow"})));
+        assert!(refused(json!({"op": "set_auto_updates", "enabled": true, "with_reboot": false, "then": "reboot"})));
+        assert!(refused(json!({"op": "wifi_scan", "interface": "-h"})));
+        assert!(refused(json!({"op": "nvme_health", "controller": "nvme0; reboot"})));
+        assert!(refused(json!({"op": "authorize_thunderbolt", "id": "../0-1"})));
+        assert_eq!(reply_to(json!({"op": "ping"})), json!({"ok": true}));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_chain_script() {
+        let commands = vec![
+            vec!["firewall-cmd".to_string(), "--permanent".into(), "--add-port=22/tcp".into()],
+            vec!["firewall-cmd".to_string(), "--reload".into()],
+        ];
+        assert_eq!(chain_script(&commands), r#""${1}" "${2}" "${3}" && "${4}" "${5}""#);
+        assert_eq!(chain_script(&commands[1..]), r#""${1}" "${2}""#);
+    }
+
+    #[test]
+    fn test_checked_path() {
+        assert!(checked_path("etc/apt/sources.list").is_err());
+        assert!(checked_path("/etc/aptitude/x").is_err());
+        assert!(checked_path("/etc/systemd/../passwd").is_err());
+        assert!(checked_path("/etc/apt/").is_err());
+        if Path::new("/etc/apt").is_dir() {
+            assert_eq!(checked_path("/etc/apt/sources.list").unwrap(), PathBuf::from("/etc/apt/sources.list"));
+        }
+    }
+
+    #[test]
+    fn test_unit_and_sysctl_names() {
+        assert!(is_valid_unit("nginx.service"));
+        assert!(is_valid_unit("getty@tty1.service"));
+        assert!(!is_valid_unit("-nginx"));
+        assert!(!is_valid_unit("a b.service"));
+        assert!(is_valid_sysctl_key("net.ipv4.ip_forward"));
+        assert!(is_valid_sysctl_key("vm/swappiness"));
+        assert!(!is_valid_sysctl_key("-w"));
+        assert!(!is_valid_sysctl_key("net..x"));
+    }
+}
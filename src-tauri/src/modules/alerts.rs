@@ -0,0 +1,343 @@
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use super::settings::AlertSettings;
+use super::system::ResourceSample;
+
+const ALERT_INTERVAL: Duration = Duration::from_secs(5);
+
+// How far a reading has to come back past its threshold before the alert clears.
+const HYSTERESIS: f64 = 5.0;
+
+const GROWTH_HYSTERESIS_DAYS: f64 = 1.0;
+
+static MONITOR: OnceLock<Mutex<AlertMonitor>> = OnceLock::new();
+
+fn get_monitor() -> &'static Mutex<AlertMonitor> {
+    MONITOR.get_or_init(|| Mutex::new(AlertMonitor::default()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum AlertKind {
+    Cpu,
+    Memory,
+    Temperature,
+    Disk,
+    #[serde(rename = "disk_growth")]
+    DiskGrowth,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Alert {
+    key: String,
+    kind: AlertKind,
+    message: String,
+    value: f64,
+    threshold: f64,
+    since: String,
+}
+
+#[derive(Debug, Clone)]
+struct AlertChange {
+    raised: bool,
+    notify: bool,
+    alert: Alert,
+}
+
+struct Check {
+    key: String,
+    kind: AlertKind,
+    subject: String,
+    value: f64,
+    threshold: f64,
+    below: bool,
+}
+
+impl Check {
+    fn tripped(&self) -> bool {
+        if self.below {
+            self.value <= self.threshold
+        } else {
+            self.value >= self.threshold
+        }
+    }
+
+    fn recovered(&self) -> bool {
+        let hysteresis = if self.kind == AlertKind::DiskGrowth { GROWTH_HYSTERESIS_DAYS } else { HYSTERESIS };
+        if self.below {
+            self.value > self.threshold + hysteresis
+        } else {
+            self.value < self.threshold - hysteresis
+        }
+    }
+
+    fn message(&self) -> String {
+        match self.kind {
+            AlertKind::Cpu => format!("CPU usage is {:.0}% (limit {:.0}%)", self.value, self.threshold),
+            AlertKind::Memory => format!("Memory usage is {:.0}% (limit {:.0}%)", self.value, self.threshold),
+            AlertKind::Temperature => {
+                format!("{} is at {:.1} °C (limit {:.0} °C)", self.subject, self.value, self.threshold)
+            }
+            AlertKind::Disk => {
+                format!("{} has {:.1}% free space left (limit {:.0}%)", self.subject, self.value, self.threshold)
+            }
+            AlertKind::DiskGrowth => {
+                format!("{} is projected to be full in {:.1} days (limit {} days)", self.subject, self.value, self.threshold)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct AlertMonitor {
+    active: BTreeMap<String, Alert>,
+    cpu_over_since: Option<u64>,
+    last_notified: HashMap<String, u64>,
+}
+
+impl AlertMonitor {
+    fn evaluate(&mut self, settings: &AlertSettings, sample: &ResourceSample, now: u64) -> Vec<AlertChange> {
+        let mut checks = Vec::new();
+        if let Some(threshold) = settings.cpu_percent {
+            let since = if sample.cpu_percent >= threshold {
+                *self.cpu_over_since.get_or_insert(now)
+            } else {
+                self.cpu_over_since = None;
+                now
+            };
+            // Short bursts don't count until they have lasted long enough.
+            let sustained = now - since >= settings.cpu_sustained_secs;
+            if sustained || self.active.contains_key("cpu") {
+                checks.push(Check {
+                    key: "cpu".to_string(),
+                    kind: AlertKind::Cpu,
+                    subject: "CPU".to_string(),
+                    value: sample.cpu_percent,
+                    threshold,
+                    below: false,
+                });
+            }
+        } else {
+            self.cpu_over_since = None;
+        }
+        if let Some(threshold) = settings.memory_percent {
+            checks.push(Check {
+                key: "memory".to_string(),
+                kind: AlertKind::Memory,
+                subject: "Memory".to_string(),
+                value: sample.memory_percent,
+                threshold,
+                below: false,
+            });
+        }
+        if let Some(threshold) = settings.temperature_celsius {
+            for (sensor, celsius) in &sample.temperatures {
+                checks.push(Check {
+                    key: format!("temperature:{}", sensor),
+                    kind: AlertKind::Temperature,
+                    subject: sensor.clone(),
+                    value: *celsius,
+                    threshold,
+                    below: false,
+                });
+            }
+        }
+        for (mount, free) in &sample.disk_free {
+            if let Some(threshold) = settings.disk_threshold(mount) {
+                checks.push(Check {
+                    key: format!("disk:{}", mount),
+                    kind: AlertKind::Disk,
+                    subject: mount.clone(),
+                    value: *free,
+                    threshold,
+                    below: true,
+                });
+            }
+        }
+
+        if let Some(threshold) = settings.disk_full_days {
+            for (mount, days) in &sample.disk_days_until_full {
+                checks.push(Check {
+                    key: format!("disk_growth:{}", mount),
+                    kind: AlertKind::DiskGrowth,
+                    subject: mount.clone(),
+                    value: *days,
+                    threshold,
+                    below: true,
+                });
+            }
+        }
+
+        let mut changes = Vec::new();
+        for check in &checks {
+            if let Some(alert) = self.active.get_mut(&check.key) {
+                if check.recovered() {
+                    let alert = self.active.remove(&check.key).unwrap();
+                    changes.push(AlertChange { raised: false, notify: false, alert });
+                } else {
+                    alert.value = check.value;
+                    alert.message = check.message();
+                }
+            } else if check.tripped() {
+                let notify = self
+                    .last_notified
+                    .get(&check.key)
+                    .map(|last| now - last >= settings.cooldown_secs)
+                    .unwrap_or(true);
+                if notify {
+                    self.last_notified.insert(check.key.clone(), now);
+                }
+                let alert = Alert {
+                    key: check.key.clone(),
+                    kind: check.kind,
+                    message: check.message(),
+                    value: check.value,
+                    threshold: check.threshold,
+                    since: chrono::Local::now().to_rfc3339(),
+                };
+                self.active.insert(check.key.clone(), alert.clone());
+                changes.push(AlertChange { raised: true, notify, alert });
+            }
+        }
+
+        let gone: Vec<String> = self
+            .active
+            .keys()
+            .filter(|key| !checks.iter().any(|c| &c.key == *key))
+            .cloned()
+            .collect();
+        for key in gone {
+            if let Some(alert) = self.active.remove(&key) {
+                changes.push(AlertChange { raised: false, notify: false, alert });
+            }
+        }
+        changes
+    }
+}
+
+pub fn start_alert_monitor(app: tauri::AppHandle) {
+    super::tasks::spawn("alert-monitor", move |task| {
+        let started = Instant::now();
+        while !task.stopped() {
+            std::thread::sleep(ALERT_INTERVAL);
+            let settings = super::settings::alert_settings();
+            if !settings.enabled {
+                if let Ok(mut monitor) = get_monitor().lock() {
+                    *monitor = AlertMonitor::default();
+                }
+                continue;
+            }
+            let sample = super::system::sample_resources();
+            let changes = match get_monitor().lock() {
+                Ok(mut monitor) => monitor.evaluate(&settings, &sample, started.elapsed().as_secs()),
+                Err(_) => continue,
+            };
+            let notify = settings.desktop_notifications && super::settings::notifications_enabled();
+            for change in changes {
+                let state = if change.raised { "raised" } else { "cleared" };
+                let _ = app.emit("system-alert", json!({"state": state, "alert": change.alert}));
+                if change.raised && change.notify && notify {
+                    use tauri_plugin_notification::NotificationExt;
+                    let _ = app.notification().builder().title("Gantry alert").body(&change.alert.message).show();
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_active_alerts() -> Result<serde_json::Value, String> {
+    let monitor = get_monitor().lock().map_err(|e| e.to_string())?;
+    Ok(json!(monitor.active.values().collect::<Vec<_>>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu: f64, memory: f64, temp: f64, root_free: f64) -> ResourceSample {
+        ResourceSample {
+            cpu_percent: cpu,
+            memory_percent: memory,
+            temperatures: vec![("coretemp Package id 0".to_string(), temp)],
+            disk_free: vec![("/".to_string(), root_free)],
+            disk_days_until_full: Vec::new(),
+        }
+    }
+
+    fn raised(changes: &[AlertChange]) -> Vec<&str> {
+        changes.iter().filter(|c| c.raised).map(|c| c.alert.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_cpu_alert_needs_sustained_load() {
+        let settings = AlertSettings { enabled: true, ..AlertSettings::default() };
+        let mut monitor = AlertMonitor::default();
+        assert!(monitor.evaluate(&settings, &sample(95.0, 10.0, 40.0, 50.0), 0).is_empty());
+        assert!(monitor.evaluate(&settings, &sample(95.0, 10.0, 40.0, 50.0), 30).is_empty());
+        assert!(monitor.evaluate(&settings, &sample(20.0, 10.0, 40.0, 50.0), 35).is_empty(), "a dip restarts the clock");
+        assert!(monitor.evaluate(&settings, &sample(95.0, 10.0, 40.0, 50.0), 40).is_empty());
+        assert_eq!(raised(&monitor.evaluate(&settings, &sample(95.0, 10.0, 40.0, 50.0), 100)), ["cpu"]);
+    }
+
+    #[test]
+    fn test_alerts_use_hysteresis_and_cooldown() {
+        let settings = AlertSettings { enabled: true, cooldown_secs: 600, ..AlertSettings::default() };
+        let mut monitor = AlertMonitor::default();
+
+        let changes = monitor.evaluate(&settings, &sample(10.0, 91.0, 92.0, 8.0), 0);
+        assert_eq!(raised(&changes), ["memory", "temperature:coretemp Package id 0", "disk:/"]);
+        assert!(changes.iter().all(|c| c.notify));
+
+        assert!(monitor.evaluate(&settings, &sample(10.0, 88.0, 89.0, 12.0), 5).is_empty(), "hovering near the limit");
+        assert_eq!(monitor.active.len(), 3);
+
+        let changes = monitor.evaluate(&settings, &sample(10.0, 80.0, 92.0, 20.0), 10);
+        assert_eq!(changes.iter().filter(|c| !c.raised).count(), 2);
+        assert_eq!(monitor.active.len(), 1);
+
+        let changes = monitor.evaluate(&settings, &sample(10.0, 95.0, 92.0, 20.0), 20);
+        assert_eq!(raised(&changes), ["memory"]);
+        assert!(!changes[0].notify, "still inside the cooldown");
+        monitor.evaluate(&settings, &sample(10.0, 50.0, 92.0, 20.0), 30);
+        let changes = monitor.evaluate(&settings, &sample(10.0, 95.0, 92.0, 20.0), 700);
+        assert!(changes[0].notify);
+    }
+
+    #[test]
+    fn test_alerts_clear_when_unchecked_or_gone() {
+        let mut settings = AlertSettings { enabled: true, ..AlertSettings::default() };
+        let mut monitor = AlertMonitor::default();
+        monitor.evaluate(&settings, &sample(10.0, 95.0, 40.0, 5.0), 0);
+        assert_eq!(monitor.active.len(), 2);
+
+        settings.memory_percent = None;
+        let mut unmounted = sample(10.0, 95.0, 40.0, 5.0);
+        unmounted.disk_free.clear();
+        let changes = monitor.evaluate(&settings, &unmounted, 5);
+        assert_eq!(changes.iter().filter(|c| !c.raised).count(), 2);
+        assert!(monitor.active.is_empty());
+    }
+
+    #[test]
+    fn test_disk_growth_alert() {
+        let settings = AlertSettings { enabled: true, disk_full_days: Some(3.0), ..AlertSettings::default() };
+        let mut monitor = AlertMonitor::default();
+        let mut filling = sample(10.0, 10.0, 40.0, 50.0);
+        filling.disk_days_until_full = vec![("/home".to_string(), 2.5)];
+        let changes = monitor.evaluate(&settings, &filling, 0);
+        assert_eq!(raised(&changes), ["disk_growth:/home"]);
+        assert!(changes[0].alert.message.contains("2.5 days"));
+
+        filling.disk_days_until_full[0].1 = 3.5;
+        assert!(monitor.evaluate(&settings, &filling, 5).is_empty(), "inside the hysteresis");
+        filling.disk_days_until_full.clear();
+        let changes = monitor.evaluate(&settings, &filling, 10);
+        assert_eq!(changes.iter().filter(|c| !c.raised).count(), 1, "clears once it stops filling");
+    }
+}
@@ -0,0 +1,360 @@
+use super::error::GantryError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+const ARCHIVE_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.yaml";
+
+const CONFIG_FILES: &[&str] = &["settings.yaml", "scripts.yaml"];
+
+const MEMBER_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+const BACKUPS_KEEP: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    gantry_app_data: u32,
+    created: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct AppData {
+    settings: Option<String>,
+    scripts: Option<Value>,
+    history: Vec<(String, Vec<u8>)>,
+}
+
+pub(crate) fn config_root() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".gantry"))
+}
+
+pub(crate) fn config_path(name: &str) -> PathBuf {
+    config_root().unwrap_or_else(|| PathBuf::from(".").join(".gantry")).join(name)
+}
+
+fn is_log_file(name: &str) -> bool {
+    name == "app.log" || name == "audit.log" || name.strip_prefix("app.log.").is_some_and(|n| n.parse::<usize>().is_ok())
+}
+
+fn is_history_file(name: &str) -> bool {
+    name.strip_suffix(".json")
+        .is_some_and(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+}
+
+fn allowed_member(name: &str) -> bool {
+    if name == MANIFEST || CONFIG_FILES.contains(&name) {
+        return true;
+    }
+    if let Some(log) = name.strip_prefix("logs/") {
+        return is_log_file(log);
+    }
+    name.strip_prefix("script_history/").is_some_and(is_history_file)
+}
+
+pub(crate) fn file_names(dir: &Path, keep: fn(&str) -> bool) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| keep(name))
+        .collect();
+    names.sort();
+    names
+}
+
+fn archive_members(root: &Path, include_logs: bool, include_history: bool) -> Vec<(String, PathBuf)> {
+    let mut members: Vec<(String, PathBuf)> = CONFIG_FILES
+        .iter()
+        .map(|name| (name.to_string(), root.join(name)))
+        .filter(|(_, path)| path.is_file())
+        .collect();
+    if include_logs {
+        members.extend(file_names(root, is_log_file).into_iter().map(|name| (format!("logs/{}", name), root.join(name))));
+    }
+    if include_history {
+        let dir = root.join("script_history");
+        members.extend(
+            file_names(&dir, is_history_file).into_iter().map(|name| (format!("script_history/{}", name), dir.join(name))),
+        );
+    }
+    members
+}
+
+// Owner-only, since scripts can hold credentials.
+fn write_archive(root: &Path, out: &Path, include_logs: bool, include_history: bool) -> Result<Vec<String>, GantryError> {
+    let members = archive_members(root, include_logs, include_history);
+    let manifest = Manifest {
+        gantry_app_data: ARCHIVE_VERSION,
+        created: chrono::Local::now().to_rfc3339(),
+        files: members.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    let manifest = serde_yaml::to_string(&manifest)?;
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(out)
+        .map_err(|e| GantryError::from(e).context(&format!("Failed to create {}", out.display())))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    builder.append_data(&mut header, MANIFEST, manifest.as_bytes())?;
+    for (name, path) in &members {
+        builder
+            .append_path_with_name(path, name)
+            .map_err(|e| GantryError::from(e).context(&format!("Failed to add {}", name)))?;
+    }
+    builder.into_inner()?.finish()?.sync_all()?;
+    Ok(members.into_iter().map(|(name, _)| name).collect())
+}
+
+fn not_an_archive(e: impl std::fmt::Display) -> GantryError {
+    GantryError::ParseError(format!("Not a Gantry app-data archive: {}", e))
+}
+
+// Links, directories and anything Gantry doesn't write are refused, so nothing can land outside the
+// config root.
+fn read_archive(reader: impl Read) -> Result<BTreeMap<String, Vec<u8>>, GantryError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().map_err(not_an_archive)? {
+        let mut entry = entry.map_err(not_an_archive)?;
+        let name = entry.path().map_err(not_an_archive)?.to_string_lossy().into_owned();
+        if !entry.header().entry_type().is_file() || !allowed_member(&name) {
+            return Err(not_an_archive(format!("unexpected entry {}", name)));
+        }
+        if entry.size() > MEMBER_MAX_BYTES {
+            return Err(not_an_archive(format!("{} is too large", name)));
+        }
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).map_err(not_an_archive)?;
+        if files.insert(name.clone(), content).is_some() {
+            return Err(not_an_archive(format!("{} appears twice", name)));
+        }
+    }
+    Ok(files)
+}
+
+fn check_archive(mut files: BTreeMap<String, Vec<u8>>) -> Result<AppData, GantryError> {
+    let manifest = files.remove(MANIFEST).ok_or_else(|| not_an_archive("no manifest"))?;
+    let manifest: Manifest = serde_yaml::from_slice(&manifest).map_err(not_an_archive)?;
+    if manifest.gantry_app_data > ARCHIVE_VERSION {
+        return Err(GantryError::ParseError(format!(
+            "Archive version {} is newer than this version of Gantry supports",
+            manifest.gantry_app_data
+        )));
+    }
+    if let Some(missing) = manifest.files.iter().find(|name| !files.contains_key(*name)) {
+        return Err(not_an_archive(format!("{} is listed but missing", missing)));
+    }
+
+    let mut data = AppData::default();
+    if let Some(content) = files.remove("settings.yaml") {
+        let content = String::from_utf8(content).map_err(|_| GantryError::ParseError("settings.yaml is not text".into()))?;
+        data.settings = Some(content);
+    }
+    if let Some(content) = files.remove("scripts.yaml") {
+        let scripts: Value = serde_yaml::from_slice(&content)
+            .map_err(|e| GantryError::ParseError(format!("scripts.yaml is not valid: {}", e)))?;
+        super::scripts::validate_scripts(&scripts).map_err(GantryError::ParseError)?;
+        data.scripts = Some(scripts);
+    }
+    for (name, content) in files {
+        let Some(file) = name.strip_prefix("script_history/") else {
+            continue;
+        };
+        if serde_json::from_slice::<Vec<Value>>(&content).is_err() {
+            return Err(GantryError::ParseError(format!("{} is not a script history file", name)));
+        }
+        data.history.push((file.to_string(), content));
+    }
+    Ok(data)
+}
+
+fn install_history(dir: &Path, history: &[(String, Vec<u8>)], merge: bool) -> Result<usize, GantryError> {
+    if history.is_empty() {
+        return Ok(0);
+    }
+    fs::create_dir_all(dir)?;
+    let mut written = 0;
+    for (name, content) in history {
+        let path = dir.join(name);
+        if merge && path.exists() {
+            continue;
+        }
+        fs::write(&path, content)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+fn backup_config(root: &Path, dir: &Path) -> Result<PathBuf, GantryError> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("app-data-{}.tar.gz", chrono::Local::now().format("%Y%m%d-%H%M%S-%3f")));
+    write_archive(root, &path, false, true)?;
+    let backups = file_names(dir, |name| name.starts_with("app-data-") && name.ends_with(".tar.gz"));
+    for old in &backups[..backups.len().saturating_sub(BACKUPS_KEEP)] {
+        let _ = fs::remove_file(dir.join(old));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn export_app_data(
+    path: Option<String>,
+    include_logs: Option<bool>,
+    include_history: Option<bool>,
+) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = config_root().ok_or_else(|| GantryError::NotFound("Could not find home directory".into()))?;
+        let path = match path {
+            Some(path) => super::scripts::expand_home(&path),
+            None => {
+                let dir = dirs::home_dir()
+                    .ok_or_else(|| GantryError::NotFound("Could not find home directory".into()))?
+                    .join("Downloads");
+                fs::create_dir_all(&dir)?;
+                dir.join(format!("gantry-data-{}.tar.gz", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+            }
+        };
+        let files = write_archive(&root, &path, include_logs.unwrap_or(false), include_history.unwrap_or(false))?;
+        Ok(json!({"path": path.to_string_lossy(), "files": files}))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn import_app_data(app: tauri::AppHandle, path: String, merge: bool) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::open(super::scripts::expand_home(&path))
+            .map_err(|e| GantryError::from(e).context(&format!("Failed to read {}", path)))?;
+        let data = check_archive(read_archive(file)?)?;
+        let settings = data
+            .settings
+            .as_deref()
+            .map(|content| super::settings::parse_settings_file(content, merge))
+            .transpose()
+            .map_err(GantryError::ParseError)?;
+
+        let root = config_root().ok_or_else(|| GantryError::NotFound("Could not find home directory".into()))?;
+        let backup = backup_config(&root, &root.join("app_data_backups"))?;
+        let scripts = match &data.scripts {
+            Some(snapshot) if merge => Some(super::scripts::merge_scripts(snapshot)?),
+            Some(snapshot) => Some(super::scripts::restore_scripts(snapshot)?),
+            None => None,
+        };
+        let history = install_history(&root.join("script_history"), &data.history, merge)?;
+        let shortcut_failures = match &settings {
+            Some(settings) => super::settings::install_settings(&app, settings)?,
+            None => Vec::new(),
+        };
+        let _ = super::logging::log(
+            "info",
+            "appdata",
+            &format!("Imported app data from {} ({}); previous config saved to {}", path, if merge { "merged" } else { "replaced" }, backup.display()),
+        );
+        Ok(json!({
+            "backup": backup.to_string_lossy(),
+            "settings": settings.is_some(),
+            "scripts": scripts,
+            "history": history,
+            "shortcut_failures": shortcut_failures,
+        }))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gantry-appdata-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("script_history")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let root = scratch_dir();
+        fs::write(root.join("settings.yaml"), "version: 1\ntheme: dark\n").unwrap();
+        fs::write(root.join("scripts.yaml"), "scripts:\n- id: s1\n  name: Hello\n  command: echo hi\n  requires_sudo: false\n  is_favorite: true\n").unwrap();
+        fs::write(root.join("app.log"), "line\n").unwrap();
+        fs::write(root.join("app.log.1"), "older\n").unwrap();
+        fs::write(root.join("instance.lock"), "1").unwrap();
+        fs::write(root.join("script_history").join("s1.json"), "[]").unwrap();
+
+        let out = root.join("out.tar.gz");
+        let files = write_archive(&root, &out, false, false).unwrap();
+        assert_eq!(files, vec!["settings.yaml", "scripts.yaml"], "logs and history are left out unless asked");
+        let files = write_archive(&root, &out, true, true).unwrap();
+        assert_eq!(files, vec!["settings.yaml", "scripts.yaml", "logs/app.log", "logs/app.log.1", "script_history/s1.json"]);
+
+        let data = check_archive(read_archive(fs::File::open(&out).unwrap()).unwrap()).unwrap();
+        assert_eq!(data.settings.as_deref(), Some("version: 1\ntheme: dark\n"));
+        assert_eq!(data.scripts.unwrap()["scripts"][0]["is_favorite"], true);
+        assert_eq!(data.history, vec![("s1.json".to_string(), b"[]".to_vec())]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn archive_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (name, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o600);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_bad_archives_are_rejected() {
+        let manifest = "gantry_app_data: 1\ncreated: now\nfiles: []\n";
+        let check = |entries: &[(&str, &str)]| read_archive(archive_of(entries).as_slice()).and_then(check_archive);
+
+        assert!(check(&[(MANIFEST, manifest)]).is_ok());
+        assert!(check(&[("settings.yaml", "theme: dark\n")]).is_err(), "the manifest is required");
+        assert!(check(&[(MANIFEST, "gantry_app_data: 9\ncreated: now\nfiles: []\n")]).is_err(), "newer archives are refused");
+        assert!(check(&[(MANIFEST, "gantry_app_data: 1\ncreated: now\nfiles: [scripts.yaml]\n")]).is_err());
+        assert!(check(&[(MANIFEST, manifest), ("../settings.yaml", "")]).is_err(), "nothing outside the root");
+        assert!(check(&[(MANIFEST, manifest), ("script_history/../x.json", "[]")]).is_err());
+        assert!(check(&[(MANIFEST, manifest), ("watches.yaml", "")]).is_err(), "unknown files are refused");
+        assert!(check(&[(MANIFEST, manifest), ("scripts.yaml", "scripts:\n- {id: a, name: A, command: ls, requires_sudo: false}\n- {id: a, name: B, command: ls, requires_sudo: false}\n")]).is_err());
+        assert!(check(&[(MANIFEST, manifest), ("script_history/a.json", "{}")]).is_err());
+        assert!(read_archive(&b"not gzip"[..]).is_err());
+    }
+
+    #[test]
+    fn test_install_history_merge_keeps_existing() {
+        let dir = scratch_dir().join("script_history");
+        fs::write(dir.join("a.json"), "[1]").unwrap();
+        let incoming = vec![("a.json".to_string(), b"[2]".to_vec()), ("b.json".to_string(), b"[3]".to_vec())];
+
+        assert_eq!(install_history(&dir, &incoming, true).unwrap(), 1);
+        assert_eq!(fs::read_to_string(dir.join("a.json")).unwrap(), "[1]");
+        assert_eq!(install_history(&dir, &incoming, false).unwrap(), 2);
+        assert_eq!(fs::read_to_string(dir.join("a.json")).unwrap(), "[2]");
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
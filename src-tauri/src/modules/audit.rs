@@ -0,0 +1,71 @@
+use super::error::GantryError;
+use serde_json::json;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+fn audit_log_path() -> Option<PathBuf> {
+    super::appdata::config_root().map(|root| root.join("audit.log"))
+}
+
+fn append_entry(path: &Path, entry: &serde_json::Value) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry)
+}
+
+pub(crate) fn flush() {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(file) = audit_log_path().and_then(|p| std::fs::File::open(p).ok()) {
+        let _ = file.sync_all();
+    }
+}
+
+fn entry(action: &str, target: &str, via: &str, result: &Result<(), GantryError>) -> serde_json::Value {
+    json!({
+        "ts": chrono::Local::now().to_rfc3339(),
+        "action": action,
+        "target": target,
+        "via": via,
+        "ok": result.is_ok(),
+        "code": result.as_ref().err().map(|e| e.code()),
+        "error": result.as_ref().err().map(|e| e.message()),
+    })
+}
+
+// One JSON object per line. A failed write is logged but never fails the action.
+pub(crate) fn record(action: &str, target: &str, via: &str, result: &Result<(), GantryError>) {
+    let Some(path) = audit_log_path() else { return };
+    if let Err(e) = append_entry(&path, &entry(action, target, via, result)) {
+        let _ = super::logging::log("warn", "audit", &format!("Could not write audit log: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_append_as_json_lines() {
+        let path = std::env::temp_dir().join(format!("gantry_audit_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        append_entry(&path, &entry("systemctl", "start nginx.service", "session", &Ok(()))).unwrap();
+        let denied = Err(GantryError::PermissionDenied("Not authorized".into()));
+        append_entry(&path, &entry("write_file", "/etc/apt/sources.list", "pkexec", &denied)).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<serde_json::Value> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["ok"], true);
+        assert!(lines[0]["code"].is_null());
+        assert_eq!(lines[1]["via"], "pkexec");
+        assert_eq!(lines[1]["code"], "permission_denied");
+    }
+}
@@ -0,0 +1,148 @@
+use super::error::GantryError;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const TOOLS: &[&str] = &["systemctl", "journalctl", "lsblk", "lspci", "lsusb", "ip", "nvidia-smi"];
+
+const PATHS: &[&str] = &["/sys/block", "/sys/class/hwmon", "/sys/class/drm", "/etc/apt"];
+
+const EXTRA_TOOL_DIRS: &[&str] = &["/usr/bin", "/usr/sbin", "/usr/local/bin", "/opt/homebrew/bin", "/snap/bin"];
+
+static CAPABILITIES: OnceLock<Capabilities> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Capabilities {
+    pub(crate) container: Option<String>,
+    pub(crate) systemd: bool,
+    tools: BTreeMap<&'static str, bool>,
+    paths: BTreeMap<&'static str, bool>,
+}
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+fn detect_container(root: &Path, snap: bool) -> Option<String> {
+    let at = |path: &str| root.join(path.trim_start_matches('/'));
+    if at("/.flatpak-info").is_file() {
+        return Some("flatpak".into());
+    }
+    if snap {
+        return Some("snap".into());
+    }
+    if at("/.dockerenv").exists() {
+        return Some("docker".into());
+    }
+    if at("/run/.containerenv").exists() {
+        return Some("podman".into());
+    }
+    fs::read_to_string(at("/run/systemd/container")).ok().map(|c| c.trim().to_string()).filter(|c| !c.is_empty())
+}
+
+impl Capabilities {
+    fn probe(path: Option<OsString>, extra_dirs: &[PathBuf], root: &Path, snap: bool) -> Self {
+        let dirs: Vec<PathBuf> = path
+            .map(|p| std::env::split_paths(&p).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .chain(extra_dirs.iter().map(|d| root.join(d.strip_prefix("/").unwrap_or(d))))
+            .collect();
+        let at = |path: &str| root.join(path.trim_start_matches('/'));
+        Capabilities {
+            container: detect_container(root, snap),
+            systemd: at("/run/systemd/system").is_dir(),
+            tools: TOOLS.iter().map(|&tool| (tool, dirs.iter().any(|d| is_executable(&d.join(tool))))).collect(),
+            paths: PATHS.iter().map(|&p| (p, fs::read_dir(at(p)).is_ok())).collect(),
+        }
+    }
+
+    pub(crate) fn has_tool(&self, tool: &str) -> bool {
+        self.tools.get(tool).copied().unwrap_or(false)
+    }
+
+    pub(crate) fn readable(&self, path: &str) -> bool {
+        self.paths.get(path).copied().unwrap_or(false)
+    }
+
+    pub(crate) fn reason(&self, reason: &str) -> String {
+        let sandbox = match self.container.as_deref() {
+            None => return reason.to_string(),
+            Some("flatpak") => "this Flatpak sandbox".to_string(),
+            Some("snap") => "this Snap".to_string(),
+            Some("docker") => "this Docker container".to_string(),
+            Some("podman") => "this Podman container".to_string(),
+            Some(other) => format!("this {} container", other),
+        };
+        format!("{} (running inside {})", reason, sandbox)
+    }
+
+    pub(crate) fn unsupported(&self, reason: &str) -> GantryError {
+        GantryError::Unsupported(self.reason(reason))
+    }
+}
+
+pub(crate) fn capabilities() -> &'static Capabilities {
+    CAPABILITIES.get_or_init(|| {
+        let extra: Vec<PathBuf> = EXTRA_TOOL_DIRS.iter().map(PathBuf::from).collect();
+        Capabilities::probe(std::env::var_os("PATH"), &extra, Path::new("/"), std::env::var_os("SNAP").is_some())
+    })
+}
+
+pub(crate) fn mark_degraded(value: &mut Value, section: &str, reason: String) {
+    value["degraded"] = json!(true);
+    if !value["degraded_reasons"].is_object() {
+        value["degraded_reasons"] = json!({});
+    }
+    value["degraded_reasons"][section] = json!(reason);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_with_path_override() {
+        let root = std::env::temp_dir().join(format!("gantry-caps-{}", uuid::Uuid::new_v4()));
+        let bin = root.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for (tool, mode) in [("lsblk", 0o755), ("ip", 0o644)] {
+            fs::write(bin.join(tool), "#!/bin/sh\n").unwrap();
+            fs::set_permissions(bin.join(tool), fs::Permissions::from_mode(mode)).unwrap();
+        }
+        fs::create_dir_all(root.join("sys/class/hwmon")).unwrap();
+        fs::write(root.join(".flatpak-info"), "[Application]\n").unwrap();
+
+        let caps = Capabilities::probe(Some(bin.clone().into_os_string()), &[], &root, false);
+        assert!(caps.has_tool("lsblk"));
+        assert!(!caps.has_tool("ip"), "not executable");
+        assert!(!caps.has_tool("systemctl"));
+        assert!(!caps.systemd);
+        assert!(caps.readable("/sys/class/hwmon"));
+        assert!(!caps.readable("/sys/block"));
+        assert_eq!(caps.container.as_deref(), Some("flatpak"));
+        let err = caps.unsupported("systemctl isn't available");
+        assert_eq!(err.code(), "unsupported");
+        assert_eq!(err.message(), "systemctl isn't available (running inside this Flatpak sandbox)");
+
+        // Extra directories are looked up under the same root.
+        fs::remove_file(root.join(".flatpak-info")).unwrap();
+        let caps = Capabilities::probe(None, &[PathBuf::from("/bin")], &root, false);
+        assert!(caps.has_tool("lsblk"));
+        assert_eq!(caps.container, None);
+        assert_eq!(caps.reason("lsblk failed"), "lsblk failed");
+        let _ = fs::remove_dir_all(&root);
+
+        let mut value = json!({"blockdevices": []});
+        mark_degraded(&mut value, "lsblk", "lsblk isn't available".into());
+        mark_degraded(&mut value, "uuid", "no /dev/disk".into());
+        assert_eq!(value["degraded"], true);
+        assert_eq!(value["degraded_reasons"]["lsblk"], "lsblk isn't available");
+        assert_eq!(value["degraded_reasons"].as_object().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,140 @@
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+static GENERATIONS: Mutex<BTreeMap<&'static str, Seen>> = Mutex::new(BTreeMap::new());
+
+struct Seen {
+    hash: u64,
+    generation: u64,
+}
+
+pub(crate) enum Fields {
+    All,
+    Only(&'static [&'static str]),
+}
+
+pub(crate) struct Dataset {
+    name: &'static str,
+    fields: Fields,
+}
+
+pub(crate) const SERVICES: Dataset = Dataset {
+    name: "services",
+    fields: Fields::Only(&[
+        "name",
+        "description",
+        "load_state",
+        "active_state",
+        "sub_state",
+        "is_running",
+        "is_enabled",
+        "is_user_service",
+    ]),
+};
+
+pub(crate) const PROCESSES: Dataset = Dataset { name: "processes", fields: Fields::All };
+
+pub(crate) const REPOS: Dataset = Dataset {
+    name: "repos",
+    fields: Fields::Only(&[
+        "id",
+        "file_path",
+        "line_number",
+        "types",
+        "uris",
+        "suites",
+        "components",
+        "enabled",
+        "fingerprint",
+    ]),
+};
+
+fn content_hash(fields: &Fields, data: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    match (fields, data.as_array()) {
+        (Fields::Only(keys), Some(rows)) => {
+            for row in rows {
+                for key in *keys {
+                    row[*key].to_string().hash(&mut hasher);
+                }
+            }
+        }
+        _ => data.to_string().hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+// A dataset seen for the first time starts at `now_ms`, so generations from an earlier run aren't
+// mistaken for current ones.
+fn advance(generations: &mut BTreeMap<&'static str, Seen>, name: &'static str, hash: u64, now_ms: u64) -> u64 {
+    let seen = generations.entry(name).or_insert(Seen { hash, generation: now_ms });
+    if seen.hash != hash {
+        seen.hash = hash;
+        seen.generation += 1;
+    }
+    seen.generation
+}
+
+pub(crate) fn respond(dataset: &Dataset, data: Value, if_changed_since: Option<u64>) -> Value {
+    let hash = content_hash(&dataset.fields, &data);
+    let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let generation = advance(&mut GENERATIONS.lock().unwrap_or_else(|e| e.into_inner()), dataset.name, hash, now_ms);
+    match if_changed_since {
+        None => data,
+        Some(since) if since == generation => json!({"unchanged": true, "generation": generation}),
+        Some(_) => json!({"unchanged": false, "generation": generation, "data": data}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(active_state: &str, cpu: f64) -> Value {
+        json!({"name": "ssh", "description": "OpenSSH", "load_state": "loaded", "active_state": active_state,
+               "sub_state": "running", "is_running": true, "is_enabled": true, "is_user_service": false, "cpu": cpu})
+    }
+
+    #[test]
+    fn test_hash_covers_identity_fields_only() {
+        let fields = &SERVICES.fields;
+        let base = content_hash(fields, &json!([service("active", 0.5)]));
+        assert_eq!(base, content_hash(fields, &json!([service("active", 12.0)])), "cpu isn't part of a service's identity");
+        assert_ne!(base, content_hash(fields, &json!([service("inactive", 0.5)])));
+        assert_ne!(base, content_hash(fields, &json!([service("active", 0.5), service("active", 0.5)])));
+
+        let repo = |enabled: bool, fingerprint: &str| json!([{"id": "/etc/apt/sources.list:3", "file_path": "/etc/apt/sources.list",
+            "line_number": 3, "types": "deb", "uris": "http://deb.debian.org/debian", "suites": "bookworm",
+            "components": "main", "enabled": enabled, "original_line": "deb http://deb.debian.org/debian bookworm main",
+            "fingerprint": fingerprint, "cpu": 1.0}]);
+        let base = content_hash(&REPOS.fields, &repo(true, "a1"));
+        assert_ne!(base, content_hash(&REPOS.fields, &repo(false, "a1")));
+        assert_ne!(base, content_hash(&REPOS.fields, &repo(true, "b2")));
+
+        let process = |cpu: f64| json!([{"name": "firefox", "total_cpu": cpu}]);
+        assert_ne!(content_hash(&PROCESSES.fields, &process(1.0)), content_hash(&PROCESSES.fields, &process(2.0)));
+    }
+
+    #[test]
+    fn test_generation_advances_on_change() {
+        let mut generations = BTreeMap::new();
+        assert_eq!(advance(&mut generations, "services", 7, 1_000), 1_000);
+        assert_eq!(advance(&mut generations, "services", 7, 2_000), 1_000, "same content, same generation");
+        assert_eq!(advance(&mut generations, "services", 8, 3_000), 1_001);
+        assert_eq!(advance(&mut generations, "repos", 8, 4_000), 4_000, "datasets are counted apart");
+
+        const TEST: Dataset = Dataset { name: "test", fields: Fields::All };
+        let data = json!([1, 2]);
+        assert_eq!(respond(&TEST, data.clone(), None), data);
+        let first = respond(&TEST, data.clone(), Some(0));
+        assert_eq!(first["unchanged"], false);
+        assert_eq!(first["data"], data);
+        let generation = first["generation"].as_u64().unwrap();
+        assert_eq!(respond(&TEST, data.clone(), Some(generation)), json!({"unchanged": true, "generation": generation}));
+        let changed = respond(&TEST, json!([1, 2, 3]), Some(generation));
+        assert_eq!(changed["generation"].as_u64(), Some(generation + 1));
+    }
+}
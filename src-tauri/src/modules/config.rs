@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use super::error::GantryError;
+use tauri::Emitter;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AptRepository {
@@ -15,19 +18,60 @@ pub struct AptRepository {
     pub components: String,
     pub enabled: bool,
     pub original_line: String,
+    pub fingerprint: String,
 }
 
 #[cfg(target_os = "linux")]
 fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
-    let mut repos = Vec::new();
     let content = match fs::read_to_string(path) {
         Ok(c) => c,
-        Err(_) => return repos,
+        Err(_) => return Vec::new(),
+    };
+    let is_deb822 = path.extension().map_or(false, |ext| ext == "sources");
+    parse_sources_content(&content, &path.to_string_lossy(), is_deb822)
+}
+
+#[cfg(target_os = "linux")]
+fn fnv1a_hex(text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+// Covers what the entry says rather than where it sits, so toggling keeps it.
+#[cfg(target_os = "linux")]
+fn repo_fingerprint(lines: &[&str], line_number: usize, is_deb822: bool) -> String {
+    let normalized = if is_deb822 {
+        let (start, end) = deb822_stanzas(lines)
+            .into_iter()
+            .find(|(start, end)| (*start..*end).contains(&line_number))
+            .unwrap_or((line_number, line_number));
+        lines[start..end]
+            .iter()
+            .map(|l| l.trim())
+            .filter(|l| !l.starts_with('#') && !l.starts_with("Enabled:"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        lines
+            .get(line_number)
+            .map(|l| l.trim().trim_start_matches('#').split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
     };
+    fnv1a_hex(&normalized)
+}
 
-    let file_path = path.to_string_lossy().to_string();
+// Ids are `<file>:<line>:<fingerprint>` so actions can find the repository again after the file
+// changed.
+#[cfg(target_os = "linux")]
+fn parse_sources_content(content: &str, file_path: &str, is_deb822: bool) -> Vec<AptRepository> {
+    let mut repos = Vec::new();
+    let file_path = file_path.to_string();
 
-    if path.extension().map_or(false, |ext| ext == "sources") {
+    if is_deb822 {
         let mut current_enabled = true;
         let mut current_types = String::new();
         let mut current_uris = String::new();
@@ -41,7 +85,7 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
             if line_trimmed.is_empty() {
                 if !current_uris.is_empty() {
                     repos.push(AptRepository {
-                        id: format!("{}:{}", file_path, start_line),
+                        id: String::new(),
                         file_path: file_path.clone(),
                         line_number: start_line,
                         types: current_types.clone(),
@@ -53,6 +97,7 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
                             "{} {} {} {}",
                             current_types, current_uris, current_suites, current_components
                         ),
+                        fingerprint: String::new(),
                     });
                 }
                 current_enabled = true;
@@ -83,7 +128,7 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
 
         if !current_uris.is_empty() {
             repos.push(AptRepository {
-                id: format!("{}:{}", file_path, start_line),
+                id: String::new(),
                 file_path: file_path.clone(),
                 line_number: start_line,
                 types: current_types,
@@ -92,6 +137,7 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
                 components: current_components,
                 enabled: current_enabled,
                 original_line: String::new(),
+                fingerprint: String::new(),
             });
         }
     } else {
@@ -115,7 +161,9 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
                 continue;
             };
 
-            let parts: Vec<&str> = effective_line.split_whitespace().collect();
+            // Leave out a `[arch=... signed-by=...]` option block after the type.
+            let mut tokens = effective_line.split_whitespace();
+            let parts: Vec<&str> = tokens.next().into_iter().chain(tokens.skip_while(|t| is_option_token(t))).collect();
             if parts.len() >= 3 {
                 let types = parts[0].to_string();
                 let uris = parts[1].to_string();
@@ -127,7 +175,7 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
                 };
 
                 repos.push(AptRepository {
-                    id: format!("{}:{}", file_path, idx),
+                    id: String::new(),
                     file_path: file_path.clone(),
                     line_number: idx,
                     types,
@@ -136,17 +184,43 @@ fn parse_sources_file(path: &PathBuf) -> Vec<AptRepository> {
                     components,
                     enabled: is_enabled,
                     original_line: line.to_string(),
+                    fingerprint: String::new(),
                 });
             }
         }
     }
 
+    let lines: Vec<&str> = content.lines().collect();
+    for repo in &mut repos {
+        repo.fingerprint = repo_fingerprint(&lines, repo.line_number, is_deb822);
+        repo.id = format!("{}:{}:{}", file_path, repo.line_number, repo.fingerprint);
+    }
     repos
 }
 
 #[cfg(target_os = "linux")]
-#[tauri::command]
-pub fn list_apt_repos() -> Result<serde_json::Value, String> {
+fn parse_repo_id(id: &str) -> Result<(&str, usize, &str), String> {
+    let parts: Vec<&str> = id.rsplitn(3, ':').collect();
+    if parts.len() != 3 || parts[0].is_empty() {
+        return Err("Invalid repository ID".to_string());
+    }
+    let line_number: usize = parts[1].parse().map_err(|_| "Invalid line number".to_string())?;
+    Ok((parts[2], line_number, parts[0]))
+}
+
+#[cfg(target_os = "linux")]
+fn locate_repo(content: &str, file_path: &str, is_deb822: bool, line_number: usize, fingerprint: &str) -> Result<usize, String> {
+    let repos = parse_sources_content(content, file_path, is_deb822);
+    repos
+        .iter()
+        .find(|r| r.line_number == line_number && r.fingerprint == fingerprint)
+        .or_else(|| repos.iter().find(|r| r.fingerprint == fingerprint))
+        .map(|r| r.line_number)
+        .ok_or_else(|| "Repository has changed since it was listed; refresh and try again".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn all_apt_repos() -> Vec<AptRepository> {
     let mut all_repos: Vec<AptRepository> = Vec::new();
 
     let base = PathBuf::from("/etc/apt/sources.list");
@@ -165,276 +239,1425 @@ pub fn list_apt_repos() -> Result<serde_json::Value, String> {
             }
         }
     }
-
-    Ok(json!(all_repos))
+    all_repos
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn toggle_apt_repo(id: String, enabled: bool) -> Result<serde_json::Value, String> {
-    let parts: Vec<&str> = id.rsplitn(2, ':').collect();
-    if parts.len() != 2 {
-        return Err("Invalid repository ID".to_string());
+pub fn list_apt_repos(if_changed_since: Option<u64>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("repos")?;
+    let caps = super::capabilities::capabilities();
+    if !caps.readable("/etc/apt") {
+        return Err(caps.unsupported("APT repositories can't be listed: /etc/apt isn't there"));
     }
+    Ok(super::changes::respond(&super::changes::REPOS, json!(all_apt_repos()), if_changed_since))
+}
 
-    let line_number: usize = parts[0].parse().map_err(|_| "Invalid line number")?;
-    let file_path = parts[1];
+#[cfg(target_os = "linux")]
+fn find_existing_repo(new_content: &str, is_deb822: bool, existing: &[AptRepository]) -> Option<AptRepository> {
+    let new_targets: Vec<_> = parse_sources_content(new_content, "", is_deb822).iter().flat_map(repo_targets).collect();
+    existing
+        .iter()
+        .find(|repo| repo_targets(repo).iter().any(|t| new_targets.contains(t)))
+        .cloned()
+}
 
-    let path = PathBuf::from(file_path);
-    if !path.exists() {
-        return Err("Repository file not found".to_string());
+#[cfg(target_os = "linux")]
+fn duplicate_check(new_content: &str, is_deb822: bool, force: Option<bool>) -> Option<serde_json::Value> {
+    if force.unwrap_or(false) {
+        return None;
     }
+    find_existing_repo(new_content, is_deb822, &all_apt_repos()).map(|existing| {
+        json!({
+            "success": false,
+            "duplicate": true,
+            "existing_id": existing.id,
+            "existing_file": existing.file_path,
+            "existing_enabled": existing.enabled,
+        })
+    })
+}
 
-    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let lines: Vec<&str> = content.lines().collect();
-    let is_deb822 = path.extension().map_or(false, |ext| ext == "sources");
-
-    let new_content = if is_deb822 {
-        let mut result_lines: Vec<String> = Vec::new();
-        let mut in_target_stanza = false;
-        let mut found_enabled_field = false;
-        let mut stanza_start = 0;
-
-        for (idx, line) in lines.iter().enumerate() {
-            let line_trimmed = line.trim();
-
-            if line_trimmed.is_empty() {
-                if in_target_stanza && !found_enabled_field {
-                    result_lines.insert(
-                        stanza_start,
-                        format!("Enabled: {}", if enabled { "yes" } else { "no" }),
-                    );
-                }
-                in_target_stanza = false;
-                found_enabled_field = false;
-                result_lines.push(line.to_string());
-                continue;
-            }
-
-            if idx == line_number || (in_target_stanza && idx > line_number) {
-                in_target_stanza = true;
-                if idx == line_number {
-                    stanza_start = result_lines.len();
-                }
+#[cfg(target_os = "linux")]
+fn deb822_stanzas(lines: &[&str]) -> Vec<(usize, usize)> {
+    let mut stanzas = Vec::new();
+    let mut start: Option<usize> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        match (line.trim().is_empty(), start) {
+            (true, Some(s)) => {
+                stanzas.push((s, idx));
+                start = None;
             }
+            (false, None) => start = Some(idx),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        stanzas.push((s, lines.len()));
+    }
+    stanzas
+}
 
-            if in_target_stanza && line_trimmed.starts_with("Enabled:") {
-                found_enabled_field = true;
-                result_lines.push(format!("Enabled: {}", if enabled { "yes" } else { "no" }));
-            } else {
-                result_lines.push(line.to_string());
+#[cfg(target_os = "linux")]
+fn toggle_deb822_stanza(content: &str, line_number: usize, enabled: bool) -> Result<String, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, end) = deb822_stanzas(&lines)
+        .into_iter()
+        .find(|(start, end)| (*start..*end).contains(&line_number))
+        .ok_or("Repository stanza not found")?;
+
+    let enabled_line = format!("Enabled: {}", if enabled { "yes" } else { "no" });
+    let mut stanza: Vec<String> = Vec::new();
+    let mut replaced = false;
+    for line in &lines[start..end] {
+        if line.trim_start().starts_with("Enabled:") {
+            if !replaced {
+                stanza.push(enabled_line.clone());
+                replaced = true;
             }
+        } else {
+            stanza.push(line.to_string());
         }
+    }
+    if !replaced {
+        stanza.push(enabled_line);
+    }
 
-        if in_target_stanza && !found_enabled_field {
-            result_lines.insert(
-                stanza_start,
-                format!("Enabled: {}", if enabled { "yes" } else { "no" }),
-            );
-        }
+    let mut result: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    result.extend(stanza);
+    result.extend(lines[end..].iter().map(|l| l.to_string()));
 
-        result_lines.join("\n")
-    } else {
-        let mut result_lines: Vec<String> = Vec::new();
-
-        for (idx, line) in lines.iter().enumerate() {
-            if idx == line_number {
-                let line_trimmed = line.trim();
-                if enabled {
-                    if line_trimmed.starts_with('#') {
-                        let uncommented = line_trimmed.trim_start_matches('#').trim();
-                        result_lines.push(uncommented.to_string());
-                    } else {
-                        result_lines.push(line.to_string());
-                    }
-                } else if !line_trimmed.starts_with('#') {
-                    result_lines.push(format!("# {}", line_trimmed));
+    let mut new_content = result.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    Ok(new_content)
+}
+
+#[cfg(target_os = "linux")]
+fn toggle_one_line(content: &str, line_number: usize, enabled: bool) -> String {
+    let mut result_lines: Vec<String> = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if idx == line_number {
+            let line_trimmed = line.trim();
+            if enabled {
+                if line_trimmed.starts_with('#') {
+                    let uncommented = line_trimmed.trim_start_matches('#').trim();
+                    result_lines.push(uncommented.to_string());
                 } else {
                     result_lines.push(line.to_string());
                 }
+            } else if !line_trimmed.starts_with('#') {
+                result_lines.push(format!("# {}", line_trimmed));
             } else {
                 result_lines.push(line.to_string());
             }
+        } else {
+            result_lines.push(line.to_string());
         }
+    }
+
+    result_lines.join("\n")
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn toggle_apt_repo(
+    app: tauri::AppHandle,
+    id: String,
+    enabled: bool,
+    auto_refresh: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    let (file_path, line_number, fingerprint) = parse_repo_id(&id)?;
 
-        result_lines.join("\n")
+    let path = PathBuf::from(file_path);
+    if !path.exists() {
+        return Err(GantryError::NotFound("Repository file not found".into()));
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let is_deb822 = path.extension().map_or(false, |ext| ext == "sources");
+    let line_number = locate_repo(&content, file_path, is_deb822, line_number, fingerprint)?;
+
+    let new_content = if is_deb822 {
+        toggle_deb822_stanza(&content, line_number, enabled)?
+    } else {
+        toggle_one_line(&content, line_number, enabled)
     };
 
+    let backup = backup_sources_file(&path, &content, if enabled { "enable" } else { "disable" }, &id)?;
+
     let temp_file = std::env::temp_dir().join("apt_repo_temp");
     fs::write(&temp_file, &new_content).map_err(|e| e.to_string())?;
 
-    let output = Command::new("pkexec")
-        .args(["cp", &temp_file.to_string_lossy(), file_path])
-        .output()
-        .map_err(|e| e.to_string())?;
+    if auto_refresh.unwrap_or(false) {
+        let result = run_apt_update(&app, Some((&temp_file, &path)));
+        let _ = fs::remove_file(&temp_file);
+        let refresh = result?;
+        if fs::read_to_string(&path).ok().as_deref() != Some(new_content.as_str()) {
+            return Err(format!("Failed to update repository: {}", refresh["stderr"].as_str().unwrap_or("")).into());
+        }
+        return Ok(json!({"success": true, "backup": backup, "refresh": refresh, "repos": list_apt_repos(None).ok()}));
+    }
 
+    let result = super::admin::copy_file(&temp_file, file_path);
     let _ = fs::remove_file(&temp_file);
+    result.map_err(|e| e.context("Failed to update repository"))?;
 
-    if output.status.success() {
-        Ok(json!({"success": true}))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to update repository: {}", stderr))
+    Ok(json!({"success": true, "backup": backup, "repos": list_apt_repos(None).ok()}))
+}
+
+#[cfg(target_os = "linux")]
+fn is_option_token(token: &str) -> bool {
+    token.starts_with('[') || token.ends_with(']') || token.contains('=')
+}
+
+#[cfg(target_os = "linux")]
+fn insert_signed_by(line: &str, keyring: &str) -> Result<String, String> {
+    if !keyring.starts_with('/') || keyring.contains(char::is_whitespace) || keyring.contains(']') {
+        return Err(format!("Invalid keyring path: {}", keyring));
     }
+    if line.contains("signed-by=") {
+        return Err("Repository line already has a signed-by option".to_string());
+    }
+    let (kind, rest) = line.split_once(char::is_whitespace).ok_or("Invalid repository line")?;
+    let rest = rest.trim_start();
+    Ok(match rest.strip_prefix('[') {
+        Some(options) => format!("{} [signed-by={} {}", kind, keyring, options.trim_start()),
+        None => format!("{} [signed-by={}] {}", kind, keyring, rest),
+    })
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn add_apt_repo(repo_line: String) -> Result<serde_json::Value, String> {
-    let trimmed = repo_line.trim();
-    if !trimmed.starts_with("deb ") && !trimmed.starts_with("deb-src ") {
-        return Err("Repository line must start with 'deb' or 'deb-src'".to_string());
+pub fn add_apt_repo(
+    app: tauri::AppHandle,
+    repo_line: String,
+    auto_refresh: Option<bool>,
+    signed_by: Option<String>,
+    validate: Option<bool>,
+    force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    let mut line = repo_line.trim().to_string();
+    if line.starts_with("ppa:") {
+        return add_ppa(app, line, auto_refresh, force);
+    }
+    if ["Types:", "URIs:", "Suites:"].iter().any(|field| line.contains(field)) {
+        return Err("This looks like a deb822 stanza; use the structured form to add .sources repositories".into());
+    }
+    if !line.starts_with("deb ") && !line.starts_with("deb-src ") {
+        return Err("Repository line must start with 'deb' or 'deb-src'".into());
+    }
+    if let Some(keyring) = signed_by.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+        line = insert_signed_by(&line, keyring)?;
     }
+    let trimmed = line.as_str();
 
-    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+    let parts: Vec<&str> = trimmed.split_whitespace().filter(|p| !is_option_token(p)).collect();
     if parts.len() < 3 {
-        return Err("Invalid repository format. Expected: deb URI suite [components...]".to_string());
+        return Err("Invalid repository format. Expected: deb URI suite [components...]".into());
+    }
+    if let Some(duplicate) = duplicate_check(trimmed, false, force) {
+        return Ok(duplicate);
     }
 
-    let uri = parts[1];
-    let sanitized: String = uri
-        .replace("http://", "")
-        .replace("https://", "")
-        .replace('/', "-")
-        .replace('.', "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect();
-
-    let mut filename = format!("{}.list", sanitized);
-    let mut target = PathBuf::from("/etc/apt/sources.list.d").join(&filename);
+    let validation = if validate.unwrap_or(false) {
+        let report = validate_apt_repo(trimmed.to_string())?;
+        if report["valid"] != json!(true) {
+            return Ok(json!({"success": false, "validation": report}));
+        }
+        Some(report)
+    } else {
+        None
+    };
 
-    if target.exists() {
-        filename = format!("{}_{}.list", sanitized, chrono::Utc::now().timestamp_millis());
-        target = PathBuf::from("/etc/apt/sources.list.d").join(&filename);
+    let (filename, target) = new_sources_target(parts[1], "list");
+    let mut result = install_sources_file(&app, &format!("{}\n", trimmed), filename, &target, auto_refresh)?;
+    if let Some(report) = validation {
+        result["validation"] = report;
     }
+    Ok(result)
+}
 
-    let content = format!("{}\n", trimmed);
-    let temp_file = std::env::temp_dir().join("apt_repo_add_temp");
-    fs::write(&temp_file, &content).map_err(|e| e.to_string())?;
+#[cfg(target_os = "linux")]
+struct RepoSpec {
+    uris: Vec<String>,
+    suites: Vec<String>,
+    components: Vec<String>,
+}
 
-    let output = Command::new("pkexec")
-        .args(["cp", &temp_file.to_string_lossy(), &target.to_string_lossy()])
-        .output()
-        .map_err(|e| e.to_string())?;
+#[cfg(target_os = "linux")]
+fn parse_repo_spec(input: &str) -> Result<RepoSpec, String> {
+    let input = input.trim();
+    if input.starts_with("deb ") || input.starts_with("deb-src ") {
+        let parts: Vec<&str> = input.split_whitespace().filter(|p| !is_option_token(p)).collect();
+        if parts.len() < 3 {
+            return Err("Invalid repository format. Expected: deb URI suite [components...]".to_string());
+        }
+        return Ok(RepoSpec {
+            uris: vec![parts[1].to_string()],
+            suites: vec![parts[2].to_string()],
+            components: parts[3..].iter().map(|c| c.to_string()).collect(),
+        });
+    }
 
-    let _ = fs::remove_file(&temp_file);
+    let field = |name: &str| -> Vec<String> {
+        input
+            .lines()
+            .find_map(|l| l.trim().strip_prefix(name)?.strip_prefix(':'))
+            .map(|v| v.split_whitespace().map(|t| t.to_string()).collect())
+            .unwrap_or_default()
+    };
+    if field("Types").is_empty() {
+        return Err("Expected a 'deb ...' line or a deb822 stanza with Types, URIs and Suites".to_string());
+    }
+    Ok(RepoSpec { uris: field("URIs"), suites: field("Suites"), components: field("Components") })
+}
 
-    if output.status.success() {
-        Ok(json!({"success": true, "file": filename}))
+#[cfg(target_os = "linux")]
+fn release_dir(uri: &str, suite: &str) -> String {
+    let base = uri.trim_end_matches('/');
+    if suite.ends_with('/') {
+        match suite.trim_start_matches("./").trim_end_matches('/') {
+            "" => base.to_string(),
+            path => format!("{}/{}", base, path),
+        }
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to add repository: {}", stderr))
+        format!("{}/dists/{}", base, suite)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn probe_url(url: &str) -> Option<u16> {
+    let request = |extra: &[&str]| -> Option<u16> {
+        let output = Command::new("curl")
+            .args(["-sS", "-L", "-o", "/dev/null", "-w", "%{http_code}", "--max-time", "5"])
+            .args(extra)
+            .arg(url)
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok().filter(|code| *code != 0)
+    };
+    match request(&["-I"]) {
+        Some(405) | Some(501) => request(&["-r", "0-0"]),
+        status => status,
     }
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn delete_apt_repo(id: String) -> Result<serde_json::Value, String> {
-    let parts: Vec<&str> = id.rsplitn(2, ':').collect();
-    if parts.len() != 2 {
-        return Err("Invalid repository ID".to_string());
+pub fn validate_apt_repo(repo_line: String) -> Result<serde_json::Value, GantryError> {
+    let spec = match parse_repo_spec(&repo_line)
+        .and_then(|spec| check_repo_syntax(&spec.uris, &spec.suites, &spec.components).map(|_| spec))
+    {
+        Ok(spec) => spec,
+        Err(e) => return Ok(json!({"valid": false, "syntax_error": e, "checks": []})),
+    };
+
+    let mut checks = Vec::new();
+    for uri in &spec.uris {
+        for suite in &spec.suites {
+            let dir = release_dir(uri, suite);
+            let check = if let Some(path) = dir.strip_prefix("file://") {
+                let local = std::path::Path::new(path);
+                let in_release = local.join("InRelease").is_file();
+                let release = local.join("Release").is_file();
+                json!({
+                    "uri": uri,
+                    "suite": suite,
+                    "url": format!("{}/{}", dir, if in_release { "InRelease" } else { "Release" }),
+                    "reachable": local.is_dir(),
+                    "exists": in_release || release,
+                    "signed": in_release || local.join("Release.gpg").is_file(),
+                    "status": null,
+                })
+            } else if dir.starts_with("http://") || dir.starts_with("https://") {
+                let in_release_url = format!("{}/InRelease", dir);
+                let in_release = probe_url(&in_release_url);
+                let (url, status, signed) = if in_release == Some(200) {
+                    (in_release_url, in_release, true)
+                } else {
+                    let release_url = format!("{}/Release", dir);
+                    let release = probe_url(&release_url);
+                    let signed = release == Some(200) && probe_url(&format!("{}/Release.gpg", dir)) == Some(200);
+                    (release_url, release.or(in_release), signed)
+                };
+                json!({
+                    "uri": uri,
+                    "suite": suite,
+                    "url": url,
+                    "reachable": status.is_some(),
+                    "exists": status == Some(200),
+                    "signed": signed,
+                    "status": status,
+                })
+            } else {
+                // cdrom:, mirror+ and tor+ URIs are resolved by apt transports we can't probe.
+                json!({
+                    "uri": uri,
+                    "suite": suite,
+                    "url": null,
+                    "reachable": null,
+                    "exists": null,
+                    "signed": null,
+                    "status": null,
+                })
+            };
+            checks.push(check);
+        }
     }
 
-    let line_number: usize = parts[0].parse().map_err(|_| "Invalid line number".to_string())?;
-    let file_path = parts[1];
-    let path = PathBuf::from(file_path);
+    let valid = checks.iter().all(|c| c["exists"] != json!(false));
+    Ok(json!({"valid": valid, "syntax_error": null, "checks": checks}))
+}
 
-    if !path.exists() {
-        return Err("Repository file not found".to_string());
+#[cfg(target_os = "linux")]
+fn normalize_repo_uri(uri: &str) -> String {
+    let uri = uri.trim().trim_end_matches('/');
+    let (scheme, rest) = uri.split_once("://").unwrap_or(("", uri));
+    let scheme = match scheme.to_ascii_lowercase().as_str() {
+        "https" => "http".to_string(),
+        other => other.to_string(),
+    };
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = host.to_ascii_lowercase();
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        format!("{}://{}", scheme, host)
+    } else {
+        format!("{}://{}/{}", scheme, host, path)
     }
+}
 
-    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let lines: Vec<&str> = content.lines().collect();
-    let is_deb822 = path.extension().map_or(false, |ext| ext == "sources");
+#[cfg(target_os = "linux")]
+fn repo_targets(repo: &AptRepository) -> Vec<(String, String, String, String)> {
+    let components: Vec<&str> = if repo.components.trim().is_empty() {
+        vec![""]
+    } else {
+        repo.components.split_whitespace().collect()
+    };
+    let mut targets = Vec::new();
+    for kind in repo.types.split_whitespace() {
+        for uri in repo.uris.split_whitespace() {
+            for suite in repo.suites.split_whitespace() {
+                for component in &components {
+                    targets.push((
+                        kind.to_string(),
+                        normalize_repo_uri(uri),
+                        suite.trim_end_matches('/').to_lowercase(),
+                        component.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    targets
+}
 
-    let new_lines: Vec<&str> = if is_deb822 {
-        let mut result: Vec<&str> = Vec::new();
-        let mut skip = false;
-        for (idx, line) in lines.iter().enumerate() {
-            if idx == line_number {
-                skip = true;
-                continue;
+#[cfg(target_os = "linux")]
+fn find_duplicate_repos(repos: &[AptRepository]) -> std::collections::HashMap<String, Vec<String>> {
+    let mut owners: std::collections::HashMap<(String, String, String, String), Vec<&str>> = std::collections::HashMap::new();
+    for repo in repos.iter().filter(|r| r.enabled) {
+        for target in repo_targets(repo) {
+            let ids = owners.entry(target).or_default();
+            if !ids.contains(&repo.id.as_str()) {
+                ids.push(&repo.id);
             }
-            if skip {
-                if line.trim().is_empty() {
-                    skip = false;
-                    continue;
+        }
+    }
+
+    let mut duplicates: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    for ids in owners.values().filter(|ids| ids.len() > 1) {
+        for id in ids {
+            let others = duplicates.entry(id.to_string()).or_default();
+            for other in ids.iter().filter(|o| *o != id) {
+                if !others.iter().any(|o| o == other) {
+                    others.push(other.to_string());
                 }
-                continue;
             }
-            result.push(line);
         }
-        result
+    }
+    duplicates
+}
+
+#[cfg(target_os = "linux")]
+fn repo_has_signed_by(lines: &[&str], line_number: usize, is_deb822: bool) -> bool {
+    if is_deb822 {
+        deb822_stanzas(lines)
+            .into_iter()
+            .find(|(start, end)| (*start..*end).contains(&line_number))
+            .is_some_and(|(start, end)| lines[start..end].iter().any(|l| l.trim_start().starts_with("Signed-By:")))
     } else {
-        lines
-            .iter()
-            .enumerate()
-            .filter(|(idx, _)| *idx != line_number)
-            .map(|(_, line)| *line)
-            .collect()
-    };
+        lines.get(line_number).is_some_and(|l| l.contains("signed-by="))
+    }
+}
 
-    let new_content = new_lines.join("\n");
+#[cfg(target_os = "linux")]
+fn apt_lists_name(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let rest = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+    rest.replace('/', "_")
+}
 
-    if new_content.trim().is_empty() {
-        let output = Command::new("pkexec")
-            .args(["rm", file_path])
-            .output()
-            .map_err(|e| e.to_string())?;
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to delete repository file: {}", stderr));
-        }
+#[cfg(target_os = "linux")]
+fn parse_gpgv_validsig(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|l| l.strip_prefix("[GNUPG:] VALIDSIG "))
+        .and_then(|rest| rest.split_whitespace().last())
+        .map(|fpr| fpr.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn legacy_signing_key(uri: &str, suite: &str) -> Option<String> {
+    let lists = std::path::Path::new("/var/lib/apt/lists");
+    let base = apt_lists_name(&release_dir(uri, suite));
+    let in_release = lists.join(format!("{}_InRelease", base));
+    let mut command = Command::new("gpgv");
+    command.args(["--status-fd", "1", "--keyring", "/etc/apt/trusted.gpg"]);
+    if in_release.is_file() {
+        command.arg(&in_release);
     } else {
-        let temp_file = std::env::temp_dir().join("apt_repo_del_temp");
-        fs::write(&temp_file, format!("{}\n", new_content)).map_err(|e| e.to_string())?;
+        command.arg(lists.join(format!("{}_Release.gpg", base))).arg(lists.join(format!("{}_Release", base)));
+    }
+    let output = command.output().ok()?;
+    parse_gpgv_validsig(&String::from_utf8_lossy(&output.stdout))
+}
 
-        let output = Command::new("pkexec")
-            .args(["cp", &temp_file.to_string_lossy(), file_path])
-            .output()
-            .map_err(|e| e.to_string())?;
+#[cfg(target_os = "linux")]
+const HEALTH_CACHE_SECS: u64 = 300;
 
-        let _ = fs::remove_file(&temp_file);
+#[cfg(target_os = "linux")]
+type ProbeCache = std::sync::Mutex<std::collections::HashMap<String, (std::time::Instant, Option<u16>)>>;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to update repository file: {}", stderr));
+#[cfg(target_os = "linux")]
+static RELEASE_PROBES: std::sync::OnceLock<ProbeCache> = std::sync::OnceLock::new();
+
+#[cfg(target_os = "linux")]
+fn cached_probe(url: &str, force: bool) -> Option<u16> {
+    let cache = RELEASE_PROBES.get_or_init(Default::default);
+    if !force {
+        if let Some((at, status)) = cache.lock().ok()?.get(url) {
+            if at.elapsed().as_secs() < HEALTH_CACHE_SECS {
+                return *status;
+            }
         }
     }
-
-    Ok(json!({"success": true}))
+    let status = probe_url(url);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(url.to_string(), (std::time::Instant::now(), status));
+    }
+    status
 }
 
-#[cfg(target_os = "macos")]
-fn find_brew() -> Option<PathBuf> {
-    for path in &["/opt/homebrew/bin/brew", "/usr/local/bin/brew"] {
-        if std::path::Path::new(path).exists() {
-            return Some(PathBuf::from(path));
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn repo_health_check(force: Option<bool>) -> Result<serde_json::Value, GantryError> {
+    let force = force.unwrap_or(false);
+    let mut files = vec![PathBuf::from("/etc/apt/sources.list")];
+    if let Ok(dir) = fs::read_dir("/etc/apt/sources.list.d") {
+        files.extend(dir.flatten().map(|e| e.path()).filter(|p| {
+            let ext = p.extension().and_then(|e| e.to_str());
+            ext == Some("list") || ext == Some("sources")
+        }));
+    }
+    let legacy_keyring = std::path::Path::new("/etc/apt/trusted.gpg").is_file();
+
+    let mut all_repos: Vec<AptRepository> = Vec::new();
+    let mut findings = serde_json::Map::new();
+    for file in files.iter().filter(|f| f.is_file()) {
+        let Ok(content) = fs::read_to_string(file) else { continue };
+        let is_deb822 = file.extension().map_or(false, |ext| ext == "sources");
+        let lines: Vec<&str> = content.lines().collect();
+        let repos = parse_sources_content(&content, &file.to_string_lossy(), is_deb822);
+
+        for repo in repos.iter().filter(|r| r.enabled) {
+            let mut issues = Vec::new();
+            for uri in repo.uris.split_whitespace() {
+                for suite in repo.suites.split_whitespace() {
+                    let dir = release_dir(uri, suite);
+                    if dir.starts_with("http://") || dir.starts_with("https://") {
+                        let in_release = cached_probe(&format!("{}/InRelease", dir), force);
+                        if in_release == Some(404) && cached_probe(&format!("{}/Release", dir), force) == Some(404) {
+                            issues.push(json!({
+                                "kind": "release_missing",
+                                "message": format!("No Release file at {}", dir),
+                                "url": dir,
+                                "status": 404,
+                            }));
+                        }
+                    }
+                    if legacy_keyring && !repo_has_signed_by(&lines, repo.line_number, is_deb822) {
+                        let key = legacy_signing_key(uri, suite);
+                        if let Some(key) = key.filter(|k| !issues.iter().any(|i| i["key_fingerprint"] == json!(k))) {
+                            issues.push(json!({
+                                "kind": "legacy_keyring",
+                                "message": "Key is stored in legacy trusted.gpg keyring (/etc/apt/trusted.gpg)",
+                                "key_fingerprint": key,
+                            }));
+                        }
+                    }
+                }
+            }
+            if !issues.is_empty() {
+                findings.insert(repo.id.clone(), json!(issues));
+            }
+        }
+        all_repos.extend(repos);
+    }
+
+    for (id, others) in find_duplicate_repos(&all_repos) {
+        let entry = findings.entry(id).or_insert_with(|| json!([]));
+        if let Some(issues) = entry.as_array_mut() {
+            issues.push(json!({
+                "kind": "duplicate",
+                "message": "Configured multiple times",
+                "duplicate_of": others,
+            }));
         }
     }
-    None
+
+    Ok(json!({"findings": findings, "legacy_keyring": legacy_keyring}))
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(target_os = "linux")]
+fn parse_ppa_name(name: &str) -> Result<(String, String), String> {
+    let spec = name.trim().strip_prefix("ppa:").ok_or("PPA names must start with 'ppa:'")?;
+    let (user, project) = spec.split_once('/').unwrap_or((spec, "ppa"));
+    let valid = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+            && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '+'))
+    };
+    if !valid(user) || !valid(project) {
+        return Err(format!("Invalid PPA name: {}", name.trim()));
+    }
+    Ok((user.to_string(), project.to_string()))
+}
+
+#[cfg(target_os = "linux")]
+fn ubuntu_codename(os_release: &str) -> Option<String> {
+    let field = |key: &str| {
+        os_release
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let is_ubuntu = field("ID").as_deref() == Some("ubuntu")
+        || field("ID_LIKE").is_some_and(|like| like.split_whitespace().any(|id| id == "ubuntu"));
+    if !is_ubuntu {
+        return None;
+    }
+    field("UBUNTU_CODENAME").or_else(|| field("VERSION_CODENAME"))
+}
+
+#[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn list_apt_repos() -> Result<serde_json::Value, String> {
-    let brew = find_brew().ok_or_else(|| {
-        "Homebrew not found. Install it from https://brew.sh".to_string()
-    })?;
+pub fn add_ppa(
+    app: tauri::AppHandle,
+    name: String,
+    auto_refresh: Option<bool>,
+    force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    let (user, project) = parse_ppa_name(&name)?;
+    let os_release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let codename = ubuntu_codename(&os_release)
+        .ok_or("PPAs are only available on Ubuntu and Ubuntu-based systems")?;
+    let ppa_uri = format!("https://ppa.launchpadcontent.net/{}/{}/ubuntu", user, project);
+    if let Some(duplicate) = duplicate_check(&format!("deb {} {} main", ppa_uri, codename), false, force) {
+        return Ok(duplicate);
+    }
 
-    let output = Command::new(&brew)
-        .args(["tap-info", "--json=v2", "--installed"])
+    let api_url = format!("https://api.launchpad.net/1.0/~{}/+archive/ubuntu/{}", user, project);
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", "30", &api_url])
         .output()
-        .map_err(|e| format!("Failed to run brew: {}", e))?;
-
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("brew tap-info failed: {}", stderr));
+        return Err(GantryError::NotFound(format!("PPA ppa:{}/{} was not found on Launchpad", user, project)));
+    }
+    let archive: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected Launchpad response: {}", e))?;
+    let fingerprint = archive["signing_key_fingerprint"]
+        .as_str()
+        .filter(|fp| !fp.is_empty() && fp.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or("This PPA has no signing key yet; nothing has been published to it")?;
+
+    let keyring_name = format!("{}-ubuntu-{}", user, project);
+    let keyring = PathBuf::from(KEYRING_DIR).join(format!("{}.gpg", keyring_name));
+    let filename = format!("{}-{}.list", keyring_name, codename);
+    let target = PathBuf::from("/etc/apt/sources.list.d").join(&filename);
+    if target.exists() {
+        return Err(format!("ppa:{}/{} is already configured in {}", user, project, target.display()).into());
     }
 
-    let text = String::from_utf8_lossy(&output.stdout);
-    let parsed: serde_json::Value = serde_json::from_str(&text)
+    let installed_key = !keyring.exists();
+    if installed_key {
+        let key_url = format!(
+            "https://keyserver.ubuntu.com/pks/lookup?op=get&options=mr&search=0x{}",
+            fingerprint
+        );
+        add_apt_key(key_url, keyring_name.clone())?;
+    }
+
+    let line = format!("deb [signed-by={}] {} {} main\n", keyring.display(), ppa_uri, codename);
+    let installed = install_sources_file(&app, &line, filename, &target, auto_refresh);
+    if installed.is_err() && installed_key && !target.exists() {
+        // Nothing refers to the keyring without the sources file.
+        let _ = delete_apt_key(keyring.to_string_lossy().to_string());
+    }
+    let mut result = installed?;
+    result["fingerprint"] = json!(fingerprint);
+    result["keyring"] = json!(keyring.to_string_lossy());
+    Ok(result)
+}
+
+#[cfg(target_os = "linux")]
+fn new_sources_target(uri: &str, extension: &str) -> (String, PathBuf) {
+    let sanitized: String = uri
+        .replace("http://", "")
+        .replace("https://", "")
+        .replace('/', "-")
+        .replace('.', "-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect();
+
+    let mut filename = format!("{}.{}", sanitized, extension);
+    let mut target = PathBuf::from("/etc/apt/sources.list.d").join(&filename);
+
+    if target.exists() {
+        filename = format!("{}_{}.{}", sanitized, chrono::Utc::now().timestamp_millis(), extension);
+        target = PathBuf::from("/etc/apt/sources.list.d").join(&filename);
+    }
+
+    (filename, target)
+}
+
+#[cfg(target_os = "linux")]
+fn install_sources_file(
+    app: &tauri::AppHandle,
+    content: &str,
+    filename: String,
+    target: &std::path::Path,
+    auto_refresh: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    let temp_file = std::env::temp_dir().join("apt_repo_add_temp");
+    fs::write(&temp_file, content).map_err(|e| e.to_string())?;
+
+    if auto_refresh.unwrap_or(false) {
+        let result = run_apt_update(app, Some((&temp_file, target)));
+        let _ = fs::remove_file(&temp_file);
+        let refresh = result?;
+        if !target.exists() {
+            return Err(format!("Failed to add repository: {}", refresh["stderr"].as_str().unwrap_or("")));
+        }
+        return Ok(json!({"success": true, "file": filename, "refresh": refresh, "repos": list_apt_repos(None).ok()}));
+    }
+
+    let result = super::admin::copy_file(&temp_file, &target.to_string_lossy());
+    let _ = fs::remove_file(&temp_file);
+    result.map_err(|e| format!("Failed to add repository: {}", e))?;
+
+    Ok(json!({"success": true, "file": filename, "repos": list_apt_repos(None).ok()}))
+}
+
+#[cfg(target_os = "linux")]
+fn check_repo_syntax(uris: &[String], suites: &[String], components: &[String]) -> Result<(), String> {
+    if uris.is_empty() {
+        return Err("At least one URI is required".to_string());
+    }
+    for uri in uris {
+        let scheme = uri.split_once("://").map(|(s, _)| s).unwrap_or("");
+        let base_scheme = scheme.rsplit('+').next().unwrap_or("");
+        if !matches!(base_scheme, "http" | "https" | "file" | "cdrom" | "ftp" | "mirror" | "tor") {
+            return Err(format!("Unsupported URI: {}", uri));
+        }
+    }
+    if suites.is_empty() {
+        return Err("At least one suite is required".to_string());
+    }
+    let exact_path = suites.iter().any(|s| s.ends_with('/'));
+    if exact_path && !components.is_empty() {
+        return Err("Suites ending in '/' are exact paths and must not have components".to_string());
+    }
+    if !exact_path && components.is_empty() {
+        return Err("At least one component is required".to_string());
+    }
+    Ok(())
+}
+
+// Values must be single tokens, so nothing can pass for one-line syntax or inject extra fields.
+#[cfg(target_os = "linux")]
+fn build_deb822_stanza(
+    types: &[String],
+    uris: &[String],
+    suites: &[String],
+    components: &[String],
+    signed_by: Option<&str>,
+    enabled: bool,
+) -> Result<String, String> {
+    let all = types.iter().chain(uris).chain(suites).chain(components).map(|v| v.as_str()).chain(signed_by);
+    for value in all {
+        if value.is_empty() || value.contains(char::is_whitespace) || value.contains(['[', ']', '#']) {
+            return Err(format!(
+                "Invalid value '{}': deb822 fields take plain values, not one-line 'deb [...]' syntax",
+                value
+            ));
+        }
+    }
+
+    if types.is_empty() || types.iter().any(|t| t != "deb" && t != "deb-src") {
+        return Err("Types must be 'deb' and/or 'deb-src'".to_string());
+    }
+    check_repo_syntax(uris, suites, components)?;
+    if let Some(keyring) = signed_by {
+        if !keyring.starts_with('/') {
+            return Err(format!("Invalid keyring path: {}", keyring));
+        }
+    }
+
+    let mut stanza = format!("Types: {}\nURIs: {}\nSuites: {}\n", types.join(" "), uris.join(" "), suites.join(" "));
+    if !components.is_empty() {
+        stanza.push_str(&format!("Components: {}\n", components.join(" ")));
+    }
+    if let Some(keyring) = signed_by {
+        stanza.push_str(&format!("Signed-By: {}\n", keyring));
+    }
+    if !enabled {
+        stanza.push_str("Enabled: no\n");
+    }
+    Ok(stanza)
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn add_apt_repo_deb822(
+    app: tauri::AppHandle,
+    types: Vec<String>,
+    uris: Vec<String>,
+    suites: Vec<String>,
+    components: Vec<String>,
+    signed_by: Option<String>,
+    enabled: Option<bool>,
+    auto_refresh: Option<bool>,
+    force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    let trim_all = |v: Vec<String>| -> Vec<String> {
+        v.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+    };
+    let (types, uris, suites, components) = (trim_all(types), trim_all(uris), trim_all(suites), trim_all(components));
+    let signed_by = signed_by.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let stanza = build_deb822_stanza(&types, &uris, &suites, &components, signed_by.as_deref(), enabled.unwrap_or(true))?;
+    if let Some(duplicate) = duplicate_check(&stanza, true, force) {
+        return Ok(duplicate);
+    }
+    let (filename, target) = new_sources_target(&uris[0], "sources");
+    Ok(install_sources_file(&app, &stanza, filename, &target, auto_refresh)?)
+}
+
+#[cfg(target_os = "linux")]
+fn remove_repo(content: &str, is_deb822: bool, line_number: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut range = (line_number, line_number + 1);
+    if is_deb822 {
+        if let Some(stanza) = deb822_stanzas(&lines).into_iter().find(|(start, end)| (*start..*end).contains(&line_number)) {
+            range = stanza;
+        }
+        while range.1 < lines.len() && lines[range.1].trim().is_empty() {
+            range.1 += 1;
+        }
+    }
+    let (start, end) = (range.0.min(lines.len()), range.1.min(lines.len()));
+
+    lines[..start]
+        .iter()
+        .chain(lines[end..].iter())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn delete_apt_repo(id: String) -> Result<serde_json::Value, GantryError> {
+    let (file_path, line_number, fingerprint) = parse_repo_id(&id)?;
+    let path = PathBuf::from(file_path);
+
+    if !path.exists() {
+        return Err(GantryError::NotFound("Repository file not found".into()));
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let is_deb822 = path.extension().map_or(false, |ext| ext == "sources");
+    let line_number = locate_repo(&content, file_path, is_deb822, line_number, fingerprint)?;
+
+    let new_content = remove_repo(&content, is_deb822, line_number);
+    let backup = backup_sources_file(&path, &content, "delete", &id)?;
+
+    if new_content.trim().is_empty() {
+        super::admin::remove_file(file_path).map_err(|e| e.context("Failed to delete repository file"))?;
+    } else {
+        let temp_file = std::env::temp_dir().join("apt_repo_del_temp");
+        fs::write(&temp_file, format!("{}\n", new_content)).map_err(|e| e.to_string())?;
+
+        let result = super::admin::copy_file(&temp_file, file_path);
+        let _ = fs::remove_file(&temp_file);
+        result.map_err(|e| e.context("Failed to update repository file"))?;
+    }
+
+    Ok(json!({"success": true, "backup": backup, "repos": list_apt_repos(None).ok()}))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoBackup {
+    pub id: String,
+    pub timestamp: String,
+    pub action: String,
+    pub file_path: String,
+    pub repo_id: String,
+}
+
+fn backups_dir() -> PathBuf {
+    super::appdata::config_path("backups")
+}
+
+fn load_backup_journal() -> Vec<RepoBackup> {
+    fs::read_to_string(backups_dir().join("journal.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn save_backup_journal(entries: &[RepoBackup]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    fs::write(backups_dir().join("journal.json"), content).map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn prune_backups(entries: Vec<RepoBackup>, keep: usize) -> (Vec<RepoBackup>, Vec<RepoBackup>) {
+    let mut remaining: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for entry in &entries {
+        *remaining.entry(entry.file_path.clone()).or_default() += 1;
+    }
+
+    let (mut kept, mut dropped) = (Vec::new(), Vec::new());
+    for entry in entries {
+        let count = remaining.get_mut(&entry.file_path).unwrap();
+        if *count > keep {
+            *count -= 1;
+            dropped.push(entry);
+        } else {
+            kept.push(entry);
+        }
+    }
+    (kept, dropped)
+}
+
+#[cfg(target_os = "linux")]
+fn backup_sources_file(path: &std::path::Path, content: &str, action: &str, repo_id: &str) -> Result<String, String> {
+    let dir = backups_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let now = chrono::Local::now();
+    let filename = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let id = format!("{}_{}", now.format("%Y%m%d-%H%M%S%.3f"), filename);
+    fs::write(dir.join(&id), content).map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    let mut journal = load_backup_journal();
+    journal.push(RepoBackup {
+        id: id.clone(),
+        timestamp: now.to_rfc3339(),
+        action: action.to_string(),
+        file_path: path.to_string_lossy().to_string(),
+        repo_id: repo_id.to_string(),
+    });
+    let (kept, dropped) = prune_backups(journal, super::settings::repo_backup_keep());
+    for entry in dropped {
+        let _ = fs::remove_file(dir.join(&entry.id));
+    }
+    save_backup_journal(&kept)?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn list_repo_backups() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("repos")?;
+    let dir = backups_dir();
+    let backups: Vec<serde_json::Value> = load_backup_journal()
+        .into_iter()
+        .rev()
+        .map(|entry| {
+            let available = dir.join(&entry.id).is_file();
+            let mut value = json!(entry);
+            value["available"] = json!(available);
+            value
+        })
+        .collect();
+    Ok(json!(backups))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn restore_repo_backup(id: String) -> Result<serde_json::Value, GantryError> {
+    let entry = load_backup_journal()
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("Backup {} not found", id))?;
+    let backup_content = fs::read_to_string(backups_dir().join(&entry.id)).map_err(|e| format!("Failed to read backup: {}", e))?;
+
+    let target = PathBuf::from(&entry.file_path);
+    let in_apt_dir = target == std::path::Path::new("/etc/apt/sources.list")
+        || target.parent() == Some(std::path::Path::new("/etc/apt/sources.list.d"));
+    if !in_apt_dir {
+        return Err(format!("Refusing to restore outside /etc/apt: {}", entry.file_path).into());
+    }
+
+    let current = fs::read_to_string(&target).unwrap_or_default();
+    let undo = backup_sources_file(&target, &current, "restore", &entry.repo_id)?;
+
+    let staged = super::util::private_temp("repo-restore", backup_content.as_bytes())?;
+    super::admin::copy_file(staged.path(), &entry.file_path).map_err(|e| e.context("Failed to restore repository file"))?;
+
+    Ok(json!({"success": true, "file": entry.file_path, "backup": undo, "repos": list_apt_repos(None).ok()}))
+}
+
+pub(crate) fn run_streaming(
+    app: &tauri::AppHandle,
+    mut cmd: Command,
+    event: &str,
+) -> Result<(bool, String, String), String> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {:?}: {}", cmd.get_program(), e))?;
+
+    let stderr_pipe = child.stderr.take();
+    let stderr_app = app.clone();
+    let stderr_event = event.to_string();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut collected = String::new();
+        if let Some(pipe) = stderr_pipe {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                let _ = stderr_app.emit(&stderr_event, json!({"line": line, "stream": "stderr"}));
+                collected.push_str(&line);
+                collected.push('\n');
+            }
+        }
+        collected
+    });
+
+    let mut stdout = String::new();
+    if let Some(pipe) = child.stdout.take() {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            let _ = app.emit(event, json!({"line": line, "stream": "stdout"}));
+            stdout.push_str(&line);
+            stdout.push('\n');
+        }
+    }
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    Ok((status.success(), stdout, stderr))
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+struct AptSourceStatus {
+    uri: String,
+    suite: String,
+    status: String,
+    reason: Option<String>,
+    missing_key: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn extract_missing_key(text: &str) -> Option<String> {
+    let after = text.split("NO_PUBKEY").nth(1)?;
+    after.split_whitespace().next().map(|k| k.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn parse_apt_update(stdout: &str, stderr: &str) -> Vec<AptSourceStatus> {
+    let mut sources: Vec<AptSourceStatus> = Vec::new();
+    let mut last_err: Option<usize> = None;
+
+    for line in stdout.lines() {
+        if line.starts_with(' ') {
+            if let Some(idx) = last_err {
+                let reason = line.trim();
+                let source = &mut sources[idx];
+                if source.reason.is_none() && !reason.is_empty() {
+                    source.missing_key = extract_missing_key(reason);
+                    source.status = if source.missing_key.is_some() {
+                        "no_pubkey".to_string()
+                    } else if reason.contains("404") {
+                        "not_found".to_string()
+                    } else {
+                        "error".to_string()
+                    };
+                    source.reason = Some(reason.to_string());
+                }
+            }
+            continue;
+        }
+        last_err = None;
+
+        let Some((tag, rest)) = line.split_once(':') else { continue };
+        let status = match tag {
+            "Hit" | "Get" => "ok",
+            "Ign" => "ignored",
+            "Err" => "error",
+            _ => continue,
+        };
+        let mut parts = rest.split_whitespace().skip(1);
+        let (Some(uri), Some(suite)) = (parts.next(), parts.next()) else { continue };
+
+        let idx = match sources.iter().position(|s| s.uri == uri && s.suite == suite) {
+            Some(idx) => idx,
+            None => {
+                sources.push(AptSourceStatus {
+                    uri: uri.to_string(),
+                    suite: suite.to_string(),
+                    status: "ok".to_string(),
+                    reason: None,
+                    missing_key: None,
+                });
+                sources.len() - 1
+            }
+        };
+        let source = &mut sources[idx];
+        if status == "error" {
+            source.status = "error".to_string();
+            last_err = Some(idx);
+        } else if status == "ignored" && source.status == "ok" {
+            source.status = "ignored".to_string();
+        }
+    }
+
+    for line in stderr.lines() {
+        let Some(rest) = line.strip_prefix("W: GPG error: ") else { continue };
+        let Some(key) = extract_missing_key(rest) else { continue };
+        let mut parts = rest.split_whitespace();
+        let (Some(uri), Some(suite)) = (parts.next(), parts.next()) else { continue };
+        if let Some(source) = sources.iter_mut().find(|s| s.uri == uri && s.suite == suite) {
+            source.status = "no_pubkey".to_string();
+            source.missing_key = Some(key);
+            source.reason = Some(rest.to_string());
+        }
+    }
+
+    sources
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn apt_result(success: bool, stderr: &str) -> Result<(), GantryError> {
+    if success {
+        Ok(())
+    } else {
+        Err(GantryError::classify(stderr.trim().to_string()))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn run_apt_update(
+    app: &tauri::AppHandle,
+    copy: Option<(&std::path::Path, &std::path::Path)>,
+) -> Result<serde_json::Value, String> {
+    // apt's progress streams to the UI while it runs, which the admin helper's single reply can't
+    // carry, so apt runs stay on pkexec and are only audited.
+    let mut cmd = Command::new("pkexec");
+    match copy {
+        Some((from, to)) => {
+            cmd.args(["sh", "-c", "cp \"$1\" \"$2\" && LC_ALL=C apt-get update", "sh"])
+                .arg(from)
+                .arg(to);
+        }
+        None => {
+            cmd.args(["env", "LC_ALL=C", "apt-get", "update"]);
+        }
+    }
+
+    let (success, stdout, stderr) = run_streaming(app, cmd, "package-index-progress")?;
+    let target = copy.map(|(_, to)| to.to_string_lossy().to_string()).unwrap_or_default();
+    super::audit::record("apt_update", &target, "pkexec", &apt_result(success, &stderr));
+    let sources = parse_apt_update(&stdout, &stderr);
+    let messages: Vec<&str> = stderr
+        .lines()
+        .filter(|l| l.starts_with("E: ") || l.starts_with("W: "))
+        .collect();
+
+    Ok(json!({
+        "success": success && !sources.iter().any(|s| s.status != "ok" && s.status != "ignored"),
+        "sources": sources,
+        "messages": messages,
+        "stderr": stderr,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn refresh_package_index(app: tauri::AppHandle) -> Result<serde_json::Value, GantryError> {
+    Ok(run_apt_update(&app, None)?)
+}
+
+#[cfg(target_os = "linux")]
+const KEYRING_DIR: &str = "/etc/apt/keyrings";
+
+#[cfg(target_os = "linux")]
+fn parse_gpg_colons(output: &str) -> Vec<serde_json::Value> {
+    let now = chrono::Utc::now().timestamp();
+    let mut keys: Vec<serde_json::Value> = Vec::new();
+    let mut in_primary = false;
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields.first().copied() {
+            Some("pub") => {
+                let created = fields.get(5).and_then(|v| v.parse::<i64>().ok());
+                let expires = fields.get(6).and_then(|v| v.parse::<i64>().ok());
+                keys.push(json!({
+                    "key_id": fields.get(4).unwrap_or(&""),
+                    "fingerprint": null,
+                    "uids": [],
+                    "created": created,
+                    "expires": expires,
+                    "expired": fields.get(1) == Some(&"e") || expires.is_some_and(|e| e < now),
+                    "revoked": fields.get(1) == Some(&"r"),
+                }));
+                in_primary = true;
+            }
+            Some("sub") => in_primary = false,
+            Some("fpr") if in_primary => {
+                if let Some(key) = keys.last_mut() {
+                    if key["fingerprint"].is_null() {
+                        key["fingerprint"] = json!(fields.get(9).unwrap_or(&""));
+                    }
+                }
+            }
+            Some("uid") => {
+                if let Some(uids) = keys.last_mut().and_then(|k| k["uids"].as_array_mut()) {
+                    uids.push(json!(fields.get(9).unwrap_or(&"")));
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+#[cfg(target_os = "linux")]
+fn show_keys(path: &std::path::Path) -> Result<Vec<serde_json::Value>, String> {
+    let output = Command::new("gpg")
+        .args(["--show-keys", "--with-colons", "--with-fingerprint"])
+        .arg(path)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| format!("Failed to run gpg: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gpg could not read {}: {}", path.display(), stderr.trim()));
+    }
+    Ok(parse_gpg_colons(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "linux")]
+fn signed_by_references() -> std::collections::HashMap<String, Vec<String>> {
+    let mut refs: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    let mut files = vec![PathBuf::from("/etc/apt/sources.list")];
+    if let Ok(dir) = fs::read_dir("/etc/apt/sources.list.d") {
+        files.extend(dir.flatten().map(|e| e.path()));
+    }
+
+    for file in files {
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        for line in content.lines() {
+            let line = line.trim();
+            let path = if let Some(value) = line.strip_prefix("Signed-By:") {
+                Some(value.trim())
+            } else {
+                line.split("signed-by=")
+                    .nth(1)
+                    .and_then(|v| v.split(|c: char| c.is_whitespace() || c == ']').next())
+            };
+            if let Some(path) = path.filter(|p| p.starts_with('/')) {
+                let entry = refs.entry(path.to_string()).or_default();
+                let file = file.to_string_lossy().to_string();
+                if !entry.contains(&file) {
+                    entry.push(file);
+                }
+            }
+        }
+    }
+    refs
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_apt_keys() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("repos")?;
+    let refs = signed_by_references();
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for dir in ["/etc/apt/trusted.gpg.d", KEYRING_DIR] {
+        if let Ok(entries) = fs::read_dir(dir) {
+            paths.extend(entries.flatten().map(|e| e.path()).filter(|p| p.is_file()));
+        }
+    }
+    let legacy = PathBuf::from("/etc/apt/trusted.gpg");
+    if legacy.is_file() {
+        paths.push(legacy);
+    }
+    for path in refs.keys() {
+        let path = PathBuf::from(path);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let keyrings: Vec<serde_json::Value> = paths
+        .into_iter()
+        .map(|path| {
+            let path_str = path.to_string_lossy().to_string();
+            let (keys, error) = if path.exists() {
+                match show_keys(&path) {
+                    Ok(keys) => (keys, None),
+                    Err(e) => (vec![], Some(e)),
+                }
+            } else {
+                (vec![], Some("Keyring file is missing".to_string()))
+            };
+            json!({
+                "path": path_str,
+                "deletable": path_str.starts_with(KEYRING_DIR) || path_str.starts_with("/etc/apt/trusted.gpg.d/"),
+                "referenced_by": refs.get(&path_str).cloned().unwrap_or_default(),
+                "keys": keys,
+                "error": error,
+            })
+        })
+        .collect();
+
+    Ok(json!(keyrings))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn keyring_path(name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+        return Err("Keyring name may only contain letters, digits, '-', '_' and '.'".to_string());
+    }
+    Ok(PathBuf::from(KEYRING_DIR).join(format!("{}.gpg", name)))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn add_apt_key(source: String, keyring_name: String) -> Result<serde_json::Value, GantryError> {
+    let name = keyring_name.trim().trim_end_matches(".gpg").trim_end_matches(".asc");
+    let target = keyring_path(name)?;
+    if target.exists() {
+        return Err(format!("Keyring {} already exists", target.display()).into());
+    }
+
+    let source = source.trim();
+    let key_data: Vec<u8> = if source.starts_with("https://") {
+        let output = Command::new("curl")
+            .args(["-fsSL", "--proto", "=https", "--proto-redir", "=https", "--max-time", "30", source])
+            .output()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(GantryError::from_output("curl", &output).context("Failed to download key"));
+        }
+        output.stdout
+    } else if source.starts_with("http://") {
+        return Err("Key URLs must use https".into());
+    } else if source.contains("-----BEGIN PGP PUBLIC KEY BLOCK-----") {
+        source.as_bytes().to_vec()
+    } else {
+        return Err("Provide a key URL or an ASCII-armored public key".into());
+    };
+
+    // Keyrings referenced by signed-by must be binary unless named .asc.
+    let key_data = if key_data.starts_with(b"-----BEGIN") {
+        let armored = super::util::private_temp("key", &key_data)?;
+        let output = Command::new("gpg")
+            .args(["--dearmor", "--output", "-"])
+            .arg(armored.path())
+            .output()
+            .map_err(|e| format!("Failed to run gpg: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("gpg --dearmor failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+        }
+        output.stdout
+    } else {
+        key_data
+    };
+
+    let staged = super::util::private_temp("keyring", &key_data)?;
+    let keys = show_keys(staged.path())?;
+    if keys.is_empty() {
+        return Err("No public keys found in the provided data".into());
+    }
+
+    super::admin::install_keyring(name, staged.path()).map_err(|e| e.context("Failed to install keyring"))?;
+    Ok(json!({"success": true, "path": target.to_string_lossy(), "keys": keys}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn delete_apt_key(path: String) -> Result<serde_json::Value, GantryError> {
+    let canonical = fs::canonicalize(&path).map_err(|_| "Keyring file not found".to_string())?;
+    let allowed = [KEYRING_DIR, "/etc/apt/trusted.gpg.d"]
+        .iter()
+        .any(|dir| canonical.parent() == Some(std::path::Path::new(dir)));
+    if !allowed || !canonical.is_file() {
+        return Err("Only keyrings in /etc/apt/keyrings or /etc/apt/trusted.gpg.d can be deleted".into());
+    }
+
+    super::admin::remove_file(&canonical.to_string_lossy()).map_err(|e| e.context("Failed to delete keyring"))?;
+    Ok(json!({"success": true}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_apt_keys() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("repos")?;
+    Ok(json!([]))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn add_apt_key(_source: String, _keyring_name: String) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("Homebrew taps do not use signing keys".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn delete_apt_key(_path: String) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("Homebrew taps do not use signing keys".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_apt_repos(if_changed_since: Option<u64>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("repos")?;
+    let brew = super::util::find_brew().ok_or_else(|| GantryError::ToolMissing { tool: "brew".to_string() })?;
+
+    let output = Command::new(&brew)
+        .args(["tap-info", "--json=v2", "--installed"])
+        .output()
+        .map_err(|e| format!("Failed to run brew: {}", e))?;
+
+    if !output.status.success() {
+        return Err(GantryError::from_output("brew", &output).context("brew tap-info failed"));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&text)
         .map_err(|e| format!("Failed to parse brew output: {}", e))?;
 
     let taps = parsed["taps"].as_array().map(|arr| {
@@ -451,118 +1674,669 @@ pub fn list_apt_repos() -> Result<serde_json::Value, String> {
                 .map(|a| a.len())
                 .unwrap_or(0);
 
-            json!({
-                "id": name,
-                "file_path": tap_path,
-                "line_number": 0,
-                "types": "tap",
-                "uris": remote,
-                "suites": formula_count.to_string(),
-                "components": cask_count.to_string(),
-                "enabled": true,
-                "original_line": name,
-            })
-        }).collect::<Vec<_>>()
-    }).unwrap_or_default();
+            json!({
+                "id": name,
+                "file_path": tap_path,
+                "line_number": 0,
+                "types": "tap",
+                "uris": remote,
+                "suites": formula_count.to_string(),
+                "components": cask_count.to_string(),
+                "enabled": true,
+                "original_line": name,
+            })
+        }).collect::<Vec<_>>()
+    }).unwrap_or_default();
+
+    Ok(super::changes::respond(&super::changes::REPOS, json!(taps), if_changed_since))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn toggle_apt_repo(
+    _app: tauri::AppHandle,
+    _id: String,
+    _enabled: bool,
+    _auto_refresh: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("Homebrew taps cannot be toggled. Use Remove to delete a tap.".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn refresh_package_index(app: tauri::AppHandle) -> Result<serde_json::Value, GantryError> {
+    let brew = super::util::find_brew().ok_or_else(|| GantryError::ToolMissing { tool: "brew".to_string() })?;
+    let mut cmd = Command::new(&brew);
+    cmd.arg("update");
+    let (success, _stdout, stderr) = run_streaming(&app, cmd, "package-index-progress")?;
+    let messages: Vec<&str> = stderr.lines().filter(|l| l.starts_with("Error:") || l.starts_with("Warning:")).collect();
+    Ok(json!({"success": success, "sources": [], "messages": messages, "stderr": stderr}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn add_apt_repo(
+    _app: tauri::AppHandle,
+    repo_line: String,
+    _auto_refresh: Option<bool>,
+    _signed_by: Option<String>,
+    _validate: Option<bool>,
+    _force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    let tap_name = repo_line.trim();
+    if tap_name.is_empty() {
+        return Err("Tap name cannot be empty".into());
+    }
+
+    let brew = super::util::find_brew().ok_or_else(|| GantryError::ToolMissing { tool: "brew".to_string() })?;
+
+    let output = Command::new(&brew)
+        .args(["tap", tap_name])
+        .output()
+        .map_err(|e| format!("Failed to run brew tap: {}", e))?;
+
+    if output.status.success() {
+        Ok(json!({"success": true}))
+    } else {
+        Err(GantryError::from_output("brew", &output).context("brew tap failed"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn restore_repo_backup(_id: String) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("Repository backups are only supported on Debian-based systems".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn validate_apt_repo(_repo_line: String) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("Repository validation is only supported on Debian-based systems".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn repo_health_check(_force: Option<bool>) -> Result<serde_json::Value, GantryError> {
+    Ok(json!({"findings": {}, "legacy_keyring": false}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn add_ppa(
+    _app: tauri::AppHandle,
+    _name: String,
+    _auto_refresh: Option<bool>,
+    _force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("PPAs are only available on Ubuntu and Ubuntu-based systems".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn add_apt_repo_deb822(
+    _app: tauri::AppHandle,
+    _types: Vec<String>,
+    _uris: Vec<String>,
+    _suites: Vec<String>,
+    _components: Vec<String>,
+    _signed_by: Option<String>,
+    _enabled: Option<bool>,
+    _auto_refresh: Option<bool>,
+    _force: Option<bool>,
+) -> Result<serde_json::Value, GantryError> {
+    Err(GantryError::Unsupported("deb822 repositories are only supported on Debian-based systems".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn delete_apt_repo(id: String) -> Result<serde_json::Value, GantryError> {
+    let brew = super::util::find_brew().ok_or_else(|| GantryError::ToolMissing { tool: "brew".to_string() })?;
+
+    let output = Command::new(&brew)
+        .args(["untap", &id])
+        .output()
+        .map_err(|e| format!("Failed to run brew untap: {}", e))?;
+
+    if output.status.success() {
+        Ok(json!({"success": true}))
+    } else {
+        Err(GantryError::from_output("brew", &output).context("brew untap failed"))
+    }
+}
+
+// Exec values are unescaped first (`\s`, `\\`), then split on spaces, with double quotes
+// grouping arguments.
+fn parse_exec(value: &str) -> Result<Vec<String>, String> {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    let mut chars = unescaped.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_arg = true;
+            }
+            '\\' => {
+                let next = chars.next().ok_or("Exec ends with a backslash")?;
+                if !in_quotes || matches!(next, '"' | '`' | '$' | '\\') {
+                    current.push(next);
+                } else {
+                    current.push('\\');
+                    current.push(next);
+                }
+                in_arg = true;
+            }
+            ' ' | '\t' | '\n' if !in_quotes => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            _ => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_quotes {
+        return Err("Exec has an unterminated quote".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+    if args.is_empty() {
+        return Err("Exec cannot be empty".to_string());
+    }
+    Ok(args)
+}
+
+fn serialize_exec(args: &[String]) -> String {
+    const RESERVED: &[char] = &[
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`',
+    ];
+    args.iter()
+        .map(|arg| {
+            let is_field_code = arg.len() == 2 && arg.starts_with('%');
+            let quoted = if !is_field_code && (arg.is_empty() || arg.contains(RESERVED)) {
+                let mut q = String::from('"');
+                for c in arg.chars() {
+                    if matches!(c, '"' | '`' | '$' | '\\') {
+                        q.push('\\');
+                    }
+                    q.push(c);
+                }
+                q.push('"');
+                q
+            } else {
+                arg.clone()
+            };
+            quoted.replace('\\', "\\\\").replace('\n', "\\n").replace('\t', "\\t")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn startup_argv(exec: &str, argv: Option<Vec<String>>) -> Result<Vec<String>, String> {
+    if let Some(args) = argv.filter(|a| !a.is_empty()) {
+        return Ok(args);
+    }
+    let exec = exec.trim();
+    if exec.contains(char::is_whitespace) && std::path::Path::new(exec).is_file() {
+        return Ok(vec![exec.to_string()]);
+    }
+    parse_exec(exec)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_desktop_entry(content: &str) -> std::collections::HashMap<String, String> {
+    let mut fields = std::collections::HashMap::new();
+    let mut in_main_group = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_main_group = trimmed == "[Desktop Entry]";
+        } else if in_main_group && !trimmed.starts_with('#') {
+            if let Some((key, value)) = trimmed.split_once('=') {
+                fields.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(target_os = "linux")]
+fn update_desktop_entry(content: &str, updates: &[(&str, Option<String>)]) -> String {
+    let mut result: Vec<String> = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut in_main_group = false;
+    let mut insert_at: Option<usize> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_main_group = trimmed == "[Desktop Entry]";
+            result.push(line.to_string());
+            if in_main_group {
+                insert_at = Some(result.len());
+            }
+            continue;
+        }
+
+        if in_main_group {
+            let key = trimmed.split_once('=').map(|(k, _)| k.trim());
+            if let Some((name, value)) = updates.iter().find(|(name, _)| Some(*name) == key) {
+                seen.push(name);
+                match value {
+                    Some(v) if v.is_empty() => continue,
+                    Some(v) => result.push(format!("{}={}", name, v)),
+                    None => result.push(line.to_string()),
+                }
+            } else {
+                result.push(line.to_string());
+            }
+            if !trimmed.is_empty() {
+                insert_at = Some(result.len());
+            }
+        } else {
+            result.push(line.to_string());
+        }
+    }
+
+    let missing: Vec<String> = updates
+        .iter()
+        .filter(|(name, value)| !seen.contains(name) && value.as_deref().is_some_and(|v| !v.is_empty()))
+        .map(|(name, value)| format!("{}={}", name, value.as_deref().unwrap_or_default()))
+        .collect();
+    let at = insert_at.unwrap_or(result.len());
+    result.splice(at..at, missing);
+
+    format!("{}\n", result.join("\n"))
+}
+
+#[cfg(target_os = "linux")]
+fn startup_app_updates(
+    name: &str,
+    exec: &str,
+    delay_seconds: Option<u32>,
+    only_show_in: Option<Vec<String>>,
+    comment: Option<String>,
+    terminal: Option<bool>,
+) -> Result<Vec<(&'static str, Option<String>)>, String> {
+    let only_show_in = only_show_in.map(|desktops| {
+        let desktops: Vec<String> = desktops
+            .iter()
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty())
+            .collect();
+        if desktops.is_empty() {
+            String::new()
+        } else {
+            format!("{};", desktops.join(";"))
+        }
+    });
+
+    let updates = vec![
+        ("Name", Some(name.to_string())),
+        ("Exec", Some(exec.to_string())),
+        ("Comment", comment.map(|c| c.trim().to_string())),
+        ("Terminal", terminal.map(|t| t.to_string())),
+        ("OnlyShowIn", only_show_in),
+        ("X-GNOME-Autostart-Delay", delay_seconds.map(|d| if d == 0 { String::new() } else { d.to_string() })),
+    ];
+    if updates.iter().any(|(_, v)| v.as_deref().is_some_and(|v| v.contains(['\n', '\r']))) {
+        return Err("Startup entry values cannot contain line breaks".to_string());
+    }
+    Ok(updates)
+}
+
+#[cfg(target_os = "linux")]
+const SYSTEM_AUTOSTART_DIR: &str = "/etc/xdg/autostart";
+
+#[cfg(target_os = "linux")]
+fn user_autostart_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".config").join("autostart"))
+        .ok_or_else(|| "Cannot determine home directory".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn resolve_autostart_file(file: &str) -> Result<(PathBuf, String), String> {
+    if file.is_empty() || file.contains('/') || file.starts_with('.') {
+        return Err("Invalid desktop file name".to_string());
+    }
+    let user_path = user_autostart_dir()?.join(file);
+    let content = fs::read_to_string(&user_path)
+        .or_else(|_| fs::read_to_string(PathBuf::from(SYSTEM_AUTOSTART_DIR).join(file)))
+        .map_err(|_| "Desktop file not found".to_string())?;
+    Ok((user_path, content))
+}
+
+#[cfg(target_os = "linux")]
+fn write_user_autostart(path: &std::path::Path, content: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn startup_entry_json(path: &std::path::Path, content: &str, scope: &str) -> serde_json::Value {
+    let fields = parse_desktop_entry(content);
+    let is_true = |key: &str| fields.get(key).is_some_and(|v| v.eq_ignore_ascii_case("true"));
+    let only_show_in: Vec<&str> = fields
+        .get("OnlyShowIn")
+        .map(|v| v.split(';').filter(|d| !d.is_empty()).collect())
+        .unwrap_or_default();
+    json!({
+        "file": path.file_name().map(|n| n.to_string_lossy().to_string()),
+        "name": fields.get("Name"),
+        "exec": fields.get("Exec"),
+        "argv": fields.get("Exec").and_then(|e| parse_exec(e).ok()),
+        "enabled": !is_true("Hidden"),
+        "comment": fields.get("Comment"),
+        "delay_seconds": fields.get("X-GNOME-Autostart-Delay").and_then(|d| d.parse::<u32>().ok()),
+        "only_show_in": only_show_in,
+        "terminal": is_true("Terminal"),
+        "scope": scope,
+        "source": "autostart",
+        "file_path": path.to_string_lossy().to_string()
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_default_target_services(output: &str) -> Vec<String> {
+    let mut units: Vec<String> = output
+        .lines()
+        .skip(1)
+        .map(|l| l.trim().to_string())
+        .filter(|u| u.ends_with(".service"))
+        .collect();
+    units.sort();
+    units.dedup();
+    units
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+struct UserUnit {
+    description: Option<String>,
+    exec_start: Option<String>,
+    wanted_by: Vec<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_systemctl_cat(output: &str) -> std::collections::HashMap<String, UserUnit> {
+    let mut units: std::collections::HashMap<String, UserUnit> = std::collections::HashMap::new();
+    let mut current: Option<String> = None;
+    let mut section = String::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(path) = trimmed.strip_prefix("# /") {
+            let path = std::path::Path::new(path);
+            let file = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+            current = if file.ends_with(".service") {
+                Some(file)
+            } else {
+                path.parent()
+                    .and_then(|p| p.file_name())
+                    .map(|d| d.to_string_lossy().trim_end_matches(".d").to_string())
+            };
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            section = trimmed.to_string();
+            continue;
+        }
+        let (Some(unit), Some((key, value))) = (current.as_ref(), trimmed.split_once('=')) else { continue };
+        let entry = units.entry(unit.clone()).or_default();
+        let value = value.trim();
+        match (section.as_str(), key.trim()) {
+            ("[Unit]", "Description") => entry.description = Some(value.to_string()),
+            ("[Service]", "ExecStart") if !value.is_empty() => entry.exec_start = Some(value.to_string()),
+            ("[Install]", "WantedBy") => entry.wanted_by.extend(value.split_whitespace().map(|t| t.to_string())),
+            _ => {}
+        }
+    }
+    units
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_user_startup_entries() -> Vec<serde_json::Value> {
+    let systemctl = |args: &[&str]| {
+        Command::new("systemctl")
+            .arg("--user")
+            .args(args)
+            .output()
+            .ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+
+    let Some(deps) = systemctl(&["list-dependencies", "default.target", "--plain", "--no-pager"]) else {
+        return Vec::new();
+    };
+    let enabled = parse_default_target_services(&deps);
+
+    let disabled: Vec<String> = systemctl(&["list-unit-files", "--type=service", "--state=disabled", "--no-legend", "--no-pager"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.split_whitespace().next())
+        .filter(|u| u.ends_with(".service") && !u.contains("@.") && !enabled.iter().any(|e| e == u))
+        .map(|u| u.to_string())
+        .collect();
 
-    Ok(json!(taps))
-}
+    let mut cat_args = vec!["cat", "--no-pager"];
+    cat_args.extend(enabled.iter().chain(&disabled).map(|u| u.as_str()));
+    let units = if cat_args.len() > 2 {
+        parse_systemctl_cat(&systemctl(&cat_args).unwrap_or_default())
+    } else {
+        Default::default()
+    };
 
-#[cfg(target_os = "macos")]
-#[tauri::command]
-pub fn toggle_apt_repo(_id: String, _enabled: bool) -> Result<serde_json::Value, String> {
-    Err("Homebrew taps cannot be toggled. Use Remove to delete a tap.".to_string())
+    let entry = |unit: &String, is_enabled: bool| {
+        let info = units.get(unit);
+        json!({
+            "file": unit,
+            "name": info.and_then(|i| i.description.clone()).unwrap_or_else(|| unit.clone()),
+            "exec": info.and_then(|i| i.exec_start.clone()),
+            "argv": null,
+            "enabled": is_enabled,
+            "scope": "user",
+            "source": "systemd-user",
+            "file_path": null
+        })
+    };
+
+    let mut entries: Vec<serde_json::Value> = enabled.iter().map(|u| entry(u, true)).collect();
+    entries.extend(
+        disabled
+            .iter()
+            .filter(|u| units.get(*u).is_some_and(|i| i.wanted_by.iter().any(|t| t == "default.target")))
+            .map(|u| entry(u, false)),
+    );
+    entries
 }
 
-#[cfg(target_os = "macos")]
-#[tauri::command]
-pub fn add_apt_repo(repo_line: String) -> Result<serde_json::Value, String> {
-    let tap_name = repo_line.trim();
-    if tap_name.is_empty() {
-        return Err("Tap name cannot be empty".to_string());
+#[cfg(target_os = "linux")]
+fn set_user_unit_enabled(unit: &str, enabled: bool) -> Result<serde_json::Value, String> {
+    let name = unit.strip_suffix(".service").ok_or("Invalid unit name")?;
+    if name.is_empty() || name.contains('/') {
+        return Err("Invalid unit name".to_string());
     }
+    super::services::run_systemctl(if enabled { "enable" } else { "disable" }, name, true)?;
+    Ok(json!({"success": true}))
+}
 
-    let brew = find_brew().ok_or_else(|| "Homebrew not found".to_string())?;
+#[cfg(target_os = "linux")]
+fn read_desktop_files(dir: &std::path::Path) -> Vec<(PathBuf, String)> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "desktop"))
+                .filter_map(|p| fs::read_to_string(&p).ok().map(|c| (p, c)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-    let output = Command::new(&brew)
-        .args(["tap", tap_name])
-        .output()
-        .map_err(|e| format!("Failed to run brew tap: {}", e))?;
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_startup_apps() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("startup")?;
+    let system = read_desktop_files(std::path::Path::new(SYSTEM_AUTOSTART_DIR));
+    let user = user_autostart_dir().map(|dir| read_desktop_files(&dir)).unwrap_or_default();
 
-    if output.status.success() {
-        Ok(json!({"success": true}))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("brew tap failed: {}", stderr))
+    let mut apps: Vec<serde_json::Value> = Vec::new();
+    for (path, content) in &user {
+        let overrides_system = system.iter().any(|(sys, _)| sys.file_name() == path.file_name());
+        let scope = if overrides_system { "user-override" } else { "user" };
+        apps.push(startup_entry_json(path, content, scope));
+    }
+    for (path, content) in &system {
+        if !user.iter().any(|(usr, _)| usr.file_name() == path.file_name()) {
+            apps.push(startup_entry_json(path, content, "system"));
+        }
     }
+    apps.extend(systemd_user_startup_entries());
+    Ok(json!(apps))
 }
 
-#[cfg(target_os = "macos")]
-#[tauri::command]
-pub fn delete_apt_repo(id: String) -> Result<serde_json::Value, String> {
-    let brew = find_brew().ok_or_else(|| "Homebrew not found".to_string())?;
+#[cfg(target_os = "linux")]
+fn desktop_exec_value(exec: &str, argv: Option<Vec<String>>) -> Result<String, String> {
+    let explicit = argv.is_some();
+    let args = startup_argv(exec, argv)?;
+    if !explicit && parse_exec(exec.trim()).as_ref() == Ok(&args) {
+        return Ok(exec.trim().to_string());
+    }
+    Ok(serialize_exec(&args))
+}
 
-    let output = Command::new(&brew)
-        .args(["untap", &id])
-        .output()
-        .map_err(|e| format!("Failed to run brew untap: {}", e))?;
+#[cfg(target_os = "linux")]
+fn collect_desktop_files(dir: &std::path::Path, prefix: &str, out: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            collect_desktop_files(&path, &format!("{}{}-", prefix, name), out);
+        } else if name.ends_with(".desktop") {
+            out.push((format!("{}{}", prefix, name), path));
+        }
+    }
+}
 
-    if output.status.success() {
-        Ok(json!({"success": true}))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("brew untap failed: {}", stderr))
+#[cfg(target_os = "linux")]
+fn installed_desktop_files() -> Vec<(String, PathBuf)> {
+    let mut dirs_to_scan = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs_to_scan.push(home.join(".local/share/applications"));
     }
+    dirs_to_scan.push(PathBuf::from("/usr/share/applications"));
+
+    let mut files = Vec::new();
+    for dir in dirs_to_scan {
+        collect_desktop_files(&dir, "", &mut files);
+    }
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|(id, _)| seen.insert(id.clone()));
+    files
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn list_startup_apps() -> Result<serde_json::Value, String> {
-    let mut apps: Vec<serde_json::Value> = Vec::new();
-    if let Some(home) = dirs::home_dir() {
-        let autostart = home.join(".config").join("autostart");
-        if autostart.exists() {
-            if let Ok(entries) = fs::read_dir(&autostart) {
-                for e in entries.flatten() {
-                    if let Ok(s) = fs::read_to_string(e.path()) {
-                        let mut name = None;
-                        let mut exec = None;
-                        let mut hidden = false;
-                        for line in s.lines() {
-                            if line.starts_with("Name=") {
-                                name = Some(line.trim_start_matches("Name=").to_string());
-                            }
-                            if line.starts_with("Exec=") {
-                                exec = Some(line.trim_start_matches("Exec=").to_string());
-                            }
-                            if line.starts_with("Hidden=") {
-                                hidden = line
-                                    .trim_start_matches("Hidden=")
-                                    .trim()
-                                    .eq_ignore_ascii_case("true");
-                            }
-                        }
-                        apps.push(json!({
-                            "file": e.path().file_name().map(|n| n.to_string_lossy().to_string()),
-                            "name": name,
-                            "exec": exec,
-                            "enabled": !hidden,
-                            "file_path": e.path().to_string_lossy().to_string()
-                        }));
-                    }
-                }
+pub fn list_installed_applications() -> Result<serde_json::Value, GantryError> {
+    let mut apps: Vec<serde_json::Value> = installed_desktop_files()
+        .into_iter()
+        .filter_map(|(desktop_id, path)| {
+            let fields = parse_desktop_entry(&fs::read_to_string(&path).ok()?);
+            let is_true = |key: &str| fields.get(key).is_some_and(|v| v.eq_ignore_ascii_case("true"));
+            if is_true("NoDisplay") || is_true("Hidden") || fields.get("Type").map(String::as_str) != Some("Application") {
+                return None;
             }
-        }
-    }
+            Some(json!({
+                "name": fields.get("Name")?,
+                "exec": fields.get("Exec")?,
+                "icon": fields.get("Icon"),
+                "desktop_id": desktop_id,
+            }))
+        })
+        .collect();
+    apps.sort_by_key(|a| a["name"].as_str().unwrap_or_default().to_lowercase());
     Ok(json!(apps))
 }
 
+#[cfg(target_os = "linux")]
+fn add_startup_app_from_desktop_id(
+    desktop_id: &str,
+    updates: Vec<(&'static str, Option<String>)>,
+) -> Result<serde_json::Value, String> {
+    let (_, source) = installed_desktop_files()
+        .into_iter()
+        .find(|(id, _)| id == desktop_id)
+        .ok_or_else(|| format!("Application {} not found", desktop_id))?;
+    let content = fs::read_to_string(&source).map_err(|e| e.to_string())?;
+
+    let autostart = user_autostart_dir()?;
+    let filepath = autostart.join(desktop_id);
+    if filepath.exists() {
+        return Err(format!("{} is already a startup app", desktop_id));
+    }
+
+    let mut updates: Vec<(&str, Option<String>)> =
+        updates.into_iter().filter(|(key, _)| *key != "Name" && *key != "Exec").collect();
+    updates.push(("Hidden", Some("false".to_string())));
+    write_user_autostart(&filepath, &update_desktop_entry(&content, &updates))?;
+
+    Ok(json!({"success": true, "file": desktop_id}))
+}
+
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value, String> {
-    let home = dirs::home_dir()
-        .ok_or_else(|| "Cannot determine home directory".to_string())?;
-    let autostart = home.join(".config").join("autostart");
+#[allow(clippy::too_many_arguments)]
+pub fn add_startup_app(
+    name: String,
+    exec: String,
+    delay_seconds: Option<u32>,
+    only_show_in: Option<Vec<String>>,
+    comment: Option<String>,
+    terminal: Option<bool>,
+    argv: Option<Vec<String>>,
+    from_desktop_id: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    if let Some(desktop_id) = from_desktop_id {
+        let updates = startup_app_updates(&name, "", delay_seconds, only_show_in, comment, terminal)?;
+        return Ok(add_startup_app_from_desktop_id(&desktop_id, updates)?);
+    }
+
+    let exec = desktop_exec_value(&exec, argv)?;
+    let updates = startup_app_updates(&name, &exec, delay_seconds, only_show_in, comment, terminal)?;
+    let autostart = user_autostart_dir()?;
     fs::create_dir_all(&autostart).map_err(|e| e.to_string())?;
 
     let sanitized: String = name
@@ -574,15 +2348,13 @@ pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value,
     let mut filename = format!("{}.desktop", sanitized);
     let mut filepath = autostart.join(&filename);
 
-    if filepath.exists() {
+    // A name matching a system entry would silently override it.
+    if filepath.exists() || PathBuf::from(SYSTEM_AUTOSTART_DIR).join(&filename).exists() {
         filename = format!("{}_{}.desktop", sanitized, chrono::Utc::now().timestamp_millis());
         filepath = autostart.join(&filename);
     }
 
-    let content = format!(
-        "[Desktop Entry]\nType=Application\nName={}\nExec={}\nHidden=false\n",
-        name, exec
-    );
+    let content = update_desktop_entry("[Desktop Entry]\nType=Application\nName=\nExec=\nHidden=false\n", &updates);
     fs::write(&filepath, content).map_err(|e| e.to_string())?;
 
     Ok(json!({"success": true, "file": filename}))
@@ -590,83 +2362,116 @@ pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value,
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn edit_startup_app(file: String, name: String, exec: String) -> Result<serde_json::Value, String> {
-    let home = dirs::home_dir()
-        .ok_or_else(|| "Cannot determine home directory".to_string())?;
-    let filepath = home.join(".config").join("autostart").join(&file);
-
-    if !filepath.exists() {
-        return Err("Desktop file not found".to_string());
+#[allow(clippy::too_many_arguments)]
+pub fn edit_startup_app(
+    file: String,
+    name: String,
+    exec: String,
+    delay_seconds: Option<u32>,
+    only_show_in: Option<Vec<String>>,
+    comment: Option<String>,
+    terminal: Option<bool>,
+    argv: Option<Vec<String>>,
+) -> Result<serde_json::Value, GantryError> {
+    if file.ends_with(".service") {
+        return Err(GantryError::Unsupported("systemd user units are edited with systemctl --user edit".into()));
     }
-
-    let content = fs::read_to_string(&filepath).map_err(|e| e.to_string())?;
-    let new_content: String = content
-        .lines()
-        .map(|line| {
-            if line.starts_with("Name=") {
-                format!("Name={}", name)
-            } else if line.starts_with("Exec=") {
-                format!("Exec={}", exec)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    fs::write(&filepath, format!("{}\n", new_content)).map_err(|e| e.to_string())?;
+    let exec = desktop_exec_value(&exec, argv)?;
+    let updates = startup_app_updates(&name, &exec, delay_seconds, only_show_in, comment, terminal)?;
+    let (filepath, content) = resolve_autostart_file(&file)?;
+    write_user_autostart(&filepath, &update_desktop_entry(&content, &updates))?;
     Ok(json!({"success": true}))
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn delete_startup_app(file: String) -> Result<serde_json::Value, String> {
-    let home = dirs::home_dir()
-        .ok_or_else(|| "Cannot determine home directory".to_string())?;
-    let filepath = home.join(".config").join("autostart").join(&file);
+pub fn delete_startup_app(file: String) -> Result<serde_json::Value, GantryError> {
+    // Unit files usually belong to packages, so deleting only disables.
+    if file.ends_with(".service") {
+        return Ok(set_user_unit_enabled(&file, false)?);
+    }
+    let (filepath, content) = resolve_autostart_file(&file)?;
 
-    if !filepath.exists() {
-        return Err("Desktop file not found".to_string());
+    if PathBuf::from(SYSTEM_AUTOSTART_DIR).join(&file).exists() {
+        write_user_autostart(&filepath, &update_desktop_entry(&content, &[("Hidden", Some("true".to_string()))]))?;
+        return Ok(json!({"success": true, "overridden": true}));
     }
 
     fs::remove_file(&filepath).map_err(|e| e.to_string())?;
-    Ok(json!({"success": true}))
+    Ok(json!({"success": true, "overridden": false}))
 }
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn toggle_startup_app(file: String, enabled: bool) -> Result<serde_json::Value, String> {
-    let home = dirs::home_dir()
-        .ok_or_else(|| "Cannot determine home directory".to_string())?;
-    let filepath = home.join(".config").join("autostart").join(&file);
+pub fn toggle_startup_app(file: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    if file.ends_with(".service") {
+        return Ok(set_user_unit_enabled(&file, enabled)?);
+    }
+    let (filepath, content) = resolve_autostart_file(&file)?;
+    let hidden = if enabled { "false" } else { "true" };
+    write_user_autostart(&filepath, &update_desktop_entry(&content, &[("Hidden", Some(hidden.to_string()))]))?;
+    Ok(json!({"success": true}))
+}
 
-    if !filepath.exists() {
-        return Err("Desktop file not found".to_string());
+#[cfg(target_os = "linux")]
+const LOGIN_ITEM_FILE: &str = "gantry.desktop";
+
+// Marks gantry's own entry, so unregistering never removes one the user added under the same name.
+#[cfg(target_os = "linux")]
+const LOGIN_ITEM_MARKER: &str = "X-Gantry-Login-Item";
+
+fn login_item_program() -> Result<String, String> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE").filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(appimage).to_string_lossy().to_string());
     }
+    let exe = std::env::current_exe().map_err(|e| format!("Cannot find the gantry executable: {}", e))?;
+    Ok(exe.to_string_lossy().to_string())
+}
 
-    let content = fs::read_to_string(&filepath).map_err(|e| e.to_string())?;
-    let hidden_value = if enabled { "false" } else { "true" };
-    let mut found_hidden = false;
+#[cfg(target_os = "linux")]
+fn is_gantry_login_item(content: &str) -> bool {
+    parse_desktop_entry(content).get(LOGIN_ITEM_MARKER).is_some_and(|v| v == "true")
+}
 
-    let new_lines: Vec<String> = content
-        .lines()
-        .map(|line| {
-            if line.starts_with("Hidden=") {
-                found_hidden = true;
-                format!("Hidden={}", hidden_value)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect();
+#[cfg(target_os = "linux")]
+fn register_login_item_in(dir: &std::path::Path, program: &str) -> Result<PathBuf, String> {
+    let path = dir.join(LOGIN_ITEM_FILE);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !is_gantry_login_item(&existing) {
+            return Err(format!("{} already exists and was not created by Gantry", path.display()));
+        }
+    }
+    let updates = [
+        ("Name", Some("Gantry".to_string())),
+        ("Exec", Some(serialize_exec(&[program.to_string()]))),
+        ("Comment", Some("Start Gantry at login".to_string())),
+        (LOGIN_ITEM_MARKER, Some("true".to_string())),
+    ];
+    let content = update_desktop_entry("[Desktop Entry]\nType=Application\nName=\nExec=\nHidden=false\n", &updates);
+    write_user_autostart(&path, &content)?;
+    Ok(path)
+}
 
-    let mut new_content = new_lines.join("\n");
-    if !found_hidden {
-        new_content.push_str(&format!("\nHidden={}", hidden_value));
+#[cfg(target_os = "linux")]
+fn unregister_login_item_in(dir: &std::path::Path) -> Result<bool, String> {
+    let path = dir.join(LOGIN_ITEM_FILE);
+    match fs::read_to_string(&path) {
+        Ok(content) if is_gantry_login_item(&content) => {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        _ => Ok(false),
     }
+}
 
-    fs::write(&filepath, format!("{}\n", new_content)).map_err(|e| e.to_string())?;
-    Ok(json!({"success": true}))
+#[cfg(target_os = "linux")]
+pub(crate) fn set_login_item(enabled: bool) -> Result<(), String> {
+    let dir = user_autostart_dir()?;
+    if enabled {
+        register_login_item_in(&dir, &login_item_program()?).map(|_| ())
+    } else {
+        unregister_login_item_in(&dir).map(|_| ())
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -676,7 +2481,8 @@ fn launch_agents_dir() -> Option<PathBuf> {
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn list_startup_apps() -> Result<serde_json::Value, String> {
+pub fn list_startup_apps() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("startup")?;
     let dir = launch_agents_dir()
         .ok_or_else(|| "Cannot determine home directory".to_string())?;
 
@@ -702,11 +2508,10 @@ pub fn list_startup_apps() -> Result<serde_json::Value, String> {
                 .and_then(|v| v.as_string())
                 .map(|s| s.to_string());
 
-            let exec = dict.get("ProgramArguments")
+            let argv: Option<Vec<String>> = dict.get("ProgramArguments")
                 .and_then(|v| v.as_array())
-                .and_then(|a| a.first())
-                .and_then(|v| v.as_string())
-                .map(|s| s.to_string());
+                .map(|a| a.iter().filter_map(|v| v.as_string()).map(|s| s.to_string()).collect());
+            let exec = argv.as_deref().map(serialize_exec);
 
             let disabled = dict.get("Disabled")
                 .and_then(|v| v.as_boolean())
@@ -719,6 +2524,7 @@ pub fn list_startup_apps() -> Result<serde_json::Value, String> {
                 "file": filename,
                 "name": label,
                 "exec": exec,
+                "argv": argv,
                 "enabled": !disabled,
                 "file_path": path.to_string_lossy().to_string(),
             }));
@@ -728,9 +2534,88 @@ pub fn list_startup_apps() -> Result<serde_json::Value, String> {
     Ok(json!(apps))
 }
 
+#[cfg(target_os = "macos")]
+fn installed_app_bundles() -> Vec<(String, PathBuf, plist::Dictionary)> {
+    let mut dirs_to_scan = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs_to_scan.push(home.join("Applications"));
+    }
+    dirs_to_scan.push(PathBuf::from("/Applications"));
+
+    let mut seen = std::collections::HashSet::new();
+    let mut bundles = Vec::new();
+    for dir in dirs_to_scan {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("app") {
+                continue;
+            }
+            let Some(info) = plist::from_file::<plist::Value, _>(path.join("Contents/Info.plist"))
+                .ok()
+                .and_then(|v| v.into_dictionary())
+            else {
+                continue;
+            };
+            let Some(id) = info.get("CFBundleIdentifier").and_then(|v| v.as_string()).map(|s| s.to_string()) else {
+                continue;
+            };
+            if seen.insert(id.clone()) {
+                bundles.push((id, path, info));
+            }
+        }
+    }
+    bundles
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_installed_applications() -> Result<serde_json::Value, GantryError> {
+    let mut apps: Vec<serde_json::Value> = installed_app_bundles()
+        .into_iter()
+        .filter(|(_, _, info)| info.get("LSUIElement").and_then(|v| v.as_boolean()) != Some(true))
+        .map(|(id, path, info)| {
+            let name = info
+                .get("CFBundleDisplayName")
+                .or_else(|| info.get("CFBundleName"))
+                .and_then(|v| v.as_string())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+            json!({
+                "name": name,
+                "exec": serialize_exec(&["/usr/bin/open".to_string(), path.to_string_lossy().to_string()]),
+                "icon": info.get("CFBundleIconFile").and_then(|v| v.as_string()),
+                "desktop_id": id,
+            })
+        })
+        .collect();
+    apps.sort_by_key(|a| a["name"].as_str().unwrap_or_default().to_lowercase());
+    Ok(json!(apps))
+}
+
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value, String> {
+#[allow(clippy::too_many_arguments)]
+pub fn add_startup_app(
+    name: String,
+    exec: String,
+    _delay_seconds: Option<u32>,
+    _only_show_in: Option<Vec<String>>,
+    _comment: Option<String>,
+    _terminal: Option<bool>,
+    argv: Option<Vec<String>>,
+    from_desktop_id: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    let args = match from_desktop_id {
+        Some(id) => {
+            let (_, path, _) = installed_app_bundles()
+                .into_iter()
+                .find(|(bundle_id, _, _)| *bundle_id == id)
+                .ok_or_else(|| format!("Application {} not found", id))?;
+            vec!["/usr/bin/open".to_string(), path.to_string_lossy().to_string()]
+        }
+        None => startup_argv(&exec, argv)?,
+    };
     let dir = launch_agents_dir()
         .ok_or_else(|| "Cannot determine home directory".to_string())?;
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
@@ -754,7 +2639,7 @@ pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value,
     dict.insert("Label".into(), plist::Value::String(label));
     dict.insert(
         "ProgramArguments".into(),
-        plist::Value::Array(vec![plist::Value::String(exec)]),
+        plist::Value::Array(args.into_iter().map(plist::Value::String).collect()),
     );
     dict.insert("RunAtLoad".into(), plist::Value::Boolean(true));
 
@@ -768,13 +2653,24 @@ pub fn add_startup_app(name: String, exec: String) -> Result<serde_json::Value,
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn edit_startup_app(file: String, name: String, exec: String) -> Result<serde_json::Value, String> {
+#[allow(clippy::too_many_arguments)]
+pub fn edit_startup_app(
+    file: String,
+    name: String,
+    exec: String,
+    _delay_seconds: Option<u32>,
+    _only_show_in: Option<Vec<String>>,
+    _comment: Option<String>,
+    _terminal: Option<bool>,
+    argv: Option<Vec<String>>,
+) -> Result<serde_json::Value, GantryError> {
+    let args = startup_argv(&exec, argv)?;
     let dir = launch_agents_dir()
         .ok_or_else(|| "Cannot determine home directory".to_string())?;
     let filepath = dir.join(&file);
 
     if !filepath.exists() {
-        return Err("Plist file not found".to_string());
+        return Err(GantryError::NotFound("Plist file not found".into()));
     }
 
     let _ = Command::new("launchctl").args(["unload", &filepath.to_string_lossy()]).output();
@@ -796,7 +2692,7 @@ pub fn edit_startup_app(file: String, name: String, exec: String) -> Result<serd
     dict.insert("Label".into(), plist::Value::String(label));
     dict.insert(
         "ProgramArguments".into(),
-        plist::Value::Array(vec![plist::Value::String(exec)]),
+        plist::Value::Array(args.into_iter().map(plist::Value::String).collect()),
     );
 
     plist::to_file_xml(&filepath, &plist::Value::Dictionary(dict))
@@ -809,13 +2705,13 @@ pub fn edit_startup_app(file: String, name: String, exec: String) -> Result<serd
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn delete_startup_app(file: String) -> Result<serde_json::Value, String> {
+pub fn delete_startup_app(file: String) -> Result<serde_json::Value, GantryError> {
     let dir = launch_agents_dir()
         .ok_or_else(|| "Cannot determine home directory".to_string())?;
     let filepath = dir.join(&file);
 
     if !filepath.exists() {
-        return Err("Plist file not found".to_string());
+        return Err(GantryError::NotFound("Plist file not found".into()));
     }
 
     let _ = Command::new("launchctl").args(["unload", &filepath.to_string_lossy()]).output();
@@ -826,13 +2722,13 @@ pub fn delete_startup_app(file: String) -> Result<serde_json::Value, String> {
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn toggle_startup_app(file: String, enabled: bool) -> Result<serde_json::Value, String> {
+pub fn toggle_startup_app(file: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
     let dir = launch_agents_dir()
         .ok_or_else(|| "Cannot determine home directory".to_string())?;
     let filepath = dir.join(&file);
 
     if !filepath.exists() {
-        return Err("Plist file not found".to_string());
+        return Err(GantryError::NotFound("Plist file not found".into()));
     }
 
     let path_str = filepath.to_string_lossy().to_string();
@@ -848,7 +2744,571 @@ pub fn toggle_startup_app(file: String, enabled: bool) -> Result<serde_json::Val
     if output.status.success() {
         Ok(json!({"success": true}))
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("launchctl failed: {}", stderr))
+        Err(GantryError::from_output("launchctl", &output).context("launchctl failed"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+const LOGIN_ITEM_LABEL: &str = "com.gantry.manager";
+
+#[cfg(target_os = "macos")]
+const LOGIN_ITEM_MARKER: &str = "GantryLoginItem";
+
+#[cfg(target_os = "macos")]
+fn is_gantry_login_item(path: &std::path::Path) -> bool {
+    plist::from_file::<plist::Value, _>(path)
+        .ok()
+        .and_then(|v| v.into_dictionary())
+        .and_then(|d| d.get(LOGIN_ITEM_MARKER).and_then(|v| v.as_boolean()))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn set_login_item(enabled: bool) -> Result<(), String> {
+    let dir = launch_agents_dir()
+        .ok_or_else(|| "Cannot determine home directory".to_string())?;
+    let filepath = dir.join(format!("{}.plist", LOGIN_ITEM_LABEL));
+    let ours = is_gantry_login_item(&filepath);
+
+    if !enabled {
+        if ours {
+            let _ = Command::new("launchctl").args(["unload", &filepath.to_string_lossy()]).output();
+            fs::remove_file(&filepath).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    if filepath.exists() && !ours {
+        return Err(format!("{} already exists and was not created by Gantry", filepath.display()));
+    }
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let mut dict = plist::Dictionary::new();
+    dict.insert("Label".into(), plist::Value::String(LOGIN_ITEM_LABEL.to_string()));
+    dict.insert(
+        "ProgramArguments".into(),
+        plist::Value::Array(vec![plist::Value::String(login_item_program()?)]),
+    );
+    dict.insert("RunAtLoad".into(), plist::Value::Boolean(true));
+    dict.insert(LOGIN_ITEM_MARKER.into(), plist::Value::Boolean(true));
+
+    plist::to_file_xml(&filepath, &plist::Value::Dictionary(dict))
+        .map_err(|e| format!("Failed to write plist: {}", e))?;
+    Ok(())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_apt_update() {
+        let stdout = "Hit:1 http://archive.ubuntu.com/ubuntu jammy InRelease\n\
+                      Get:2 http://archive.ubuntu.com/ubuntu jammy-updates InRelease [119 kB]\n\
+                      Ign:3 http://example.com/repo stable InRelease\n\
+                      Err:4 http://example.com/repo stable Release\n\
+                      \x20 404  Not Found [IP: 93.184.216.34 80]\n\
+                      Get:5 https://repo.vendor.io/apt stable InRelease [3,000 B]\n\
+                      Err:5 https://repo.vendor.io/apt stable InRelease\n\
+                      \x20 The following signatures couldn't be verified because the public key is not available: NO_PUBKEY 1A2B3C4D5E6F7A8B\n\
+                      Reading package lists...\n";
+        let stderr = "W: GPG error: https://repo.vendor.io/apt stable InRelease: The following signatures couldn't be verified because the public key is not available: NO_PUBKEY 1A2B3C4D5E6F7A8B\n\
+                      E: The repository 'http://example.com/repo stable Release' does not have a Release file.\n";
+        let sources = parse_apt_update(stdout, stderr);
+        assert_eq!(sources.len(), 4);
+        assert_eq!(sources[0].status, "ok");
+        assert_eq!(sources[1].suite, "jammy-updates");
+        assert_eq!(sources[2].status, "not_found");
+        assert!(sources[2].reason.as_deref().unwrap_or("").contains("404"));
+        assert_eq!(sources[3].status, "no_pubkey");
+        assert_eq!(sources[3].missing_key.as_deref(), Some("1A2B3C4D5E6F7A8B"));
+    }
+
+    #[test]
+    fn test_toggle_deb822_single_stanza() {
+        let one = "Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\n";
+        assert_eq!(
+            toggle_deb822_stanza(one, 0, false).unwrap(),
+            "Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\nEnabled: no\n"
+        );
+
+        let with_field = "Enabled: no\nTypes: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\n";
+        assert_eq!(
+            toggle_deb822_stanza(with_field, 1, true).unwrap(),
+            "Enabled: yes\nTypes: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\n"
+        );
+    }
+
+    #[test]
+    fn test_toggle_deb822_two_stanzas() {
+        let two = "Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\n\n\
+                   Types: deb\nURIs: https://b.example\nSuites: stable\nComponents: main\n";
+
+        let second = toggle_deb822_stanza(two, 5, false).unwrap();
+        assert_eq!(second.matches("Enabled:").count(), 1, "only one stanza should change");
+        assert!(second.ends_with("URIs: https://b.example\nSuites: stable\nComponents: main\nEnabled: no\n"));
+
+        let first = toggle_deb822_stanza(two, 0, false).unwrap();
+        assert!(first.starts_with("Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\nEnabled: no\n\n"));
+        assert_eq!(first.matches("Enabled:").count(), 1);
+
+        // Toggling again must replace the field, not add a second one.
+        let again = toggle_deb822_stanza(&second, 5, true).unwrap();
+        assert_eq!(again.matches("Enabled: yes").count(), 1);
+        assert!(!again.contains("Enabled: no"));
+    }
+
+    #[test]
+    fn test_toggle_deb822_three_stanzas_round_trip() {
+        let three = "# managed by hand\n\
+                     Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\nEnabled: yes\n\n\n\
+                     Types: deb\nURIs: https://b.example\nSuites: stable\nComponents: main\n\n\
+                     Types: deb-src\nURIs: https://c.example\nSuites: stable\nComponents: main\nEnabled: no\n";
+        let path = std::env::temp_dir().join(format!("gantry_toggle_{}.sources", std::process::id()));
+        fs::write(&path, three).unwrap();
+        let repos = parse_sources_file(&path);
+        assert_eq!(repos.len(), 3);
+
+        let toggled = toggle_deb822_stanza(three, repos[1].line_number, false).unwrap();
+        fs::write(&path, &toggled).unwrap();
+        let after = parse_sources_file(&path);
+        let _ = fs::remove_file(&path);
+
+        let enabled: Vec<bool> = after.iter().map(|r| r.enabled).collect();
+        assert_eq!(enabled, vec![true, false, false]);
+        assert_eq!(after[0].line_number, repos[0].line_number);
+        assert_eq!(after[1].line_number, repos[1].line_number, "the toggled stanza keeps its id");
+        assert!(toggled.starts_with("# managed by hand\n"));
+        assert!(toggle_deb822_stanza(three, 7, true).is_err(), "blank lines belong to no stanza");
+    }
+
+    #[test]
+    fn test_delete_then_toggle_with_listed_ids() {
+        let file = "/etc/apt/sources.list";
+        let content = "# main archive\n\
+                       deb http://archive.ubuntu.com/ubuntu noble main\n\
+                       deb https://a.example stable main\n\
+                       # deb https://b.example stable main\n\
+                       deb https://c.example stable main\n";
+        let listed = parse_sources_content(content, file, false);
+        assert_eq!(listed.len(), 4);
+
+        // Delete a, then toggle b and c using the ids from before the delete.
+        let (_, line, fp) = parse_repo_id(&listed[1].id).unwrap();
+        let after_delete = remove_repo(content, false, locate_repo(content, file, false, line, fp).unwrap()) + "\n";
+        assert!(!after_delete.contains("a.example"));
+
+        let (_, line, fp) = parse_repo_id(&listed[2].id).unwrap();
+        let b_line = locate_repo(&after_delete, file, false, line, fp).unwrap();
+        assert_eq!(b_line, 2, "b moved up a line after the delete");
+        let after_toggle = toggle_one_line(&after_delete, b_line, true) + "\n";
+
+        let (_, line, fp) = parse_repo_id(&listed[3].id).unwrap();
+        let c_line = locate_repo(&after_toggle, file, false, line, fp).unwrap();
+        let after_second = toggle_one_line(&after_toggle, c_line, false);
+
+        let repos = parse_sources_content(&after_second, file, false);
+        let state: Vec<(&str, bool)> = repos.iter().map(|r| (r.uris.as_str(), r.enabled)).collect();
+        assert_eq!(
+            state,
+            vec![("http://archive.ubuntu.com/ubuntu", true), ("https://b.example", true), ("https://c.example", false)]
+        );
+        assert_eq!(repos[1].fingerprint, listed[2].fingerprint, "toggling keeps the fingerprint");
+
+        // The deleted repository can no longer be addressed, even by line.
+        let (_, line, fp) = parse_repo_id(&listed[1].id).unwrap();
+        assert!(locate_repo(&after_second, file, false, line, fp).is_err());
+        assert!(parse_repo_id("/etc/apt/sources.list:3").is_err(), "ids without a fingerprint are rejected");
+    }
+
+    #[test]
+    fn test_delete_then_toggle_deb822_stanzas() {
+        let file = "/etc/apt/sources.list.d/example.sources";
+        let content = "Types: deb\nURIs: https://a.example\nSuites: stable\nComponents: main\n\n\
+                       Types: deb\nURIs: https://b.example\nSuites: stable\nComponents: main\n\n\
+                       Types: deb\nURIs: https://c.example\nSuites: stable\nComponents: main\nEnabled: no\n";
+        let listed = parse_sources_content(content, file, true);
+        assert_eq!(listed.len(), 3);
+
+        let (_, line, fp) = parse_repo_id(&listed[0].id).unwrap();
+        let after_delete = remove_repo(content, true, locate_repo(content, file, true, line, fp).unwrap()) + "\n";
+        assert!(after_delete.starts_with("Types: deb\nURIs: https://b.example\n"), "the stanza and its separator go");
+
+        let (_, line, fp) = parse_repo_id(&listed[2].id).unwrap();
+        let c_line = locate_repo(&after_delete, file, true, line, fp).unwrap();
+        let toggled = toggle_deb822_stanza(&after_delete, c_line, true).unwrap();
+
+        let repos = parse_sources_content(&toggled, file, true);
+        let state: Vec<(&str, bool)> = repos.iter().map(|r| (r.uris.as_str(), r.enabled)).collect();
+        assert_eq!(state, vec![("https://b.example", true), ("https://c.example", true)]);
+        assert_eq!(repos[1].fingerprint, listed[2].fingerprint, "Enabled: is not part of the fingerprint");
+    }
+
+    #[test]
+    fn test_find_duplicate_repos_across_files() {
+        let mut repos = parse_sources_content(
+            "deb https://Repo.Example.com/apt/ stable main\n# deb https://c.example stable main\n",
+            "/etc/apt/sources.list",
+            false,
+        );
+        repos.extend(parse_sources_content(
+            "Types: deb\nURIs: http://repo.example.com/apt\nSuites: stable\nComponents: main extra\n\n\
+             Types: deb\nURIs: https://c.example\nSuites: stable\nComponents: main\nEnabled: no\n",
+            "/etc/apt/sources.list.d/example.sources",
+            true,
+        ));
+        let duplicates = find_duplicate_repos(&repos);
+        assert_eq!(duplicates.len(), 2, "disabled copies are not reported: {:?}", duplicates);
+        assert_eq!(duplicates[&repos[0].id], vec![repos[2].id.clone()]);
+        assert_eq!(duplicates[&repos[2].id], vec![repos[0].id.clone()]);
+    }
+
+    #[test]
+    fn test_find_existing_repo_across_formats() {
+        let legacy = parse_sources_content(
+            "deb [arch=amd64 signed-by=/etc/apt/keyrings/docker.gpg] https://download.docker.com/linux/ubuntu noble stable\n",
+            "/etc/apt/sources.list.d/docker.list",
+            false,
+        );
+        assert_eq!(legacy[0].uris, "https://download.docker.com/linux/ubuntu", "options are not the URI");
+
+        let stanza = "Types: deb\nURIs: http://Download.Docker.com/linux/ubuntu/\nSuites: Noble\nComponents: stable\n\
+                      Signed-By: /etc/apt/keyrings/docker.gpg\n";
+        let existing = find_existing_repo(stanza, true, &legacy).expect("deb822 form of the same repo");
+        assert_eq!(existing.id, legacy[0].id);
+
+        let deb822 = parse_sources_content(stanza, "/etc/apt/sources.list.d/docker.sources", true);
+        let line = "deb https://download.docker.com/linux/ubuntu/ noble stable";
+        assert_eq!(find_existing_repo(line, false, &deb822).map(|r| r.file_path), Some(deb822[0].file_path.clone()));
+
+        assert!(find_existing_repo("deb https://download.docker.com/linux/ubuntu noble test", false, &legacy).is_none());
+        assert!(find_existing_repo("deb-src https://download.docker.com/linux/ubuntu noble stable", false, &legacy).is_none());
+    }
+
+    #[test]
+    fn test_legacy_keyring_helpers() {
+        assert_eq!(
+            apt_lists_name("http://archive.ubuntu.com/ubuntu/dists/noble"),
+            "archive.ubuntu.com_ubuntu_dists_noble"
+        );
+        assert_eq!(apt_lists_name("https://user:pw@repo.example/apt/dists/stable"), "repo.example_apt_dists_stable");
+
+        let status = "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG 871920D1991BC93C Ubuntu Archive\n\
+                      [GNUPG:] VALIDSIG 0123 2024-04-25 1714000000 0 4 0 1 10 01 F6ECB3762474EDA9D21B7022871920D1991BC93C\n";
+        assert_eq!(parse_gpgv_validsig(status).as_deref(), Some("F6ECB3762474EDA9D21B7022871920D1991BC93C"));
+        assert_eq!(parse_gpgv_validsig("[GNUPG:] NO_PUBKEY 871920D1991BC93C\n"), None);
+
+        let lines = ["deb [signed-by=/etc/apt/keyrings/a.gpg] https://a.example stable main", "deb https://b.example stable main"];
+        assert!(repo_has_signed_by(&lines, 0, false));
+        assert!(!repo_has_signed_by(&lines, 1, false));
+        let stanza = ["Types: deb", "URIs: https://a.example", "Signed-By: /etc/apt/keyrings/a.gpg"];
+        assert!(repo_has_signed_by(&stanza, 0, true));
+    }
+
+    #[test]
+    fn test_insert_signed_by() {
+        assert_eq!(
+            insert_signed_by("deb https://repo.example.com stable main", "/etc/apt/keyrings/example.gpg").unwrap(),
+            "deb [signed-by=/etc/apt/keyrings/example.gpg] https://repo.example.com stable main"
+        );
+        assert_eq!(
+            insert_signed_by("deb [arch=amd64] https://repo.example.com stable main", "/etc/apt/keyrings/example.gpg").unwrap(),
+            "deb [signed-by=/etc/apt/keyrings/example.gpg arch=amd64] https://repo.example.com stable main"
+        );
+        assert!(insert_signed_by("deb [signed-by=/a.gpg] https://x stable", "/b.gpg").is_err());
+        assert!(insert_signed_by("deb https://x stable", "relative.gpg").is_err());
+    }
+
+    #[test]
+    fn test_parse_gpg_colons() {
+        let sample = "pub:-:4096:1:ABCDEF0123456789:1600000000:::-:::scESC::::::23::0:\n\
+                      fpr:::::::::0123456789ABCDEF0123456789ABCDEF01234567:\n\
+                      uid:-::::1600000000::HASH::Example Repo <repo@example.com>::::::::::0:\n\
+                      sub:-:4096:1:1111222233334444:1600000000::::::e::::::23:\n\
+                      fpr:::::::::FFFFEEEEDDDDCCCCBBBBAAAA1111222233334444:\n\
+                      pub:e:2048:1:9999888877776666:1300000000:1400000000::-:::sc::::::23::0:\n\
+                      fpr:::::::::AAAABBBBCCCCDDDDEEEEFFFF9999888877776666:\n";
+        let keys = parse_gpg_colons(sample);
+        assert_eq!(keys.len(), 2, "subkeys should not become separate entries");
+        assert_eq!(keys[0]["fingerprint"], "0123456789ABCDEF0123456789ABCDEF01234567");
+        assert_eq!(keys[0]["uids"][0], "Example Repo <repo@example.com>");
+        assert_eq!(keys[0]["expired"], false);
+        assert_eq!(keys[1]["expired"], true);
+        assert_eq!(keys[1]["expires"], 1400000000);
+    }
+
+    #[test]
+    fn test_deb822_stanza_round_trip() {
+        let strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let stanza = build_deb822_stanza(
+            &strings(&["deb"]),
+            &strings(&["https://repo.example.com/debian"]),
+            &strings(&["bookworm"]),
+            &strings(&["main", "contrib"]),
+            Some("/etc/apt/keyrings/example.gpg"),
+            false,
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!("gantry_test_{}.sources", std::process::id()));
+        fs::write(&path, &stanza).unwrap();
+        let repos = parse_sources_file(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].types, "deb");
+        assert_eq!(repos[0].uris, "https://repo.example.com/debian");
+        assert_eq!(repos[0].suites, "bookworm");
+        assert_eq!(repos[0].components, "main contrib");
+        assert!(!repos[0].enabled);
+    }
+
+    #[test]
+    fn test_deb822_stanza_rejects_invalid_fields() {
+        let s = |v: &str| vec![v.to_string()];
+        assert!(build_deb822_stanza(&s("deb"), &s("deb https://x"), &s("stable"), &s("main"), None, true).is_err());
+        assert!(build_deb822_stanza(&s("deb"), &s("[arch=amd64]"), &s("stable"), &s("main"), None, true).is_err());
+        assert!(build_deb822_stanza(&s("rpm"), &s("https://x"), &s("stable"), &s("main"), None, true).is_err());
+        assert!(build_deb822_stanza(&s("deb"), &s("https://x"), &s("stable"), &[], None, true).is_err());
+        assert!(build_deb822_stanza(&s("deb"), &s("https://x"), &s("./"), &[], None, true).is_ok());
+        assert!(build_deb822_stanza(&s("deb"), &s("https://x\nEnabled:"), &s("stable"), &s("main"), None, true).is_err());
+    }
+
+    #[test]
+    fn test_parse_ppa_name() {
+        assert_eq!(
+            parse_ppa_name("ppa:deadsnakes/ppa").unwrap(),
+            ("deadsnakes".to_string(), "ppa".to_string())
+        );
+        assert_eq!(
+            parse_ppa_name("ppa:graphics-drivers").unwrap(),
+            ("graphics-drivers".to_string(), "ppa".to_string())
+        );
+        assert!(parse_ppa_name("ppa:user/pro ject").is_err());
+        assert!(parse_ppa_name("ppa:../etc").is_err());
+        assert!(parse_ppa_name("deb http://x stable").is_err());
+    }
+
+    #[test]
+    fn test_ubuntu_codename() {
+        let ubuntu = "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\nVERSION_CODENAME=noble\nUBUNTU_CODENAME=noble\n";
+        assert_eq!(ubuntu_codename(ubuntu).as_deref(), Some("noble"));
+        let mint = "ID=linuxmint\nID_LIKE=\"ubuntu debian\"\nVERSION_CODENAME=wilma\nUBUNTU_CODENAME=noble\n";
+        assert_eq!(ubuntu_codename(mint).as_deref(), Some("noble"), "derivatives use the base release");
+        let debian = "ID=debian\nVERSION_CODENAME=bookworm\n";
+        assert_eq!(ubuntu_codename(debian), None);
+    }
+
+    #[test]
+    fn test_parse_repo_spec_and_release_dir() {
+        let spec = parse_repo_spec("deb [arch=amd64] https://repo.example.com/debian/ bookworm main contrib").unwrap();
+        assert_eq!(spec.uris, vec!["https://repo.example.com/debian/"]);
+        assert_eq!(spec.suites, vec!["bookworm"]);
+        assert_eq!(spec.components, vec!["main", "contrib"]);
+        assert_eq!(release_dir(&spec.uris[0], &spec.suites[0]), "https://repo.example.com/debian/dists/bookworm");
+
+        let stanza = "Types: deb\nURIs: https://a.example https://b.example\nSuites: ./\n";
+        let spec = parse_repo_spec(stanza).unwrap();
+        assert_eq!(spec.uris.len(), 2);
+        assert!(spec.components.is_empty());
+        assert_eq!(release_dir("https://a.example", "./"), "https://a.example");
+        assert_eq!(release_dir("https://a.example/", "stable/"), "https://a.example/stable");
+
+        assert!(parse_repo_spec("https://example.com stable main").is_err());
+    }
+
+    #[test]
+    fn test_validate_apt_repo_syntax_errors() {
+        let report = validate_apt_repo("deb ftp:/broken stable main".to_string()).unwrap();
+        assert_eq!(report["valid"], json!(false));
+        assert!(report["syntax_error"].as_str().is_some());
+
+        let report = validate_apt_repo("deb https://example.com stable".to_string()).unwrap();
+        assert_eq!(report["valid"], json!(false), "components are required for dists/ suites");
+    }
+
+    #[test]
+    fn test_prune_backups_per_file() {
+        let entry = |id: &str, file: &str| RepoBackup {
+            id: id.to_string(),
+            timestamp: String::new(),
+            action: "disable".to_string(),
+            file_path: file.to_string(),
+            repo_id: String::new(),
+        };
+        let journal = vec![
+            entry("1", "/etc/apt/sources.list"),
+            entry("2", "/etc/apt/sources.list.d/a.list"),
+            entry("3", "/etc/apt/sources.list"),
+            entry("4", "/etc/apt/sources.list"),
+        ];
+        let (kept, dropped) = prune_backups(journal, 2);
+        let ids = |v: &[RepoBackup]| v.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&kept), vec!["2", "3", "4"]);
+        assert_eq!(ids(&dropped), vec!["1"], "only the oldest backup of the busy file is dropped");
+    }
+
+    #[test]
+    fn test_edit_desktop_entry_preserves_other_keys() {
+        let original = "[Desktop Entry]\n\
+                        Type=Application\n\
+                        Name=Old Name\n\
+                        Name[fr]=Ancien nom\n\
+                        Exec=/usr/bin/old --flag\n\
+                        Icon=old-icon\n\
+                        Comment=Keeps running in the tray\n\
+                        OnlyShowIn=GNOME;XFCE;\n\
+                        X-Custom-Key=value\n\
+                        \n\
+                        [Desktop Action quit]\n\
+                        Name=Quit\n\
+                        Exec=/usr/bin/old --quit\n";
+        let updates = startup_app_updates("New Name", "/usr/bin/new", None, None, None, None).unwrap();
+        let edited = update_desktop_entry(original, &updates);
+
+        let expected = original
+            .replacen("Name=Old Name", "Name=New Name", 1)
+            .replacen("Exec=/usr/bin/old --flag", "Exec=/usr/bin/new", 1);
+        assert_eq!(edited, expected, "only Name and Exec of the main group should change");
+    }
+
+    #[test]
+    fn test_startup_app_optional_fields_round_trip() {
+        let base = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nComment=Old\n\n[Desktop Action x]\nName=X\n";
+        let updates = startup_app_updates(
+            "App",
+            "app",
+            Some(15),
+            Some(vec!["GNOME".to_string(), " KDE ".to_string()]),
+            Some(String::new()),
+            Some(true),
+        )
+        .unwrap();
+        let edited = update_desktop_entry(base, &updates);
+        let fields = parse_desktop_entry(&edited);
+
+        assert_eq!(fields.get("X-GNOME-Autostart-Delay").map(String::as_str), Some("15"));
+        assert_eq!(fields.get("OnlyShowIn").map(String::as_str), Some("GNOME;KDE;"));
+        assert_eq!(fields.get("Terminal").map(String::as_str), Some("true"));
+        assert!(!fields.contains_key("Comment"), "an empty comment removes the key");
+        assert!(
+            edited.find("Terminal=").unwrap() < edited.find("[Desktop Action x]").unwrap(),
+            "new keys belong to the main group"
+        );
+
+        assert!(startup_app_updates("App", "app\nHidden=true", None, None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_autostart_file_rejects_paths() {
+        assert!(resolve_autostart_file("../../.bashrc").is_err());
+        assert!(resolve_autostart_file("/etc/passwd").is_err());
+        assert!(resolve_autostart_file("").is_err());
+        assert!(resolve_autostart_file("gantry-test-missing-entry.desktop").is_err());
+    }
+
+    #[test]
+    fn test_parse_default_target_services() {
+        let sample = "default.target\n  syncthing.service\n  basic.target\n    paths.target\n    ssh-agent.service\n  syncthing.service\n";
+        assert_eq!(parse_default_target_services(sample), vec!["ssh-agent.service", "syncthing.service"]);
+    }
+
+    #[test]
+    fn test_parse_systemctl_cat() {
+        let sample = "# /usr/lib/systemd/user/syncthing.service\n\
+                      [Unit]\n\
+                      Description=Syncthing - Open Source Continuous File Synchronization\n\
+                      [Service]\n\
+                      ExecStart=/usr/bin/syncthing serve --no-browser\n\
+                      [Install]\n\
+                      WantedBy=default.target\n\
+                      \n\
+                      # /home/u/.config/systemd/user/syncthing.service.d/override.conf\n\
+                      [Service]\n\
+                      ExecStart=\n\
+                      ExecStart=/usr/bin/syncthing serve --no-browser --no-restart\n\
+                      \n\
+                      # /usr/lib/systemd/user/pipewire.service\n\
+                      [Unit]\n\
+                      Description=PipeWire Multimedia Service\n\
+                      [Install]\n\
+                      Also=pipewire.socket\n";
+        let units = parse_systemctl_cat(sample);
+        let syncthing = &units["syncthing.service"];
+        assert_eq!(syncthing.exec_start.as_deref(), Some("/usr/bin/syncthing serve --no-browser --no-restart"));
+        assert_eq!(syncthing.wanted_by, vec!["default.target"]);
+        assert!(units["pipewire.service"].wanted_by.is_empty());
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        let args = parse_exec(r#""/opt/My App/run" --name="a b" %U"#).unwrap();
+        assert_eq!(args, vec!["/opt/My App/run", "--name=a b", "%U"]);
+
+        // A literal backslash inside quotes is escaped twice in the file.
+        let args = parse_exec(r#"sh -c "echo \\$HOME \\\\ \\"hi\\"""#).unwrap();
+        assert_eq!(args, vec!["sh", "-c", r#"echo $HOME \ "hi""#]);
+
+        assert_eq!(parse_exec(r"env\sVAR=1").unwrap(), vec!["env", "VAR=1"]);
+        assert!(parse_exec(r#"app "unterminated"#).is_err());
+        assert!(parse_exec("   ").is_err());
+    }
+
+    #[test]
+    fn test_serialize_exec_round_trip() {
+        let cases: Vec<Vec<String>> = vec![
+            vec!["/usr/bin/app".into(), "%U".into()],
+            vec!["/opt/My App/run".into(), "--flag".into()],
+            vec!["sh".into(), "-c".into(), r#"echo "$HOME" \ `date`; exit"#.into()],
+            vec!["app".into(), "".into()],
+        ];
+        for args in cases {
+            let exec = serialize_exec(&args);
+            assert_eq!(parse_exec(&exec).unwrap(), args, "round trip of {}", exec);
+        }
+        assert_eq!(serialize_exec(&["/opt/My App/run".to_string(), "%f".to_string()]), r#""/opt/My App/run" %f"#);
+    }
+
+    #[test]
+    fn test_desktop_exec_value_keeps_valid_input() {
+        assert_eq!(desktop_exec_value("  firefox %u ", None).unwrap(), "firefox %u");
+        assert_eq!(desktop_exec_value(r#""/opt/a b/run" -x"#, None).unwrap(), r#""/opt/a b/run" -x"#);
+        let argv = vec!["/opt/a b/run".to_string()];
+        assert_eq!(desktop_exec_value("ignored", Some(argv)).unwrap(), r#""/opt/a b/run""#);
+    }
+
+    #[test]
+    fn test_collect_desktop_files_ids() {
+        let root = std::env::temp_dir().join(format!("gantry_apps_{}", std::process::id()));
+        fs::create_dir_all(root.join("kde4")).unwrap();
+        fs::write(root.join("firefox.desktop"), "").unwrap();
+        fs::write(root.join("kde4").join("kate.desktop"), "").unwrap();
+        fs::write(root.join("mimeinfo.cache"), "").unwrap();
+
+        let mut files = Vec::new();
+        collect_desktop_files(&root, "", &mut files);
+        let _ = fs::remove_dir_all(&root);
+
+        let mut ids: Vec<String> = files.into_iter().map(|(id, _)| id).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["firefox.desktop", "kde4-kate.desktop"]);
+    }
+
+    #[test]
+    fn test_login_item_only_removes_its_own_entry() {
+        let dir = std::env::temp_dir().join(format!("gantry_login_{}", std::process::id()));
+        let path = register_login_item_in(&dir, "/opt/Gantry App/gantry").unwrap();
+        let fields = parse_desktop_entry(&fs::read_to_string(&path).unwrap());
+        assert_eq!(fields.get("Exec").map(String::as_str), Some("\"/opt/Gantry App/gantry\""));
+        assert!(register_login_item_in(&dir, "/usr/bin/gantry").is_ok(), "re-registering updates the entry");
+        assert!(unregister_login_item_in(&dir).unwrap());
+        assert!(!path.exists());
+
+        fs::write(&path, "[Desktop Entry]\nType=Application\nName=Gantry\nExec=gantry --debug\n").unwrap();
+        assert!(register_login_item_in(&dir, "/usr/bin/gantry").is_err());
+        let removed = unregister_login_item_in(&dir).unwrap();
+        let kept = path.exists();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(!removed && kept, "a user entry with the same name stays");
     }
 }
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::any::Any;
+use std::fs;
+use std::panic::Location;
+use std::path::PathBuf;
+
+const CRASH_FILE: &str = "last_crash.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashRecord {
+    pub ts: String,
+    pub version: String,
+    pub thread: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+}
+
+fn crash_file_path() -> Option<PathBuf> {
+    super::appdata::config_root().map(|root| root.join(CRASH_FILE))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+// Takes the parts of the hook's argument it needs, as that type was renamed
+// in later Rust releases.
+fn crash_record(payload: &(dyn Any + Send), location: Option<&Location<'_>>) -> CrashRecord {
+    CrashRecord {
+        ts: chrono::Local::now().to_rfc3339(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+        message: panic_message(payload),
+        location: location.map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+    }
+}
+
+fn save_record(record: &CrashRecord) -> Result<(), String> {
+    let path = crash_file_path().ok_or_else(|| "Could not determine home directory".to_string())?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let record = crash_record(info.payload(), info.location());
+        let location = record.location.as_deref().unwrap_or("unknown location");
+        let _ = super::logging::log(
+            "error",
+            "panic",
+            &format!("Thread '{}' panicked at {}: {}\n{}", record.thread, location, record.message, record.backtrace),
+        );
+        let _ = save_record(&record);
+        default_hook(info);
+    }));
+}
+
+#[tauri::command]
+pub fn get_last_crash() -> Result<serde_json::Value, String> {
+    let Some(path) = crash_file_path().filter(|p| p.exists()) else { return Ok(serde_json::Value::Null) };
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    match serde_json::from_str::<CrashRecord>(&content) {
+        Ok(record) => Ok(json!(record)),
+        Err(_) => Ok(serde_json::Value::Null),
+    }
+}
+
+#[tauri::command]
+pub fn clear_last_crash() -> Result<serde_json::Value, String> {
+    if let Some(path) = crash_file_path().filter(|p| p.exists()) {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(json!({"success": true}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_record_from_payload() {
+        let record = std::thread::Builder::new()
+            .name("crash-test".into())
+            .spawn(|| crash_record(&format!("sensor {} went away", 3), Some(Location::caller())))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(record.message, "sensor 3 went away");
+        assert_eq!(record.thread, "crash-test");
+        assert!(record.location.unwrap().contains("crash.rs"));
+        assert_eq!(crash_record(&"static message", None).message, "static message");
+        assert_eq!(crash_record(&42, None).message, "Box<dyn Any>");
+    }
+}
@@ -0,0 +1,344 @@
+use serde::Serialize;
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CronJob {
+    pub id: usize,
+    pub schedule: String,
+    pub command: String,
+    pub comment: Option<String>,
+    pub enabled: bool,
+}
+
+const CRON_KEYWORDS: [&str; 8] = [
+    "@reboot", "@yearly", "@annually", "@monthly", "@weekly", "@daily", "@midnight", "@hourly",
+];
+
+const MONTH_NAMES: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+fn cron_value(value: &str, min: u32, max: u32, names: &[&str]) -> Option<u32> {
+    let parsed = value.parse::<u32>().ok().or_else(|| {
+        let lower = value.to_ascii_lowercase();
+        names.iter().position(|n| *n == lower).map(|i| i as u32 + min)
+    })?;
+    (min..=max).contains(&parsed).then_some(parsed)
+}
+
+fn is_valid_cron_field(field: &str, min: u32, max: u32, names: &[&str]) -> bool {
+    field.split(',').all(|part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (part, None),
+        };
+        if step.is_some_and(|s| !matches!(s.parse::<u32>(), Ok(n) if n > 0)) {
+            return false;
+        }
+        if range == "*" {
+            return true;
+        }
+        match range.split_once('-') {
+            Some((start, end)) => match (cron_value(start, min, max, names), cron_value(end, min, max, names)) {
+                (Some(start), Some(end)) => start <= end,
+                _ => false,
+            },
+            None => cron_value(range, min, max, names).is_some(),
+        }
+    })
+}
+
+fn validate_schedule(schedule: &str) -> Result<(), String> {
+    let schedule = schedule.trim();
+    if schedule.starts_with('@') {
+        return if CRON_KEYWORDS.contains(&schedule) {
+            Ok(())
+        } else {
+            Err(format!("Unknown schedule keyword: {}", schedule))
+        };
+    }
+
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err("A schedule needs five fields: minute hour day-of-month month day-of-week".to_string());
+    }
+    // Day of week accepts both 0 and 7 for Sunday.
+    let limits: [(&str, u32, u32, &[&str]); 5] = [
+        ("minute", 0, 59, &[]),
+        ("hour", 0, 23, &[]),
+        ("day of month", 1, 31, &[]),
+        ("month", 1, 12, &MONTH_NAMES),
+        ("day of week", 0, 7, &DAY_NAMES),
+    ];
+    for (field, (label, min, max, names)) in fields.iter().zip(limits) {
+        if !is_valid_cron_field(field, min, max, names) {
+            return Err(format!("Invalid {} field: {}", label, field));
+        }
+    }
+    Ok(())
+}
+
+fn parse_cron_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let field_count = if line.starts_with('@') { 1 } else { 5 };
+
+    let mut rest = line;
+    let mut fields = Vec::new();
+    for _ in 0..field_count {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    let schedule = fields.join(" ");
+    if rest.is_empty() || validate_schedule(&schedule).is_err() {
+        return None;
+    }
+    Some((schedule, rest.to_string()))
+}
+
+fn parse_job_line(line: &str) -> Option<(String, String, bool)> {
+    let trimmed = line.trim();
+    match trimmed.strip_prefix('#') {
+        Some(uncommented) => parse_cron_line(uncommented).map(|(s, c)| (s, c, false)),
+        None => parse_cron_line(trimmed).map(|(s, c)| (s, c, true)),
+    }
+}
+
+fn comment_text(line: &str) -> Option<String> {
+    let text = line.trim().strip_prefix('#')?.trim();
+    if text.is_empty() || parse_job_line(line).is_some() {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+fn parse_crontab(content: &str) -> Vec<CronJob> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let (schedule, command, enabled) = parse_job_line(line)?;
+            let comment = idx.checked_sub(1).and_then(|prev| comment_text(lines[prev]));
+            Some(CronJob { id: idx, schedule, command, comment, enabled })
+        })
+        .collect()
+}
+
+fn job_line(schedule: &str, command: &str, enabled: bool) -> Result<String, String> {
+    validate_schedule(schedule)?;
+    let command = command.trim();
+    if command.is_empty() {
+        return Err("Command cannot be empty".to_string());
+    }
+    if command.contains(['\n', '\r']) {
+        return Err("Command cannot contain line breaks".to_string());
+    }
+    let line = format!("{} {}", schedule.split_whitespace().collect::<Vec<_>>().join(" "), command);
+    Ok(if enabled { line } else { format!("# {}", line) })
+}
+
+fn comment_line(comment: &str) -> Result<String, String> {
+    if comment.contains(['\n', '\r']) {
+        return Err("Comment cannot contain line breaks".to_string());
+    }
+    Ok(format!("# {}", comment.trim()))
+}
+
+fn job_at(lines: &[String], id: usize) -> Result<CronJob, String> {
+    let content = lines.join("\n");
+    parse_crontab(&content)
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| "Cron job not found; the crontab may have changed".to_string())
+}
+
+fn crontab_lines(content: &str) -> Vec<String> {
+    content.lines().map(|l| l.to_string()).collect()
+}
+
+fn join_crontab(lines: &[String]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+fn crontab_add(content: &str, schedule: &str, command: &str, comment: Option<&str>) -> Result<String, String> {
+    let mut lines = crontab_lines(content);
+    if let Some(comment) = comment.filter(|c| !c.trim().is_empty()) {
+        lines.push(comment_line(comment)?);
+    }
+    lines.push(job_line(schedule, command, true)?);
+    Ok(join_crontab(&lines))
+}
+
+fn crontab_edit(content: &str, id: usize, schedule: &str, command: &str, comment: Option<&str>) -> Result<String, String> {
+    let mut lines = crontab_lines(content);
+    let job = job_at(&lines, id)?;
+    lines[id] = job_line(schedule, command, job.enabled)?;
+
+    match (comment.map(str::trim), job.comment.is_some()) {
+        (Some(""), true) => {
+            lines.remove(id - 1);
+        }
+        (Some(""), false) | (None, _) => {}
+        (Some(text), true) => lines[id - 1] = comment_line(text)?,
+        (Some(text), false) => lines.insert(id, comment_line(text)?),
+    }
+    Ok(join_crontab(&lines))
+}
+
+fn crontab_delete(content: &str, id: usize) -> Result<String, String> {
+    let mut lines = crontab_lines(content);
+    let job = job_at(&lines, id)?;
+    lines.remove(id);
+    if job.comment.is_some() {
+        lines.remove(id - 1);
+    }
+    Ok(join_crontab(&lines))
+}
+
+fn crontab_toggle(content: &str, id: usize, enabled: bool) -> Result<String, String> {
+    let mut lines = crontab_lines(content);
+    let job = job_at(&lines, id)?;
+    lines[id] = job_line(&job.schedule, &job.command, enabled)?;
+    Ok(join_crontab(&lines))
+}
+
+fn read_crontab() -> Result<String, String> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no crontab for") {
+        Ok(String::new())
+    } else {
+        Err(format!("Failed to read crontab: {}", stderr.trim()))
+    }
+}
+
+fn write_crontab(content: &str) -> Result<(), String> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+    let written = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(content.as_bytes()));
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        written.map_err(|e| format!("Failed to save crontab: {}", e))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Failed to save crontab: {}", stderr.trim()))
+    }
+}
+
+#[tauri::command]
+pub fn list_cron_jobs() -> Result<serde_json::Value, String> {
+    Ok(json!(parse_crontab(&read_crontab()?)))
+}
+
+#[tauri::command]
+pub fn add_cron_job(schedule: String, command: String, comment: Option<String>) -> Result<serde_json::Value, String> {
+    write_crontab(&crontab_add(&read_crontab()?, &schedule, &command, comment.as_deref())?)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn edit_cron_job(
+    id: usize,
+    schedule: String,
+    command: String,
+    comment: Option<String>,
+) -> Result<serde_json::Value, String> {
+    write_crontab(&crontab_edit(&read_crontab()?, id, &schedule, &command, comment.as_deref())?)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn delete_cron_job(id: usize) -> Result<serde_json::Value, String> {
+    write_crontab(&crontab_delete(&read_crontab()?, id)?)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn toggle_cron_job(id: usize, enabled: bool) -> Result<serde_json::Value, String> {
+    write_crontab(&crontab_toggle(&read_crontab()?, id, enabled)?)?;
+    Ok(json!({"success": true}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# m h  dom mon dow   command\n\
+                          SHELL=/bin/bash\n\
+                          MAILTO=\"\"\n\
+                          \n\
+                          # Nightly backup\n\
+                          30 2 * * 1-5 /usr/local/bin/backup.sh --quiet\n\
+                          #@reboot  /usr/bin/syncthing serve\n\
+                          */15 * * jan-mar sun  echo   spaced   args\n";
+
+    #[test]
+    fn test_validate_schedule() {
+        assert!(validate_schedule("*/5 * * * *").is_ok());
+        assert!(validate_schedule("0 9-17/2 1,15 jan-jun mon-fri").is_ok());
+        assert!(validate_schedule("0 0 * * 7").is_ok());
+        assert!(validate_schedule("@daily").is_ok());
+        assert!(validate_schedule("60 * * * *").is_err());
+        assert!(validate_schedule("* * * *").is_err());
+        assert!(validate_schedule("*/0 * * * *").is_err());
+        assert!(validate_schedule("5-1 * * * *").is_err());
+        assert!(validate_schedule("@sometimes").is_err());
+    }
+
+    #[test]
+    fn test_parse_crontab() {
+        let jobs = parse_crontab(SAMPLE);
+        assert_eq!(jobs.len(), 3, "headers and environment lines are not jobs");
+
+        assert_eq!(jobs[0].id, 5);
+        assert_eq!(jobs[0].schedule, "30 2 * * 1-5");
+        assert_eq!(jobs[0].command, "/usr/local/bin/backup.sh --quiet");
+        assert_eq!(jobs[0].comment.as_deref(), Some("Nightly backup"));
+        assert!(jobs[0].enabled);
+
+        assert_eq!(jobs[1].schedule, "@reboot");
+        assert!(!jobs[1].enabled);
+        assert_eq!(jobs[1].comment, None, "a disabled job is not a comment");
+
+        assert_eq!(jobs[2].command, "echo   spaced   args");
+    }
+
+    #[test]
+    fn test_crontab_edits_preserve_other_lines() {
+        let toggled = crontab_toggle(SAMPLE, 6, true).unwrap();
+        assert!(toggled.contains("\n@reboot /usr/bin/syncthing serve\n"));
+        assert!(toggled.contains("SHELL=/bin/bash\nMAILTO=\"\"\n"));
+
+        let edited = crontab_edit(SAMPLE, 5, "0 3 * * *", "/usr/local/bin/backup.sh", Some("Backup at 3")).unwrap();
+        assert!(edited.contains("# Backup at 3\n0 3 * * * /usr/local/bin/backup.sh\n"));
+        assert_eq!(edited.lines().count(), SAMPLE.lines().count());
+
+        let deleted = crontab_delete(SAMPLE, 5).unwrap();
+        assert!(!deleted.contains("Nightly backup"), "the job's comment goes with it");
+        assert!(deleted.starts_with("# m h  dom mon dow   command\nSHELL=/bin/bash\n"));
+
+        let added = crontab_add("", "@hourly", "date", Some("Clock")).unwrap();
+        assert_eq!(added, "# Clock\n@hourly date\n");
+
+        assert!(crontab_toggle(SAMPLE, 1, false).is_err(), "environment lines are not jobs");
+        assert!(crontab_add(SAMPLE, "* * * * *", "a\nb", None).is_err());
+    }
+}
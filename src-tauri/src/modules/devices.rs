@@ -1,6 +1,17 @@
 use serde_json::json;
 use std::process::Command;
 use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::Emitter;
+use super::error::GantryError;
+#[cfg(target_os = "linux")]
+use super::util::read_sysfs_string;
+
+static INPUT_TEST: OnceLock<Mutex<Option<Arc<AtomicBool>>>> = OnceLock::new();
+
+const INPUT_TEST_TIMEOUT_SECS: u64 = 60;
+const INPUT_POLL_MS: i32 = 250;
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
@@ -13,8 +24,6 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
     let mut stepping = String::new();
     let mut cache_size = String::new();
     let mut flags_str = String::new();
-    let mut physical_ids = std::collections::HashSet::new();
-    let mut core_ids = std::collections::HashSet::new();
     let mut thread_count = 0u32;
 
     for line in content.lines() {
@@ -32,12 +41,6 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
             "stepping" if stepping.is_empty() => stepping = val.to_string(),
             "cache size" if cache_size.is_empty() => cache_size = val.to_string(),
             "flags" if flags_str.is_empty() => flags_str = val.to_string(),
-            "physical id" => {
-                physical_ids.insert(val.to_string());
-            }
-            "core id" => {
-                core_ids.insert(val.to_string());
-            }
             "processor" => {
                 thread_count += 1;
             }
@@ -45,8 +48,7 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
         }
     }
 
-    let sockets = physical_ids.len().max(1);
-    let cores = core_ids.len().max(1);
+    let topology = super::system::cpu_topology();
 
     let features: Vec<&str> = flags_str
         .split_whitespace()
@@ -59,9 +61,10 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
     Ok(json!({
         "model": model_name,
         "vendor": vendor,
-        "sockets": sockets,
-        "cores": cores,
+        "sockets": topology.sockets,
+        "cores": topology.cores,
         "threads": thread_count,
+        "topology": topology,
         "cache": cache_size,
         "family": cpu_family,
         "stepping": stepping,
@@ -86,7 +89,7 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
     let vendor = sysctl("machdep.cpu.vendor");
     let cpu_family = sysctl("machdep.cpu.family");
     let stepping = sysctl("machdep.cpu.stepping");
-    let cores: u32 = sysctl("hw.physicalcpu").parse().unwrap_or(0);
+    let topology = super::system::cpu_topology();
     let threads: u32 = sysctl("hw.logicalcpu").parse().unwrap_or(0);
     let cache_kb: u64 = sysctl("hw.l2cachesize").parse().unwrap_or(0);
     let cache = if cache_kb > 0 {
@@ -105,9 +108,10 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
     Ok(json!({
         "model": model,
         "vendor": vendor,
-        "sockets": 1,
-        "cores": cores,
+        "sockets": topology.sockets,
+        "cores": topology.cores,
         "threads": threads,
+        "topology": topology,
         "cache": cache,
         "family": cpu_family,
         "stepping": stepping,
@@ -117,35 +121,319 @@ pub fn get_processor_info() -> Result<serde_json::Value, String> {
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn list_devices() -> Result<serde_json::Value, String> {
-    match Command::new("lsblk")
-        .args(["-J", "-o", "NAME,SIZE,TYPE,MOUNTPOINT,MODEL,VENDOR,FSTYPE,SERIAL,ROTA,TRAN"])
-        .output()
-    {
-        Ok(out) if out.status.success() => {
+pub fn list_devices() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("devices")?;
+    let caps = super::capabilities::capabilities();
+    if !caps.has_tool("lsblk") && !caps.readable("/sys/block") {
+        return Err(caps.unsupported("Block devices can't be listed: lsblk isn't installed and /sys/block can't be read"));
+    }
+    let lsblk = caps.has_tool("lsblk").then(|| {
+        Command::new("lsblk")
+            .args(["-J", "-o", "NAME,SIZE,TYPE,MOUNTPOINT,MODEL,VENDOR,FSTYPE,SERIAL,ROTA,RM,TRAN,UUID,PARTUUID,LABEL"])
+            .output()
+    });
+    let reason = match lsblk {
+        Some(Ok(out)) if out.status.success() => {
             let output = String::from_utf8_lossy(&out.stdout);
-            serde_json::from_str::<serde_json::Value>(&output)
-                .map_err(|e| format!("Failed to parse lsblk: {}", e))
+            let mut data = serde_json::from_str::<serde_json::Value>(&output)
+                .map_err(|e| GantryError::ParseError(format!("Failed to parse lsblk: {}", e)))?;
+            data["source"] = json!("lsblk");
+            enrich_block_devices(&mut data);
+            return Ok(data);
+        }
+        Some(Ok(out)) => format!("lsblk failed: {}", GantryError::from_output("lsblk", &out).message()),
+        Some(Err(e)) => format!("lsblk failed: {}", e),
+        None => "lsblk isn't installed".to_string(),
+    };
+    let mut data = list_devices_sysfs();
+    enrich_block_devices(&mut data);
+    super::capabilities::mark_degraded(&mut data, "blockdevices", caps.reason(&format!("{}, so devices were read from /sys/block", reason)));
+    Ok(data)
+}
+
+#[cfg(target_os = "linux")]
+fn mmc_manufacturer(card_type: &str, manfid: u32) -> Option<&'static str> {
+    let name = if card_type == "SD" {
+        match manfid {
+            0x01 => "Panasonic",
+            0x02 => "Toshiba",
+            0x03 => "SanDisk",
+            0x1b => "Samsung",
+            0x1d => "ADATA",
+            0x27 => "Phison",
+            0x28 => "Lexar",
+            0x31 => "Silicon Power",
+            0x41 => "Kingston",
+            0x74 => "Transcend",
+            0x76 => "Patriot",
+            0x82 => "Sony",
+            _ => return None,
+        }
+    } else {
+        match manfid {
+            0x11 => "Toshiba",
+            0x13 | 0xfe => "Micron",
+            0x15 => "Samsung",
+            0x45 => "SanDisk",
+            0x70 => "Kingston",
+            0x90 => "SK Hynix",
+            _ => return None,
+        }
+    };
+    Some(name)
+}
+
+#[cfg(target_os = "linux")]
+fn enrich_block_devices(data: &mut serde_json::Value) {
+    let Some(devices) = data["blockdevices"].as_array_mut() else { return };
+    for dev in devices {
+        let Some(name) = dev["name"].as_str().map(|n| n.to_string()) else { continue };
+        let block_path = std::path::PathBuf::from("/sys/block").join(&name);
+        let is_blank = |v: &serde_json::Value| match v.as_str() {
+            Some(s) => s.trim().is_empty(),
+            None => true,
+        };
+
+        if name.starts_with("mmcblk") {
+            let device_dir = block_path.join("device");
+            let card_type = read_sysfs_string(&device_dir.join("type")).unwrap_or_default();
+            if is_blank(&dev["model"]) {
+                dev["model"] = json!(read_sysfs_string(&device_dir.join("name")));
+            }
+            if is_blank(&dev["vendor"]) {
+                let manfid = read_sysfs_string(&device_dir.join("manfid"))
+                    .and_then(|m| u32::from_str_radix(m.trim_start_matches("0x"), 16).ok());
+                dev["vendor"] = json!(manfid.and_then(|id| mmc_manufacturer(&card_type, id)));
+            }
+            dev["oemid"] = json!(read_sysfs_string(&device_dir.join("oemid")));
+            dev["card_type"] = json!(if card_type.is_empty() { None } else { Some(card_type) });
+            if dev["tran"].is_null() {
+                dev["tran"] = json!("mmc");
+            }
+        } else if name.starts_with("sr") {
+            // The block size drops to zero when the tray is empty.
+            let sectors: u64 = read_sysfs_string(&block_path.join("size"))
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            dev["media_present"] = json!(sectors > 0);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn unescape_mountinfo(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && i + 3 < bytes.len()
+            && bytes[i + 1..i + 4].iter().all(|b| (b'0'..=b'7').contains(b))
+        {
+            let code = bytes[i + 1..i + 4].iter().fold(0u32, |acc, b| acc * 8 + (b - b'0') as u32);
+            out.push(code as u8);
+            i += 4;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn parse_mountinfo(content: &str) -> std::collections::HashMap<String, (String, String)> {
+    let mut mounts = std::collections::HashMap::new();
+    for line in content.lines() {
+        let Some((left, right)) = line.split_once(" - ") else { continue };
+        let left_parts: Vec<&str> = left.split_whitespace().collect();
+        let right_parts: Vec<&str> = right.split_whitespace().collect();
+        if left_parts.len() < 5 || right_parts.len() < 2 {
+            continue;
+        }
+        let mountpoint = unescape_mountinfo(left_parts[4]);
+        let fstype = right_parts[0].to_string();
+        let source = unescape_mountinfo(right_parts[1]);
+        if source.starts_with("/dev/") {
+            mounts.entry(source).or_insert((mountpoint, fstype));
+        }
+    }
+    mounts
+}
+
+#[cfg(target_os = "linux")]
+fn sysfs_transport(block_path: &std::path::Path, name: &str) -> Option<String> {
+    if name.starts_with("nvme") {
+        return Some("nvme".to_string());
+    }
+    if name.starts_with("mmcblk") {
+        return Some("mmc".to_string());
+    }
+    let real = fs::canonicalize(block_path).ok()?.to_string_lossy().to_string();
+    if real.contains("/usb") {
+        Some("usb".to_string())
+    } else if real.contains("/ata") {
+        Some("sata".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn unescape_udev_link(name: &str) -> String {
+    let bytes = name.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1] == b'x' {
+            let hex = std::str::from_utf8(&bytes[i + 2..i + 4]).ok();
+            if let Some(code) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(code);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn read_disk_links(kind: &str) -> std::collections::HashMap<String, String> {
+    let mut links = std::collections::HashMap::new();
+    let Ok(entries) = fs::read_dir(format!("/dev/disk/by-{}", kind)) else { return links };
+    for entry in entries.flatten() {
+        let Ok(target) = fs::canonicalize(entry.path()) else { continue };
+        let Some(dev_name) = target.file_name().map(|n| n.to_string_lossy().to_string()) else { continue };
+        links.insert(dev_name, unescape_udev_link(&entry.file_name().to_string_lossy()));
+    }
+    links
+}
+
+#[cfg(target_os = "linux")]
+struct DiskIds {
+    uuid: std::collections::HashMap<String, String>,
+    partuuid: std::collections::HashMap<String, String>,
+    label: std::collections::HashMap<String, String>,
+}
+
+#[cfg(target_os = "linux")]
+fn sysfs_block_entry(
+    path: &std::path::Path,
+    name: &str,
+    dev_type: &str,
+    mounts: &std::collections::HashMap<String, (String, String)>,
+    ids: &DiskIds,
+) -> serde_json::Value {
+    let sectors: u64 = read_sysfs_string(&path.join("size"))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let mount = mounts.get(&format!("/dev/{}", name));
+
+    json!({
+        "name": name,
+        "size": format_bytes(sectors * 512),
+        "type": dev_type,
+        "mountpoint": mount.map(|(m, _)| m.clone()),
+        "model": null,
+        "vendor": null,
+        "fstype": mount.map(|(_, f)| f.clone()),
+        "serial": null,
+        "rota": null,
+        "rm": null,
+        "tran": null,
+        "uuid": ids.uuid.get(name),
+        "partuuid": ids.partuuid.get(name),
+        "label": ids.label.get(name),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn list_devices_sysfs() -> serde_json::Value {
+    let mounts = fs::read_to_string("/proc/self/mountinfo")
+        .map(|c| parse_mountinfo(&c))
+        .unwrap_or_default();
+    let ids = DiskIds {
+        uuid: read_disk_links("uuid"),
+        partuuid: read_disk_links("partuuid"),
+        label: read_disk_links("label"),
+    };
+
+    let mut block_devices: Vec<serde_json::Value> = Vec::new();
+
+    let mut entries: Vec<_> = fs::read_dir("/sys/block")
+        .map(|d| d.flatten().collect())
+        .unwrap_or_default();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("ram") {
+            continue;
+        }
+        let path = entry.path();
+
+        let dev_type = if name.starts_with("loop") {
+            "loop"
+        } else if name.starts_with("sr") {
+            "rom"
+        } else {
+            "disk"
+        };
+
+        let mut disk = sysfs_block_entry(&path, &name, dev_type, &mounts, &ids);
+        if dev_type == "loop" && disk["size"] == "0B" {
+            continue;
+        }
+
+        let device_dir = path.join("device");
+        disk["model"] = json!(read_sysfs_string(&device_dir.join("model")));
+        disk["vendor"] = json!(read_sysfs_string(&device_dir.join("vendor")));
+        disk["serial"] = json!(read_sysfs_string(&device_dir.join("serial")));
+        disk["rota"] = json!(read_sysfs_string(&path.join("queue").join("rotational")).map(|v| v == "1"));
+        disk["rm"] = json!(read_sysfs_string(&path.join("removable")).map(|v| v == "1"));
+        disk["tran"] = json!(sysfs_transport(&path, &name));
+
+        let mut children: Vec<(String, serde_json::Value)> = Vec::new();
+        if let Ok(subdirs) = fs::read_dir(&path) {
+            for sub in subdirs.flatten() {
+                let sub_path = sub.path();
+                if !sub_path.join("partition").exists() {
+                    continue;
+                }
+                let part_name = sub.file_name().to_string_lossy().to_string();
+                let part = sysfs_block_entry(&sub_path, &part_name, "part", &mounts, &ids);
+                children.push((part_name, part));
+            }
+        }
+        children.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if !children.is_empty() {
+            disk["children"] = json!(children.into_iter().map(|(_, c)| c).collect::<Vec<_>>());
         }
-        Ok(out) => Err(format!("lsblk failed: {}", String::from_utf8_lossy(&out.stderr))),
-        Err(e) => Err(format!("failed to run lsblk: {}", e)),
+
+        block_devices.push(disk);
     }
+
+    json!({"blockdevices": block_devices, "source": "sysfs"})
 }
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn list_devices() -> Result<serde_json::Value, String> {
+pub fn list_devices() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("devices")?;
     let out = Command::new("diskutil")
         .args(["list", "-plist"])
         .output()
-        .map_err(|e| format!("Failed to run diskutil: {}", e))?;
+        .map_err(|e| GantryError::spawn("diskutil", e))?;
 
     if !out.status.success() {
-        return Err(format!("diskutil failed: {}", String::from_utf8_lossy(&out.stderr)));
+        return Err(GantryError::from_output("diskutil", &out));
     }
 
     let val = plist::from_bytes::<plist::Value>(&out.stdout)
-        .map_err(|e| format!("Failed to parse diskutil output: {}", e))?;
+        .map_err(|e| GantryError::ParseError(format!("Failed to parse diskutil output: {}", e)))?;
 
     let dict = val.into_dictionary().ok_or("Unexpected plist format")?;
 
@@ -195,6 +483,9 @@ pub fn list_devices() -> Result<serde_json::Value, String> {
                     .and_then(|v| v.as_string())
                     .unwrap_or("")
                     .to_string();
+                let uuid = p.get("VolumeUUID").and_then(|v| v.as_string());
+                let partuuid = p.get("DiskUUID").and_then(|v| v.as_string());
+                let label = p.get("VolumeName").and_then(|v| v.as_string());
 
                 children.push(json!({
                     "name": part_name,
@@ -207,6 +498,9 @@ pub fn list_devices() -> Result<serde_json::Value, String> {
                     "serial": "",
                     "rota": false,
                     "tran": "",
+                    "uuid": uuid,
+                    "partuuid": partuuid,
+                    "label": label,
                 }));
             }
         }
@@ -222,6 +516,9 @@ pub fn list_devices() -> Result<serde_json::Value, String> {
             "serial": "",
             "rota": false,
             "tran": "",
+            "uuid": null,
+            "partuuid": null,
+            "label": null,
         });
 
         if !children.is_empty() {
@@ -231,10 +528,9 @@ pub fn list_devices() -> Result<serde_json::Value, String> {
         block_devices.push(entry);
     }
 
-    Ok(json!({"blockdevices": block_devices}))
+    Ok(json!({"blockdevices": block_devices, "source": "diskutil"}))
 }
 
-#[cfg(target_os = "macos")]
 fn format_bytes(bytes: u64) -> String {
     const GB: u64 = 1_073_741_824;
     const MB: u64 = 1_048_576;
@@ -263,9 +559,85 @@ fn categorize_usb_device(name: &str) -> String {
     "Other".to_string()
 }
 
+#[cfg(target_os = "linux")]
+fn categorize_usb_class(device_class: &str, interfaces: &[(String, String, String)], name: &str) -> Option<String> {
+    let mut classes: Vec<(&str, &str, &str)> = interfaces
+        .iter()
+        .map(|(c, s, p)| (c.as_str(), s.as_str(), p.as_str()))
+        .collect();
+    if device_class != "00" && device_class != "ef" && !device_class.is_empty() {
+        classes.insert(0, (device_class, "", ""));
+    }
+
+    let mut candidates: Vec<&str> = Vec::new();
+    for (class, subclass, protocol) in classes {
+        let category = match (class.to_lowercase().as_str(), protocol) {
+            ("09", _) => "Hub",
+            ("0e", _) => "Camera",
+            ("08", _) => "Storage",
+            ("03", "01") => "Keyboard",
+            ("03", "02") => "Mouse",
+            ("e0", _) if subclass == "01" && protocol == "01" => "Bluetooth",
+            ("e0", _) => "Wireless",
+            ("01", _) => "Audio",
+            ("07", _) => "Printer",
+            _ => continue,
+        };
+        if !candidates.contains(&category) {
+            candidates.push(category);
+        }
+    }
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let by_name = categorize_usb_device(name);
+    if candidates.len() > 1 && candidates.contains(&by_name.as_str()) {
+        return Some(by_name);
+    }
+
+    let precedence = ["Hub", "Camera", "Storage", "Keyboard", "Mouse", "Bluetooth", "Wireless", "Audio", "Printer"];
+    precedence
+        .iter()
+        .find(|p| candidates.contains(p))
+        .map(|p| p.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn read_usb_sysfs_classes(path: &std::path::Path) -> (String, Vec<(String, String, String)>) {
+    let read = |p: &std::path::Path, file: &str| -> String {
+        fs::read_to_string(p.join(file)).map(|s| s.trim().to_string()).unwrap_or_default()
+    };
+    let device_class = read(path, "bDeviceClass");
+    let sysfs_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    let mut interfaces: Vec<(String, String, String, String)> = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().starts_with(&format!("{}:", sysfs_name)))
+                .map(|e| {
+                    let p = e.path();
+                    (
+                        e.file_name().to_string_lossy().to_string(),
+                        read(&p, "bInterfaceClass"),
+                        read(&p, "bInterfaceSubClass"),
+                        read(&p, "bInterfaceProtocol"),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    interfaces.sort();
+
+    (device_class, interfaces.into_iter().map(|(_, c, s, p)| (c, s, p)).collect())
+}
+
 #[cfg(target_os = "linux")]
 #[tauri::command]
 pub fn list_usb_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let output = match Command::new("lsusb").output() {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
         _ => return Ok(vec![]),
@@ -298,7 +670,14 @@ pub fn list_usb_devices() -> Result<Vec<serde_json::Value>, String> {
             }
         }
 
-        let device_type = categorize_usb_device(&name);
+        let by_class = find_usb_sysfs_device(&bus, &device).and_then(|path| {
+            let (device_class, interfaces) = read_usb_sysfs_classes(&path);
+            categorize_usb_class(&device_class, &interfaces, &name)
+        });
+        let (device_type, classified_by) = match by_class {
+            Some(t) => (t, "class"),
+            None => (categorize_usb_device(&name), "name"),
+        };
 
         devices.push(json!({
             "bus": bus,
@@ -307,6 +686,7 @@ pub fn list_usb_devices() -> Result<Vec<serde_json::Value>, String> {
             "product_id": product_id,
             "name": name,
             "device_type": device_type,
+            "classified_by": classified_by,
         }));
     }
 
@@ -316,6 +696,7 @@ pub fn list_usb_devices() -> Result<Vec<serde_json::Value>, String> {
 #[cfg(target_os = "macos")]
 #[tauri::command]
 pub fn list_usb_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let out = Command::new("system_profiler")
         .args(["SPUSBDataType", "-json"])
         .output();
@@ -344,11 +725,12 @@ fn collect_usb_devices(node: &serde_json::Value, devices: &mut Vec<serde_json::V
 
         devices.push(json!({
             "bus": "",
-            "device": "",
+            "device": node["location_id"].as_str().unwrap_or(""),
             "vendor_id": vendor_id,
             "product_id": product_id,
             "name": name,
             "device_type": device_type,
+            "classified_by": "name",
         }));
     }
 
@@ -359,6 +741,269 @@ fn collect_usb_devices(node: &serde_json::Value, devices: &mut Vec<serde_json::V
     }
 }
 
+#[cfg(target_os = "linux")]
+fn find_usb_sysfs_device(bus: &str, device: &str) -> Option<std::path::PathBuf> {
+    let bus_num: u32 = bus.trim().parse().ok()?;
+    let dev_num: u32 = device.trim().parse().ok()?;
+
+    fs::read_dir("/sys/bus/usb/devices").ok()?.flatten().map(|e| e.path()).find(|path| {
+        let read_num = |file: &str| -> Option<u32> {
+            fs::read_to_string(path.join(file)).ok()?.trim().parse().ok()
+        };
+        read_num("busnum") == Some(bus_num) && read_num("devnum") == Some(dev_num)
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_usb_device_details(bus: String, device: String) -> Result<serde_json::Value, String> {
+    let path = find_usb_sysfs_device(&bus, &device)
+        .ok_or_else(|| format!("USB device {}:{} not found", bus, device))?;
+
+    let read = |file: &str| -> Option<String> {
+        fs::read_to_string(path.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let sysfs_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut interfaces = Vec::new();
+    if let Ok(entries) = fs::read_dir(&path) {
+        let mut iface_paths: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .map(|n| n.to_string_lossy().starts_with(&format!("{}:", sysfs_name)))
+                    .unwrap_or(false)
+            })
+            .collect();
+        iface_paths.sort();
+
+        for iface in iface_paths {
+            let read_iface = |file: &str| -> Option<String> {
+                fs::read_to_string(iface.join(file)).ok().map(|s| s.trim().to_string())
+            };
+            let driver = fs::read_link(iface.join("driver"))
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+            interfaces.push(json!({
+                "number": read_iface("bInterfaceNumber"),
+                "class": read_iface("bInterfaceClass"),
+                "subclass": read_iface("bInterfaceSubClass"),
+                "protocol": read_iface("bInterfaceProtocol"),
+                "driver": driver,
+            }));
+        }
+    }
+
+    Ok(json!({
+        "bus": bus,
+        "device": device,
+        "sysfs_path": path.to_string_lossy(),
+        "vendor_id": read("idVendor"),
+        "product_id": read("idProduct"),
+        "manufacturer": read("manufacturer"),
+        "product": read("product"),
+        "serial": read("serial"),
+        "speed_mbps": read("speed"),
+        "max_power": read("bMaxPower"),
+        "usb_version": read("version"),
+        "device_class": read("bDeviceClass"),
+        "interfaces": interfaces,
+    }))
+}
+
+#[cfg(target_os = "macos")]
+fn find_usb_node<'a>(node: &'a serde_json::Value, location_id: &str) -> Option<&'a serde_json::Value> {
+    if node["location_id"].as_str() == Some(location_id) {
+        return Some(node);
+    }
+    node["_items"]
+        .as_array()?
+        .iter()
+        .find_map(|item| find_usb_node(item, location_id))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_usb_device_details(bus: String, device: String) -> Result<serde_json::Value, String> {
+    let out = Command::new("system_profiler")
+        .args(["SPUSBDataType", "-json"])
+        .output()
+        .map_err(|e| format!("Failed to run system_profiler: {}", e))?;
+
+    let text = String::from_utf8_lossy(&out.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse system_profiler output: {}", e))?;
+
+    let node = parsed["SPUSBDataType"]
+        .as_array()
+        .and_then(|controllers| controllers.iter().find_map(|c| find_usb_node(c, &device)))
+        .ok_or_else(|| format!("USB device {} not found", device))?;
+
+    Ok(json!({
+        "bus": bus,
+        "device": device,
+        "location_id": node["location_id"],
+        "vendor_id": node["vendor_id"],
+        "product_id": node["product_id"],
+        "manufacturer": node["manufacturer"],
+        "product": node["_name"],
+        "serial": node["serial_num"],
+        "speed": node["device_speed"],
+        "current_available": node["bus_power"],
+        "current_required": node["bus_power_used"],
+        "interfaces": [],
+    }))
+}
+
+#[cfg(target_os = "linux")]
+fn is_thunderbolt_device_id(id: &str) -> bool {
+    id.split_once('-').is_some_and(|(domain, route)| {
+        !domain.is_empty()
+            && domain.chars().all(|c| c.is_ascii_digit())
+            && !route.is_empty()
+            && route.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_thunderbolt_devices() -> Result<Vec<serde_json::Value>, String> {
+    let base = std::path::Path::new("/sys/bus/thunderbolt/devices");
+    let Ok(entries) = fs::read_dir(base) else {
+        return Ok(vec![]);
+    };
+
+    let mut devices: Vec<serde_json::Value> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            if !is_thunderbolt_device_id(&id) {
+                return None;
+            }
+            let path = entry.path();
+            let read = |file: &str| read_sysfs_string(&path.join(file));
+            let domain = id.split('-').next().unwrap_or("0");
+            let security = read_sysfs_string(&base.join(format!("domain{}", domain)).join("security"));
+            let authorized = read("authorized");
+
+            Some(json!({
+                "id": id,
+                "name": read("device_name"),
+                "vendor": read("vendor_name"),
+                "vendor_id": read("vendor"),
+                "device_id": read("device"),
+                "unique_id": read("unique_id"),
+                "generation": read("generation").and_then(|g| g.parse::<u32>().ok()),
+                "is_host": id.ends_with("-0"),
+                "authorized": authorized.as_deref().map(|a| a != "0"),
+                "security_level": security,
+                "rx_speed": read("rx_speed"),
+                "rx_lanes": read("rx_lanes").and_then(|l| l.parse::<u32>().ok()),
+                "tx_speed": read("tx_speed"),
+                "tx_lanes": read("tx_lanes").and_then(|l| l.parse::<u32>().ok()),
+            }))
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+    Ok(devices)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn thunderbolt_authorize_command(id: &str) -> Result<Vec<String>, GantryError> {
+    if !is_thunderbolt_device_id(id) {
+        return Err(GantryError::Failed(format!("Invalid Thunderbolt device ID: {}", id)));
+    }
+    let authorized = format!("/sys/bus/thunderbolt/devices/{}/authorized", id);
+    Ok(["sh", "-c", "echo 1 > \"$1\"", "sh", &authorized].map(String::from).to_vec())
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn authorize_thunderbolt_device(id: String) -> Result<serde_json::Value, String> {
+    if !is_thunderbolt_device_id(&id) {
+        return Err(format!("Invalid Thunderbolt device ID: {}", id));
+    }
+    let path = std::path::PathBuf::from("/sys/bus/thunderbolt/devices").join(&id);
+    if !path.exists() {
+        return Err(format!("Thunderbolt device {} not found", id));
+    }
+    if read_sysfs_string(&path.join("authorized")).is_some_and(|a| a != "0") {
+        return Ok(json!({"success": true, "already_authorized": true}));
+    }
+
+    // boltd keeps its own device database and policy, so go through it when present.
+    if let Some(uuid) = read_sysfs_string(&path.join("unique_id")) {
+        if let Ok(out) = Command::new("boltctl").args(["authorize", &uuid]).output() {
+            if out.status.success() {
+                return Ok(json!({"success": true, "already_authorized": false, "method": "boltctl"}));
+            }
+        }
+    }
+
+    super::admin::authorize_thunderbolt(&id, &thunderbolt_authorize_command(&id)?)
+        .map_err(|e| format!("Failed to authorize Thunderbolt device: {}", e.message()))?;
+    Ok(json!({"success": true, "already_authorized": false, "method": "sysfs"}))
+}
+
+#[cfg(target_os = "macos")]
+fn collect_thunderbolt_devices(node: &serde_json::Value, is_host: bool, devices: &mut Vec<serde_json::Value>) {
+    if let Some(name) = node["device_name_key"].as_str() {
+        let port = &node["receptacle_1_tag"];
+        devices.push(json!({
+            "id": node["switch_uid_key"].as_str().unwrap_or(""),
+            "name": name,
+            "vendor": node["vendor_name_key"].as_str(),
+            "vendor_id": node["vendor_id_key"].as_str(),
+            "device_id": node["device_id_key"].as_str(),
+            "unique_id": node["switch_uid_key"].as_str(),
+            "generation": null,
+            "is_host": is_host,
+            "authorized": true,
+            "security_level": null,
+            "rx_speed": port["current_speed_key"].as_str(),
+            "rx_lanes": null,
+            "tx_speed": port["current_speed_key"].as_str(),
+            "tx_lanes": null,
+        }));
+    }
+    if let Some(items) = node["_items"].as_array() {
+        for item in items {
+            collect_thunderbolt_devices(item, false, devices);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_thunderbolt_devices() -> Result<Vec<serde_json::Value>, String> {
+    let out = Command::new("system_profiler")
+        .args(["SPThunderboltDataType", "-json"])
+        .output();
+
+    let Ok(out) = out else { return Ok(vec![]) };
+    let Ok(text) = String::from_utf8(out.stdout) else { return Ok(vec![]) };
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&text) else { return Ok(vec![]) };
+
+    let mut devices = Vec::new();
+    if let Some(buses) = parsed["SPThunderboltDataType"].as_array() {
+        for bus in buses {
+            collect_thunderbolt_devices(bus, true, &mut devices);
+        }
+    }
+    Ok(devices)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn authorize_thunderbolt_device(_id: String) -> Result<serde_json::Value, String> {
+    Err("macOS authorizes Thunderbolt devices itself".to_string())
+}
+
 fn categorize_network_device(name: &str) -> String {
     let lower = name.to_lowercase();
     if lower == "lo" || lower == "lo0" { return "Loopback".to_string(); }
@@ -375,6 +1020,7 @@ fn categorize_network_device(name: &str) -> String {
 #[cfg(target_os = "linux")]
 #[tauri::command]
 pub fn list_network_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let link_output = match Command::new("ip").args(["-d", "link", "show"]).output() {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
         _ => return Ok(vec![]),
@@ -475,6 +1121,7 @@ fn extract_ip_addresses_linux(addr_output: &str, device_name: &str) -> Vec<Strin
 #[cfg(target_os = "macos")]
 #[tauri::command]
 pub fn list_network_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let out = Command::new("ifconfig")
         .arg("-a")
         .output();
@@ -566,6 +1213,7 @@ fn categorize_pci_device(category: &str) -> String {
 #[cfg(target_os = "linux")]
 #[tauri::command]
 pub fn list_pci_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let output = match Command::new("lspci").args(["-mm"]).output() {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
         _ => return Ok(vec![]),
@@ -617,6 +1265,7 @@ fn parse_lspci_mm_line(line: &str) -> Vec<String> {
 #[cfg(target_os = "macos")]
 #[tauri::command]
 pub fn list_pci_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let out = Command::new("system_profiler")
         .args(["SPPCIDataType", "-json"])
         .output();
@@ -661,6 +1310,7 @@ fn categorize_input_device(name: &str, handlers: &str) -> String {
 #[cfg(target_os = "linux")]
 #[tauri::command]
 pub fn list_input_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
     let mut devices = Vec::new();
 
     if let Ok(content) = fs::read_to_string("/proc/bus/input/devices") {
@@ -704,55 +1354,729 @@ pub fn list_input_devices() -> Result<Vec<serde_json::Value>, String> {
     Ok(devices)
 }
 
-#[cfg(target_os = "macos")]
-#[tauri::command]
-pub fn list_input_devices() -> Result<Vec<serde_json::Value>, String> {
-    let out = Command::new("system_profiler")
-        .args(["SPUSBDataType", "-json"])
-        .output();
-
-    let Ok(out) = out else { return Ok(vec![]) };
-    let Ok(text) = String::from_utf8(out.stdout) else { return Ok(vec![]) };
-    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&text) else { return Ok(vec![]) };
-
-    let mut devices = Vec::new();
-    if let Some(controllers) = parsed["SPUSBDataType"].as_array() {
-        for controller in controllers {
-            collect_hid_devices(controller, &mut devices);
+#[cfg(target_os = "linux")]
+fn parse_v4l2_resolutions(output: &str) -> Vec<String> {
+    let mut sizes: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if let Some(size) = line.trim().strip_prefix("Size: Discrete ") {
+            let size = size.trim().to_string();
+            if !sizes.contains(&size) {
+                sizes.push(size);
+            }
         }
     }
-
-    Ok(devices)
+    sizes
 }
 
-#[cfg(target_os = "macos")]
-fn collect_hid_devices(node: &serde_json::Value, devices: &mut Vec<serde_json::Value>) {
-    let name = node["_name"].as_str().unwrap_or("").to_string();
-    if !name.is_empty() {
-        let device_type = categorize_input_device(&name, "");
-        if matches!(device_type.as_str(), "Keyboard" | "Mouse" | "Touchscreen" | "Controller") {
-            devices.push(json!({
-                "name": name,
-                "device_type": device_type,
-                "path": "",
-            }));
-        }
-    }
-
-    if let Some(items) = node["_items"].as_array() {
-        for item in items {
-            collect_hid_devices(item, devices);
-        }
+#[cfg(target_os = "linux")]
+fn v4l2_resolutions(node: &str) -> Option<Vec<String>> {
+    let out = Command::new("v4l2-ctl")
+        .args(["-d", node, "--list-formats-ext"])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
     }
+    Some(parse_v4l2_resolutions(&String::from_utf8_lossy(&out.stdout)))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// VIDIOC_QUERYCAP, VIDIOC_ENUM_FMT and VIDIOC_ENUM_FRAMESIZES from
+// linux/videodev2.h, with the structs they fill laid out to match.
+#[cfg(target_os = "linux")]
+const VIDIOC_QUERYCAP: u64 = 0x8068_5600;
+#[cfg(target_os = "linux")]
+const VIDIOC_ENUM_FMT: u64 = 0xC040_5602;
+#[cfg(target_os = "linux")]
+const VIDIOC_ENUM_FRAMESIZES: u64 = 0xC02C_564A;
 
-    #[cfg(target_os = "linux")]
-    #[test]
-    fn test_get_processor_info() {
+#[cfg(target_os = "linux")]
+const V4L2_CAP_DEVICE_CAPS: u32 = 0x8000_0000;
+#[cfg(target_os = "linux")]
+const V4L2_CAP_VIDEO_CAPTURE: u32 = 0x0000_0001;
+#[cfg(target_os = "linux")]
+const V4L2_CAP_VIDEO_CAPTURE_MPLANE: u32 = 0x0000_1000;
+#[cfg(target_os = "linux")]
+const V4L2_BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
+#[cfg(target_os = "linux")]
+const V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE: u32 = 9;
+#[cfg(target_os = "linux")]
+const V4L2_FRMSIZE_TYPE_DISCRETE: u32 = 1;
+
+#[cfg(target_os = "linux")]
+const V4L2_CAPABILITY_NAMES: &[(u32, &str)] = &[
+    (0x0000_0001, "video_capture"),
+    (0x0000_0002, "video_output"),
+    (0x0000_0004, "video_overlay"),
+    (0x0000_1000, "video_capture_mplane"),
+    (0x0000_2000, "video_output_mplane"),
+    (0x0000_4000, "video_m2m_mplane"),
+    (0x0000_8000, "video_m2m"),
+    (0x0001_0000, "tuner"),
+    (0x0002_0000, "audio"),
+    (0x0004_0000, "radio"),
+    (0x0010_0000, "sdr_capture"),
+    (0x0080_0000, "meta_capture"),
+    (0x0100_0000, "readwrite"),
+    (0x0400_0000, "streaming"),
+    (0x0800_0000, "meta_output"),
+    (0x1000_0000, "touch"),
+];
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct V4l2Capability {
+    driver: [u8; 16],
+    card: [u8; 32],
+    bus_info: [u8; 32],
+    _version: u32,
+    capabilities: u32,
+    device_caps: u32,
+    _reserved: [u32; 3],
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct V4l2FmtDesc {
+    index: u32,
+    kind: u32,
+    _flags: u32,
+    _description: [u8; 32],
+    pixelformat: u32,
+    _mbus_code: u32,
+    _reserved: [u32; 3],
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct V4l2FrmSizeEnum {
+    index: u32,
+    pixel_format: u32,
+    kind: u32,
+    // discrete: width, height; stepwise: min/max/step width, min/max/step height
+    size: [u32; 6],
+    _reserved: [u32; 2],
+}
+
+#[cfg(target_os = "linux")]
+fn v4l2_capability_names(caps: u32) -> Vec<&'static str> {
+    V4L2_CAPABILITY_NAMES
+        .iter()
+        .filter(|(bit, _)| caps & bit != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn v4l2_string(raw: &[u8]) -> String {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    String::from_utf8_lossy(&raw[..end]).trim().to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn open_video_node(node: &str) -> Option<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(node)
+        .ok()
+}
+
+/// Driver, card, bus and the node's own capability flags, or None when the
+/// node can't be opened or isn't a V4L2 device.
+#[cfg(target_os = "linux")]
+fn v4l2_query_capabilities(file: &fs::File) -> Option<(String, String, String, u32)> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut cap: V4l2Capability = unsafe { std::mem::zeroed() };
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), VIDIOC_QUERYCAP as _, &mut cap) };
+    if rc != 0 {
+        return None;
+    }
+    // device_caps describes this node; capabilities covers the whole device.
+    let caps = if cap.capabilities & V4L2_CAP_DEVICE_CAPS != 0 {
+        cap.device_caps
+    } else {
+        cap.capabilities
+    };
+    Some((v4l2_string(&cap.driver), v4l2_string(&cap.card), v4l2_string(&cap.bus_info), caps))
+}
+
+#[cfg(target_os = "linux")]
+fn v4l2_frame_sizes(file: &fs::File, buf_type: u32) -> Vec<String> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = file.as_raw_fd();
+    let mut sizes: Vec<String> = Vec::new();
+    for fmt_index in 0.. {
+        let mut fmt: V4l2FmtDesc = unsafe { std::mem::zeroed() };
+        fmt.index = fmt_index;
+        fmt.kind = buf_type;
+        if unsafe { libc::ioctl(fd, VIDIOC_ENUM_FMT as _, &mut fmt) } != 0 {
+            break;
+        }
+        for size_index in 0.. {
+            let mut frame: V4l2FrmSizeEnum = unsafe { std::mem::zeroed() };
+            frame.index = size_index;
+            frame.pixel_format = fmt.pixelformat;
+            if unsafe { libc::ioctl(fd, VIDIOC_ENUM_FRAMESIZES as _, &mut frame) } != 0 {
+                break;
+            }
+            let size = if frame.kind == V4L2_FRMSIZE_TYPE_DISCRETE {
+                format!("{}x{}", frame.size[0], frame.size[1])
+            } else {
+                format!("{}x{}-{}x{}", frame.size[0], frame.size[3], frame.size[1], frame.size[4])
+            };
+            if !sizes.contains(&size) {
+                sizes.push(size);
+            }
+            // Stepwise and continuous ranges are reported as a single entry.
+            if frame.kind != V4L2_FRMSIZE_TYPE_DISCRETE {
+                break;
+            }
+        }
+    }
+    sizes
+}
+
+#[cfg(target_os = "linux")]
+struct VideoNodeGroup {
+    parent: String,
+    name: String,
+    nodes: Vec<(u32, String)>,
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_video_devices() -> Result<Vec<serde_json::Value>, String> {
+    let Ok(entries) = fs::read_dir("/sys/class/video4linux") else {
+        return Ok(vec![]);
+    };
+
+    // A single camera exposes several /dev/video nodes (capture + metadata),
+    // all sharing the same parent device in sysfs.
+    let mut groups: Vec<VideoNodeGroup> = Vec::new();
+    for entry in entries.flatten() {
+        let node_name = entry.file_name().to_string_lossy().to_string();
+        if !node_name.starts_with("video") {
+            continue;
+        }
+        let path = entry.path();
+        let name = fs::read_to_string(path.join("name"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| node_name.clone());
+        let index: u32 = fs::read_to_string(path.join("index"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0);
+        let parent = fs::canonicalize(path.join("device"))
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| node_name.clone());
+        let dev_node = format!("/dev/{}", node_name);
+
+        match groups.iter_mut().find(|g| g.parent == parent) {
+            Some(group) => group.nodes.push((index, dev_node)),
+            None => groups.push(VideoNodeGroup { parent, name, nodes: vec![(index, dev_node)] }),
+        }
+    }
+
+    let has_v4l2_ctl = Command::new("v4l2-ctl").arg("--version").output().is_ok();
+
+    let mut devices: Vec<serde_json::Value> = groups
+        .into_iter()
+        .map(|VideoNodeGroup { parent, name, mut nodes }| {
+            nodes.sort();
+            let mut capture_node: Option<String> = None;
+            let mut resolutions: Vec<String> = Vec::new();
+            let mut node_info: Vec<serde_json::Value> = Vec::new();
+
+            for (_, node) in &nodes {
+                let file = open_video_node(node);
+                let Some((driver, card, bus_info, caps)) = file.as_ref().and_then(v4l2_query_capabilities) else {
+                    node_info.push(json!({
+                        "path": node,
+                        "driver": null,
+                        "card": null,
+                        "bus_info": null,
+                        "capabilities": [],
+                    }));
+                    continue;
+                };
+                let buf_type = if caps & V4L2_CAP_VIDEO_CAPTURE != 0 {
+                    Some(V4L2_BUF_TYPE_VIDEO_CAPTURE)
+                } else if caps & V4L2_CAP_VIDEO_CAPTURE_MPLANE != 0 {
+                    Some(V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE)
+                } else {
+                    None
+                };
+                if let (None, Some(buf_type), Some(file)) = (&capture_node, buf_type, &file) {
+                    capture_node = Some(node.clone());
+                    resolutions = v4l2_frame_sizes(file, buf_type);
+                }
+                node_info.push(json!({
+                    "path": node,
+                    "driver": driver,
+                    "card": card,
+                    "bus_info": bus_info,
+                    "capabilities": v4l2_capability_names(caps),
+                }));
+            }
+
+            // Nodes we couldn't open (no access to the video group) still get
+            // a best-effort answer from v4l2-ctl when it's installed.
+            if resolutions.is_empty() && has_v4l2_ctl {
+                for (_, node) in &nodes {
+                    if let Some(sizes) = v4l2_resolutions(node) {
+                        if !sizes.is_empty() {
+                            capture_node = Some(node.clone());
+                            resolutions = sizes;
+                            break;
+                        }
+                    }
+                }
+            }
+            if capture_node.is_none() {
+                capture_node = nodes.first().map(|(_, n)| n.clone());
+            }
+
+            json!({
+                "name": name,
+                "bus_path": parent,
+                "nodes": node_info,
+                "capture_node": capture_node,
+                "resolutions": resolutions,
+            })
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a["capture_node"].as_str().cmp(&b["capture_node"].as_str()));
+    Ok(devices)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_video_devices() -> Result<Vec<serde_json::Value>, String> {
+    let out = Command::new("system_profiler")
+        .args(["SPCameraDataType", "-json"])
+        .output();
+
+    let Ok(out) = out else { return Ok(vec![]) };
+    let Ok(text) = String::from_utf8(out.stdout) else { return Ok(vec![]) };
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&text) else { return Ok(vec![]) };
+
+    let devices = parsed["SPCameraDataType"]
+        .as_array()
+        .map(|items| {
+            items.iter().map(|item| json!({
+                "name": item["_name"].as_str().unwrap_or("Camera"),
+                "bus_path": item["spcamera_unique-id"].as_str().unwrap_or(""),
+                "model_id": item["spcamera_model-id"].as_str().unwrap_or(""),
+                "nodes": [],
+                "capture_node": null,
+                "resolutions": [],
+            })).collect()
+        })
+        .unwrap_or_default();
+
+    Ok(devices)
+}
+
+#[cfg(target_os = "linux")]
+fn is_serial_port_name(name: &str) -> bool {
+    ["ttyUSB", "ttyACM", "ttyS"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn find_usb_ancestor(path: &std::path::Path) -> Option<std::path::PathBuf> {
+    path.ancestors()
+        .take_while(|p| p.starts_with("/sys/devices"))
+        .find(|p| p.join("idVendor").exists())
+        .map(|p| p.to_path_buf())
+}
+
+#[cfg(target_os = "linux")]
+fn serial_port_holders(nodes: &[String]) -> std::collections::HashMap<String, Vec<serde_json::Value>> {
+    let mut holders: std::collections::HashMap<String, Vec<serde_json::Value>> = std::collections::HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else { return holders };
+    for entry in entries.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else { continue };
+
+        let mut open_nodes: Vec<String> = fds
+            .flatten()
+            .filter_map(|fd| fs::read_link(fd.path()).ok())
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|p| nodes.contains(p))
+            .collect();
+        open_nodes.sort();
+        open_nodes.dedup();
+        if open_nodes.is_empty() {
+            continue;
+        }
+
+        let name = fs::read_to_string(entry.path().join("comm"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        for node in open_nodes {
+            holders.entry(node).or_default().push(json!({"pid": pid, "name": name}));
+        }
+    }
+    holders
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_serial_devices() -> Result<Vec<serde_json::Value>, String> {
+    let Ok(entries) = fs::read_dir("/sys/class/tty") else {
+        return Ok(vec![]);
+    };
+
+    let mut ports: Vec<(String, std::path::PathBuf, String)> = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_serial_port_name(&name) {
+            continue;
+        }
+        // Virtual consoles and unused legacy ports have no driver bound.
+        let Some(driver) = fs::read_link(entry.path().join("device/driver"))
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        else {
+            continue;
+        };
+        // serial8250 registers every legacy ttyS slot; type 0 means no UART behind it.
+        if driver == "serial8250" && read_sysfs_string(&entry.path().join("type")).as_deref() == Some("0") {
+            continue;
+        }
+        let device_path = fs::canonicalize(entry.path().join("device")).unwrap_or_else(|_| entry.path());
+        ports.push((name, device_path, driver));
+    }
+
+    let nodes: Vec<String> = ports.iter().map(|(name, _, _)| format!("/dev/{}", name)).collect();
+    let holders = serial_port_holders(&nodes);
+
+    let mut devices: Vec<serde_json::Value> = ports
+        .into_iter()
+        .map(|(name, device_path, driver)| {
+            let path = format!("/dev/{}", name);
+            let usb = find_usb_ancestor(&device_path);
+            let read_usb = |file: &str| usb.as_ref().and_then(|u| read_sysfs_string(&u.join(file)));
+            let manufacturer = read_usb("manufacturer");
+            let product = read_usb("product");
+            let description = match (&manufacturer, &product) {
+                (Some(m), Some(p)) => format!("{} {}", m, p),
+                (None, Some(p)) => p.clone(),
+                _ if name.starts_with("ttyS") => "Built-in serial port".to_string(),
+                _ => name.clone(),
+            };
+            let open_by = holders.get(&path).cloned().unwrap_or_default();
+
+            json!({
+                "name": name,
+                "path": path,
+                "driver": driver,
+                "description": description,
+                "vendor_id": read_usb("idVendor"),
+                "product_id": read_usb("idProduct"),
+                "manufacturer": manufacturer,
+                "product": product,
+                "serial": read_usb("serial"),
+                "in_use": !open_by.is_empty(),
+                "open_by": open_by,
+            })
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+    Ok(devices)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_serial_devices() -> Result<Vec<serde_json::Value>, String> {
+    let Ok(entries) = fs::read_dir("/dev") else {
+        return Ok(vec![]);
+    };
+
+    let mut paths: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with("cu."))
+        .map(|n| format!("/dev/{}", n))
+        .collect();
+    paths.sort();
+
+    // lsof -F prints "p<pid>", "c<command>" and "n<file>" records per open file.
+    let mut holders: std::collections::HashMap<String, Vec<serde_json::Value>> = std::collections::HashMap::new();
+    if !paths.is_empty() {
+        if let Ok(out) = Command::new("lsof").arg("-F").arg("pcn").args(&paths).output() {
+            let text = String::from_utf8_lossy(&out.stdout);
+            let mut pid: Option<u32> = None;
+            let mut command = String::new();
+            for line in text.lines() {
+                if let Some(v) = line.strip_prefix('p') {
+                    pid = v.parse().ok();
+                } else if let Some(v) = line.strip_prefix('c') {
+                    command = v.to_string();
+                } else if let (Some(v), Some(pid)) = (line.strip_prefix('n'), pid) {
+                    holders.entry(v.to_string()).or_default().push(json!({"pid": pid, "name": command}));
+                }
+            }
+        }
+    }
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let name = path.trim_start_matches("/dev/").to_string();
+            let open_by = holders.get(&path).cloned().unwrap_or_default();
+            json!({
+                "name": name,
+                "path": path,
+                "driver": null,
+                "description": name.trim_start_matches("cu."),
+                "vendor_id": null,
+                "product_id": null,
+                "manufacturer": null,
+                "product": null,
+                "serial": null,
+                "in_use": !open_by.is_empty(),
+                "open_by": open_by,
+            })
+        })
+        .collect())
+}
+
+fn get_input_test() -> &'static Mutex<Option<Arc<AtomicBool>>> {
+    INPUT_TEST.get_or_init(|| Mutex::new(None))
+}
+
+// A raw `struct input_event`: a timeval (two native longs), then u16 type, u16 code and i32 value.
+#[cfg(target_os = "linux")]
+fn parse_input_event(buf: &[u8]) -> Option<serde_json::Value> {
+    let long = std::mem::size_of::<usize>();
+    if buf.len() < 2 * long + 8 {
+        return None;
+    }
+    let read_long = |offset: usize| -> i64 {
+        if long == 8 {
+            i64::from_ne_bytes(buf[offset..offset + 8].try_into().unwrap())
+        } else {
+            i32::from_ne_bytes(buf[offset..offset + 4].try_into().unwrap()) as i64
+        }
+    };
+    let sec = read_long(0);
+    let usec = read_long(long);
+    let rest = &buf[2 * long..];
+    let event_type = u16::from_ne_bytes([rest[0], rest[1]]);
+    let code = u16::from_ne_bytes([rest[2], rest[3]]);
+    let value = i32::from_ne_bytes([rest[4], rest[5], rest[6], rest[7]]);
+
+    Some(json!({
+        "type": event_type,
+        "code": code,
+        "value": value,
+        "timestamp": sec as f64 + usec as f64 / 1_000_000.0,
+    }))
+}
+
+// Expects a canonical path, so `..` and symlinks can't lead out of /dev/input.
+#[cfg(target_os = "linux")]
+fn is_input_event_node(path: &std::path::Path) -> bool {
+    path.parent() == Some(std::path::Path::new("/dev/input"))
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix("event"))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn start_input_test(app: tauri::AppHandle, path: String) -> Result<serde_json::Value, String> {
+    use std::io::Read;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::io::AsRawFd;
+
+    let path = fs::canonicalize(&path)
+        .ok()
+        .filter(|p| is_input_event_node(p))
+        .ok_or_else(|| format!("Not an input event device: {}", path))?
+        .to_string_lossy()
+        .to_string();
+
+    let mut current = get_input_test().lock().unwrap();
+    if current.is_some() {
+        return Err("An input test is already running".to_string());
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(&path)
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                format!(
+                    "Permission denied opening {}. Add your user to the 'input' group \
+                     (sudo usermod -aG input $USER) and log in again.",
+                    path
+                )
+            } else {
+                format!("Failed to open {}: {}", path, e)
+            }
+        })?;
+
+    let task = super::tasks::register("input-test");
+    let stop = task.stop_flag();
+    *current = Some(stop.clone());
+    drop(current);
+
+    std::thread::spawn(move || {
+        let _task = task;
+        let event_size = 2 * std::mem::size_of::<usize>() + 8;
+        let mut buf = vec![0u8; event_size * 64];
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(INPUT_TEST_TIMEOUT_SECS);
+        let mut reason = "stopped";
+
+        while !stop.load(Ordering::Relaxed) {
+            if std::time::Instant::now() >= deadline {
+                reason = "timeout";
+                break;
+            }
+            let mut pollfd = libc::pollfd { fd: file.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            let ready = unsafe { libc::poll(&mut pollfd, 1, INPUT_POLL_MS) };
+            if ready == 0 {
+                continue;
+            }
+            if ready < 0 {
+                if std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                reason = "disconnected";
+                break;
+            }
+            if pollfd.revents & (libc::POLLERR | libc::POLLHUP | libc::POLLNVAL) != 0 {
+                reason = "disconnected";
+                break;
+            }
+            match file.read(&mut buf) {
+                Ok(0) => {
+                    reason = "disconnected";
+                    break;
+                }
+                Ok(n) => {
+                    for chunk in buf[..n].chunks_exact(event_size) {
+                        if let Some(event) = parse_input_event(chunk) {
+                            let _ = app.emit("input-event", event);
+                        }
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(_) => {
+                    reason = "disconnected";
+                    break;
+                }
+            }
+        }
+
+        let mut current = get_input_test().lock().unwrap();
+        if current.as_ref().is_some_and(|s| Arc::ptr_eq(s, &stop)) {
+            *current = None;
+        }
+        drop(current);
+        let _ = app.emit("input-test-stopped", json!({"path": path, "reason": reason}));
+    });
+
+    Ok(json!({"success": true, "timeout_secs": INPUT_TEST_TIMEOUT_SECS}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn start_input_test(_app: tauri::AppHandle, _path: String) -> Result<serde_json::Value, String> {
+    Err("Live input testing is only supported on Linux".to_string())
+}
+
+#[tauri::command]
+pub fn stop_input_test() -> Result<serde_json::Value, String> {
+    let stopped = match get_input_test().lock().unwrap().take() {
+        Some(stop) => {
+            stop.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    };
+    Ok(json!({"success": true, "stopped": stopped}))
+}
+
+#[cfg(target_os = "macos")]
+fn hid_usage_category(page: u64, usage: u64) -> Option<&'static str> {
+    match (page, usage) {
+        (0x01, 0x02) => Some("Mouse"),
+        (0x01, 0x06) | (0x01, 0x07) => Some("Keyboard"),
+        (0x01, 0x04) | (0x01, 0x05) | (0x01, 0x08) => Some("Controller"),
+        (0x0D, 0x04) => Some("Touchscreen"),
+        (0x0D, 0x05) => Some("Mouse"),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_input_devices() -> Result<Vec<serde_json::Value>, String> {
+    super::settings::require_module("devices")?;
+    let out = Command::new("ioreg")
+        .args(["-rc", "IOHIDDevice", "-a"])
+        .output();
+
+    let Ok(out) = out else { return Ok(vec![]) };
+    let Ok(parsed) = plist::from_bytes::<plist::Value>(&out.stdout) else { return Ok(vec![]) };
+    let Some(entries) = parsed.as_array() else { return Ok(vec![]) };
+
+    let mut devices: Vec<serde_json::Value> = Vec::new();
+    for entry in entries {
+        let Some(d) = entry.as_dictionary() else { continue };
+        let get_str = |key: &str| d.get(key).and_then(|v| v.as_string()).map(|s| s.to_string());
+        let get_num = |key: &str| d.get(key).and_then(|v| v.as_unsigned_integer());
+
+        let Some(name) = get_str("Product").filter(|n| !n.is_empty()) else { continue };
+        let device_type = get_num("PrimaryUsagePage")
+            .zip(get_num("PrimaryUsage"))
+            .and_then(|(page, usage)| hid_usage_category(page, usage))
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| categorize_input_device(&name, ""));
+        if !matches!(device_type.as_str(), "Keyboard" | "Mouse" | "Touchscreen" | "Controller") {
+            continue;
+        }
+
+        // Composite devices register one IOHIDDevice per top-level collection.
+        if devices.iter().any(|e| e["name"] == name.as_str() && e["device_type"] == device_type.as_str()) {
+            continue;
+        }
+
+        devices.push(json!({
+            "name": name,
+            "device_type": device_type,
+            "path": get_num("IORegistryEntryID").map(|id| id.to_string()).unwrap_or_default(),
+            "transport": get_str("Transport"),
+            "vendor_id": get_num("VendorID").map(|v| format!("{:04x}", v)),
+            "battery_percent": get_num("BatteryPercent"),
+        }));
+    }
+
+    Ok(devices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_processor_info() {
         let result = get_processor_info();
         assert!(result.is_ok(), "get_processor_info failed: {:?}", result.err());
         let info = result.unwrap();
@@ -818,6 +2142,38 @@ mod tests {
         assert!(result.is_ok(), "list_devices failed: {:?}", result.err());
         let data = result.unwrap();
         assert!(data["blockdevices"].as_array().is_some(), "should have blockdevices array");
+        if data["source"] == "sysfs" {
+            assert_eq!(data["degraded"], true, "the sysfs fallback should be marked degraded");
+            assert!(data["degraded_reasons"]["blockdevices"].is_string());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_list_devices_sysfs_fallback() {
+        let data = list_devices_sysfs();
+        assert_eq!(data["source"].as_str(), Some("sysfs"));
+        let devices = data["blockdevices"].as_array().expect("should have blockdevices array");
+        for dev in devices {
+            assert!(dev["name"].as_str().is_some(), "block device should have a name");
+            assert!(dev["size"].as_str().is_some(), "block device should have a size");
+            assert!(dev["type"].as_str().is_some(), "block device should have a type");
+            assert!(dev.get("uuid").is_some(), "block device should carry a uuid field");
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_mountinfo() {
+        let sample = "22 1 8:2 / / rw,relatime shared:1 - ext4 /dev/sda2 rw\n\
+                      40 22 8:1 / /boot/efi rw shared:2 - vfat /dev/sda1 rw\n\
+                      41 22 8:17 / /media/My\\040Drive rw - exfat /dev/sdb1 rw\n\
+                      25 22 0:5 / /proc rw - proc proc rw";
+        let mounts = parse_mountinfo(sample);
+        assert_eq!(mounts.len(), 3, "only /dev sources should be kept");
+        assert_eq!(mounts["/dev/sda2"], ("/".to_string(), "ext4".to_string()));
+        assert_eq!(mounts["/dev/sda1"].0, "/boot/efi");
+        assert_eq!(mounts["/dev/sdb1"].0, "/media/My Drive", "octal escapes should be decoded");
     }
 
     #[cfg(target_os = "macos")]
@@ -829,6 +2185,112 @@ mod tests {
         assert!(data["blockdevices"].as_array().is_some(), "should have blockdevices array");
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_usb_device_details_not_found() {
+        let result = get_usb_device_details("999".to_string(), "999".to_string());
+        assert!(result.is_err(), "nonexistent USB device should return an error");
+        let result = get_usb_device_details("abc".to_string(), "1".to_string());
+        assert!(result.is_err(), "non-numeric bus should return an error");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_input_event() {
+        let long = std::mem::size_of::<usize>();
+        let mut buf = vec![0u8; 2 * long + 8];
+        buf[..long].copy_from_slice(&12usize.to_ne_bytes());
+        buf[long..2 * long].copy_from_slice(&500_000usize.to_ne_bytes());
+        buf[2 * long..2 * long + 2].copy_from_slice(&1u16.to_ne_bytes());
+        buf[2 * long + 2..2 * long + 4].copy_from_slice(&30u16.to_ne_bytes());
+        buf[2 * long + 4..].copy_from_slice(&1i32.to_ne_bytes());
+
+        let event = parse_input_event(&buf).expect("event should parse");
+        assert_eq!(event["type"], 1);
+        assert_eq!(event["code"], 30);
+        assert_eq!(event["value"], 1);
+        assert_eq!(event["timestamp"].as_f64(), Some(12.5));
+        assert!(parse_input_event(&buf[..4]).is_none(), "short buffer should not parse");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_input_event_node() {
+        let node = |p: &str| is_input_event_node(std::path::Path::new(p));
+        assert!(node("/dev/input/event0"));
+        assert!(node("/dev/input/event12"));
+        assert!(!node("/dev/input/event"));
+        assert!(!node("/dev/input/event1x"));
+        assert!(!node("/dev/input/mice"));
+        assert!(!node("/dev/input/by-id/event3"));
+        assert!(!node("/dev/sda"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_v4l2_resolutions() {
+        let sample = "ioctl: VIDIOC_ENUM_FMT\n\
+                      \tType: Video Capture\n\
+                      \t[0]: 'MJPG' (Motion-JPEG, compressed)\n\
+                      \t\tSize: Discrete 1280x720\n\
+                      \t\t\tInterval: Discrete 0.033s (30.000 fps)\n\
+                      \t\tSize: Discrete 640x480\n\
+                      \t[1]: 'YUYV' (YUYV 4:2:2)\n\
+                      \t\tSize: Discrete 640x480\n";
+        assert_eq!(parse_v4l2_resolutions(sample), vec!["1280x720", "640x480"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_v4l2_capability_names() {
+        // A UVC capture node: capture + streaming + the device_caps marker.
+        assert_eq!(
+            v4l2_capability_names(0x8400_0001),
+            vec!["video_capture", "streaming"]
+        );
+        assert_eq!(v4l2_capability_names(0x0480_0000), vec!["meta_capture", "streaming"]);
+        assert!(v4l2_capability_names(0).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_v4l2_struct_sizes_match_ioctl_numbers() {
+        // The size is encoded in bits 16..30 of each request number.
+        assert_eq!(std::mem::size_of::<V4l2Capability>() as u64, (VIDIOC_QUERYCAP >> 16) & 0x3fff);
+        assert_eq!(std::mem::size_of::<V4l2FmtDesc>() as u64, (VIDIOC_ENUM_FMT >> 16) & 0x3fff);
+        assert_eq!(std::mem::size_of::<V4l2FrmSizeEnum>() as u64, (VIDIOC_ENUM_FRAMESIZES >> 16) & 0x3fff);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_v4l2_string_stops_at_nul() {
+        let mut raw = [0u8; 16];
+        raw[..8].copy_from_slice(b"uvcvideo");
+        assert_eq!(v4l2_string(&raw), "uvcvideo");
+        assert_eq!(v4l2_string(b"full-width-name!"), "full-width-name!");
+    }
+
+    #[test]
+    fn test_list_video_devices_fields() {
+        let result = list_video_devices();
+        assert!(result.is_ok(), "list_video_devices failed: {:?}", result.err());
+        for dev in result.unwrap() {
+            assert!(dev["name"].as_str().is_some(), "video device should have a name");
+            assert!(dev["nodes"].as_array().is_some(), "video device should have nodes array");
+            for node in dev["nodes"].as_array().unwrap() {
+                assert!(node["path"].as_str().is_some(), "video node should have a path");
+                assert!(node["capabilities"].as_array().is_some(), "video node should have capabilities");
+            }
+            assert!(dev["resolutions"].as_array().is_some(), "video device should have resolutions array");
+        }
+    }
+
+    #[test]
+    fn test_stop_input_test_when_idle() {
+        let result = stop_input_test().unwrap();
+        assert_eq!(result["stopped"].as_bool(), Some(false));
+    }
+
     #[test]
     fn test_categorize_usb_device() {
         assert_eq!(categorize_usb_device("USB Hub"), "Hub");
@@ -841,6 +2303,46 @@ mod tests {
         assert_eq!(categorize_usb_device("Unknown Device XYZ"), "Other");
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_categorize_usb_class() {
+        let iface = |c: &str, s: &str, p: &str| (c.to_string(), s.to_string(), p.to_string());
+
+        assert_eq!(categorize_usb_class("09", &[], "Generic").as_deref(), Some("Hub"));
+        assert_eq!(
+            categorize_usb_class("00", &[iface("08", "06", "50")], "USB2.0 Device").as_deref(),
+            Some("Storage"),
+            "terse names should still be classified by interface class"
+        );
+        assert_eq!(
+            categorize_usb_class("ef", &[iface("0e", "01", "00"), iface("01", "01", "00")], "HD Webcam").as_deref(),
+            Some("Camera"),
+            "video interface should win over the camera's microphone"
+        );
+        assert_eq!(
+            categorize_usb_class("00", &[iface("03", "01", "01")], "Gaming Mouse").as_deref(),
+            Some("Keyboard"),
+            "a lone keyboard boot interface beats a misleading name"
+        );
+        assert_eq!(
+            categorize_usb_class("00", &[iface("03", "01", "01"), iface("03", "01", "02")], "Gaming Mouse").as_deref(),
+            Some("Mouse"),
+            "name breaks the tie for composite keyboard+mouse devices"
+        );
+        assert_eq!(categorize_usb_class("e0", &[iface("e0", "01", "01")], "").as_deref(), Some("Bluetooth"));
+        assert_eq!(categorize_usb_class("00", &[iface("03", "00", "00")], "Macro Pad"), None);
+        assert_eq!(categorize_usb_class("00", &[], "Unknown"), None);
+    }
+
+    #[test]
+    fn test_list_usb_devices_classified_by() {
+        let devices = list_usb_devices().unwrap();
+        for dev in &devices {
+            let source = dev["classified_by"].as_str().expect("usb device should have classified_by");
+            assert!(source == "class" || source == "name", "unexpected classified_by: {}", source);
+        }
+    }
+
     #[test]
     fn test_categorize_network_device() {
         assert_eq!(categorize_network_device("lo"), "Loopback");
@@ -866,4 +2368,72 @@ mod tests {
         assert_eq!(categorize_pci_device("Non-Volatile memory controller"), "Storage");
         assert_eq!(categorize_pci_device("PCI bridge"), "System");
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_serial_port_name() {
+        assert!(is_serial_port_name("ttyUSB0"));
+        assert!(is_serial_port_name("ttyACM12"));
+        assert!(is_serial_port_name("ttyS3"));
+        assert!(!is_serial_port_name("tty1"), "virtual consoles are not serial ports");
+        assert!(!is_serial_port_name("ttyS"));
+        assert!(!is_serial_port_name("ttyUSBx"));
+    }
+
+    #[test]
+    fn test_list_serial_devices_structure() {
+        let result = list_serial_devices();
+        assert!(result.is_ok(), "list_serial_devices failed: {:?}", result.err());
+        for device in result.unwrap() {
+            assert!(device["path"].as_str().is_some_and(|p| p.starts_with("/dev/")));
+            assert!(device["in_use"].as_bool().is_some(), "in_use should be a bool");
+            assert!(device["open_by"].as_array().is_some(), "open_by should be an array");
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_unescape_udev_link() {
+        assert_eq!(unescape_udev_link("My\\x20Disk"), "My Disk");
+        assert_eq!(unescape_udev_link("EFI"), "EFI");
+        assert_eq!(unescape_udev_link("bad\\xZZ"), "bad\\xZZ", "invalid escapes are kept verbatim");
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_hid_usage_category() {
+        assert_eq!(hid_usage_category(0x01, 0x06), Some("Keyboard"));
+        assert_eq!(hid_usage_category(0x01, 0x02), Some("Mouse"));
+        assert_eq!(hid_usage_category(0x0D, 0x05), Some("Mouse"), "trackpads report as digitizer touchpads");
+        assert_eq!(hid_usage_category(0xFF00, 0x01), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_thunderbolt_device_id() {
+        assert!(is_thunderbolt_device_id("0-0"));
+        assert!(is_thunderbolt_device_id("0-1"));
+        assert!(is_thunderbolt_device_id("1-301"));
+        assert!(!is_thunderbolt_device_id("domain0"));
+        assert!(!is_thunderbolt_device_id("0-1.1"), "XDomain services are not devices");
+        assert!(!is_thunderbolt_device_id("../0-1"));
+    }
+
+    #[test]
+    fn test_list_thunderbolt_devices_structure() {
+        let result = list_thunderbolt_devices();
+        assert!(result.is_ok(), "list_thunderbolt_devices failed: {:?}", result.err());
+        for device in result.unwrap() {
+            assert!(device["id"].as_str().is_some(), "device should have an id");
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mmc_manufacturer() {
+        assert_eq!(mmc_manufacturer("SD", 0x03), Some("SanDisk"));
+        assert_eq!(mmc_manufacturer("MMC", 0x15), Some("Samsung"));
+        assert_eq!(mmc_manufacturer("MMC", 0x03), None, "SD and eMMC ids use different tables");
+        assert_eq!(mmc_manufacturer("SD", 0xee), None);
+    }
 }
@@ -0,0 +1,394 @@
+use serde::Serialize;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentEntry {
+    pub scope: String,
+    pub file: String,
+    pub key: String,
+    pub value: String,
+    pub shadowed: bool,
+}
+
+fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn validate_entry(key: &str, value: &str) -> Result<(), String> {
+    if !is_valid_env_key(key) {
+        return Err(format!("Invalid variable name '{}': use A-Z, 0-9 and '_', not starting with a digit", key));
+    }
+    if value.contains(['\n', '\r', '\0']) {
+        return Err("Values cannot contain line breaks".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) const SYSTEM_ENVIRONMENT_FILE: &str = "/etc/environment";
+
+#[cfg(target_os = "linux")]
+const DEFAULT_USER_FILE: &str = "60-gantry.conf";
+
+#[cfg(target_os = "linux")]
+fn user_environment_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".config").join("environment.d"))
+        .ok_or_else(|| "Cannot determine home directory".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn parse_environment_file(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if !is_valid_env_key(key) {
+                return None;
+            }
+            let value = value.trim();
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+                .unwrap_or(value);
+            Some((key.to_string(), unquoted.to_string()))
+        })
+        .collect()
+}
+
+// pam_env has no escape sequences, so a value can't hold both quote characters.
+#[cfg(target_os = "linux")]
+fn format_assignment(key: &str, value: &str) -> Result<String, String> {
+    let needs_quotes = value.is_empty() || value.contains(|c: char| c.is_whitespace() || "#\"'\\$`".contains(c));
+    if !needs_quotes {
+        return Ok(format!("{}={}", key, value));
+    }
+    if !value.contains('"') {
+        Ok(format!("{}=\"{}\"", key, value))
+    } else if !value.contains('\'') {
+        Ok(format!("{}='{}'", key, value))
+    } else {
+        Err("Values cannot contain both single and double quotes".to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn upsert_assignment(content: &str, key: &str, assignment: &str) -> String {
+    let mut replaced = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line_assigns(line, key) {
+            if !replaced {
+                lines.push(assignment.to_string());
+                replaced = true;
+            }
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if !replaced {
+        lines.push(assignment.to_string());
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+#[cfg(target_os = "linux")]
+fn remove_assignment(content: &str, key: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().filter(|line| !line_assigns(line, key)).collect();
+    if lines.len() == content.lines().count() {
+        return None;
+    }
+    Some(if lines.is_empty() { String::new() } else { format!("{}\n", lines.join("\n")) })
+}
+
+#[cfg(target_os = "linux")]
+fn line_assigns(line: &str, key: &str) -> bool {
+    let line = line.trim();
+    !line.starts_with('#') && line.split_once('=').is_some_and(|(k, _)| k.trim() == key)
+}
+
+#[cfg(target_os = "linux")]
+fn environment_files() -> Vec<(&'static str, PathBuf)> {
+    let mut files = vec![("system", PathBuf::from(SYSTEM_ENVIRONMENT_FILE))];
+    if let Ok(dir) = user_environment_dir() {
+        let mut user: Vec<PathBuf> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        user.sort();
+        files.extend(user.into_iter().map(|p| ("user", p)));
+    }
+    files
+}
+
+fn mark_shadowed(entries: &mut [EnvironmentEntry]) {
+    for i in 0..entries.len() {
+        let key = entries[i].key.clone();
+        entries[i].shadowed = entries[i + 1..].iter().any(|later| later.key == key);
+    }
+}
+
+// User files must be plain `.conf` names inside ~/.config/environment.d.
+#[cfg(target_os = "linux")]
+fn target_file(scope: &str, file: Option<&str>) -> Result<PathBuf, String> {
+    match scope {
+        "system" => Ok(PathBuf::from(SYSTEM_ENVIRONMENT_FILE)),
+        "user" => {
+            let name = file
+                .map(|f| f.rsplit('/').next().unwrap_or(f))
+                .filter(|f| !f.is_empty())
+                .unwrap_or(DEFAULT_USER_FILE);
+            if !name.ends_with(".conf") || name.starts_with('.') {
+                return Err("User environment files must be .conf files".to_string());
+            }
+            Ok(user_environment_dir()?.join(name))
+        }
+        _ => Err(format!("Unknown scope: {}", scope)),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn write_environment_file(scope: &str, path: &std::path::Path, content: &str) -> Result<(), String> {
+    if scope == "user" {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        return fs::write(path, content).map_err(|e| e.to_string());
+    }
+
+    let staged = super::util::private_temp("environment", content.as_bytes())?;
+    super::admin::write_environment(staged.path()).map_err(|e| format!("Failed to write {}: {}", path.display(), e.message()))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_environment_entries() -> Result<serde_json::Value, String> {
+    let mut entries: Vec<EnvironmentEntry> = Vec::new();
+    for (scope, path) in environment_files() {
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        for (key, value) in parse_environment_file(&content) {
+            entries.push(EnvironmentEntry {
+                scope: scope.to_string(),
+                file: path.to_string_lossy().to_string(),
+                key,
+                value,
+                shadowed: false,
+            });
+        }
+    }
+    mark_shadowed(&mut entries);
+    Ok(json!(entries))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn set_environment_entry(
+    scope: String,
+    key: String,
+    value: String,
+    file: Option<String>,
+) -> Result<serde_json::Value, String> {
+    validate_entry(&key, &value)?;
+    let path = target_file(&scope, file.as_deref())?;
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let updated = upsert_assignment(&content, &key, &format_assignment(&key, &value)?);
+    write_environment_file(&scope, &path, &updated)?;
+    Ok(json!({"success": true, "file": path.to_string_lossy()}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn delete_environment_entry(scope: String, key: String, file: Option<String>) -> Result<serde_json::Value, String> {
+    let path = target_file(&scope, file.as_deref())?;
+    let content = fs::read_to_string(&path).map_err(|_| format!("{} not found", path.display()))?;
+    let updated = remove_assignment(&content, &key).ok_or_else(|| format!("{} is not set in {}", key, path.display()))?;
+    write_environment_file(&scope, &path, &updated)?;
+    Ok(json!({"success": true}))
+}
+
+#[cfg(target_os = "macos")]
+const AGENT_PREFIX: &str = "com.gantry.env.";
+
+#[cfg(target_os = "macos")]
+fn launch_agents_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|h| h.join("Library/LaunchAgents"))
+        .ok_or_else(|| "Cannot determine home directory".to_string())
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_environment_entries() -> Result<serde_json::Value, String> {
+    let dir = launch_agents_dir()?;
+    let mut entries: Vec<EnvironmentEntry> = fs::read_dir(&dir)
+        .map(|items| {
+            items
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(AGENT_PREFIX)))
+                .filter_map(|path| {
+                    let dict = plist::from_file::<plist::Value, _>(&path).ok()?.into_dictionary()?;
+                    let args: Vec<String> = dict
+                        .get("ProgramArguments")?
+                        .as_array()?
+                        .iter()
+                        .filter_map(|v| v.as_string().map(|s| s.to_string()))
+                        .collect();
+                    let [_, action, key, value] = args.as_slice() else { return None };
+                    (action == "setenv").then(|| EnvironmentEntry {
+                        scope: "user".to_string(),
+                        file: path.to_string_lossy().to_string(),
+                        key: key.clone(),
+                        value: value.clone(),
+                        shadowed: false,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    mark_shadowed(&mut entries);
+    Ok(json!(entries))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn set_environment_entry(
+    scope: String,
+    key: String,
+    value: String,
+    _file: Option<String>,
+) -> Result<serde_json::Value, String> {
+    if scope != "user" {
+        return Err("Only per-user variables are supported on macOS".to_string());
+    }
+    validate_entry(&key, &value)?;
+    let dir = launch_agents_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let label = format!("{}{}", AGENT_PREFIX, key);
+    let path = dir.join(format!("{}.plist", label));
+    let mut dict = plist::Dictionary::new();
+    dict.insert("Label".into(), plist::Value::String(label));
+    dict.insert(
+        "ProgramArguments".into(),
+        plist::Value::Array(
+            ["/bin/launchctl", "setenv", &key, &value]
+                .iter()
+                .map(|s| plist::Value::String(s.to_string()))
+                .collect(),
+        ),
+    );
+    dict.insert("RunAtLoad".into(), plist::Value::Boolean(true));
+    plist::to_file_xml(&path, &plist::Value::Dictionary(dict)).map_err(|e| format!("Failed to write plist: {}", e))?;
+
+    // Apply now as well, so the variable doesn't wait for the next login.
+    let _ = Command::new("launchctl").args(["setenv", &key, &value]).output();
+    Ok(json!({"success": true, "file": path.to_string_lossy()}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn delete_environment_entry(scope: String, key: String, _file: Option<String>) -> Result<serde_json::Value, String> {
+    if scope != "user" {
+        return Err("Only per-user variables are supported on macOS".to_string());
+    }
+    if !is_valid_env_key(&key) {
+        return Err(format!("Invalid variable name '{}'", key));
+    }
+    let path = launch_agents_dir()?.join(format!("{}{}.plist", AGENT_PREFIX, key));
+    if !path.exists() {
+        return Err(format!("{} is not managed by Gantry", key));
+    }
+    fs::remove_file(&path).map_err(|e| e.to_string())?;
+    let _ = Command::new("launchctl").args(["unsetenv", &key]).output();
+    Ok(json!({"success": true}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_env_key() {
+        assert!(is_valid_env_key("PATH"));
+        assert!(is_valid_env_key("_JAVA_OPTIONS"));
+        assert!(is_valid_env_key("QT_SCALE_FACTOR_2"));
+        assert!(!is_valid_env_key("2FAST"));
+        assert!(!is_valid_env_key("lower"));
+        assert!(!is_valid_env_key("WITH-DASH"));
+        assert!(!is_valid_env_key(""));
+    }
+
+    #[test]
+    fn test_mark_shadowed() {
+        let entry = |file: &str, key: &str| EnvironmentEntry {
+            scope: "user".to_string(),
+            file: file.to_string(),
+            key: key.to_string(),
+            value: String::new(),
+            shadowed: false,
+        };
+        let mut entries = vec![entry("/etc/environment", "EDITOR"), entry("a.conf", "PATH"), entry("b.conf", "EDITOR")];
+        mark_shadowed(&mut entries);
+        assert!(entries[0].shadowed, "overridden by b.conf");
+        assert!(!entries[1].shadowed);
+        assert!(!entries[2].shadowed, "the last assignment is effective");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_environment_file() {
+        let content = "# comment\nPATH=\"/usr/local/bin:/usr/bin\"\n\nEDITOR=vim\nlower=ignored\nGREETING='hello world'\n";
+        let entries = parse_environment_file(content);
+        assert_eq!(
+            entries,
+            vec![
+                ("PATH".to_string(), "/usr/local/bin:/usr/bin".to_string()),
+                ("EDITOR".to_string(), "vim".to_string()),
+                ("GREETING".to_string(), "hello world".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_assignment_editing() {
+        assert_eq!(format_assignment("EDITOR", "vim").unwrap(), "EDITOR=vim");
+        assert_eq!(format_assignment("MSG", "a b").unwrap(), "MSG=\"a b\"");
+        assert_eq!(format_assignment("MSG", "say \"hi\"").unwrap(), "MSG='say \"hi\"'");
+        assert!(format_assignment("MSG", "it's \"x\"").is_err());
+
+        let content = "# keep me\nEDITOR=nano\nPATH=/bin\nEDITOR=vi\n";
+        assert_eq!(upsert_assignment(content, "EDITOR", "EDITOR=vim"), "# keep me\nEDITOR=vim\nPATH=/bin\n");
+        assert_eq!(upsert_assignment(content, "LANG", "LANG=C"), "# keep me\nEDITOR=nano\nPATH=/bin\nEDITOR=vi\nLANG=C\n");
+        assert_eq!(remove_assignment(content, "EDITOR").as_deref(), Some("# keep me\nPATH=/bin\n"));
+        assert_eq!(remove_assignment(content, "LANG"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_target_file_rejects_paths() {
+        assert!(target_file("user", Some("../../.bashrc")).is_err());
+        assert!(target_file("user", Some(".hidden.conf")).is_err());
+        assert!(target_file("user", Some("../../evil.conf")).unwrap().ends_with("environment.d/evil.conf"));
+        assert_eq!(target_file("system", None).unwrap(), PathBuf::from("/etc/environment"));
+        assert!(target_file("global", None).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::json;
+use std::fmt;
+use std::process::Output;
+
+const PERMISSION_HINTS: &[&str] = &[
+    "permission denied",
+    "operation not permitted",
+    "access denied",
+    "not authorized",
+    "authentication is required",
+    "interactive authentication required",
+    "must be root",
+    "are you root",
+];
+
+const NOT_FOUND_HINTS: &[&str] = &[
+    "not found",
+    "no such file",
+    "no such process",
+    "does not exist",
+    "could not find",
+    "not loaded",
+];
+
+const CANCELLED_HINTS: &[&str] = &["user canceled", "user cancelled", "request dismissed"];
+
+// Reaches the frontend as `{code, message, details}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GantryError {
+    PermissionDenied(String),
+    NotFound(String),
+    ToolMissing { tool: String },
+    ParseError(String),
+    Io(String),
+    Unsupported(String),
+    Cancelled(String),
+    Failed(String),
+}
+
+impl GantryError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            GantryError::PermissionDenied(_) => "permission_denied",
+            GantryError::NotFound(_) => "not_found",
+            GantryError::ToolMissing { .. } => "tool_missing",
+            GantryError::ParseError(_) => "parse_error",
+            GantryError::Io(_) => "io",
+            GantryError::Unsupported(_) => "unsupported",
+            GantryError::Cancelled(_) => "cancelled",
+            GantryError::Failed(_) => "failed",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            GantryError::ToolMissing { tool } => format!("{} is not installed", tool),
+            GantryError::PermissionDenied(m)
+            | GantryError::NotFound(m)
+            | GantryError::ParseError(m)
+            | GantryError::Io(m)
+            | GantryError::Unsupported(m)
+            | GantryError::Cancelled(m)
+            | GantryError::Failed(m) => m.clone(),
+        }
+    }
+
+    fn details(&self) -> serde_json::Value {
+        match self {
+            GantryError::ToolMissing { tool } => json!({"tool": tool}),
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    pub fn spawn(tool: &str, e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            GantryError::ToolMissing { tool: tool.to_string() }
+        } else {
+            e.into()
+        }
+    }
+
+    // pkexec exits 126 when its dialog is dismissed and 127 when authorization fails.
+    pub fn from_output(tool: &str, output: &Output) -> Self {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let message = if stderr.is_empty() { format!("{} failed ({})", tool, output.status) } else { stderr };
+        if tool == "pkexec" {
+            match output.status.code() {
+                Some(126) => return GantryError::Cancelled(message),
+                Some(127) => return GantryError::PermissionDenied(message),
+                _ => {}
+            }
+        }
+        Self::classify(message)
+    }
+
+    pub fn context(self, doing: &str) -> Self {
+        let wrap = |m: String| format!("{}: {}", doing, m);
+        match self {
+            GantryError::ToolMissing { .. } => self,
+            GantryError::PermissionDenied(m) => GantryError::PermissionDenied(wrap(m)),
+            GantryError::NotFound(m) => GantryError::NotFound(wrap(m)),
+            GantryError::ParseError(m) => GantryError::ParseError(wrap(m)),
+            GantryError::Io(m) => GantryError::Io(wrap(m)),
+            GantryError::Unsupported(m) => GantryError::Unsupported(wrap(m)),
+            GantryError::Cancelled(m) => GantryError::Cancelled(wrap(m)),
+            GantryError::Failed(m) => GantryError::Failed(wrap(m)),
+        }
+    }
+
+    pub fn classify(message: String) -> Self {
+        let lower = message.to_lowercase();
+        let has = |hints: &[&str]| hints.iter().any(|h| lower.contains(h));
+        if has(CANCELLED_HINTS) {
+            GantryError::Cancelled(message)
+        } else if has(PERMISSION_HINTS) {
+            GantryError::PermissionDenied(message)
+        } else if has(NOT_FOUND_HINTS) {
+            GantryError::NotFound(message)
+        } else {
+            GantryError::Failed(message)
+        }
+    }
+}
+
+impl fmt::Display for GantryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl std::error::Error for GantryError {}
+
+impl Serialize for GantryError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("GantryError", 3)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.message())?;
+        s.serialize_field("details", &self.details())?;
+        s.end()
+    }
+}
+
+impl From<std::io::Error> for GantryError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::PermissionDenied => GantryError::PermissionDenied(e.to_string()),
+            std::io::ErrorKind::NotFound => GantryError::NotFound(e.to_string()),
+            _ => GantryError::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<serde_yaml::Error> for GantryError {
+    fn from(e: serde_yaml::Error) -> Self {
+        GantryError::ParseError(e.to_string())
+    }
+}
+
+impl From<String> for GantryError {
+    fn from(message: String) -> Self {
+        GantryError::Failed(message)
+    }
+}
+
+impl From<&str> for GantryError {
+    fn from(message: &str) -> Self {
+        GantryError::Failed(message.to_string())
+    }
+}
+
+impl From<GantryError> for String {
+    fn from(e: GantryError) -> Self {
+        e.message()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn output(code: i32, stderr: &str) -> Output {
+        Output {
+            status: std::process::ExitStatus::from_raw(code << 8),
+            stdout: Vec::new(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_missing_tool_code() {
+        let missing = std::process::Command::new("gantry-no-such-helper").output().unwrap_err();
+        let err = GantryError::spawn("pkexec", missing);
+        assert_eq!(err.code(), "tool_missing");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({"code": "tool_missing", "message": "pkexec is not installed", "details": {"tool": "pkexec"}})
+        );
+    }
+
+    #[test]
+    fn test_output_codes() {
+        let unit = output(5, "Failed to start nosuch.service: Unit nosuch.service not found.\n");
+        assert_eq!(GantryError::from_output("pkexec", &unit).code(), "not_found");
+        assert_eq!(GantryError::from_output("pkexec", &output(126, "")).code(), "cancelled");
+        assert_eq!(GantryError::from_output("pkexec", &output(127, "Error executing command as another user: Not authorized")).code(), "permission_denied");
+        assert_eq!(GantryError::from_output("osascript", &output(1, "execution error: User canceled. (-128)")).code(), "cancelled");
+        let other = GantryError::from_output("apt-get", &output(100, ""));
+        assert_eq!(other.code(), "failed");
+        assert!(other.message().starts_with("apt-get failed"));
+    }
+
+    #[test]
+    fn test_io_and_string_conversions() {
+        let eperm = std::io::Error::from_raw_os_error(libc::EPERM);
+        assert_eq!(GantryError::from(eperm).code(), "permission_denied");
+        assert_eq!(GantryError::from("Script not found".to_string()).code(), "failed");
+        assert_eq!(String::from(GantryError::NotFound("gone".into())), "gone");
+    }
+}
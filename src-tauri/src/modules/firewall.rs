@@ -0,0 +1,642 @@
+use super::error::GantryError;
+use super::util::run_tool;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+#[cfg(target_os = "linux")]
+use std::fs;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FirewallRule {
+    // ufw: the rule number and a fingerprint of its line, `3:<hex>`. firewalld: `port:`, `service:`
+    // or `rich:` and the rule.
+    pub id: Option<String>,
+    pub action: String,
+    pub direction: String,
+    pub protocol: String,
+    pub port: Option<String>,
+    pub source: Option<String>,
+    pub service: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewRule {
+    pub action: String,
+    pub direction: String,
+    pub protocol: String,
+    pub port: String,
+    pub source: Option<String>,
+}
+
+impl NewRule {
+    // Everything here ends up on a root command line, so anything unexpected is refused.
+    pub(crate) fn normalized(&self) -> Result<NewRule, GantryError> {
+        let action = self.action.trim().to_lowercase();
+        if !["allow", "deny", "reject"].contains(&action.as_str()) {
+            return Err(GantryError::ParseError(format!("Unknown firewall action: {}", self.action)));
+        }
+        let direction = self.direction.trim().to_lowercase();
+        if !["in", "out"].contains(&direction.as_str()) {
+            return Err(GantryError::ParseError(format!("Unknown direction: {}", self.direction)));
+        }
+        let protocol = self.protocol.trim().to_lowercase();
+        if !["tcp", "udp", "any"].contains(&protocol.as_str()) {
+            return Err(GantryError::ParseError(format!("Unknown protocol: {}", self.protocol)));
+        }
+        let port = normalize_port(&self.port)?;
+        let source = match self.source.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(s) if s.eq_ignore_ascii_case("any") => None,
+            Some(s) if is_address_or_network(s) => Some(s.to_string()),
+            Some(s) => return Err(GantryError::ParseError(format!("Not an address or network: {}", s))),
+        };
+        Ok(NewRule { action, direction, protocol, port, source })
+    }
+}
+
+fn parse_port(s: &str) -> Option<u16> {
+    s.parse::<u16>().ok().filter(|p| *p > 0)
+}
+
+fn normalize_port(port: &str) -> Result<String, GantryError> {
+    let port = port.trim();
+    let invalid = || GantryError::ParseError(format!("Invalid port: {}", port));
+    match port.split_once(['-', ':']) {
+        Some((first, last)) => {
+            let (first, last) = (parse_port(first).ok_or_else(invalid)?, parse_port(last).ok_or_else(invalid)?);
+            if first >= last {
+                return Err(invalid());
+            }
+            Ok(format!("{}-{}", first, last))
+        }
+        None => parse_port(port).map(|p| p.to_string()).ok_or_else(invalid),
+    }
+}
+
+fn is_address_or_network(s: &str) -> bool {
+    let (addr, prefix) = match s.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (s, None),
+    };
+    let Ok(addr) = addr.parse::<IpAddr>() else { return false };
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    prefix.map_or(true, |p| p.parse::<u8>().is_ok_and(|p| p <= max))
+}
+
+pub(crate) fn add_commands(backend: &str, rule: &NewRule) -> Result<Vec<Vec<String>>, GantryError> {
+    let rule = rule.normalized()?;
+    match backend {
+        "ufw" => {
+            if rule.protocol == "any" && rule.port.contains('-') {
+                return Err(GantryError::ParseError("ufw needs tcp or udp for a port range".into()));
+            }
+            let mut args = vec!["ufw".to_string(), rule.action.clone(), rule.direction.clone()];
+            if rule.protocol != "any" {
+                args.extend(["proto".to_string(), rule.protocol.clone()]);
+            }
+            let source = rule.source.clone().unwrap_or_else(|| "any".to_string());
+            args.extend(["from".to_string(), source, "to".into(), "any".into(), "port".into(), rule.port.replace('-', ":")]);
+            Ok(vec![args])
+        }
+        "firewalld" => Ok(firewalld_commands(firewalld_add_arg(&rule)?)),
+        other => Err(GantryError::Unsupported(format!("Gantry can't change {} rules", other))),
+    }
+}
+
+pub(crate) fn delete_commands(backend: &str, id: &str) -> Result<Vec<Vec<String>>, GantryError> {
+    match backend {
+        "ufw" => {
+            let number = id.split(':').next().and_then(|n| n.parse::<u32>().ok()).filter(|n| *n > 0);
+            let number = number.ok_or_else(|| GantryError::ParseError(format!("Invalid ufw rule number: {}", id)))?;
+            Ok(vec![vec!["ufw".into(), "--force".into(), "delete".into(), number.to_string()]])
+        }
+        "firewalld" => Ok(firewalld_commands(firewalld_remove_arg(id)?)),
+        other => Err(GantryError::Unsupported(format!("Gantry can't change {} rules", other))),
+    }
+}
+
+fn firewalld_commands(arg: String) -> Vec<Vec<String>> {
+    vec![
+        vec!["firewall-cmd".into(), "--permanent".into(), arg],
+        vec!["firewall-cmd".into(), "--reload".into()],
+    ]
+}
+
+fn firewalld_add_arg(rule: &NewRule) -> Result<String, GantryError> {
+    if rule.direction == "out" {
+        return Err(GantryError::Unsupported("firewalld zones only filter incoming traffic".into()));
+    }
+    if rule.protocol == "any" {
+        return Err(GantryError::ParseError("firewalld needs tcp or udp for a port".into()));
+    }
+    if rule.action == "allow" && rule.source.is_none() {
+        return Ok(format!("--add-port={}/{}", rule.port, rule.protocol));
+    }
+    let mut parts = vec!["rule".to_string()];
+    if let Some(source) = &rule.source {
+        let family = if source.contains(':') { "ipv6" } else { "ipv4" };
+        parts.push(format!("family=\"{}\" source address=\"{}\"", family, source));
+    }
+    parts.push(format!("port port=\"{}\" protocol=\"{}\"", rule.port, rule.protocol));
+    parts.push(
+        match rule.action.as_str() {
+            "allow" => "accept",
+            "deny" => "drop",
+            _ => "reject",
+        }
+        .to_string(),
+    );
+    Ok(format!("--add-rich-rule={}", parts.join(" ")))
+}
+
+fn firewalld_remove_arg(id: &str) -> Result<String, GantryError> {
+    let invalid = || GantryError::ParseError(format!("Invalid firewalld rule: {}", id));
+    let (kind, value) = id.split_once(':').ok_or_else(invalid)?;
+    match kind {
+        "port" => {
+            let (port, protocol) = value.split_once('/').ok_or_else(invalid)?;
+            if !["tcp", "udp", "sctp", "dccp"].contains(&protocol) {
+                return Err(invalid());
+            }
+            Ok(format!("--remove-port={}/{}", normalize_port(port)?, protocol))
+        }
+        "service" if !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) => {
+            Ok(format!("--remove-service={}", value))
+        }
+        "rich" if value.starts_with("rule ") && !value.contains(|c: char| c.is_control()) => {
+            Ok(format!("--remove-rich-rule={}", value))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+pub(crate) fn status_command(backend: &str) -> Result<Vec<String>, GantryError> {
+    match backend {
+        "ufw" => Ok(vec!["ufw".into(), "status".into(), "numbered".into()]),
+        "nftables" => Ok(vec!["nft".into(), "list".into(), "ruleset".into()]),
+        other => Err(GantryError::Unsupported(format!("{} rules don't need root to read", other))),
+    }
+}
+
+fn policy_word(target: &str) -> String {
+    match target.trim().trim_matches('"').trim_matches('%').to_uppercase().as_str() {
+        "ACCEPT" | "ALLOW" => "allow",
+        "DROP" | "DENY" => "deny",
+        "REJECT" | "DEFAULT" => "reject",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+fn strip_ufw_suffixes(column: &str) -> &str {
+    column.trim().trim_end_matches(" (out)").trim_end_matches(" (v6)")
+}
+
+fn ufw_rule_id(number: u32, line: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    line.split_whitespace().for_each(|word| word.hash(&mut hasher));
+    format!("{}:{:016x}", number, hasher.finish())
+}
+
+fn parse_ufw_status(output: &str) -> (bool, Vec<FirewallRule>) {
+    let active = output.lines().any(|l| l.trim() == "Status: active");
+    let rules = output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix('[')?;
+            let (number, rest) = rest.split_once(']')?;
+            let number = number.trim().parse::<u32>().ok()?;
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            let at = words.iter().position(|w| ["ALLOW", "DENY", "REJECT", "LIMIT"].contains(w))?;
+            let direction = match words.get(at + 1) {
+                Some(&"OUT") => "out",
+                Some(&"FWD") => "forward",
+                _ => "in",
+            };
+            let from_start = if words.get(at + 1).is_some_and(|w| ["IN", "OUT", "FWD"].contains(w)) { at + 2 } else { at + 1 };
+            let from = words[from_start..].iter().take_while(|w| !w.starts_with('#')).copied().collect::<Vec<_>>().join(" ");
+            let to = words[..at].join(" ");
+            let to = strip_ufw_suffixes(to.split(" on ").next().unwrap_or_default());
+            let (port, protocol, service) = match to.split_once('/') {
+                Some((port, protocol)) => (Some(port.replace(':', "-")), protocol.to_string(), None),
+                None if to == "Anywhere" => (None, "any".to_string(), None),
+                None if to.chars().all(|c| c.is_ascii_digit() || ",:".contains(c)) => {
+                    (Some(to.replace(':', "-")), "any".to_string(), None)
+                }
+                // An application profile, or an address for a rule with no port.
+                None if is_address_or_network(to) => (None, "any".to_string(), None),
+                None => (None, "any".to_string(), Some(to.to_string())),
+            };
+            let source = strip_ufw_suffixes(&from);
+            Some(FirewallRule {
+                id: Some(ufw_rule_id(number, rest)),
+                action: words[at].to_lowercase(),
+                direction: direction.to_string(),
+                protocol,
+                port,
+                source: (source != "Anywhere" && !source.is_empty()).then(|| source.to_string()),
+                service,
+            })
+        })
+        .collect();
+    (active, rules)
+}
+
+fn parse_ufw_defaults(content: &str) -> Value {
+    let policy = |key: &str| {
+        content
+            .lines()
+            .find_map(|l| l.trim().strip_prefix(key)?.strip_prefix('='))
+            .map(policy_word)
+    };
+    json!({"incoming": policy("DEFAULT_INPUT_POLICY"), "outgoing": policy("DEFAULT_OUTPUT_POLICY")})
+}
+
+fn zone_field<'a>(output: &'a str, key: &str) -> Option<&'a str> {
+    output.lines().find_map(|l| l.trim().strip_prefix(key)?.strip_prefix(':')).map(str::trim)
+}
+
+fn rich_value<'a>(rule: &'a str, key: &str) -> Option<&'a str> {
+    let start = rule.find(&format!("{}=\"", key))? + key.len() + 2;
+    rule[start..].split('"').next()
+}
+
+fn parse_firewalld_zone(output: &str) -> (Value, Vec<FirewallRule>) {
+    let incoming = zone_field(output, "target").map(policy_word);
+    let allow = |id: String, protocol: &str, port: Option<String>, service: Option<String>| FirewallRule {
+        id: Some(id),
+        action: "allow".into(),
+        direction: "in".into(),
+        protocol: protocol.into(),
+        port,
+        source: None,
+        service,
+    };
+    let mut rules: Vec<FirewallRule> = zone_field(output, "services")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|s| allow(format!("service:{}", s), "any", None, Some(s.to_string())))
+        .collect();
+    for spec in zone_field(output, "ports").unwrap_or_default().split_whitespace() {
+        if let Some((port, protocol)) = spec.split_once('/') {
+            rules.push(allow(format!("port:{}", spec), protocol, Some(port.to_string()), None));
+        }
+    }
+
+    // Rich rules follow their heading, one per indented line.
+    let rich = output.lines().skip_while(|l| l.trim() != "rich rules:").skip(1);
+    for rule in rich.map(str::trim).filter(|l| l.starts_with("rule ")) {
+        let action = if rule.ends_with(" accept") {
+            "allow"
+        } else if rule.ends_with(" drop") {
+            "deny"
+        } else if rule.contains(" reject") {
+            "reject"
+        } else {
+            continue;
+        };
+        rules.push(FirewallRule {
+            id: Some(format!("rich:{}", rule)),
+            action: action.into(),
+            direction: "in".into(),
+            protocol: rich_value(rule, "protocol").unwrap_or("any").to_string(),
+            port: rich_value(rule, "port port").map(|p| p.to_string()),
+            source: rich_value(rule, "source address").map(|s| s.to_string()),
+            service: rich_value(rule, "service name").map(|s| s.to_string()),
+        });
+    }
+    (json!({"incoming": incoming, "outgoing": "allow"}), rules)
+}
+
+fn parse_nft_ruleset(output: &str) -> (Value, Vec<FirewallRule>) {
+    let mut hook: Option<&str> = None;
+    let (mut incoming, mut outgoing) = (None, None);
+    let mut rules = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("chain ") {
+            hook = None;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("type filter hook ") {
+            hook = rest.split_whitespace().next().filter(|h| ["input", "output"].contains(h));
+            let policy = rest.split("policy ").nth(1).map(|p| policy_word(p.trim_end_matches(';')));
+            match hook {
+                Some("input") => incoming = policy.or(incoming),
+                Some("output") => outgoing = policy.or(outgoing),
+                _ => {}
+            }
+            continue;
+        }
+        let Some(hook) = hook else { continue };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(at) = words.iter().position(|w| *w == "dport") else { continue };
+        let action = if words.contains(&"accept") {
+            "allow"
+        } else if words.contains(&"drop") {
+            "deny"
+        } else if words.contains(&"reject") {
+            "reject"
+        } else {
+            continue;
+        };
+        let port = if words.get(at + 1) == Some(&"{") {
+            words[at + 2..].iter().take_while(|w| **w != "}").map(|w| w.trim_end_matches(',')).collect::<Vec<_>>().join(",")
+        } else {
+            words.get(at + 1).map(|w| w.to_string()).unwrap_or_default()
+        };
+        let source = words.iter().position(|w| *w == "saddr").and_then(|i| words.get(i + 1)).map(|s| s.to_string());
+        let protocol = at.checked_sub(1).map(|i| words[i]).filter(|p| ["tcp", "udp"].contains(p)).unwrap_or("any");
+        rules.push(FirewallRule {
+            id: None,
+            action: action.into(),
+            direction: if hook == "input" { "in" } else { "out" }.into(),
+            protocol: protocol.into(),
+            port: Some(port),
+            source,
+            service: None,
+        });
+    }
+    (json!({"incoming": incoming, "outgoing": outgoing}), rules)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_backend() -> Option<&'static str> {
+    let firewalld_running = run_tool("firewall-cmd", &["--state"]).is_ok_and(|s| s.trim() == "running");
+    if firewalld_running {
+        Some("firewalld")
+    } else if super::system::has_binary("ufw") {
+        Some("ufw")
+    } else if super::system::has_binary("firewall-cmd") {
+        Some("firewalld")
+    } else if super::system::has_binary("nft") {
+        Some("nftables")
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn root_listing(backend: &str, elevated: bool) -> Result<Option<String>, GantryError> {
+    let command = status_command(backend)?;
+    let args: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+    match run_tool(&command[0], &args) {
+        Ok(output) => Ok(Some(output)),
+        Err(_) if elevated => super::admin::read_firewall(backend, &command).map(Some),
+        Err(GantryError::ToolMissing { tool }) => Err(GantryError::ToolMissing { tool }),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn get_firewall_status(elevated: Option<bool>) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || firewall_status(elevated.unwrap_or(false)))
+        .await
+        .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(target_os = "linux")]
+fn firewall_status(elevated: bool) -> Result<Value, GantryError> {
+    let Some(backend) = detect_backend() else {
+        return Ok(json!({
+            "backend": null,
+            "active": false,
+            "read_only": true,
+            "defaults": {"incoming": null, "outgoing": null},
+            "rules": [],
+            "rules_visible": false,
+            "note": "No ufw, firewalld or nftables found",
+        }));
+    };
+    let (active, defaults, rules, note) = match backend {
+        "firewalld" => {
+            let active = run_tool("firewall-cmd", &["--state"]).is_ok_and(|s| s.trim() == "running");
+            let (defaults, rules) = if active {
+                parse_firewalld_zone(&run_tool("firewall-cmd", &["--list-all"])?)
+            } else {
+                (json!({"incoming": null, "outgoing": null}), Vec::new())
+            };
+            (active, defaults, Some(rules), (!active).then_some("firewalld is not running"))
+        }
+        "ufw" => {
+            let defaults = parse_ufw_defaults(&fs::read_to_string("/etc/default/ufw").unwrap_or_default());
+            match root_listing("ufw", elevated)? {
+                Some(output) => {
+                    let (active, rules) = parse_ufw_status(&output);
+                    (active, defaults, Some(rules), None)
+                }
+                None => {
+                    let conf = fs::read_to_string("/etc/ufw/ufw.conf").unwrap_or_default();
+                    let active = conf.lines().any(|l| l.trim() == "ENABLED=yes");
+                    (active, defaults, None, Some("ufw only lists its rules to root"))
+                }
+            }
+        }
+        _ => match root_listing("nftables", elevated)? {
+            Some(output) => {
+                let (defaults, rules) = parse_nft_ruleset(&output);
+                (true, defaults, Some(rules), None)
+            }
+            None => (true, json!({"incoming": null, "outgoing": null}), None, Some("nftables only lists its rules to root")),
+        },
+    };
+    Ok(json!({
+        "backend": backend,
+        "active": active,
+        "read_only": !matches!(backend, "ufw" | "firewalld"),
+        "defaults": defaults,
+        "rules_visible": rules.is_some(),
+        "rules": rules.unwrap_or_default(),
+        "note": note,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn add_firewall_rule(rule: NewRule) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backend = detect_backend().ok_or_else(|| GantryError::NotFound("No firewall found".into()))?;
+        let rule = rule.normalized()?;
+        let commands = add_commands(backend, &rule)?;
+        let request = json!({"op": "add_firewall_rule", "backend": backend, "rule": rule});
+        super::admin::change_firewall("add_firewall_rule", &commands[0][1..].join(" "), request, &commands)
+            .map_err(|e| e.context("Could not add the firewall rule"))?;
+        Ok(json!({"success": true, "backend": backend}))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn delete_firewall_rule(id: String) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backend = detect_backend().ok_or_else(|| GantryError::NotFound("No firewall found".into()))?;
+        let commands = delete_commands(backend, &id)?;
+        // ufw deletes by number, and numbers shift whenever a rule is added
+        // or removed, so check the number still points at the listed rule.
+        if backend == "ufw" {
+            let listing = root_listing(backend, true)?.unwrap_or_default();
+            if !parse_ufw_status(&listing).1.iter().any(|rule| rule.id.as_deref() == Some(id.as_str())) {
+                return Err(GantryError::NotFound("The firewall rules changed since they were listed; refresh and try again".into()));
+            }
+        }
+        let request = json!({"op": "delete_firewall_rule", "backend": backend, "id": id});
+        super::admin::change_firewall("delete_firewall_rule", &format!("{} {}", backend, id), request, &commands)
+            .map_err(|e| e.context("Could not delete the firewall rule"))?;
+        Ok(json!({"success": true, "backend": backend}))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub async fn get_firewall_status(elevated: Option<bool>) -> Result<Value, GantryError> {
+    let _ = elevated;
+    let state = run_tool("/usr/libexec/ApplicationFirewall/socketfilterfw", &["--getglobalstate"])?;
+    Ok(json!({
+        "backend": "application_firewall",
+        "active": state.contains("enabled"),
+        "read_only": true,
+        "defaults": {"incoming": null, "outgoing": "allow"},
+        "rules": [],
+        "rules_visible": true,
+        "note": "Manage the Application Firewall in System Settings",
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub async fn add_firewall_rule(rule: NewRule) -> Result<Value, GantryError> {
+    let _ = rule;
+    Err(GantryError::Unsupported("Firewall rules can only be changed on Linux".into()))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub async fn delete_firewall_rule(id: String) -> Result<Value, GantryError> {
+    let _ = id;
+    Err(GantryError::Unsupported("Firewall rules can only be changed on Linux".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: &str, direction: &str, protocol: &str, port: &str, source: Option<&str>) -> NewRule {
+        NewRule {
+            action: action.into(),
+            direction: direction.into(),
+            protocol: protocol.into(),
+            port: port.into(),
+            source: source.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_new_rule_validation() {
+        let r = rule("Allow", "IN", "tcp", "8000:8100", Some(" any ")).normalized().unwrap();
+        assert_eq!((r.action.as_str(), r.port.as_str(), r.source), ("allow", "8000-8100", None));
+        assert!(rule("allow", "in", "tcp", "0", None).normalized().is_err());
+        assert!(rule("allow", "in", "tcp", "90-80", None).normalized().is_err());
+        assert!(rule("allow", "in", "tcp", "22; reboot", None).normalized().is_err());
+        assert!(rule("permit", "in", "tcp", "22", None).normalized().is_err());
+        assert!(rule("allow", "in", "tcp", "22", Some("10.0.0.0/33")).normalized().is_err());
+        assert!(rule("allow", "in", "tcp", "22", Some("fd00::/64")).normalized().is_ok());
+        assert!(rule("allow", "in", "tcp", "22", Some("-h")).normalized().is_err());
+    }
+
+    #[test]
+    fn test_commands() {
+        let ufw = add_commands("ufw", &rule("deny", "in", "udp", "5353", Some("192.168.1.0/24"))).unwrap();
+        assert_eq!(ufw[0].join(" "), "ufw deny in proto udp from 192.168.1.0/24 to any port 5353");
+        let ufw = add_commands("ufw", &rule("allow", "in", "any", "22", None)).unwrap();
+        assert_eq!(ufw[0].join(" "), "ufw allow in from any to any port 22");
+        assert!(add_commands("ufw", &rule("allow", "in", "any", "80-90", None)).is_err());
+        assert_eq!(delete_commands("ufw", "3:00ff").unwrap()[0].join(" "), "ufw --force delete 3");
+        assert!(delete_commands("ufw", "3 && reboot").is_err());
+
+        let fwd = add_commands("firewalld", &rule("allow", "in", "tcp", "8080", None)).unwrap();
+        assert_eq!(fwd[0], vec!["firewall-cmd", "--permanent", "--add-port=8080/tcp"]);
+        assert_eq!(fwd[1], vec!["firewall-cmd", "--reload"]);
+        let fwd = add_commands("firewalld", &rule("deny", "in", "tcp", "22", Some("10.0.0.0/8"))).unwrap();
+        assert_eq!(
+            fwd[0][2],
+            r#"--add-rich-rule=rule family="ipv4" source address="10.0.0.0/8" port port="22" protocol="tcp" drop"#
+        );
+        assert!(add_commands("firewalld", &rule("allow", "out", "tcp", "22", None)).is_err());
+        assert_eq!(delete_commands("firewalld", "service:ssh").unwrap()[0][2], "--remove-service=ssh");
+        assert_eq!(delete_commands("firewalld", "port:9000-9100/udp").unwrap()[0][2], "--remove-port=9000-9100/udp");
+        assert!(delete_commands("firewalld", "service:ssh --panic-on").is_err());
+        assert!(add_commands("nftables", &rule("allow", "in", "tcp", "22", None)).is_err());
+    }
+
+    #[test]
+    fn test_parse_ufw_status() {
+        let output = "Status: active\n\n     To                         Action      From\n     --                         ------      ----\n\
+                      [ 1] 22/tcp                     ALLOW IN    Anywhere\n\
+                      [ 2] 8000:8100/udp              DENY IN     192.168.1.0/24             # media\n\
+                      [ 3] Nginx Full                 ALLOW IN    Anywhere\n\
+                      [ 4] 25/tcp                     REJECT OUT  Anywhere (out)\n\
+                      [ 5] 22/tcp (v6)                ALLOW IN    Anywhere (v6)\n\
+                      [ 6] 53 on eth0                 LIMIT IN    Anywhere\n";
+        let (active, rules) = parse_ufw_status(output);
+        assert!(active);
+        assert_eq!(rules.len(), 6);
+        assert!(rules[0].id.as_deref().is_some_and(|id| id.starts_with("1:")));
+        let (_, shifted) = parse_ufw_status(&output.replace("[ 1] 22/tcp", "").replace("[ 2]", "[ 1]"));
+        assert_ne!(shifted[0].id, rules[0].id, "another rule now has number 1");
+        assert_eq!((rules[0].protocol.as_str(), rules[0].port.as_deref()), ("tcp", Some("22")));
+        assert_eq!(rules[1].action, "deny");
+        assert_eq!(rules[1].port.as_deref(), Some("8000-8100"));
+        assert_eq!(rules[1].source.as_deref(), Some("192.168.1.0/24"));
+        assert_eq!(rules[2].service.as_deref(), Some("Nginx Full"));
+        assert_eq!(rules[3].direction, "out");
+        assert_eq!(rules[3].source, None);
+        assert_eq!(rules[4].source, None);
+        assert_eq!((rules[5].action.as_str(), rules[5].port.as_deref()), ("limit", Some("53")));
+        assert!(!parse_ufw_status("Status: inactive\n").0);
+
+        let defaults = parse_ufw_defaults("IPV6=yes\nDEFAULT_INPUT_POLICY=\"DROP\"\nDEFAULT_OUTPUT_POLICY=\"ACCEPT\"\n");
+        assert_eq!(defaults, json!({"incoming": "deny", "outgoing": "allow"}));
+    }
+
+    #[test]
+    fn test_parse_firewalld_zone() {
+        let output = "public (active)\n  target: default\n  icmp-block-inversion: no\n  interfaces: eth0\n  sources: \n\
+                      \x20 services: dhcpv6-client ssh\n  ports: 8080/tcp 9000-9100/udp\n  protocols: \n  masquerade: no\n\
+                      \x20 rich rules: \n\
+                      \trule family=\"ipv4\" source address=\"10.0.0.0/8\" port port=\"22\" protocol=\"tcp\" reject\n";
+        let (defaults, rules) = parse_firewalld_zone(output);
+        assert_eq!(defaults["incoming"], "reject");
+        assert_eq!(rules.len(), 5);
+        assert_eq!(rules[1].id.as_deref(), Some("service:ssh"));
+        assert_eq!(rules[3].port.as_deref(), Some("9000-9100"));
+        assert_eq!(rules[3].protocol, "udp");
+        let rich = &rules[4];
+        assert_eq!(rich.action, "reject");
+        assert_eq!(rich.source.as_deref(), Some("10.0.0.0/8"));
+        assert_eq!((rich.port.as_deref(), rich.protocol.as_str()), (Some("22"), "tcp"));
+        assert!(firewalld_remove_arg(rich.id.as_deref().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_nft_ruleset() {
+        let output = "table inet filter {\n\tchain input {\n\t\ttype filter hook input priority filter; policy drop;\n\
+                      \t\tct state established,related accept\n\t\ttcp dport 22 accept\n\
+                      \t\tip saddr 10.0.0.0/8 tcp dport { 80, 443 } drop\n\t}\n\
+                      \tchain output {\n\t\ttype filter hook output priority filter; policy accept;\n\t}\n}\n";
+        let (defaults, rules) = parse_nft_ruleset(output);
+        assert_eq!(defaults, json!({"incoming": "deny", "outgoing": "allow"}));
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].port.as_deref(), Some("22"));
+        assert_eq!(rules[1].port.as_deref(), Some("80,443"));
+        assert_eq!(rules[1].source.as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(rules[1].action, "deny");
+        assert!(rules.iter().all(|r| r.id.is_none()));
+    }
+}
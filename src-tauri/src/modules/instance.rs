@@ -0,0 +1,214 @@
+use serde_json::json;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const LOCK_FILE: &str = "gantry.lock";
+const SOCKET_FILE: &str = "gantry.sock";
+
+const CONNECT_RETRIES: u32 = 20;
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub enum Instance {
+    Primary(UnixListener),
+    Forwarded,
+}
+
+fn instance_dir() -> Result<PathBuf, String> {
+    super::appdata::config_root().ok_or_else(|| "Could not find home directory".to_string())
+}
+
+fn pid_alive(pid: i32) -> bool {
+    if pid <= 0 {
+        return false;
+    }
+    // Signal 0 only checks the process exists; EPERM means it does but
+    // belongs to someone else.
+    let rc = unsafe { libc::kill(pid, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(target_os = "linux")]
+fn program_of(pid: i32) -> Option<PathBuf> {
+    let exe = fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    // A binary replaced by an upgrade while running reads as "<path> (deleted)".
+    let exe = exe.to_string_lossy();
+    Some(PathBuf::from(exe.strip_suffix(" (deleted)").unwrap_or(&exe)))
+}
+
+#[cfg(target_os = "macos")]
+fn program_of(pid: i32) -> Option<PathBuf> {
+    let mut buf = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe { libc::proc_pidpath(pid, buf.as_mut_ptr().cast(), buf.len() as u32) };
+    if len <= 0 {
+        return None;
+    }
+    buf.truncate(len as usize);
+    Some(PathBuf::from(String::from_utf8_lossy(&buf).to_string()))
+}
+
+// A pid reused by something else after a crash doesn't hold the lock.
+fn lock_owner(lock: &Path) -> Option<i32> {
+    let pid = fs::read_to_string(lock).ok()?.trim().parse::<i32>().ok()?;
+    let ours = || program_of(pid).is_some_and(|p| Some(p) == program_of(std::process::id() as i32));
+    (pid != std::process::id() as i32 && pid_alive(pid) && ours()).then_some(pid)
+}
+
+fn forward(socket: &Path, args: &[String]) -> bool {
+    let Ok(mut stream) = UnixStream::connect(socket) else { return false };
+    let _ = stream.set_read_timeout(Some(REPLY_TIMEOUT));
+    if writeln!(stream, "{}", json!({"args": args})).is_err() {
+        return false;
+    }
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).is_ok() && reply.trim() == "ok"
+}
+
+// Linking a temp file into place makes the claim atomic and the lock never empty.
+fn claim_lock(lock: &Path) -> std::io::Result<()> {
+    let tmp = lock.with_extension(format!("lock.{}", std::process::id()));
+    fs::write(&tmp, std::process::id().to_string())?;
+    let linked = fs::hard_link(&tmp, lock);
+    let _ = fs::remove_file(&tmp);
+    linked
+}
+
+fn acquire_in(dir: &Path, args: &[String]) -> std::io::Result<Instance> {
+    fs::create_dir_all(dir)?;
+    let lock = dir.join(LOCK_FILE);
+    let socket = dir.join(SOCKET_FILE);
+
+    for _ in 0..CONNECT_RETRIES {
+        if forward(&socket, args) {
+            return Ok(Instance::Forwarded);
+        }
+        match claim_lock(&lock) {
+            Ok(()) => {
+                // Nobody answered, so a socket left here is from a crash.
+                let _ = fs::remove_file(&socket);
+                return Ok(Instance::Primary(UnixListener::bind(&socket)?));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                if lock_owner(&lock).is_none() {
+                    let _ = fs::remove_file(&lock);
+                } else {
+                    std::thread::sleep(CONNECT_RETRY_DELAY);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(std::io::Error::other("another instance holds the lock but does not answer"))
+}
+
+pub fn acquire() -> Result<Instance, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    acquire_in(&instance_dir()?, &args).map_err(|e| format!("Single-instance check failed: {}", e))
+}
+
+pub fn serve(listener: UnixListener, app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = stream.set_read_timeout(Some(REPLY_TIMEOUT));
+            let mut line = String::new();
+            if BufReader::new(&stream).read_line(&mut line).is_err() {
+                continue;
+            }
+            let args = serde_json::from_str::<serde_json::Value>(&line)
+                .ok()
+                .and_then(|v| v.get("args").cloned())
+                .unwrap_or_else(|| json!([]));
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit("second-instance", json!({"args": args}));
+            let _ = (&stream).write_all(b"ok\n");
+        }
+    });
+}
+
+pub fn release() {
+    let Ok(dir) = instance_dir() else { return };
+    let lock = dir.join(LOCK_FILE);
+    let ours = fs::read_to_string(&lock).is_ok_and(|pid| pid.trim() == std::process::id().to_string());
+    if ours {
+        let _ = fs::remove_file(dir.join(SOCKET_FILE));
+        let _ = fs::remove_file(lock);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gantry_instance_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_second_launch_is_forwarded() {
+        let dir = scratch_dir("forward");
+        let Instance::Primary(listener) = acquire_in(&dir, &[]).unwrap() else { panic!("expected to be primary") };
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            (&stream).write_all(b"ok\n").unwrap();
+            line
+        });
+
+        let forwarded = acquire_in(&dir, &["--minimized".to_string()]).unwrap();
+        let line = server.join().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(matches!(forwarded, Instance::Forwarded));
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&line).unwrap(), json!({"args": ["--minimized"]}));
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let dir = scratch_dir("stale");
+        fs::create_dir_all(&dir).unwrap();
+        // Far above any pid_max, so no such process.
+        fs::write(dir.join(LOCK_FILE), i32::MAX.to_string()).unwrap();
+        fs::write(dir.join(SOCKET_FILE), "").unwrap();
+
+        let instance = acquire_in(&dir, &[]).unwrap();
+        let lock = fs::read_to_string(dir.join(LOCK_FILE)).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(matches!(instance, Instance::Primary(_)));
+        assert_eq!(lock, std::process::id().to_string());
+    }
+
+    #[test]
+    fn test_lock_held_by_another_program_is_taken_over() {
+        let dir = scratch_dir("reused");
+        fs::create_dir_all(&dir).unwrap();
+        // A live process that isn't gantry, as when a crashed instance's pid
+        // is reused.
+        let mut other = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        fs::write(dir.join(LOCK_FILE), other.id().to_string()).unwrap();
+
+        let instance = acquire_in(&dir, &[]);
+        let _ = other.kill();
+        let _ = other.wait();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(matches!(instance.unwrap(), Instance::Primary(_)));
+        assert!(program_of(std::process::id() as i32).is_some());
+    }
+
+    #[test]
+    fn test_pid_alive() {
+        assert!(pid_alive(std::process::id() as i32));
+        assert!(!pid_alive(i32::MAX));
+        assert!(!pid_alive(0));
+    }
+}
@@ -1,48 +1,760 @@
-use chrono::Local;
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+use super::settings::LogFormat;
+
+const LOG_LEVELS: &[&str] = &["debug", "info", "warn", "error"];
+
+const TEXT_TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub ts: String,
+    pub level: String,
+    pub message: String,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+const TAIL_MAX_LINES: usize = 5000;
+
+const SEARCH_DEFAULT_RESULTS: usize = 200;
+const SEARCH_MAX_RESULTS: usize = 5000;
+
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+static LOG_FOLLOW: OnceLock<Mutex<Option<Arc<AtomicBool>>>> = OnceLock::new();
+
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize)]
+pub struct LogFileContent {
+    pub content: String,
+    pub exists: bool,
+    pub size: u64,
+}
+
+fn log_file_path_with(create_dir: bool) -> Result<PathBuf, String> {
+    let log_file = super::appdata::config_root()
+        .map(|root| root.join("app.log"))
+        .ok_or_else(|| "Could not find home directory".to_string())?;
+    if create_dir {
+        if let Some(parent) = log_file.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create log dir: {}", e))?;
+        }
+    }
+    Ok(log_file)
+}
 
 fn log_file_path() -> Result<PathBuf, String> {
-    dirs::home_dir()
-        .map(|h| h.join(".gantry").join("app.log"))
-        .ok_or_else(|| "Could not find home directory".to_string())
+    log_file_path_with(false)
 }
 
-#[tauri::command]
-pub fn write_log(message: String) -> Result<String, String> {
-    let log_file = log_file_path()?;
+fn rotated_path(log_file: &Path, n: usize) -> PathBuf {
+    let mut name = log_file.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
 
-    if let Some(parent) = log_file.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("Failed to create log dir: {}", e))?;
+// Each step is a rename, so a crash leaves every line in some file.
+fn rotate_if_needed(log_file: &Path, incoming: u64, max_bytes: u64, keep: usize) -> Result<(), String> {
+    let size = match fs::metadata(log_file) {
+        Ok(meta) => meta.len(),
+        Err(_) => return Ok(()),
+    };
+    if size == 0 || size + incoming <= max_bytes {
+        return Ok(());
+    }
+    let _ = fs::remove_file(rotated_path(log_file, keep));
+    for n in (1..keep).rev() {
+        let from = rotated_path(log_file, n);
+        if from.exists() {
+            fs::rename(&from, rotated_path(log_file, n + 1))
+                .map_err(|e| format!("Failed to rotate log file: {}", e))?;
+        }
     }
+    fs::rename(log_file, rotated_path(log_file, 1)).map_err(|e| format!("Failed to rotate log file: {}", e))
+}
 
-    let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-    let log_entry = format!("[{}] {}\n", timestamp, message);
+fn append_log(log_file: &Path, entry: &str, max_bytes: u64, keep: usize) -> Result<(), String> {
+    let _guard = LOG_LOCK.lock().map_err(|e| e.to_string())?;
+    rotate_if_needed(log_file, entry.len() as u64, max_bytes, keep)?;
 
     let mut file = fs::OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&log_file)
+        .open(log_file)
         .map_err(|e| format!("Failed to open log file: {}", e))?;
 
-    file.write_all(log_entry.as_bytes())
-        .map_err(|e| format!("Failed to write log: {}", e))?;
+    file.write_all(entry.as_bytes())
+        .map_err(|e| format!("Failed to write log: {}", e))
+}
+
+pub(crate) fn flush() {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Ok(file) = log_file_path().and_then(|p| fs::File::open(p).map_err(|e| e.to_string())) {
+        let _ = file.sync_all();
+    }
+}
+
+// Text lines look like `[ts] [LEVEL] message`; a multi-line message runs on over the following
+// lines.
+fn format_entry(entry: &LogEntry, format: LogFormat) -> String {
+    match format {
+        LogFormat::Text => format!("[{}] [{}] {}\n", entry.ts, entry.level.to_uppercase(), entry.message),
+        LogFormat::Json => format!("{}\n", json!(entry)),
+    }
+}
+
+fn parse_entry_line(line: &str) -> Option<LogEntry> {
+    if line.starts_with('{') {
+        if let Ok(entry) = serde_json::from_str::<LogEntry>(line) {
+            return Some(entry);
+        }
+    }
+    let rest = line.strip_prefix('[')?;
+    let (ts, rest) = rest.split_once("] ")?;
+    chrono::NaiveDateTime::parse_from_str(ts, TEXT_TIMESTAMP).ok()?;
+    // Lines from before levels were written have none; they count as info.
+    let (level, message) = rest
+        .strip_prefix('[')
+        .and_then(|r| r.split_once("] "))
+        .map(|(level, message)| (level.to_lowercase(), message))
+        .filter(|(level, _)| LOG_LEVELS.contains(&level.as_str()))
+        .unwrap_or_else(|| ("info".to_string(), rest));
+    Some(LogEntry { ts: ts.to_string(), level, message: message.to_string(), source: None })
+}
+
+fn parse_log(content: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in content.lines() {
+        match (parse_entry_line(line), entries.last_mut()) {
+            (Some(entry), _) => entries.push(entry),
+            (None, Some(last)) => {
+                last.message.push('\n');
+                last.message.push_str(line);
+            }
+            (None, None) if line.is_empty() => {}
+            (None, None) => entries.push(LogEntry {
+                ts: String::new(),
+                level: "info".to_string(),
+                message: line.to_string(),
+                source: None,
+            }),
+        }
+    }
+    entries
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read log file: {}", e)),
+    }
+}
+
+fn read_log_chain(log_file: &Path, keep: usize) -> Result<LogFileContent, String> {
+    let mut content = String::new();
+    for n in (1..=keep).rev() {
+        if let Some(older) = read_optional(&rotated_path(log_file, n))? {
+            content.push_str(&older);
+        }
+    }
+    let current = read_optional(log_file)?;
+    let exists = current.is_some();
+    let size = current.as_ref().map(|c| c.len() as u64).unwrap_or(0);
+    content.push_str(&current.unwrap_or_default());
+    Ok(LogFileContent { content, exists, size })
+}
+
+fn tail_start(buf: &[u8], lines: usize) -> Option<usize> {
+    let body = buf.strip_suffix(b"\n").unwrap_or(buf);
+    body.iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, b)| **b == b'\n')
+        .nth(lines.saturating_sub(1))
+        .map(|(i, _)| i + 1)
+}
+
+fn read_tail(file: &mut fs::File, end: u64, lines: usize) -> std::io::Result<(u64, String)> {
+    let mut start = end;
+    let mut buf: Vec<u8> = Vec::new();
+    let offset = loop {
+        if let Some(i) = tail_start(&buf, lines) {
+            break start + i as u64;
+        }
+        if start == 0 {
+            break 0;
+        }
+        let from = start.saturating_sub(TAIL_CHUNK);
+        let mut chunk = vec![0; (start - from) as usize];
+        file.seek(SeekFrom::Start(from))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+        start = from;
+    };
+    let text = String::from_utf8_lossy(&buf[(offset - start) as usize..]).to_string();
+    if offset == 0 {
+        return Ok((0, text));
+    }
+    let mut skipped = 0;
+    for line in text.split_inclusive('\n') {
+        if parse_entry_line(line.trim_end_matches('\n')).is_some() {
+            break;
+        }
+        skipped += line.len();
+    }
+    if skipped == text.len() {
+        return Ok((offset, text));
+    }
+    Ok((offset + skipped as u64, text[skipped..].to_string()))
+}
+
+fn get_log_follow() -> &'static Mutex<Option<Arc<AtomicBool>>> {
+    LOG_FOLLOW.get_or_init(|| Mutex::new(None))
+}
+
+fn file_id(meta: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.ino()
+}
+
+struct LogFollower {
+    path: PathBuf,
+    keep: usize,
+    id: Option<u64>,
+    pos: u64,
+    partial: Vec<u8>,
+}
+
+impl LogFollower {
+    fn new(path: PathBuf, keep: usize) -> Self {
+        let meta = fs::metadata(&path).ok();
+        LogFollower {
+            keep,
+            id: meta.as_ref().map(file_id),
+            pos: meta.map(|m| m.len()).unwrap_or(0),
+            path,
+            partial: Vec::new(),
+        }
+    }
+
+    fn read_from(&mut self, path: &Path) {
+        let Ok(mut file) = fs::File::open(path) else { return };
+        if file.seek(SeekFrom::Start(self.pos)).is_ok() {
+            if let Ok(n) = file.read_to_end(&mut self.partial) {
+                self.pos += n as u64;
+            }
+        }
+    }
+
+    fn poll(&mut self) -> Vec<String> {
+        // Between a rotation and the next write there is no app.log.
+        let Ok(meta) = fs::metadata(&self.path) else { return Vec::new() };
+        let id = file_id(&meta);
+        if self.id != Some(id) {
+            let rotated: Vec<PathBuf> = (1..=self.keep).map(|n| rotated_path(&self.path, n)).collect();
+            let old = rotated
+                .iter()
+                .position(|p| self.id.is_some() && fs::metadata(p).ok().map(|m| file_id(&m)) == self.id);
+            if let Some(n) = old {
+                self.read_from(&rotated[n]);
+                for newer in rotated[..n].iter().rev() {
+                    self.pos = 0;
+                    self.read_from(newer);
+                }
+            }
+            self.id = Some(id);
+            self.pos = 0;
+        } else if meta.len() < self.pos {
+            // Truncated in place, as clear_log_file does.
+            self.pos = 0;
+            self.partial.clear();
+        }
+        if meta.len() > self.pos {
+            let path = self.path.clone();
+            self.read_from(&path);
+        }
+
+        let Some(end) = self.partial.iter().rposition(|b| *b == b'\n') else { return Vec::new() };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        String::from_utf8_lossy(&complete).lines().map(|l| l.to_string()).collect()
+    }
+}
+
+pub(crate) fn log(level: &str, source: &str, message: &str) -> Result<PathBuf, String> {
+    if !LOG_LEVELS.contains(&level) {
+        return Err(format!("Unknown log level: {}", level));
+    }
+    let log_file = log_file_path_with(true)?;
+    let format = super::settings::log_format();
+    let now = Local::now();
+    let entry = LogEntry {
+        ts: match format {
+            LogFormat::Text => now.format(TEXT_TIMESTAMP).to_string(),
+            LogFormat::Json => now.to_rfc3339_opts(SecondsFormat::Millis, false),
+        },
+        level: level.to_string(),
+        message: message.to_string(),
+        source: Some(source.to_string()),
+    };
+    let (max_bytes, keep) = super::settings::log_rotation();
+    append_log(&log_file, &format_entry(&entry, format), max_bytes, keep)?;
+    Ok(log_file)
+}
 
+#[tauri::command]
+pub fn write_log(message: String, level: Option<String>, source: Option<String>) -> Result<String, String> {
+    let level = level.map(|l| l.to_lowercase()).unwrap_or_else(|| "info".to_string());
+    let log_file = log(&level, source.as_deref().unwrap_or("frontend"), &message)?;
     Ok(log_file.to_string_lossy().to_string())
 }
 
+fn read_log_content(include_rotated: Option<bool>) -> Result<LogFileContent, String> {
+    super::settings::require_module("logs")?;
+    let keep = if include_rotated.unwrap_or(false) { super::settings::log_rotation().1 } else { 0 };
+    read_log_chain(&log_file_path()?, keep)
+}
+
 #[tauri::command]
-pub fn read_log_file() -> Result<String, String> {
+pub fn read_log_file(include_rotated: Option<bool>) -> Result<LogFileContent, String> {
+    read_log_content(include_rotated)
+}
+
+#[tauri::command]
+pub fn read_log_entries(include_rotated: Option<bool>) -> Result<serde_json::Value, String> {
+    Ok(json!(parse_log(&read_log_content(include_rotated)?.content)))
+}
+
+#[tauri::command]
+pub fn read_log_tail(lines: usize, before_cursor: Option<u64>) -> Result<serde_json::Value, String> {
+    super::settings::require_module("logs")?;
     let log_file = log_file_path()?;
-    fs::read_to_string(&log_file)
-        .map_err(|e| format!("Failed to read log file: {}", e))
+    let mut file = match fs::File::open(&log_file) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(json!({"entries": [], "cursor": null, "exists": false}));
+        }
+        Err(e) => return Err(format!("Failed to read log file: {}", e)),
+    };
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+    // A cursor past the end is from before a rotation; start over at the end.
+    let end = before_cursor.filter(|c| *c <= size).unwrap_or(size);
+    let (offset, text) = read_tail(&mut file, end, lines.clamp(1, TAIL_MAX_LINES))
+        .map_err(|e| format!("Failed to read log file: {}", e))?;
+    Ok(json!({
+        "entries": parse_log(&text),
+        "cursor": if offset > 0 { Some(offset) } else { None },
+        "exists": true,
+    }))
 }
 
 #[tauri::command]
-pub fn clear_log_file() -> Result<String, String> {
+pub fn log_file_info() -> Result<serde_json::Value, String> {
+    let log_file = log_file_path()?;
+    let Ok(meta) = fs::metadata(&log_file) else {
+        return Ok(json!({"path": log_file.to_string_lossy(), "exists": false, "size": 0, "line_estimate": 0, "rotated": []}));
+    };
+    let size = meta.len();
+    let mut sample = Vec::new();
+    if let Ok(mut file) = fs::File::open(&log_file) {
+        let from = size.saturating_sub(TAIL_CHUNK);
+        if file.seek(SeekFrom::Start(from)).is_ok() {
+            let _ = file.read_to_end(&mut sample);
+        }
+    }
+    let newlines = sample.iter().filter(|b| **b == b'\n').count() as u64;
+    let line_estimate = match sample.len() as u64 {
+        0 => 0,
+        len if len >= size => newlines,
+        len => size * newlines / len,
+    };
+    let rotated: Vec<serde_json::Value> = (1..=super::settings::log_rotation().1)
+        .filter_map(|n| {
+            let path = rotated_path(&log_file, n);
+            let size = fs::metadata(&path).ok()?.len();
+            Some(json!({"path": path.to_string_lossy(), "size": size}))
+        })
+        .collect();
+    Ok(json!({
+        "path": log_file.to_string_lossy(),
+        "exists": true,
+        "size": size,
+        "modified": meta.modified().ok().map(|t| chrono::DateTime::<Local>::from(t).to_rfc3339()),
+        "line_estimate": line_estimate,
+        "rotated": rotated,
+    }))
+}
+
+#[tauri::command]
+pub fn start_log_follow(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    let mut current = get_log_follow().lock().map_err(|e| e.to_string())?;
+    if current.is_some() {
+        return Ok(json!({"success": true, "already_running": true}));
+    }
+    let mut follower = LogFollower::new(log_file_path()?, super::settings::log_rotation().1);
+    *current = Some(super::tasks::spawn("log-follow", move |task| {
+        while !task.stopped() {
+            std::thread::sleep(FOLLOW_POLL_INTERVAL);
+            for line in follower.poll() {
+                let entry = parse_entry_line(&line);
+                let _ = app.emit("log-line", json!({"line": line, "entry": entry}));
+            }
+        }
+    }));
+    drop(current);
+
+    Ok(json!({"success": true, "already_running": false}))
+}
+
+#[tauri::command]
+pub fn stop_log_follow() -> Result<serde_json::Value, String> {
+    Ok(json!({"success": true, "stopped": stop_following()}))
+}
+
+pub fn stop_following() -> bool {
+    match get_log_follow().lock().ok().and_then(|mut current| current.take()) {
+        Some(stop) => {
+            stop.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LogMatch {
+    file: String,
+    line: usize,
+    offset: u64,
+    entry: LogEntry,
+}
+
+fn search_reader(
+    reader: impl BufRead,
+    file: &str,
+    matcher: &regex::Regex,
+    level: Option<&str>,
+    max: usize,
+    out: &mut Vec<LogMatch>,
+) -> std::io::Result<bool> {
+    let accept = |m: &LogMatch| level.map_or(true, |l| m.entry.level == l) && matcher.is_match(&m.entry.message);
+    let mut pending: Option<LogMatch> = None;
+    let mut offset = 0u64;
+    for (i, line) in reader.split(b'\n').enumerate() {
+        let bytes = line?;
+        let text = String::from_utf8_lossy(&bytes);
+        let line_offset = offset;
+        offset += bytes.len() as u64 + 1;
+
+        let entry = parse_entry_line(&text);
+        if let (None, Some(current)) = (&entry, pending.as_mut()) {
+            current.entry.message.push('\n');
+            current.entry.message.push_str(&text);
+            continue;
+        }
+        if let Some(done) = pending.take().filter(|m| accept(m)) {
+            if out.len() >= max {
+                return Ok(true);
+            }
+            out.push(done);
+        }
+        let entry = entry.unwrap_or_else(|| LogEntry {
+            ts: String::new(),
+            level: "info".to_string(),
+            message: text.to_string(),
+            source: None,
+        });
+        pending = Some(LogMatch { file: file.to_string(), line: i + 1, offset: line_offset, entry });
+    }
+    if let Some(done) = pending.filter(|m| accept(m)) {
+        if out.len() >= max {
+            return Ok(true);
+        }
+        out.push(done);
+    }
+    Ok(false)
+}
+
+fn build_matcher(query: &str, regex: bool, case_sensitive: bool) -> Result<regex::Regex, String> {
+    let pattern = if regex { query.to_string() } else { regex::escape(query) };
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regular expression: {}", e))
+}
+
+#[tauri::command]
+pub fn search_log(
+    query: String,
+    regex: Option<bool>,
+    case_sensitive: Option<bool>,
+    max_results: Option<usize>,
+    level: Option<String>,
+) -> Result<serde_json::Value, String> {
+    super::settings::require_module("logs")?;
+    let matcher = build_matcher(&query, regex.unwrap_or(false), case_sensitive.unwrap_or(false))?;
+    let max = max_results.unwrap_or(SEARCH_DEFAULT_RESULTS).clamp(1, SEARCH_MAX_RESULTS);
+    let level = level.map(|l| l.to_lowercase());
+
     let log_file = log_file_path()?;
+    let mut files: Vec<PathBuf> = (1..=super::settings::log_rotation().1)
+        .rev()
+        .map(|n| rotated_path(&log_file, n))
+        .collect();
+    files.push(log_file);
+
+    let mut results = Vec::new();
+    let mut truncated = false;
+    for path in files {
+        let Ok(file) = fs::File::open(&path) else { continue };
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        truncated = search_reader(BufReader::new(file), &name, &matcher, level.as_deref(), max, &mut results)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        if truncated {
+            break;
+        }
+    }
+    Ok(json!({"results": results, "truncated": truncated}))
+}
+
+#[tauri::command]
+pub fn clear_log_file() -> Result<String, String> {
+    let log_file = log_file_path_with(true)?;
+    let keep = super::settings::log_rotation().1;
+    let _guard = LOG_LOCK.lock().map_err(|e| e.to_string())?;
     fs::write(&log_file, "")
         .map_err(|e| format!("Failed to clear log file: {}", e))?;
+    for n in 1..=keep {
+        let _ = fs::remove_file(rotated_path(&log_file, n));
+    }
     Ok(log_file.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gantry_log_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("app.log")
+    }
+
+    #[test]
+    fn test_rotation_keeps_every_line() {
+        let log = scratch_log("chain");
+        let lines: Vec<String> = (0..40).map(|i| format!("line {:02} {}\n", i, "x".repeat(20))).collect();
+        for line in &lines {
+            append_log(&log, line, 200, 3).unwrap();
+        }
+
+        for path in [log.clone(), rotated_path(&log, 1), rotated_path(&log, 2), rotated_path(&log, 3)] {
+            let content = fs::read_to_string(&path).unwrap();
+            assert!(content.len() <= 200, "{} is over the cap", path.display());
+            assert!(content.ends_with('\n'), "lines are never split across files");
+        }
+        assert!(!rotated_path(&log, 4).exists(), "only `keep` rotated files remain");
+
+        let chain = read_log_chain(&log, 3).unwrap().content;
+        let expected: String = lines.concat();
+        assert!(expected.ends_with(&chain), "the chain is the newest lines in order");
+        assert!(chain.ends_with(&lines[39]));
+        let _ = fs::remove_dir_all(log.parent().unwrap());
+    }
+
+    #[test]
+    fn test_rotation_at_the_boundary() {
+        let log = scratch_log("boundary");
+        append_log(&log, "aaaa\n", 10, 2).unwrap();
+        append_log(&log, "bbbb\n", 10, 2).unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), "aaaa\nbbbb\n", "exactly at the cap still fits");
+        append_log(&log, "cccc\n", 10, 2).unwrap();
+        assert_eq!(fs::read_to_string(rotated_path(&log, 1)).unwrap(), "aaaa\nbbbb\n");
+        assert_eq!(fs::read_to_string(&log).unwrap(), "cccc\n");
+
+        // An entry larger than the cap still gets written, alone in its file.
+        let big = "d".repeat(30) + "\n";
+        append_log(&log, &big, 10, 2).unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), big);
+        assert_eq!(fs::read_to_string(rotated_path(&log, 2)).unwrap(), "aaaa\nbbbb\n");
+        assert_eq!(read_log_chain(&log, 2).unwrap().content, format!("aaaa\nbbbb\ncccc\n{}", big));
+        let _ = fs::remove_dir_all(log.parent().unwrap());
+    }
+
+    #[test]
+    fn test_missing_log_reads_as_empty() {
+        let log = scratch_log("missing");
+        let missing = read_log_chain(&log, 0).unwrap();
+        assert_eq!((missing.content.as_str(), missing.exists, missing.size), ("", false, 0));
+
+        fs::write(&log, "").unwrap();
+        let cleared = read_log_chain(&log, 0).unwrap();
+        assert_eq!((cleared.exists, cleared.size), (true, 0));
+
+        // Rotated files are still read when app.log itself is gone.
+        fs::rename(&log, rotated_path(&log, 1)).unwrap();
+        fs::write(rotated_path(&log, 1), "older\n").unwrap();
+        let rotated = read_log_chain(&log, 2).unwrap();
+        let _ = fs::remove_dir_all(log.parent().unwrap());
+        assert_eq!((rotated.content.as_str(), rotated.exists), ("older\n", false));
+    }
+
+    #[test]
+    fn test_read_tail_pages_back_through_the_file() {
+        let log = scratch_log("tail");
+        let mut content = String::new();
+        for i in 0..500 {
+            content.push_str(&format!("[2026-03-04 05:06:07.890] [INFO] entry {}\n", i));
+            if i % 100 == 50 {
+                content.push_str("  continued\n");
+            }
+        }
+        fs::write(&log, &content).unwrap();
+        let mut file = fs::File::open(&log).unwrap();
+
+        let (cursor, text) = read_tail(&mut file, content.len() as u64, 10).unwrap();
+        let entries = parse_log(&text);
+        assert_eq!(entries.len(), 10);
+        assert_eq!(entries[9].message, "entry 499");
+        assert_eq!(&content[cursor as usize..], text);
+
+        // Walk back to the start; every entry turns up once, in order.
+        let (mut end, mut seen) = (content.len() as u64, Vec::new());
+        loop {
+            let (cursor, text) = read_tail(&mut file, end, 7).unwrap();
+            let mut page = parse_log(&text);
+            page.append(&mut seen);
+            seen = page;
+            if cursor == 0 {
+                break;
+            }
+            end = cursor;
+        }
+        let _ = fs::remove_dir_all(log.parent().unwrap());
+        assert_eq!(seen.len(), 500);
+        assert_eq!(seen[150].message, "entry 150\n  continued", "continuation lines stay with their entry");
+        assert!(seen.iter().enumerate().all(|(i, e)| e.message.starts_with(&format!("entry {}", i))));
+    }
+
+    #[test]
+    fn test_follower_survives_rotation_and_truncation() {
+        let log = scratch_log("follow");
+        append_log(&log, "old\n", 1024, 2).unwrap();
+        let mut follower = LogFollower::new(log.clone(), 2);
+        assert!(follower.poll().is_empty(), "existing lines are not replayed");
+
+        append_log(&log, "one\n", 1024, 2).unwrap();
+        fs::OpenOptions::new().append(true).open(&log).unwrap().write_all(b"two, half").unwrap();
+        assert_eq!(follower.poll(), ["one"]);
+
+        fs::OpenOptions::new().append(true).open(&log).unwrap().write_all(b" done\n").unwrap();
+        append_log(&log, &"x".repeat(1100), 1024, 2).unwrap();
+        append_log(&log, "\nthree\n", 1024, 2).unwrap();
+        assert_eq!(follower.poll(), ["two, half done".to_string(), "x".repeat(1100), String::new(), "three".to_string()]);
+
+        fs::write(&log, "").unwrap();
+        append_log(&log, "four\n", 1024, 2).unwrap();
+        let lines = follower.poll();
+        let _ = fs::remove_dir_all(log.parent().unwrap());
+        assert_eq!(lines, ["four"]);
+    }
+
+    #[test]
+    fn test_search_reader() {
+        let content = "[2026-03-04 05:06:07.890] [INFO] Started\n\
+                       [2026-03-04 05:06:08.000] [ERROR] Disk full\n\
+                       while writing /var/tmp\n\
+                       {\"ts\":\"2026-03-04T05:07:00.000+01:00\",\"level\":\"warn\",\"message\":\"disk almost full\",\"source\":\"alerts\"}\n";
+        let search = |query: &str, regex: bool, case_sensitive: bool, level: Option<&str>, max: usize| {
+            let matcher = build_matcher(query, regex, case_sensitive).unwrap();
+            let mut out = Vec::new();
+            let truncated = search_reader(content.as_bytes(), "app.log", &matcher, level, max, &mut out).unwrap();
+            (out, truncated)
+        };
+
+        let (found, truncated) = search("disk", false, false, None, 10);
+        assert!(!truncated);
+        assert_eq!(found.iter().map(|m| m.line).collect::<Vec<_>>(), [2, 4]);
+        assert_eq!(found[1].offset as usize, content.find("{\"ts\"").unwrap());
+
+        assert_eq!(search("disk", false, true, None, 10).0.len(), 1);
+        assert_eq!(search("disk", false, false, Some("error"), 10).0.len(), 1);
+        assert_eq!(search(r"/var/\w+", true, false, None, 10).0[0].line, 2, "continuation lines are searched");
+        assert_eq!(search("(", false, false, None, 10).0.len(), 0, "plain queries are not patterns");
+
+        let (found, truncated) = search("d", false, false, None, 1);
+        assert_eq!(found.len(), 1);
+        assert!(truncated);
+        assert!(!search("Started", false, false, None, 1).1, "exactly max matches is not truncated");
+
+        assert!(build_matcher("(unclosed", true, false).unwrap_err().starts_with("Invalid regular expression"));
+    }
+
+    #[test]
+    fn test_tail_start() {
+        assert_eq!(tail_start(b"a\nb\nc\n", 2), Some(2));
+        assert_eq!(tail_start(b"a\nb\nc", 1), Some(4));
+        assert_eq!(tail_start(b"a\nb\n", 2), None, "the first line may be cut, so it needs a newline before it");
+    }
+
+    #[test]
+    fn test_entries_round_trip_in_both_formats() {
+        let messages = [
+            "plain",
+            "said \"hello\" and left",
+            "first line\nsecond line\n{\"not\": \"an entry\"}",
+            "[ERROR] trailing backslash \\",
+        ];
+        for format in [LogFormat::Text, LogFormat::Json] {
+            let written: Vec<LogEntry> = messages
+                .iter()
+                .map(|m| LogEntry {
+                    ts: "2026-03-04 05:06:07.890".to_string(),
+                    level: "warn".to_string(),
+                    message: m.to_string(),
+                    source: Some("frontend".to_string()),
+                })
+                .collect();
+            let content: String = written.iter().map(|e| format_entry(e, format)).collect();
+            let read = parse_log(&content);
+            assert_eq!(read.len(), written.len(), "{:?}", format);
+            for (read, written) in read.iter().zip(&written) {
+                assert_eq!(read.message, written.message, "{:?}", format);
+                assert_eq!(read.level, "warn");
+                if format == LogFormat::Json {
+                    assert_eq!(read, written);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_mixed_format_log() {
+        let content = "[2026-03-04 05:06:07.890] Settings file moved aside\n\
+                       [2026-03-04 05:06:08.000] [ERROR] Failed to load\n\
+                       {\"ts\":\"2026-03-04T05:07:00.000+01:00\",\"level\":\"info\",\"message\":\"switched\",\"source\":\"settings\"}\n\
+                       [2026-03-04 05:08:00.000] [WARN] back to text\n\
+                       with a second line\n";
+        let entries = parse_log(content);
+        assert_eq!(entries.len(), 4);
+        assert_eq!((entries[0].level.as_str(), entries[0].message.as_str()), ("info", "Settings file moved aside"));
+        assert_eq!(entries[1].level, "error");
+        assert_eq!(entries[2].source.as_deref(), Some("settings"));
+        assert_eq!(entries[3].message, "back to text\nwith a second line");
+    }
+}
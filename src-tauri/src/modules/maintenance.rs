@@ -0,0 +1,294 @@
+use super::error::GantryError;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+const CRASH_DUMP_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[cfg(target_os = "linux")]
+const JOURNAL_KEEP: &str = "100M";
+
+#[cfg(target_os = "linux")]
+const CRASH_DIRS: &[&str] = &["/var/crash", "/var/lib/systemd/coredump"];
+
+#[cfg(target_os = "linux")]
+const APT_ARCHIVES: &str = "/var/cache/apt/archives";
+
+#[cfg(target_os = "linux")]
+const TARGETS: &[(&str, &str, bool)] = &[
+    ("trash", "Trash", false),
+    ("thumbnails", "Thumbnail cache", false),
+    ("journal", "System journal", true),
+    ("apt_cache", "APT package cache", true),
+    ("crash_dumps", "Old crash dumps", true),
+];
+
+#[cfg(target_os = "macos")]
+const TARGETS: &[(&str, &str, bool)] = &[("trash", "Trash", false), ("crash_dumps", "Old crash reports", false)];
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else { return 0 };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| dir_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+fn old_files(dirs: &[PathBuf], max_age: Duration, now: SystemTime) -> Vec<(PathBuf, u64)> {
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            let age = now.duration_since(meta.modified().ok()?).ok()?;
+            (age > max_age).then(|| (entry.path(), meta.len()))
+        })
+        .collect()
+}
+
+fn clear_dir(dir: &Path) -> Result<(), GantryError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(GantryError::from(e).context(&dir.display().to_string())),
+    };
+    let mut first_error = None;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let removed = match entry.file_type() {
+            Ok(t) if t.is_dir() => fs::remove_dir_all(&path),
+            _ => fs::remove_file(&path),
+        };
+        if let Err(e) = removed {
+            first_error.get_or_insert_with(|| GantryError::from(e).context(&path.display().to_string()));
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim_end_matches('B') {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_journal_usage(output: &str) -> Option<u64> {
+    let rest = output.split("take up ").nth(1)?;
+    parse_size(rest.split_whitespace().next()?)
+}
+
+#[cfg(target_os = "linux")]
+fn trash_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("Trash"))
+}
+
+#[cfg(target_os = "macos")]
+fn trash_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".Trash"))
+}
+
+#[cfg(target_os = "linux")]
+fn crash_dirs() -> Vec<PathBuf> {
+    CRASH_DIRS.iter().map(PathBuf::from).collect()
+}
+
+#[cfg(target_os = "macos")]
+fn crash_dirs() -> Vec<PathBuf> {
+    dirs::home_dir().map(|h| vec![h.join("Library/Logs/DiagnosticReports")]).unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn empty_trash(trash: &Path) -> Result<(), GantryError> {
+    ["files", "info", "expunged"].iter().map(|sub| clear_dir(&trash.join(sub))).fold(Ok(()), Result::and)
+}
+
+#[cfg(target_os = "macos")]
+fn empty_trash(trash: &Path) -> Result<(), GantryError> {
+    clear_dir(trash)
+}
+
+#[cfg(target_os = "linux")]
+fn journal_usage() -> Option<u64> {
+    let output = Command::new("journalctl").arg("--disk-usage").env("LC_ALL", "C").output().ok()?;
+    parse_journal_usage(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn available(target: &str) -> bool {
+    match target {
+        #[cfg(target_os = "linux")]
+        "journal" => super::system::has_binary("journalctl"),
+        #[cfg(target_os = "linux")]
+        "apt_cache" => Path::new(APT_ARCHIVES).is_dir(),
+        _ => true,
+    }
+}
+
+fn reclaimable(target: &str) -> Result<u64, GantryError> {
+    match target {
+        "trash" => Ok(trash_dir().map(|d| dir_size(&d)).unwrap_or(0)),
+        #[cfg(target_os = "linux")]
+        "thumbnails" => Ok(dirs::cache_dir().map(|d| dir_size(&d.join("thumbnails"))).unwrap_or(0)),
+        #[cfg(target_os = "linux")]
+        "journal" => journal_usage()
+            .map(|usage| usage.saturating_sub(parse_size(JOURNAL_KEEP).unwrap_or(0)))
+            .ok_or_else(|| GantryError::Failed("Could not read the journal size".into())),
+        #[cfg(target_os = "linux")]
+        "apt_cache" => Ok(dir_size(Path::new(APT_ARCHIVES))),
+        "crash_dumps" => Ok(old_files(&crash_dirs(), CRASH_DUMP_MAX_AGE, SystemTime::now()).iter().map(|(_, len)| len).sum()),
+        other => Err(GantryError::NotFound(format!("Unknown cleanup target: {}", other))),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn cleanup_command(target: &str) -> Result<Vec<String>, GantryError> {
+    let command: Vec<String> = match target {
+        "journal" => vec!["journalctl".into(), format!("--vacuum-size={}", JOURNAL_KEEP)],
+        "apt_cache" => vec!["apt-get".into(), "clean".into()],
+        "crash_dumps" => {
+            let dirs: Vec<String> = CRASH_DIRS.iter().filter(|d| Path::new(d).is_dir()).map(|d| d.to_string()).collect();
+            if dirs.is_empty() {
+                return Ok(Vec::new());
+            }
+            let minutes = (CRASH_DUMP_MAX_AGE.as_secs() / 60).to_string();
+            let mut command = vec!["find".to_string()];
+            command.extend(dirs);
+            command.extend(["-maxdepth", "1", "-xdev", "-type", "f", "-mmin"].map(String::from));
+            command.extend([format!("+{}", minutes), "-delete".into()]);
+            command
+        }
+        other => return Err(GantryError::Unsupported(format!("{} isn't a system cleanup", other))),
+    };
+    Ok(command)
+}
+
+fn clean(target: &str) -> Result<(), GantryError> {
+    match target {
+        "trash" => trash_dir().map_or(Ok(()), |trash| empty_trash(&trash)),
+        #[cfg(target_os = "linux")]
+        "thumbnails" => dirs::cache_dir().map_or(Ok(()), |d| clear_dir(&d.join("thumbnails"))),
+        #[cfg(target_os = "linux")]
+        "journal" | "apt_cache" | "crash_dumps" => {
+            let command = cleanup_command(target)?;
+            if command.is_empty() {
+                return Ok(());
+            }
+            super::admin::cleanup(target, &command)
+        }
+        #[cfg(target_os = "macos")]
+        "crash_dumps" => {
+            let mut first_error = None;
+            for (path, _) in old_files(&crash_dirs(), CRASH_DUMP_MAX_AGE, SystemTime::now()) {
+                if let Err(e) = fs::remove_file(&path) {
+                    first_error.get_or_insert_with(|| GantryError::from(e).context(&path.display().to_string()));
+                }
+            }
+            first_error.map_or(Ok(()), Err)
+        }
+        other => Err(GantryError::NotFound(format!("Unknown cleanup target: {}", other))),
+    }
+}
+
+#[tauri::command]
+pub async fn get_cleanup_candidates() -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let candidates: Vec<Value> = TARGETS
+            .iter()
+            .filter(|(id, _, _)| available(id))
+            .map(|(id, label, system)| {
+                let (bytes, error) = match reclaimable(id) {
+                    Ok(bytes) => (Some(bytes), None),
+                    Err(e) => (None, Some(e.message())),
+                };
+                json!({"id": id, "label": label, "system": system, "bytes": bytes, "error": error})
+            })
+            .collect();
+        Ok(json!(candidates))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn run_cleanup(targets: Vec<String>) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut total = 0;
+        let results: Vec<Value> = targets
+            .iter()
+            .map(|target| {
+                if !TARGETS.iter().any(|(id, _, _)| id == target) || !available(target) {
+                    let error = GantryError::NotFound(format!("Unknown cleanup target: {}", target));
+                    return json!({"target": target, "success": false, "bytes_freed": 0, "error": error});
+                }
+                let before = reclaimable(target).unwrap_or(0);
+                let result = clean(target);
+                let freed = before.saturating_sub(reclaimable(target).unwrap_or(before));
+                total += freed;
+                let _ = super::logging::log(
+                    if result.is_ok() { "info" } else { "warn" },
+                    "maintenance",
+                    &format!("Cleanup {} freed {} bytes{}", target, freed, result.as_ref().err().map(|e| format!(": {}", e)).unwrap_or_default()),
+                );
+                json!({"target": target, "success": result.is_ok(), "bytes_freed": freed, "error": result.err()})
+            })
+            .collect();
+        Ok(json!({"results": results, "bytes_freed": total}))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_journal_usage() {
+        let output = "Archived and active journals take up 1.5G in the file system.\n";
+        assert_eq!(parse_journal_usage(output), Some(1_610_612_736));
+        assert_eq!(parse_journal_usage("Journals take up 512.0K on disk."), Some(524_288));
+        assert_eq!(parse_size("48B"), Some(48));
+        assert_eq!(parse_size("3X"), None);
+        assert_eq!(parse_journal_usage("No journal files were found."), None);
+        assert_eq!(parse_size(JOURNAL_KEEP), Some(100 << 20));
+    }
+
+    #[test]
+    fn test_clear_dir_and_old_files() {
+        let dir = std::env::temp_dir().join(format!("gantry_cleanup_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.crash"), vec![0u8; 100]).unwrap();
+        fs::write(dir.join("nested/b"), vec![0u8; 20]).unwrap();
+        assert_eq!(dir_size(&dir), 120);
+
+        let now = SystemTime::now();
+        assert!(old_files(std::slice::from_ref(&dir), CRASH_DUMP_MAX_AGE, now).is_empty());
+        let later = now + CRASH_DUMP_MAX_AGE + Duration::from_secs(60);
+        let old = old_files(std::slice::from_ref(&dir), CRASH_DUMP_MAX_AGE, later);
+        assert_eq!(old, vec![(dir.join("a.crash"), 100)], "only files directly in the directory");
+
+        clear_dir(&dir).unwrap();
+        assert!(dir.is_dir());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        assert!(clear_dir(&dir.join("missing")).is_ok());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
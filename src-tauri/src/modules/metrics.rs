@@ -0,0 +1,323 @@
+use super::error::GantryError;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+struct Endpoint {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    stopped: mpsc::Receiver<()>,
+}
+
+static ENDPOINT: Mutex<Option<Endpoint>> = Mutex::new(None);
+
+struct Counters {
+    at: Instant,
+    network: BTreeMap<String, (u64, u64)>,
+    disk_io: BTreeMap<String, (u64, u64)>,
+}
+
+static PREVIOUS: Mutex<Option<Counters>> = Mutex::new(None);
+
+fn counters(resources: &Value, at: Instant) -> Counters {
+    let pairs = |list: &Value, a: &str, b: &str| -> BTreeMap<String, (u64, u64)> {
+        list.as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| Some((v["name"].as_str()?.to_string(), (v[a].as_u64()?, v[b].as_u64()?))))
+            .collect()
+    };
+    Counters {
+        at,
+        network: pairs(&resources["network"], "rx_bytes", "tx_bytes"),
+        disk_io: pairs(&resources["disk_io"], "read_bytes", "write_bytes"),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, value);
+        return;
+    }
+    let labels: Vec<String> = labels.iter().map(|(k, v)| format!("{}=\"{}\"", k, escape(v))).collect();
+    let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+}
+
+fn rates(current: &BTreeMap<String, (u64, u64)>, previous: &BTreeMap<String, (u64, u64)>, secs: f64) -> Vec<(String, f64, f64)> {
+    if secs <= 0.0 {
+        return Vec::new();
+    }
+    current
+        .iter()
+        .filter_map(|(name, &(a, b))| {
+            let &(pa, pb) = previous.get(name)?;
+            Some((name.clone(), a.saturating_sub(pa) as f64 / secs, b.saturating_sub(pb) as f64 / secs))
+        })
+        .collect()
+}
+
+fn render(resources: &Value, failed_services: Option<usize>, previous: Option<&Counters>, current: &Counters) -> String {
+    let mut out = String::new();
+    let num = |v: &Value| v.as_f64().unwrap_or(0.0);
+
+    family(&mut out, "gantry_cpu_usage_percent", "gauge", "CPU usage across all cores.");
+    sample(&mut out, "gantry_cpu_usage_percent", &[], num(&resources["cpu"]));
+    family(&mut out, "gantry_load_average", "gauge", "System load average.");
+    for (i, period) in ["1m", "5m", "15m"].iter().enumerate() {
+        sample(&mut out, "gantry_load_average", &[("period", period)], num(&resources["load_avg"][i]));
+    }
+    family(&mut out, "gantry_uptime_seconds", "gauge", "Time since boot.");
+    sample(&mut out, "gantry_uptime_seconds", &[], num(&resources["uptime"]));
+
+    let memory = &resources["memory"];
+    for (name, field, help) in [
+        ("gantry_memory_used_bytes", "used", "Memory in use."),
+        ("gantry_memory_total_bytes", "total", "Installed memory."),
+        ("gantry_swap_used_bytes", "swap_used", "Swap in use."),
+        ("gantry_swap_total_bytes", "swap_total", "Swap available."),
+    ] {
+        family(&mut out, name, "gauge", help);
+        sample(&mut out, name, &[], num(&memory[field]));
+    }
+
+    let disks: Vec<&Value> = resources["disks"].as_array().into_iter().flatten().collect();
+    family(&mut out, "gantry_disk_total_bytes", "gauge", "Size of each mounted filesystem.");
+    for d in &disks {
+        let labels = [("device", d["name"].as_str().unwrap_or("")), ("mount", d["mount_point"].as_str().unwrap_or(""))];
+        sample(&mut out, "gantry_disk_total_bytes", &labels, num(&d["total_space"]));
+    }
+    family(&mut out, "gantry_disk_available_bytes", "gauge", "Free space on each mounted filesystem.");
+    for d in &disks {
+        let labels = [("device", d["name"].as_str().unwrap_or("")), ("mount", d["mount_point"].as_str().unwrap_or(""))];
+        sample(&mut out, "gantry_disk_available_bytes", &labels, num(&d["available_space"]));
+    }
+
+    let secs = previous.map(|p| current.at.duration_since(p.at).as_secs_f64()).unwrap_or(0.0);
+    for (prefix, list, prev, what, (a, b)) in [
+        ("gantry_disk", &current.disk_io, previous.map(|p| &p.disk_io), "device", ("read", "written")),
+        ("gantry_network", &current.network, previous.map(|p| &p.network), "interface", ("received", "transmitted")),
+    ] {
+        for (direction, pick) in [(a, 0), (b, 1)] {
+            let name = format!("{}_{}_bytes_total", prefix, direction);
+            family(&mut out, &name, "counter", &format!("Bytes {} per {}.", direction, what));
+            for (label, pair) in list {
+                let value = if pick == 0 { pair.0 } else { pair.1 };
+                sample(&mut out, &name, &[(what, label.as_str())], value as f64);
+            }
+        }
+        let Some(prev) = prev else { continue };
+        let per_second = rates(list, prev, secs);
+        for (direction, pick) in [(a, 0), (b, 1)] {
+            let name = format!("{}_{}_bytes_per_second", prefix, direction);
+            family(&mut out, &name, "gauge", &format!("Bytes {} per second per {} since the last scrape.", direction, what));
+            for (label, ra, rb) in &per_second {
+                sample(&mut out, &name, &[(what, label.as_str())], if pick == 0 { *ra } else { *rb });
+            }
+        }
+    }
+
+    family(&mut out, "gantry_temperature_celsius", "gauge", "Temperature sensor readings.");
+    for t in resources["temperatures"].as_array().into_iter().flatten() {
+        let labels = [("sensor", t["sensor"].as_str().unwrap_or("")), ("label", t["label"].as_str().unwrap_or(""))];
+        sample(&mut out, "gantry_temperature_celsius", &labels, num(&t["celsius"]));
+    }
+
+    if let Some(failed) = failed_services {
+        family(&mut out, "gantry_failed_services", "gauge", "System and user services in the failed state.");
+        sample(&mut out, "gantry_failed_services", &[], failed as f64);
+    }
+    out
+}
+
+fn collect() -> String {
+    let resources = super::system::get_resources().unwrap_or(Value::Null);
+    let failed = super::services::failed_service_count();
+    let current = counters(&resources, Instant::now());
+    let mut previous = PREVIOUS.lock().unwrap_or_else(|e| e.into_inner());
+    let text = render(&resources, failed, previous.as_ref(), &current);
+    *previous = Some(current);
+    text
+}
+
+fn text_response(status: StatusCode, content_type: &'static str, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+async fn respond(request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    if request.uri().path() != "/metrics" {
+        return Ok(text_response(StatusCode::NOT_FOUND, "text/plain", "Not found; metrics are at /metrics\n".into()));
+    }
+    // Sampling reads /proc and runs systemctl, so keep it off the reactor.
+    Ok(match tauri::async_runtime::spawn_blocking(collect).await {
+        Ok(body) => text_response(StatusCode::OK, METRICS_CONTENT_TYPE, body),
+        Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, "text/plain", format!("{}\n", e)),
+    })
+}
+
+async fn serve(listener: std::net::TcpListener, mut shutdown: oneshot::Receiver<()>, stopped: mpsc::Sender<()>) {
+    let listener = match tokio::net::TcpListener::from_std(listener) {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = super::logging::log("error", "metrics", &format!("Metrics endpoint failed: {}", e));
+            let _ = stopped.send(());
+            return;
+        }
+    };
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            accepted = listener.accept() => {
+                let Ok((stream, _)) = accepted else { continue };
+                tauri::async_runtime::spawn(async move {
+                    let _ = http1::Builder::new().serve_connection(TokioIo::new(stream), service_fn(respond)).await;
+                });
+            }
+        }
+    }
+    drop(listener);
+    let _ = stopped.send(());
+}
+
+fn socket_addr(bind: &str, port: u16) -> Result<SocketAddr, GantryError> {
+    let ip: IpAddr = bind
+        .parse()
+        .map_err(|_| GantryError::ParseError(format!("{} is not an IP address to bind to", bind)))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+fn start(addr: SocketAddr) -> Result<(), GantryError> {
+    let listener = std::net::TcpListener::bind(addr).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AddrInUse => GantryError::Failed(format!("{} is already in use", addr)),
+        _ => GantryError::from(e).context(&format!("Could not listen on {}", addr)),
+    })?;
+    listener.set_nonblocking(true)?;
+    let (shutdown, shutdown_rx) = oneshot::channel();
+    let (stopped_tx, stopped) = mpsc::channel();
+    tauri::async_runtime::spawn(serve(listener, shutdown_rx, stopped_tx));
+    *ENDPOINT.lock().unwrap_or_else(|e| e.into_inner()) = Some(Endpoint { addr, shutdown, stopped });
+    Ok(())
+}
+
+pub(crate) fn stop() {
+    let Some(endpoint) = ENDPOINT.lock().unwrap_or_else(|e| e.into_inner()).take() else { return };
+    let _ = endpoint.shutdown.send(());
+    let _ = endpoint.stopped.recv_timeout(STOP_TIMEOUT);
+}
+
+pub(crate) fn restart_from_settings() {
+    stop();
+    let (enabled, bind, port) = super::settings::metrics_endpoint();
+    if !enabled {
+        return;
+    }
+    if let Err(e) = socket_addr(&bind, port).and_then(start) {
+        let _ = super::logging::log("error", "metrics", &format!("Metrics endpoint not started: {}", e));
+    }
+}
+
+fn status() -> Value {
+    match ENDPOINT.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(endpoint) => json!({"running": true, "url": format!("http://{}/metrics", endpoint.addr)}),
+        None => json!({"running": false, "url": null}),
+    }
+}
+
+// Binding to anything but loopback exposes the metrics to the network.
+#[tauri::command]
+pub fn set_metrics_endpoint(enabled: bool, bind: Option<String>, port: Option<u16>) -> Result<Value, GantryError> {
+    let (_, current_bind, current_port) = super::settings::metrics_endpoint();
+    let bind = bind.map(|b| b.trim().to_string()).unwrap_or(current_bind);
+    let port = port.unwrap_or(current_port);
+    let addr = socket_addr(&bind, port)?;
+
+    stop();
+    if enabled {
+        if let Err(e) = start(addr) {
+            restart_from_settings();
+            return Err(e);
+        }
+    }
+    if let Err(e) = super::settings::set_metrics_endpoint(enabled, bind, port) {
+        restart_from_settings();
+        return Err(e.into());
+    }
+    Ok(status())
+}
+
+#[tauri::command]
+pub fn get_metrics_endpoint() -> Result<Value, GantryError> {
+    Ok(status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(rx: u64, read: u64) -> Value {
+        json!({
+            "cpu": 12.5,
+            "load_avg": [0.5, 0.25, 0.125],
+            "uptime": 3600,
+            "memory": {"total": 1000, "used": 400, "swap_total": 0, "swap_used": 0},
+            "disks": [{"name": "/dev/sda1", "mount_point": "/", "total_space": 100, "available_space": 40}],
+            "network": [{"name": "eth0", "rx_bytes": rx, "tx_bytes": 0}],
+            "disk_io": [{"name": "sda", "read_bytes": read, "write_bytes": 0, "io_ms": 0}],
+            "temperatures": [{"sensor": "coretemp", "label": "Package \"0\"", "celsius": 55.0}],
+        })
+    }
+
+    #[test]
+    fn test_render_counters_then_rates() {
+        let start = Instant::now();
+        let first = counters(&resources(1000, 0), start);
+        let text = render(&resources(1000, 0), Some(2), None, &first);
+        assert!(text.contains("# TYPE gantry_cpu_usage_percent gauge\ngantry_cpu_usage_percent 12.5\n"));
+        assert!(text.contains("gantry_load_average{period=\"5m\"} 0.25\n"));
+        assert!(text.contains("gantry_disk_available_bytes{device=\"/dev/sda1\",mount=\"/\"} 40\n"));
+        assert!(text.contains("gantry_network_received_bytes_total{interface=\"eth0\"} 1000\n"));
+        assert!(text.contains("gantry_temperature_celsius{sensor=\"coretemp\",label=\"Package \\\"0\\\"\"} 55\n"));
+        assert!(text.contains("gantry_failed_services 2\n"));
+        assert!(!text.contains("_per_second"), "no rates before a second scrape");
+
+        let second = counters(&resources(3000, 512), start + Duration::from_secs(2));
+        let text = render(&resources(3000, 512), None, Some(&first), &second);
+        assert!(text.contains("gantry_network_received_bytes_per_second{interface=\"eth0\"} 1000\n"));
+        assert!(text.contains("gantry_disk_read_bytes_per_second{device=\"sda\"} 256\n"));
+        assert!(!text.contains("gantry_failed_services"));
+    }
+
+    #[test]
+    fn test_socket_addr() {
+        assert_eq!(socket_addr("127.0.0.1", 9839).unwrap().to_string(), "127.0.0.1:9839");
+        assert_eq!(socket_addr("::1", 9839).unwrap().to_string(), "[::1]:9839");
+        assert_eq!(socket_addr("localhost", 9839).unwrap_err().code(), "parse_error");
+    }
+}
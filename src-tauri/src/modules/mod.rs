@@ -6,12 +6,57 @@ pub mod logging;
 pub mod scripts;
 pub mod services;
 pub mod settings;
+pub mod printers;
+pub mod storage;
+pub mod network;
+pub mod power;
+pub mod security;
+pub mod packages;
+pub mod cron;
+pub mod environment;
+pub mod alerts;
+pub mod admin;
+pub mod audit;
+pub mod error;
+pub mod instance;
+pub mod widget;
+pub mod shortcuts;
+pub mod crash;
+pub mod metrics;
+pub mod firewall;
+pub mod maintenance;
+pub mod appdata;
+pub mod tasks;
+pub mod snapshots;
+pub mod search;
+pub mod capabilities;
+pub mod changes;
+pub mod util;
 
-pub use system::{get_system_overview, get_resources, get_os_info, get_platform, save_report_file};
-pub use processes::{list_processes, kill_process, kill_process_group};
-pub use config::{list_apt_repos, list_startup_apps, toggle_apt_repo, add_apt_repo, delete_apt_repo, add_startup_app, edit_startup_app, delete_startup_app, toggle_startup_app};
-pub use devices::{get_processor_info, list_devices, list_usb_devices, list_network_devices, list_pci_devices, list_input_devices};
-pub use logging::{write_log, read_log_file, clear_log_file};
-pub use scripts::{list_scripts, add_script, remove_script, update_script, run_script};
+pub use system::{get_system_overview, get_resources, get_os_info, get_platform, save_report_file, get_gpu_details, get_platform_capabilities, get_thermal_history, get_disk_growth};
+pub use processes::{list_processes, kill_process, kill_process_group, get_usage_by_user, get_process_detail};
+pub use config::{list_apt_repos, list_startup_apps, toggle_apt_repo, add_apt_repo, delete_apt_repo, add_startup_app, edit_startup_app, delete_startup_app, toggle_startup_app, refresh_package_index, list_apt_keys, add_apt_key, delete_apt_key, add_apt_repo_deb822, add_ppa, validate_apt_repo, list_repo_backups, restore_repo_backup, list_installed_applications, repo_health_check};
+pub use devices::{get_processor_info, list_devices, list_usb_devices, get_usb_device_details, list_network_devices, list_pci_devices, list_input_devices, list_video_devices, start_input_test, stop_input_test, list_serial_devices, list_thunderbolt_devices, authorize_thunderbolt_device};
+pub use logging::{write_log, read_log_file, read_log_entries, read_log_tail, log_file_info, start_log_follow, stop_log_follow, search_log, clear_log_file};
+pub use scripts::{list_scripts, add_script, remove_script, update_script, run_script, run_script_streaming, cancel_script_run, get_script_history, clear_script_history, export_scripts, import_scripts, reorder_scripts, preview_script, set_script_chain, set_script_notify, list_favorite_scripts, set_script_favorite, set_script_login_shell, set_script_accepts_stdin, set_script_skip_preflight};
 pub use services::{list_services, start_service, stop_service, restart_service, enable_service, disable_service};
-pub use settings::{get_settings, set_theme};
+pub use settings::{get_settings, set_theme, set_repo_backup_keep, set_notifications_enabled, set_script_output_limit, update_settings, set_temperature_unit, export_settings, import_settings, reset_settings, reset_window_state, set_module_enabled, update_alert_settings, set_start_at_login, set_start_minimized, set_page_preference};
+pub use printers::{list_printers, list_print_jobs, cancel_print_job};
+pub use storage::{get_nvme_info, eject_device, get_fstab_entries};
+pub use network::{get_network_device_details, scan_wifi_networks, get_wifi_status, get_network_top_talkers, set_network_device_state, set_network_device_mtu, get_wol_status, set_wol, send_wol_packet, list_lan_neighbors, ping_host};
+pub use power::{list_power_supplies};
+pub use security::{get_tpm_info};
+pub use packages::{list_packages, get_package_details, list_upgradable_packages, upgrade_packages, get_auto_update_config, set_auto_updates};
+pub use cron::{list_cron_jobs, add_cron_job, edit_cron_job, delete_cron_job, toggle_cron_job};
+pub use environment::{list_environment_entries, set_environment_entry, delete_environment_entry};
+pub use alerts::{get_active_alerts};
+pub use admin::{begin_admin_session, end_admin_session, admin_session_status};
+pub use widget::{open_monitor_widget, close_monitor_widget};
+pub use shortcuts::{set_global_shortcut, list_global_shortcuts};
+pub use crash::{get_last_crash, clear_last_crash};
+pub use metrics::{set_metrics_endpoint, get_metrics_endpoint};
+pub use firewall::{get_firewall_status, add_firewall_rule, delete_firewall_rule};
+pub use maintenance::{get_cleanup_candidates, run_cleanup};
+pub use appdata::{export_app_data, import_app_data};
+pub use snapshots::{take_resource_snapshot, list_resource_snapshots, compare_resource_snapshots};
+pub use search::global_search;
@@ -0,0 +1,1362 @@
+use super::error::GantryError;
+use super::util::run_tool;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use std::fs;
+
+fn validate_interface_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name.len() <= 15
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid interface name: {}", name))
+    }
+}
+
+fn parse_key_values(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_ethtool_list(output: &str, field: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut in_field = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix(field).and_then(|r| r.strip_prefix(':')) {
+            in_field = true;
+            values.extend(rest.split_whitespace().map(|s| s.to_string()));
+        } else if in_field {
+            if trimmed.contains(':') {
+                in_field = false;
+            } else {
+                values.extend(trimmed.split_whitespace().map(|s| s.to_string()));
+            }
+        }
+    }
+
+    values.retain(|v| v != "Not" && v != "reported");
+    values
+}
+
+#[cfg(target_os = "linux")]
+fn read_interface_statistics(name: &str) -> serde_json::Value {
+    let dir = std::path::PathBuf::from("/sys/class/net").join(name).join("statistics");
+    let mut stats = serde_json::Map::new();
+    for key in [
+        "rx_bytes", "tx_bytes", "rx_packets", "tx_packets", "rx_errors", "tx_errors",
+        "rx_dropped", "tx_dropped", "collisions", "multicast",
+    ] {
+        let value = fs::read_to_string(dir.join(key))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok());
+        stats.insert(key.to_string(), json!(value));
+    }
+    serde_json::Value::Object(stats)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_iw_link(output: &str) -> serde_json::Value {
+    if output.trim_start().starts_with("Not connected") {
+        return json!({"connected": false});
+    }
+    let kv = parse_key_values(output);
+    let signal_dbm = kv
+        .get("signal")
+        .and_then(|s| s.split_whitespace().next())
+        .and_then(|s| s.parse::<i32>().ok());
+
+    json!({
+        "connected": true,
+        "ssid": kv.get("SSID"),
+        "frequency_mhz": kv.get("freq").and_then(|f| f.parse::<f64>().ok()),
+        "signal_dbm": signal_dbm,
+        "tx_bitrate": kv.get("tx bitrate"),
+        "rx_bitrate": kv.get("rx bitrate"),
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_network_device_details(name: String) -> Result<serde_json::Value, String> {
+    validate_interface_name(&name)?;
+    let sys_path = std::path::PathBuf::from("/sys/class/net").join(&name);
+    if !sys_path.exists() {
+        return Err(format!("Network interface {} not found", name));
+    }
+
+    let driver_info = run_tool("ethtool", &["-i", &name]).ok().map(|o| parse_key_values(&o));
+    let link_info = run_tool("ethtool", &[&name]).ok();
+
+    let link = link_info.as_deref().map(|o| {
+        let kv = parse_key_values(o);
+        json!({
+            "speed": kv.get("Speed"),
+            "duplex": kv.get("Duplex"),
+            "port": kv.get("Port"),
+            "auto_negotiation": kv.get("Auto-negotiation"),
+            "link_detected": kv.get("Link detected").map(|v| v == "yes"),
+            "supported_link_modes": parse_ethtool_list(o, "Supported link modes"),
+            "advertised_link_modes": parse_ethtool_list(o, "Advertised link modes"),
+        })
+    });
+
+    let driver = driver_info
+        .as_ref()
+        .and_then(|kv| kv.get("driver").cloned())
+        .or_else(|| {
+            fs::read_link(sys_path.join("device").join("driver"))
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        });
+
+    let wireless = if sys_path.join("wireless").exists() || sys_path.join("phy80211").exists() {
+        run_tool("iw", &["dev", &name, "link"]).ok().map(|o| parse_iw_link(&o))
+    } else {
+        None
+    };
+
+    Ok(json!({
+        "name": name,
+        "driver": driver,
+        "driver_version": driver_info.as_ref().and_then(|kv| kv.get("version").cloned()),
+        "firmware_version": driver_info.as_ref().and_then(|kv| kv.get("firmware-version").cloned()),
+        "bus_info": driver_info.as_ref().and_then(|kv| kv.get("bus-info").cloned()),
+        "ethtool_available": driver_info.is_some(),
+        "link": link,
+        "statistics": read_interface_statistics(&name),
+        "wireless": wireless,
+    }))
+}
+
+#[cfg(target_os = "macos")]
+const AIRPORT_PATH: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+#[cfg(target_os = "macos")]
+fn hardware_port_for(name: &str) -> Option<String> {
+    let output = run_tool("networksetup", &["-listallhardwareports"]).ok()?;
+    let mut current_port: Option<String> = None;
+    for line in output.lines() {
+        if let Some(port) = line.strip_prefix("Hardware Port:") {
+            current_port = Some(port.trim().to_string());
+        } else if let Some(device) = line.strip_prefix("Device:") {
+            if device.trim() == name {
+                return current_port;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn parse_netstat_interface(output: &str, name: &str) -> serde_json::Value {
+    let mut lines = output.lines();
+    let header: Vec<&str> = lines.next().unwrap_or("").split_whitespace().collect();
+    let col = |row: &[&str], key: &str| -> Option<u64> {
+        let idx = header.iter().position(|h| *h == key)?;
+        // Rows without an address column are one field short
+        let offset = header.len().saturating_sub(row.len());
+        row.get(idx.checked_sub(offset)?)?.parse().ok()
+    };
+    for line in lines {
+        let row: Vec<&str> = line.split_whitespace().collect();
+        if row.first() == Some(&name) && line.contains("<Link#") {
+            return json!({
+                "rx_packets": col(&row, "Ipkts"),
+                "rx_errors": col(&row, "Ierrs"),
+                "rx_bytes": col(&row, "Ibytes"),
+                "tx_packets": col(&row, "Opkts"),
+                "tx_errors": col(&row, "Oerrs"),
+                "tx_bytes": col(&row, "Obytes"),
+                "collisions": col(&row, "Coll"),
+            });
+        }
+    }
+    json!({})
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_network_device_details(name: String) -> Result<serde_json::Value, String> {
+    validate_interface_name(&name)?;
+
+    let hardware_port = hardware_port_for(&name);
+    let media = hardware_port
+        .as_deref()
+        .and_then(|port| run_tool("networksetup", &["-getMedia", port]).ok())
+        .map(|o| parse_key_values(&o));
+    let statistics = run_tool("netstat", &["-I", &name, "-b"]).ok()
+        .map(|o| parse_netstat_interface(&o, &name))
+        .unwrap_or_else(|| json!({}));
+
+    let is_wifi = hardware_port.as_deref() == Some("Wi-Fi");
+    let wireless = if is_wifi {
+        run_tool(AIRPORT_PATH, &["-I"]).ok().map(|o| {
+            let kv = parse_key_values(&o);
+            json!({
+                "connected": kv.get("SSID").is_some(),
+                "ssid": kv.get("SSID"),
+                "signal_dbm": kv.get("agrCtlRSSI").and_then(|s| s.parse::<i32>().ok()),
+                "tx_bitrate": kv.get("lastTxRate"),
+                "channel": kv.get("channel"),
+            })
+        })
+    } else {
+        None
+    };
+
+    Ok(json!({
+        "name": name,
+        "hardware_port": hardware_port,
+        "driver": null,
+        "firmware_version": null,
+        "bus_info": null,
+        "ethtool_available": false,
+        "link": media.map(|kv| json!({
+            "speed": kv.get("Current"),
+            "active": kv.get("Active"),
+        })),
+        "statistics": statistics,
+        "wireless": wireless,
+    }))
+}
+
+const MTU_RANGE: std::ops::RangeInclusive<u32> = 576..=9216;
+
+fn is_loopback_interface(name: &str) -> bool {
+    if matches!(name, "lo" | "lo0") {
+        return true;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string(format!("/sys/class/net/{}/flags", name))
+            .ok()
+            .and_then(|f| u32::from_str_radix(f.trim().trim_start_matches("0x"), 16).ok())
+            .is_some_and(|flags| flags & libc::IFF_LOOPBACK as u32 != 0)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        false
+    }
+}
+
+fn check_link_change(name: &str, up: Option<bool>, mtu: Option<u32>) -> Result<(), GantryError> {
+    validate_interface_name(name).map_err(GantryError::Failed)?;
+    if name.starts_with('-') {
+        return Err(GantryError::Failed(format!("Invalid interface name: {}", name)));
+    }
+    if up == Some(false) && is_loopback_interface(name) {
+        return Err(GantryError::Unsupported(format!("{} is the loopback interface and can't be taken down", name)));
+    }
+    if mtu.is_some_and(|mtu| !MTU_RANGE.contains(&mtu)) {
+        return Err(GantryError::Failed(format!(
+            "MTU must be between {} and {}",
+            MTU_RANGE.start(),
+            MTU_RANGE.end()
+        )));
+    }
+    if up.is_none() && mtu.is_none() {
+        return Err(GantryError::Failed("Nothing to change".into()));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn link_command(name: &str, up: Option<bool>, mtu: Option<u32>) -> Result<Vec<String>, GantryError> {
+    check_link_change(name, up, mtu)?;
+    let mut command: Vec<String> = ["ip", "link", "set", "dev", name].map(String::from).to_vec();
+    if let Some(up) = up {
+        command.push(if up { "up" } else { "down" }.into());
+    }
+    if let Some(mtu) = mtu {
+        command.extend(["mtu".to_string(), mtu.to_string()]);
+    }
+    Ok(command)
+}
+
+#[cfg(target_os = "linux")]
+fn apply_link_change(name: &str, up: Option<bool>, mtu: Option<u32>) -> Result<(), GantryError> {
+    let command = link_command(name, up, mtu)?;
+    super::admin::set_link(name, up, mtu, &command)
+}
+
+#[cfg(target_os = "macos")]
+fn apply_link_change(name: &str, up: Option<bool>, mtu: Option<u32>) -> Result<(), GantryError> {
+    check_link_change(name, up, mtu)?;
+    let mut commands = Vec::new();
+    if let Some(up) = up {
+        commands.push(format!("ifconfig {} {}", name, if up { "up" } else { "down" }));
+    }
+    if let Some(mtu) = mtu {
+        commands.push(format!("networksetup -setMTU {} {}", name, mtu));
+    }
+    let output = Command::new("osascript")
+        .args(["-e", &format!("do shell script \"{}\" with administrator privileges", commands.join(" && "))])
+        .output()
+        .map_err(|e| GantryError::spawn("osascript", e))?;
+    if !output.status.success() {
+        return Err(GantryError::from_output("osascript", &output));
+    }
+    Ok(())
+}
+
+fn network_device(name: &str) -> Result<serde_json::Value, GantryError> {
+    super::devices::list_network_devices()?
+        .into_iter()
+        .find(|d| d["name"] == name)
+        .ok_or_else(|| GantryError::NotFound(format!("Network interface {} not found", name)))
+}
+
+fn change_link(name: &str, up: Option<bool>, mtu: Option<u32>) -> Result<serde_json::Value, GantryError> {
+    check_link_change(name, up, mtu)?;
+    network_device(name)?;
+    apply_link_change(name, up, mtu)?;
+    network_device(name)
+}
+
+#[tauri::command]
+pub async fn set_network_device_state(name: String, up: bool) -> Result<serde_json::Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || change_link(&name, Some(up), None))
+        .await
+        .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn set_network_device_mtu(name: String, mtu: u32) -> Result<serde_json::Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || change_link(&name, None, Some(mtu)))
+        .await
+        .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+const WOL_PORT: u16 = 9;
+
+fn parse_mac(mac: &str) -> Result<[u8; 6], GantryError> {
+    let invalid = || GantryError::Failed(format!("Invalid MAC address: {}", mac));
+    let hex: String = mac.trim().chars().filter(|c| !matches!(c, ':' | '-')).collect();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let mut bytes = [0u8; 6];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+fn magic_packet(mac: [u8; 6]) -> [u8; 102] {
+    let mut packet = [0xFF; 102];
+    for chunk in packet[6..].chunks_mut(6) {
+        chunk.copy_from_slice(&mac);
+    }
+    packet
+}
+
+#[tauri::command]
+pub fn send_wol_packet(mac: String, broadcast_addr: Option<String>) -> Result<serde_json::Value, GantryError> {
+    let packet = magic_packet(parse_mac(&mac)?);
+    let target = broadcast_addr.as_deref().map(str::trim).filter(|a| !a.is_empty()).unwrap_or("255.255.255.255");
+    let target: std::net::SocketAddr = target
+        .parse()
+        .or_else(|_| target.parse::<std::net::IpAddr>().map(|ip| (ip, WOL_PORT).into()))
+        .map_err(|_| GantryError::Failed(format!("Invalid broadcast address: {}", target)))?;
+    let bind: std::net::SocketAddr = if target.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0u16; 8], 0).into() };
+    let socket = std::net::UdpSocket::bind(bind)?;
+    socket.set_broadcast(true)?;
+    socket.send_to(&packet, target)?;
+    Ok(json!({"mac": mac.trim(), "target": target.to_string(), "bytes": packet.len()}))
+}
+
+#[cfg(target_os = "linux")]
+fn wol_mode_name(mode: char) -> Option<&'static str> {
+    Some(match mode {
+        'p' => "phy",
+        'u' => "unicast",
+        'm' => "multicast",
+        'b' => "broadcast",
+        'a' => "arp",
+        'g' => "magic_packet",
+        's' => "secureon",
+        'f' => "filter",
+        _ => return None,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_ethtool_wol(output: &str) -> serde_json::Value {
+    let kv = parse_key_values(output);
+    let modes = |key: &str| -> Option<Vec<&'static str>> {
+        kv.get(key).map(|v| v.chars().filter_map(wol_mode_name).collect())
+    };
+    let supported = modes("Supports Wake-on");
+    let current = modes("Wake-on");
+    json!({
+        "supported": supported.as_ref().map(|m| m.contains(&"magic_packet")),
+        "enabled": current.as_ref().map(|m| m.contains(&"magic_packet")),
+        "supported_modes": supported,
+        "current_modes": current,
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn wol_command(interface: &str, enabled: bool) -> Result<Vec<String>, GantryError> {
+    validate_interface_name(interface).map_err(GantryError::Failed)?;
+    if interface.starts_with('-') {
+        return Err(GantryError::Failed(format!("Invalid interface name: {}", interface)));
+    }
+    Ok(["ethtool", "-s", interface, "wol", if enabled { "g" } else { "d" }].map(String::from).to_vec())
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_wol_status(interface: String) -> Result<serde_json::Value, GantryError> {
+    validate_interface_name(&interface).map_err(GantryError::Failed)?;
+    if !std::path::Path::new("/sys/class/net").join(&interface).exists() {
+        return Err(GantryError::NotFound(format!("Network interface {} not found", interface)));
+    }
+    let output = Command::new("ethtool")
+        .arg(&interface)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| GantryError::spawn("ethtool", e))?;
+    let mut status = parse_ethtool_wol(&String::from_utf8_lossy(&output.stdout));
+    status["interface"] = json!(interface);
+    Ok(status)
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn set_wol(interface: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let command = wol_command(&interface, enabled)?;
+        super::admin::set_wol(&interface, enabled, &command)?;
+        get_wol_status(interface)
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_wol_status(interface: String) -> Result<serde_json::Value, GantryError> {
+    validate_interface_name(&interface).map_err(GantryError::Failed)?;
+    let womp = run_tool("pmset", &["-g"]).ok().and_then(|o| {
+        o.lines()
+            .find_map(|l| l.trim().strip_prefix("womp").map(|v| v.trim() == "1"))
+    });
+    Ok(json!({
+        "interface": interface,
+        "supported": womp.is_some(),
+        "enabled": womp,
+        "supported_modes": womp.map(|_| vec!["magic_packet"]),
+        "current_modes": womp.map(|on| if on { vec!["magic_packet"] } else { vec![] }),
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub async fn set_wol(interface: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let script = format!(
+            "do shell script \"pmset -a womp {}\" with administrator privileges",
+            if enabled { 1 } else { 0 }
+        );
+        let output = Command::new("osascript")
+            .args(["-e", &script])
+            .output()
+            .map_err(|e| GantryError::spawn("osascript", e))?;
+        if !output.status.success() {
+            return Err(GantryError::from_output("osascript", &output));
+        }
+        get_wol_status(interface)
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+const REVERSE_DNS_TIMEOUT: Duration = Duration::from_secs(2);
+const PING_MAX_COUNT: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Neighbor {
+    ip: String,
+    mac: String,
+    interface: String,
+    state: Option<String>,
+    hostname: Option<String>,
+}
+
+type NeighborParser = fn(&str) -> Vec<Neighbor>;
+
+#[cfg(target_os = "linux")]
+fn parse_ip_neigh(output: &str) -> Vec<Neighbor> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let after = |key: &str| fields.iter().position(|f| *f == key).and_then(|i| fields.get(i + 1));
+            Some(Neighbor {
+                ip: fields.first()?.to_string(),
+                mac: after("lladdr")?.to_lowercase(),
+                interface: after("dev")?.to_string(),
+                state: fields.last().map(|s| s.to_string()),
+                hostname: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn parse_arp(output: &str) -> Vec<Neighbor> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let after = |key: &str| fields.iter().position(|f| *f == key).and_then(|i| fields.get(i + 1));
+            let mac = after("at")?;
+            if mac.starts_with('(') {
+                return None;
+            }
+            let mac: Vec<String> = mac.split(':').map(|b| format!("{:0>2}", b.to_lowercase())).collect();
+            let name = fields.first().filter(|n| **n != "?");
+            Some(Neighbor {
+                ip: fields.get(1)?.trim_matches(|c| c == '(' || c == ')').to_string(),
+                mac: mac.join(":"),
+                interface: after("on")?.to_string(),
+                state: None,
+                hostname: name.map(|n| n.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn reverse_dns(ip: std::net::IpAddr) -> Option<String> {
+    let mut host = [0 as libc::c_char; 1025];
+    let rc = unsafe {
+        let mut storage: libc::sockaddr_storage = std::mem::zeroed();
+        let len = match ip {
+            std::net::IpAddr::V4(v4) => {
+                let sa = &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in);
+                sa.sin_family = libc::AF_INET as libc::sa_family_t;
+                sa.sin_addr.s_addr = u32::from_ne_bytes(v4.octets());
+                std::mem::size_of::<libc::sockaddr_in>()
+            }
+            std::net::IpAddr::V6(v6) => {
+                let sa = &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in6);
+                sa.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                sa.sin6_addr.s6_addr = v6.octets();
+                std::mem::size_of::<libc::sockaddr_in6>()
+            }
+        };
+        #[cfg(target_os = "macos")]
+        {
+            storage.ss_len = len as u8;
+        }
+        libc::getnameinfo(
+            &storage as *const _ as *const libc::sockaddr,
+            len as libc::socklen_t,
+            host.as_mut_ptr(),
+            host.len() as libc::socklen_t,
+            std::ptr::null_mut(),
+            0,
+            libc::NI_NAMEREQD,
+        )
+    };
+    if rc != 0 {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(host.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+fn resolve_hostnames(neighbors: &mut [Neighbor]) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    for (i, neighbor) in neighbors.iter().enumerate() {
+        if neighbor.hostname.is_some() {
+            continue;
+        }
+        let Ok(ip) = neighbor.ip.parse() else { continue };
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send((i, reverse_dns(ip)));
+        });
+    }
+    drop(tx);
+    let deadline = std::time::Instant::now() + REVERSE_DNS_TIMEOUT;
+    while let Ok((i, hostname)) = rx.recv_timeout(deadline.saturating_duration_since(std::time::Instant::now())) {
+        neighbors[i].hostname = hostname;
+    }
+}
+
+#[tauri::command]
+pub async fn list_lan_neighbors(resolve: Option<bool>) -> Result<serde_json::Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        #[cfg(target_os = "linux")]
+        let (tool, args, parse): (&str, &[&str], NeighborParser) = ("ip", &["neigh", "show"], parse_ip_neigh);
+        #[cfg(target_os = "macos")]
+        let (tool, args, parse): (&str, &[&str], NeighborParser) = ("arp", &["-an"], parse_arp);
+        let output = Command::new(tool)
+            .args(args)
+            .env("LC_ALL", "C")
+            .output()
+            .map_err(|e| GantryError::spawn(tool, e))?;
+        if !output.status.success() {
+            return Err(GantryError::from_output(tool, &output));
+        }
+        let mut neighbors = parse(&String::from_utf8_lossy(&output.stdout));
+        if resolve.unwrap_or(false) {
+            resolve_hostnames(&mut neighbors);
+        }
+        Ok(json!(neighbors))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+// A leading `-` would be read by ping as an option.
+fn validate_host(address: &str) -> Result<(), GantryError> {
+    let valid = address.parse::<std::net::IpAddr>().is_ok()
+        || (!address.is_empty()
+            && address.len() <= 253
+            && !address.starts_with(['-', '.'])
+            && address.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.')));
+    if valid {
+        Ok(())
+    } else {
+        Err(GantryError::Failed(format!("Invalid address: {}", address)))
+    }
+}
+
+fn parse_ping_summary(output: &str) -> serde_json::Value {
+    let mut transmitted = None;
+    let mut received = None;
+    let mut times: Vec<f64> = Vec::new();
+    for line in output.lines() {
+        if line.contains("packets transmitted") {
+            let count = |label: &str| {
+                line.split(',')
+                    .find(|part| part.contains(label))
+                    .and_then(|part| part.split_whitespace().next())
+                    .and_then(|n| n.parse::<u32>().ok())
+            };
+            transmitted = count("transmitted");
+            received = count("received");
+        } else if line.contains("min/avg/max") {
+            if let Some((_, values)) = line.split_once('=') {
+                times = values.split_whitespace().next().unwrap_or("").split('/').filter_map(|v| v.parse().ok()).collect();
+            }
+        }
+    }
+    let loss = match (transmitted, received) {
+        (Some(sent), Some(got)) if sent > 0 => Some((sent - got.min(sent)) as f64 * 100.0 / sent as f64),
+        _ => None,
+    };
+    json!({
+        "transmitted": transmitted,
+        "received": received,
+        "loss_percent": loss,
+        "min_ms": times.first(),
+        "avg_ms": times.get(1),
+        "max_ms": times.get(2),
+        "stddev_ms": times.get(3),
+    })
+}
+
+#[tauri::command]
+pub async fn ping_host(address: String, count: Option<u32>) -> Result<serde_json::Value, GantryError> {
+    let address = address.trim().to_string();
+    validate_host(&address)?;
+    let count = count.unwrap_or(4).clamp(1, PING_MAX_COUNT).to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        // 0.2s is the shortest interval iputils allows without root.
+        #[cfg(target_os = "linux")]
+        let timing: &[&str] = &["-i", "0.2", "-W", "1"];
+        #[cfg(target_os = "macos")]
+        let timing: &[&str] = &["-W", "1000"];
+        let output = Command::new("ping")
+            .args(["-n", "-c", &count])
+            .args(timing)
+            .arg(&address)
+            .env("LC_ALL", "C")
+            .output()
+            .map_err(|e| GantryError::spawn("ping", e))?;
+        let mut summary = parse_ping_summary(&String::from_utf8_lossy(&output.stdout));
+        if summary["transmitted"].is_null() {
+            return Err(GantryError::from_output("ping", &output));
+        }
+        summary["address"] = json!(address);
+        Ok(summary)
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub signal: u32,
+    pub security: String,
+    pub frequency_mhz: Option<u32>,
+}
+
+fn dbm_to_percent(dbm: f64) -> u32 {
+    (2.0 * (dbm + 100.0)).clamp(0.0, 100.0) as u32
+}
+
+fn dedup_wifi_networks(networks: Vec<WifiNetwork>) -> Vec<WifiNetwork> {
+    let mut best: HashMap<String, WifiNetwork> = HashMap::new();
+    for network in networks {
+        if network.ssid.is_empty() {
+            continue;
+        }
+        match best.get(&network.ssid) {
+            Some(existing) if existing.signal >= network.signal => {}
+            _ => {
+                best.insert(network.ssid.clone(), network);
+            }
+        }
+    }
+    let mut result: Vec<WifiNetwork> = best.into_values().collect();
+    result.sort_by(|a, b| b.signal.cmp(&a.signal).then_with(|| a.ssid.cmp(&b.ssid)));
+    result
+}
+
+#[cfg(target_os = "linux")]
+fn split_nmcli_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ':' => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(target_os = "linux")]
+fn parse_nmcli_wifi_list(output: &str) -> Vec<WifiNetwork> {
+    output
+        .lines()
+        .map(split_nmcli_fields)
+        .filter(|f| f.len() >= 4)
+        .map(|f| WifiNetwork {
+            ssid: f[0].clone(),
+            signal: f[1].parse().unwrap_or(0),
+            security: if f[2].is_empty() { "Open".to_string() } else { f[2].clone() },
+            frequency_mhz: f[3].split_whitespace().next().and_then(|v| v.parse().ok()),
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_iw_scan(output: &str) -> Vec<WifiNetwork> {
+    let mut networks = Vec::new();
+    let mut current: Option<(WifiNetwork, bool, bool, bool)> = None;
+
+    let finish = |entry: Option<(WifiNetwork, bool, bool, bool)>, networks: &mut Vec<WifiNetwork>| {
+        if let Some((mut network, rsn, wpa, privacy)) = entry {
+            network.security = if rsn {
+                "WPA2".to_string()
+            } else if wpa {
+                "WPA".to_string()
+            } else if privacy {
+                "WEP".to_string()
+            } else {
+                "Open".to_string()
+            };
+            networks.push(network);
+        }
+    };
+
+    for line in output.lines() {
+        if line.starts_with("BSS ") {
+            finish(current.take(), &mut networks);
+            current = Some((
+                WifiNetwork { ssid: String::new(), signal: 0, security: String::new(), frequency_mhz: None },
+                false,
+                false,
+                false,
+            ));
+            continue;
+        }
+        let Some((network, rsn, wpa, privacy)) = current.as_mut() else { continue };
+        let trimmed = line.trim();
+        if let Some(v) = trimmed.strip_prefix("SSID:") {
+            network.ssid = v.trim().to_string();
+        } else if let Some(v) = trimmed.strip_prefix("signal:") {
+            let dbm = v.split_whitespace().next().and_then(|d| d.parse::<f64>().ok()).unwrap_or(-100.0);
+            network.signal = dbm_to_percent(dbm);
+        } else if let Some(v) = trimmed.strip_prefix("freq:") {
+            network.frequency_mhz = v.trim().split('.').next().and_then(|f| f.parse().ok());
+        } else if trimmed.starts_with("RSN:") {
+            *rsn = true;
+        } else if trimmed.starts_with("WPA:") {
+            *wpa = true;
+        } else if trimmed.starts_with("capability:") && trimmed.contains("Privacy") {
+            *privacy = true;
+        }
+    }
+    finish(current, &mut networks);
+
+    networks
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn wifi_scan_command(interface: &str) -> Result<Vec<String>, GantryError> {
+    validate_interface_name(interface).map_err(GantryError::Failed)?;
+    if interface.starts_with('-') {
+        return Err(GantryError::Failed(format!("Invalid interface name: {}", interface)));
+    }
+    Ok(["iw", "dev", interface, "scan"].map(String::from).to_vec())
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn scan_wifi_networks(interface: Option<String>) -> Result<serde_json::Value, String> {
+    if let Some(ref iface) = interface {
+        validate_interface_name(iface)?;
+    }
+
+    let mut args = vec!["-t", "-f", "SSID,SIGNAL,SECURITY,FREQ", "dev", "wifi", "list"];
+    if let Some(ref iface) = interface {
+        args.extend(["ifname", iface.as_str()]);
+    }
+    args.extend(["--rescan", "yes"]);
+
+    if let Ok(output) = run_tool("nmcli", &args) {
+        let networks = dedup_wifi_networks(parse_nmcli_wifi_list(&output));
+        return Ok(json!({"backend": "nmcli", "networks": networks}));
+    }
+
+    let iface = interface.ok_or_else(|| {
+        "NetworkManager is not available; an interface name is required to scan with iw".to_string()
+    })?;
+    let output = super::admin::scan_wifi(&iface, &wifi_scan_command(&iface)?)
+        .map_err(|e| format!("WiFi scan failed: {}", e.message()))?;
+
+    let networks = dedup_wifi_networks(parse_iw_scan(&output));
+    Ok(json!({"backend": "iw", "networks": networks}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_wifi_status(interface: String) -> Result<serde_json::Value, String> {
+    validate_interface_name(&interface)?;
+
+    let ip_addresses: Vec<String> = run_tool("ip", &["-o", "addr", "show", "dev", &interface]).ok()
+        .map(|o| {
+            o.lines()
+                .filter_map(|l| {
+                    let parts: Vec<&str> = l.split_whitespace().collect();
+                    let idx = parts.iter().position(|p| *p == "inet" || *p == "inet6")?;
+                    parts.get(idx + 1).map(|a| a.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Ok(output) = run_tool(
+        "nmcli",
+        &["-t", "-f", "ACTIVE,SSID,SIGNAL,SECURITY,FREQ", "dev", "wifi", "list", "ifname", &interface, "--rescan", "no"],
+    ) {
+        let active = output
+            .lines()
+            .map(split_nmcli_fields)
+            .find(|f| f.len() >= 5 && f[0] == "yes");
+        return Ok(match active {
+            Some(f) => json!({
+                "interface": interface,
+                "connected": true,
+                "ssid": f[1],
+                "signal": f[2].parse::<u32>().ok(),
+                "security": f[3],
+                "frequency_mhz": f[4].split_whitespace().next().and_then(|v| v.parse::<u32>().ok()),
+                "ip_addresses": ip_addresses,
+            }),
+            None => json!({"interface": interface, "connected": false, "ip_addresses": ip_addresses}),
+        });
+    }
+
+    let link = run_tool("iw", &["dev", &interface, "link"]).ok()
+        .map(|o| parse_iw_link(&o))
+        .ok_or_else(|| format!("Could not query WiFi status for {}", interface))?;
+
+    Ok(json!({
+        "interface": interface,
+        "connected": link["connected"],
+        "ssid": link["ssid"],
+        "signal": link["signal_dbm"].as_f64().map(dbm_to_percent),
+        "frequency_mhz": link["frequency_mhz"],
+        "ip_addresses": ip_addresses,
+    }))
+}
+
+#[cfg(target_os = "macos")]
+fn parse_airport_scan(output: &str) -> Vec<WifiNetwork> {
+    let is_bssid = |t: &str| t.len() == 17 && t.split(':').count() == 6;
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let bssid_idx = tokens.iter().position(|t| is_bssid(t))?;
+            let ssid = tokens[..bssid_idx].join(" ");
+            let rssi: f64 = tokens.get(bssid_idx + 1)?.parse().ok()?;
+            let security = tokens.get(bssid_idx + 5..).map(|s| s.join(" ")).unwrap_or_default();
+            Some(WifiNetwork {
+                ssid,
+                signal: dbm_to_percent(rssi),
+                security: if security == "NONE" { "Open".to_string() } else { security },
+                frequency_mhz: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn scan_wifi_networks(interface: Option<String>) -> Result<serde_json::Value, String> {
+    if let Some(ref iface) = interface {
+        validate_interface_name(iface)?;
+    }
+    let output = run_tool(AIRPORT_PATH, &["-s"]).ok()
+        .ok_or_else(|| "WiFi scanning is not available on this macOS version".to_string())?;
+    let networks = dedup_wifi_networks(parse_airport_scan(&output));
+    Ok(json!({"backend": "airport", "networks": networks}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_wifi_status(interface: String) -> Result<serde_json::Value, String> {
+    validate_interface_name(&interface)?;
+    let ip = run_tool("ipconfig", &["getifaddr", &interface]).ok().map(|s| s.trim().to_string());
+    let info = run_tool(AIRPORT_PATH, &["-I"]).ok()
+        .map(|o| parse_key_values(&o))
+        .ok_or_else(|| format!("Could not query WiFi status for {}", interface))?;
+
+    Ok(json!({
+        "interface": interface,
+        "connected": info.contains_key("SSID"),
+        "ssid": info.get("SSID"),
+        "signal": info.get("agrCtlRSSI").and_then(|s| s.parse::<f64>().ok()).map(dbm_to_percent),
+        "security": info.get("link auth"),
+        "ip_addresses": ip.into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>(),
+    }))
+}
+
+const TALKER_SAMPLE: Duration = Duration::from_secs(1);
+
+#[cfg(target_os = "linux")]
+const TALKER_NOTE: &str = "Estimated from TCP socket byte counters over one second. UDP is not counted, and other users' processes only show when Gantry runs as root.";
+#[cfg(target_os = "macos")]
+const TALKER_NOTE: &str = "Estimated from nettop over one second.";
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct SocketBytes {
+    key: String,
+    pid: u32,
+    name: String,
+    sent: u64,
+    received: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn is_loopback(addr: &str) -> bool {
+    let host = addr.rsplit_once(':').map(|(h, _)| h).unwrap_or(addr).trim_matches(|c| c == '[' || c == ']');
+    host.starts_with("127.") || host == "::1" || host.starts_with("::ffff:127.")
+}
+
+#[cfg(target_os = "linux")]
+fn parse_ss_socket_line(line: &str) -> Option<SocketBytes> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let (local, peer) = (*fields.get(3)?, *fields.get(4)?);
+    if is_loopback(peer) {
+        return None;
+    }
+    let (name, rest) = line.split_once("users:((\"")?.1.split_once('"')?;
+    let pid = rest.strip_prefix(",pid=")?.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+    Some(SocketBytes { key: format!("{} {}", local, peer), pid, name: name.to_string(), sent: 0, received: 0 })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_ss_tcp_info(output: &str) -> Vec<SocketBytes> {
+    let mut sockets = Vec::new();
+    let mut current = None;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            current = parse_ss_socket_line(line);
+            continue;
+        }
+        let Some(mut socket) = current.take() else { continue };
+        for field in line.split_whitespace() {
+            if let Some(v) = field.strip_prefix("bytes_sent:") {
+                socket.sent = v.parse().unwrap_or(0);
+            } else if let Some(v) = field.strip_prefix("bytes_received:") {
+                socket.received = v.parse().unwrap_or(0);
+            }
+        }
+        sockets.push(socket);
+    }
+    sockets
+}
+
+fn rank_talkers(mut talkers: Vec<(u32, String, f64, f64)>) -> Vec<serde_json::Value> {
+    talkers.retain(|t| t.2 + t.3 > 0.0);
+    talkers.sort_by(|a, b| (b.2 + b.3).total_cmp(&(a.2 + a.3)));
+    talkers
+        .into_iter()
+        .map(|(pid, name, rx, tx)| json!({"pid": pid, "name": name, "rx_bps": rx.round(), "tx_bps": tx.round()}))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn talker_rates(before: &[SocketBytes], after: &[SocketBytes], secs: f64) -> Vec<serde_json::Value> {
+    let earlier: HashMap<&str, &SocketBytes> = before.iter().map(|s| (s.key.as_str(), s)).collect();
+    let mut by_pid: HashMap<u32, (String, u64, u64)> = HashMap::new();
+    for socket in after {
+        let (sent, received) = earlier
+            .get(socket.key.as_str())
+            .filter(|b| b.pid == socket.pid)
+            .map(|b| (b.sent, b.received))
+            .unwrap_or((0, 0));
+        let entry = by_pid.entry(socket.pid).or_insert_with(|| (socket.name.clone(), 0, 0));
+        entry.1 += socket.received.saturating_sub(received);
+        entry.2 += socket.sent.saturating_sub(sent);
+    }
+    let secs = secs.max(f64::EPSILON);
+    rank_talkers(
+        by_pid
+            .into_iter()
+            .map(|(pid, (name, rx, tx))| (pid, name, rx as f64 / secs, tx as f64 / secs))
+            .collect(),
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn sample_tcp_sockets() -> Result<Vec<SocketBytes>, String> {
+    run_tool("ss", &["-tinpH"]).ok()
+        .map(|o| parse_ss_tcp_info(&o))
+        .ok_or_else(|| "Could not read socket statistics; is ss (iproute2) installed?".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn measure_top_talkers() -> Result<Vec<serde_json::Value>, String> {
+    let before = sample_tcp_sockets()?;
+    let started = std::time::Instant::now();
+    std::thread::sleep(TALKER_SAMPLE);
+    let after = sample_tcp_sockets()?;
+    Ok(talker_rates(&before, &after, started.elapsed().as_secs_f64()))
+}
+
+#[cfg(target_os = "macos")]
+fn parse_nettop(output: &str, secs: f64) -> Vec<serde_json::Value> {
+    let mut columns: Option<(usize, usize)> = None;
+    let mut rows = Vec::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(',').collect();
+        let find = |name: &str| fields.iter().position(|f| f.trim() == name);
+        if let (Some(rx), Some(tx)) = (find("bytes_in"), find("bytes_out")) {
+            columns = Some((rx, tx));
+            rows.clear();
+            continue;
+        }
+        let Some((rx, tx)) = columns else { continue };
+        let Some((name, pid)) = fields.get(1).and_then(|p| p.rsplit_once('.')) else { continue };
+        let (Ok(pid), Some(rx), Some(tx)) = (
+            pid.parse::<u32>(),
+            fields.get(rx).and_then(|v| v.trim().parse::<u64>().ok()),
+            fields.get(tx).and_then(|v| v.trim().parse::<u64>().ok()),
+        ) else {
+            continue;
+        };
+        rows.push((pid, name.to_string(), rx as f64 / secs, tx as f64 / secs));
+    }
+    rank_talkers(rows)
+}
+
+#[cfg(target_os = "macos")]
+fn measure_top_talkers() -> Result<Vec<serde_json::Value>, String> {
+    let secs = TALKER_SAMPLE.as_secs().max(1).to_string();
+    let output = run_tool("nettop", &["-P", "-L", "2", "-d", "-x", "-s", &secs, "-J", "bytes_in,bytes_out"]).ok()
+        .ok_or_else(|| "Could not run nettop".to_string())?;
+    Ok(parse_nettop(&output, TALKER_SAMPLE.as_secs_f64()))
+}
+
+#[tauri::command]
+pub async fn get_network_top_talkers() -> Result<serde_json::Value, String> {
+    let talkers = tauri::async_runtime::spawn_blocking(measure_top_talkers)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(json!({
+        "estimated": true,
+        "note": TALKER_NOTE,
+        "interval_ms": TALKER_SAMPLE.as_millis() as u64,
+        "talkers": talkers,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_interface_name() {
+        assert!(validate_interface_name("eth0").is_ok());
+        assert!(validate_interface_name("wlp2s0").is_ok());
+        assert!(validate_interface_name("br-1a2b3c").is_ok());
+        assert!(validate_interface_name("").is_err());
+        assert!(validate_interface_name("../etc").is_err());
+        assert!(validate_interface_name(".").is_err());
+        assert!(validate_interface_name("..").is_err());
+        assert!(validate_interface_name("vlan.10").is_ok());
+        assert!(validate_interface_name("eth0; reboot").is_err());
+    }
+
+    #[test]
+    fn test_magic_packet() {
+        let mac = parse_mac("00:1A:2b:3c:4D:5e").unwrap();
+        assert_eq!(mac, [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+        assert_eq!(parse_mac("00-1a-2b-3c-4d-5e").unwrap(), mac);
+        assert_eq!(parse_mac("001a2b3c4d5e").unwrap(), mac);
+        assert!(parse_mac("00:1a:2b:3c:4d").is_err());
+        assert!(parse_mac("00:1a:2b:3c:4d:zz").is_err());
+
+        let packet = magic_packet(mac);
+        assert_eq!(packet.len(), 6 + 16 * 6);
+        assert_eq!(packet[..6], [0xFF; 6]);
+        assert!(packet[6..].chunks(6).all(|chunk| chunk == mac));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_ethtool_wol() {
+        let output = "Settings for eth0:\n\tSupported ports: [ TP ]\n\tSupports Wake-on: pumbg\n\tWake-on: d\n\tLink detected: yes\n";
+        let status = parse_ethtool_wol(output);
+        assert_eq!(status["supported"], true);
+        assert_eq!(status["enabled"], false);
+        assert_eq!(status["supported_modes"], json!(["phy", "unicast", "multicast", "broadcast", "magic_packet"]));
+        assert_eq!(status["current_modes"], json!([]));
+        assert_eq!(parse_ethtool_wol("Settings for wlan0:\n\tLink detected: yes\n")["supported"], serde_json::Value::Null);
+        assert_eq!(wol_command("eth0", true).unwrap(), ["ethtool", "-s", "eth0", "wol", "g"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_ip_neigh() {
+        let output = "192.168.1.1 dev eth0 lladdr AA:BB:CC:DD:EE:FF router REACHABLE\n\
+                      192.168.1.23 dev eth0 FAILED\n\
+                      fe80::1c2d dev wlan0 lladdr 11:22:33:44:55:66 STALE\n";
+        let neighbors = parse_ip_neigh(output);
+        assert_eq!(neighbors.len(), 2, "entries without an address are skipped");
+        assert_eq!(
+            neighbors[0],
+            Neighbor {
+                ip: "192.168.1.1".into(),
+                mac: "aa:bb:cc:dd:ee:ff".into(),
+                interface: "eth0".into(),
+                state: Some("REACHABLE".into()),
+                hostname: None,
+            }
+        );
+        assert_eq!(neighbors[1].interface, "wlan0");
+    }
+
+    #[test]
+    fn test_parse_ping_summary() {
+        let linux = "--- 192.168.1.1 ping statistics ---\n\
+                     4 packets transmitted, 3 received, 25% packet loss, time 603ms\n\
+                     rtt min/avg/max/mdev = 0.412/0.530/0.701/0.122 ms\n";
+        let summary = parse_ping_summary(linux);
+        assert_eq!(summary["transmitted"], 4);
+        assert_eq!(summary["received"], 3);
+        assert_eq!(summary["loss_percent"], 25.0);
+        assert_eq!(summary["avg_ms"], 0.53);
+        let macos = "2 packets transmitted, 0 packets received, 100.0% packet loss\n";
+        let summary = parse_ping_summary(macos);
+        assert_eq!(summary["loss_percent"], 100.0);
+        assert_eq!(summary["min_ms"], serde_json::Value::Null);
+
+        assert!(validate_host("router.lan").is_ok());
+        assert!(validate_host("fe80::1").is_ok());
+        assert!(validate_host("-f").is_err());
+        assert!(validate_host("host; reboot").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_link_command() {
+        assert_eq!(link_command("eth0", Some(false), None).unwrap(), ["ip", "link", "set", "dev", "eth0", "down"]);
+        assert_eq!(link_command("eth0", None, Some(9000)).unwrap(), ["ip", "link", "set", "dev", "eth0", "mtu", "9000"]);
+        assert!(link_command("lo", Some(true), Some(1500)).is_ok(), "loopback can be brought up");
+        assert!(matches!(link_command("lo", Some(false), None), Err(GantryError::Unsupported(_))));
+        assert!(link_command("eth0", None, Some(575)).is_err());
+        assert!(link_command("eth0", None, Some(9217)).is_err());
+        assert!(link_command("eth0", None, None).is_err());
+        assert!(link_command("-eth0", Some(true), None).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_talkers_from_ss_samples() {
+        let before = "ESTAB 0 0 192.168.1.5:51000 140.82.112.3:443 users:((\"firefox\",pid=4242,fd=87))\n\
+                      \t cubic wscale:7,7 bytes_sent:1000 bytes_acked:1000 bytes_received:50000 segs_out:10\n\
+                      ESTAB 0 0 127.0.0.1:42172 127.0.0.1:48271 users:((\"node\",pid=77,fd=3))\n\
+                      \t cubic bytes_sent:9999999 bytes_received:9999999\n";
+        let after = "ESTAB 0 0 192.168.1.5:51000 140.82.112.3:443 users:((\"firefox\",pid=4242,fd=87))\n\
+                     \t cubic wscale:7,7 bytes_sent:3000 bytes_acked:3000 bytes_received:250000 segs_out:30\n\
+                     ESTAB 0 0 [2001:db8::5]:40000 [2001:db8::1]:22 users:((\"ssh\",pid=900,fd=3),(\"ssh\",pid=901,fd=3))\n\
+                     \t cubic bytes_sent:400 bytes_received:100\n\
+                     ESTAB 0 0 127.0.0.1:42172 127.0.0.1:48271 users:((\"node\",pid=77,fd=3))\n\
+                     \t cubic bytes_sent:19999999 bytes_received:19999999\n";
+        let before = parse_ss_tcp_info(before);
+        assert_eq!(before.len(), 1, "loopback sockets are skipped");
+        assert_eq!(before[0].received, 50000);
+
+        let talkers = talker_rates(&before, &parse_ss_tcp_info(after), 2.0);
+        assert_eq!(talkers.len(), 2);
+        assert_eq!(talkers[0], json!({"pid": 4242, "name": "firefox", "rx_bps": 100000.0, "tx_bps": 1000.0}));
+        assert_eq!(talkers[1]["pid"], 900, "a new socket counts all of its bytes, for its first process");
+        assert_eq!(talkers[1]["tx_bps"], 200.0);
+    }
+
+    #[test]
+    fn test_parse_key_values_ethtool_driver() {
+        let sample = "driver: e1000e\nversion: 6.5.0\nfirmware-version: 0.4-4\nbus-info: 0000:00:1f.6\n";
+        let kv = parse_key_values(sample);
+        assert_eq!(kv["driver"], "e1000e");
+        assert_eq!(kv["firmware-version"], "0.4-4");
+        assert_eq!(kv["bus-info"], "0000:00:1f.6", "values containing colons should be kept whole");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_ethtool_list() {
+        let sample = "Settings for eth0:\n\
+                      \tSupported ports: [ TP ]\n\
+                      \tSupported link modes:   10baseT/Half 10baseT/Full\n\
+                      \t                        100baseT/Half 100baseT/Full\n\
+                      \t                        1000baseT/Full\n\
+                      \tSupported pause frame use: No\n\
+                      \tAdvertised link modes:  Not reported\n\
+                      \tSpeed: 1000Mb/s\n";
+        assert_eq!(
+            parse_ethtool_list(sample, "Supported link modes"),
+            vec!["10baseT/Half", "10baseT/Full", "100baseT/Half", "100baseT/Full", "1000baseT/Full"]
+        );
+        assert!(parse_ethtool_list(sample, "Advertised link modes").is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_iw_link() {
+        let sample = "Connected to aa:bb:cc:dd:ee:ff (on wlan0)\n\
+                      \tSSID: HomeNet\n\
+                      \tfreq: 5180\n\
+                      \tsignal: -52 dBm\n\
+                      \ttx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2\n";
+        let link = parse_iw_link(sample);
+        assert_eq!(link["connected"], true);
+        assert_eq!(link["ssid"], "HomeNet");
+        assert_eq!(link["signal_dbm"], -52);
+        assert_eq!(link["frequency_mhz"].as_f64(), Some(5180.0));
+        assert_eq!(parse_iw_link("Not connected.\n")["connected"], false);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_network_device_details_loopback() {
+        let result = get_network_device_details("lo".to_string());
+        assert!(result.is_ok(), "get_network_device_details failed: {:?}", result.err());
+        let details = result.unwrap();
+        assert_eq!(details["name"], "lo");
+        assert!(details["statistics"]["rx_bytes"].as_u64().is_some(), "statistics should include rx_bytes");
+        assert!(details["wireless"].is_null(), "loopback is not wireless");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_network_device_details_missing() {
+        assert!(get_network_device_details("nosuchif0".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_dbm_to_percent() {
+        assert_eq!(dbm_to_percent(-50.0), 100);
+        assert_eq!(dbm_to_percent(-75.0), 50);
+        assert_eq!(dbm_to_percent(-110.0), 0);
+    }
+
+    #[test]
+    fn test_dedup_wifi_networks() {
+        let net = |ssid: &str, signal: u32| WifiNetwork {
+            ssid: ssid.to_string(),
+            signal,
+            security: "WPA2".to_string(),
+            frequency_mhz: None,
+        };
+        let result = dedup_wifi_networks(vec![net("Home", 40), net("Cafe", 60), net("Home", 80), net("", 99)]);
+        assert_eq!(result.len(), 2, "duplicates and hidden networks should be dropped");
+        assert_eq!(result[0].ssid, "Home");
+        assert_eq!(result[0].signal, 80, "strongest access point should be kept");
+        assert_eq!(result[1].ssid, "Cafe");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_nmcli_wifi_list() {
+        let sample = "Home\\:Net:72:WPA2:5180 MHz\nOpen Cafe:40::2412 MHz\n";
+        let networks = parse_nmcli_wifi_list(sample);
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "Home:Net", "escaped colons belong to the SSID");
+        assert_eq!(networks[0].signal, 72);
+        assert_eq!(networks[0].frequency_mhz, Some(5180));
+        assert_eq!(networks[1].security, "Open");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_iw_scan() {
+        let sample = "BSS aa:bb:cc:dd:ee:01(on wlan0)\n\
+                      \tfreq: 2412\n\
+                      \tcapability: ESS Privacy ShortSlotTime (0x0411)\n\
+                      \tsignal: -45.00 dBm\n\
+                      \tSSID: Secure\n\
+                      \tRSN:\t * Version: 1\n\
+                      BSS aa:bb:cc:dd:ee:02(on wlan0)\n\
+                      \tfreq: 5180.0\n\
+                      \tcapability: ESS (0x0401)\n\
+                      \tsignal: -80.00 dBm\n\
+                      \tSSID: Guest\n";
+        let networks = parse_iw_scan(sample);
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "Secure");
+        assert_eq!(networks[0].security, "WPA2");
+        assert_eq!(networks[0].signal, 100);
+        assert_eq!(networks[1].security, "Open");
+        assert_eq!(networks[1].frequency_mhz, Some(5180));
+    }
+}
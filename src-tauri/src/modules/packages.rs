@@ -0,0 +1,942 @@
+use serde::Serialize;
+use serde_json::json;
+use std::process::Command;
+
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: String,
+    pub architecture: Option<String>,
+    pub installed_size: Option<u64>,
+    pub summary: String,
+}
+
+fn validate_package_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | ':' | '_' | '@' | '/'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid package name: {}", name))
+    }
+}
+
+fn filter_packages(
+    packages: Vec<PackageInfo>,
+    query: Option<&str>,
+    offset: usize,
+    limit: usize,
+) -> (usize, Vec<PackageInfo>) {
+    let needle = query.map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty());
+    let matching: Vec<PackageInfo> = packages
+        .into_iter()
+        .filter(|p| match &needle {
+            Some(n) => p.name.to_lowercase().contains(n) || p.summary.to_lowercase().contains(n),
+            None => true,
+        })
+        .collect();
+    let total = matching.len();
+    (total, matching.into_iter().skip(offset).take(limit).collect())
+}
+
+#[cfg(target_os = "linux")]
+const DPKG_QUERY_FORMAT: &str =
+    "${db:Status-Abbrev}\t${Package}\t${Version}\t${Architecture}\t${Installed-Size}\t${binary:Summary}\n";
+
+#[cfg(target_os = "linux")]
+fn parse_dpkg_query(output: &str) -> Vec<PackageInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 6 || !fields[0].starts_with("ii") {
+                return None;
+            }
+            Some(PackageInfo {
+                name: fields[1].to_string(),
+                version: fields[2].to_string(),
+                architecture: Some(fields[3].to_string()).filter(|a| !a.is_empty()),
+                // dpkg reports Installed-Size in KiB.
+                installed_size: fields[4].trim().parse::<u64>().ok().map(|kib| kib * 1024),
+                summary: fields[5].to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_control_fields(text: &str) -> HashMap<String, String> {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for line in text.lines() {
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(value) = last_key.as_ref().and_then(|k| fields.get_mut(k)) {
+                let cont = line.trim();
+                value.push('\n');
+                value.push_str(if cont == "." { "" } else { cont });
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            fields.insert(key.clone(), value.trim().to_string());
+            last_key = Some(key);
+        }
+    }
+    fields
+}
+
+#[cfg(target_os = "linux")]
+fn parse_apt_policy_origin(output: &str) -> Option<String> {
+    let mut in_installed = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("***") {
+            in_installed = !rest.trim().is_empty();
+            continue;
+        }
+        if !in_installed {
+            continue;
+        }
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if parts.len() >= 2 && parts[0].chars().all(|c| c.is_ascii_digit()) {
+            if parts[1] == "/var/lib/dpkg/status" {
+                continue;
+            }
+            return Some(parts[1..parts.len().min(3)].join(" "));
+        }
+        if !parts.is_empty() {
+            break;
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn split_dependencies(value: Option<&String>) -> Vec<String> {
+    value
+        .map(|v| v.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_packages(
+    query: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    let output = Command::new("dpkg-query")
+        .args(["-W", "-f", DPKG_QUERY_FORMAT])
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|_| "Package listing requires dpkg (Debian-based systems)".to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("dpkg-query failed: {}", stderr.trim()));
+    }
+
+    let mut packages = parse_dpkg_query(&String::from_utf8_lossy(&output.stdout));
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, page) = filter_packages(packages, query.as_deref(), offset, limit);
+    Ok(json!({"manager": "dpkg", "total": total, "offset": offset, "limit": limit, "packages": page}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_package_details(name: String) -> Result<serde_json::Value, String> {
+    validate_package_name(&name)?;
+
+    let run = |program: &str, args: &[&str]| super::util::run_tool(program, args).ok();
+
+    let status = run("dpkg", &["-s", &name]);
+    let installed = status
+        .as_deref()
+        .map(parse_control_fields)
+        .is_some_and(|f| f.get("Status").is_some_and(|s| s.ends_with("installed")));
+    let control = status
+        .filter(|_| installed)
+        .or_else(|| run("apt-cache", &["show", &name]))
+        .ok_or_else(|| format!("Package {} not found", name))?;
+    let fields = parse_control_fields(&control);
+
+    let origin = if installed {
+        run("apt-cache", &["policy", &name])
+            .and_then(|p| parse_apt_policy_origin(&p))
+            .or_else(|| Some("local".to_string()))
+    } else {
+        None
+    };
+
+    let (summary, description) = match fields.get("Description") {
+        Some(d) => match d.split_once('\n') {
+            Some((first, rest)) => (first.to_string(), rest.to_string()),
+            None => (d.clone(), String::new()),
+        },
+        None => (String::new(), String::new()),
+    };
+
+    Ok(json!({
+        "name": name,
+        "installed": installed,
+        "version": fields.get("Version"),
+        "architecture": fields.get("Architecture"),
+        "installed_size": fields.get("Installed-Size").and_then(|s| s.parse::<u64>().ok()).map(|kib| kib * 1024),
+        "maintainer": fields.get("Maintainer"),
+        "section": fields.get("Section"),
+        "homepage": fields.get("Homepage"),
+        "summary": summary,
+        "description": description,
+        "depends": split_dependencies(fields.get("Depends")),
+        "recommends": split_dependencies(fields.get("Recommends")),
+        "origin": origin,
+    }))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradablePackage {
+    pub name: String,
+    pub current_version: String,
+    pub candidate_version: String,
+    pub source: String,
+    pub held: bool,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_apt_upgradable(output: &str) -> Vec<UpgradablePackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, rest) = line.split_once('/')?;
+            let mut parts = rest.split_whitespace();
+            let source = parts.next()?;
+            let candidate = parts.next()?;
+            let current = line
+                .split("[upgradable from:")
+                .nth(1)?
+                .trim()
+                .trim_end_matches(']')
+                .trim();
+            Some(UpgradablePackage {
+                name: name.to_string(),
+                current_version: current.to_string(),
+                candidate_version: candidate.to_string(),
+                source: source.to_string(),
+                held: false,
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_dpkg_holds(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            (parts.next()? == "hold").then(|| name.split(':').next().unwrap_or(name).to_string())
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_apt_lock_error(stderr: &str) -> Option<String> {
+    let line = stderr.lines().find(|l| l.contains("Could not get lock"))?;
+    Some(match line.split_once("It is held by ") {
+        Some((_, holder)) => holder.trim().trim_end_matches('.').to_string(),
+        None => "another process".to_string(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn running_package_manager() -> Option<String> {
+    const MANAGERS: [&str; 6] = ["apt", "apt-get", "aptitude", "dpkg", "unattended-upgr", "synaptic"];
+    let entries = std::fs::read_dir("/proc").ok()?;
+    entries.flatten().find_map(|entry| {
+        let pid = entry.file_name().to_string_lossy().parse::<u32>().ok()?;
+        let comm = std::fs::read_to_string(entry.path().join("comm")).ok()?;
+        let comm = comm.trim();
+        MANAGERS.contains(&comm).then(|| format!("process {} ({})", pid, comm))
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_upgradable_packages() -> Result<serde_json::Value, String> {
+    let output = Command::new("apt")
+        .args(["list", "--upgradable"])
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|_| "Upgrade listing requires apt (Debian-based systems)".to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("apt list failed: {}", stderr.trim()));
+    }
+
+    // apt-mark only knows holds set through apt; dpkg's selections also
+    // carry the ones set with `dpkg --set-selections`.
+    let mut held: Vec<String> = Command::new("apt-mark")
+        .arg("showhold")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(|l| l.trim().to_string()).collect())
+        .unwrap_or_default();
+    if let Ok(o) = Command::new("dpkg").arg("--get-selections").env("LC_ALL", "C").output() {
+        held.extend(parse_dpkg_holds(&String::from_utf8_lossy(&o.stdout)));
+    }
+
+    let mut packages = parse_apt_upgradable(&String::from_utf8_lossy(&output.stdout));
+    for package in packages.iter_mut() {
+        package.held = held.contains(&package.name);
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(json!({"manager": "apt", "packages": packages}))
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn upgrade_packages(app: tauri::AppHandle, names: Option<Vec<String>>) -> Result<serde_json::Value, String> {
+    if let Some(ref names) = names {
+        if names.is_empty() {
+            return Err("No packages selected".to_string());
+        }
+        for name in names {
+            validate_package_name(name)?;
+        }
+    }
+    if let Some(holder) = running_package_manager() {
+        return Err(format!(
+            "Another package manager is running ({}). Try again when it finishes.",
+            holder
+        ));
+    }
+
+    let mut cmd = Command::new("pkexec");
+    cmd.args([
+        "env",
+        "LC_ALL=C",
+        "DEBIAN_FRONTEND=noninteractive",
+        "apt-get",
+        "-y",
+        "-o",
+        "Dpkg::Options::=--force-confold",
+    ]);
+    match names {
+        Some(ref names) => {
+            cmd.args(["install", "--only-upgrade"]).args(names);
+        }
+        None => {
+            cmd.arg("dist-upgrade");
+        }
+    }
+
+    let (success, _stdout, stderr) = super::config::run_streaming(&app, cmd, "package-upgrade-progress")?;
+    let target = names.as_ref().map(|names| names.join(" ")).unwrap_or_else(|| "all".to_string());
+    super::audit::record("upgrade_packages", &target, "pkexec", &super::config::apt_result(success, &stderr));
+    if let Some(holder) = parse_apt_lock_error(&stderr) {
+        return Err(format!(
+            "The package database is locked by {}. Try again when it finishes.",
+            holder
+        ));
+    }
+
+    Ok(json!({"success": success, "packages": names, "stderr": stderr}))
+}
+
+#[cfg(target_os = "linux")]
+const AUTO_UPGRADES_FILE: &str = "/etc/apt/apt.conf.d/20auto-upgrades";
+#[cfg(target_os = "linux")]
+const UNATTENDED_UPGRADES_FILE: &str = "/etc/apt/apt.conf.d/50unattended-upgrades";
+#[cfg(target_os = "linux")]
+const DNF_AUTOMATIC_FILE: &str = "/etc/dnf/automatic.conf";
+
+#[cfg(target_os = "linux")]
+fn parse_apt_conf_values(content: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, rest)) = line.split_once(char::is_whitespace) else { continue };
+        let rest = rest.trim();
+        if let Some(value) = rest.strip_prefix('"').and_then(|v| v.strip_suffix("\";")) {
+            values.insert(key.to_string(), value.to_string());
+        }
+    }
+    values
+}
+
+#[cfg(target_os = "linux")]
+fn parse_apt_conf_list(content: &str, key: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut inside = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+        if !inside {
+            inside = line.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with('{'));
+            continue;
+        }
+        if line.starts_with('}') {
+            inside = false;
+            continue;
+        }
+        if let Some(value) = line.strip_prefix('"').and_then(|v| v.split_once('"')).map(|(v, _)| v) {
+            items.push(value.to_string());
+        }
+    }
+    items
+}
+
+#[cfg(target_os = "linux")]
+fn upsert_apt_conf(content: &str, key: &str, value: &str) -> String {
+    let assignment = format!("{} \"{}\";", key, value);
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let is_key = line.trim().strip_prefix(key).is_some_and(|rest| rest.starts_with(char::is_whitespace));
+            if is_key && !replaced {
+                replaced = true;
+                assignment.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(assignment);
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(target_os = "linux")]
+fn parse_unattended_last_run(log: &str) -> Option<String> {
+    log.lines()
+        .rev()
+        .find(|l| l.contains("Starting unattended upgrades script"))
+        .or_else(|| log.lines().rev().find(|l| !l.trim().is_empty()))
+        .and_then(|l| l.get(..19))
+        .filter(|ts| chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S").is_ok())
+        .map(|ts| ts.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn ini_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut current = String::new();
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = name.trim().to_string();
+        } else if current == section && !line.starts_with('#') {
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key {
+                    return Some(v.trim().to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn upsert_ini_value(content: &str, section: &str, key: &str, value: &str) -> String {
+    let assignment = format!("{} = {}", key, value);
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let header = lines.iter().position(|l| l.trim() == format!("[{}]", section));
+    match header {
+        Some(start) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|l| l.trim().starts_with('['))
+                .map_or(lines.len(), |p| start + 1 + p);
+            let existing = (start + 1..end).find(|&i| {
+                let line = lines[i].trim();
+                !line.starts_with('#') && line.split_once('=').is_some_and(|(k, _)| k.trim() == key)
+            });
+            match existing {
+                Some(i) => lines[i] = assignment,
+                None => {
+                    // Keep the key next to the section's other settings, before any trailing blank lines.
+                    let mut insert_at = end;
+                    while insert_at > start + 1 && lines[insert_at - 1].trim().is_empty() {
+                        insert_at -= 1;
+                    }
+                    lines.insert(insert_at, assignment);
+                }
+            }
+        }
+        None => {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", section));
+            lines.push(assignment);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(target_os = "linux")]
+fn dnf_automatic_timer() -> Option<&'static str> {
+    ["dnf5-automatic.timer", "dnf-automatic.timer"].into_iter().find(|unit| {
+        ["/usr/lib/systemd/system", "/lib/systemd/system", "/etc/systemd/system"]
+            .iter()
+            .any(|dir| std::path::Path::new(dir).join(unit).exists())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn dnf_auto_update_config() -> serde_json::Value {
+    let timer = dnf_automatic_timer();
+    let content = std::fs::read_to_string(DNF_AUTOMATIC_FILE).unwrap_or_default();
+    let timer_enabled = timer.is_some_and(|unit| {
+        Command::new("systemctl")
+            .args(["is-enabled", unit])
+            .output()
+            .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).trim() == "enabled")
+    });
+    let apply_updates = ini_value(&content, "commands", "apply_updates").is_some_and(|v| matches!(v.as_str(), "yes" | "true" | "1"));
+    let reboot = ini_value(&content, "commands", "reboot").unwrap_or_else(|| "never".to_string());
+    let last_run = timer.and_then(|unit| {
+        let output = Command::new("systemctl").args(["show", unit, "-p", "LastTriggerUSec", "--value"]).output().ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!value.is_empty() && value != "n/a").then_some(value)
+    });
+
+    json!({
+        "manager": "dnf-automatic",
+        "installed": timer.is_some(),
+        "enabled": timer_enabled && apply_updates,
+        "timer": timer,
+        "timer_enabled": timer_enabled,
+        "apply_updates": apply_updates,
+        "upgrade_type": ini_value(&content, "commands", "upgrade_type").unwrap_or_else(|| "default".to_string()),
+        "automatic_reboot": reboot != "never",
+        "reboot": reboot,
+        "last_run": last_run,
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_auto_update_config() -> Result<serde_json::Value, String> {
+    let has_apt = std::path::Path::new("/usr/bin/apt-get").exists();
+    if !has_apt && std::path::Path::new("/usr/bin/dnf").exists() {
+        return Ok(dnf_auto_update_config());
+    }
+
+    let periodic = parse_apt_conf_values(&std::fs::read_to_string(AUTO_UPGRADES_FILE).unwrap_or_default());
+    let unattended = std::fs::read_to_string(UNATTENDED_UPGRADES_FILE).unwrap_or_default();
+    let settings = parse_apt_conf_values(&unattended);
+    let mut allowed_origins = parse_apt_conf_list(&unattended, "Unattended-Upgrade::Allowed-Origins");
+    allowed_origins.extend(parse_apt_conf_list(&unattended, "Unattended-Upgrade::Origins-Pattern"));
+
+    // 50unattended-upgrades is read after 20auto-upgrades, so its value wins.
+    let reboot = settings.get("Unattended-Upgrade::Automatic-Reboot").or_else(|| periodic.get("Unattended-Upgrade::Automatic-Reboot"));
+    let is_on = |value: Option<&String>| value.is_some_and(|v| v != "0" && !v.is_empty());
+    let update_lists = periodic.get("APT::Periodic::Update-Package-Lists").cloned();
+    let unattended_upgrade = periodic.get("APT::Periodic::Unattended-Upgrade").cloned();
+
+    let last_run = std::fs::read_to_string("/var/log/unattended-upgrades/unattended-upgrades.log")
+        .ok()
+        .and_then(|log| parse_unattended_last_run(&log))
+        .or_else(|| {
+            let modified = std::fs::metadata("/var/lib/apt/periodic/unattended-upgrades-stamp").ok()?.modified().ok()?;
+            Some(chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d %H:%M:%S").to_string())
+        });
+
+    Ok(json!({
+        "manager": if has_apt { Some("unattended-upgrades") } else { None },
+        "installed": std::path::Path::new("/usr/bin/unattended-upgrade").exists(),
+        "enabled": is_on(update_lists.as_ref()) && is_on(unattended_upgrade.as_ref()),
+        "update_package_lists": update_lists,
+        "unattended_upgrade": unattended_upgrade,
+        "allowed_origins": allowed_origins,
+        "automatic_reboot": reboot.is_some_and(|v| v == "true"),
+        "automatic_reboot_time": settings.get("Unattended-Upgrade::Automatic-Reboot-Time"),
+        "last_run": last_run,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) struct AutoUpdatePlan {
+    pub(crate) target: &'static str,
+    pub(crate) content: String,
+    pub(crate) then: Option<Vec<String>>,
+}
+
+// The admin helper recomputes this as root, so a request only ever carries the two switches.
+#[cfg(target_os = "linux")]
+pub(crate) fn auto_update_plan(enabled: bool, with_reboot: bool) -> Result<AutoUpdatePlan, String> {
+    let has_apt = std::path::Path::new("/usr/bin/apt-get").exists();
+    if !has_apt && std::path::Path::new("/usr/bin/dnf").exists() {
+        let timer = dnf_automatic_timer().ok_or("dnf-automatic is not installed. Install it with: sudo dnf install dnf-automatic")?;
+        let current = std::fs::read_to_string(DNF_AUTOMATIC_FILE).unwrap_or_default();
+        let content = upsert_ini_value(&current, "commands", "apply_updates", if enabled { "yes" } else { "no" });
+        let content = upsert_ini_value(&content, "commands", "reboot", if with_reboot { "when-needed" } else { "never" });
+        let verb = if enabled { "enable" } else { "disable" };
+        let then = ["systemctl", verb, "--now", timer].map(String::from).to_vec();
+        return Ok(AutoUpdatePlan { target: DNF_AUTOMATIC_FILE, content, then: Some(then) });
+    }
+    if enabled && !std::path::Path::new("/usr/bin/unattended-upgrade").exists() {
+        return Err("unattended-upgrades is not installed. Install it with: sudo apt install unattended-upgrades".to_string());
+    }
+    let value = if enabled { "1" } else { "0" };
+    let current = std::fs::read_to_string(AUTO_UPGRADES_FILE).unwrap_or_default();
+    let content = upsert_apt_conf(&current, "APT::Periodic::Update-Package-Lists", value);
+    let content = upsert_apt_conf(&content, "APT::Periodic::Unattended-Upgrade", value);
+    let content = upsert_apt_conf(&content, "Unattended-Upgrade::Automatic-Reboot", if with_reboot { "true" } else { "false" });
+    Ok(AutoUpdatePlan { target: AUTO_UPGRADES_FILE, content, then: None })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn set_auto_updates(enabled: bool, with_reboot: bool) -> Result<serde_json::Value, String> {
+    let plan = auto_update_plan(enabled, with_reboot)?;
+    let staged = super::util::private_temp("auto-updates", plan.content.as_bytes())?;
+    super::admin::set_auto_updates(enabled, with_reboot, &plan, staged.path())
+        .map_err(|e| format!("Failed to update automatic updates: {}", e.message()))?;
+
+    let mut config = get_auto_update_config()?;
+    if enabled && config["automatic_reboot"] != json!(with_reboot) {
+        config["warning"] = json!(format!("Automatic-Reboot is also set in {}, which takes precedence", UNATTENDED_UPGRADES_FILE));
+    }
+    Ok(config)
+}
+
+#[cfg(target_os = "macos")]
+fn brew_info_json(args: &[&str]) -> Result<serde_json::Value, String> {
+    let brew = super::util::find_brew().ok_or_else(|| "Homebrew not found. Install it from https://brew.sh".to_string())?;
+    let output = Command::new(&brew)
+        .args(["info", "--json=v2"])
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run brew: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("brew info failed: {}", stderr.trim()));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Failed to parse brew output: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_packages(
+    query: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    let info = brew_info_json(&["--installed"])?;
+
+    let mut packages: Vec<PackageInfo> = Vec::new();
+    for formula in info["formulae"].as_array().into_iter().flatten() {
+        packages.push(PackageInfo {
+            name: formula["name"].as_str().unwrap_or("").to_string(),
+            version: formula["installed"][0]["version"].as_str().unwrap_or("").to_string(),
+            architecture: None,
+            installed_size: None,
+            summary: formula["desc"].as_str().unwrap_or("").to_string(),
+        });
+    }
+    for cask in info["casks"].as_array().into_iter().flatten() {
+        packages.push(PackageInfo {
+            name: cask["token"].as_str().unwrap_or("").to_string(),
+            version: cask["installed"].as_str().unwrap_or("").to_string(),
+            architecture: None,
+            installed_size: None,
+            summary: cask["desc"].as_str().unwrap_or("").to_string(),
+        });
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, page) = filter_packages(packages, query.as_deref(), offset, limit);
+    Ok(json!({"manager": "brew", "total": total, "offset": offset, "limit": limit, "packages": page}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_package_details(name: String) -> Result<serde_json::Value, String> {
+    validate_package_name(&name)?;
+    let info = brew_info_json(&[&name])?;
+
+    let formula = &info["formulae"][0];
+    let cask = &info["casks"][0];
+    let (item, version) = if !formula.is_null() {
+        (formula, formula["installed"][0]["version"].as_str().or(formula["versions"]["stable"].as_str()))
+    } else if !cask.is_null() {
+        (cask, cask["installed"].as_str().or(cask["version"].as_str()))
+    } else {
+        return Err(format!("Package {} not found", name));
+    };
+
+    Ok(json!({
+        "name": name,
+        "installed": !item["installed"].is_null() && item["installed"] != json!([]),
+        "version": version,
+        "architecture": null,
+        "installed_size": null,
+        "maintainer": null,
+        "section": null,
+        "homepage": item["homepage"].as_str(),
+        "summary": item["desc"].as_str().unwrap_or(""),
+        "description": "",
+        "depends": item["dependencies"].as_array().cloned().unwrap_or_default(),
+        "recommends": [],
+        "origin": item["tap"].as_str(),
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_upgradable_packages() -> Result<serde_json::Value, String> {
+    let brew = super::util::find_brew().ok_or_else(|| "Homebrew not found. Install it from https://brew.sh".to_string())?;
+    let output = Command::new(&brew)
+        .args(["outdated", "--json=v2"])
+        .output()
+        .map_err(|e| format!("Failed to run brew: {}", e))?;
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse brew output: {}", e))?;
+
+    let mut packages: Vec<UpgradablePackage> = ["formulae", "casks"]
+        .iter()
+        .flat_map(|kind| parsed[*kind].as_array().cloned().unwrap_or_default())
+        .map(|item| UpgradablePackage {
+            name: item["name"].as_str().unwrap_or("").to_string(),
+            current_version: item["installed_versions"][0].as_str().unwrap_or("").to_string(),
+            candidate_version: item["current_version"].as_str().unwrap_or("").to_string(),
+            source: "homebrew".to_string(),
+            held: item["pinned"].as_bool().unwrap_or(false),
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(json!({"manager": "brew", "packages": packages}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn upgrade_packages(app: tauri::AppHandle, names: Option<Vec<String>>) -> Result<serde_json::Value, String> {
+    if let Some(ref names) = names {
+        if names.is_empty() {
+            return Err("No packages selected".to_string());
+        }
+        for name in names {
+            validate_package_name(name)?;
+        }
+    }
+    let brew = super::util::find_brew().ok_or_else(|| "Homebrew not found".to_string())?;
+    let mut cmd = Command::new(&brew);
+    cmd.arg("upgrade");
+    if let Some(ref names) = names {
+        cmd.args(names);
+    }
+
+    let (success, _stdout, stderr) = super::config::run_streaming(&app, cmd, "package-upgrade-progress")?;
+    Ok(json!({"success": success, "packages": names, "stderr": stderr}))
+}
+
+#[cfg(target_os = "macos")]
+fn software_update_pref(key: &str) -> Option<String> {
+    let output = Command::new("defaults")
+        .args(["read", "/Library/Preferences/com.apple.SoftwareUpdate", key])
+        .output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_auto_update_config() -> Result<serde_json::Value, String> {
+    // Unset keys mean the System Settings default, which is on.
+    let flag = |key: &str| software_update_pref(key).as_deref() != Some("0");
+    Ok(json!({
+        "manager": "softwareupdate",
+        "installed": true,
+        "enabled": flag("AutomaticCheckEnabled") && flag("AutomaticallyInstallMacOSUpdates"),
+        "automatic_check": flag("AutomaticCheckEnabled"),
+        "automatic_download": flag("AutomaticDownload"),
+        "install_macos_updates": flag("AutomaticallyInstallMacOSUpdates"),
+        "install_security_updates": flag("CriticalUpdateInstall"),
+        "automatic_reboot": flag("AutomaticallyInstallMacOSUpdates"),
+        "last_run": software_update_pref("LastSuccessfulDate"),
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn set_auto_updates(_enabled: bool, _with_reboot: bool) -> Result<serde_json::Value, String> {
+    Err("Change automatic updates in System Settings > General > Software Update".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, summary: &str) -> PackageInfo {
+        PackageInfo {
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            architecture: None,
+            installed_size: None,
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_filter_packages() {
+        let packages = vec![pkg("curl", "command line URL tool"), pkg("libcurl4", "easy-to-use client-side URL library"), pkg("vim", "Vi IMproved")];
+        let (total, page) = filter_packages(packages.clone(), Some("URL"), 0, 10);
+        assert_eq!(total, 2, "query should match names and summaries case-insensitively");
+        assert_eq!(page.len(), 2);
+
+        let (total, page) = filter_packages(packages, None, 1, 1);
+        assert_eq!(total, 3);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].name, "libcurl4");
+    }
+
+    #[test]
+    fn test_validate_package_name() {
+        assert!(validate_package_name("libc6:amd64").is_ok());
+        assert!(validate_package_name("g++").is_ok());
+        assert!(validate_package_name("--purge").is_err());
+        assert!(validate_package_name("foo; rm").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_dpkg_query() {
+        let sample = "ii \tcurl\t7.81.0-1ubuntu1.15\tamd64\t453\tcommand line tool for transferring data with URL syntax\n\
+                      rc \told-pkg\t1.0\tamd64\t10\tremoved but configured\n\
+                      ii \ttzdata\t2024a-0ubuntu0.22.04\tall\t\ttime zone and daylight-saving time data\n";
+        let packages = parse_dpkg_query(sample);
+        assert_eq!(packages.len(), 2, "only installed packages should be listed");
+        assert_eq!(packages[0].name, "curl");
+        assert_eq!(packages[0].installed_size, Some(453 * 1024));
+        assert_eq!(packages[1].architecture.as_deref(), Some("all"));
+        assert_eq!(packages[1].installed_size, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_control_fields() {
+        let sample = "Package: curl\nStatus: install ok installed\nDepends: libc6 (>= 2.34), libcurl4 (= 7.81.0)\n\
+                      Description: command line tool\n transfers data\n .\n with URL syntax\n";
+        let fields = parse_control_fields(sample);
+        assert_eq!(fields["Package"], "curl");
+        assert_eq!(split_dependencies(fields.get("Depends")), vec!["libc6 (>= 2.34)", "libcurl4 (= 7.81.0)"]);
+        assert_eq!(fields["Description"], "command line tool\ntransfers data\n\nwith URL syntax");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_apt_policy_origin() {
+        let sample = "curl:\n  Installed: 7.81.0-1ubuntu1.15\n  Candidate: 7.81.0-1ubuntu1.15\n  Version table:\n\
+                      \x20*** 7.81.0-1ubuntu1.15 500\n\
+                      \x20       500 http://archive.ubuntu.com/ubuntu jammy-updates/main amd64 Packages\n\
+                      \x20       100 /var/lib/dpkg/status\n\
+                      \x20    7.81.0-1 500\n\
+                      \x20       500 http://archive.ubuntu.com/ubuntu jammy/main amd64 Packages\n";
+        assert_eq!(
+            parse_apt_policy_origin(sample).as_deref(),
+            Some("http://archive.ubuntu.com/ubuntu jammy-updates/main")
+        );
+        let local = "foo:\n  Version table:\n *** 1.0 100\n        100 /var/lib/dpkg/status\n";
+        assert_eq!(parse_apt_policy_origin(local), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_apt_upgradable() {
+        let sample = "Listing... Done\n\
+                      curl/jammy-updates,jammy-security 7.81.0-1ubuntu1.16 amd64 [upgradable from: 7.81.0-1ubuntu1.15]\n\
+                      tzdata/jammy-updates 2024a-0ubuntu0.22.04.1 all [upgradable from: 2024a-0ubuntu0.22.04]\n";
+        let packages = parse_apt_upgradable(sample);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "curl");
+        assert_eq!(packages[0].source, "jammy-updates,jammy-security");
+        assert_eq!(packages[0].candidate_version, "7.81.0-1ubuntu1.16");
+        assert_eq!(packages[0].current_version, "7.81.0-1ubuntu1.15");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_dpkg_holds() {
+        let sample = "curl\t\t\t\t\t\tinstall\nlinux-image-generic\t\t\t\thold\nlibc6:amd64\t\t\t\t\thold\nfoo\t\t\t\t\t\tdeinstall\n";
+        assert_eq!(parse_dpkg_holds(sample), vec!["linux-image-generic", "libc6"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_apt_lock_error() {
+        let stderr = "E: Could not get lock /var/lib/dpkg/lock-frontend. It is held by process 4242 (unattended-upgr)\n\
+                      E: Unable to acquire the dpkg frontend lock (/var/lib/dpkg/lock-frontend), is another process using it?\n";
+        assert_eq!(parse_apt_lock_error(stderr).as_deref(), Some("process 4242 (unattended-upgr)"));
+        assert_eq!(parse_apt_lock_error("E: Unable to locate package foo\n"), None);
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_unattended_upgrades_config() {
+        let periodic = "APT::Periodic::Update-Package-Lists \"1\";\nAPT::Periodic::Unattended-Upgrade \"1\";\n";
+        let values = parse_apt_conf_values(periodic);
+        assert_eq!(values["APT::Periodic::Update-Package-Lists"], "1");
+
+        let unattended = "Unattended-Upgrade::Allowed-Origins {\n\
+                          \t\"${distro_id}:${distro_codename}\";\n\
+                          \t\"${distro_id}:${distro_codename}-security\";\n\
+                          //\t\"${distro_id}:${distro_codename}-proposed\";\n\
+                          };\n\
+                          //Unattended-Upgrade::Automatic-Reboot \"false\";\n\
+                          Unattended-Upgrade::Automatic-Reboot-Time \"02:00\";\n";
+        assert_eq!(
+            parse_apt_conf_list(unattended, "Unattended-Upgrade::Allowed-Origins"),
+            vec!["${distro_id}:${distro_codename}", "${distro_id}:${distro_codename}-security"]
+        );
+        let settings = parse_apt_conf_values(unattended);
+        assert!(!settings.contains_key("Unattended-Upgrade::Automatic-Reboot"), "commented settings are ignored");
+        assert_eq!(settings["Unattended-Upgrade::Automatic-Reboot-Time"], "02:00");
+
+        let log = "2024-05-01 06:25:13,456 INFO Starting unattended upgrades script\n\
+                   2024-05-01 06:25:20,001 INFO No packages found that can be upgraded unattended\n";
+        assert_eq!(parse_unattended_last_run(log).as_deref(), Some("2024-05-01 06:25:13"));
+        assert_eq!(parse_unattended_last_run(""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_upsert_auto_update_settings() {
+        let current = "APT::Periodic::Update-Package-Lists \"1\";\nAPT::Periodic::AutocleanInterval \"7\";\n";
+        let updated = upsert_apt_conf(current, "APT::Periodic::Update-Package-Lists", "0");
+        let updated = upsert_apt_conf(&updated, "APT::Periodic::Unattended-Upgrade", "0");
+        assert_eq!(
+            updated,
+            "APT::Periodic::Update-Package-Lists \"0\";\nAPT::Periodic::AutocleanInterval \"7\";\nAPT::Periodic::Unattended-Upgrade \"0\";\n"
+        );
+
+        let dnf = "[commands]\nupgrade_type = default\napply_updates = no\n\n[emitters]\nemit_via = stdio\n";
+        let updated = upsert_ini_value(dnf, "commands", "apply_updates", "yes");
+        let updated = upsert_ini_value(&updated, "commands", "reboot", "when-needed");
+        assert_eq!(ini_value(&updated, "commands", "apply_updates").as_deref(), Some("yes"));
+        assert_eq!(ini_value(&updated, "commands", "reboot").as_deref(), Some("when-needed"));
+        assert!(updated.contains("reboot = when-needed\n\n[emitters]"), "new keys stay in their section: {}", updated);
+        assert_eq!(upsert_ini_value("", "commands", "apply_updates", "yes"), "[commands]\napply_updates = yes\n");
+    }
+}
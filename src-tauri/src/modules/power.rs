@@ -0,0 +1,252 @@
+use super::util::run_tool;
+use serde::Serialize;
+use serde_json::json;
+
+#[cfg(target_os = "linux")]
+use super::util::read_sysfs_string;
+#[cfg(target_os = "linux")]
+use std::fs;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerSupply {
+    pub name: String,
+    pub supply_type: String,
+    pub model: Option<String>,
+    pub manufacturer: Option<String>,
+    pub percentage: Option<f64>,
+    pub online: Option<bool>,
+    pub charging: Option<bool>,
+    pub status: Option<String>,
+    pub watts: Option<f64>,
+    pub source: String,
+}
+
+#[cfg(target_os = "linux")]
+fn is_laptop_battery(supply_type: &str, scope: Option<&str>) -> bool {
+    supply_type == "Battery" && scope != Some("Device")
+}
+
+#[cfg(target_os = "linux")]
+fn sysfs_power_supply(path: &std::path::Path) -> Option<PowerSupply> {
+    let read = |file: &str| read_sysfs_string(&path.join(file));
+    let read_num = |file: &str| read(file).and_then(|v| v.parse::<f64>().ok());
+
+    let supply_type = read("type").unwrap_or_else(|| "Unknown".to_string());
+    if is_laptop_battery(&supply_type, read("scope").as_deref()) {
+        return None;
+    }
+
+    let status = read("status");
+    // voltage_max and current_max are in µV / µA; USB-PD adapters expose both.
+    let watts = match (read_num("voltage_max"), read_num("current_max")) {
+        (Some(v), Some(a)) if v > 0.0 && a > 0.0 => Some((v * a / 1e12 * 10.0).round() / 10.0),
+        _ => None,
+    };
+
+    Some(PowerSupply {
+        name: path.file_name()?.to_string_lossy().to_string(),
+        supply_type,
+        model: read("model_name"),
+        manufacturer: read("manufacturer"),
+        percentage: read_num("capacity"),
+        online: read("online").map(|v| v == "1"),
+        charging: status.as_deref().map(|s| s == "Charging"),
+        status,
+        watts,
+        source: "sysfs".to_string(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_upower_ups(output: &str) -> Vec<PowerSupply> {
+    let mut supplies = Vec::new();
+
+    for block in output.split("\n\n") {
+        let mut lines = block.lines();
+        let Some(header) = lines.next() else { continue };
+        let Some(object_path) = header.strip_prefix("Device:") else { continue };
+
+        let mut is_ups = false;
+        let mut ups = PowerSupply {
+            name: object_path.trim().rsplit('/').next().unwrap_or("ups").to_string(),
+            supply_type: "UPS".to_string(),
+            model: None,
+            manufacturer: None,
+            percentage: None,
+            online: None,
+            charging: None,
+            status: None,
+            watts: None,
+            source: "upower".to_string(),
+        };
+
+        for line in lines {
+            let trimmed = line.trim();
+            if trimmed == "ups" {
+                is_ups = true;
+                continue;
+            }
+            let Some((key, value)) = trimmed.split_once(':') else { continue };
+            let value = value.trim().to_string();
+            match key.trim() {
+                "model" => ups.model = Some(value),
+                "vendor" => ups.manufacturer = Some(value),
+                "percentage" => ups.percentage = value.trim_end_matches('%').trim().parse().ok(),
+                "power supply" => ups.online = Some(value == "yes"),
+                "state" => {
+                    ups.charging = Some(value == "charging");
+                    ups.status = Some(value);
+                }
+                "energy-rate" => {
+                    ups.watts = value.split_whitespace().next().and_then(|w| w.parse().ok());
+                }
+                _ => {}
+            }
+        }
+
+        if is_ups {
+            supplies.push(ups);
+        }
+    }
+
+    supplies
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn list_power_supplies() -> Result<serde_json::Value, String> {
+    let mut supplies: Vec<PowerSupply> = fs::read_dir("/sys/class/power_supply")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| sysfs_power_supply(&e.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let upower_available = match run_tool("upower", &["-d"]) {
+        Ok(output) => {
+            for ups in parse_upower_ups(&output) {
+                // USB HID UPSes also show up in sysfs; prefer the richer upower entry.
+                supplies.retain(|s| !(s.supply_type == "UPS" && ups.name.contains(&s.name)));
+                supplies.push(ups);
+            }
+            true
+        }
+        Err(_) => false,
+    };
+
+    supplies.sort_by(|a, b| a.supply_type.cmp(&b.supply_type).then_with(|| a.name.cmp(&b.name)));
+    Ok(json!({"upower_available": upower_available, "supplies": supplies}))
+}
+
+#[cfg(target_os = "macos")]
+fn parse_pmset_ps(output: &str) -> Vec<PowerSupply> {
+    let mut supplies = Vec::new();
+    let mut lines = output.lines();
+
+    if let Some(first) = lines.next() {
+        if let Some(source) = first.split('\'').nth(1) {
+            supplies.push(PowerSupply {
+                name: source.to_string(),
+                supply_type: if source == "UPS Power" { "UPS".to_string() } else { "Mains".to_string() },
+                model: None,
+                manufacturer: None,
+                percentage: None,
+                online: Some(true),
+                charging: None,
+                status: None,
+                watts: None,
+                source: "pmset".to_string(),
+            });
+        }
+    }
+
+    for line in lines {
+        let Some(rest) = line.trim().strip_prefix('-') else { continue };
+        if rest.starts_with("InternalBattery") {
+            continue;
+        }
+        let (name, details) = rest.split_once('\t').unwrap_or((rest, ""));
+        let name = name.split(" (id=").next().unwrap_or(name).trim().to_string();
+        let fields: Vec<&str> = details.split(';').map(|f| f.trim()).collect();
+        let status = fields.get(1).map(|s| s.to_string());
+        supplies.push(PowerSupply {
+            name,
+            supply_type: "UPS".to_string(),
+            model: None,
+            manufacturer: None,
+            percentage: fields.first().and_then(|p| p.trim_end_matches('%').parse().ok()),
+            online: None,
+            charging: status.as_deref().map(|s| s == "charging"),
+            status,
+            watts: None,
+            source: "pmset".to_string(),
+        });
+    }
+
+    supplies
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn list_power_supplies() -> Result<serde_json::Value, String> {
+    let supplies = run_tool("pmset", &["-g", "ps"]).ok()
+        .map(|o| parse_pmset_ps(&o))
+        .unwrap_or_default();
+    Ok(json!({"upower_available": false, "supplies": supplies}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_laptop_battery() {
+        assert!(is_laptop_battery("Battery", None));
+        assert!(is_laptop_battery("Battery", Some("System")));
+        assert!(!is_laptop_battery("Battery", Some("Device")), "peripheral batteries should be kept");
+        assert!(!is_laptop_battery("Mains", None));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_upower_ups() {
+        let sample = "Device: /org/freedesktop/UPower/devices/line_power_AC\n\
+                      \x20 native-path:          AC\n\
+                      \x20 line-power\n\
+                      \x20   online:             yes\n\
+                      \n\
+                      Device: /org/freedesktop/UPower/devices/ups_hiddev0\n\
+                      \x20 native-path:          /sys/devices/pci0000:00/usb1/1-2/hiddev0\n\
+                      \x20 vendor:               CPS\n\
+                      \x20 model:                CP1500PFCLCD\n\
+                      \x20 power supply:         yes\n\
+                      \x20 ups\n\
+                      \x20   present:            yes\n\
+                      \x20   state:              fully-charged\n\
+                      \x20   percentage:         100%\n\
+                      \n\
+                      Daemon:\n\
+                      \x20 daemon-version:  1.90.2\n";
+        let ups = parse_upower_ups(sample);
+        assert_eq!(ups.len(), 1, "only UPS devices should be returned");
+        assert_eq!(ups[0].name, "ups_hiddev0");
+        assert_eq!(ups[0].model.as_deref(), Some("CP1500PFCLCD"));
+        assert_eq!(ups[0].manufacturer.as_deref(), Some("CPS"));
+        assert_eq!(ups[0].percentage, Some(100.0));
+        assert_eq!(ups[0].online, Some(true));
+        assert_eq!(ups[0].charging, Some(false));
+        assert_eq!(ups[0].status.as_deref(), Some("fully-charged"));
+    }
+
+    #[test]
+    fn test_list_power_supplies_structure() {
+        let result = list_power_supplies();
+        assert!(result.is_ok(), "list_power_supplies failed: {:?}", result.err());
+        let data = result.unwrap();
+        assert!(data["supplies"].as_array().is_some(), "supplies should be an array");
+        assert!(data["upower_available"].as_bool().is_some());
+    }
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrinterInfo {
+    pub name: String,
+    pub description: String,
+    pub state: String,
+    pub is_default: bool,
+    pub jobs_queued: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintJob {
+    pub id: String,
+    pub printer: String,
+    pub user: String,
+    pub size: u64,
+    pub submitted: String,
+}
+
+fn run_lpstat(args: &[&str]) -> Option<String> {
+    let output = Command::new("lpstat")
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Where cupsd listens locally; socket-activated installs only have the socket
+// until the first client connects.
+const CUPS_SOCKETS: &[&str] = &["/run/cups/cups.sock", "/var/run/cups/cups.sock", "/private/var/run/cupsd"];
+
+fn scheduler_reported_running(stdout: &str) -> bool {
+    stdout.lines().any(|line| line.trim() == "scheduler is running")
+}
+
+// lpstat also fails with no printers configured, so whether CUPS is there at
+// all has to be asked separately.
+fn cups_scheduler_running() -> bool {
+    if let Some(stdout) = run_lpstat(&["-r"]) {
+        if scheduler_reported_running(&stdout) {
+            return true;
+        }
+    }
+    CUPS_SOCKETS.iter().any(|path| Path::new(path).exists())
+}
+
+fn parse_lpstat_printers(stdout: &str) -> Vec<PrinterInfo> {
+    let mut printers: Vec<PrinterInfo> = Vec::new();
+    let mut default_name: Option<String> = None;
+
+    for line in stdout.lines() {
+        if let Some(rest) = line.strip_prefix("printer ") {
+            let mut parts = rest.splitn(2, ' ');
+            let name = parts.next().unwrap_or("").to_string();
+            let status = parts.next().unwrap_or("");
+            let state = if status.starts_with("disabled") {
+                "disabled"
+            } else if status.contains("now printing") {
+                "printing"
+            } else if status.starts_with("is idle") {
+                "idle"
+            } else {
+                "unknown"
+            };
+            printers.push(PrinterInfo {
+                name: name.clone(),
+                description: name,
+                state: state.to_string(),
+                is_default: false,
+                jobs_queued: 0,
+            });
+        } else if let Some(desc) = line.trim().strip_prefix("Description:") {
+            if let Some(last) = printers.last_mut() {
+                let desc = desc.trim();
+                if !desc.is_empty() {
+                    last.description = desc.to_string();
+                }
+            }
+        } else if let Some(name) = line.strip_prefix("system default destination:") {
+            default_name = Some(name.trim().to_string());
+        }
+    }
+
+    if let Some(default_name) = default_name {
+        for printer in printers.iter_mut() {
+            printer.is_default = printer.name == default_name;
+        }
+    }
+
+    printers
+}
+
+fn parse_lpstat_jobs(stdout: &str) -> Vec<PrintJob> {
+    let mut jobs = Vec::new();
+
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        let id = parts[0].to_string();
+        let printer = id
+            .rsplit_once('-')
+            .map(|(p, _)| p.to_string())
+            .unwrap_or_default();
+        jobs.push(PrintJob {
+            id,
+            printer,
+            user: parts[1].to_string(),
+            size: parts[2].parse().unwrap_or(0),
+            submitted: parts[3..].join(" "),
+        });
+    }
+
+    jobs
+}
+
+#[tauri::command]
+pub fn list_printers() -> Result<serde_json::Value, String> {
+    if !cups_scheduler_running() {
+        return Ok(json!({"cups_available": false, "printers": []}));
+    }
+    let Some(stdout) = run_lpstat(&["-l", "-p", "-d"]) else {
+        return Ok(json!({"cups_available": true, "printers": []}));
+    };
+
+    let mut printers = parse_lpstat_printers(&stdout);
+
+    let jobs = run_lpstat(&["-o"]).map(|o| parse_lpstat_jobs(&o)).unwrap_or_default();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for job in &jobs {
+        *counts.entry(job.printer.as_str()).or_insert(0) += 1;
+    }
+    for printer in printers.iter_mut() {
+        printer.jobs_queued = counts.get(printer.name.as_str()).copied().unwrap_or(0);
+    }
+
+    Ok(json!({"cups_available": true, "printers": printers}))
+}
+
+#[tauri::command]
+pub fn list_print_jobs() -> Result<serde_json::Value, String> {
+    if !cups_scheduler_running() {
+        return Ok(json!({"cups_available": false, "jobs": []}));
+    }
+    let Some(stdout) = run_lpstat(&["-o"]) else {
+        return Ok(json!({"cups_available": true, "jobs": []}));
+    };
+    Ok(json!({"cups_available": true, "jobs": parse_lpstat_jobs(&stdout)}))
+}
+
+#[tauri::command]
+pub fn cancel_print_job(id: String) -> Result<serde_json::Value, String> {
+    let id = id.trim();
+    if id.is_empty() || id.starts_with('-') {
+        return Err("Invalid print job ID".to_string());
+    }
+
+    let output = Command::new("cancel")
+        .arg(id)
+        .output()
+        .map_err(|e| format!("Failed to run cancel: {}", e))?;
+
+    if output.status.success() {
+        Ok(json!({"success": true}))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Failed to cancel print job {}: {}", id, stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lpstat_printers() {
+        let sample = "printer HP_LaserJet is idle.  enabled since Mon 01 Jan 2024 10:00:00 AM UTC\n\
+                      \tDescription: HP LaserJet Pro\n\
+                      \tLocation: Office\n\
+                      printer Label now printing Label-7.  enabled since Mon 01 Jan 2024\n\
+                      printer Old disabled since Mon 01 Jan 2024 -\n\
+                      \treason unknown\n\
+                      system default destination: Label";
+        let printers = parse_lpstat_printers(sample);
+        assert_eq!(printers.len(), 3);
+        assert_eq!(printers[0].name, "HP_LaserJet");
+        assert_eq!(printers[0].description, "HP LaserJet Pro");
+        assert_eq!(printers[0].state, "idle");
+        assert!(!printers[0].is_default);
+        assert_eq!(printers[1].state, "printing");
+        assert!(printers[1].is_default, "default destination should be flagged");
+        assert_eq!(printers[2].state, "disabled");
+        assert_eq!(printers[2].description, "Old", "description should fall back to the name");
+    }
+
+    #[test]
+    fn test_parse_lpstat_jobs() {
+        let sample = "HP_LaserJet-12          alice          2048   Mon 01 Jan 2024 10:00:00 AM UTC\n\
+                      My-Printer-3            bob            512    Mon 01 Jan 2024 10:05:00 AM UTC";
+        let jobs = parse_lpstat_jobs(sample);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].id, "HP_LaserJet-12");
+        assert_eq!(jobs[0].printer, "HP_LaserJet");
+        assert_eq!(jobs[0].user, "alice");
+        assert_eq!(jobs[0].size, 2048);
+        assert_eq!(jobs[1].printer, "My-Printer", "printer names may contain dashes");
+    }
+
+    #[test]
+    fn test_scheduler_reported_running() {
+        assert!(scheduler_reported_running("scheduler is running\n"));
+        assert!(!scheduler_reported_running("scheduler is not running\n"));
+        assert!(!scheduler_reported_running(""));
+    }
+
+    #[test]
+    fn test_list_printers_structure() {
+        let result = list_printers();
+        assert!(result.is_ok(), "list_printers failed: {:?}", result.err());
+        let data = result.unwrap();
+        assert!(data["cups_available"].as_bool().is_some(), "cups_available should be a bool");
+        assert!(data["printers"].as_array().is_some(), "printers should be an array");
+    }
+
+    #[test]
+    fn test_cancel_print_job_rejects_options() {
+        assert!(cancel_print_job("-a".to_string()).is_err());
+        assert!(cancel_print_job("  ".to_string()).is_err());
+    }
+}
@@ -2,8 +2,8 @@ use serde::Serialize;
 use serde_json::json;
 use std::collections::HashMap;
 use std::fs;
-use std::process::Command;
-use sysinfo::{System, RefreshKind, ProcessRefreshKind};
+use super::error::GantryError;
+use sysinfo::{System, RefreshKind, ProcessRefreshKind, Users};
 
 #[cfg(target_os = "linux")]
 fn is_thread_group_leader(pid: u32) -> bool {
@@ -42,6 +42,30 @@ fn get_process_private_mem(_pid: u32) -> Option<u64> {
     None
 }
 
+// The command name may contain spaces and parentheses, so fields are counted from the last `)`.
+#[cfg(target_os = "linux")]
+fn parse_stat_cpu_ticks(stat: &str) -> Option<u64> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(target_os = "linux")]
+fn get_process_cpu_time(pid: u32) -> Option<f64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let ticks_per_second = match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        n if n > 0 => n as f64,
+        _ => 100.0,
+    };
+    parse_stat_cpu_ticks(&stat).map(|ticks| ticks as f64 / ticks_per_second)
+}
+
+#[cfg(target_os = "macos")]
+fn get_process_cpu_time(_pid: u32) -> Option<f64> {
+    None
+}
+
 #[derive(Serialize)]
 struct ProcessEntry {
     pid: u32,
@@ -51,6 +75,17 @@ struct ProcessEntry {
     cpu: f64,
     memory: u64,
     status: String,
+    uid: Option<u32>,
+    cpu_time_seconds: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct UserUsage {
+    uid: Option<u32>,
+    user: String,
+    cpu: f64,
+    memory: u64,
+    count: usize,
 }
 
 #[derive(Serialize)]
@@ -58,6 +93,7 @@ struct ProcessGroup {
     name: String,
     icon: String,
     total_cpu: f64,
+    total_cpu_time_seconds: f64,
     total_memory: u64,
     count: usize,
     main_pid: u32,
@@ -111,8 +147,7 @@ fn detect_app_name(name: &str, exe: &str) -> (String, String) {
     (name.to_string(), "default".to_string())
 }
 
-#[tauri::command]
-pub fn list_processes() -> Result<serde_json::Value, String> {
+fn process_snapshot() -> Vec<ProcessEntry> {
     let sys = System::new_with_specifics(
         RefreshKind::new().with_processes(ProcessRefreshKind::everything())
     );
@@ -141,8 +176,46 @@ pub fn list_processes() -> Result<serde_json::Value, String> {
             cpu: cpu_value,
             memory,
             status: format!("{:?}", process.status()),
+            uid: process.user_id().map(|uid| **uid),
+            cpu_time_seconds: get_process_cpu_time(pid_u32),
+        });
+    }
+    entries
+}
+
+pub(crate) fn top_processes(limit: usize) -> serde_json::Value {
+    let mut entries = process_snapshot();
+    entries.sort_by(|a, b| b.memory.cmp(&a.memory));
+    entries.truncate(limit);
+    json!(entries)
+}
+
+fn usage_by_user(entries: &[ProcessEntry], name_of: impl Fn(u32) -> Option<String>) -> Vec<UserUsage> {
+    let mut totals: HashMap<Option<u32>, UserUsage> = HashMap::new();
+    for entry in entries {
+        let usage = totals.entry(entry.uid).or_insert_with(|| UserUsage {
+            uid: entry.uid,
+            user: match entry.uid {
+                Some(uid) => name_of(uid).unwrap_or_else(|| uid.to_string()),
+                None => "unknown".to_string(),
+            },
+            cpu: 0.0,
+            memory: 0,
+            count: 0,
         });
+        usage.cpu += entry.cpu;
+        usage.memory += entry.memory;
+        usage.count += 1;
     }
+    let mut usage: Vec<UserUsage> = totals.into_values().collect();
+    usage.sort_by(|a, b| b.memory.cmp(&a.memory).then_with(|| a.user.cmp(&b.user)));
+    usage
+}
+
+#[tauri::command]
+pub fn list_processes(if_changed_since: Option<u64>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("processes")?;
+    let entries = process_snapshot();
 
     let mut groups: HashMap<String, (String, Vec<ProcessEntry>)> = HashMap::new();
     for entry in entries {
@@ -156,6 +229,7 @@ pub fn list_processes() -> Result<serde_json::Value, String> {
         .map(|(name, (icon, mut procs))| {
             procs.sort_by(|a, b| b.memory.cmp(&a.memory));
             let total_cpu: f64 = procs.iter().map(|p| p.cpu).sum();
+            let total_cpu_time_seconds: f64 = procs.iter().filter_map(|p| p.cpu_time_seconds).sum();
             let total_memory: u64 = procs.iter().map(|p| p.memory).sum();
             let count = procs.len();
             let main_pid = procs[0].pid;
@@ -164,6 +238,7 @@ pub fn list_processes() -> Result<serde_json::Value, String> {
                 name,
                 icon,
                 total_cpu,
+                total_cpu_time_seconds,
                 total_memory,
                 count,
                 main_pid,
@@ -174,16 +249,201 @@ pub fn list_processes() -> Result<serde_json::Value, String> {
 
     result.sort_by(|a, b| b.total_memory.cmp(&a.total_memory));
 
-    Ok(serde_json::to_value(result).unwrap_or(json!([])))
+    let groups = serde_json::to_value(result).unwrap_or(json!([]));
+    Ok(super::changes::respond(&super::changes::PROCESSES, groups, if_changed_since))
+}
+
+#[tauri::command]
+pub fn get_usage_by_user() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("processes")?;
+    let entries = process_snapshot();
+    let users = Users::new_with_refreshed_list();
+    let usage = usage_by_user(&entries, |uid| {
+        users.iter().find(|u| **u.id() == uid).map(|u| u.name().to_string())
+    });
+    Ok(serde_json::to_value(usage).unwrap_or(json!([])))
+}
+
+#[cfg(target_os = "linux")]
+const SECRET_ENV_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "PASSWD", "CREDENTIAL", "AUTH", "COOKIE"];
+
+#[cfg(target_os = "linux")]
+fn parse_environ(environ: &[u8], redact: bool) -> serde_json::Map<String, serde_json::Value> {
+    environ
+        .split(|b| *b == 0)
+        .filter_map(|pair| {
+            let pair = String::from_utf8_lossy(pair);
+            let (name, value) = pair.split_once('=')?;
+            let secret = redact && SECRET_ENV_MARKERS.iter().any(|m| name.to_uppercase().contains(m));
+            Some((name.to_string(), json!(if secret { "[redacted]" } else { value })))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn status_field<'a>(status: &'a str, key: &str) -> Option<&'a str> {
+    status.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix(':')).map(str::trim)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_stat_start_ticks(stat: &str) -> Option<u64> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    fields.get(19)?.parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn process_detail(pid: u32, redact_env: bool) -> Result<serde_json::Value, GantryError> {
+    let dir = std::path::PathBuf::from(format!("/proc/{}", pid));
+    let status = fs::read_to_string(dir.join("status"))
+        .map_err(|_| GantryError::NotFound(format!("No process with PID {}", pid)))?;
+    let field = |key: &str| status_field(&status, key);
+
+    let cmdline = fs::read(dir.join("cmdline")).ok().map(|raw| {
+        raw.split(|b| *b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect::<Vec<_>>()
+    });
+    let link = |name: &str| fs::read_link(dir.join(name)).ok().map(|p| p.to_string_lossy().to_string());
+    let environment = fs::read(dir.join("environ")).ok().map(|raw| parse_environ(&raw, redact_env));
+    let open_fds = fs::read_dir(dir.join("fd")).ok().map(|d| d.count());
+
+    let ticks_per_second = match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        n if n > 0 => n as u64,
+        _ => 100,
+    };
+    let boot_time: Option<i64> = fs::read_to_string("/proc/stat")
+        .ok()
+        .and_then(|s| s.lines().find_map(|l| l.strip_prefix("btime ")?.trim().parse().ok()));
+    let start_time = fs::read_to_string(dir.join("stat"))
+        .ok()
+        .and_then(|stat| parse_stat_start_ticks(&stat))
+        .zip(boot_time)
+        .map(|(ticks, boot)| boot + (ticks / ticks_per_second) as i64);
+
+    Ok(json!({
+        "pid": pid,
+        "parent_pid": field("PPid").and_then(|v| v.parse::<u32>().ok()),
+        "name": field("Name"),
+        "state": field("State"),
+        "uid": field("Uid").and_then(|v| v.split_whitespace().next()?.parse::<u32>().ok()),
+        "threads": field("Threads").and_then(|v| v.parse::<u32>().ok()),
+        "cmdline": cmdline,
+        "exe": link("exe"),
+        "cwd": link("cwd"),
+        "environment": environment,
+        "start_time": start_time,
+        "open_fds": open_fds,
+    }))
+}
+
+#[cfg(target_os = "macos")]
+fn parse_lsof(output: &str) -> (Option<String>, usize) {
+    let mut cwd = None;
+    let mut fds = 0;
+    let mut current = "";
+    for line in output.lines() {
+        if let Some(fd) = line.strip_prefix('f') {
+            current = fd;
+            fds += usize::from(fd.parse::<u32>().is_ok());
+        } else if let Some(name) = line.strip_prefix('n') {
+            if current == "cwd" {
+                cwd = Some(name.to_string());
+            }
+        }
+    }
+    (cwd, fds)
+}
+
+#[cfg(target_os = "macos")]
+fn process_detail(pid: u32, _redact_env: bool) -> Result<serde_json::Value, GantryError> {
+    let ps = |format: &str| -> Option<String> {
+        let output = std::process::Command::new("ps").args(["-ww", "-o", format, "-p", &pid.to_string()]).output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !text.is_empty()).then_some(text)
+    };
+    let summary = ps("ppid=,uid=,state=").ok_or_else(|| GantryError::NotFound(format!("No process with PID {}", pid)))?;
+    let fields: Vec<&str> = summary.split_whitespace().collect();
+    let start_time = ps("lstart=")
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(&s, "%a %b %e %H:%M:%S %Y").ok())
+        .and_then(|t| t.and_local_timezone(chrono::Local).single())
+        .map(|t| t.timestamp());
+    let threads = std::process::Command::new("ps")
+        .args(["-M", "-p", &pid.to_string()])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().count().saturating_sub(1));
+    let lsof = std::process::Command::new("lsof")
+        .args(["-p", &pid.to_string(), "-Fn"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_lsof(&String::from_utf8_lossy(&o.stdout)));
+    let exe = ps("comm=");
+
+    Ok(json!({
+        "pid": pid,
+        "parent_pid": fields.first().and_then(|v| v.parse::<u32>().ok()),
+        "name": exe.as_deref().map(|e| e.rsplit('/').next().unwrap_or(e).to_string()),
+        "state": fields.get(2),
+        "uid": fields.get(1).and_then(|v| v.parse::<u32>().ok()),
+        "threads": threads,
+        "cmdline": ps("args=").map(|a| a.split_whitespace().map(str::to_string).collect::<Vec<_>>()),
+        "exe": exe,
+        "cwd": lsof.as_ref().and_then(|(cwd, _)| cwd.clone()),
+        "environment": null,
+        "start_time": start_time,
+        "open_fds": lsof.map(|(_, fds)| fds),
+    }))
+}
+
+#[tauri::command]
+pub async fn get_process_detail(pid: u32, redact_env: Option<bool>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("processes")?;
+    tauri::async_runtime::spawn_blocking(move || process_detail(pid, redact_env.unwrap_or(true)))
+        .await
+        .map_err(|e| GantryError::Failed(e.to_string()))?
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_detail_of_self() {
+        let pid = std::process::id();
+        let detail = process_detail(pid, true).unwrap();
+        assert_eq!(detail["pid"], pid);
+        assert_eq!(detail["uid"].as_u64(), Some(unsafe { libc::getuid() } as u64));
+        assert!(detail["threads"].as_u64().unwrap() >= 1);
+        assert_eq!(detail["cwd"], json!(std::env::current_dir().unwrap().to_string_lossy()));
+        assert!(!detail["cmdline"].as_array().unwrap().is_empty());
+        assert!(detail["environment"].is_object());
+        assert!(detail["open_fds"].as_u64().unwrap() >= 1);
+        let started = detail["start_time"].as_i64().unwrap();
+        let now = chrono::Utc::now().timestamp();
+        assert!(started <= now + 1 && started > now - 86_400, "started at {}, now {}", started, now);
+
+        assert_eq!(process_detail(u32::MAX, true).unwrap_err().code(), "not_found");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_environ_redacts_secrets() {
+        let raw = b"HOME=/home/ada\0GITHUB_TOKEN=ghp_x\0db_password=hunter2\0EMPTY=\0";
+        let env = parse_environ(raw, true);
+        assert_eq!(env["HOME"], "/home/ada");
+        assert_eq!(env["GITHUB_TOKEN"], "[redacted]");
+        assert_eq!(env["db_password"], "[redacted]");
+        assert_eq!(env["EMPTY"], "");
+        assert_eq!(parse_environ(raw, false)["GITHUB_TOKEN"], "ghp_x");
+    }
+
     #[test]
     fn test_list_processes_returns_data() {
-        let result = list_processes();
+        let result = list_processes(None);
         assert!(result.is_ok(), "list_processes failed: {:?}", result.err());
         let procs = result.unwrap();
         let arr = procs.as_array().expect("processes should be an array");
@@ -192,7 +452,7 @@ mod tests {
 
     #[test]
     fn test_list_processes_group_structure() {
-        let result = list_processes().unwrap();
+        let result = list_processes(None).unwrap();
         let arr = result.as_array().unwrap();
         for group in arr {
             assert!(group["name"].as_str().is_some(), "group should have a name");
@@ -207,7 +467,7 @@ mod tests {
 
     #[test]
     fn test_list_processes_entry_fields() {
-        let result = list_processes().unwrap();
+        let result = list_processes(None).unwrap();
         let arr = result.as_array().unwrap();
         for group in arr {
             for proc in group["processes"].as_array().unwrap() {
@@ -222,7 +482,7 @@ mod tests {
 
     #[test]
     fn test_list_processes_sorted_by_memory() {
-        let result = list_processes().unwrap();
+        let result = list_processes(None).unwrap();
         let arr = result.as_array().unwrap();
         let memories: Vec<u64> = arr.iter()
             .map(|g| g["total_memory"].as_u64().unwrap_or(0))
@@ -232,6 +492,45 @@ mod tests {
         assert_eq!(memories, sorted, "process groups should be sorted by memory descending");
     }
 
+    #[test]
+    fn test_usage_by_user_totals() {
+        let entry = |pid: u32, uid: Option<u32>, cpu: f64, memory: u64| ProcessEntry {
+            pid,
+            parent_pid: None,
+            name: format!("p{}", pid),
+            exe: String::new(),
+            cpu,
+            memory,
+            status: "Run".to_string(),
+            uid,
+            cpu_time_seconds: None,
+        };
+        let entries = vec![
+            entry(1, Some(0), 1.0, 100),
+            entry(2, Some(1000), 20.0, 300),
+            entry(3, Some(1000), 5.5, 200),
+            entry(4, Some(1001), 0.0, 50),
+            entry(5, None, 0.0, 10),
+        ];
+        let name_of = |uid: u32| match uid {
+            0 => Some("root".to_string()),
+            1000 => Some("alice".to_string()),
+            _ => None,
+        };
+        let usage = usage_by_user(&entries, name_of);
+        let users: Vec<&str> = usage.iter().map(|u| u.user.as_str()).collect();
+        assert_eq!(users, vec!["alice", "root", "1001", "unknown"]);
+        assert_eq!(usage[0], UserUsage { uid: Some(1000), user: "alice".into(), cpu: 25.5, memory: 500, count: 2 });
+    }
+
+    #[test]
+    fn test_get_usage_by_user_sorted_by_memory() {
+        let usage = get_usage_by_user().unwrap();
+        let memories: Vec<u64> = usage.as_array().unwrap().iter().map(|u| u["memory"].as_u64().unwrap()).collect();
+        assert!(!memories.is_empty());
+        assert!(memories.windows(2).all(|w| w[0] >= w[1]));
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_get_process_private_mem_pid1() {
@@ -241,6 +540,34 @@ mod tests {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cpu_time_from_stat() {
+        let stat = "4242 (my (odd) app) S 1 4242 4242 0 -1 4194560 1500 0 0 0 250 75 0 0 20 0 4 0 12345 0 0";
+        assert_eq!(parse_stat_cpu_ticks(stat), Some(325));
+        assert_eq!(parse_stat_cpu_ticks("4242 (cut short) S 1"), None);
+
+        let pid = std::process::id();
+        let before = get_process_cpu_time(pid).expect("our own stat is readable");
+        let mut spin = 0u64;
+        for i in 0..5_000_000u64 {
+            spin = spin.wrapping_add(std::hint::black_box(i));
+        }
+        std::hint::black_box(spin);
+        assert!(get_process_cpu_time(pid).unwrap() >= before, "cpu time never goes down");
+    }
+
+    #[test]
+    fn test_kill_errors_have_codes() {
+        assert_eq!(kill_process(i32::MAX as u32).unwrap_err().code(), "not_found");
+        assert_eq!(kill_process(u32::MAX).unwrap_err().code(), "not_found");
+        assert_eq!(kill_process(0).unwrap_err().code(), "not_found");
+        // pid 1 belongs to root; only run the EPERM case when we aren't.
+        if unsafe { libc::geteuid() } != 0 {
+            assert_eq!(kill_process(1).unwrap_err().code(), "permission_denied");
+        }
+    }
+
     #[test]
     fn test_is_thread_group_leader_self() {
         let pid = std::process::id();
@@ -248,36 +575,37 @@ mod tests {
     }
 }
 
-#[tauri::command]
-pub fn kill_process(pid: u32) -> Result<String, String> {
-    let output = Command::new("kill")
-        .arg("-9")
-        .arg(pid.to_string())
-        .output()
-        .map_err(|e| format!("Failed to kill process: {}", e))?;
-
-    if output.status.success() {
-        Ok(format!("Process {} terminated", pid))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to kill process {}: {}", pid, stderr))
+fn kill_pid(pid: u32) -> Result<(), GantryError> {
+    // 0 and negative pids address whole process groups, never one process.
+    let pid = i32::try_from(pid)
+        .ok()
+        .filter(|p| *p > 0)
+        .ok_or_else(|| GantryError::NotFound(format!("No process {}", pid)))?;
+    if unsafe { libc::kill(pid, libc::SIGKILL) } == 0 {
+        return Ok(());
     }
+    let e = std::io::Error::last_os_error();
+    Err(match e.raw_os_error() {
+        Some(libc::ESRCH) => GantryError::NotFound(format!("No process {}", pid)),
+        Some(libc::EPERM) => GantryError::PermissionDenied(format!("Not allowed to kill process {}", pid)),
+        _ => GantryError::from(e),
+    })
+}
+
+#[tauri::command]
+pub fn kill_process(pid: u32) -> Result<String, GantryError> {
+    kill_pid(pid)?;
+    Ok(format!("Process {} terminated", pid))
 }
 
 #[tauri::command]
-pub fn kill_process_group(pids: Vec<u32>) -> Result<String, String> {
+pub fn kill_process_group(pids: Vec<u32>) -> Result<String, GantryError> {
     let mut killed = 0;
     let mut errors = Vec::new();
 
     for pid in &pids {
-        let output = Command::new("kill")
-            .arg("-9")
-            .arg(pid.to_string())
-            .output();
-
-        match output {
-            Ok(o) if o.status.success() => killed += 1,
-            Ok(o) => errors.push(format!("PID {}: {}", pid, String::from_utf8_lossy(&o.stderr))),
+        match kill_pid(*pid) {
+            Ok(()) => killed += 1,
             Err(e) => errors.push(format!("PID {}: {}", pid, e)),
         }
     }
@@ -1,25 +1,124 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use tauri::Emitter;
+use super::error::GantryError;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+static SCRIPT_RUNS: OnceLock<Mutex<HashMap<String, Child>>> = OnceLock::new();
+static NEXT_RUN: AtomicU64 = AtomicU64::new(1);
+static CANCELLED_RUNS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+const MAX_CHAIN_STEPS: usize = 10;
+
+const CANCEL_GRACE_SECS: u64 = 3;
+
+const STDIN_MAX_BYTES: usize = 1024 * 1024;
+
+type EmitFn = Arc<dyn Fn(&str, serde_json::Value) + Send + Sync>;
+type NotifyFn = Arc<dyn Fn(&CompletionNotice) + Send + Sync>;
+
+const HISTORY_MAX_RUNS: usize = 20;
+const HISTORY_MAX_OUTPUT: usize = 16 * 1024;
+const HISTORY_MAX_FILE: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptKind {
+    #[default]
+    Text,
+    Password,
+    Select,
+    Boolean,
+    File,
+    Number,
+}
+
+// Command scripts get the value in place of `{variable}`; body scripts get it as `$n` and, when
+// `variable` is a valid identifier, in the environment.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ScriptPrompt {
     pub variable: String,
     pub label: String,
+    #[serde(default)]
+    pub kind: PromptKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    // Substituted as-is instead of shell-quoted.
+    #[serde(default)]
+    pub raw: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CustomScript {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub command: String,
     pub requires_sudo: bool,
     #[serde(default)]
     pub prompts: Vec<ScriptPrompt>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notify_on_completion: bool,
+    #[serde(default)]
+    pub is_favorite: bool,
+    #[serde(default)]
+    pub login_shell: bool,
+    #[serde(default)]
+    pub accepts_stdin: bool,
+    #[serde(default)]
+    pub skip_preflight: bool,
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ScriptRunRecord {
+    started_at: String,
+    duration_ms: u64,
+    success: bool,
+    exit_code: Option<i32>,
+    resolved_command: String,
+    stdout: String,
+    stderr: String,
+    #[serde(default)]
+    truncated: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    steps: Vec<ChainStepRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chain_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ChainStepRecord {
+    script_id: String,
+    name: String,
+    success: bool,
+    exit_code: Option<i32>,
+    duration_ms: u64,
+    resolved_command: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -27,14 +126,49 @@ struct ScriptsConfig {
     scripts: Vec<CustomScript>,
 }
 
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct BundledScript {
+    name: String,
+    #[serde(default)]
+    command: String,
+    #[serde(default)]
+    requires_sudo: bool,
+    #[serde(default)]
+    prompts: Vec<ScriptPrompt>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interpreter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptBundle {
+    gantry_scripts: u32,
+    scripts: Vec<BundledScript>,
+}
+
+// Tests get a directory of their own so they never touch the user's scripts.
+fn scripts_path(name: &str) -> PathBuf {
+    if cfg!(test) {
+        return std::env::temp_dir().join(format!("gantry-scripts-test-{}", std::process::id())).join(name);
+    }
+    super::appdata::config_path(name)
+}
+
 fn get_config_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".gantry").join("scripts.yaml")
+    scripts_path("scripts.yaml")
 }
 
 fn get_legacy_config_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".gantry").join("scripts.json")
+    scripts_path("scripts.json")
 }
 
 fn ensure_config_dir() -> Result<(), String> {
@@ -65,34 +199,103 @@ fn load_config() -> Result<ScriptsConfig, String> {
     Ok(ScriptsConfig::default())
 }
 
+// Held from load to save by everything that changes scripts.yaml, so two commands running at once
+// can't drop each other's changes.
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+fn config_lock() -> MutexGuard<'static, ()> {
+    CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Temp file and rename, so a crash mid-write leaves either the old or the new file.
 fn save_config(config: &ScriptsConfig) -> Result<(), String> {
     ensure_config_dir()?;
     let config_path = get_config_path();
     let content = serde_yaml::to_string(config).map_err(|e| e.to_string())?;
-    let mut file = fs::File::create(&config_path).map_err(|e| e.to_string())?;
-    file.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    let tmp_path = config_path.with_file_name(format!(".scripts.yaml.{}.tmp", uuid::Uuid::new_v4()));
+    let written = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| {
+            if config_path.exists() {
+                fs::copy(&config_path, config_path.with_extension("yaml.bak"))?;
+            }
+            fs::rename(&tmp_path, &config_path)
+        });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.to_string());
+    }
     Ok(())
 }
 
+pub(crate) fn scripts_snapshot() -> Result<serde_json::Value, String> {
+    Ok(json!(load_config()?))
+}
+
+fn parse_snapshot(snapshot: &serde_json::Value) -> Result<ScriptsConfig, String> {
+    let config: ScriptsConfig =
+        serde_json::from_value(snapshot.clone()).map_err(|e| format!("Invalid scripts: {}", e))?;
+    let mut ids = HashSet::new();
+    if config.scripts.iter().any(|s| s.id.is_empty() || !ids.insert(s.id.as_str())) {
+        return Err("Every script must have a unique id".to_string());
+    }
+    Ok(config)
+}
+
+pub(crate) fn validate_scripts(snapshot: &serde_json::Value) -> Result<usize, String> {
+    Ok(parse_snapshot(snapshot)?.scripts.len())
+}
+
+pub(crate) fn restore_scripts(snapshot: &serde_json::Value) -> Result<usize, String> {
+    let config = parse_snapshot(snapshot)?;
+    let _lock = config_lock();
+    save_config(&config)?;
+    Ok(config.scripts.len())
+}
+
+pub(crate) fn merge_scripts(snapshot: &serde_json::Value) -> Result<usize, String> {
+    let incoming = parse_snapshot(snapshot)?;
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let before = config.scripts.len();
+    for script in incoming.scripts {
+        if !config.scripts.iter().any(|s| s.id == script.id) {
+            config.scripts.push(script);
+        }
+    }
+    let added = config.scripts.len() - before;
+    if added > 0 {
+        save_config(&config)?;
+    }
+    Ok(added)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn run_quiet(id: String, args: Option<HashMap<String, String>>) -> Result<serde_json::Value, GantryError> {
+        run_script_with(id, args, None, Arc::new(|_: &CompletionNotice| {}))
+    }
+
     #[test]
     fn test_list_scripts_returns_array() {
-        let result = list_scripts();
+        let result = list_scripts(None, None);
         assert!(result.is_ok(), "list_scripts failed: {:?}", result.err());
         assert!(result.unwrap().is_array(), "scripts should be an array");
     }
 
     #[test]
     fn test_add_and_remove_script() {
-        let result = add_script("Test Script".to_string(), "echo hello".to_string(), false, None);
+        let result = add_script("Test Script".to_string(), "echo hello".to_string(), false, None, None, None, None, None);
         assert!(result.is_ok(), "add_script failed: {:?}", result.err());
         let script = result.unwrap();
         let id = script["id"].as_str().expect("script should have an id").to_string();
 
-        let scripts = list_scripts().unwrap();
+        let scripts = list_scripts(None, None).unwrap();
         let found = scripts.as_array().unwrap().iter().any(|s| s["id"] == id);
         assert!(found, "newly added script should appear in list");
 
@@ -100,12 +303,34 @@ mod tests {
         assert!(remove.is_ok(), "remove_script failed: {:?}", remove.err());
     }
 
+    #[test]
+    fn test_rapid_adds_get_unique_ids() {
+        let ids: Vec<String> = (0..100)
+            .map(|i| {
+                let script = add_script(format!("Rapid {}", i), "true".to_string(), false, None, None, None, None, None).unwrap();
+                script["id"].as_str().unwrap().to_string()
+            })
+            .collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), 100, "ids created in a tight loop must not collide");
+        assert!(ids.iter().all(|id| history_path(&history_dir(), id).is_ok()), "uuid ids are valid file names");
+
+        let config = load_config().expect("scripts.yaml should still parse");
+        let mut seen = HashSet::new();
+        assert!(config.scripts.iter().all(|s| seen.insert(s.id.clone())), "no duplicate ids on disk");
+        assert!(get_config_path().with_extension("yaml.bak").exists(), "the previous file is kept as a backup");
+
+        for id in ids {
+            let _ = remove_script(id);
+        }
+    }
+
     #[test]
     fn test_run_script_echo() {
-        let script = add_script("Run Test".to_string(), "echo gantry_test_output".to_string(), false, None).unwrap();
+        let script = add_script("Run Test".to_string(), "echo gantry_test_output".to_string(), false, None, None, None, None, None).unwrap();
         let id = script["id"].as_str().unwrap().to_string();
 
-        let run = run_script(id.clone(), None);
+        let run = run_quiet(id.clone(), None);
         assert!(run.is_ok(), "run_script failed: {:?}", run.err());
         let result = run.unwrap();
         assert_eq!(result["success"].as_bool(), Some(true));
@@ -120,10 +345,10 @@ mod tests {
 
     #[test]
     fn test_run_script_failure() {
-        let script = add_script("Failing Script".to_string(), "exit 1".to_string(), false, None).unwrap();
+        let script = add_script("Failing Script".to_string(), "exit 1".to_string(), false, None, None, None, None, None).unwrap();
         let id = script["id"].as_str().unwrap().to_string();
 
-        let run = run_script(id.clone(), None);
+        let run = run_quiet(id.clone(), None);
         assert!(run.is_ok(), "run_script should not error even on failure");
         let result = run.unwrap();
         assert_eq!(result["success"].as_bool(), Some(false), "script with exit 1 should not succeed");
@@ -139,9 +364,13 @@ mod tests {
             "echo {greeting} {name}".to_string(),
             false,
             Some(vec![
-                ScriptPrompt { variable: "greeting".to_string(), label: "Greeting".to_string() },
-                ScriptPrompt { variable: "name".to_string(), label: "Name".to_string() },
+                ScriptPrompt { variable: "greeting".to_string(), label: "Greeting".to_string(), ..Default::default() },
+                ScriptPrompt { variable: "name".to_string(), label: "Name".to_string(), ..Default::default() },
             ]),
+            None,
+            None,
+            None,
+            None,
         ).unwrap();
         let id = script["id"].as_str().unwrap().to_string();
 
@@ -149,7 +378,7 @@ mod tests {
         args.insert("greeting".to_string(), "hello".to_string());
         args.insert("name".to_string(), "world".to_string());
 
-        let run = run_script(id.clone(), Some(args));
+        let run = run_quiet(id.clone(), Some(args));
         assert!(run.is_ok());
         let result = run.unwrap();
         assert_eq!(result["success"].as_bool(), Some(true));
@@ -159,15 +388,126 @@ mod tests {
         let _ = remove_script(id);
     }
 
+    #[test]
+    fn test_legacy_prompts_load_as_text() {
+        let yaml = "scripts:\n- id: s1\n  name: Old\n  command: echo {who}\n  requires_sudo: false\n  prompts:\n  - variable: who\n    label: Who\n";
+        let config: ScriptsConfig = serde_yaml::from_str(yaml).unwrap();
+        let prompt = &config.scripts[0].prompts[0];
+        assert_eq!(prompt.kind, PromptKind::Text);
+        assert!(!prompt.required && prompt.options.is_empty() && prompt.default.is_none());
+    }
+
+    #[test]
+    fn test_validate_args_against_prompts() {
+        let prompt = |variable: &str, kind: PromptKind| ScriptPrompt {
+            variable: variable.to_string(),
+            label: variable.to_string(),
+            kind,
+            ..Default::default()
+        };
+        let prompts = vec![
+            ScriptPrompt { required: true, ..prompt("host", PromptKind::Text) },
+            ScriptPrompt { options: vec!["fast".into(), "slow".into()], default: Some("fast".into()), ..prompt("mode", PromptKind::Select) },
+            prompt("count", PromptKind::Number),
+            prompt("verbose", PromptKind::Boolean),
+        ];
+        let args = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        let values = validate_args(&prompts, Some(&args(&[("host", "example.org"), ("count", "3")]))).unwrap();
+        assert_eq!(values["mode"], "fast", "defaults fill in missing values");
+        assert_eq!(values["verbose"], "", "optional prompts substitute as empty");
+
+        assert!(validate_args(&prompts, None).unwrap_err().contains("host is required"));
+        assert!(validate_args(&prompts, Some(&args(&[("host", "h"), ("mode", "medium")]))).is_err());
+        assert!(validate_args(&prompts, Some(&args(&[("host", "h"), ("count", "three")]))).is_err());
+        assert!(validate_args(&prompts, Some(&args(&[("host", "h"), ("verbose", "yes")]))).is_err());
+    }
+
+    #[test]
+    fn test_run_script_masks_passwords() {
+        let script = add_script(
+            "Password Test".to_string(),
+            "echo {user} {secret}".to_string(),
+            false,
+            Some(vec![
+                ScriptPrompt { variable: "user".to_string(), label: "User".to_string(), ..Default::default() },
+                ScriptPrompt { variable: "secret".to_string(), label: "Secret".to_string(), kind: PromptKind::Password, ..Default::default() },
+            ]),
+            None,
+            None,
+            None,
+            None,
+        ).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+
+        let mut args = HashMap::new();
+        args.insert("user".to_string(), "alice".to_string());
+        args.insert("secret".to_string(), "hunter2".to_string());
+        let result = run_quiet(id.clone(), Some(args)).unwrap();
+        let _ = remove_script(id);
+
+        assert!(result["stdout"].as_str().unwrap_or("").contains("alice hunter2"), "the command gets the real value");
+        let echoed = result["resolved_command"].as_str().unwrap();
+        assert!(!echoed.contains("hunter2"), "password leaked into {}", echoed);
+        assert!(echoed.starts_with("echo alice "));
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("hello"), "hello");
+        assert_eq!(shell_quote("/tmp/a-b_c.txt"), "/tmp/a-b_c.txt");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(substitute("{a}{b}{", |k| (k == "a").then(|| "{b}".to_string())), "{b}{b}{");
+    }
+
+    #[test]
+    fn test_run_script_quotes_prompt_values() {
+        let prompts = vec![
+            ScriptPrompt { variable: "value".to_string(), label: "Value".to_string(), ..Default::default() },
+            ScriptPrompt { variable: "flags".to_string(), label: "Flags".to_string(), raw: true, ..Default::default() },
+        ];
+        let script = add_script("Quote Test".to_string(), "printf '%s|' {value} {flags}".to_string(), false, Some(prompts), None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+
+        let nasty = [
+            "; echo INJECTED",
+            "$(echo INJECTED)",
+            "`echo INJECTED`",
+            "it's \"quoted\"",
+            "line one\nline two",
+            "a && b || c > /dev/null",
+            "{flags}",
+        ];
+        let mut outputs = Vec::new();
+        for value in nasty {
+            let mut args = HashMap::new();
+            args.insert("value".to_string(), value.to_string());
+            args.insert("flags".to_string(), "one two".to_string());
+            outputs.push(run_quiet(id.clone(), Some(args)));
+        }
+        let _ = remove_script(id);
+
+        for (value, run) in nasty.iter().zip(outputs) {
+            let result = run.unwrap();
+            assert_eq!(result["success"].as_bool(), Some(true), "{:?}: {}", value, result);
+            // The raw prompt splits into words, the quoted one arrives whole.
+            assert_eq!(result["stdout"].as_str(), Some(format!("{}|one|two|", value).as_str()));
+        }
+    }
+
     #[test]
     fn test_update_script() {
-        let script = add_script("Original".to_string(), "echo original".to_string(), false, None).unwrap();
+        let script = add_script("Original".to_string(), "echo original".to_string(), false, None, None, None, None, None).unwrap();
         let id = script["id"].as_str().unwrap().to_string();
 
-        let update = update_script(id.clone(), "Updated".to_string(), "echo updated".to_string(), false, None);
+        let update = update_script(id.clone(), "Updated".to_string(), "echo updated".to_string(), false, None, None, None, None, None);
         assert!(update.is_ok(), "update_script failed: {:?}", update.err());
 
-        let scripts = list_scripts().unwrap();
+        let scripts = list_scripts(None, None).unwrap();
         let updated = scripts.as_array().unwrap().iter()
             .find(|s| s["id"] == id)
             .expect("updated script should still exist");
@@ -179,107 +519,2350 @@ mod tests {
 
     #[test]
     fn test_run_nonexistent_script() {
-        let result = run_script("nonexistent_id_xyz".to_string(), None);
-        assert!(result.is_err(), "running nonexistent script should return error");
+        let result = run_quiet("nonexistent_id_xyz".to_string(), None);
+        assert_eq!(result.unwrap_err().code(), "not_found", "running nonexistent script should return error");
     }
 
     #[test]
-    fn test_remove_nonexistent_script_is_ok() {
-        // retain() silently no-ops when the id isn't found
-        let result = remove_script("nonexistent_id_xyz".to_string());
-        assert!(result.is_ok(), "remove_script on nonexistent id should not error");
+    fn test_completion_notice_urgency_and_flags() {
+        let mut script = CustomScript { name: "Backup".into(), ..Default::default() };
+        let failed = ScriptRunRecord { success: false, exit_code: Some(3), duration_ms: 2_500, ..Default::default() };
+        assert!(completion_notice(&script, &failed, true).is_none(), "scripts opt in");
+
+        script.notify_on_completion = true;
+        assert!(completion_notice(&script, &failed, false).is_none(), "the global toggle wins");
+        let notice = completion_notice(&script, &failed, true).unwrap();
+        assert_eq!(notice.urgency, Urgency::Critical);
+        assert_eq!(notice.body, "Backup failed with exit code 3 after 2.5s");
+
+        let ok = ScriptRunRecord { success: true, exit_code: Some(0), duration_ms: 125_000, ..Default::default() };
+        let notice = completion_notice(&script, &ok, true).unwrap();
+        assert_eq!(notice.urgency, Urgency::Normal);
+        assert_eq!(notice.body, "Backup finished after 2m 5s");
     }
-}
 
-#[tauri::command]
-pub fn list_scripts() -> Result<serde_json::Value, String> {
-    let config = load_config()?;
-    Ok(json!(config.scripts))
-}
+    #[test]
+    fn test_run_script_reports_notification() {
+        let script = add_script("Notify Test".to_string(), "exit 3".to_string(), false, None, None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+        set_script_notify(id.clone(), true).unwrap();
 
-#[tauri::command]
-pub fn add_script(name: String, command: String, requires_sudo: bool, prompts: Option<Vec<ScriptPrompt>>) -> Result<serde_json::Value, String> {
-    let mut config = load_config()?;
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sink = sent.clone();
+        let notify: NotifyFn = Arc::new(move |notice: &CompletionNotice| sink.lock().unwrap().push(notice.clone()));
+        let result = run_script_with(id.clone(), None, None, notify).unwrap();
+        remove_script(id).unwrap();
 
-    let id = format!("script_{}", chrono::Utc::now().timestamp_millis());
-    let script = CustomScript {
-        id: id.clone(),
-        name,
-        command,
-        requires_sudo,
-        prompts: prompts.unwrap_or_default(),
-    };
+        let enabled = super::super::settings::notifications_enabled();
+        assert_eq!(result["notified"].as_bool(), Some(enabled));
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), usize::from(enabled));
+        assert!(sent.iter().all(|n| n.urgency == Urgency::Critical && n.body.starts_with("Notify Test failed")));
+    }
 
-    config.scripts.push(script.clone());
-    save_config(&config)?;
+    #[test]
+    fn test_favorites_follow_sort_order() {
+        let old: CustomScript = serde_yaml::from_str("id: old\nname: Old\ncommand: 'true'\nrequires_sudo: false\n").unwrap();
+        assert!(!old.is_favorite, "configs from before favorites load as not favorite");
 
-    Ok(json!(script))
-}
+        let ids: Vec<String> = ["Fav B", "Plain", "Fav A"]
+            .iter()
+            .map(|name| add_script(name.to_string(), "true".to_string(), false, None, None, None, None, None).unwrap()["id"].as_str().unwrap().to_string())
+            .collect();
+        set_script_favorite(ids[0].clone(), true).unwrap();
+        set_script_favorite(ids[2].clone(), true).unwrap();
+        reorder_scripts(vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]).unwrap();
 
-#[tauri::command]
-pub fn remove_script(id: String) -> Result<serde_json::Value, String> {
-    let mut config = load_config()?;
-    config.scripts.retain(|s| s.id != id);
-    save_config(&config)?;
-    Ok(json!({"success": true}))
-}
+        let favorites = list_favorite_scripts().unwrap();
+        let ours: Vec<&serde_json::Value> = favorites.as_array().unwrap().iter().filter(|f| ids.iter().any(|id| f["id"] == *id)).collect();
+        for id in ids {
+            let _ = remove_script(id);
+        }
+        let names: Vec<&str> = ours.iter().map(|f| f["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["Fav A", "Fav B"]);
+        assert_eq!(ours[0]["prompt_count"], 0);
+        assert_eq!(ours[0]["requires_sudo"], false);
+    }
 
-#[tauri::command]
-pub fn update_script(id: String, name: String, command: String, requires_sudo: bool, prompts: Option<Vec<ScriptPrompt>>) -> Result<serde_json::Value, String> {
-    let mut config = load_config()?;
+    #[test]
+    fn test_login_shell_replaces_only_shell_interpreters() {
+        let shell = user_login_shell();
+        let values = HashMap::new();
+        let command = CustomScript { command: "echo hi".into(), login_shell: true, ..Default::default() };
+        assert_eq!(plan_run(&command, &values, "/tmp/body").argv, vec![shell.clone(), "-lc".into(), "echo hi".into()]);
 
-    if let Some(script) = config.scripts.iter_mut().find(|s| s.id == id) {
-        script.name = name;
-        script.command = command;
-        script.requires_sudo = requires_sudo;
-        script.prompts = prompts.unwrap_or_default();
-        save_config(&config)?;
-        Ok(json!({"success": true}))
-    } else {
-        Err("Script not found".to_string())
+        let bash = CustomScript { body: Some("#!/usr/bin/env bash\necho hi".into()), login_shell: true, ..Default::default() };
+        let plan = plan_run(&bash, &values, "/tmp/body");
+        assert_eq!(plan.argv, vec![shell.clone(), "-l".into(), "/tmp/body".into()]);
+        assert_eq!(plan.interpreter, shell);
+
+        let python = CustomScript { body: Some("print(1)".into()), interpreter: Some("python3".into()), login_shell: true, ..Default::default() };
+        assert!(!uses_login_shell(&python), "login_shell is ignored for python");
+        assert_eq!(plan_run(&python, &values, "/tmp/body").argv[0], "python3");
+
+        assert_eq!(interpreter_program("/usr/bin/env zsh -e"), "zsh");
+        assert_eq!(interpreter_program("/bin/bash"), "bash");
     }
-}
 
-#[tauri::command]
-pub fn run_script(id: String, args: Option<HashMap<String, String>>) -> Result<serde_json::Value, String> {
-    let config = load_config()?;
-    let script = config.scripts.iter().find(|s| s.id == id)
-        .ok_or_else(|| "Script not found".to_string())?;
+    #[test]
+    fn test_command_segments_and_programs() {
+        let segments = command_segments("FOO=1 restic backup && echo 'a && b' | grep -q x; cd /tmp || exit 1 &\nls");
+        assert_eq!(segments, vec!["FOO=1 restic backup", "echo 'a && b'", "grep -q x", "cd /tmp", "exit 1", "ls"]);
+        let programs: Vec<&str> = segments.iter().filter_map(|s| segment_program(s)).collect();
+        assert_eq!(programs, vec!["restic", "grep", "ls"], "builtins are skipped");
+        assert_eq!(segment_program("$EDITOR file"), None);
+        assert_eq!(segment_program("(cd x"), None);
+    }
+
+    #[test]
+    fn test_preflight_reports_missing_programs() {
+        let values = HashMap::new();
+        let mut script = CustomScript { command: "gantry-no-such-tool --x && ls /tmp | wc -l".into(), ..Default::default() };
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(preflight_missing(&script, &values, &path), vec!["gantry-no-such-tool"]);
+        script.skip_preflight = true;
+        assert!(preflight_missing(&script, &values, &path).is_empty());
 
-    let mut command = script.command.clone();
-    if let Some(ref args) = args {
-        for (key, value) in args {
-            command = command.replace(&format!("{{{}}}", key), value);
+        let body = CustomScript { body: Some("print(1)".into()), interpreter: Some("gantry-no-such-python".into()), ..Default::default() };
+        assert_eq!(preflight_missing(&body, &values, &path), vec!["gantry-no-such-python"]);
+
+        let added = add_script("Preflight Test".to_string(), "gantry-no-such-tool".to_string(), false, None, None, None, None, None).unwrap();
+        let id = added["id"].as_str().unwrap().to_string();
+        let result = run_quiet(id.clone(), None).unwrap();
+        let history = get_script_history(id.clone(), None).unwrap();
+        remove_script(id).unwrap();
+        assert_eq!(result["success"].as_bool(), Some(false));
+        assert_eq!(result["preflight"]["missing"], json!(["gantry-no-such-tool"]));
+        assert!(result["preflight"]["hint"].as_str().unwrap().contains("login shell"));
+        assert_eq!(history.as_array().map(|h| h.len()), Some(0), "nothing ran, so nothing is recorded");
+    }
+
+    #[test]
+    fn test_run_script_reports_effective_path() {
+        let script = add_script("Login Shell Test".to_string(), "echo ok".to_string(), false, None, None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+        set_script_login_shell(id.clone(), true).unwrap();
+        let result = run_quiet(id.clone(), None).unwrap();
+        remove_script(id).unwrap();
+
+        assert_eq!(result["success"].as_bool(), Some(true), "stderr: {}", result["stderr"]);
+        assert!(result["stdout"].as_str().unwrap().ends_with("ok\n"));
+        assert!(result["effective_path"].as_str().is_some_and(|p| !p.is_empty()), "PATH should be reported");
+    }
+
+    #[test]
+    fn test_read_capped_line() {
+        let mut reader = BufReader::with_capacity(4, "short\nmuch longer line\n\nlast".as_bytes());
+        let mut line = Vec::new();
+        let mut lines = Vec::new();
+        while let Some(cut) = read_capped_line(&mut reader, &mut line, 8).unwrap() {
+            lines.push((String::from_utf8_lossy(&line).to_string(), cut));
         }
+        assert_eq!(
+            lines,
+            vec![
+                ("short".to_string(), false),
+                ("much lon".to_string(), true),
+                (String::new(), false),
+                ("last".to_string(), false),
+            ]
+        );
     }
 
-    let output = if script.requires_sudo {
-        Command::new("pkexec")
-            .arg("sh")
-            .arg("-c")
-            .arg(&command)
-            .output()
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&command)
-            .output()
-    };
+    #[test]
+    fn test_run_captured_spools_full_output() {
+        let spool = script_output_dir().join(format!("{}.log", new_run_id()));
+        let out = run_captured(&mut sh("head -c 10000 /dev/zero | tr '\\0' x; echo err >&2"), None, (1024, 1024), &spool).unwrap();
+        let spooled = fs::read_to_string(&spool).unwrap();
+        let _ = fs::remove_file(&spool);
 
-    match output {
-        Ok(result) => Ok(json!({
-            "success": result.status.success(),
-            "stdout": String::from_utf8_lossy(&result.stdout).to_string(),
-            "stderr": String::from_utf8_lossy(&result.stderr).to_string(),
-            "exit_code": result.status.code(),
-            "resolved_command": command,
-        })),
-        Err(e) => Ok(json!({
-            "success": false,
-            "stdout": "",
-            "stderr": e.to_string(),
-            "exit_code": -1,
-            "resolved_command": command,
-        })),
+        assert!(out.status.success());
+        assert!(out.truncated);
+        assert_eq!(out.stdout.len(), 1024, "stdout is cut at the limit");
+        assert_eq!(out.stderr, "err\n", "stderr has its own limit");
+        assert_eq!(spooled.matches('x').count(), 10000, "the spool file has everything");
+        assert!(spooled.contains("err"));
+
+        let small = add_script("Small Output".to_string(), "echo hi".to_string(), false, None, None, None, None, None).unwrap();
+        let id = small["id"].as_str().unwrap().to_string();
+        let result = run_quiet(id.clone(), None).unwrap();
+        remove_script(id).unwrap();
+        assert_eq!(result["truncated"].as_bool(), Some(false));
+        assert!(result["output_path"].is_null(), "nothing is kept when the output fits");
+    }
+
+    fn sh(command: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+
+    fn collect_run(cmd: Command) -> (String, Vec<(String, serde_json::Value)>) {
+        let events: Arc<Mutex<Vec<(String, serde_json::Value)>>> = Arc::default();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let sink = events.clone();
+        let emit: EmitFn = Arc::new(move |event: &str, payload: serde_json::Value| {
+            sink.lock().unwrap().push((event.to_string(), payload));
+            if event == "script-finished" {
+                let _ = done_tx.send(());
+            }
+        });
+        let run_id = new_run_id();
+        start_script_run(&run_id, cmd, None, emit).unwrap();
+        done_rx.recv_timeout(std::time::Duration::from_secs(10)).expect("run should finish");
+        let collected = events.lock().unwrap().clone();
+        (run_id, collected)
+    }
+
+    #[test]
+    fn test_start_script_run_streams_lines() {
+        let (run_id, events) = collect_run(sh("echo one; echo two >&2; echo three; exit 3"));
+        let lines: Vec<(&str, &str)> = events
+            .iter()
+            .filter(|(event, _)| event == "script-output")
+            .map(|(_, p)| (p["stream"].as_str().unwrap(), p["line"].as_str().unwrap()))
+            .collect();
+        assert!(lines.contains(&("stdout", "one")) && lines.contains(&("stdout", "three")));
+        assert!(lines.contains(&("stderr", "two")));
+
+        let (event, finished) = events.last().unwrap();
+        assert_eq!(event, "script-finished", "finished must come after all output");
+        assert_eq!(finished["run_id"].as_str(), Some(run_id.as_str()));
+        assert_eq!(finished["exit_code"].as_i64(), Some(3));
+        assert!(!script_runs().lock().unwrap().contains_key(&run_id), "finished runs are forgotten");
+    }
+
+    #[test]
+    fn test_cancel_script_run() {
+        let events: Arc<Mutex<Vec<serde_json::Value>>> = Arc::default();
+        let sink = events.clone();
+        let emit: EmitFn = Arc::new(move |event: &str, payload: serde_json::Value| {
+            if event == "script-finished" {
+                sink.lock().unwrap().push(payload);
+            }
+        });
+        let started = std::time::Instant::now();
+        // The trap keeps sh alive past SIGTERM so the SIGKILL fallback is exercised too.
+        let run_id = new_run_id();
+        start_script_run(&run_id, sh("trap '' TERM; sleep 30"), None, emit).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert!(cancel_script_run(run_id.clone()).is_ok());
+
+        while events.lock().unwrap().is_empty() {
+            assert!(started.elapsed().as_secs() < 10, "cancelled run should finish");
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        let finished = events.lock().unwrap()[0].clone();
+        assert_eq!(finished["success"].as_bool(), Some(false));
+        assert!(finished["signal"].as_i64().is_some(), "run should end by signal: {}", finished);
+        assert!(cancel_script_run(run_id).is_err(), "finished runs can't be cancelled");
+    }
+
+    #[test]
+    fn test_script_history_caps() {
+        let dir = std::env::temp_dir().join(format!("gantry_history_test_{}", std::process::id()));
+        let path = history_path(&dir, "script_1").unwrap();
+        assert!(history_path(&dir, "../etc/passwd").is_err());
+
+        for i in 0..HISTORY_MAX_RUNS + 5 {
+            let record = ScriptRunRecord {
+                started_at: i.to_string(),
+                stdout: format!("token=s3cret run {}\n", i),
+                ..Default::default()
+            };
+            append_history(&path, record, &["s3cret".to_string()]).unwrap();
+        }
+        let history = load_history(&path);
+        assert_eq!(history.len(), HISTORY_MAX_RUNS);
+        assert_eq!(history[0].started_at, (HISTORY_MAX_RUNS + 4).to_string(), "newest run comes first");
+        assert_eq!(history[0].stdout, format!("token=******** run {}\n", HISTORY_MAX_RUNS + 4));
+
+        let big = ScriptRunRecord { stdout: "x\n".repeat(HISTORY_MAX_OUTPUT), ..Default::default() };
+        append_history(&path, big, &[]).unwrap();
+        let history = load_history(&path);
+        assert!(history[0].truncated && history[0].stdout.len() <= HISTORY_MAX_OUTPUT);
+        assert!(history[0].stdout.starts_with("x\n"), "truncation keeps whole lines");
+        assert!(fs::metadata(&path).unwrap().len() as usize <= HISTORY_MAX_FILE);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_run_script_records_history() {
+        let script = add_script("History Test".to_string(), "echo recorded; exit 4".to_string(), false, None, None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+        let _ = run_quiet(id.clone(), None).unwrap();
+
+        let history = get_script_history(id.clone(), Some(5)).unwrap();
+        assert_eq!(history[0]["exit_code"].as_i64(), Some(4));
+        assert_eq!(history[0]["stdout"].as_str(), Some("recorded\n"));
+        let listed = list_scripts(None, None).unwrap();
+        let entry = listed.as_array().unwrap().iter().find(|s| s["id"] == id.as_str()).unwrap().clone();
+        assert_eq!(entry["last_exit_code"].as_i64(), Some(4));
+        assert!(entry["last_run_at"].is_string());
+
+        assert!(clear_script_history(id.clone()).is_ok());
+        assert_eq!(get_script_history(id.clone(), None).unwrap().as_array().map(|a| a.len()), Some(0));
+        let _ = remove_script(id);
+    }
+
+    #[test]
+    fn test_run_script_pipes_stdin_without_recording_it() {
+        let prompts = vec![ScriptPrompt { variable: "pass".into(), label: "Pass".into(), kind: PromptKind::Password, ..Default::default() }];
+        let script = add_script("Stdin Test".to_string(), "cat".to_string(), false, Some(prompts), None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+        let args = HashMap::from([("pass".to_string(), "hunter2".to_string())]);
+
+        let result = run_script_with(id.clone(), Some(args.clone()), Some("key=hunter2\n".into()), Arc::new(|_: &CompletionNotice| {})).unwrap();
+        assert_eq!(result["stdout"].as_str(), Some("key=hunter2\n"));
+        assert!(!result["resolved_command"].as_str().unwrap().contains("hunter2"));
+        let history = get_script_history(id.clone(), Some(1)).unwrap();
+        assert_eq!(history[0]["stdout"].as_str(), Some("key=********\n"), "password values fed through stdin are masked");
+        assert!(!history[0].to_string().contains("hunter2"));
+
+        let too_big = "x".repeat(STDIN_MAX_BYTES + 1);
+        assert!(run_script_with(id.clone(), Some(args), Some(too_big), Arc::new(|_: &CompletionNotice| {})).is_err());
+        let _ = remove_script(id);
+    }
+
+    #[test]
+    fn test_parse_bundle_rejects_malformed() {
+        let ok = "gantry_scripts: 1\nscripts:\n- name: Update\n  command: apt update\n  requires_sudo: true\n";
+        assert_eq!(parse_bundle(ok).unwrap().scripts[0].name, "Update");
+        assert!(parse_bundle("scripts: []\n").is_err(), "version marker is required");
+        assert!(parse_bundle("gantry_scripts: 99\nscripts: []\n").is_err());
+        assert!(parse_bundle("gantry_scripts: 1\nscripts:\n- name: ''\n  command: ls\n").is_err());
+        assert!(parse_bundle("gantry_scripts: 1\nscripts:\n- id: x\n  name: a\n  command: ls\n").is_err());
+        let dup = "gantry_scripts: 1\nscripts:\n- name: a\n  command: echo {x}\n  prompts:\n  - {variable: x, label: X}\n  - {variable: x, label: Y}\n";
+        assert!(parse_bundle(dup).is_err());
+    }
+
+    #[test]
+    fn test_merge_bundle_collisions() {
+        let script = |id: &str, name: &str, command: &str| CustomScript {
+            id: id.to_string(),
+            name: name.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        };
+        let bundled = |name: &str, command: &str| BundledScript {
+            name: name.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        };
+        let bundle = || ScriptBundle {
+            gantry_scripts: 1,
+            scripts: vec![bundled("Same", "ls"), bundled("Clash", "echo new"), bundled("Fresh", "df -h")],
+        };
+        let existing = ScriptsConfig { scripts: vec![script("script_1", "Same", "ls"), script("script_2", "Clash", "echo old")] };
+
+        let mut config = existing.clone();
+        let summary = merge_bundle(&mut config, bundle(), false);
+        assert_eq!(summary["skipped"], json!(["Same"]));
+        assert_eq!(summary["renamed"], json!([{"from": "Clash", "to": "Clash (2)"}]));
+        assert_eq!(summary["imported"], json!(["Clash (2)", "Fresh"]));
+        assert_eq!(config.scripts.len(), 4);
+        let ids: std::collections::HashSet<_> = config.scripts.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids.len(), 4, "imported scripts get fresh, distinct ids");
+
+        let mut config = existing.clone();
+        let summary = merge_bundle(&mut config, bundle(), true);
+        assert_eq!(summary["overwritten"], json!(["Same", "Clash"]));
+        let clash = config.scripts.iter().find(|s| s.name == "Clash").unwrap();
+        assert_eq!((clash.id.as_str(), clash.command.as_str()), ("script_2", "echo new"));
+    }
+
+    #[test]
+    fn test_export_scripts_omits_ids() {
+        let script = add_script("Export Test".to_string(), "uptime".to_string(), false, None, None, None, None, None).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+        let path = std::env::temp_dir().join(format!("gantry_export_test_{}.yaml", std::process::id()));
+        let result = export_scripts(Some(vec![id.clone()]), Some(path.to_string_lossy().to_string()));
+        let _ = remove_script(id.clone());
+
+        assert_eq!(result.unwrap()["count"].as_u64(), Some(1));
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(!content.contains(&id), "bundle must not carry machine-local ids");
+        assert_eq!(parse_bundle(&content).unwrap().scripts[0].command, "uptime");
+    }
+
+    #[test]
+    fn test_categories_tags_and_order() {
+        let yaml = "scripts:\n- id: a\n  name: A\n  command: 'true'\n  requires_sudo: false\n\
+                    - id: b\n  name: B\n  command: 'true'\n  requires_sudo: false\n";
+        let mut config: ScriptsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.scripts.iter().all(|s| s.category.is_none() && s.tags.is_empty() && s.sort_order == 0));
+        config.scripts.push(CustomScript {
+            id: "c".to_string(),
+            category: clean_category(Some(" Backups ".to_string())),
+            tags: clean_tags(vec!["nightly".into(), " ".into(), "nightly".into(), "disk".into()]),
+            sort_order: next_sort_order(&config),
+            ..Default::default()
+        });
+        assert_eq!(config.scripts[2].category.as_deref(), Some("Backups"));
+        assert_eq!(config.scripts[2].tags, vec!["nightly", "disk"]);
+
+        let ids = |list: Vec<&CustomScript>| list.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(filter_scripts(&config.scripts, None, None)), vec!["a", "b", "c"]);
+        assert_eq!(ids(filter_scripts(&config.scripts, Some("Backups"), None)), vec!["c"]);
+        assert_eq!(ids(filter_scripts(&config.scripts, None, Some("disk"))), vec!["c"]);
+        assert!(filter_scripts(&config.scripts, Some("Backups"), Some("other")).is_empty());
+
+        apply_order(&mut config, &["c".to_string(), "missing".to_string()]);
+        assert_eq!(ids(filter_scripts(&config.scripts, None, None)), vec!["c", "a", "b"]);
+        assert_eq!(config.scripts.iter().map(|s| s.sort_order).collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_run_script_body_from_temp_file() {
+        let body = "#!/bin/sh\ncat <<'EOF'\n{not a placeholder} $1\nEOF\necho \"arg=$1 env=$target\"\n";
+        let prompts = vec![ScriptPrompt { variable: "target".to_string(), label: "Target".to_string(), ..Default::default() }];
+        let script = add_script(
+            "Body Test".to_string(),
+            String::new(),
+            false,
+            Some(prompts),
+            None,
+            None,
+            Some(body.to_string()),
+            None,
+        ).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+
+        let mut args = HashMap::new();
+        args.insert("target".to_string(), "it's; $(x)".to_string());
+        let result = run_quiet(id.clone(), Some(args)).unwrap();
+        let _ = remove_script(id);
+
+        assert_eq!(result["success"].as_bool(), Some(true), "{}", result);
+        assert_eq!(
+            result["stdout"].as_str(),
+            Some("{not a placeholder} $1\narg=it's; $(x) env=it's; $(x)\n"),
+            "heredocs and braces run untouched, args arrive literally"
+        );
+        let shown = result["resolved_command"].as_str().unwrap();
+        let temp = shown.split_whitespace().next().unwrap();
+        assert!(temp.contains(".gantry/tmp/script-"), "{}", shown);
+        assert!(!std::path::Path::new(temp).exists(), "temp file is removed after the run");
     }
+
+    #[test]
+    fn test_prepare_run_uses_interpreter_and_masks() {
+        let script = CustomScript {
+            body: Some("print('hi')".to_string()),
+            interpreter: Some("python3 -u".to_string()),
+            prompts: vec![
+                ScriptPrompt { variable: "user".to_string(), ..Default::default() },
+                ScriptPrompt { variable: "pass".to_string(), kind: PromptKind::Password, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let values: HashMap<String, String> = [("user", "bob"), ("pass", "hunter2")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let prepared = prepare_run(&script, &values).unwrap();
+        let temp = prepared.temp.as_ref().unwrap().0.clone();
+        assert!(prepared.display.starts_with("python3 -u "));
+        assert!(prepared.display.ends_with(" bob ********"), "{}", prepared.display);
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&temp).unwrap().permissions().mode() & 0o777, 0o700);
+        drop(prepared);
+        assert!(!temp.exists());
+        assert!(is_shell_identifier("_user1") && !is_shell_identifier("1user") && !is_shell_identifier("my-var"));
+    }
+
+    #[test]
+    fn test_preview_script_matches_run_without_executing() {
+        let marker = std::env::temp_dir().join(format!("gantry_preview_{}", std::process::id()));
+        let prompts = vec![
+            ScriptPrompt { variable: "file".to_string(), label: "File".to_string(), required: true, ..Default::default() },
+            ScriptPrompt { variable: "token".to_string(), label: "Token".to_string(), kind: PromptKind::Password, ..Default::default() },
+        ];
+        let script = add_script(
+            "Preview Test".to_string(),
+            "touch {file} && echo {token}".to_string(),
+            true,
+            Some(prompts),
+            None,
+            None,
+            None,
+            None,
+        ).unwrap();
+        let id = script["id"].as_str().unwrap().to_string();
+
+        let mut args = HashMap::new();
+        args.insert("file".to_string(), marker.to_string_lossy().to_string() + " x");
+        args.insert("token".to_string(), "s3cret".to_string());
+        let preview = preview_script(id.clone(), Some(args));
+        let missing = preview_script(id.clone(), None);
+        let _ = remove_script(id);
+
+        let preview = preview.unwrap();
+        assert_eq!(
+            preview["resolved_command"].as_str().unwrap(),
+            format!("touch '{} x' && echo ********", marker.to_string_lossy())
+        );
+        assert_eq!(preview["interpreter"].as_str(), Some("sh"));
+        assert_eq!(preview["elevated"].as_bool(), Some(true));
+        assert_eq!(preview["elevation_method"].as_str(), Some(ELEVATION_HELPER));
+        assert!(preview["working_directory"].is_string());
+        assert!(!marker.exists(), "preview must not run anything");
+        assert!(missing.unwrap_err().to_string().contains("required"), "preview validates like a run");
+    }
+
+    #[test]
+    fn test_next_in_chain_limits() {
+        let scripts: Vec<CustomScript> = (0..MAX_CHAIN_STEPS + 2)
+            .map(|i| CustomScript {
+                id: format!("s{}", i),
+                name: format!("S{}", i),
+                on_success: Some(format!("s{}", i + 1)),
+                on_failure: Some("s0".to_string()),
+                ..Default::default()
+            })
+            .collect();
+        let visited = |n: usize| (0..n).map(|i| format!("s{}", i)).collect::<Vec<_>>();
+
+        assert_eq!(next_in_chain(&scripts, &scripts[0], true, &visited(1)).unwrap().unwrap().id, "s1");
+        assert!(next_in_chain(&scripts, &scripts[1], false, &visited(2)).unwrap_err().contains("cycle"));
+        let last = MAX_CHAIN_STEPS - 1;
+        assert!(next_in_chain(&scripts, &scripts[last], true, &visited(MAX_CHAIN_STEPS)).unwrap_err().contains("after"));
+        let end = CustomScript { on_success: Some("gone".to_string()), ..Default::default() };
+        assert!(next_in_chain(&scripts, &end, true, &[]).is_err());
+        assert!(next_in_chain(&scripts, &end, false, &[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_script_and_chain_sudo() {
+        let script = |id: &str, name: &str| CustomScript { id: id.to_string(), name: name.to_string(), ..Default::default() };
+        let scripts = vec![
+            CustomScript { on_failure: Some("c".to_string()), ..script("a", "Backup") },
+            script("b", "Backup"),
+            CustomScript { requires_sudo: true, on_success: Some("a".to_string()), ..script("c", "Cleanup") },
+        ];
+
+        assert_eq!(find_script(&scripts, "b").unwrap().id, "b");
+        assert_eq!(find_script(&scripts, "Cleanup").unwrap().id, "c");
+        assert!(find_script(&scripts, "Backup").unwrap_err().to_string().contains("use the id"));
+        assert_eq!(find_script(&scripts, "Missing").unwrap_err().code(), "not_found");
+
+        assert_eq!(chain_sudo_script(&scripts, &scripts[0]).unwrap().id, "c", "reached through on_failure");
+        assert!(chain_sudo_script(&scripts, &scripts[1]).is_none());
+    }
+
+    #[test]
+    fn test_run_script_follows_chain() {
+        let add = |name: &str, command: &str| {
+            add_script(name.to_string(), command.to_string(), false, None, None, None, None, None).unwrap()["id"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let first = add("Chain First", "echo first; exit 1");
+        let second = add("Chain Second", "echo second");
+        set_script_chain(first.clone(), None, Some(second.clone())).unwrap();
+        set_script_chain(second.clone(), Some(first.clone()), None).unwrap();
+        assert!(set_script_chain(first.clone(), Some(first.clone()), None).is_err());
+
+        let result = run_quiet(first.clone(), None).unwrap();
+        let history = get_script_history(first.clone(), Some(1)).unwrap();
+        let removed = remove_script(second.clone()).unwrap();
+        let config = load_config().unwrap();
+        let _ = remove_script(first.clone());
+
+        assert_eq!(result["steps"].as_array().map(|s| s.len()), Some(2));
+        assert_eq!(result["stdout"].as_str(), Some("first\n==> Chain Second\nsecond\n"));
+        assert_eq!(result["success"].as_bool(), Some(true), "the chain ends with the second step");
+        assert!(result["chain_error"].as_str().unwrap().contains("cycle"));
+        assert_eq!(history[0]["steps"].as_array().map(|s| s.len()), Some(2), "a chain is one history entry");
+
+        assert_eq!(removed["cleared_references"], json!(["Chain First"]));
+        assert!(config.scripts.iter().find(|s| s.id == first).unwrap().on_failure.is_none());
+    }
+
+    #[test]
+    fn test_streaming_chain_shares_run_id() {
+        let scripts = vec![
+            CustomScript { id: "a".into(), name: "A".into(), command: "echo a".into(), on_success: Some("b".into()), ..Default::default() },
+            CustomScript { id: "b".into(), name: "B".into(), command: "echo b; exit 2".into(), ..Default::default() },
+        ];
+        let events: Arc<Mutex<Vec<(String, serde_json::Value)>>> = Arc::default();
+        let sink = events.clone();
+        let emit: EmitFn = Arc::new(move |event: &str, payload: serde_json::Value| {
+            sink.lock().unwrap().push((event.to_string(), payload));
+        });
+        // An id no history file can exist for keeps this test off disk.
+        let root = CustomScript { id: "../chain-test".into(), ..scripts[0].clone() };
+        start_chain("run_chain_test".into(), scripts, root, HashMap::new(), None, None, emit, Arc::new(|_: &CompletionNotice| {})).unwrap();
+
+        let started = std::time::Instant::now();
+        while !events.lock().unwrap().iter().any(|(e, _)| e == "script-finished") {
+            assert!(started.elapsed().as_secs() < 10, "chain should finish");
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        let events = events.lock().unwrap();
+        assert!(events.iter().all(|(_, p)| p["run_id"] == "run_chain_test"));
+        let kinds: Vec<&str> = events.iter().map(|(e, _)| e.as_str()).collect();
+        assert_eq!(kinds, vec!["script-output", "script-step", "script-output", "script-finished"]);
+        let finished = &events[3].1;
+        assert_eq!(finished["exit_code"].as_i64(), Some(2));
+        assert_eq!(finished["steps"].as_array().map(|s| s.len()), Some(2));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_osascript_source_escaping() {
+        assert_eq!(applescript_quote(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+
+        let plan = RunPlan {
+            argv: vec!["sh".into(), "-c".into(), r#"echo "it's" \$HOME"#.into()],
+            env: vec![("name".into(), "a b".into())],
+            display: String::new(),
+            interpreter: "sh".into(),
+        };
+        assert_eq!(
+            osascript_source(&plan),
+            r#"do shell script "env name='a b' sh -c 'echo \"it'\\''s\" \\$HOME'" with administrator privileges"#
+        );
+    }
+
+    #[test]
+    fn test_remove_nonexistent_script_is_ok() {
+        // retain() silently no-ops when the id isn't found
+        let result = remove_script("nonexistent_id_xyz".to_string());
+        assert!(result.is_ok(), "remove_script on nonexistent id should not error");
+    }
+}
+
+fn clean_category(category: Option<String>) -> Option<String> {
+    category.map(|c| c.trim().to_string()).filter(|c| !c.is_empty())
+}
+
+fn clean_tags(tags: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !cleaned.contains(&tag) {
+            cleaned.push(tag);
+        }
+    }
+    cleaned
+}
+
+fn next_sort_order(config: &ScriptsConfig) -> u32 {
+    config.scripts.iter().map(|s| s.sort_order + 1).max().unwrap_or(0)
+}
+
+fn filter_scripts<'a>(scripts: &'a [CustomScript], category: Option<&str>, tag: Option<&str>) -> Vec<&'a CustomScript> {
+    let mut matching: Vec<&CustomScript> = scripts
+        .iter()
+        .filter(|s| category.is_none() || s.category.as_deref() == category)
+        .filter(|s| tag.is_none() || s.tags.iter().any(|t| Some(t.as_str()) == tag))
+        .collect();
+    matching.sort_by_key(|s| s.sort_order);
+    matching
+}
+
+fn apply_order(config: &mut ScriptsConfig, ids: &[String]) {
+    config.scripts.sort_by_key(|s| s.sort_order);
+    let rank = |s: &CustomScript| ids.iter().position(|id| *id == s.id).unwrap_or(ids.len());
+    config.scripts.sort_by_key(rank);
+    for (i, script) in config.scripts.iter_mut().enumerate() {
+        script.sort_order = i as u32;
+    }
+}
+
+#[tauri::command]
+pub fn list_scripts(category: Option<String>, tag: Option<String>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("scripts")?;
+    let config = load_config()?;
+    let dir = history_dir();
+    let scripts: Vec<serde_json::Value> = filter_scripts(&config.scripts, category.as_deref(), tag.as_deref())
+        .into_iter()
+        .map(|script| {
+            let last = history_path(&dir, &script.id).ok().and_then(|p| load_history(&p).into_iter().next());
+            let mut value = json!(script);
+            value["last_run_at"] = json!(last.as_ref().map(|r| &r.started_at));
+            value["last_exit_code"] = json!(last.and_then(|r| r.exit_code));
+            value
+        })
+        .collect();
+    Ok(json!(scripts))
+}
+
+#[tauri::command]
+pub fn list_favorite_scripts() -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("scripts")?;
+    let config = load_config()?;
+    let favorites: Vec<serde_json::Value> = filter_scripts(&config.scripts, None, None)
+        .into_iter()
+        .filter(|s| s.is_favorite)
+        .map(|s| {
+            json!({
+                "id": s.id,
+                "name": s.name,
+                "requires_sudo": s.requires_sudo,
+                "prompt_count": s.prompts.len(),
+            })
+        })
+        .collect();
+    Ok(json!(favorites))
+}
+
+pub(crate) fn require_favorite(id: &str) -> Result<(), GantryError> {
+    let config = load_config()?;
+    let script = config.scripts.iter().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    if !script.is_favorite {
+        return Err(format!("{} is not a favorite script", script.name).into());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_script_login_shell(id: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.login_shell = enabled;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_script_accepts_stdin(id: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.accepts_stdin = enabled;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_script_skip_preflight(id: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.skip_preflight = enabled;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_script_favorite(id: String, value: bool) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.is_favorite = value;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+fn clean_body(body: Option<String>) -> Option<String> {
+    body.filter(|b| !b.trim().is_empty())
+}
+
+fn clean_interpreter(interpreter: Option<String>) -> Option<String> {
+    interpreter.map(|i| i.trim().to_string()).filter(|i| !i.is_empty())
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub fn add_script(
+    name: String,
+    command: String,
+    requires_sudo: bool,
+    prompts: Option<Vec<ScriptPrompt>>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    body: Option<String>,
+    interpreter: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    let body = clean_body(body);
+    if command.trim().is_empty() && body.is_none() {
+        return Err("A script needs a command or a body".into());
+    }
+    let _lock = config_lock();
+    let mut config = load_config()?;
+
+    let id = next_script_id(&config);
+    let script = CustomScript {
+        id: id.clone(),
+        name,
+        command,
+        requires_sudo,
+        prompts: prompts.unwrap_or_default(),
+        category: clean_category(category),
+        tags: clean_tags(tags.unwrap_or_default()),
+        sort_order: next_sort_order(&config),
+        body,
+        interpreter: clean_interpreter(interpreter),
+        on_success: None,
+        on_failure: None,
+        notify_on_completion: false,
+        is_favorite: false,
+        login_shell: false,
+        accepts_stdin: false,
+        skip_preflight: false,
+    };
+
+    config.scripts.push(script.clone());
+    save_config(&config)?;
+
+    Ok(json!(script))
+}
+
+#[tauri::command]
+pub fn remove_script(id: String) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    config.scripts.retain(|s| s.id != id);
+    let cleared = clear_chain_references(&mut config, &id);
+    save_config(&config)?;
+    if let Ok(path) = history_path(&history_dir(), &id) {
+        let _ = fs::remove_file(path);
+    }
+    Ok(json!({"success": true, "cleared_references": cleared}))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub fn update_script(
+    id: String,
+    name: String,
+    command: String,
+    requires_sudo: bool,
+    prompts: Option<Vec<ScriptPrompt>>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    body: Option<String>,
+    interpreter: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+
+    if let Some(script) = config.scripts.iter_mut().find(|s| s.id == id) {
+        if body.is_some() {
+            script.body = clean_body(body);
+        }
+        if interpreter.is_some() {
+            script.interpreter = clean_interpreter(interpreter);
+        }
+        if command.trim().is_empty() && script.body.is_none() {
+            return Err("A script needs a command or a body".into());
+        }
+        script.name = name;
+        script.command = command;
+        script.requires_sudo = requires_sudo;
+        script.prompts = prompts.unwrap_or_default();
+        if category.is_some() {
+            script.category = clean_category(category);
+        }
+        if let Some(tags) = tags {
+            script.tags = clean_tags(tags);
+        }
+        save_config(&config)?;
+        Ok(json!({"success": true}))
+    } else {
+        Err(GantryError::NotFound("Script not found".into()))
+    }
+}
+
+fn clear_chain_references(config: &mut ScriptsConfig, id: &str) -> Vec<String> {
+    let mut cleared = Vec::new();
+    for script in &mut config.scripts {
+        let before = (script.on_success.is_some(), script.on_failure.is_some());
+        script.on_success = script.on_success.take().filter(|next| next != id);
+        script.on_failure = script.on_failure.take().filter(|next| next != id);
+        if before != (script.on_success.is_some(), script.on_failure.is_some()) {
+            cleared.push(script.name.clone());
+        }
+    }
+    cleared
+}
+
+#[tauri::command]
+pub fn set_script_chain(id: String, on_success: Option<String>, on_failure: Option<String>) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let on_success = on_success.filter(|next| !next.is_empty());
+    let on_failure = on_failure.filter(|next| !next.is_empty());
+    for next in on_success.iter().chain(on_failure.iter()) {
+        if *next == id {
+            return Err("A script can't chain to itself".into());
+        }
+        if !config.scripts.iter().any(|s| s.id == *next) {
+            return Err(GantryError::NotFound(format!("Script {} not found", next)));
+        }
+    }
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.on_success = on_success;
+    script.on_failure = on_failure;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_script_notify(id: String, enabled: bool) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let script = config.scripts.iter_mut().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+    script.notify_on_completion = enabled;
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+fn next_in_chain<'a>(
+    scripts: &'a [CustomScript],
+    current: &CustomScript,
+    success: bool,
+    visited: &[String],
+) -> Result<Option<&'a CustomScript>, String> {
+    let next_id = match if success { &current.on_success } else { &current.on_failure } {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let next = scripts.iter().find(|s| s.id == *next_id)
+        .ok_or_else(|| format!("Chain stopped: {} links to a script that no longer exists", current.name))?;
+    if visited.contains(next_id) {
+        return Err(format!("Chain stopped: {} would run again (cycle)", next.name));
+    }
+    if visited.len() >= MAX_CHAIN_STEPS {
+        return Err(format!("Chain stopped after {} steps", MAX_CHAIN_STEPS));
+    }
+    Ok(Some(next))
+}
+
+#[tauri::command]
+pub fn reorder_scripts(ids_in_order: Vec<String>) -> Result<serde_json::Value, GantryError> {
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    apply_order(&mut config, &ids_in_order);
+    save_config(&config)?;
+    Ok(json!({"success": true}))
+}
+
+fn next_script_id(config: &ScriptsConfig) -> String {
+    loop {
+        let id = format!("script_{}", uuid::Uuid::new_v4());
+        if !config.scripts.iter().any(|s| s.id == id) {
+            return id;
+        }
+    }
+}
+
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn parse_bundle(content: &str) -> Result<ScriptBundle, String> {
+    let bundle: ScriptBundle = serde_yaml::from_str(content).map_err(|e| format!("Not a valid script bundle: {}", e))?;
+    if bundle.gantry_scripts > BUNDLE_VERSION {
+        return Err(format!("Bundle version {} is newer than this version of Gantry supports", bundle.gantry_scripts));
+    }
+    for (i, script) in bundle.scripts.iter().enumerate() {
+        let has_body = script.body.as_deref().is_some_and(|b| !b.trim().is_empty());
+        if script.name.trim().is_empty() || (script.command.trim().is_empty() && !has_body) {
+            return Err(format!("Script {} in the bundle has no name, command or body", i + 1));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for prompt in &script.prompts {
+            if prompt.variable.trim().is_empty() || !seen.insert(prompt.variable.as_str()) {
+                return Err(format!("{}: prompt variables must be non-empty and unique", script.name));
+            }
+        }
+    }
+    Ok(bundle)
+}
+
+fn merge_bundle(config: &mut ScriptsConfig, bundle: ScriptBundle, overwrite: bool) -> serde_json::Value {
+    let mut imported = Vec::new();
+    let mut overwritten = Vec::new();
+    let mut renamed = Vec::new();
+    let mut skipped = Vec::new();
+
+    for incoming in bundle.scripts {
+        let existing = config.scripts.iter().position(|s| s.name == incoming.name);
+        let mut name = incoming.name.clone();
+        if let Some(index) = existing {
+            let current = &mut config.scripts[index];
+            let same = current.command == incoming.command
+                && current.requires_sudo == incoming.requires_sudo
+                && current.body == incoming.body
+                && current.interpreter == incoming.interpreter
+                && serde_json::to_value(&current.prompts).ok() == serde_json::to_value(&incoming.prompts).ok();
+            if overwrite {
+                current.command = incoming.command;
+                current.body = clean_body(incoming.body);
+                current.interpreter = clean_interpreter(incoming.interpreter);
+                current.requires_sudo = incoming.requires_sudo;
+                current.prompts = incoming.prompts;
+                current.category = clean_category(incoming.category);
+                current.tags = clean_tags(incoming.tags);
+                overwritten.push(name);
+                continue;
+            }
+            if same {
+                skipped.push(name);
+                continue;
+            }
+            let mut n = 2;
+            while config.scripts.iter().any(|s| s.name == name) {
+                name = format!("{} ({})", incoming.name, n);
+                n += 1;
+            }
+            renamed.push(json!({"from": incoming.name, "to": name}));
+        }
+        let id = next_script_id(config);
+        let sort_order = next_sort_order(config);
+        config.scripts.push(CustomScript {
+            id,
+            name: name.clone(),
+            command: incoming.command,
+            requires_sudo: incoming.requires_sudo,
+            prompts: incoming.prompts,
+            category: clean_category(incoming.category),
+            tags: clean_tags(incoming.tags),
+            sort_order,
+            body: clean_body(incoming.body),
+            interpreter: clean_interpreter(incoming.interpreter),
+            // Chain links are machine-local ids and aren't part of bundles.
+            on_success: None,
+            on_failure: None,
+        notify_on_completion: false,
+        is_favorite: false,
+        login_shell: false,
+        accepts_stdin: false,
+        skip_preflight: false,
+        });
+        imported.push(name);
+    }
+
+    json!({
+        "imported": imported,
+        "overwritten": overwritten,
+        "renamed": renamed,
+        "skipped": skipped,
+    })
+}
+
+#[tauri::command]
+pub fn export_scripts(ids: Option<Vec<String>>, path: Option<String>) -> Result<serde_json::Value, GantryError> {
+    let config = load_config()?;
+    let scripts: Vec<BundledScript> = filter_scripts(&config.scripts, None, None)
+        .into_iter()
+        .filter(|s| ids.as_ref().map(|ids| ids.contains(&s.id)).unwrap_or(true))
+        .map(|s| BundledScript {
+            name: s.name.clone(),
+            command: s.command.clone(),
+            requires_sudo: s.requires_sudo,
+            prompts: s.prompts.clone(),
+            category: s.category.clone(),
+            tags: s.tags.clone(),
+            body: s.body.clone(),
+            interpreter: s.interpreter.clone(),
+        })
+        .collect();
+    if scripts.is_empty() {
+        return Err("No scripts to export".into());
+    }
+
+    let bundle = ScriptBundle { gantry_scripts: BUNDLE_VERSION, scripts };
+    let content = serde_yaml::to_string(&bundle).map_err(|e| e.to_string())?;
+    let path = match path {
+        Some(path) => expand_home(&path),
+        None => {
+            let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+            let dir = home.join("Downloads");
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            dir.join(format!("gantry-scripts-{}.yaml", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+        }
+    };
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(json!({"path": path.to_string_lossy(), "count": bundle.scripts.len()}))
+}
+
+#[tauri::command]
+pub fn import_scripts(path: String, overwrite: bool) -> Result<serde_json::Value, GantryError> {
+    let content = fs::read_to_string(expand_home(&path)).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let bundle = parse_bundle(&content)?;
+    let _lock = config_lock();
+    let mut config = load_config()?;
+    let summary = merge_bundle(&mut config, bundle, overwrite);
+    save_config(&config)?;
+    Ok(summary)
+}
+
+fn validate_args(prompts: &[ScriptPrompt], args: Option<&HashMap<String, String>>) -> Result<HashMap<String, String>, String> {
+    let mut values = args.cloned().unwrap_or_default();
+    for prompt in prompts {
+        let name = if prompt.label.is_empty() { &prompt.variable } else { &prompt.label };
+        let value = values
+            .get(&prompt.variable)
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .or_else(|| prompt.default.clone().filter(|d| !d.is_empty()));
+        let Some(value) = value else {
+            if prompt.required {
+                return Err(format!("{} is required", name));
+            }
+            values.insert(prompt.variable.clone(), String::new());
+            continue;
+        };
+
+        match prompt.kind {
+            PromptKind::Select if !prompt.options.is_empty() && !prompt.options.contains(&value) => {
+                return Err(format!("{} must be one of: {}", name, prompt.options.join(", ")));
+            }
+            PromptKind::Number if value.trim().parse::<f64>().is_err() => {
+                return Err(format!("{} must be a number", name));
+            }
+            PromptKind::Boolean if value != "true" && value != "false" => {
+                return Err(format!("{} must be true or false", name));
+            }
+            _ => {}
+        }
+        values.insert(prompt.variable.clone(), value);
+    }
+    Ok(values)
+}
+
+fn shell_quote(value: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+    if !value.is_empty() && value.chars().all(plain) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+// One pass, so a value that contains a placeholder is never expanded again.
+fn substitute(template: &str, mut value_for: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').and_then(|end| value_for(&after[..end]).map(|v| (end, v))) {
+            Some((end, value)) => {
+                out.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn resolve_command(script: &CustomScript, values: &HashMap<String, String>) -> (String, String) {
+    let prompt = |key: &str| script.prompts.iter().find(|p| p.variable == key);
+    let quoted = |key: &str, value: &str| {
+        if prompt(key).is_some_and(|p| p.raw) {
+            value.to_string()
+        } else {
+            shell_quote(value)
+        }
+    };
+    let command = substitute(&script.command, |key| values.get(key).map(|v| quoted(key, v)));
+    let display = substitute(&script.command, |key| {
+        let value = values.get(key)?;
+        if prompt(key).is_some_and(|p| p.kind == PromptKind::Password) {
+            Some("********".to_string())
+        } else {
+            Some(quoted(key, value))
+        }
+    });
+    (command, display)
+}
+
+struct TempScript(PathBuf);
+
+impl Drop for TempScript {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn script_tmp_dir() -> PathBuf {
+    scripts_path("tmp")
+}
+
+fn write_temp_script(body: &str) -> Result<TempScript, String> {
+    use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+    let dir = script_tmp_dir();
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("script-{}-{}", std::process::id(), NEXT_RUN.fetch_add(1, Ordering::Relaxed)));
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o700)
+        .open(&path)
+        .map_err(|e| format!("Failed to write script file: {}", e))?;
+    let guard = TempScript(path);
+    file.write_all(body.as_bytes()).map_err(|e| e.to_string())?;
+    if !body.ends_with('\n') {
+        file.write_all(b"\n").map_err(|e| e.to_string())?;
+    }
+    Ok(guard)
+}
+
+fn is_shell_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+struct RunPlan {
+    argv: Vec<String>,
+    env: Vec<(String, String)>,
+    display: String,
+    interpreter: String,
+}
+
+fn script_body(script: &CustomScript) -> Option<&str> {
+    script.body.as_deref().filter(|b| !b.trim().is_empty())
+}
+
+// $SHELL isn't used: a GUI session may not set it, or may have inherited it from something else.
+fn user_login_shell() -> String {
+    let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buf = vec![0 as libc::c_char; 4096];
+    let mut found = std::ptr::null_mut();
+    let rc = unsafe { libc::getpwuid_r(libc::getuid(), &mut pwd, buf.as_mut_ptr(), buf.len(), &mut found) };
+    let shell = if rc == 0 && !found.is_null() && !pwd.pw_shell.is_null() {
+        unsafe { std::ffi::CStr::from_ptr(pwd.pw_shell) }.to_string_lossy().to_string()
+    } else {
+        String::new()
+    };
+    if shell.is_empty() || shell.ends_with("nologin") || shell.ends_with("/false") {
+        return "/bin/sh".to_string();
+    }
+    shell
+}
+
+fn interpreter_program(interpreter: &str) -> &str {
+    let mut tokens = interpreter.split_whitespace();
+    let mut program = tokens.next().unwrap_or("");
+    if program.rsplit('/').next() == Some("env") {
+        program = tokens.next().unwrap_or("");
+    }
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+fn uses_login_shell(script: &CustomScript) -> bool {
+    script.login_shell
+        && match script_body(script) {
+            None => true,
+            Some(body) => {
+                let interpreter = script.interpreter.as_deref().map(str::trim).filter(|i| !i.is_empty())
+                    .or_else(|| body.lines().next().and_then(|l| l.strip_prefix("#!")))
+                    .unwrap_or("sh");
+                matches!(interpreter_program(interpreter), "sh" | "bash" | "zsh")
+            }
+        }
+}
+
+fn effective_path(script: &CustomScript) -> Option<String> {
+    if !uses_login_shell(script) {
+        return std::env::var("PATH").ok();
+    }
+    let output = Command::new(user_login_shell())
+        .arg("-lc")
+        .arg("printf '\\n%s' \"$PATH\"")
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    // Profiles sometimes print a banner; the PATH is the last line.
+    String::from_utf8_lossy(&output.stdout).lines().last().map(|l| l.to_string())
+}
+
+const SHELL_BUILTINS: &[&str] = &[
+    "!", ".", ":", "[", "[[", "{", "}", "alias", "bg", "break", "builtin", "case", "cd", "command", "continue",
+    "declare", "do", "done", "echo", "elif", "else", "esac", "eval", "exec", "exit", "export", "false", "fg", "fi",
+    "for", "function", "getopts", "hash", "if", "jobs", "kill", "let", "local", "printf", "pwd", "read", "readonly",
+    "return", "select", "set", "shift", "source", "test", "then", "time", "times", "trap", "true", "type",
+    "typeset", "ulimit", "umask", "unalias", "unset", "until", "wait", "while",
+];
+
+fn command_segments(command: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '\\') => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                continue;
+            }
+            (None, ';' | '|' | '&' | '\n') => {
+                if matches!(chars.peek(), Some('|' | '&')) {
+                    chars.next();
+                }
+                segments.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    segments.push(current);
+    segments.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+fn segment_program(segment: &str) -> Option<&str> {
+    let word = segment.split_whitespace().find(|w| {
+        !w.split_once('=').is_some_and(|(name, _)| is_shell_identifier(name))
+    })?;
+    let opaque = word.contains(|c| "$`(){}*?<>'\"\\~".contains(c));
+    (!opaque && !SHELL_BUILTINS.contains(&word)).then_some(word)
+}
+
+fn program_exists(program: &str, path: &str) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    let is_executable = |p: &std::path::Path| fs::metadata(p).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0);
+    if program.contains('/') {
+        return is_executable(std::path::Path::new(program));
+    }
+    std::env::split_paths(path).any(|dir| is_executable(&dir.join(program)))
+}
+
+fn preflight_missing(script: &CustomScript, values: &HashMap<String, String>, path: &str) -> Vec<String> {
+    if script.skip_preflight {
+        return vec![];
+    }
+    let plan = plan_run(script, values, "");
+    let mut interpreter = plan.interpreter.split_whitespace();
+    let mut programs: Vec<String> = interpreter.next().map(String::from).into_iter().collect();
+    if programs.first().is_some_and(|p| p.rsplit('/').next() == Some("env")) {
+        programs.extend(interpreter.next().map(String::from));
+    }
+    if script_body(script).is_none() {
+        let segments = plan.argv.last().map(|c| command_segments(c)).unwrap_or_default();
+        programs.extend(segments.iter().filter_map(|s| segment_program(s)).map(String::from));
+    }
+    let mut missing: Vec<String> = Vec::new();
+    for program in programs {
+        if !program_exists(&program, path) && !missing.contains(&program) {
+            missing.push(program);
+        }
+    }
+    missing
+}
+
+fn preflight_error(script: &CustomScript, missing: &[String]) -> serde_json::Value {
+    let hint = if script.login_shell {
+        format!("{} wasn't found on your login shell's PATH. Install it or use its full path.", missing.join(", "))
+    } else {
+        format!(
+            "{} wasn't found on gantry's PATH. Install it, use its full path, or turn on \"Run in my login shell\" if it works in your terminal.",
+            missing.join(", ")
+        )
+    };
+    json!({"missing": missing, "hint": hint})
+}
+
+fn plan_run(script: &CustomScript, values: &HashMap<String, String>, body_path: &str) -> RunPlan {
+    let login_shell = uses_login_shell(script).then(user_login_shell);
+    let body = match script_body(script) {
+        Some(body) => body,
+        None => {
+            let (command, display) = resolve_command(script, values);
+            let (shell, flag) = match login_shell {
+                Some(shell) => (shell, "-lc"),
+                None => ("sh".to_string(), "-c"),
+            };
+            return RunPlan {
+                argv: vec![shell.clone(), flag.to_string(), command],
+                env: vec![],
+                display,
+                interpreter: shell,
+            };
+        }
+    };
+
+    let (mut argv, mut interpreter): (Vec<String>, String) =
+        match script.interpreter.as_deref().map(str::trim).filter(|i| !i.is_empty()) {
+            Some(interpreter) => (interpreter.split_whitespace().map(String::from).collect(), interpreter.to_string()),
+            None => match body.lines().next().and_then(|l| l.strip_prefix("#!")) {
+                Some(shebang) => (vec![], shebang.trim().to_string()),
+                None => (vec!["sh".to_string()], "sh".to_string()),
+            },
+        };
+    if let Some(shell) = login_shell {
+        argv = vec![shell.clone(), "-l".to_string()];
+        interpreter = shell;
+    }
+    argv.push(body_path.to_string());
+
+    let mut display_args = Vec::new();
+    let mut env = Vec::new();
+    for prompt in &script.prompts {
+        let value = values.get(&prompt.variable).cloned().unwrap_or_default();
+        display_args.push(if prompt.kind == PromptKind::Password { "********".to_string() } else { shell_quote(&value) });
+        if is_shell_identifier(&prompt.variable) {
+            env.push((prompt.variable.clone(), value.clone()));
+        }
+    }
+    let display = argv.iter().map(|a| shell_quote(a)).chain(display_args).collect::<Vec<_>>().join(" ");
+    argv.extend(script.prompts.iter().map(|p| values.get(&p.variable).cloned().unwrap_or_default()));
+    RunPlan { argv, env, display, interpreter }
+}
+
+#[cfg(target_os = "linux")]
+const ELEVATION_HELPER: &str = "pkexec";
+#[cfg(target_os = "macos")]
+const ELEVATION_HELPER: &str = "osascript";
+
+fn elevation_method(requires_sudo: bool) -> Option<&'static str> {
+    requires_sudo.then_some(ELEVATION_HELPER)
+}
+
+// pkexec clears the environment, so variables go through env(1).
+#[cfg(target_os = "linux")]
+fn elevated_command(plan: &RunPlan) -> Command {
+    let mut cmd = Command::new(ELEVATION_HELPER);
+    if !plan.env.is_empty() {
+        cmd.arg("env").args(plan.env.iter().map(|(k, v)| format!("{}={}", k, v)));
+    }
+    cmd.args(&plan.argv);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_os = "macos")]
+fn osascript_source(plan: &RunPlan) -> String {
+    let env = plan.env.iter().map(|(k, v)| format!("{}={}", k, shell_quote(v)));
+    let argv = plan.argv.iter().map(|a| shell_quote(a));
+    let line = std::iter::once("env".to_string()).chain(env).chain(argv).collect::<Vec<_>>().join(" ");
+    format!("do shell script {} with administrator privileges", applescript_quote(&line))
+}
+
+#[cfg(target_os = "macos")]
+fn elevated_command(plan: &RunPlan) -> Command {
+    let mut cmd = Command::new(ELEVATION_HELPER);
+    cmd.arg("-e").arg(osascript_source(plan));
+    cmd
+}
+
+fn build_command(plan: &RunPlan, requires_sudo: bool) -> Command {
+    if elevation_method(requires_sudo).is_some() {
+        return elevated_command(plan);
+    }
+    let mut cmd = Command::new(&plan.argv[0]);
+    cmd.args(&plan.argv[1..]).envs(plan.env.iter().cloned());
+    cmd
+}
+
+struct PreparedRun {
+    cmd: Command,
+    display: String,
+    temp: Option<TempScript>,
+}
+
+fn prepare_run(script: &CustomScript, values: &HashMap<String, String>) -> Result<PreparedRun, String> {
+    let temp = script_body(script).map(write_temp_script).transpose()?;
+    let body_path = temp.as_ref().map(|t| t.0.to_string_lossy().to_string()).unwrap_or_default();
+    let plan = plan_run(script, values, &body_path);
+    Ok(PreparedRun { cmd: build_command(&plan, script.requires_sudo), display: plan.display, temp })
+}
+
+#[tauri::command]
+pub fn preview_script(id: String, args: Option<HashMap<String, String>>) -> Result<serde_json::Value, GantryError> {
+    let config = load_config()?;
+    let script = config.scripts.iter().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+
+    let values = validate_args(&script.prompts, args.as_ref())?;
+    let body_path = script_tmp_dir().join("script-<run>").to_string_lossy().to_string();
+    let plan = plan_run(script, &values, &body_path);
+    let working_directory = std::env::current_dir().map(|d| d.to_string_lossy().to_string()).ok();
+    Ok(json!({
+        "resolved_command": plan.display,
+        "interpreter": plan.interpreter,
+        "working_directory": working_directory,
+        "elevated": script.requires_sudo,
+        "elevation_method": elevation_method(script.requires_sudo),
+        "body": script_body(script),
+        "env": plan.env.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+        "login_shell": uses_login_shell(script),
+    }))
+}
+
+#[cfg(target_os = "linux")]
+const FAILURE_SOUND: &str = "dialog-warning";
+#[cfg(target_os = "macos")]
+const FAILURE_SOUND: &str = "Basso";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Urgency {
+    Normal,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CompletionNotice {
+    title: String,
+    body: String,
+    urgency: Urgency,
+}
+
+fn format_duration(ms: u64) -> String {
+    match ms {
+        0..=59_999 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m {}s", ms / 60_000, ms % 60_000 / 1000),
+    }
+}
+
+fn completion_notice(script: &CustomScript, record: &ScriptRunRecord, enabled: bool) -> Option<CompletionNotice> {
+    if !enabled || !script.notify_on_completion {
+        return None;
+    }
+    let (title, status) = match (record.success, record.exit_code) {
+        (true, _) => ("Script finished", "finished".to_string()),
+        (false, Some(code)) => ("Script failed", format!("failed with exit code {}", code)),
+        (false, None) => ("Script failed", "was killed".to_string()),
+    };
+    Some(CompletionNotice {
+        title: title.to_string(),
+        body: format!("{} {} after {}", script.name, status, format_duration(record.duration_ms)),
+        urgency: if record.success { Urgency::Normal } else { Urgency::Critical },
+    })
+}
+
+fn desktop_notifier(app: tauri::AppHandle) -> NotifyFn {
+    Arc::new(move |notice: &CompletionNotice| {
+        use tauri_plugin_notification::NotificationExt;
+        let mut builder = app.notification().builder().title(&notice.title).body(&notice.body);
+        if notice.urgency == Urgency::Critical {
+            builder = builder.sound(FAILURE_SOUND);
+        }
+        let _ = builder.show();
+    })
+}
+
+fn notify_completion(script: &CustomScript, record: &ScriptRunRecord, notify: &NotifyFn) -> bool {
+    match completion_notice(script, record, super::settings::notifications_enabled()) {
+        Some(notice) => {
+            notify(&notice);
+            true
+        }
+        None => false,
+    }
+}
+
+#[tauri::command]
+pub fn run_script(
+    app: tauri::AppHandle,
+    id: String,
+    args: Option<HashMap<String, String>>,
+    stdin: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    run_script_with(id, args, stdin, desktop_notifier(app))
+}
+
+fn run_script_with(
+    id: String,
+    args: Option<HashMap<String, String>>,
+    stdin: Option<String>,
+    notify: NotifyFn,
+) -> Result<serde_json::Value, GantryError> {
+    let mut stdin = check_stdin(stdin)?;
+    let config = load_config()?;
+    let script = config.scripts.iter().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?;
+
+    let mut values = validate_args(&script.prompts, args.as_ref())?;
+    let path = effective_path(script);
+    let missing = preflight_missing(script, &values, path.as_deref().unwrap_or(""));
+    if !missing.is_empty() {
+        let preflight = preflight_error(script, &missing);
+        return Ok(json!({
+            "success": false,
+            "stdout": "",
+            "stderr": preflight["hint"],
+            "exit_code": null,
+            "resolved_command": plan_run(script, &values, "").display,
+            "elevation": elevation_method(script.requires_sudo),
+            "effective_path": path,
+            "steps": [],
+            "chain_error": null,
+            "notified": false,
+            "truncated": false,
+            "output_path": null,
+            "preflight": preflight,
+        }));
+    }
+    let started_at = chrono::Local::now();
+    let mut current = script;
+    let mut record = ScriptRunRecord { started_at: started_at.to_rfc3339(), ..Default::default() };
+    let mut visited = Vec::new();
+    let mut secrets = Vec::new();
+    let mut steps = Vec::new();
+    let limit = super::settings::script_output_limit();
+    let spool = script_output_dir().join(format!("{}.log", new_run_id()));
+    let mut spilled = false;
+
+    loop {
+        let prepared = match prepare_run(current, &values) {
+            Ok(prepared) => prepared,
+            Err(e) if visited.is_empty() => return Err(e.into()),
+            Err(e) => {
+                record.chain_error = Some(e);
+                break;
+            }
+        };
+        let PreparedRun { mut cmd, display, temp } = prepared;
+        let step_started = chrono::Local::now();
+        let limits = (limit.saturating_sub(record.stdout.len()), limit.saturating_sub(record.stderr.len()));
+        let output = run_captured(&mut cmd, stdin.take(), limits, &spool);
+        drop(temp);
+
+        let (success, exit_code, stdout, stderr) = match output {
+            Ok(result) => {
+                spilled |= result.truncated;
+                (result.status.success(), result.status.code(), result.stdout, result.stderr)
+            }
+            Err(e) => (false, Some(-1), String::new(), e.to_string()),
+        };
+        if !visited.is_empty() {
+            record.stdout.push_str(&format!("==> {}\n", current.name));
+        }
+        record.stdout.push_str(&stdout);
+        record.stderr.push_str(&stderr);
+        record.success = success;
+        record.exit_code = exit_code;
+        if visited.is_empty() {
+            record.resolved_command = display.clone();
+        }
+        record.steps.push(ChainStepRecord {
+            script_id: current.id.clone(),
+            name: current.name.clone(),
+            success,
+            exit_code,
+            duration_ms: (chrono::Local::now() - step_started).num_milliseconds().max(0) as u64,
+            resolved_command: display.clone(),
+        });
+        steps.push(json!({
+            "script_id": current.id,
+            "name": current.name,
+            "success": success,
+            "exit_code": exit_code,
+            "stdout": stdout,
+            "stderr": stderr,
+            "resolved_command": display,
+            "elevation": elevation_method(current.requires_sudo),
+        }));
+        secrets.extend(password_values(current, &values));
+        visited.push(current.id.clone());
+
+        match next_in_chain(&config.scripts, current, success, &visited) {
+            Ok(Some(next)) => match validate_args(&next.prompts, args.as_ref()) {
+                Ok(next_values) => {
+                    current = next;
+                    values = next_values;
+                }
+                Err(e) => {
+                    record.chain_error = Some(format!("Chain stopped before {}: {}", next.name, e));
+                    break;
+                }
+            },
+            Ok(None) => break,
+            Err(e) => {
+                record.chain_error = Some(e);
+                break;
+            }
+        }
+    }
+
+    record.duration_ms = (chrono::Local::now() - started_at).num_milliseconds().max(0) as u64;
+    if record.steps.len() == 1 {
+        record.steps.clear();
+    }
+    let notified = notify_completion(script, &record, &notify);
+    // The full output only needs keeping when some of it didn't fit.
+    let output_path = if spilled {
+        record.truncated = true;
+        Some(spool.to_string_lossy().to_string())
+    } else {
+        let _ = fs::remove_file(&spool);
+        None
+    };
+    let response = json!({
+        "success": record.success,
+        "stdout": record.stdout,
+        "stderr": record.stderr,
+        "exit_code": record.exit_code,
+        "resolved_command": record.resolved_command,
+        "elevation": elevation_method(script.requires_sudo),
+        "effective_path": path,
+        "steps": steps,
+        "chain_error": record.chain_error,
+        "notified": notified,
+        "truncated": spilled,
+        "output_path": output_path,
+        "preflight": null,
+    });
+    record_run(&script.id, record, &secrets);
+    Ok(response)
+}
+
+fn find_script<'a>(scripts: &'a [CustomScript], target: &str) -> Result<&'a CustomScript, GantryError> {
+    if let Some(script) = scripts.iter().find(|s| s.id == target) {
+        return Ok(script);
+    }
+    let named: Vec<&CustomScript> = scripts.iter().filter(|s| s.name == target).collect();
+    match named.as_slice() {
+        [script] => Ok(script),
+        [] => Err(GantryError::NotFound(format!("No script with id or name {}", target))),
+        _ => Err(format!("{} scripts are named {}; use the id instead", named.len(), target).into()),
+    }
+}
+
+fn chain_sudo_script<'a>(scripts: &'a [CustomScript], start: &'a CustomScript) -> Option<&'a CustomScript> {
+    let mut pending = vec![start];
+    let mut seen = HashSet::new();
+    while let Some(script) = pending.pop() {
+        if !seen.insert(script.id.as_str()) {
+            continue;
+        }
+        if script.requires_sudo {
+            return Some(script);
+        }
+        for next in [&script.on_success, &script.on_failure].into_iter().flatten() {
+            pending.extend(scripts.iter().find(|s| s.id == *next));
+        }
+    }
+    None
+}
+
+// There may be no polkit agent to ask for a password, so a chain that needs root only runs when
+// this already is root.
+fn run_script_headless(target: &str, args: HashMap<String, String>) -> Result<serde_json::Value, GantryError> {
+    let config = load_config()?;
+    let script = find_script(&config.scripts, target)?;
+    if let Some(sudo) = chain_sudo_script(&config.scripts, script) {
+        if unsafe { libc::geteuid() } != 0 {
+            return Err(GantryError::PermissionDenied(format!("{} requires root; run gantry run-script as root", sudo.name)));
+        }
+    }
+    run_script_with(script.id.clone(), Some(args), None, Arc::new(|_: &CompletionNotice| {}))
+}
+
+pub(crate) fn run_script_cli(target: &str, args: HashMap<String, String>) -> i32 {
+    let result = match run_script_headless(target, args) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("gantry: {}", e);
+            return 2;
+        }
+    };
+    print!("{}", result["stdout"].as_str().unwrap_or(""));
+    eprint!("{}", result["stderr"].as_str().unwrap_or(""));
+    if let Some(error) = result["chain_error"].as_str() {
+        eprintln!("gantry: {}", error);
+    }
+    if let Some(path) = result["output_path"].as_str() {
+        eprintln!("gantry: output was cut short; the full output is in {}", path);
+    }
+    // No code means the script never ran or was killed by a signal.
+    result["exit_code"].as_i64().map_or(1, |code| code as i32)
+}
+
+fn script_runs() -> &'static Mutex<HashMap<String, Child>> {
+    SCRIPT_RUNS.get_or_init(Default::default)
+}
+
+fn signal_group(pid: u32, signal: i32) -> Result<(), String> {
+    if unsafe { libc::kill(-(pid as i32), signal) } == 0 {
+        return Ok(());
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(libc::EPERM) => Err("The script is running as root and can't be stopped from here".to_string()),
+        Some(libc::ESRCH) => Ok(()),
+        _ => Err(std::io::Error::last_os_error().to_string()),
+    }
+}
+
+fn read_capped_line<R: BufRead>(reader: &mut R, line: &mut Vec<u8>, limit: usize) -> std::io::Result<Option<bool>> {
+    line.clear();
+    let mut cut = false;
+    let mut read_any = false;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(read_any.then_some(cut));
+        }
+        read_any = true;
+        let (chunk, done) = match buf.iter().position(|&b| b == b'\n') {
+            Some(end) => (&buf[..end], Some(end + 1)),
+            None => (buf, None),
+        };
+        let room = limit.saturating_sub(line.len());
+        cut |= chunk.len() > room;
+        line.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        let used = done.unwrap_or(buf.len());
+        reader.consume(used);
+        if done.is_some() {
+            return Ok(Some(cut));
+        }
+    }
+}
+
+fn forward_lines<R: std::io::Read + Send + 'static>(
+    pipe: Option<R>,
+    stream: &'static str,
+    run_id: String,
+    emit: EmitFn,
+) -> std::thread::JoinHandle<()> {
+    let limit = super::settings::script_output_limit();
+    std::thread::spawn(move || {
+        let Some(pipe) = pipe else { return };
+        let mut reader = BufReader::new(pipe);
+        let mut line = Vec::new();
+        while let Ok(Some(cut)) = read_capped_line(&mut reader, &mut line, limit) {
+            let text = String::from_utf8_lossy(&line);
+            let mut payload = json!({"run_id": run_id, "stream": stream, "line": text});
+            if cut {
+                payload["truncated"] = json!(true);
+            }
+            emit("script-output", payload);
+        }
+    })
+}
+
+fn script_output_dir() -> PathBuf {
+    scripts_path("script_output")
+}
+
+struct CapturedOutput {
+    status: std::process::ExitStatus,
+    stdout: String,
+    stderr: String,
+    truncated: bool,
+}
+
+fn capture_stream<R: std::io::Read + Send + 'static>(
+    pipe: Option<R>,
+    limit: usize,
+    spool: Arc<Mutex<fs::File>>,
+) -> std::thread::JoinHandle<(Vec<u8>, bool)> {
+    std::thread::spawn(move || {
+        let mut kept = Vec::new();
+        let mut truncated = false;
+        let Some(mut pipe) = pipe else { return (kept, truncated) };
+        let mut chunk = [0u8; 8192];
+        loop {
+            let n = match pipe.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            };
+            if let Ok(mut file) = spool.lock() {
+                let _ = file.write_all(&chunk[..n]);
+            }
+            let room = limit.saturating_sub(kept.len());
+            truncated |= n > room;
+            kept.extend_from_slice(&chunk[..n.min(room)]);
+        }
+        (kept, truncated)
+    })
+}
+
+fn run_captured(
+    cmd: &mut Command,
+    stdin: Option<Vec<u8>>,
+    limits: (usize, usize),
+    spool: &std::path::Path,
+) -> std::io::Result<CapturedOutput> {
+    use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+    if let Some(dir) = spool.parent() {
+        fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    }
+    let file = fs::OpenOptions::new().create(true).append(true).mode(0o600).open(spool)?;
+    let file = Arc::new(Mutex::new(file));
+    let mut child = cmd.stdin(stdin_for(&stdin)).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    feed_stdin(&mut child, stdin);
+    let stdout = capture_stream(child.stdout.take(), limits.0, file.clone());
+    let stderr = capture_stream(child.stderr.take(), limits.1, file);
+    let status = child.wait()?;
+    let (stdout, stdout_cut) = stdout.join().unwrap_or_default();
+    let (stderr, stderr_cut) = stderr.join().unwrap_or_default();
+    Ok(CapturedOutput {
+        status,
+        stdout: String::from_utf8_lossy(&stdout).to_string(),
+        stderr: String::from_utf8_lossy(&stderr).to_string(),
+        truncated: stdout_cut || stderr_cut,
+    })
+}
+
+fn check_stdin(stdin: Option<String>) -> Result<Option<Vec<u8>>, String> {
+    match stdin {
+        Some(input) if input.len() > STDIN_MAX_BYTES => {
+            Err(format!("Input is larger than the {} KB limit", STDIN_MAX_BYTES / 1024))
+        }
+        input => Ok(input.map(String::into_bytes)),
+    }
+}
+
+fn feed_stdin(child: &mut Child, input: Option<Vec<u8>>) {
+    if let (Some(mut pipe), Some(input)) = (child.stdin.take(), input) {
+        std::thread::spawn(move || {
+            let _ = pipe.write_all(&input);
+        });
+    }
+}
+
+fn stdin_for(input: &Option<Vec<u8>>) -> Stdio {
+    if input.is_some() { Stdio::piped() } else { Stdio::null() }
+}
+
+fn new_run_id() -> String {
+    format!("run_{}_{}", chrono::Utc::now().timestamp_millis(), NEXT_RUN.fetch_add(1, Ordering::Relaxed))
+}
+
+fn cancelled_runs() -> &'static Mutex<HashSet<String>> {
+    CANCELLED_RUNS.get_or_init(Default::default)
+}
+
+fn start_script_run(run_id: &str, mut cmd: Command, stdin: Option<Vec<u8>>, emit: EmitFn) -> Result<(), String> {
+    let run_id = run_id.to_string();
+    let mut child = cmd
+        .stdin(stdin_for(&stdin))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .map_err(|e| format!("Failed to start script: {}", e))?;
+    feed_stdin(&mut child, stdin);
+
+    let readers = [
+        forward_lines(child.stdout.take(), "stdout", run_id.clone(), emit.clone()),
+        forward_lines(child.stderr.take(), "stderr", run_id.clone(), emit.clone()),
+    ];
+    script_runs().lock().map_err(|e| e.to_string())?.insert(run_id.clone(), child);
+
+    let id = run_id.clone();
+    std::thread::spawn(move || {
+        // The child stays in the map while it runs so cancel_script_run can reach it.
+        let status = loop {
+            let finished = match script_runs().lock() {
+                Ok(mut runs) => match runs.get_mut(&id).map(|child| child.try_wait()) {
+                    Some(Ok(None)) => None,
+                    Some(Ok(Some(status))) => {
+                        runs.remove(&id);
+                        Some(Some(status))
+                    }
+                    Some(Err(_)) | None => {
+                        runs.remove(&id);
+                        Some(None)
+                    }
+                },
+                Err(_) => Some(None),
+            };
+            if let Some(status) = finished {
+                break status;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        };
+
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let cancelled = cancelled_runs().lock().is_ok_and(|mut ids| ids.remove(&id));
+        emit(
+            "script-finished",
+            json!({
+                "run_id": id,
+                "success": status.is_some_and(|s| s.success()),
+                "exit_code": status.and_then(|s| s.code()),
+                "signal": status.and_then(|s| s.signal()),
+                "cancelled": cancelled,
+            }),
+        );
+    });
+
+    Ok(())
+}
+
+struct ChainRun {
+    run_id: String,
+    root_id: String,
+    scripts: Vec<CustomScript>,
+    args: Option<HashMap<String, String>>,
+    started_at: chrono::DateTime<chrono::Local>,
+    state: Mutex<ChainState>,
+    emit: EmitFn,
+    notify: NotifyFn,
+}
+
+#[derive(Default)]
+struct ChainState {
+    record: ScriptRunRecord,
+    visited: Vec<String>,
+    secrets: Vec<String>,
+    stdin: Option<Vec<u8>>,
+}
+
+impl ChainRun {
+    fn start_step(self: &Arc<Self>, script: CustomScript, values: HashMap<String, String>) -> Result<String, String> {
+        let PreparedRun { cmd, display, temp } = prepare_run(&script, &values)?;
+        let (step, stdin) = {
+            let mut state = self.state.lock().map_err(|e| e.to_string())?;
+            let step = state.visited.len();
+            state.visited.push(script.id.clone());
+            state.secrets.extend(password_values(&script, &values));
+            if step == 0 {
+                state.record.resolved_command = display.clone();
+            } else {
+                state.record.stdout.push_str(&format!("==> {}\n", script.name));
+            }
+            (step, state.stdin.take())
+        };
+        if step > 0 {
+            (self.emit)(
+                "script-step",
+                json!({
+                    "run_id": self.run_id,
+                    "step": step,
+                    "script_id": script.id,
+                    "name": script.name,
+                    "resolved_command": display,
+                    "elevation": elevation_method(script.requires_sudo),
+                }),
+            );
+        }
+
+        let chain = self.clone();
+        let temp = Mutex::new(temp);
+        let step_started = chrono::Local::now();
+        let step_display = display.clone();
+        let emit: EmitFn = Arc::new(move |event: &str, mut payload: serde_json::Value| match event {
+            "script-output" => {
+                if let Ok(mut state) = chain.state.lock() {
+                    let record = &mut state.record;
+                    let buffer = if payload["stream"] == "stderr" { &mut record.stderr } else { &mut record.stdout };
+                    buffer.push_str(payload["line"].as_str().unwrap_or(""));
+                    buffer.push('\n');
+                    if buffer.len() > HISTORY_MAX_OUTPUT * 2 {
+                        let (kept, _) = truncate_output(buffer);
+                        *buffer = kept;
+                        record.truncated = true;
+                    }
+                }
+                payload["step"] = json!(step);
+                (chain.emit)(event, payload);
+            }
+            "script-finished" => {
+                // The process is gone, so the body file can go too.
+                if let Ok(mut temp) = temp.lock() {
+                    temp.take();
+                }
+                let duration_ms = (chrono::Local::now() - step_started).num_milliseconds().max(0) as u64;
+                chain.step_finished(&script, &step_display, duration_ms, payload);
+            }
+            _ => (chain.emit)(event, payload),
+        });
+        start_script_run(&self.run_id, cmd, stdin, emit)?;
+        Ok(display)
+    }
+
+    fn step_finished(self: &Arc<Self>, script: &CustomScript, display: &str, duration_ms: u64, payload: serde_json::Value) {
+        let success = payload["success"].as_bool().unwrap_or(false);
+        let visited = match self.state.lock() {
+            Ok(mut state) => {
+                let exit_code = payload["exit_code"].as_i64().map(|c| c as i32);
+                state.record.success = success;
+                state.record.exit_code = exit_code;
+                state.record.steps.push(ChainStepRecord {
+                    script_id: script.id.clone(),
+                    name: script.name.clone(),
+                    success,
+                    exit_code,
+                    duration_ms,
+                    resolved_command: display.to_string(),
+                });
+                state.visited.clone()
+            }
+            Err(_) => return,
+        };
+
+        if payload["cancelled"].as_bool() == Some(true) {
+            return self.finish(&payload, None);
+        }
+        let chain_error = match next_in_chain(&self.scripts, script, success, &visited) {
+            Ok(None) => None,
+            Ok(Some(next)) => match validate_args(&next.prompts, self.args.as_ref()) {
+                Ok(values) => match self.start_step(next.clone(), values) {
+                    Ok(_) => return,
+                    Err(e) => Some(format!("Chain stopped before {}: {}", next.name, e)),
+                },
+                Err(e) => Some(format!("Chain stopped before {}: {}", next.name, e)),
+            },
+            Err(e) => Some(e),
+        };
+        self.finish(&payload, chain_error);
+    }
+
+    fn finish(&self, last: &serde_json::Value, chain_error: Option<String>) {
+        let Ok(mut state) = self.state.lock() else { return };
+        let mut record = std::mem::take(&mut state.record);
+        record.duration_ms = (chrono::Local::now() - self.started_at).num_milliseconds().max(0) as u64;
+        record.chain_error = chain_error;
+        if record.steps.len() == 1 {
+            record.steps.clear();
+        }
+        // Nobody needs telling about a run they stopped themselves.
+        let notified = last["cancelled"].as_bool() != Some(true)
+            && self.scripts.iter().find(|s| s.id == self.root_id)
+                .is_some_and(|root| notify_completion(root, &record, &self.notify));
+        (self.emit)(
+            "script-finished",
+            json!({
+                "run_id": self.run_id,
+                "success": record.success,
+                "exit_code": record.exit_code,
+                "signal": last["signal"],
+                "cancelled": last["cancelled"],
+                "steps": record.steps,
+                "chain_error": record.chain_error,
+                "notified": notified,
+            }),
+        );
+        record_run(&self.root_id, record, &state.secrets);
+    }
+}
+
+#[tauri::command]
+pub fn run_script_streaming(
+    app: tauri::AppHandle,
+    id: String,
+    args: Option<HashMap<String, String>>,
+    stdin: Option<String>,
+) -> Result<serde_json::Value, GantryError> {
+    let stdin = check_stdin(stdin)?;
+    let config = load_config()?;
+    let script = config.scripts.iter().find(|s| s.id == id)
+        .ok_or_else(|| GantryError::NotFound("Script not found".into()))?.clone();
+    let values = validate_args(&script.prompts, args.as_ref())?;
+
+    let notify = desktop_notifier(app.clone());
+    let emit: EmitFn = Arc::new(move |event: &str, payload: serde_json::Value| {
+        let _ = app.emit(event, payload);
+    });
+    let elevation = elevation_method(script.requires_sudo);
+    let path = effective_path(&script);
+    let missing = preflight_missing(&script, &values, path.as_deref().unwrap_or(""));
+    if !missing.is_empty() {
+        return Ok(json!({
+            "run_id": null,
+            "resolved_command": plan_run(&script, &values, "").display,
+            "elevation": elevation,
+            "effective_path": path,
+            "preflight": preflight_error(&script, &missing),
+        }));
+    }
+    let run_id = new_run_id();
+    let display = start_chain(run_id.clone(), config.scripts, script, values, args, stdin, emit, notify)?;
+    Ok(json!({
+        "run_id": run_id,
+        "resolved_command": display,
+        "elevation": elevation,
+        "effective_path": path,
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_chain(
+    run_id: String,
+    scripts: Vec<CustomScript>,
+    script: CustomScript,
+    values: HashMap<String, String>,
+    args: Option<HashMap<String, String>>,
+    stdin: Option<Vec<u8>>,
+    emit: EmitFn,
+    notify: NotifyFn,
+) -> Result<String, String> {
+    let chain = Arc::new(ChainRun {
+        run_id,
+        root_id: script.id.clone(),
+        scripts,
+        args,
+        started_at: chrono::Local::now(),
+        state: Mutex::new(ChainState {
+            record: ScriptRunRecord { started_at: chrono::Local::now().to_rfc3339(), ..Default::default() },
+            stdin,
+            ..Default::default()
+        }),
+        emit,
+        notify,
+    });
+    chain.start_step(script, values)
+}
+
+#[tauri::command]
+pub fn cancel_script_run(run_id: String) -> Result<serde_json::Value, GantryError> {
+    let pid = script_runs()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(&run_id)
+        .map(|child| child.id())
+        .ok_or_else(|| GantryError::NotFound("Script run not found or already finished".into()))?;
+    signal_group(pid, libc::SIGTERM)?;
+    if let Ok(mut cancelled) = cancelled_runs().lock() {
+        cancelled.insert(run_id.clone());
+    }
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(CANCEL_GRACE_SECS));
+        let still_running = script_runs().lock().is_ok_and(|runs| runs.get(&run_id).is_some_and(|c| c.id() == pid));
+        if still_running {
+            let _ = signal_group(pid, libc::SIGKILL);
+        }
+    });
+    Ok(json!({"success": true}))
+}
+
+pub(crate) fn kill_running_scripts() {
+    if let Ok(mut runs) = script_runs().lock() {
+        for (_, mut child) in runs.drain() {
+            let _ = signal_group(child.id(), libc::SIGKILL);
+            let _ = child.wait();
+        }
+    }
+    // Body files of the runs just killed; their waiter threads may not get
+    // to clean up before the process exits.
+    let prefix = format!("script-{}-", std::process::id());
+    if let Ok(entries) = fs::read_dir(script_tmp_dir()) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+fn history_dir() -> PathBuf {
+    scripts_path("script_history")
+}
+
+// Ids come from the frontend, so anything that could escape the directory is rejected.
+fn history_path(dir: &std::path::Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("Invalid script id".to_string());
+    }
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+fn load_history(path: &std::path::Path) -> Vec<ScriptRunRecord> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn truncate_output(output: &str) -> (String, bool) {
+    if output.len() <= HISTORY_MAX_OUTPUT {
+        return (output.to_string(), false);
+    }
+    let mut start = output.len() - HISTORY_MAX_OUTPUT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    if let Some(newline) = output[start..].find('\n') {
+        start += newline + 1;
+    }
+    (output[start..].to_string(), true)
+}
+
+fn password_values(script: &CustomScript, values: &HashMap<String, String>) -> Vec<String> {
+    script
+        .prompts
+        .iter()
+        .filter(|p| p.kind == PromptKind::Password)
+        .filter_map(|p| values.get(&p.variable))
+        .filter(|v| !v.is_empty())
+        .cloned()
+        .collect()
+}
+
+fn append_history(path: &std::path::Path, mut record: ScriptRunRecord, secrets: &[String]) -> Result<(), String> {
+    for secret in secrets {
+        record.stdout = record.stdout.replace(secret.as_str(), "********");
+        record.stderr = record.stderr.replace(secret.as_str(), "********");
+    }
+    let (stdout, stdout_cut) = truncate_output(&record.stdout);
+    let (stderr, stderr_cut) = truncate_output(&record.stderr);
+    record.stdout = stdout;
+    record.stderr = stderr;
+    record.truncated |= stdout_cut || stderr_cut;
+
+    let mut history = load_history(path);
+    history.insert(0, record);
+    history.truncate(HISTORY_MAX_RUNS);
+    let mut content = serde_json::to_string(&history).map_err(|e| e.to_string())?;
+    while content.len() > HISTORY_MAX_FILE && history.len() > 1 {
+        history.pop();
+        content = serde_json::to_string(&history).map_err(|e| e.to_string())?;
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+fn record_run(script_id: &str, record: ScriptRunRecord, secrets: &[String]) {
+    if let Ok(path) = history_path(&history_dir(), script_id) {
+        let _ = append_history(&path, record, secrets);
+    }
+}
+
+#[tauri::command]
+pub fn get_script_history(id: String, limit: Option<usize>) -> Result<serde_json::Value, GantryError> {
+    let mut history = load_history(&history_path(&history_dir(), &id)?);
+    if let Some(limit) = limit {
+        history.truncate(limit);
+    }
+    Ok(json!(history))
+}
+
+#[tauri::command]
+pub fn clear_script_history(id: String) -> Result<serde_json::Value, GantryError> {
+    let path = history_path(&history_dir(), &id)?;
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(json!({"success": true}))
 }
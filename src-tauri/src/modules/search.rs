@@ -0,0 +1,283 @@
+use super::error::GantryError;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+const SOURCE_LIMIT: usize = 8;
+
+#[derive(Debug, Default)]
+struct Candidate {
+    id: String,
+    title: String,
+    detail: Option<String>,
+    section: Option<&'static str>,
+    extra: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct SearchHit {
+    id: String,
+    title: String,
+    detail: Option<String>,
+    section: Option<&'static str>,
+}
+
+fn text(value: &Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+fn score(candidate: &Candidate, query: &str) -> Option<u8> {
+    let title = candidate.title.to_lowercase();
+    if title == query {
+        Some(0)
+    } else if title.starts_with(query) {
+        Some(1)
+    } else if title.contains(query) {
+        Some(2)
+    } else if candidate
+        .detail
+        .iter()
+        .chain(&candidate.extra)
+        .any(|t| t.to_lowercase().contains(query))
+    {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+fn rank(candidates: Vec<Candidate>, query: &str, limit: usize) -> (Vec<SearchHit>, bool) {
+    let mut matched: Vec<(u8, Candidate)> = candidates
+        .into_iter()
+        .filter_map(|c| Some((score(&c, query)?, c)))
+        .collect();
+    matched.sort_by(|(a, ca), (b, cb)| a.cmp(b).then_with(|| ca.title.to_lowercase().cmp(&cb.title.to_lowercase())));
+    let more = matched.len() > limit;
+    let hits = matched
+        .into_iter()
+        .take(limit)
+        .map(|(_, c)| SearchHit { id: c.id, title: c.title, detail: c.detail, section: c.section })
+        .collect();
+    (hits, more)
+}
+
+fn process_candidates() -> Result<Vec<Candidate>, String> {
+    let groups = super::processes::list_processes(None)?;
+    Ok(groups
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|group| {
+            let name = text(&group["name"])?;
+            let processes = group["processes"].as_array().cloned().unwrap_or_default();
+            let extra = processes
+                .iter()
+                .flat_map(|p| [text(&p["name"]), text(&p["exe"])])
+                .flatten()
+                .collect();
+            Some(Candidate {
+                id: name.clone(),
+                detail: processes.first().and_then(|p| text(&p["exe"])),
+                title: name,
+                extra,
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+fn service_candidates() -> Result<Vec<Candidate>, String> {
+    let services = super::services::list_services(None)?;
+    Ok(services
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| {
+            let name = text(&s["name"])?;
+            let user = s["is_user_service"].as_bool().unwrap_or(false);
+            Some(Candidate {
+                id: format!("{}-{}", if user { "user" } else { "system" }, name),
+                title: name,
+                detail: text(&s["description"]),
+                section: Some(if user { "user" } else { "system" }),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+fn block_candidates(devices: &[Value], out: &mut Vec<Candidate>) {
+    for device in devices {
+        if let Some(name) = text(&device["name"]) {
+            let extra = ["model", "vendor", "label", "mountpoint", "serial", "fstype"]
+                .iter()
+                .filter_map(|key| text(&device[*key]))
+                .collect();
+            out.push(Candidate {
+                id: format!("block:{}", name),
+                detail: text(&device["model"]).or_else(|| text(&device["mountpoint"])),
+                title: name,
+                section: Some("storage"),
+                extra,
+            });
+        }
+        if let Some(children) = device["children"].as_array() {
+            block_candidates(children, out);
+        }
+    }
+}
+
+fn device_candidates() -> Result<Vec<Candidate>, String> {
+    let mut out = Vec::new();
+    let blocks = super::devices::list_devices()?;
+    block_candidates(blocks["blockdevices"].as_array().map(Vec::as_slice).unwrap_or(&[]), &mut out);
+    for usb in super::devices::list_usb_devices().unwrap_or_default() {
+        let Some(name) = text(&usb["name"]) else { continue };
+        out.push(Candidate {
+            id: format!("usb:{}:{}", usb["bus"].as_str().unwrap_or(""), usb["device"].as_str().unwrap_or("")),
+            title: name,
+            detail: text(&usb["device_type"]),
+            section: Some("usb"),
+            extra: [text(&usb["vendor_id"]), text(&usb["product_id"])].into_iter().flatten().collect(),
+        });
+    }
+    for pci in super::devices::list_pci_devices().unwrap_or_default() {
+        let Some(name) = text(&pci["name"]) else { continue };
+        out.push(Candidate {
+            id: format!("pci:{}", pci["slot"].as_str().unwrap_or("")),
+            title: name,
+            detail: text(&pci["vendor"]),
+            section: Some("pci"),
+            extra: [text(&pci["category"]), text(&pci["slot"])].into_iter().flatten().collect(),
+        });
+    }
+    for net in super::devices::list_network_devices().unwrap_or_default() {
+        let Some(name) = text(&net["name"]) else { continue };
+        out.push(Candidate {
+            id: format!("net:{}", name),
+            title: name,
+            detail: text(&net["device_type"]),
+            section: Some("network"),
+            extra: text(&net["mac_address"]).into_iter().collect(),
+        });
+    }
+    Ok(out)
+}
+
+fn script_candidates() -> Result<Vec<Candidate>, String> {
+    let scripts = super::scripts::list_scripts(None, None)?;
+    Ok(scripts
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| {
+            let mut extra: Vec<String> = [text(&s["command"]), text(&s["category"])].into_iter().flatten().collect();
+            extra.extend(s["tags"].as_array().into_iter().flatten().filter_map(text));
+            Some(Candidate {
+                id: text(&s["id"])?,
+                title: text(&s["name"])?,
+                detail: text(&s["description"]),
+                extra,
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+fn repo_candidates() -> Result<Vec<Candidate>, String> {
+    let repos = super::config::list_apt_repos(None)?;
+    Ok(repos
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|r| {
+            Some(Candidate {
+                id: text(&r["id"])?,
+                title: text(&r["uris"])?,
+                detail: text(&r["file_path"]),
+                extra: ["suites", "components", "original_line"].iter().filter_map(|key| text(&r[*key])).collect(),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+fn startup_candidates() -> Result<Vec<Candidate>, String> {
+    let apps = super::config::list_startup_apps()?;
+    Ok(apps
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|app| {
+            let file = text(&app["file"])?;
+            Some(Candidate {
+                id: text(&app["file_path"]).unwrap_or_else(|| file.clone()),
+                title: text(&app["name"]).unwrap_or_else(|| file.clone()),
+                detail: text(&app["exec"]),
+                extra: [Some(file), text(&app["comment"])].into_iter().flatten().collect(),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn global_search(query: String) -> Result<Value, GantryError> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(json!({"query": query, "groups": []}));
+    }
+    let run = |source: fn() -> Result<Vec<Candidate>, String>| tauri::async_runtime::spawn_blocking(source);
+    let (processes, services, devices, scripts, repos, startup) = tokio::join!(
+        run(process_candidates),
+        run(service_candidates),
+        run(device_candidates),
+        run(script_candidates),
+        run(repo_candidates),
+        run(startup_candidates),
+    );
+    let sources = [
+        ("processes", processes),
+        ("services", services),
+        ("devices", devices),
+        ("scripts", scripts),
+        ("repositories", repos),
+        ("startup", startup),
+    ];
+    let groups: Vec<Value> = sources
+        .into_iter()
+        .map(|(page, found)| {
+            let found = found.map_err(|e| e.to_string()).and_then(|r| r);
+            let error = found.as_ref().err().cloned();
+            let (hits, more) = rank(found.unwrap_or_default(), &query, SOURCE_LIMIT);
+            json!({"page": page, "hits": hits, "more": more, "error": error})
+        })
+        .collect();
+    Ok(json!({"query": query, "groups": groups}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(title: &str, detail: &str) -> Candidate {
+        Candidate { id: title.to_string(), title: title.to_string(), detail: Some(detail.to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn test_rank_prefers_title_matches() {
+        let candidates = vec![
+            candidate("sshd-keygen", "OpenSSH key generation"),
+            candidate("cups", "uses ssh for remote printers"),
+            candidate("ssh", "OpenBSD Secure Shell server"),
+            candidate("openssh-agent", "Agent"),
+            candidate("nginx", "web server"),
+        ];
+        let (hits, more) = rank(candidates, "ssh", 3);
+        let titles: Vec<&str> = hits.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(titles, ["ssh", "sshd-keygen", "openssh-agent"]);
+        assert!(more, "cups matched on its description but didn't fit");
+
+        let (hits, more) = rank(vec![candidate("nginx", "web server")], "ssh", 3);
+        assert!(hits.is_empty() && !more);
+    }
+}
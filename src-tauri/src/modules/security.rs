@@ -0,0 +1,226 @@
+use serde_json::json;
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+#[cfg(target_os = "linux")]
+fn tpm_vendor_name(code: &str) -> Option<&'static str> {
+    let name = match code.trim() {
+        "IFX" => "Infineon",
+        "INTC" => "Intel",
+        "NTC" => "Nuvoton",
+        "STM" => "STMicroelectronics",
+        "AMD" => "AMD",
+        "ATML" => "Atmel",
+        "BRCM" => "Broadcom",
+        "MSFT" => "Microsoft",
+        "NTZ" => "Nationz",
+        "GOOG" => "Google",
+        "IBM" => "IBM",
+        "QCOM" => "Qualcomm",
+        "ROCC" => "Fuzhou Rockchip",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(target_os = "linux")]
+fn run_tpm2_getcap(capability: &str) -> Option<String> {
+    let output = Command::new("tpm2_getcap").arg(capability).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn parse_tpm2_manufacturer(output: &str) -> Option<String> {
+    let mut in_manufacturer = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("TPM2_PT_MANUFACTURER:") {
+            in_manufacturer = true;
+        } else if in_manufacturer {
+            if let Some(value) = trimmed.strip_prefix("value:") {
+                return Some(value.trim().trim_matches('"').trim().to_string());
+            }
+            if !line.starts_with(' ') && !line.starts_with('\t') {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn parse_tpm2_pcr_banks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (alg, pcrs) = line.trim().strip_prefix("- ")?.split_once(':')?;
+            let pcrs = pcrs.trim().trim_start_matches('[').trim_end_matches(']').trim();
+            if pcrs.is_empty() {
+                None
+            } else {
+                Some(alg.trim().to_string())
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_tpm2_owned(output: &str) -> Option<bool> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("ownerAuthSet:"))
+        .map(|v| v.trim() == "1" || v.trim() == "set")
+}
+
+#[cfg(target_os = "linux")]
+fn parse_tpm12_caps_manufacturer(caps: &str) -> Option<String> {
+    let hex = caps.lines().find_map(|l| l.strip_prefix("Manufacturer:"))?.trim();
+    let value = u32::from_str_radix(hex.trim_start_matches("0x"), 16).ok()?;
+    let code: String = value
+        .to_be_bytes()
+        .iter()
+        .filter(|b| b.is_ascii_graphic())
+        .map(|b| *b as char)
+        .collect();
+    Some(code)
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_tpm_info() -> Result<serde_json::Value, String> {
+    let tpm_path = std::path::Path::new("/sys/class/tpm/tpm0");
+    let tpm2_tools = Command::new("tpm2_getcap").arg("-v").output().is_ok();
+
+    if !tpm_path.exists() {
+        return Ok(json!({
+            "present": false,
+            "version": null,
+            "manufacturer": null,
+            "manufacturer_id": null,
+            "pcr_banks": [],
+            "owned": null,
+            "tpm2_tools": tpm2_tools,
+        }));
+    }
+
+    let read = |rel: &str| {
+        fs::read_to_string(tpm_path.join(rel))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let caps = read("device/caps").or_else(|| read("caps"));
+    let version = match read("tpm_version_major").as_deref() {
+        Some("2") => Some("2.0"),
+        Some("1") => Some("1.2"),
+        _ if caps.is_some() => Some("1.2"),
+        _ if fs::metadata("/dev/tpmrm0").is_ok() => Some("2.0"),
+        _ => None,
+    };
+
+    // Kernels since 5.12 expose each allocated PCR bank as a pcr-<alg> directory.
+    let mut pcr_banks: Vec<String> = fs::read_dir(tpm_path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_string_lossy().strip_prefix("pcr-").map(|a| a.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut manufacturer_id = caps.as_deref().and_then(parse_tpm12_caps_manufacturer);
+    let mut owned = read("device/owned").or_else(|| read("owned")).map(|v| v == "1");
+
+    if version == Some("2.0") && tpm2_tools {
+        if manufacturer_id.is_none() {
+            manufacturer_id = run_tpm2_getcap("properties-fixed").and_then(|o| parse_tpm2_manufacturer(&o));
+        }
+        if pcr_banks.is_empty() {
+            pcr_banks = run_tpm2_getcap("pcrs").map(|o| parse_tpm2_pcr_banks(&o)).unwrap_or_default();
+        }
+        if owned.is_none() {
+            owned = run_tpm2_getcap("properties-variable").and_then(|o| parse_tpm2_owned(&o));
+        }
+    }
+    pcr_banks.sort();
+
+    Ok(json!({
+        "present": true,
+        "version": version,
+        "manufacturer": manufacturer_id.as_deref().and_then(tpm_vendor_name),
+        "manufacturer_id": manufacturer_id,
+        "pcr_banks": pcr_banks,
+        "owned": owned,
+        "tpm2_tools": tpm2_tools,
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_tpm_info() -> Result<serde_json::Value, String> {
+    Ok(json!({
+        "present": false,
+        "version": null,
+        "manufacturer": null,
+        "manufacturer_id": null,
+        "pcr_banks": [],
+        "owned": null,
+        "tpm2_tools": false,
+        "reason": "Macs use the Secure Enclave instead of a TPM",
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_tpm2_manufacturer() {
+        let sample = "TPM2_PT_FAMILY_INDICATOR:\n  raw: 0x322E3000\n  value: \"2.0\"\n\
+                      TPM2_PT_MANUFACTURER:\n  raw: 0x494E5443\n  value: \"INTC\"\n\
+                      TPM2_PT_VENDOR_STRING_1:\n  raw: 0x496E7465\n  value: \"Inte\"\n";
+        let id = parse_tpm2_manufacturer(sample);
+        assert_eq!(id.as_deref(), Some("INTC"));
+        assert_eq!(tpm_vendor_name("INTC"), Some("Intel"));
+        assert_eq!(parse_tpm2_manufacturer("TPM2_PT_LEVEL:\n  raw: 0x0\n"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_tpm2_pcr_banks() {
+        let sample = "selected-pcrs:\n  - sha1: [ ]\n  - sha256: [ 0, 1, 2, 3 ]\n  - sha384: [ 0 ]\n";
+        assert_eq!(parse_tpm2_pcr_banks(sample), vec!["sha256", "sha384"], "empty banks are not enabled");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_tpm2_owned() {
+        assert_eq!(parse_tpm2_owned("TPM2_PT_PERMANENT:\n  ownerAuthSet:              1\n"), Some(true));
+        assert_eq!(parse_tpm2_owned("TPM2_PT_PERMANENT:\n  ownerAuthSet:              0\n"), Some(false));
+        assert_eq!(parse_tpm2_owned(""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_tpm12_caps_manufacturer() {
+        let caps = "Manufacturer: 0x49465800\nTCG version: 1.2\nFirmware version: 3.19\n";
+        assert_eq!(parse_tpm12_caps_manufacturer(caps).as_deref(), Some("IFX"));
+    }
+
+    #[test]
+    fn test_get_tpm_info_structure() {
+        let result = get_tpm_info();
+        assert!(result.is_ok(), "get_tpm_info failed: {:?}", result.err());
+        let data = result.unwrap();
+        assert!(data["present"].as_bool().is_some(), "present should be a bool");
+        assert!(data["pcr_banks"].as_array().is_some(), "pcr_banks should be an array");
+    }
+}
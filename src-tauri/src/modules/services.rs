@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::process::Command;
 use std::collections::HashSet;
+use super::error::GantryError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceInfo {
@@ -115,7 +116,15 @@ fn parse_services_output(stdout: &str, is_user: bool, enabled_services: &HashSet
 
 #[cfg(target_os = "linux")]
 #[tauri::command]
-pub fn list_services() -> Result<serde_json::Value, String> {
+pub fn list_services(if_changed_since: Option<u64>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("services")?;
+    let caps = super::capabilities::capabilities();
+    if !caps.has_tool("systemctl") {
+        return Err(caps.unsupported("Services can't be listed: systemctl isn't installed"));
+    }
+    if !caps.systemd {
+        return Err(caps.unsupported("Services can't be listed: systemd isn't running"));
+    }
     let mut all_services: Vec<ServiceInfo> = Vec::new();
 
     let system_enabled = get_enabled_services(false);
@@ -142,30 +151,48 @@ pub fn list_services() -> Result<serde_json::Value, String> {
     }
 
     all_services.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(json!(all_services))
+    Ok(super::changes::respond(&super::changes::SERVICES, json!(all_services), if_changed_since))
 }
 
 #[cfg(target_os = "linux")]
-fn run_systemctl(action: &str, name: &str, is_user: bool) -> Result<serde_json::Value, String> {
-    let service = format!("{}.service", name);
-    let output = if is_user {
-        Command::new("systemctl")
-            .args(["--user", action, &service])
-            .output()
-    } else {
-        Command::new("pkexec")
-            .args(["systemctl", action, &service])
+pub(crate) fn failed_service_count() -> Option<usize> {
+    let count = |user: bool| -> Option<usize> {
+        let mut cmd = Command::new("systemctl");
+        if user {
+            cmd.arg("--user");
+        }
+        let output = cmd
+            .args(["list-units", "--type=service", "--state=failed", "--no-pager", "--plain", "--no-legend"])
             .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).lines().filter(|l| !l.trim().is_empty()).count())
     };
+    let system = count(false)?;
+    Some(system + count(true).unwrap_or(0))
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn failed_service_count() -> Option<usize> {
+    None
+}
 
-    let output = output.map_err(|e| e.to_string())?;
-    let success = output.status.success();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+#[cfg(target_os = "linux")]
+pub(crate) fn run_systemctl(action: &str, name: &str, is_user: bool) -> Result<serde_json::Value, GantryError> {
+    let service = format!("{}.service", name);
+    if !is_user {
+        super::admin::systemctl(action, &service)?;
+        return Ok(json!({"success": true, "error": ""}));
+    }
 
-    Ok(json!({
-        "success": success,
-        "error": if success { "" } else { &stderr }
-    }))
+    let output = Command::new("systemctl")
+        .args(["--user", action, &service])
+        .output()
+        .map_err(|e| GantryError::spawn("systemctl", e))?;
+    if !output.status.success() {
+        return Err(GantryError::from_output("systemctl", &output));
+    }
+    Ok(json!({"success": true, "error": ""}))
 }
 
 #[cfg(target_os = "macos")]
@@ -200,7 +227,8 @@ fn plist_is_disabled(path: &std::path::Path) -> bool {
 
 #[cfg(target_os = "macos")]
 #[tauri::command]
-pub fn list_services() -> Result<serde_json::Value, String> {
+pub fn list_services(if_changed_since: Option<u64>) -> Result<serde_json::Value, GantryError> {
+    super::settings::require_module("services")?;
     let mut label_to_path: std::collections::HashMap<String, (std::path::PathBuf, bool)> =
         std::collections::HashMap::new();
 
@@ -228,7 +256,7 @@ pub fn list_services() -> Result<serde_json::Value, String> {
     let output = Command::new("launchctl")
         .arg("list")
         .output()
-        .map_err(|e| format!("Failed to run launchctl: {}", e))?;
+        .map_err(|e| GantryError::spawn("launchctl", e))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
 
@@ -262,11 +290,11 @@ pub fn list_services() -> Result<serde_json::Value, String> {
         .collect();
 
     services.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(json!(services))
+    Ok(super::changes::respond(&super::changes::SERVICES, json!(services), if_changed_since))
 }
 
 #[cfg(target_os = "macos")]
-fn run_launchctl(action: &str, name: &str, is_user: bool) -> Result<serde_json::Value, String> {
+fn run_launchctl(action: &str, name: &str, is_user: bool) -> Result<serde_json::Value, GantryError> {
     let uid = unsafe { libc::getuid() };
     let domain = if is_user {
         format!("gui/{}", uid)
@@ -280,34 +308,30 @@ fn run_launchctl(action: &str, name: &str, is_user: bool) -> Result<serde_json::
         "restart" => vec!["kickstart".into(), "-k".into(), format!("{}/{}", domain, name)],
         "enable" => vec!["enable".into(), format!("{}/{}", domain, name)],
         "disable" => vec!["disable".into(), format!("{}/{}", domain, name)],
-        _ => return Err(format!("Unknown launchctl action: {}", action)),
+        _ => return Err(GantryError::Unsupported(format!("Unknown launchctl action: {}", action))),
     };
 
     let run_privileged = !is_user && action != "enable" && action != "disable";
 
-    let output = if run_privileged {
+    let (tool, output) = if run_privileged {
         let cmd = format!("launchctl {}", args.join(" "));
-        Command::new("osascript")
+        let output = Command::new("osascript")
             .args(["-e", &format!("do shell script \"{}\" with administrator privileges", cmd)])
-            .output()
+            .output();
+        ("osascript", output)
     } else {
-        Command::new("launchctl")
-            .args(&args)
-            .output()
+        ("launchctl", Command::new("launchctl").args(&args).output())
     };
 
-    let output = output.map_err(|e| e.to_string())?;
-    let success = output.status.success();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    Ok(json!({
-        "success": success,
-        "error": if success { "" } else { &stderr }
-    }))
+    let output = output.map_err(|e| GantryError::spawn(tool, e))?;
+    if !output.status.success() {
+        return Err(GantryError::from_output(tool, &output));
+    }
+    Ok(json!({"success": true, "error": ""}))
 }
 
 #[tauri::command]
-pub fn start_service(name: String, is_user: bool) -> Result<serde_json::Value, String> {
+pub fn start_service(name: String, is_user: bool) -> Result<serde_json::Value, GantryError> {
     #[cfg(target_os = "linux")]
     { run_systemctl("start", &name, is_user) }
     #[cfg(target_os = "macos")]
@@ -315,7 +339,7 @@ pub fn start_service(name: String, is_user: bool) -> Result<serde_json::Value, S
 }
 
 #[tauri::command]
-pub fn stop_service(name: String, is_user: bool) -> Result<serde_json::Value, String> {
+pub fn stop_service(name: String, is_user: bool) -> Result<serde_json::Value, GantryError> {
     #[cfg(target_os = "linux")]
     { run_systemctl("stop", &name, is_user) }
     #[cfg(target_os = "macos")]
@@ -323,7 +347,7 @@ pub fn stop_service(name: String, is_user: bool) -> Result<serde_json::Value, St
 }
 
 #[tauri::command]
-pub fn restart_service(name: String, is_user: bool) -> Result<serde_json::Value, String> {
+pub fn restart_service(name: String, is_user: bool) -> Result<serde_json::Value, GantryError> {
     #[cfg(target_os = "linux")]
     { run_systemctl("restart", &name, is_user) }
     #[cfg(target_os = "macos")]
@@ -331,7 +355,7 @@ pub fn restart_service(name: String, is_user: bool) -> Result<serde_json::Value,
 }
 
 #[tauri::command]
-pub fn enable_service(name: String, is_user: bool) -> Result<serde_json::Value, String> {
+pub fn enable_service(name: String, is_user: bool) -> Result<serde_json::Value, GantryError> {
     #[cfg(target_os = "linux")]
     { run_systemctl("enable", &name, is_user) }
     #[cfg(target_os = "macos")]
@@ -339,7 +363,7 @@ pub fn enable_service(name: String, is_user: bool) -> Result<serde_json::Value,
 }
 
 #[tauri::command]
-pub fn disable_service(name: String, is_user: bool) -> Result<serde_json::Value, String> {
+pub fn disable_service(name: String, is_user: bool) -> Result<serde_json::Value, GantryError> {
     #[cfg(target_os = "linux")]
     { run_systemctl("disable", &name, is_user) }
     #[cfg(target_os = "macos")]
@@ -350,16 +374,23 @@ pub fn disable_service(name: String, is_user: bool) -> Result<serde_json::Value,
 mod tests {
     use super::*;
 
+    fn listed_services() -> Option<serde_json::Value> {
+        match list_services(None) {
+            Err(GantryError::Unsupported(_)) => None,
+            result => Some(result.expect("list_services failed")),
+        }
+    }
+
     #[test]
     fn test_list_services_returns_array() {
-        let result = list_services();
-        assert!(result.is_ok(), "list_services failed: {:?}", result.err());
-        assert!(result.unwrap().is_array(), "services should be an array");
+        if let Some(services) = listed_services() {
+            assert!(services.is_array(), "services should be an array");
+        }
     }
 
     #[test]
     fn test_list_services_have_valid_fields() {
-        let services = list_services().unwrap();
+        let Some(services) = listed_services() else { return };
         let arr = services.as_array().unwrap();
         for service in arr {
             assert!(service["name"].as_str().is_some(), "service should have a name");
@@ -374,7 +405,7 @@ mod tests {
 
     #[test]
     fn test_list_services_sorted_by_name() {
-        let services = list_services().unwrap();
+        let Some(services) = listed_services() else { return };
         let arr = services.as_array().unwrap();
         let names: Vec<&str> = arr.iter()
             .filter_map(|s| s["name"].as_str())
@@ -424,6 +455,19 @@ mod tests {
         assert_eq!(result[0].name, "sshd");
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_nonexistent_user_unit_is_not_found() {
+        // Needs a user manager to answer; without one systemctl fails for
+        // other reasons.
+        let bus = Command::new("systemctl").args(["--user", "is-system-running"]).output();
+        if !bus.map_or(false, |o| !o.stdout.is_empty() && o.stderr.is_empty()) {
+            return;
+        }
+        let err = start_service("gantry-no-such-unit-7f3a".to_string(), true).unwrap_err();
+        assert_eq!(err.code(), "not_found", "{}", err);
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_parse_services_output_user_flag() {
@@ -1,22 +1,394 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::cell::Cell;
+use std::sync::{mpsc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::{LogicalSize, PhysicalPosition, PhysicalSize, WebviewWindow, WindowEvent};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+const REFRESH_MIN_MS: u64 = 250;
+const REFRESH_MAX_MS: u64 = 60_000;
+
+const HISTORY_MIN_SECS: u64 = 60;
+const HISTORY_MAX_SECS: u64 = 3600;
+
+const SNAPSHOTS_MAX_KEEP: usize = 500;
+
+const WINDOW_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+const DEFAULT_WINDOW_WIDTH: f64 = 1400.0;
+const DEFAULT_WINDOW_HEIGHT: f64 = 900.0;
+
+// Held around every load and every load-modify-save of settings.yaml, so a save can't write back a
+// copy that was read before another change landed. A thread that already holds it doesn't wait on
+// itself, since loading can log and logging reads settings.
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    static HOLDS_SETTINGS_LOCK: Cell<bool> = const { Cell::new(false) };
+}
+
+const MODULES: &[&str] = &["processes", "services", "firewall", "repos", "startup", "devices", "scripts", "logs"];
+
+const PAGES: &[&str] = &[
+    "resources", "devices", "processes", "services", "firewall", "repositories", "startup", "scripts", "logs", "settings",
+];
+
+const PAGE_PREFERENCES_MAX_KEYS: usize = 32;
+const PAGE_PREFERENCE_KEY_MAX_LEN: usize = 64;
+const PAGE_PREFERENCE_VALUE_MAX_BYTES: usize = 4096;
+
+const LOG_MAX_BYTES_MIN: u64 = 64 * 1024;
+
+const ADMIN_SESSION_MAX_MINUTES: u64 = 60;
+
+// Bump together with a new entry in MIGRATIONS.
+const SETTINGS_VERSION: u32 = 1;
+
+// Entry N takes version N to N+1.
+const MIGRATIONS: &[fn(&mut serde_json::Map<String, serde_json::Value>)] = &[migrate_v0];
+
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl TemperatureUnit {
+    pub fn convert(self, celsius: f64) -> f64 {
+        let value = match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        };
+        (value * 10.0).round() / 10.0
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WidgetPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertSettings {
+    pub enabled: bool,
+    pub desktop_notifications: bool,
+    pub cpu_percent: Option<f64>,
+    pub cpu_sustained_secs: u64,
+    pub memory_percent: Option<f64>,
+    pub temperature_celsius: Option<f64>,
+    pub disk_free_percent: Option<f64>,
+    pub disk_free_percent_by_mount: BTreeMap<String, f64>,
+    pub disk_full_days: Option<f64>,
+    pub cooldown_secs: u64,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        AlertSettings {
+            enabled: false,
+            desktop_notifications: true,
+            cpu_percent: Some(90.0),
+            cpu_sustained_secs: 60,
+            memory_percent: Some(90.0),
+            temperature_celsius: Some(90.0),
+            disk_free_percent: Some(10.0),
+            disk_free_percent_by_mount: BTreeMap::new(),
+            disk_full_days: Some(3.0),
+            cooldown_secs: 900,
+        }
+    }
+}
+
+impl AlertSettings {
+    fn validate(&self) -> Result<(), String> {
+        let percents = [self.cpu_percent, self.memory_percent, self.disk_free_percent]
+            .into_iter()
+            .flatten()
+            .chain(self.disk_free_percent_by_mount.values().copied());
+        for percent in percents {
+            if !(percent > 0.0 && percent <= 100.0) {
+                return Err("Alert percentages must be between 0 and 100".to_string());
+            }
+        }
+        if self.temperature_celsius.is_some_and(|t| !(t > 0.0 && t < 150.0)) {
+            return Err("The temperature alert must be between 0 and 150 °C".to_string());
+        }
+        if self.disk_full_days.is_some_and(|d| !(d > 0.0 && d <= 365.0)) {
+            return Err("The disk full alert must be between 0 and 365 days".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn disk_threshold(&self, mount: &str) -> Option<f64> {
+        self.disk_free_percent_by_mount.get(mount).copied().or(self.disk_free_percent)
+    }
+}
+
+type MonitorRect = (i32, i32, u32, u32);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
+    #[serde(default)]
+    pub version: u32,
     #[serde(default = "default_theme")]
     pub theme: String,
+    #[serde(default = "default_repo_backup_keep")]
+    pub repo_backup_keep: usize,
+    #[serde(default = "default_notifications_enabled")]
+    pub notifications_enabled: bool,
+    #[serde(default = "default_script_output_limit")]
+    pub script_output_limit: usize,
+    #[serde(default = "default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+    #[serde(default = "default_processes_refresh_ms")]
+    pub processes_refresh_ms: u64,
+    #[serde(default = "default_auto_refresh_enabled")]
+    pub auto_refresh_enabled: bool,
+    #[serde(default = "default_history_secs")]
+    pub history_secs: u64,
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+    #[serde(default)]
+    pub window_state: Option<WindowState>,
+    #[serde(default)]
+    pub monitor_widget_position: Option<WidgetPosition>,
+    #[serde(default)]
+    pub enabled_modules: BTreeMap<String, bool>,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    #[serde(default)]
+    pub start_at_login: bool,
+    #[serde(default)]
+    pub start_minimized: bool,
+    #[serde(default = "default_page")]
+    pub default_page: String,
+    #[serde(default)]
+    pub page_preferences: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+    #[serde(default = "default_log_max_bytes")]
+    pub log_max_bytes: u64,
+    #[serde(default = "default_log_keep")]
+    pub log_keep: usize,
+    #[serde(default)]
+    pub log_format: LogFormat,
+    #[serde(default = "default_admin_session_minutes")]
+    pub admin_session_minutes: u64,
+    #[serde(default)]
+    pub global_shortcuts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub metrics_enabled: bool,
+    #[serde(default = "default_metrics_bind")]
+    pub metrics_bind: String,
+    #[serde(default = "default_metrics_port")]
+    pub metrics_port: u16,
+    #[serde(default = "default_snapshots_keep")]
+    pub snapshots_keep: usize,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            version: SETTINGS_VERSION,
+            theme: default_theme(),
+            repo_backup_keep: default_repo_backup_keep(),
+            notifications_enabled: default_notifications_enabled(),
+            script_output_limit: default_script_output_limit(),
+            refresh_interval_ms: default_refresh_interval_ms(),
+            processes_refresh_ms: default_processes_refresh_ms(),
+            auto_refresh_enabled: default_auto_refresh_enabled(),
+            history_secs: default_history_secs(),
+            temperature_unit: TemperatureUnit::default(),
+            window_state: None,
+            monitor_widget_position: None,
+            enabled_modules: BTreeMap::new(),
+            alerts: AlertSettings::default(),
+            start_at_login: false,
+            start_minimized: false,
+            default_page: default_page(),
+            page_preferences: BTreeMap::new(),
+            log_max_bytes: default_log_max_bytes(),
+            log_keep: default_log_keep(),
+            log_format: LogFormat::default(),
+            admin_session_minutes: default_admin_session_minutes(),
+            global_shortcuts: BTreeMap::new(),
+            metrics_enabled: false,
+            metrics_bind: default_metrics_bind(),
+            metrics_port: default_metrics_port(),
+            snapshots_keep: default_snapshots_keep(),
+        }
+    }
+}
+
+impl AppSettings {
+    fn normalized(mut self) -> Self {
+        self.refresh_interval_ms = self.refresh_interval_ms.clamp(REFRESH_MIN_MS, REFRESH_MAX_MS);
+        self.processes_refresh_ms = self.processes_refresh_ms.clamp(REFRESH_MIN_MS, REFRESH_MAX_MS);
+        self.history_secs = self.history_secs.clamp(HISTORY_MIN_SECS, HISTORY_MAX_SECS);
+        self.snapshots_keep = self.snapshots_keep.clamp(1, SNAPSHOTS_MAX_KEEP);
+        self
+    }
+
+    fn module_enabled(&self, module: &str) -> bool {
+        self.enabled_modules.get(module).copied().unwrap_or(true)
+    }
+}
+
+fn module_available(module: &str, capabilities: &serde_json::Value) -> bool {
+    let has_manager = |name: &str| {
+        capabilities["package_managers"]
+            .as_array()
+            .is_some_and(|managers| managers.iter().any(|m| m == name))
+    };
+    match module {
+        "repos" => has_manager("apt") || has_manager("brew"),
+        "services" => matches!(capabilities["init_system"].as_str(), Some("systemd") | Some("launchd")),
+        _ => true,
+    }
+}
+
+fn visible_modules(settings: &AppSettings, capabilities: &serde_json::Value) -> BTreeMap<String, bool> {
+    MODULES
+        .iter()
+        .map(|m| (m.to_string(), settings.module_enabled(m) && module_available(m, capabilities)))
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettingsExport {
+    gantry_settings: u32,
+    settings: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scripts: Option<serde_json::Value>,
+}
+
+fn default_page() -> String {
+    "resources".to_string()
+}
+
+fn check_page_preference(page: &str, key: &str, value: &serde_json::Value) -> Result<(), String> {
+    if !PAGES.contains(&page) {
+        return Err(format!("Unknown page: {}", page));
+    }
+    if key.is_empty() || key.len() > PAGE_PREFERENCE_KEY_MAX_LEN {
+        return Err(format!("Preference names must be 1 to {} characters", PAGE_PREFERENCE_KEY_MAX_LEN));
+    }
+    if value.to_string().len() > PAGE_PREFERENCE_VALUE_MAX_BYTES {
+        return Err(format!("The value of {} is larger than {} bytes", key, PAGE_PREFERENCE_VALUE_MAX_BYTES));
+    }
+    Ok(())
+}
+
+fn validate_page_preferences(preferences: &BTreeMap<String, BTreeMap<String, serde_json::Value>>) -> Result<(), String> {
+    for (page, values) in preferences {
+        if values.len() > PAGE_PREFERENCES_MAX_KEYS {
+            return Err(format!("The {} page has more than {} preferences", page, PAGE_PREFERENCES_MAX_KEYS));
+        }
+        for (key, value) in values {
+            check_page_preference(page, key, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn default_log_max_bytes() -> u64 {
+    5 * 1024 * 1024
+}
+
+fn default_log_keep() -> usize {
+    3
+}
+
+fn default_admin_session_minutes() -> u64 {
+    5
+}
+
+fn default_metrics_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_metrics_port() -> u16 {
+    9839
 }
 
 fn default_theme() -> String {
     "light".to_string()
 }
 
+fn default_repo_backup_keep() -> usize {
+    10
+}
+
+fn default_notifications_enabled() -> bool {
+    true
+}
+
+fn default_script_output_limit() -> usize {
+    1024 * 1024
+}
+
+fn default_refresh_interval_ms() -> u64 {
+    1000
+}
+
+fn default_processes_refresh_ms() -> u64 {
+    5000
+}
+
+fn default_auto_refresh_enabled() -> bool {
+    true
+}
+
+fn default_history_secs() -> u64 {
+    300
+}
+
+fn default_snapshots_keep() -> usize {
+    20
+}
+
+// Tests get a directory of their own so they never read or rewrite the user's settings.
 fn get_settings_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".gantry").join("settings.yaml")
+    if cfg!(test) {
+        return std::env::temp_dir()
+            .join(format!("gantry-settings-test-{}", std::process::id()))
+            .join("settings.yaml");
+    }
+    super::appdata::config_path("settings.yaml")
 }
 
 fn ensure_config_dir() -> Result<(), String> {
@@ -27,34 +399,893 @@ fn ensure_config_dir() -> Result<(), String> {
     Ok(())
 }
 
+struct SettingsGuard(Option<MutexGuard<'static, ()>>);
+
+impl Drop for SettingsGuard {
+    fn drop(&mut self) {
+        if self.0.is_some() {
+            HOLDS_SETTINGS_LOCK.with(|held| held.set(false));
+        }
+    }
+}
+
+fn settings_lock() -> SettingsGuard {
+    if HOLDS_SETTINGS_LOCK.with(Cell::get) {
+        return SettingsGuard(None);
+    }
+    let guard = SETTINGS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    HOLDS_SETTINGS_LOCK.with(|held| held.set(true));
+    SettingsGuard(Some(guard))
+}
+
 fn load_settings() -> Result<AppSettings, String> {
-    let settings_path = get_settings_path();
-    if !settings_path.exists() {
+    let _lock = settings_lock();
+    load_settings_from(&get_settings_path())
+}
+
+fn modify_settings<T>(change: impl FnOnce(&mut AppSettings) -> Result<T, String>) -> Result<T, String> {
+    let _lock = settings_lock();
+    let mut settings = load_settings()?;
+    let result = change(&mut settings)?;
+    ensure_config_dir()?;
+    write_settings(&get_settings_path(), &settings)?;
+    Ok(result)
+}
+
+// Swaps in what `build` makes of the current settings and returns the old and new ones.
+fn replace_settings(
+    build: impl FnOnce(&AppSettings) -> Result<AppSettings, String>,
+) -> Result<(AppSettings, AppSettings), String> {
+    modify_settings(|settings| {
+        let next = build(settings)?;
+        if next.start_at_login != settings.start_at_login {
+            super::config::set_login_item(next.start_at_login)?;
+        }
+        let previous = std::mem::replace(settings, next);
+        Ok((previous, settings.clone()))
+    })
+}
+
+// Older files are upgraded after a copy to settings.yaml.v<N>.bak. One that doesn't parse is moved
+// aside and the defaults used; one from a newer Gantry is left alone.
+fn load_settings_from(path: &Path) -> Result<AppSettings, String> {
+    if !path.exists() {
         return Ok(AppSettings::default());
     }
-    let content = fs::read_to_string(&settings_path).map_err(|e| e.to_string())?;
-    serde_yaml::from_str(&content).map_err(|e| e.to_string())
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut value: serde_json::Value = match serde_yaml::from_str(&content) {
+        Ok(value) => value,
+        Err(e) => return set_aside_corrupt_settings(path, &e.to_string()),
+    };
+    let from = settings_version(&value);
+    if from > SETTINGS_VERSION {
+        return Err(newer_settings_error(from));
+    }
+    let settings = migrate_settings(&mut value)
+        .and_then(|_| serde_json::from_value::<AppSettings>(value).map_err(|e| e.to_string()));
+    let settings = match settings {
+        Ok(settings) => settings,
+        Err(e) => return set_aside_corrupt_settings(path, &e),
+    };
+    if from < SETTINGS_VERSION {
+        fs::copy(path, path.with_extension(format!("yaml.v{}.bak", from))).map_err(|e| e.to_string())?;
+        write_settings(path, &settings)?;
+    }
+    Ok(settings.normalized())
 }
 
-fn save_settings(settings: &AppSettings) -> Result<(), String> {
-    ensure_config_dir()?;
-    let settings_path = get_settings_path();
+fn set_aside_corrupt_settings(path: &Path, error: &str) -> Result<AppSettings, String> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+    let corrupt = path.with_extension(format!("yaml.corrupt-{}", stamp));
+    fs::rename(path, &corrupt).map_err(|e| e.to_string())?;
+    let _ = super::logging::log(
+        "warn",
+        "settings",
+        &format!(
+            "Settings file {} could not be read ({}); moved it to {} and fell back to defaults",
+            path.display(),
+            error,
+            corrupt.display()
+        ),
+    );
+    Ok(AppSettings::default())
+}
+
+fn settings_version(value: &serde_json::Value) -> u32 {
+    value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .map(|v| u32::try_from(v).unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+fn newer_settings_error(version: u32) -> String {
+    format!(
+        "Settings file version {} is newer than this version of Gantry supports ({}); update Gantry to use it",
+        version, SETTINGS_VERSION
+    )
+}
+
+fn migrate_settings(value: &mut serde_json::Value) -> Result<u32, String> {
+    let from = settings_version(value);
+    if from > SETTINGS_VERSION {
+        return Err(newer_settings_error(from));
+    }
+    let fields = value.as_object_mut().ok_or_else(|| "Settings must be a mapping".to_string())?;
+    for migrate in &MIGRATIONS[from as usize..] {
+        migrate(fields);
+    }
+    fields.insert("version".to_string(), json!(SETTINGS_VERSION));
+    Ok(from)
+}
+
+fn migrate_v0(_fields: &mut serde_json::Map<String, serde_json::Value>) {}
+
+fn merge_settings(current: &AppSettings, partial: &serde_json::Value) -> Result<AppSettings, String> {
+    let changes = partial.as_object().ok_or_else(|| "Settings must be an object".to_string())?;
+    let mut merged = json!(current);
+    let fields = merged.as_object_mut().ok_or_else(|| "Settings must be an object".to_string())?;
+    for (key, value) in changes {
+        if key == "version" {
+            return Err("The settings version can't be changed".to_string());
+        }
+        if !fields.contains_key(key) {
+            return Err(format!("Unknown setting: {}", key));
+        }
+        fields.insert(key.clone(), value.clone());
+    }
+    let settings: AppSettings = serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))?;
+    if settings.theme != "light" && settings.theme != "dark" {
+        return Err("Theme must be light or dark".to_string());
+    }
+    if settings.repo_backup_keep == 0 {
+        return Err("At least one backup per file must be kept".to_string());
+    }
+    if settings.script_output_limit < 1024 {
+        return Err("The output limit must be at least 1 KB".to_string());
+    }
+    if settings.log_max_bytes < LOG_MAX_BYTES_MIN {
+        return Err("The log size limit must be at least 64 KB".to_string());
+    }
+    super::shortcuts::validate(&settings.global_shortcuts)?;
+    if settings.metrics_bind.parse::<std::net::IpAddr>().is_err() {
+        return Err(format!("{} is not an IP address to bind to", settings.metrics_bind));
+    }
+    if settings.metrics_port == 0 {
+        return Err("The metrics port must be between 1 and 65535".to_string());
+    }
+    if settings.log_keep == 0 {
+        return Err("At least one rotated log file must be kept".to_string());
+    }
+    if !(1..=ADMIN_SESSION_MAX_MINUTES).contains(&settings.admin_session_minutes) {
+        return Err(format!("Admin sessions must last 1 to {} minutes", ADMIN_SESSION_MAX_MINUTES));
+    }
+    if let Some(module) = settings.enabled_modules.keys().find(|m| !MODULES.contains(&m.as_str())) {
+        return Err(format!("Unknown module: {}", module));
+    }
+    if !PAGES.contains(&settings.default_page.as_str()) {
+        return Err(format!("Unknown page: {}", settings.default_page));
+    }
+    validate_page_preferences(&settings.page_preferences)?;
+    settings.alerts.validate()?;
+    Ok(settings.normalized())
+}
+
+fn parse_settings_export(content: &str) -> Result<(AppSettings, Option<serde_json::Value>), String> {
+    let value: serde_json::Value = serde_yaml::from_str(content).map_err(|e| format!("Not a valid settings file: {}", e))?;
+    let fields = value.as_object().ok_or_else(|| "Not a valid settings file: expected a mapping".to_string())?;
+    let export = if fields.contains_key("gantry_settings") {
+        let export: SettingsExport =
+            serde_json::from_value(value).map_err(|e| format!("Not a valid settings file: {}", e))?;
+        if export.gantry_settings > EXPORT_VERSION {
+            return Err(format!(
+                "Settings file version {} is newer than this version of Gantry supports",
+                export.gantry_settings
+            ));
+        }
+        export
+    } else {
+        SettingsExport { gantry_settings: EXPORT_VERSION, settings: value, scripts: None }
+    };
+    let settings = settings_from_fields(export.settings, &AppSettings::default())?;
+    Ok((settings, export.scripts))
+}
+
+fn settings_from_fields(mut fields: serde_json::Value, base: &AppSettings) -> Result<AppSettings, String> {
+    migrate_settings(&mut fields)?;
+    if let Some(fields) = fields.as_object_mut() {
+        fields.remove("version");
+    }
+    merge_settings(base, &fields)
+}
+
+pub(crate) fn parse_settings_file(content: &str, merge: bool) -> Result<AppSettings, String> {
+    let fields: serde_json::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("Not a valid settings file: {}", e))?;
+    let base = if merge { load_settings()? } else { AppSettings::default() };
+    settings_from_fields(fields, &base)
+}
+
+pub(crate) fn install_settings(app: &tauri::AppHandle, settings: &AppSettings) -> Result<Vec<serde_json::Value>, String> {
+    replace_settings(|_| Ok(settings.clone()))?;
+    let shortcut_failures = super::shortcuts::register_all(app);
+    super::metrics::restart_from_settings();
+    Ok(shortcut_failures)
+}
+
+fn write_settings(path: &Path, settings: &AppSettings) -> Result<(), String> {
     let content = serde_yaml::to_string(settings).map_err(|e| e.to_string())?;
-    let mut file = fs::File::create(&settings_path).map_err(|e| e.to_string())?;
-    file.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_file_name(format!(".settings.yaml.{}.tmp", uuid::Uuid::new_v4()));
+    let written = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.to_string());
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub fn get_settings() -> Result<serde_json::Value, String> {
     let settings = load_settings()?;
+    let mut value = json!(settings);
+    value["visible_modules"] = json!(visible_modules(&settings, &super::system::get_platform_capabilities()));
+    Ok(value)
+}
+
+#[tauri::command]
+pub fn update_settings(app: tauri::AppHandle, partial: serde_json::Value) -> Result<serde_json::Value, String> {
+    let (current, settings) = replace_settings(|current| merge_settings(current, &partial))?;
+    if settings.global_shortcuts != current.global_shortcuts {
+        super::shortcuts::register_all(&app);
+    }
+    let endpoint = |s: &AppSettings| (s.metrics_enabled, s.metrics_bind.clone(), s.metrics_port);
+    if endpoint(&settings) != endpoint(&current) {
+        super::metrics::restart_from_settings();
+    }
+    Ok(json!(settings))
+}
+
+#[tauri::command]
+pub fn export_settings(path: Option<String>, include_scripts: Option<bool>) -> Result<serde_json::Value, String> {
+    let export = SettingsExport {
+        gantry_settings: EXPORT_VERSION,
+        settings: json!(load_settings()?),
+        scripts: if include_scripts.unwrap_or(false) { Some(super::scripts::scripts_snapshot()?) } else { None },
+    };
+    let content = serde_yaml::to_string(&export).map_err(|e| e.to_string())?;
+    let path = match path {
+        Some(path) => super::scripts::expand_home(&path),
+        None => {
+            let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+            let dir = home.join("Downloads");
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            dir.join(format!("gantry-settings-{}.yaml", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+        }
+    };
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(json!({"path": path.to_string_lossy(), "scripts": export.scripts.is_some()}))
+}
+
+#[tauri::command]
+pub fn import_settings(app: tauri::AppHandle, path: String) -> Result<serde_json::Value, String> {
+    let content = fs::read_to_string(super::scripts::expand_home(&path))
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (settings, scripts) = parse_settings_export(&content)?;
+    let previous_scripts = match &scripts {
+        Some(snapshot) => {
+            super::scripts::validate_scripts(snapshot)?;
+            Some(super::scripts::scripts_snapshot()?)
+        }
+        None => None,
+    };
+    let scripts_imported = match &scripts {
+        Some(snapshot) => Some(super::scripts::restore_scripts(snapshot)?),
+        None => None,
+    };
+    let shortcut_failures = install_settings(&app, &settings).inspect_err(|_| {
+        if let Some(previous) = &previous_scripts {
+            let _ = super::scripts::restore_scripts(previous);
+        }
+    })?;
+    Ok(json!({"settings": settings, "scripts_imported": scripts_imported, "shortcut_failures": shortcut_failures}))
+}
+
+#[tauri::command]
+pub fn reset_settings(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    let (_, settings) = replace_settings(|_| Ok(AppSettings::default()))?;
+    super::shortcuts::register_all(&app);
+    super::metrics::restart_from_settings();
     Ok(json!(settings))
 }
 
+#[tauri::command]
+pub fn reset_window_state(window: WebviewWindow) -> Result<serde_json::Value, String> {
+    modify_settings(|settings| {
+        settings.window_state = None;
+        Ok(())
+    })?;
+    let _ = window.unmaximize();
+    window
+        .set_size(LogicalSize::new(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT))
+        .map_err(|e| e.to_string())?;
+    window.center().map_err(|e| e.to_string())?;
+    Ok(json!({"success": true}))
+}
+
 #[tauri::command]
 pub fn set_theme(theme: String) -> Result<serde_json::Value, String> {
-    let mut settings = load_settings()?;
-    settings.theme = theme;
-    save_settings(&settings)?;
+    modify_settings(|settings| {
+        settings.theme = theme;
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_temperature_unit(unit: String) -> Result<serde_json::Value, String> {
+    let unit: TemperatureUnit = serde_json::from_value(json!(unit))
+        .map_err(|_| "Temperature unit must be celsius or fahrenheit".to_string())?;
+    modify_settings(|settings| {
+        settings.temperature_unit = unit;
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn update_alert_settings(partial: serde_json::Value) -> Result<serde_json::Value, String> {
+    let changes = partial.as_object().ok_or_else(|| "Alert settings must be an object".to_string())?;
+    let (_, settings) = replace_settings(|current| {
+        let mut alerts = json!(current.alerts);
+        let fields = alerts.as_object_mut().ok_or_else(|| "Alert settings must be an object".to_string())?;
+        for (key, value) in changes {
+            if !fields.contains_key(key) {
+                return Err(format!("Unknown alert setting: {}", key));
+            }
+            fields.insert(key.clone(), value.clone());
+        }
+        merge_settings(current, &json!({"alerts": alerts}))
+    })?;
+    Ok(json!(settings.alerts))
+}
+
+#[tauri::command]
+pub fn set_start_at_login(enabled: bool) -> Result<serde_json::Value, String> {
+    replace_settings(|current| Ok(AppSettings { start_at_login: enabled, ..current.clone() }))?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_start_minimized(enabled: bool) -> Result<serde_json::Value, String> {
+    modify_settings(|settings| {
+        settings.start_minimized = enabled;
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_page_preference(page: String, key: String, value: serde_json::Value) -> Result<serde_json::Value, String> {
+    check_page_preference(&page, &key, &value)?;
+    modify_settings(|settings| {
+        let values = settings.page_preferences.entry(page.clone()).or_default();
+        if value.is_null() {
+            values.remove(&key);
+        } else {
+            values.insert(key, value);
+        }
+        if values.is_empty() {
+            settings.page_preferences.remove(&page);
+        }
+        validate_page_preferences(&settings.page_preferences)
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_module_enabled(module: String, enabled: bool) -> Result<serde_json::Value, String> {
+    if !MODULES.contains(&module.as_str()) {
+        return Err(format!("Unknown module: {}", module));
+    }
+    modify_settings(|settings| {
+        settings.enabled_modules.insert(module, enabled);
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_repo_backup_keep(keep: usize) -> Result<serde_json::Value, String> {
+    if keep == 0 {
+        return Err("At least one backup per file must be kept".to_string());
+    }
+    modify_settings(|settings| {
+        settings.repo_backup_keep = keep;
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_notifications_enabled(enabled: bool) -> Result<serde_json::Value, String> {
+    modify_settings(|settings| {
+        settings.notifications_enabled = enabled;
+        Ok(())
+    })?;
+    Ok(json!({"success": true}))
+}
+
+#[tauri::command]
+pub fn set_script_output_limit(limit: usize) -> Result<serde_json::Value, String> {
+    if limit < 1024 {
+        return Err("The output limit must be at least 1 KB".to_string());
+    }
+    modify_settings(|settings| {
+        settings.script_output_limit = limit;
+        Ok(())
+    })?;
     Ok(json!({"success": true}))
 }
+
+pub(crate) fn repo_backup_keep() -> usize {
+    load_settings()
+        .map(|s| s.repo_backup_keep)
+        .unwrap_or_else(|_| default_repo_backup_keep())
+        .max(1)
+}
+
+pub(crate) fn notifications_enabled() -> bool {
+    load_settings()
+        .map(|s| s.notifications_enabled)
+        .unwrap_or_else(|_| default_notifications_enabled())
+}
+
+pub(crate) fn require_module(module: &str) -> Result<(), String> {
+    if load_settings().map(|s| s.module_enabled(module)).unwrap_or(true) {
+        Ok(())
+    } else {
+        Err(format!("The {} module is disabled", module))
+    }
+}
+
+pub(crate) fn temperature_unit() -> TemperatureUnit {
+    load_settings().map(|s| s.temperature_unit).unwrap_or_default()
+}
+
+pub(crate) fn alert_settings() -> AlertSettings {
+    load_settings().map(|s| s.alerts).unwrap_or_default()
+}
+
+pub(crate) fn log_rotation() -> (u64, usize) {
+    let settings = load_settings().unwrap_or_default();
+    (settings.log_max_bytes.max(LOG_MAX_BYTES_MIN), settings.log_keep.max(1))
+}
+
+pub(crate) fn admin_session_minutes() -> u64 {
+    load_settings()
+        .map(|s| s.admin_session_minutes)
+        .unwrap_or_else(|_| default_admin_session_minutes())
+        .clamp(1, ADMIN_SESSION_MAX_MINUTES)
+}
+
+pub(crate) fn live_refresh_interval() -> Option<Duration> {
+    let settings = load_settings().unwrap_or_default();
+    settings.auto_refresh_enabled.then(|| Duration::from_millis(settings.refresh_interval_ms))
+}
+
+pub(crate) fn history_retention() -> Duration {
+    Duration::from_secs(load_settings().unwrap_or_default().history_secs)
+}
+
+pub(crate) fn snapshots_keep() -> usize {
+    load_settings().unwrap_or_default().snapshots_keep
+}
+
+pub(crate) fn global_shortcuts() -> BTreeMap<String, String> {
+    load_settings().map(|s| s.global_shortcuts).unwrap_or_default()
+}
+
+pub(crate) fn set_global_shortcuts(bindings: BTreeMap<String, String>) -> Result<(), String> {
+    modify_settings(|settings| {
+        settings.global_shortcuts = bindings;
+        Ok(())
+    })
+}
+
+pub(crate) fn metrics_endpoint() -> (bool, String, u16) {
+    let settings = load_settings().unwrap_or_default();
+    (settings.metrics_enabled, settings.metrics_bind, settings.metrics_port)
+}
+
+pub(crate) fn set_metrics_endpoint(enabled: bool, bind: String, port: u16) -> Result<(), String> {
+    let fields = json!({"metrics_enabled": enabled, "metrics_bind": bind, "metrics_port": port});
+    replace_settings(|current| merge_settings(current, &fields)).map(|_| ())
+}
+
+pub(crate) fn log_format() -> LogFormat {
+    load_settings().map(|s| s.log_format).unwrap_or_default()
+}
+
+pub(crate) fn script_output_limit() -> usize {
+    load_settings()
+        .map(|s| s.script_output_limit)
+        .unwrap_or_else(|_| default_script_output_limit())
+        .max(1024)
+}
+
+fn place_window(state: WindowState, monitors: &[MonitorRect]) -> Option<WindowState> {
+    let centre_x = state.x as i64 + state.width as i64 / 2;
+    let centre_y = state.y as i64 + state.height as i64 / 2;
+    let &(mx, my, mw, mh) = monitors
+        .iter()
+        .find(|&&(mx, my, mw, mh)| {
+            (mx as i64..mx as i64 + mw as i64).contains(&centre_x) && (my as i64..my as i64 + mh as i64).contains(&centre_y)
+        })
+        .or_else(|| monitors.first())?;
+    let width = state.width.clamp(1, mw.max(1));
+    let height = state.height.clamp(1, mh.max(1));
+    Some(WindowState {
+        width,
+        height,
+        x: state.x.clamp(mx, mx.saturating_add(mw.saturating_sub(width) as i32)),
+        y: state.y.clamp(my, my.saturating_add(mh.saturating_sub(height) as i32)),
+        maximized: state.maximized,
+    })
+}
+
+fn monitor_rects(window: &WebviewWindow) -> Vec<MonitorRect> {
+    let primary = window.primary_monitor().ok().flatten();
+    primary
+        .into_iter()
+        .chain(window.available_monitors().unwrap_or_default())
+        .map(|m| (m.position().x, m.position().y, m.size().width, m.size().height))
+        .collect()
+}
+
+pub fn restore_window_state(window: &WebviewWindow) {
+    let settings = load_settings().unwrap_or_default();
+    if let Some(state) = settings.window_state.and_then(|state| place_window(state, &monitor_rects(window))) {
+        let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+        let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+        if state.maximized {
+            let _ = window.maximize();
+        }
+    }
+    // The window starts hidden; a second launch or the global shortcut shows it later.
+    if !settings.start_minimized {
+        let _ = window.show();
+    }
+}
+
+fn current_window_state(window: &WebviewWindow, previous: Option<WindowState>) -> Option<WindowState> {
+    if window.is_minimized().unwrap_or(false) {
+        return None;
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+    if let (true, Some(previous)) = (maximized, previous) {
+        return Some(WindowState { maximized, ..previous });
+    }
+    let size = window.inner_size().ok()?;
+    let position = window.outer_position().ok()?;
+    Some(WindowState { width: size.width, height: size.height, x: position.x, y: position.y, maximized })
+}
+
+fn save_window_state(state: WindowState) {
+    let _ = modify_settings(|settings| {
+        settings.window_state = Some(state);
+        Ok(())
+    });
+}
+
+pub(crate) fn save_window_state_now(window: &WebviewWindow) {
+    let previous = load_settings().ok().and_then(|s| s.window_state);
+    if let Some(state) = current_window_state(window, previous) {
+        save_window_state(state);
+    }
+}
+
+pub fn track_window_state(window: &WebviewWindow) {
+    let (tx, rx) = mpsc::channel::<WindowState>();
+    std::thread::spawn(move || {
+        while let Ok(mut state) = rx.recv() {
+            while let Ok(next) = rx.recv_timeout(WINDOW_SAVE_DELAY) {
+                state = next;
+            }
+            save_window_state(state);
+        }
+    });
+
+    let tracked = window.clone();
+    let last = Mutex::new(load_settings().ok().and_then(|s| s.window_state));
+    window.on_window_event(move |event| {
+        let closing = matches!(event, WindowEvent::CloseRequested { .. });
+        if !closing && !matches!(event, WindowEvent::Resized(_) | WindowEvent::Moved(_)) {
+            return;
+        }
+        let Ok(mut last) = last.lock() else { return };
+        let Some(state) = current_window_state(&tracked, *last) else { return };
+        *last = Some(state);
+        if closing {
+            save_window_state(state);
+        } else {
+            let _ = tx.send(state);
+        }
+    });
+}
+
+pub fn restore_widget_position(window: &WebviewWindow) {
+    let monitors = monitor_rects(window);
+    if let Ok(size) = window.outer_size() {
+        let saved = load_settings().ok().and_then(|s| s.monitor_widget_position);
+        let placed = match saved {
+            Some(p) => place_window(
+                WindowState { width: size.width, height: size.height, x: p.x, y: p.y, maximized: false },
+                &monitors,
+            ),
+            None => monitors.first().map(|&(mx, my, mw, _)| WindowState {
+                width: size.width,
+                height: size.height,
+                x: mx.saturating_add(mw.saturating_sub(size.width) as i32),
+                y: my,
+                maximized: false,
+            }),
+        };
+        if let Some(state) = placed {
+            let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+        }
+    }
+    let _ = window.show();
+}
+
+pub fn track_widget_position(window: &WebviewWindow) {
+    let (tx, rx) = mpsc::channel::<WidgetPosition>();
+    std::thread::spawn(move || {
+        while let Ok(mut position) = rx.recv() {
+            while let Ok(next) = rx.recv_timeout(WINDOW_SAVE_DELAY) {
+                position = next;
+            }
+            let _ = modify_settings(|settings| {
+                settings.monitor_widget_position = Some(position);
+                Ok(())
+            });
+        }
+    });
+
+    window.on_window_event(move |event| {
+        if let WindowEvent::Moved(position) = event {
+            let _ = tx.send(WidgetPosition { x: position.x, y: position.y });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_settings_validates_and_clamps() {
+        let current = AppSettings::default();
+        let merged = merge_settings(&current, &json!({"refresh_interval_ms": 5, "auto_refresh_enabled": false})).unwrap();
+        assert_eq!(merged.refresh_interval_ms, REFRESH_MIN_MS);
+        assert!(!merged.auto_refresh_enabled);
+        assert_eq!(merged.processes_refresh_ms, 5000, "keys not in the update are kept");
+
+        assert_eq!(merge_settings(&current, &json!({"processes_refresh_ms": 999_999})).unwrap().processes_refresh_ms, REFRESH_MAX_MS);
+        assert_eq!(merge_settings(&current, &json!({"history_secs": 5})).unwrap().history_secs, HISTORY_MIN_SECS);
+        assert_eq!(merge_settings(&current, &json!({"snapshots_keep": 0})).unwrap().snapshots_keep, 1);
+        assert!(merge_settings(&current, &json!({"refresh_ms": 1000})).is_err(), "unknown keys are rejected");
+        assert!(merge_settings(&current, &json!({"auto_refresh_enabled": "yes"})).is_err());
+        assert!(merge_settings(&current, &json!({"theme": "neon"})).is_err());
+        assert!(merge_settings(&current, &json!({"temperature_unit": "kelvin"})).is_err());
+        assert_eq!(merge_settings(&current, &json!({"temperature_unit": "fahrenheit"})).unwrap().temperature_unit, TemperatureUnit::Fahrenheit);
+        assert!(merge_settings(&current, &json!({"log_max_bytes": 1024})).is_err());
+        assert!(merge_settings(&current, &json!({"log_keep": 0})).is_err());
+        assert!(merge_settings(&current, &json!({"admin_session_minutes": 0})).is_err());
+        assert!(merge_settings(&current, &json!({"admin_session_minutes": 61})).is_err());
+        assert!(merge_settings(&current, &json!({"log_format": "xml"})).is_err());
+        assert!(merge_settings(&current, &json!([1])).is_err());
+    }
+
+    #[test]
+    fn test_old_settings_files_get_defaults() {
+        let old: AppSettings = serde_yaml::from_str("theme: dark\n").unwrap();
+        let value = json!(old.normalized());
+        assert_eq!(value["theme"], "dark");
+        assert_eq!(value["refresh_interval_ms"], 1000);
+        assert_eq!(value["auto_refresh_enabled"], true);
+        assert_eq!(value["temperature_unit"], "celsius");
+    }
+
+    #[test]
+    fn test_parse_settings_export() {
+        let export = SettingsExport {
+            gantry_settings: EXPORT_VERSION,
+            settings: json!({"theme": "dark", "refresh_interval_ms": 2000}),
+            scripts: Some(json!({"scripts": []})),
+        };
+        let (settings, scripts) = parse_settings_export(&serde_yaml::to_string(&export).unwrap()).unwrap();
+        assert_eq!(settings.theme, "dark");
+        assert_eq!(settings.refresh_interval_ms, 2000);
+        assert_eq!(settings.repo_backup_keep, 10, "missing keys get defaults");
+        assert_eq!(scripts, Some(json!({"scripts": []})));
+
+        let (plain, scripts) = parse_settings_export("theme: dark\nauto_refresh_enabled: false\n").unwrap();
+        assert!(!plain.auto_refresh_enabled, "a bare settings.yaml is accepted");
+        assert!(scripts.is_none());
+
+        assert!(parse_settings_export("gantry_settings: 1\nsettings: {}\nwatches: []\n").is_err(), "unknown sections are rejected");
+        assert!(parse_settings_export("gantry_settings: 1\nsettings: {colour: red}\n").is_err(), "unknown settings are rejected");
+        assert!(parse_settings_export("gantry_settings: 99\nsettings: {}\n").is_err());
+        assert!(parse_settings_export("- theme\n").is_err());
+        assert!(parse_settings_export("theme: [dark\n").is_err());
+
+        let base = AppSettings { repo_backup_keep: 3, ..AppSettings::default() };
+        let merged = settings_from_fields(json!({"version": 0, "theme": "dark"}), &base).unwrap();
+        assert_eq!((merged.theme.as_str(), merged.repo_backup_keep), ("dark", 3), "missing keys come from the base");
+        assert!(settings_from_fields(json!({"version": 99}), &base).is_err());
+    }
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gantry-settings-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_corrupt_settings_fall_back_to_defaults() {
+        let dir = scratch_dir();
+        let path = dir.join("settings.yaml");
+        let garbage = "theme: dark\nrepo_backup_keep: [3\nnotifications_ena";
+        fs::write(&path, garbage).unwrap();
+
+        let settings = load_settings_from(&path).unwrap();
+        assert_eq!(settings.theme, "light");
+        assert!(!path.exists(), "the corrupt file is moved aside");
+        let copies: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.to_string_lossy().contains("settings.yaml.corrupt-"))
+            .collect();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(fs::read_to_string(&copies[0]).unwrap(), garbage);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_v0_settings_are_migrated_and_backed_up() {
+        let dir = scratch_dir();
+        let path = dir.join("settings.yaml");
+        fs::write(&path, "theme: dark\n").unwrap();
+
+        let settings = load_settings_from(&path).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.theme, "dark");
+        assert_eq!(fs::read_to_string(dir.join("settings.yaml.v0.bak")).unwrap(), "theme: dark\n");
+        let saved: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"].as_u64(), Some(SETTINGS_VERSION as u64), "persisted as the current version");
+        assert_eq!(saved["theme"].as_str(), Some("dark"));
+
+        let reloaded = load_settings_from(&path).unwrap();
+        assert_eq!(reloaded.version, SETTINGS_VERSION);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2, "a current file is not backed up again");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_newer_settings_are_left_alone() {
+        let dir = scratch_dir();
+        let path = dir.join("settings.yaml");
+        let future = "version: 99\ntheme: dark\nhologram: true\n";
+        fs::write(&path, future).unwrap();
+
+        let err = load_settings_from(&path).unwrap_err();
+        assert!(err.contains("newer"), "{}", err);
+        assert_eq!(fs::read_to_string(&path).unwrap(), future);
+        assert!(merge_settings(&AppSettings::default(), &json!({"version": 0})).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_settings_replaces_atomically() {
+        let dir = scratch_dir();
+        let path = dir.join("settings.yaml");
+        fs::write(&path, "theme: light\n").unwrap();
+        let settings = AppSettings { theme: "dark".to_string(), ..AppSettings::default() };
+
+        write_settings(&path, &settings).unwrap();
+        assert_eq!(load_settings_from(&path).unwrap().theme, "dark");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1, "no temp file is left behind");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_place_window_keeps_it_on_screen() {
+        let laptop = (0, 0, 1920, 1080);
+        let external = (1920, 0, 2560, 1440);
+        let on_external = WindowState { width: 1400, height: 900, x: 2200, y: 100, maximized: true };
+
+        assert_eq!(place_window(on_external, &[laptop, external]), Some(on_external));
+        let moved = place_window(on_external, &[laptop]).unwrap();
+        assert_eq!((moved.x, moved.y, moved.width, moved.height), (520, 100, 1400, 900), "pulled back onto the laptop");
+        assert!(moved.maximized);
+
+        let huge = WindowState { width: 4000, height: 3000, x: -50, y: -50, maximized: false };
+        let fitted = place_window(huge, &[laptop]).unwrap();
+        assert_eq!((fitted.x, fitted.y, fitted.width, fitted.height), (0, 0, 1920, 1080));
+
+        assert_eq!(place_window(on_external, &[]), None);
+    }
+
+    #[test]
+    fn test_visible_modules_follow_settings_and_platform() {
+        let mut settings = AppSettings::default();
+        settings.enabled_modules.insert("processes".to_string(), false);
+        let debian = json!({"package_managers": ["apt", "flatpak"], "init_system": "systemd"});
+        let headless = json!({"package_managers": [], "init_system": "openrc"});
+
+        let visible = visible_modules(&settings, &debian);
+        assert_eq!(visible.len(), MODULES.len());
+        assert!(!visible["processes"]);
+        assert!(visible["repos"] && visible["services"] && visible["logs"]);
+
+        let visible = visible_modules(&settings, &headless);
+        assert!(!visible["repos"], "no package manager to manage");
+        assert!(!visible["services"]);
+        assert!(visible["startup"]);
+
+        assert!(merge_settings(&settings, &json!({"enabled_modules": {"repos": false}})).is_ok());
+        assert!(merge_settings(&settings, &json!({"enabled_modules": {"printers": false}})).is_err());
+    }
+
+    #[test]
+    fn test_page_preferences_are_bounded() {
+        let current = AppSettings::default();
+        let merged = merge_settings(
+            &current,
+            &json!({"default_page": "processes", "page_preferences": {"processes": {"sort": {"key": "cpu", "dir": "desc"}}}}),
+        )
+        .unwrap();
+        assert_eq!(merged.default_page, "processes");
+        assert_eq!(merged.page_preferences["processes"]["sort"]["key"], "cpu");
+
+        assert!(merge_settings(&current, &json!({"default_page": "nowhere"})).is_err());
+        assert!(merge_settings(&current, &json!({"page_preferences": {"nowhere": {"a": 1}}})).is_err());
+        let too_many: serde_json::Map<String, serde_json::Value> =
+            (0..=PAGE_PREFERENCES_MAX_KEYS).map(|i| (format!("k{}", i), json!(i))).collect();
+        assert!(merge_settings(&current, &json!({"page_preferences": {"services": too_many}})).is_err());
+        assert!(check_page_preference("logs", &"k".repeat(PAGE_PREFERENCE_KEY_MAX_LEN + 1), &json!(1)).is_err());
+        assert!(check_page_preference("logs", "filter", &json!("x".repeat(PAGE_PREFERENCE_VALUE_MAX_BYTES))).is_err());
+    }
+
+    #[test]
+    fn test_alert_settings_validation() {
+        let current = AppSettings::default();
+        let merged = merge_settings(
+            &current,
+            &json!({"alerts": {"enabled": true, "memory_percent": null, "disk_free_percent_by_mount": {"/boot": 20.0}}}),
+        )
+        .unwrap();
+        assert!(merged.alerts.enabled);
+        assert_eq!(merged.alerts.memory_percent, None);
+        assert_eq!(merged.alerts.cpu_percent, Some(90.0), "missing keys keep their defaults");
+        assert_eq!(merged.alerts.disk_threshold("/boot"), Some(20.0));
+        assert_eq!(merged.alerts.disk_threshold("/"), Some(10.0));
+
+        assert!(merge_settings(&current, &json!({"alerts": {"cpu_percent": 150}})).is_err());
+        assert!(merge_settings(&current, &json!({"alerts": {"disk_free_percent_by_mount": {"/": 0}}})).is_err());
+        assert!(merge_settings(&current, &json!({"alerts": {"temperature_celsius": -5}})).is_err());
+    }
+
+    #[test]
+    fn test_temperature_unit_conversion() {
+        assert_eq!(TemperatureUnit::Celsius.convert(45.25), 45.3);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(100.0), 212.0);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(37.0), 98.6);
+        assert_eq!(TemperatureUnit::Fahrenheit.symbol(), "°F");
+    }
+}
@@ -0,0 +1,149 @@
+use super::error::GantryError;
+use serde_json::json;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+const TOGGLE_WINDOW: &str = "toggle_window";
+
+const SCRIPT_PREFIX: &str = "script:";
+
+fn parse(accelerator: &str) -> Result<Shortcut, GantryError> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| GantryError::ParseError(format!("Invalid shortcut {}: {}", accelerator, e)))
+}
+
+pub(crate) fn validate(bindings: &BTreeMap<String, String>) -> Result<(), String> {
+    let mut seen: Vec<(Shortcut, &str)> = Vec::new();
+    for (action, accelerator) in bindings {
+        if action != TOGGLE_WINDOW && !action.starts_with(SCRIPT_PREFIX) {
+            return Err(format!("Unknown shortcut action: {}", action));
+        }
+        let shortcut = parse(accelerator)?;
+        if let Some((_, other)) = seen.iter().find(|(s, _)| *s == shortcut) {
+            return Err(format!("{} is bound to both {} and {}", accelerator, other, action));
+        }
+        seen.push((shortcut, action));
+    }
+    Ok(())
+}
+
+pub fn register_all(app: &AppHandle) -> Vec<serde_json::Value> {
+    let manager = app.global_shortcut();
+    let _ = manager.unregister_all();
+    let mut failed = Vec::new();
+    for (action, accelerator) in super::settings::global_shortcuts() {
+        let result = parse(&accelerator).and_then(|s| manager.register(s).map_err(|e| GantryError::Failed(e.to_string())));
+        if let Err(e) = result {
+            let _ = super::logging::log("warn", "shortcuts", &format!("Could not register {} for {}: {}", accelerator, action, e));
+            failed.push(json!({"action": action, "accelerator": accelerator, "error": e.message()}));
+        }
+    }
+    failed
+}
+
+fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else { return };
+    let in_front = window.is_visible().unwrap_or(false)
+        && !window.is_minimized().unwrap_or(false)
+        && window.is_focused().unwrap_or(false);
+    if in_front {
+        let _ = window.hide();
+    } else {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+pub fn handle(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let bound = super::settings::global_shortcuts()
+        .into_iter()
+        .find(|(_, accelerator)| parse(accelerator).is_ok_and(|s| s == *shortcut));
+    let Some((action, _)) = bound else { return };
+    if action == TOGGLE_WINDOW {
+        toggle_main_window(app);
+    } else if let Some(id) = action.strip_prefix(SCRIPT_PREFIX) {
+        let run = super::scripts::require_favorite(id)
+            .and_then(|_| super::scripts::run_script_streaming(app.clone(), id.to_string(), None, None));
+        if let Err(e) = run {
+            let _ = super::logging::log("warn", "shortcuts", &format!("Shortcut for script {} failed: {}", id, e));
+        }
+    }
+}
+
+#[tauri::command]
+pub fn set_global_shortcut(app: AppHandle, action: String, accelerator: Option<String>) -> Result<serde_json::Value, GantryError> {
+    if let Some(id) = action.strip_prefix(SCRIPT_PREFIX) {
+        super::scripts::require_favorite(id)?;
+    }
+    let mut bindings = super::settings::global_shortcuts();
+    let previous = bindings.get(&action).cloned();
+    let accelerator = accelerator.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    match &accelerator {
+        Some(accelerator) => bindings.insert(action.clone(), accelerator.clone()),
+        None => bindings.remove(&action),
+    };
+    validate(&bindings)?;
+
+    let manager = app.global_shortcut();
+    if let Some(old) = previous.as_deref().and_then(|a| parse(a).ok()) {
+        let _ = manager.unregister(old);
+    }
+    if let Some(accelerator) = &accelerator {
+        if let Err(e) = manager.register(parse(accelerator)?) {
+            if let Some(old) = previous.as_deref().and_then(|a| parse(a).ok()) {
+                let _ = manager.register(old);
+            }
+            return Ok(json!({
+                "action": action,
+                "accelerator": previous,
+                "registered": false,
+                "conflict": format!("{} is already in use: {}", accelerator, e),
+            }));
+        }
+    }
+    super::settings::set_global_shortcuts(bindings)?;
+    Ok(json!({"action": action, "accelerator": accelerator, "registered": accelerator.is_some(), "conflict": null}))
+}
+
+#[tauri::command]
+pub fn list_global_shortcuts(app: AppHandle) -> Result<serde_json::Value, GantryError> {
+    let manager = app.global_shortcut();
+    let shortcuts: Vec<serde_json::Value> = super::settings::global_shortcuts()
+        .into_iter()
+        .map(|(action, accelerator)| {
+            let registered = parse(&accelerator).is_ok_and(|s| manager.is_registered(s));
+            json!({"action": action, "accelerator": accelerator, "registered": registered})
+        })
+        .collect();
+    Ok(json!(shortcuts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_bindings() {
+        let mut bindings = BTreeMap::new();
+        bindings.insert("toggle_window".to_string(), "CommandOrControl+Shift+G".to_string());
+        bindings.insert("script:abc".to_string(), "Alt+F9".to_string());
+        assert!(validate(&bindings).is_ok());
+
+        bindings.insert("script:def".to_string(), "alt+f9".to_string());
+        assert!(validate(&bindings).unwrap_err().contains("bound to both"));
+        bindings.remove("script:def");
+
+        bindings.insert("launch_rockets".to_string(), "Alt+F10".to_string());
+        assert!(validate(&bindings).unwrap_err().contains("Unknown shortcut action"));
+        bindings.remove("launch_rockets");
+
+        bindings.insert("script:ghi".to_string(), "Shift+NoSuchKey".to_string());
+        assert!(validate(&bindings).is_err());
+    }
+}
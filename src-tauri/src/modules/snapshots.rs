@@ -0,0 +1,274 @@
+use super::error::GantryError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TOP_PROCESSES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotProcess {
+    pid: u32,
+    name: String,
+    memory: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SnapshotService {
+    name: String,
+    user: bool,
+    active_state: String,
+    sub_state: String,
+    enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    id: String,
+    label: String,
+    created: String,
+    resources: Value,
+    processes: Vec<SnapshotProcess>,
+    services: Option<Vec<SnapshotService>>,
+}
+
+fn snapshots_dir() -> PathBuf {
+    super::appdata::config_path("snapshots")
+}
+
+// Ids are timestamps, so they never name a path.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_digit() || c == '-')
+}
+
+fn is_snapshot_file(name: &str) -> bool {
+    name.strip_suffix(".json").is_some_and(valid_id)
+}
+
+fn read_snapshot(dir: &Path, id: &str) -> Result<Snapshot, GantryError> {
+    if !valid_id(id) {
+        return Err(GantryError::NotFound(format!("No snapshot {}", id)));
+    }
+    let content = fs::read_to_string(dir.join(format!("{}.json", id))).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => GantryError::NotFound(format!("No snapshot {}", id)),
+        _ => GantryError::from(e),
+    })?;
+    serde_json::from_str(&content).map_err(|e| GantryError::ParseError(format!("Snapshot {}: {}", id, e)))
+}
+
+fn save_snapshot(dir: &Path, snapshot: &Snapshot, keep: usize) -> Result<(), GantryError> {
+    fs::create_dir_all(dir)?;
+    let content = serde_json::to_string(snapshot).map_err(|e| GantryError::Failed(e.to_string()))?;
+    fs::write(dir.join(format!("{}.json", snapshot.id)), content)?;
+    let names = super::appdata::file_names(dir, is_snapshot_file);
+    for old in &names[..names.len().saturating_sub(keep)] {
+        let _ = fs::remove_file(dir.join(old));
+    }
+    Ok(())
+}
+
+fn services_now() -> Option<Vec<SnapshotService>> {
+    let services: Vec<super::services::ServiceInfo> =
+        serde_json::from_value(super::services::list_services(None).ok()?).ok()?;
+    Some(
+        services
+            .into_iter()
+            .map(|s| SnapshotService {
+                name: s.name,
+                user: s.is_user_service,
+                active_state: s.active_state,
+                sub_state: s.sub_state,
+                enabled: s.is_enabled,
+            })
+            .collect(),
+    )
+}
+
+fn delta(before: Option<u64>, after: Option<u64>) -> Option<i64> {
+    Some(after? as i64 - before? as i64)
+}
+
+fn free_by_mount(resources: &Value) -> BTreeMap<String, u64> {
+    resources["disks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| Some((d["mount_point"].as_str()?.to_string(), d["available_space"].as_u64()?)))
+        .collect()
+}
+
+fn processes_by_name(processes: &[SnapshotProcess]) -> BTreeMap<&str, (usize, u64)> {
+    let mut by_name: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+    for p in processes {
+        let slot = by_name.entry(&p.name).or_default();
+        slot.0 += 1;
+        slot.1 += p.memory;
+    }
+    by_name
+}
+
+fn compare(a: &Snapshot, b: &Snapshot) -> Value {
+    let memory = |s: &Snapshot, key: &str| s.resources["memory"][key].as_u64();
+    let (free_a, free_b) = (free_by_mount(&a.resources), free_by_mount(&b.resources));
+    let mut mounts: Vec<&String> = free_a.keys().chain(free_b.keys()).collect();
+    mounts.sort();
+    mounts.dedup();
+    let disks: Vec<Value> = mounts
+        .into_iter()
+        .map(|mount| {
+            let (before, after) = (free_a.get(mount).copied(), free_b.get(mount).copied());
+            json!({"mount_point": mount, "free_before": before, "free_after": after, "free_delta": delta(before, after)})
+        })
+        .collect();
+
+    let (procs_a, procs_b) = (processes_by_name(&a.processes), processes_by_name(&b.processes));
+    let only_in = |from: &BTreeMap<&str, (usize, u64)>, other: &BTreeMap<&str, (usize, u64)>| -> Vec<Value> {
+        from.iter()
+            .filter(|(name, _)| !other.contains_key(*name))
+            .map(|(name, (count, memory))| json!({"name": name, "count": count, "memory": memory}))
+            .collect()
+    };
+
+    let services = match (&a.services, &b.services) {
+        (Some(before), Some(after)) => {
+            let key = |s: &SnapshotService| (s.name.clone(), s.user);
+            let before: BTreeMap<_, _> = before.iter().map(|s| (key(s), s)).collect();
+            let after: BTreeMap<_, _> = after.iter().map(|s| (key(s), s)).collect();
+            let changed: Vec<Value> = after
+                .iter()
+                .filter_map(|(k, now)| {
+                    let was = before.get(k)?;
+                    (was != now).then(|| json!({"name": now.name, "user": now.user, "before": was, "after": now}))
+                })
+                .collect();
+            json!({
+                "added": after.iter().filter(|(k, _)| !before.contains_key(*k)).map(|(_, s)| s).collect::<Vec<_>>(),
+                "removed": before.iter().filter(|(k, _)| !after.contains_key(*k)).map(|(_, s)| s).collect::<Vec<_>>(),
+                "changed": changed,
+            })
+        }
+        _ => Value::Null,
+    };
+
+    json!({
+        "a": {"id": a.id, "label": a.label, "created": a.created},
+        "b": {"id": b.id, "label": b.label, "created": b.created},
+        "memory": {
+            "used_before": memory(a, "used"),
+            "used_after": memory(b, "used"),
+            "used_delta": delta(memory(a, "used"), memory(b, "used")),
+            "swap_used_delta": delta(memory(a, "swap_used"), memory(b, "swap_used")),
+        },
+        "disks": disks,
+        "processes": {"new": only_in(&procs_b, &procs_a), "removed": only_in(&procs_a, &procs_b)},
+        "services": services,
+    })
+}
+
+#[tauri::command]
+pub async fn take_resource_snapshot(label: Option<String>) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let now = chrono::Local::now();
+        let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+        let snapshot = Snapshot {
+            id: now.format("%Y%m%d-%H%M%S-%3f").to_string(),
+            label: label.unwrap_or_else(|| now.format("%Y-%m-%d %H:%M").to_string()),
+            created: now.to_rfc3339(),
+            resources: super::system::get_resources()?,
+            processes: serde_json::from_value(super::processes::top_processes(TOP_PROCESSES)).unwrap_or_default(),
+            services: services_now(),
+        };
+        save_snapshot(&snapshots_dir(), &snapshot, super::settings::snapshots_keep())?;
+        Ok(json!({"id": snapshot.id, "label": snapshot.label, "created": snapshot.created}))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[tauri::command]
+pub fn list_resource_snapshots() -> Result<Value, GantryError> {
+    let dir = snapshots_dir();
+    let snapshots: Vec<Value> = super::appdata::file_names(&dir, is_snapshot_file)
+        .iter()
+        .rev()
+        .filter_map(|name| read_snapshot(&dir, name.trim_end_matches(".json")).ok())
+        .map(|s| json!({"id": s.id, "label": s.label, "created": s.created}))
+        .collect();
+    Ok(json!(snapshots))
+}
+
+#[tauri::command]
+pub async fn compare_resource_snapshots(id_a: String, id_b: String) -> Result<Value, GantryError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = snapshots_dir();
+        Ok(compare(&read_snapshot(&dir, &id_a)?, &read_snapshot(&dir, &id_b)?))
+    })
+    .await
+    .map_err(|e| GantryError::Failed(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(name: &str, active_state: &str, enabled: bool) -> SnapshotService {
+        SnapshotService {
+            name: name.into(),
+            user: false,
+            active_state: active_state.into(),
+            sub_state: if active_state == "active" { "running" } else { "dead" }.into(),
+            enabled,
+        }
+    }
+
+    fn snapshot(id: &str, used: u64, disks: Value, processes: &[(&str, u64)], services: Vec<SnapshotService>) -> Snapshot {
+        Snapshot {
+            id: id.into(),
+            label: id.into(),
+            created: String::new(),
+            resources: json!({"memory": {"used": used, "swap_used": 0}, "disks": disks}),
+            processes: processes
+                .iter()
+                .enumerate()
+                .map(|(pid, &(name, memory))| SnapshotProcess { pid: pid as u32, name: name.into(), memory })
+                .collect(),
+            services: Some(services),
+        }
+    }
+
+    #[test]
+    fn test_compare_and_prune_snapshots() {
+        let a = snapshot(
+            "20260101-000000-000",
+            4_000,
+            json!([{"mount_point": "/", "available_space": 1_000}, {"mount_point": "/mnt/usb", "available_space": 50}]),
+            &[("firefox", 300), ("firefox", 200), ("updater", 10)],
+            vec![service("nginx", "inactive", false), service("cups", "active", true)],
+        );
+        let b = snapshot(
+            "20260101-000100-000",
+            3_000,
+            json!([{"mount_point": "/", "available_space": 1_500}]),
+            &[("firefox", 400), ("postgres", 100)],
+            vec![service("nginx", "active", true), service("cups", "active", true), service("postgresql", "active", true)],
+        );
+        let diff = compare(&a, &b);
+        assert_eq!(diff["memory"]["used_delta"], -1_000);
+        assert_eq!(diff["disks"][0], json!({"mount_point": "/", "free_before": 1_000, "free_after": 1_500, "free_delta": 500}));
+        assert_eq!(diff["disks"][1]["free_after"], Value::Null, "an unmounted disk has no delta");
+        assert_eq!(diff["processes"]["new"], json!([{"name": "postgres", "count": 1, "memory": 100}]));
+        assert_eq!(diff["processes"]["removed"][0]["name"], "updater");
+        assert_eq!(diff["services"]["added"][0]["name"], "postgresql");
+        assert_eq!(diff["services"]["changed"].as_array().unwrap().len(), 1);
+        assert_eq!(diff["services"]["changed"][0]["before"]["active_state"], "inactive");
+
+        let dir = std::env::temp_dir().join(format!("gantry-snapshots-{}", uuid::Uuid::new_v4()));
+        save_snapshot(&dir, &a, 1).unwrap();
+        save_snapshot(&dir, &b, 1).unwrap();
+        assert_eq!(super::super::appdata::file_names(&dir, is_snapshot_file), vec!["20260101-000100-000.json"]);
+        assert_eq!(read_snapshot(&dir, &b.id).unwrap().processes.len(), 2);
+        assert!(matches!(read_snapshot(&dir, "../settings"), Err(GantryError::NotFound(_))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,842 @@
+#[cfg(target_os = "linux")]
+use super::error::GantryError;
+#[cfg(target_os = "linux")]
+use serde_json::json;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::process::Command;
+use serde::Serialize;
+#[cfg(target_os = "linux")]
+use std::collections::BTreeMap;
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+
+#[cfg(target_os = "linux")]
+const NVME_DATA_UNIT_BYTES: f64 = 512_000.0;
+
+#[cfg(target_os = "linux")]
+fn nvme_controller_name(device: &str) -> Option<String> {
+    let name = device.trim().trim_start_matches("/dev/");
+    let digits: String = name.strip_prefix("nvme")?.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return None;
+    }
+    Some(format!("nvme{}", digits))
+}
+
+#[cfg(target_os = "linux")]
+fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_f64().map(|f| f as u64))
+        .or_else(|| value.as_str().and_then(|s| s.replace(',', "").trim().parse().ok()))
+}
+
+#[cfg(target_os = "linux")]
+fn parse_nvme_smart_log(smart: &serde_json::Value) -> serde_json::Value {
+    let percentage_used = json_u64(&smart["percentage_used"]).or_else(|| json_u64(&smart["percent_used"]));
+    let to_tb = |units: Option<u64>| {
+        units.map(|u| ((u as f64 * NVME_DATA_UNIT_BYTES / 1e12) * 100.0).round() / 100.0)
+    };
+    // nvme-cli reports the composite temperature in Kelvin
+    let temperature = json_u64(&smart["temperature"]).map(|k| {
+        if k > 200 { k as f64 - 273.0 } else { k as f64 }
+    });
+
+    json!({
+        "percentage_used": percentage_used,
+        "data_read_tb": to_tb(json_u64(&smart["data_units_read"])),
+        "data_written_tb": to_tb(json_u64(&smart["data_units_written"])),
+        "temperature_c": temperature,
+        "available_spare": json_u64(&smart["avail_spare"]),
+        "unsafe_shutdowns": json_u64(&smart["unsafe_shutdowns"]),
+        "power_on_hours": json_u64(&smart["power_on_hours"]),
+        "media_errors": json_u64(&smart["media_errors"]),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_nvme_namespaces(list: &serde_json::Value, controller: &str) -> Vec<serde_json::Value> {
+    let Some(devices) = list["Devices"].as_array() else { return vec![] };
+    devices
+        .iter()
+        .filter_map(|d| {
+            let path = d["DevicePath"].as_str()?;
+            let name = path.trim_start_matches("/dev/");
+            if nvme_controller_name(name).as_deref() != Some(controller) {
+                return None;
+            }
+            Some(json!({
+                "name": name,
+                "namespace": json_u64(&d["NameSpace"]),
+                "capacity_bytes": json_u64(&d["PhysicalSize"]),
+                "used_bytes": json_u64(&d["UsedBytes"]),
+            }))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn nvme_sysfs_info(controller: &str) -> serde_json::Value {
+    let base = std::path::PathBuf::from("/sys/class/nvme").join(controller);
+    let read = |file: &str| -> Option<String> {
+        fs::read_to_string(base.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let mut temperature: Option<f64> = None;
+    for hwmon_parent in [base.clone(), base.join("device")] {
+        if let Ok(entries) = fs::read_dir(&hwmon_parent) {
+            for entry in entries.flatten() {
+                if !entry.file_name().to_string_lossy().starts_with("hwmon") {
+                    continue;
+                }
+                if let Ok(raw) = fs::read_to_string(entry.path().join("temp1_input")) {
+                    temperature = raw.trim().parse::<f64>().ok().map(|mc| (mc / 100.0).round() / 10.0);
+                }
+            }
+        }
+        if temperature.is_some() {
+            break;
+        }
+    }
+
+    json!({
+        "model": read("model"),
+        "serial": read("serial"),
+        "firmware": read("firmware_rev"),
+        "temperature_c": temperature,
+    })
+}
+
+// One root shell for both queries, so without an admin session the user is prompted once.
+#[cfg(target_os = "linux")]
+pub(crate) fn nvme_health_command(controller: &str) -> Result<Vec<String>, GantryError> {
+    if nvme_controller_name(controller).as_deref() != Some(controller) {
+        return Err(GantryError::Failed(format!("{} is not an NVMe controller", controller)));
+    }
+    let script = format!(
+        "nvme smart-log /dev/{0} -o json && echo '---gantry---' && nvme id-ctrl /dev/{0} -o json",
+        controller
+    );
+    Ok(vec!["sh".into(), "-c".into(), script])
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_nvme_info(device: String) -> Result<serde_json::Value, String> {
+    let controller = nvme_controller_name(&device)
+        .ok_or_else(|| format!("{} is not an NVMe device", device))?;
+
+    let sysfs = nvme_sysfs_info(&controller);
+    let nvme_cli_available = Command::new("nvme").arg("version").output().is_ok();
+
+    if !nvme_cli_available {
+        return Ok(json!({
+            "device": device,
+            "controller": controller,
+            "model": sysfs["model"],
+            "serial": sysfs["serial"],
+            "firmware": sysfs["firmware"],
+            "temperature_c": sysfs["temperature_c"],
+            "health": null,
+            "namespaces": [],
+            "nvme_cli_available": false,
+            "source": "sysfs",
+        }));
+    }
+
+    let stdout = super::admin::read_nvme_health(&controller, &nvme_health_command(&controller)?)
+        .map_err(|e| format!("nvme smart-log failed: {}", e.message()))?;
+    let (smart_raw, ctrl_raw) = stdout.split_once("---gantry---").unwrap_or((&stdout, ""));
+    let smart: serde_json::Value = serde_json::from_str(smart_raw.trim())
+        .map_err(|e| format!("Failed to parse nvme smart-log: {}", e))?;
+    let ctrl: serde_json::Value = serde_json::from_str(ctrl_raw.trim()).unwrap_or(json!({}));
+
+    let namespaces = Command::new("nvme")
+        .args(["list", "-o", "json"])
+        .output()
+        .ok()
+        .and_then(|o| serde_json::from_slice::<serde_json::Value>(&o.stdout).ok())
+        .map(|list| parse_nvme_namespaces(&list, &controller))
+        .unwrap_or_default();
+
+    let health = parse_nvme_smart_log(&smart);
+    let field = |key: &str, fallback: &serde_json::Value| -> serde_json::Value {
+        ctrl[key]
+            .as_str()
+            .map(|s| json!(s.trim()))
+            .unwrap_or_else(|| fallback.clone())
+    };
+
+    Ok(json!({
+        "device": device,
+        "controller": controller,
+        "model": field("mn", &sysfs["model"]),
+        "serial": field("sn", &sysfs["serial"]),
+        "firmware": field("fr", &sysfs["firmware"]),
+        "temperature_c": if health["temperature_c"].is_null() { sysfs["temperature_c"].clone() } else { health["temperature_c"].clone() },
+        "health": health,
+        "namespaces": namespaces,
+        "nvme_cli_available": true,
+        "source": "nvme-cli",
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_nvme_info(_device: String) -> Result<serde_json::Value, String> {
+    Err("NVMe health details are only supported on Linux".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct BlockDeviceInfo {
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    pub transport: Option<String>,
+    pub display_name: String,
+}
+
+#[cfg(target_os = "linux")]
+// Keyed on the node's major:minor and the disk's serial, so a name the kernel
+// hands to a different disk after a hotplug doesn't get the old description.
+static BLOCK_DEVICES: Mutex<BTreeMap<(String, Option<String>), BlockDeviceInfo>> = Mutex::new(BTreeMap::new());
+
+#[cfg(target_os = "linux")]
+fn parent_disk(name: &str) -> &str {
+    if name.starts_with("nvme") || name.starts_with("mmcblk") {
+        if let Some((disk, part)) = name.rsplit_once('p') {
+            if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) && disk.ends_with(|c: char| c.is_ascii_digit()) {
+                return disk;
+            }
+        }
+        return name;
+    }
+    if name.starts_with("sd") || name.starts_with("vd") || name.starts_with("hd") || name.starts_with("xvd") {
+        return name.trim_end_matches(|c: char| c.is_ascii_digit());
+    }
+    name
+}
+
+#[cfg(target_os = "linux")]
+fn read_sysfs_value(path: std::path::PathBuf) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+fn disk_serial(dir: &std::path::Path) -> Option<String> {
+    read_sysfs_value(dir.join("device").join("serial")).or_else(|| read_sysfs_value(dir.join("serial")))
+}
+
+#[cfg(target_os = "linux")]
+fn block_device_key(sys: &std::path::Path, name: &str) -> Option<(String, Option<String>)> {
+    let dev = read_sysfs_value(sys.join("class").join("block").join(name).join("dev"))?;
+    Some((dev, disk_serial(&sys.join("block").join(parent_disk(name)))))
+}
+
+#[cfg(target_os = "linux")]
+fn read_block_device(sys: &std::path::Path, name: &str) -> Option<BlockDeviceInfo> {
+    let disk = parent_disk(name);
+    let dir = sys.join("block").join(disk);
+    if !dir.is_dir() {
+        return None;
+    }
+    let device = dir.join("device");
+    let read = read_sysfs_value;
+    let vendor = read(device.join("vendor"));
+    let transport = if disk.starts_with("nvme") {
+        Some(read(device.join("transport")).filter(|t| t != "pcie").unwrap_or_else(|| "nvme".to_string()))
+    } else if disk.starts_with("vd") {
+        Some("virtio".to_string())
+    } else if disk.starts_with("mmcblk") {
+        Some("mmc".to_string())
+    } else if fs::canonicalize(&device).is_ok_and(|p| p.to_string_lossy().contains("/usb")) {
+        Some("usb".to_string())
+    } else if vendor.as_deref() == Some("ATA") {
+        Some("sata".to_string())
+    } else {
+        None
+    };
+    // SATA disks report ATA and virtio disks a PCI id as their vendor.
+    let vendor = vendor.filter(|v| v != "ATA" && !v.starts_with("0x"));
+    let model = read(device.join("model")).or_else(|| read(device.join("name")));
+    let label = match (vendor, model.clone()) {
+        (Some(vendor), Some(model)) if !model.to_lowercase().starts_with(&vendor.to_lowercase()) => {
+            Some(format!("{} {}", vendor, model))
+        }
+        (_, Some(model)) => Some(model),
+        (vendor, None) => vendor.or_else(|| (transport.as_deref() == Some("virtio")).then(|| "Virtio disk".to_string())),
+    };
+    Some(BlockDeviceInfo {
+        model,
+        serial: disk_serial(&dir),
+        transport,
+        display_name: label.map_or_else(|| name.to_string(), |label| format!("{} ({})", label, name)),
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn block_device_info(name: &str) -> Option<BlockDeviceInfo> {
+    let name = name.trim_start_matches("/dev/");
+    let sys = std::path::Path::new("/sys");
+    let key = block_device_key(sys, name)?;
+    let mut cache = BLOCK_DEVICES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(info) = cache.get(&key) {
+        return Some(info.clone());
+    }
+    // Misses aren't cached: the device may just not have settled in sysfs yet.
+    let info = read_block_device(sys, name)?;
+    cache.insert(key, info.clone());
+    Some(info)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn block_device_info(_name: &str) -> Option<BlockDeviceInfo> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn hwmon_block_device(hwmon: &std::path::Path) -> Option<String> {
+    let names = |dir: std::path::PathBuf| -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .map(|d| d.flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    };
+    let device = hwmon.join("device");
+    if let Some(name) = names(device.join("block")).into_iter().next() {
+        return Some(name);
+    }
+    let parent = fs::canonicalize(hwmon).ok().and_then(|p| p.parent().map(|p| p.to_path_buf()));
+    [Some(device), parent].into_iter().flatten().flat_map(names).find(|name| {
+        name.strip_prefix("nvme")
+            .and_then(|rest| rest.split_once('n'))
+            .is_some_and(|(ctrl, ns)| {
+                !ctrl.is_empty() && !ns.is_empty() && format!("{}{}", ctrl, ns).chars().all(|c| c.is_ascii_digit())
+            })
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn backing_disks(dev_name: &str) -> Vec<String> {
+    let class_path = std::path::PathBuf::from("/sys/class/block").join(dev_name);
+    let slaves: Vec<String> = fs::read_dir(class_path.join("slaves"))
+        .map(|d| d.flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect())
+        .unwrap_or_default();
+    if !slaves.is_empty() {
+        return slaves.iter().flat_map(|s| backing_disks(s)).collect();
+    }
+    if class_path.join("partition").exists() {
+        if let Some(parent) = fs::canonicalize(&class_path)
+            .ok()
+            .and_then(|p| p.parent().and_then(|p| p.file_name()).map(|n| n.to_string_lossy().to_string()))
+        {
+            return vec![parent];
+        }
+    }
+    vec![dev_name.to_string()]
+}
+
+// The mount source can be /dev/root, which names no real node, so the
+// device number of / is looked up in /sys/dev/block first. Filesystems
+// with anonymous device numbers (btrfs) fall back to the mount table.
+#[cfg(target_os = "linux")]
+fn root_disks(mounts: &std::collections::HashMap<String, (String, String)>) -> Vec<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let by_dev = fs::metadata("/").ok().and_then(|m| {
+        let dev = m.dev();
+        fs::canonicalize(format!("/sys/dev/block/{}:{}", libc::major(dev), libc::minor(dev))).ok()
+    });
+    let by_mount = || {
+        mounts
+            .iter()
+            .filter(|(_, (mountpoint, _))| mountpoint == "/")
+            .filter_map(|(source, _)| fs::canonicalize(source).ok())
+            .collect::<Vec<_>>()
+    };
+    by_dev
+        .map(|p| vec![p])
+        .unwrap_or_else(by_mount)
+        .into_iter()
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .flat_map(|name| backing_disks(&name))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn processes_using_mount(mountpoint: &str) -> Vec<serde_json::Value> {
+    let prefix = format!("{}/", mountpoint.trim_end_matches('/'));
+    let under_mount = |p: &std::path::Path| -> bool {
+        let s = p.to_string_lossy();
+        s == mountpoint || s.starts_with(&prefix)
+    };
+
+    let mut holders = Vec::new();
+    let Ok(entries) = fs::read_dir("/proc") else { return holders };
+    for entry in entries.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        let proc_dir = entry.path();
+
+        let mut busy = ["cwd", "root", "exe"]
+            .iter()
+            .filter_map(|l| fs::read_link(proc_dir.join(l)).ok())
+            .any(|p| under_mount(&p));
+        if !busy {
+            if let Ok(fds) = fs::read_dir(proc_dir.join("fd")) {
+                busy = fds.flatten().filter_map(|fd| fs::read_link(fd.path()).ok()).any(|p| under_mount(&p));
+            }
+        }
+
+        if busy {
+            let name = fs::read_to_string(proc_dir.join("comm"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            holders.push(json!({"pid": pid, "name": name}));
+        }
+    }
+    holders
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn eject_device(name: String) -> Result<serde_json::Value, String> {
+    let name = name.trim().trim_start_matches("/dev/").to_string();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Invalid device name".to_string());
+    }
+
+    let disk_path = std::path::PathBuf::from("/sys/block").join(&name);
+    if !disk_path.exists() {
+        return Err(format!("Disk {} not found", name));
+    }
+
+    let mounts = fs::read_to_string("/proc/self/mountinfo")
+        .map(|c| super::devices::parse_mountinfo(&c))
+        .unwrap_or_default();
+
+    if root_disks(&mounts).contains(&name) {
+        return Err(format!("Refusing to eject {}: it hosts the root filesystem", name));
+    }
+
+    let mut nodes = vec![name.clone()];
+    if let Ok(entries) = fs::read_dir(&disk_path) {
+        let mut parts: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().join("partition").exists())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        parts.sort();
+        nodes.extend(parts);
+    }
+
+    let mut unmounted = Vec::new();
+    let mut all_unmounted = true;
+
+    for node in &nodes {
+        let dev = format!("/dev/{}", node);
+        let Some((mountpoint, _)) = mounts.get(&dev) else { continue };
+
+        let output = Command::new("udisksctl")
+            .args(["unmount", "-b", &dev])
+            .output()
+            .map_err(|e| format!("Failed to run udisksctl: {}", e))?;
+
+        if output.status.success() {
+            unmounted.push(json!({
+                "partition": node,
+                "mountpoint": mountpoint,
+                "success": true,
+                "error": "",
+                "busy_processes": [],
+            }));
+        } else {
+            all_unmounted = false;
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let busy = stderr.to_lowercase().contains("busy");
+            let error = if busy { format!("Device busy: {}", stderr) } else { stderr };
+            unmounted.push(json!({
+                "partition": node,
+                "mountpoint": mountpoint,
+                "success": false,
+                "error": error,
+                "busy_processes": if busy { processes_using_mount(mountpoint) } else { vec![] },
+            }));
+        }
+    }
+
+    if !all_unmounted {
+        return Ok(json!({
+            "success": false,
+            "device": name,
+            "unmounted": unmounted,
+            "powered_off": false,
+            "error": "Could not unmount all partitions",
+        }));
+    }
+
+    let output = if name.starts_with("sr") {
+        Command::new("eject").arg(format!("/dev/{}", name)).output()
+    } else {
+        Command::new("udisksctl")
+            .args(["power-off", "-b", &format!("/dev/{}", name)])
+            .output()
+    };
+    let output = output.map_err(|e| format!("Failed to eject {}: {}", name, e))?;
+    let success = output.status.success();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Ok(json!({
+        "success": success,
+        "device": name,
+        "unmounted": unmounted,
+        "powered_off": success,
+        "error": if success { String::new() } else { stderr },
+    }))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn eject_device(name: String) -> Result<serde_json::Value, String> {
+    let name = name.trim().trim_start_matches("/dev/").to_string();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Invalid device name".to_string());
+    }
+
+    let root_disk = Command::new("diskutil")
+        .args(["info", "-plist", "/"])
+        .output()
+        .ok()
+        .and_then(|o| plist::from_bytes::<plist::Value>(&o.stdout).ok())
+        .and_then(|v| v.into_dictionary())
+        .and_then(|d| d.get("ParentWholeDisk").and_then(|v| v.as_string()).map(|s| s.to_string()));
+
+    if root_disk.as_deref() == Some(name.as_str()) {
+        return Err(format!("Refusing to eject {}: it hosts the root filesystem", name));
+    }
+
+    let output = Command::new("diskutil")
+        .args(["eject", &format!("/dev/{}", name)])
+        .output()
+        .map_err(|e| format!("Failed to run diskutil: {}", e))?;
+    let success = output.status.success();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Ok(json!({
+        "success": success,
+        "device": name,
+        "unmounted": [],
+        "powered_off": success,
+        "error": if success { String::new() } else { stderr },
+    }))
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct FstabEntry {
+    spec: String,
+    mountpoint: String,
+    fstype: String,
+    options: String,
+    pass: u32,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_fstab(content: &str) -> Vec<FstabEntry> {
+    use super::devices::unescape_mountinfo;
+
+    content
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            Some(FstabEntry {
+                spec: unescape_mountinfo(fields[0]),
+                mountpoint: unescape_mountinfo(fields[1]),
+                fstype: fields[2].to_string(),
+                options: fields.get(3).unwrap_or(&"defaults").to_string(),
+                pass: fields.get(5).and_then(|p| p.parse().ok()).unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn fstab_spec_path(spec: &str) -> Option<String> {
+    // udev escapes spaces and slashes in by-label/by-partlabel link names.
+    let escape = |v: &str| v.replace('\\', "\\x5c").replace(' ', "\\x20").replace('/', "\\x2f");
+    let (kind, value) = spec.split_once('=').unwrap_or(("", spec));
+    match kind.to_ascii_uppercase().as_str() {
+        "UUID" => Some(format!("/dev/disk/by-uuid/{}", value)),
+        "PARTUUID" => Some(format!("/dev/disk/by-partuuid/{}", value)),
+        "LABEL" => Some(format!("/dev/disk/by-label/{}", escape(value))),
+        "PARTLABEL" => Some(format!("/dev/disk/by-partlabel/{}", escape(value))),
+        "" if spec.starts_with("/dev/") => Some(spec.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn fstab_entry_status(
+    entry: &FstabEntry,
+    is_device: bool,
+    device: Option<&str>,
+    mounted_at: Option<&str>,
+) -> &'static str {
+    if !is_device {
+        return "virtual";
+    }
+    if device.is_none() {
+        return "missing";
+    }
+    let expected = match entry.mountpoint.trim_end_matches('/') {
+        "" => "/",
+        mp => mp,
+    };
+    match mounted_at {
+        Some(mp) if entry.fstype == "swap" || mp == expected => "mounted",
+        Some(_) => "mounted_elsewhere",
+        None => "not_mounted",
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_fstab_entries() -> Result<serde_json::Value, String> {
+    let content = fs::read_to_string("/etc/fstab").map_err(|e| format!("Failed to read /etc/fstab: {}", e))?;
+
+    // Mount sources may be symlinks (/dev/mapper/*, /dev/disk/by-*), so compare canonical paths.
+    let mut mounted: std::collections::HashMap<String, String> = fs::read_to_string("/proc/self/mountinfo")
+        .map(|c| super::devices::parse_mountinfo(&c))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(source, (mountpoint, _))| {
+            let canonical = fs::canonicalize(&source)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(source);
+            (canonical, mountpoint)
+        })
+        .collect();
+    if let Ok(swaps) = fs::read_to_string("/proc/swaps") {
+        for line in swaps.lines().skip(1) {
+            if let Some(source) = line.split_whitespace().next() {
+                mounted.entry(source.to_string()).or_insert_with(|| "swap".to_string());
+            }
+        }
+    }
+
+    let entries: Vec<serde_json::Value> = parse_fstab(&content)
+        .into_iter()
+        .map(|entry| {
+            let spec_path = fstab_spec_path(&entry.spec);
+            let device = spec_path
+                .as_ref()
+                .and_then(|p| fs::canonicalize(p).ok())
+                .map(|p| p.to_string_lossy().to_string());
+            let mounted_at = device.as_ref().and_then(|d| mounted.get(d)).map(|m| m.as_str());
+            let status = fstab_entry_status(&entry, spec_path.is_some(), device.as_deref(), mounted_at);
+            let noauto = entry.options.split(',').any(|o| o == "noauto");
+
+            json!({
+                "spec": entry.spec,
+                "mountpoint": entry.mountpoint,
+                "fstype": entry.fstype,
+                "options": entry.options,
+                "pass": entry.pass,
+                "noauto": noauto,
+                "device": device,
+                "mounted_at": mounted_at,
+                "status": status,
+                "mismatch": status == "missing" || status == "mounted_elsewhere",
+            })
+        })
+        .collect();
+
+    let mismatches = entries.iter().filter(|e| e["mismatch"] == true).count();
+    Ok(json!({"entries": entries, "mismatches": mismatches}))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_fstab_entries() -> Result<serde_json::Value, String> {
+    Err("fstab checks are only supported on Linux".to_string())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nvme_controller_name() {
+        assert_eq!(nvme_controller_name("nvme0n1").as_deref(), Some("nvme0"));
+        assert_eq!(nvme_controller_name("/dev/nvme12n1p3").as_deref(), Some("nvme12"));
+        assert_eq!(nvme_controller_name("nvme1").as_deref(), Some("nvme1"));
+        assert_eq!(nvme_controller_name("sda"), None);
+        assert_eq!(nvme_controller_name("nvme"), None);
+    }
+
+    #[test]
+    fn test_parse_nvme_smart_log() {
+        let smart = json!({
+            "critical_warning": 0,
+            "temperature": 310,
+            "avail_spare": 100,
+            "percent_used": 3,
+            "data_units_read": 2_000_000,
+            "data_units_written": "4,000,000",
+            "unsafe_shutdowns": 12,
+        });
+        let health = parse_nvme_smart_log(&smart);
+        assert_eq!(health["percentage_used"], 3);
+        assert_eq!(health["available_spare"], 100);
+        assert_eq!(health["unsafe_shutdowns"], 12);
+        assert_eq!(health["data_read_tb"].as_f64(), Some(1.02));
+        assert_eq!(health["data_written_tb"].as_f64(), Some(2.05));
+        assert_eq!(health["temperature_c"].as_f64(), Some(37.0));
+    }
+
+    #[test]
+    fn test_parse_nvme_namespaces_filters_controller() {
+        let list = json!({"Devices": [
+            {"DevicePath": "/dev/nvme0n1", "NameSpace": 1, "PhysicalSize": 512110190592u64, "UsedBytes": 100},
+            {"DevicePath": "/dev/nvme1n1", "NameSpace": 1, "PhysicalSize": 1, "UsedBytes": 1},
+        ]});
+        let namespaces = parse_nvme_namespaces(&list, "nvme0");
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0]["name"], "nvme0n1");
+        assert_eq!(namespaces[0]["capacity_bytes"], 512110190592u64);
+    }
+
+    #[test]
+    fn test_get_nvme_info_rejects_non_nvme() {
+        assert!(get_nvme_info("sda".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_eject_device_rejects_invalid_names() {
+        assert!(eject_device("".to_string()).is_err());
+        assert!(eject_device("sda; rm -rf /".to_string()).is_err());
+        assert!(eject_device("doesnotexist0".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_eject_device_refuses_root_disk() {
+        let mounts = fs::read_to_string("/proc/self/mountinfo")
+            .map(|c| super::super::devices::parse_mountinfo(&c))
+            .unwrap_or_default();
+        for disk in root_disks(&mounts) {
+            if std::path::Path::new("/sys/block").join(&disk).exists() {
+                let result = eject_device(disk.clone());
+                assert!(result.is_err(), "ejecting the root disk {} should be refused", disk);
+            }
+        }
+    }
+
+    #[test]
+    fn test_processes_using_mount_nonexistent() {
+        assert!(processes_using_mount("/nonexistent/gantry/mount").is_empty());
+    }
+
+    #[test]
+    fn test_parse_fstab() {
+        let sample = "# /etc/fstab\n\
+                      UUID=1234-ABCD  /boot/efi  vfat  umask=0077  0  1\n\
+                      \n\
+                      LABEL=My\\040Data /mnt/data ext4 defaults,noauto 0 2\n\
+                      tmpfs /tmp tmpfs\n";
+        let entries = parse_fstab(sample);
+        assert_eq!(entries.len(), 3, "comments and blank lines should be skipped");
+        assert_eq!(entries[0].spec, "UUID=1234-ABCD");
+        assert_eq!(entries[0].pass, 1);
+        assert_eq!(entries[1].spec, "LABEL=My Data", "octal escapes should be decoded");
+        assert_eq!(entries[2].options, "defaults", "missing options default to defaults");
+    }
+
+    #[test]
+    fn test_fstab_spec_path() {
+        assert_eq!(fstab_spec_path("UUID=1234-ABCD").as_deref(), Some("/dev/disk/by-uuid/1234-ABCD"));
+        assert_eq!(fstab_spec_path("LABEL=My Data").as_deref(), Some("/dev/disk/by-label/My\\x20Data"));
+        assert_eq!(fstab_spec_path("/dev/sda1").as_deref(), Some("/dev/sda1"));
+        assert_eq!(fstab_spec_path("tmpfs"), None);
+        assert_eq!(fstab_spec_path("server:/export"), None);
+    }
+
+    #[test]
+    fn test_fstab_entry_status() {
+        let entry = FstabEntry {
+            spec: "UUID=abc".to_string(),
+            mountpoint: "/mnt/data".to_string(),
+            fstype: "ext4".to_string(),
+            options: "defaults".to_string(),
+            pass: 2,
+        };
+        assert_eq!(fstab_entry_status(&entry, false, None, None), "virtual");
+        assert_eq!(fstab_entry_status(&entry, true, None, None), "missing");
+        assert_eq!(fstab_entry_status(&entry, true, Some("/dev/sdb1"), None), "not_mounted");
+        assert_eq!(fstab_entry_status(&entry, true, Some("/dev/sdb1"), Some("/mnt/data")), "mounted");
+        assert_eq!(fstab_entry_status(&entry, true, Some("/dev/sdb1"), Some("/media/x")), "mounted_elsewhere");
+    }
+
+    fn write(root: &std::path::Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_block_devices_from_sysfs() {
+        let sys = std::env::temp_dir().join(format!("gantry-sysfs-{}", uuid::Uuid::new_v4()));
+        write(&sys, "block/nvme0n1/device/model", "Samsung SSD 980 1TB                     \n");
+        write(&sys, "block/nvme0n1/device/serial", "S64ANS0T123456\n");
+        write(&sys, "block/nvme0n1/device/transport", "pcie\n");
+        write(&sys, "block/sda/device/vendor", "ATA     \n");
+        write(&sys, "block/sda/device/model", "Samsung SSD 860 \n");
+        write(&sys, "block/vda/serial", "vol-1234\n");
+        write(&sys, "class/block/nvme0n1p2/dev", "259:2\n");
+        write(&sys, "class/block/vda/dev", "252:0\n");
+        write(&sys, "block/vda/device/vendor", "0x1af4\n");
+        write(&sys, "class/hwmon/hwmon1/device/block/sda/size", "0\n");
+        fs::create_dir_all(sys.join("class/hwmon/hwmon2/device/nvme0n1")).unwrap();
+
+        let nvme = read_block_device(&sys, "nvme0n1p2").unwrap();
+        assert_eq!(nvme.display_name, "Samsung SSD 980 1TB (nvme0n1p2)");
+        assert_eq!(nvme.serial.as_deref(), Some("S64ANS0T123456"));
+        assert_eq!(nvme.transport.as_deref(), Some("nvme"));
+
+        let sata = read_block_device(&sys, "sda1").unwrap();
+        assert_eq!(sata.display_name, "Samsung SSD 860 (sda1)", "the ATA vendor is left out");
+        assert_eq!(sata.transport.as_deref(), Some("sata"));
+
+        let virtio = read_block_device(&sys, "vda").unwrap();
+        assert_eq!(virtio.display_name, "Virtio disk (vda)");
+        assert_eq!((virtio.serial.as_deref(), virtio.transport.as_deref()), (Some("vol-1234"), Some("virtio")));
+
+        assert_eq!(read_block_device(&sys, "dm-0"), None);
+        assert_eq!(
+            block_device_key(&sys, "nvme0n1p2"),
+            Some(("259:2".to_string(), Some("S64ANS0T123456".to_string())))
+        );
+        assert_eq!(block_device_key(&sys, "vda"), Some(("252:0".to_string(), Some("vol-1234".to_string()))));
+        assert_eq!(block_device_key(&sys, "dm-0"), None);
+        assert_eq!(hwmon_block_device(&sys.join("class/hwmon/hwmon1")).as_deref(), Some("sda"));
+        assert_eq!(hwmon_block_device(&sys.join("class/hwmon/hwmon2")).as_deref(), Some("nvme0n1"));
+        let _ = fs::remove_dir_all(&sys);
+    }
+
+    #[test]
+    fn test_parent_disk() {
+        assert_eq!(parent_disk("nvme0n1p12"), "nvme0n1");
+        assert_eq!(parent_disk("nvme0n1"), "nvme0n1");
+        assert_eq!(parent_disk("mmcblk0p1"), "mmcblk0");
+        assert_eq!(parent_disk("sdb3"), "sdb");
+        assert_eq!(parent_disk("loop0"), "loop0");
+    }
+}
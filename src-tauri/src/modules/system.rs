@@ -1,33 +1,66 @@
+use serde::Serialize;
 use serde_json::json;
 use sysinfo::{Disks, Networks, System};
-use std::sync::Mutex;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
 use std::sync::OnceLock;
 use std::process::Command;
 use std::fs;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use super::settings::TemperatureUnit;
 
 static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
 static NETWORKS: OnceLock<Mutex<Networks>> = OnceLock::new();
 static DISKS: OnceLock<Mutex<Disks>> = OnceLock::new();
 static CPU_MODEL: OnceLock<String> = OnceLock::new();
+static CPU_TOPOLOGY: OnceLock<CpuTopology> = OnceLock::new();
+static PLATFORM_CAPABILITIES: OnceLock<serde_json::Value> = OnceLock::new();
+static THERMAL_HISTORY: Mutex<BTreeMap<String, ThermalSeries>> = Mutex::new(BTreeMap::new());
+static DISK_HISTORY: Mutex<BTreeMap<String, DiskSeries>> = Mutex::new(BTreeMap::new());
 
-fn get_system() -> &'static Mutex<System> {
-    SYSTEM.get_or_init(|| {
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        Mutex::new(sys)
-    })
+const DISK_SAMPLE_INTERVAL: Duration = Duration::from_secs(300);
+const DISK_HISTORY_KEEP_SECS: i64 = 7 * 24 * 3600;
+
+const CLEANUP_MIN_FRACTION: f64 = 0.01;
+
+const GROWTH_MIN_SPAN_SECS: i64 = 3600;
+
+const GROWTH_ALERT_HOURS: f64 = 24.0;
+
+#[cfg(target_os = "linux")]
+const EFFICIENCY_CORE_FRACTION: f64 = 0.85;
+
+fn new_system() -> System {
+    let mut sys = System::new_all();
+    sys.refresh_all();
+    sys
 }
 
-fn get_networks() -> &'static Mutex<Networks> {
-    NETWORKS.get_or_init(|| {
-        Mutex::new(Networks::new_with_refreshed_list())
-    })
+// A poisoned handle may be half refreshed, so it is rebuilt instead of every later command
+// panicking too.
+fn lock_or_rebuild<T>(mutex: &'static Mutex<T>, rebuild: fn() -> T) -> MutexGuard<'static, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            let mut guard = poisoned.into_inner();
+            *guard = rebuild();
+            mutex.clear_poison();
+            guard
+        }
+    }
 }
 
-fn get_disks() -> &'static Mutex<Disks> {
-    DISKS.get_or_init(|| {
-        Mutex::new(Disks::new_with_refreshed_list())
-    })
+fn get_system() -> MutexGuard<'static, System> {
+    lock_or_rebuild(SYSTEM.get_or_init(|| Mutex::new(new_system())), new_system)
+}
+
+fn get_networks() -> MutexGuard<'static, Networks> {
+    lock_or_rebuild(NETWORKS.get_or_init(|| Mutex::new(Networks::new_with_refreshed_list())), Networks::new_with_refreshed_list)
+}
+
+fn get_disks() -> MutexGuard<'static, Disks> {
+    lock_or_rebuild(DISKS.get_or_init(|| Mutex::new(Disks::new_with_refreshed_list())), Disks::new_with_refreshed_list)
 }
 
 #[cfg(target_os = "linux")]
@@ -60,9 +93,162 @@ fn get_cpu_model() -> &'static str {
     })
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogicalCpu {
+    pub(crate) cpu: u32,
+    pub(crate) core_id: Option<u32>,
+    pub(crate) package_id: Option<u32>,
+    pub(crate) core_type: Option<CoreType>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct CpuTopology {
+    #[serde(skip)]
+    pub(crate) cpus: Vec<LogicalCpu>,
+    pub(crate) sockets: usize,
+    pub(crate) cores: usize,
+    pub(crate) cores_per_socket: usize,
+    pub(crate) threads_per_core: usize,
+    pub(crate) performance_cores: Option<usize>,
+    pub(crate) efficiency_cores: Option<usize>,
+}
+
+impl CpuTopology {
+    fn from_cpus(cpus: Vec<LogicalCpu>) -> Self {
+        // A CPU without a core id is a core of its own.
+        let mut cores: BTreeMap<_, (usize, Option<CoreType>)> = BTreeMap::new();
+        for cpu in &cpus {
+            let core = cores.entry((cpu.package_id, cpu.core_id.or(Some(cpu.cpu)))).or_default();
+            core.0 += 1;
+            core.1 = core.1.or(cpu.core_type);
+        }
+        let sockets = cpus.iter().map(|c| c.package_id).collect::<std::collections::BTreeSet<_>>().len().max(1);
+        let hybrid = cores.values().any(|(_, t)| t.is_some());
+        let of_type = |kind| hybrid.then(|| cores.values().filter(|(_, t)| *t == Some(kind)).count());
+        CpuTopology {
+            sockets,
+            cores: cores.len().max(1),
+            cores_per_socket: (cores.len() / sockets).max(1),
+            threads_per_core: cores.values().map(|(threads, _)| *threads).max().unwrap_or(1),
+            performance_cores: of_type(CoreType::Performance),
+            efficiency_cores: of_type(CoreType::Efficiency),
+            cpus,
+        }
+    }
+
+    pub(crate) fn cpu(&self, id: u32) -> Option<&LogicalCpu> {
+        self.cpus.iter().find(|c| c.cpu == id)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(list: &str) -> Vec<u32> {
+    list.trim()
+        .split(',')
+        .filter_map(|part| match part.split_once('-') {
+            Some((start, end)) => Some((start.parse().ok()?..=end.parse().ok()?).collect::<Vec<u32>>()),
+            None => part.parse().ok().map(|cpu| vec![cpu]),
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn core_types_by(values: &BTreeMap<u32, u64>) -> BTreeMap<u32, CoreType> {
+    let max = values.values().copied().max().unwrap_or(0) as f64;
+    let slow = |v: u64| (v as f64) < max * EFFICIENCY_CORE_FRACTION;
+    if !values.values().any(|&v| slow(v)) {
+        return BTreeMap::new();
+    }
+    values
+        .iter()
+        .map(|(&cpu, &v)| (cpu, if slow(v) { CoreType::Efficiency } else { CoreType::Performance }))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_cpu_topology(sys: &std::path::Path) -> CpuTopology {
+    let cpu_dir = sys.join("devices/system/cpu");
+    let mut ids: Vec<u32> = fs::read_dir(&cpu_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+        .filter(|id| cpu_dir.join(format!("cpu{}/topology", id)).is_dir())
+        .collect();
+    ids.sort_unstable();
+    let read = |id: u32, file: &str| -> Option<u64> {
+        fs::read_to_string(cpu_dir.join(format!("cpu{}/{}", id, file))).ok()?.trim().parse().ok()
+    };
+    let pmu_cpus = |pmu: &str| fs::read_to_string(sys.join("devices").join(pmu).join("cpus")).ok().map(|l| parse_cpu_list(&l));
+    let by = |file: &str| -> Option<BTreeMap<u32, u64>> {
+        let values: BTreeMap<u32, u64> = ids.iter().filter_map(|&id| Some((id, read(id, file)?))).collect();
+        (!values.is_empty() && values.len() == ids.len()).then_some(values)
+    };
+    let types: BTreeMap<u32, CoreType> = match (pmu_cpus("cpu_core"), pmu_cpus("cpu_atom")) {
+        (Some(performance), Some(efficiency)) => performance
+            .into_iter()
+            .map(|cpu| (cpu, CoreType::Performance))
+            .chain(efficiency.into_iter().map(|cpu| (cpu, CoreType::Efficiency)))
+            .collect(),
+        _ => by("cpu_capacity")
+            .or_else(|| by("cpufreq/cpuinfo_max_freq"))
+            .map(|values| core_types_by(&values))
+            .unwrap_or_default(),
+    };
+    let cpus = ids
+        .iter()
+        .map(|&cpu| LogicalCpu {
+            cpu,
+            core_id: read(cpu, "topology/core_id").map(|v| v as u32),
+            package_id: read(cpu, "topology/physical_package_id").map(|v| v as u32),
+            core_type: types.get(&cpu).copied(),
+        })
+        .collect();
+    CpuTopology::from_cpus(cpus)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn cpu_topology() -> &'static CpuTopology {
+    CPU_TOPOLOGY.get_or_init(|| read_cpu_topology(std::path::Path::new("/sys")))
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn cpu_topology() -> &'static CpuTopology {
+    CPU_TOPOLOGY.get_or_init(|| {
+        let sysctl = |key: &str| -> Option<usize> {
+            let output = Command::new("sysctl").args(["-n", key]).output().ok()?;
+            String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+        };
+        let logical = sysctl("hw.logicalcpu").unwrap_or(1).max(1);
+        let physical = sysctl("hw.physicalcpu").unwrap_or(logical).max(1);
+        let sockets = sysctl("hw.packages").unwrap_or(1).max(1);
+        let hybrid = sysctl("hw.nperflevels") == Some(2);
+        let level = |n: u32| hybrid.then(|| sysctl(&format!("hw.perflevel{}.physicalcpu", n))).flatten();
+        CpuTopology {
+            cpus: (0..logical as u32)
+                .map(|cpu| LogicalCpu { cpu, core_id: None, package_id: None, core_type: None })
+                .collect(),
+            sockets,
+            cores: physical,
+            cores_per_socket: physical / sockets,
+            threads_per_core: (logical / physical).max(1),
+            performance_cores: level(0),
+            efficiency_cores: level(1),
+        }
+    })
+}
+
 #[tauri::command]
 pub fn get_system_overview() -> Result<serde_json::Value, String> {
-    let mut sys = get_system().lock().unwrap();
+    let mut sys = get_system();
     sys.refresh_all();
 
     let cpus: Vec<_> = sys
@@ -127,7 +313,10 @@ fn get_gpu_info() -> serde_json::Value {
     }
 
     if let Ok(entries) = fs::read_dir("/sys/class/drm") {
-        for entry in entries.flatten() {
+        // Sorted so get_gpu_details can address cards by the same index.
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
             let name = entry.file_name().to_string_lossy().to_string();
             if !name.starts_with("card") || name.contains('-') {
                 continue;
@@ -243,6 +432,265 @@ fn get_gpu_info() -> serde_json::Value {
     if gpus.is_empty() { json!(null) } else { json!(gpus) }
 }
 
+#[cfg(target_os = "linux")]
+fn parse_smi_value(field: &str) -> Option<f64> {
+    field.trim().parse::<f64>().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_pp_dpm(content: &str) -> (Option<u32>, Option<u32>) {
+    let mut current = None;
+    let mut max = None;
+    for line in content.lines() {
+        let Some((_, rest)) = line.split_once(':') else { continue };
+        let mhz = rest
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.to_lowercase().trim_end_matches("mhz").parse::<u32>().ok());
+        let Some(mhz) = mhz else { continue };
+        if rest.contains('*') {
+            current = Some(mhz);
+        }
+        max = max.max(Some(mhz));
+    }
+    (current, max)
+}
+
+#[cfg(target_os = "linux")]
+struct DrmClient {
+    pdev: String,
+    client_id: String,
+    vram_bytes: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_drm_fdinfo(content: &str) -> Option<DrmClient> {
+    let mut pdev = None;
+    let mut client_id = None;
+    let mut vram_bytes = 0u64;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim() {
+            "drm-pdev" => pdev = Some(value.to_string()),
+            "drm-client-id" => client_id = Some(value.to_string()),
+            k if k.starts_with("drm-memory-vram") || k.starts_with("drm-total-vram") => {
+                let mut parts = value.split_whitespace();
+                let amount: u64 = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+                let multiplier = match parts.next() {
+                    Some("KiB") => 1024,
+                    Some("MiB") => 1024 * 1024,
+                    Some("GiB") => 1024 * 1024 * 1024,
+                    _ => 1,
+                };
+                vram_bytes = vram_bytes.max(amount * multiplier);
+            }
+            _ => {}
+        }
+    }
+    Some(DrmClient { pdev: pdev?, client_id: client_id?, vram_bytes })
+}
+
+#[cfg(target_os = "linux")]
+fn drm_processes(pci_slot: &str) -> Vec<serde_json::Value> {
+    let mut processes = Vec::new();
+    let Ok(entries) = fs::read_dir("/proc") else { return processes };
+    for entry in entries.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        let Ok(fdinfos) = fs::read_dir(entry.path().join("fdinfo")) else { continue };
+
+        // Several fds can share one DRM client; count each client once.
+        let mut clients: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+        for fdinfo in fdinfos.flatten() {
+            let Ok(content) = fs::read_to_string(fdinfo.path()) else { continue };
+            if let Some(client) = parse_drm_fdinfo(&content) {
+                if client.pdev == pci_slot {
+                    clients.insert(client.client_id, client.vram_bytes);
+                }
+            }
+        }
+        if clients.is_empty() {
+            continue;
+        }
+
+        let name = fs::read_to_string(entry.path().join("comm"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        processes.push(json!({
+            "pid": pid,
+            "name": name,
+            "memory_used": clients.values().sum::<u64>(),
+        }));
+    }
+    processes
+}
+
+#[cfg(target_os = "linux")]
+fn nvidia_gpu_count() -> usize {
+    Command::new("nvidia-smi")
+        .args(["--query-gpu=index", "--format=csv,noheader"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().filter(|l| !l.trim().is_empty()).count())
+        .unwrap_or(0)
+}
+
+#[cfg(target_os = "linux")]
+fn nvidia_gpu_details(index: usize) -> Result<serde_json::Value, String> {
+    let idx = index.to_string();
+    let output = Command::new("nvidia-smi")
+        .args([
+            "-i", &idx,
+            "--query-gpu=name,clocks.gr,clocks.max.gr,clocks.mem,clocks.max.mem,power.draw,power.limit,pcie.link.width.current,pcie.link.width.max,pcie.link.gen.current,pcie.link.gen.max,utilization.encoder,utilization.decoder",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run nvidia-smi: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("nvidia-smi failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let f: Vec<&str> = stdout.lines().next().unwrap_or("").split(',').map(|s| s.trim()).collect();
+    if f.len() < 13 {
+        return Err("Unexpected nvidia-smi output".to_string());
+    }
+
+    let processes: Vec<serde_json::Value> = Command::new("nvidia-smi")
+        .args(["-i", &idx, "--query-compute-apps=pid,process_name,used_memory", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let p: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+                    if p.len() < 3 {
+                        return None;
+                    }
+                    Some(json!({
+                        "pid": p[0].parse::<u32>().ok()?,
+                        "name": p[1],
+                        "memory_used": parse_smi_value(p[2]).map(|mb| mb as u64 * 1024 * 1024),
+                    }))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(json!({
+        "index": index,
+        "name": f[0],
+        "vendor": "NVIDIA",
+        "core_clock_mhz": parse_smi_value(f[1]),
+        "core_clock_max_mhz": parse_smi_value(f[2]),
+        "memory_clock_mhz": parse_smi_value(f[3]),
+        "memory_clock_max_mhz": parse_smi_value(f[4]),
+        "power_draw_watts": parse_smi_value(f[5]),
+        "power_limit_watts": parse_smi_value(f[6]),
+        "pcie_link_width": parse_smi_value(f[7]),
+        "pcie_link_width_max": parse_smi_value(f[8]),
+        "pcie_link_gen": parse_smi_value(f[9]),
+        "pcie_link_gen_max": parse_smi_value(f[10]),
+        "encoder_usage": parse_smi_value(f[11]),
+        "decoder_usage": parse_smi_value(f[12]),
+        "processes": processes,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+fn drm_gpu_details(index: usize, card_path: &std::path::Path, vendor: &str) -> serde_json::Value {
+    let device_path = card_path.join("device");
+    let read = |path: std::path::PathBuf| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+    let (mut core_clock, mut core_max) = read(device_path.join("pp_dpm_sclk"))
+        .map(|c| parse_pp_dpm(&c))
+        .unwrap_or((None, None));
+    let (memory_clock, memory_max) = read(device_path.join("pp_dpm_mclk"))
+        .map(|c| parse_pp_dpm(&c))
+        .unwrap_or((None, None));
+    if vendor == "Intel" {
+        core_clock = read(card_path.join("gt_cur_freq_mhz")).and_then(|v| v.parse().ok());
+        core_max = read(card_path.join("gt_max_freq_mhz")).and_then(|v| v.parse().ok());
+    }
+
+    // hwmon reports power in microwatts.
+    let mut power_draw: Option<f64> = None;
+    let mut power_limit: Option<f64> = None;
+    if let Ok(hwmons) = fs::read_dir(device_path.join("hwmon")) {
+        for hwmon in hwmons.flatten() {
+            let uw = |file: &str| read(hwmon.path().join(file)).and_then(|v| v.parse::<f64>().ok()).map(|v| v / 1_000_000.0);
+            power_draw = power_draw.or_else(|| uw("power1_average")).or_else(|| uw("power1_input"));
+            power_limit = power_limit.or_else(|| uw("power1_cap"));
+        }
+    }
+
+    let pci_slot = fs::canonicalize(&device_path)
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+
+    json!({
+        "index": index,
+        "name": get_gpu_name_from_pci(&device_path),
+        "vendor": vendor,
+        "core_clock_mhz": core_clock,
+        "core_clock_max_mhz": core_max,
+        "memory_clock_mhz": memory_clock,
+        "memory_clock_max_mhz": memory_max,
+        "power_draw_watts": power_draw,
+        "power_limit_watts": power_limit,
+        "pcie_link_width": read(device_path.join("current_link_width")),
+        "pcie_link_width_max": read(device_path.join("max_link_width")),
+        "pcie_link_speed": read(device_path.join("current_link_speed")),
+        "pcie_link_speed_max": read(device_path.join("max_link_speed")),
+        "encoder_usage": null,
+        "decoder_usage": null,
+        "processes": if pci_slot.is_empty() { vec![] } else { drm_processes(&pci_slot) },
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_gpu_details(index: usize) -> Result<serde_json::Value, String> {
+    let nvidia_count = nvidia_gpu_count();
+    if index < nvidia_count {
+        return nvidia_gpu_details(index);
+    }
+
+    let mut entries: Vec<_> = fs::read_dir("/sys/class/drm")
+        .map(|d| d.flatten().collect())
+        .unwrap_or_default();
+    entries.sort_by_key(|e| e.file_name());
+
+    let cards: Vec<(std::path::PathBuf, &str)> = entries
+        .iter()
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.starts_with("card") && !name.contains('-')
+        })
+        .filter_map(|e| {
+            let vendor = fs::read_to_string(e.path().join("device/vendor")).unwrap_or_default();
+            match vendor.trim() {
+                "0x1002" => Some((e.path(), "AMD")),
+                "0x8086" => Some((e.path(), "Intel")),
+                _ => None,
+            }
+        })
+        .collect();
+
+    let (card_path, vendor) = cards
+        .get(index - nvidia_count)
+        .ok_or_else(|| format!("GPU {} not found", index))?;
+    Ok(drm_gpu_details(index, card_path, vendor))
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn get_gpu_details(_index: usize) -> Result<serde_json::Value, String> {
+    Err("GPU details are only supported on Linux".to_string())
+}
+
 #[cfg(target_os = "linux")]
 fn resolve_hwmon_device_name(hwmon_path: &std::path::Path, driver_name: &str) -> String {
     if driver_name == "nvme" {
@@ -281,7 +729,12 @@ fn get_thermal_info() -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
             let hwmon_id = path.file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
-            let device_name = resolve_hwmon_device_name(&path, &name);
+            let drive = super::storage::hwmon_block_device(&path).and_then(|b| super::storage::block_device_info(&b));
+            let device_name = drive
+                .as_ref()
+                .and_then(|d| d.model.clone())
+                .unwrap_or_else(|| resolve_hwmon_device_name(&path, &name));
+            let display_name = drive.map(|d| d.display_name);
 
             for i in 1..=32 {
                 let input_path = path.join(format!("temp{}_input", i));
@@ -298,6 +751,7 @@ fn get_thermal_info() -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
                             "sensor": name,
                             "device_id": hwmon_id,
                             "device_name": device_name,
+                            "display_name": display_name,
                             "celsius": (temp_c * 10.0).round() / 10.0,
                         }));
                     }
@@ -316,6 +770,7 @@ fn get_thermal_info() -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
                         "label": label,
                         "sensor": name,
                         "device_id": hwmon_id,
+                        "display_name": display_name,
                         "rpm": rpm,
                     }));
                 }
@@ -331,8 +786,26 @@ fn get_thermal_info() -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
     (vec![], vec![])
 }
 
+fn apply_temperature_unit(temperatures: &mut [serde_json::Value], gpus: &mut serde_json::Value, unit: TemperatureUnit) {
+    for temp in temperatures.iter_mut() {
+        if let Some(celsius) = temp["celsius"].as_f64() {
+            temp["value"] = json!(unit.convert(celsius));
+            temp["unit"] = json!(unit.symbol());
+        }
+    }
+    for gpu in gpus.as_array_mut().into_iter().flatten() {
+        let celsius = gpu["temperature"].as_f64();
+        gpu["temperature_value"] = json!(celsius.map(|c| unit.convert(c)));
+        gpu["temperature_unit"] = json!(unit.symbol());
+    }
+}
+
+fn display_name(name: &str) -> String {
+    super::storage::block_device_info(name).map_or_else(|| name.trim_start_matches("/dev/").to_string(), |d| d.display_name)
+}
+
 fn get_network_stats() -> serde_json::Value {
-    let mut nets = get_networks().lock().unwrap();
+    let mut nets = get_networks();
     nets.refresh();
 
     let stats: Vec<_> = nets.iter().map(|(name, data)| {
@@ -369,6 +842,7 @@ fn get_disk_io() -> Vec<serde_json::Value> {
 
                 io_stats.push(json!({
                     "name": name,
+                    "display_name": display_name(name),
                     "read_bytes": read_bytes,
                     "write_bytes": write_bytes,
                     "io_ms": io_ms,
@@ -385,6 +859,78 @@ fn get_disk_io() -> Vec<serde_json::Value> {
     vec![]
 }
 
+#[cfg(target_os = "linux")]
+fn parse_proc_swaps(content: &str) -> Vec<serde_json::Value> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [path, kind, size, used, priority] = parts[..] else { return None };
+            Some(json!({
+                // Spaces in a swap file's path are escaped as \040.
+                "path": path.replace("\\040", " "),
+                "kind": kind,
+                "size": size.parse::<u64>().ok()? * 1024,
+                "used": used.parse::<u64>().ok()? * 1024,
+                "priority": priority.parse::<i64>().ok()?,
+            }))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_zram_stats(mm_stat: &str, comp_algorithm: Option<&str>) -> Option<serde_json::Value> {
+    let fields: Vec<u64> = mm_stat.split_whitespace().map_while(|f| f.parse().ok()).collect();
+    let (&orig, &compr, &mem_used) = (fields.first()?, fields.get(1)?, fields.get(2)?);
+    let algorithm = comp_algorithm.and_then(|a| {
+        a.split_whitespace().find_map(|alg| alg.strip_prefix('[').and_then(|alg| alg.strip_suffix(']')))
+    });
+    Some(json!({
+        "orig_data_size": orig,
+        "compr_data_size": compr,
+        "mem_used_total": mem_used,
+        "ratio": if compr > 0 { Some((orig as f64 / compr as f64 * 100.0).round() / 100.0) } else { None },
+        "algorithm": algorithm,
+    }))
+}
+
+#[cfg(target_os = "linux")]
+fn get_swap_info() -> (Vec<serde_json::Value>, serde_json::Value) {
+    let read = |path: &str| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let mut devices = read("/proc/swaps").map(|c| parse_proc_swaps(&c)).unwrap_or_default();
+    for device in &mut devices {
+        let zram = device["path"]
+            .as_str()
+            .and_then(|p| p.strip_prefix("/dev/"))
+            .filter(|name| name.starts_with("zram"))
+            .and_then(|name| {
+                let mm_stat = read(&format!("/sys/block/{}/mm_stat", name))?;
+                parse_zram_stats(&mm_stat, read(&format!("/sys/block/{}/comp_algorithm", name)).as_deref())
+            });
+        device["zram"] = json!(zram);
+    }
+
+    let zswap = match read("/sys/module/zswap/parameters/enabled").as_deref() {
+        Some("Y") => {
+            let number = |path: &str| read(path).and_then(|v| v.parse::<u64>().ok());
+            json!({
+                "compressor": read("/sys/module/zswap/parameters/compressor"),
+                "max_pool_percent": number("/sys/module/zswap/parameters/max_pool_percent"),
+                "pool_total_size": number("/sys/kernel/debug/zswap/pool_total_size"),
+                "stored_pages": number("/sys/kernel/debug/zswap/stored_pages"),
+            })
+        }
+        _ => serde_json::Value::Null,
+    };
+    (devices, zswap)
+}
+
+#[cfg(target_os = "macos")]
+fn get_swap_info() -> (Vec<serde_json::Value>, serde_json::Value) {
+    (vec![], serde_json::Value::Null)
+}
+
 #[cfg(target_os = "linux")]
 fn get_load_average() -> (f64, f64, f64) {
     if let Ok(content) = fs::read_to_string("/proc/loadavg") {
@@ -555,9 +1101,342 @@ pub fn get_platform() -> &'static str {
     std::env::consts::OS
 }
 
+fn distro_family(id: &str, id_like: &str) -> Option<&'static str> {
+    let families: [(&str, &[&str]); 4] = [
+        ("debian", &["debian", "ubuntu"]),
+        ("rhel", &["rhel", "fedora", "centos"]),
+        ("arch", &["arch"]),
+        ("suse", &["suse", "opensuse"]),
+    ];
+    std::iter::once(id)
+        .chain(id_like.split_whitespace())
+        .find_map(|candidate| {
+            let candidate = candidate.trim_matches('"');
+            families
+                .iter()
+                .find(|(_, ids)| ids.iter().any(|known| candidate == *known || candidate.starts_with(&format!("{}-", known))))
+                .map(|(family, _)| *family)
+        })
+}
+
+pub(crate) fn has_binary(name: &str) -> bool {
+    let extra = ["/usr/bin", "/usr/sbin", "/usr/local/bin", "/opt/homebrew/bin", "/snap/bin"];
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .chain(extra.iter().map(std::path::PathBuf::from))
+        .any(|dir| dir.join(name).is_file())
+}
+
+fn detect_package_managers() -> Vec<&'static str> {
+    let managers: [(&str, &[&str], &[&str]); 6] = [
+        ("apt", &["apt-get"], &["/etc/apt"]),
+        ("dnf", &["dnf", "dnf5"], &["/etc/dnf"]),
+        ("pacman", &["pacman"], &["/etc/pacman.conf"]),
+        ("flatpak", &["flatpak"], &[]),
+        ("snap", &["snap"], &[]),
+        ("brew", &["brew"], &[]),
+    ];
+    managers
+        .iter()
+        .filter(|(_, binaries, configs)| {
+            binaries.iter().any(|b| has_binary(b)) && configs.iter().all(|c| std::path::Path::new(c).exists())
+        })
+        .map(|(name, _, _)| *name)
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn detect_platform_capabilities() -> serde_json::Value {
+    let os_release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let field = |key: &str| {
+        os_release
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .unwrap_or_default()
+    };
+    let distro_id = field("ID");
+
+    let init_system = if std::path::Path::new("/run/systemd/system").is_dir() {
+        "systemd".to_string()
+    } else if std::path::Path::new("/run/openrc").is_dir() {
+        "openrc".to_string()
+    } else {
+        fs::read_to_string("/proc/1/comm").map(|c| c.trim().to_string()).unwrap_or_else(|_| "unknown".to_string())
+    };
+
+    json!({
+        "os": std::env::consts::OS,
+        "distro_id": if distro_id.is_empty() { None } else { Some(distro_id.clone()) },
+        "distro_family": distro_family(&distro_id, &field("ID_LIKE")),
+        "package_managers": detect_package_managers(),
+        "init_system": init_system,
+        "desktop_environment": std::env::var("XDG_CURRENT_DESKTOP").ok().filter(|d| !d.is_empty()),
+        "container": super::capabilities::capabilities().container,
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn detect_platform_capabilities() -> serde_json::Value {
+    json!({
+        "os": std::env::consts::OS,
+        "distro_id": "macos",
+        "distro_family": null,
+        "package_managers": detect_package_managers(),
+        "init_system": "launchd",
+        "desktop_environment": "Aqua",
+        "container": null,
+    })
+}
+
+#[tauri::command]
+pub fn get_platform_capabilities() -> serde_json::Value {
+    PLATFORM_CAPABILITIES.get_or_init(detect_platform_capabilities).clone()
+}
+
+pub(crate) struct ResourceSample {
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    pub temperatures: Vec<(String, f64)>,
+    pub disk_free: Vec<(String, f64)>,
+    pub disk_days_until_full: Vec<(String, f64)>,
+}
+
+pub(crate) fn sample_resources() -> ResourceSample {
+    let (cpu_percent, memory_percent) = {
+        let mut sys = get_system();
+        sys.refresh_cpu_all();
+        sys.refresh_memory();
+        let cpu_count = sys.cpus().len().max(1) as f64;
+        let cpu = sys.cpus().iter().map(|c| c.cpu_usage() as f64).sum::<f64>() / cpu_count;
+        let memory = sys.used_memory() as f64 * 100.0 / sys.total_memory().max(1) as f64;
+        (cpu, memory)
+    };
+
+    let disk_free = {
+        let mut disks = get_disks();
+        disks.refresh_list();
+        disks
+            .iter()
+            .filter(|d| d.total_space() > 0 && !d.is_read_only())
+            .map(|d| {
+                let free = d.available_space() as f64 * 100.0 / d.total_space() as f64;
+                (d.mount_point().to_string_lossy().to_string(), free)
+            })
+            .collect()
+    };
+
+    let temperatures = get_thermal_info()
+        .0
+        .iter()
+        .filter_map(|t| {
+            let (sensor, label) = (t["sensor"].as_str()?, t["label"].as_str()?);
+            let name = if label.starts_with(sensor) { label.to_string() } else { format!("{} {}", sensor, label) };
+            Some((name, t["celsius"].as_f64()?))
+        })
+        .collect();
+
+    let disk_days_until_full = {
+        let since = chrono::Utc::now().timestamp() - (GROWTH_ALERT_HOURS * 3600.0) as i64;
+        lock_or_rebuild(&DISK_HISTORY, BTreeMap::new)
+            .iter()
+            .filter_map(|(mount, series)| Some((mount.clone(), disk_growth(series, mount, since)?.days_until_full?)))
+            .collect()
+    };
+
+    ResourceSample { cpu_percent, memory_percent, temperatures, disk_free, disk_days_until_full }
+}
+
+#[derive(Debug, Default)]
+struct ThermalSeries {
+    kind: &'static str,
+    label: String,
+    device_id: String,
+    points: VecDeque<(i64, f64)>,
+}
+
+struct ThermalReading {
+    id: String,
+    kind: &'static str,
+    label: String,
+    device_id: String,
+    value: f64,
+}
+
+fn thermal_readings(temps: &[serde_json::Value], fans: &[serde_json::Value]) -> Vec<ThermalReading> {
+    let mut hottest: BTreeMap<&str, ThermalReading> = BTreeMap::new();
+    for temp in temps {
+        let (Some(device), Some(celsius)) = (temp["device_id"].as_str(), temp["celsius"].as_f64()) else {
+            continue;
+        };
+        if hottest.get(device).is_some_and(|r| r.value >= celsius) {
+            continue;
+        }
+        let label = temp["device_name"].as_str().filter(|n| !n.is_empty()).or(temp["sensor"].as_str()).unwrap_or(device);
+        hottest.insert(device, ThermalReading {
+            id: format!("temp:{}", device),
+            kind: "temperature",
+            label: label.to_string(),
+            device_id: device.to_string(),
+            value: celsius,
+        });
+    }
+    let fans = fans.iter().filter_map(|fan| {
+        let (device, label, rpm) = (fan["device_id"].as_str()?, fan["label"].as_str()?, fan["rpm"].as_f64()?);
+        Some(ThermalReading {
+            id: format!("fan:{}:{}", device, label),
+            kind: "fan",
+            label: label.to_string(),
+            device_id: device.to_string(),
+            value: rpm,
+        })
+    });
+    hottest.into_values().chain(fans).collect()
+}
+
+fn record_thermal(history: &mut BTreeMap<String, ThermalSeries>, readings: Vec<ThermalReading>, now: i64, keep_ms: i64) {
+    for reading in readings {
+        let series = history.entry(reading.id).or_default();
+        series.kind = reading.kind;
+        series.label = reading.label;
+        series.device_id = reading.device_id;
+        series.points.push_back((now, reading.value));
+    }
+    history.retain(|_, series| {
+        while series.points.front().is_some_and(|(at, _)| now - at > keep_ms) {
+            series.points.pop_front();
+        }
+        !series.points.is_empty()
+    });
+}
+
+#[tauri::command]
+pub fn get_thermal_history(sensor_id: Option<String>, seconds: Option<u64>) -> Result<serde_json::Value, String> {
+    let unit = super::settings::temperature_unit();
+    let since = seconds.map_or(i64::MIN, |s| chrono::Utc::now().timestamp_millis() - s as i64 * 1000);
+    let history = lock_or_rebuild(&THERMAL_HISTORY, BTreeMap::new);
+    if let Some(id) = sensor_id.as_deref().filter(|id| !history.contains_key(*id)) {
+        return Err(format!("No history for sensor {}", id));
+    }
+    let series: Vec<_> = history
+        .iter()
+        .filter(|(id, _)| sensor_id.as_deref().map_or(true, |wanted| wanted == id.as_str()))
+        .map(|(id, series)| {
+            let temperature = series.kind == "temperature";
+            let points: Vec<_> = series
+                .points
+                .iter()
+                .filter(|(at, _)| *at >= since)
+                .map(|&(at, value)| json!([at, if temperature { (unit.convert(value) * 10.0).round() / 10.0 } else { value }]))
+                .collect();
+            json!({
+                "id": id,
+                "kind": series.kind,
+                "label": series.label,
+                "device_id": series.device_id,
+                "unit": if temperature { unit.symbol() } else { "RPM" },
+                "points": points,
+            })
+        })
+        .collect();
+    Ok(json!(series))
+}
+
+#[derive(Debug, Default)]
+struct DiskSeries {
+    total: u64,
+    points: VecDeque<(i64, u64)>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiskGrowth {
+    mount_point: String,
+    total_space: u64,
+    available_space: u64,
+    samples: usize,
+    since: i64,
+    bytes_per_hour: f64,
+    days_until_full: Option<f64>,
+}
+
+fn disk_readings() -> Vec<(String, u64, u64)> {
+    let mut disks = get_disks();
+    disks.refresh_list();
+    disks
+        .iter()
+        .filter(|d| d.total_space() > 0 && !d.is_read_only() && !d.is_removable())
+        .map(|d| (d.mount_point().to_string_lossy().to_string(), d.total_space(), d.available_space()))
+        .collect()
+}
+
+fn record_disks(history: &mut BTreeMap<String, DiskSeries>, readings: Vec<(String, u64, u64)>, now: i64, keep_secs: i64) {
+    history.retain(|mount, _| readings.iter().any(|(m, _, _)| m == mount));
+    for (mount, total, available) in readings {
+        let series = history.entry(mount).or_default();
+        series.total = total;
+        series.points.push_back((now, available));
+        while series.points.front().is_some_and(|(at, _)| now - at > keep_secs) {
+            series.points.pop_front();
+        }
+    }
+}
+
+fn disk_growth(series: &DiskSeries, mount: &str, since: i64) -> Option<DiskGrowth> {
+    let points: Vec<(i64, u64)> = series.points.iter().copied().filter(|(at, _)| *at >= since).collect();
+    let &(_, available) = points.last()?;
+    let cleanup = series.total as f64 * CLEANUP_MIN_FRACTION;
+    let start = (1..points.len())
+        .rev()
+        .find(|&i| points[i].1 as f64 - points[i - 1].1 as f64 >= cleanup)
+        .unwrap_or(0);
+    let stretch = &points[start..];
+    let n = stretch.len() as f64;
+    let t0 = stretch[0].0;
+    let mean_t = stretch.iter().map(|(at, _)| (at - t0) as f64).sum::<f64>() / n;
+    let mean_a = stretch.iter().map(|(_, a)| *a as f64).sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (at, a) in stretch {
+        let dt = (at - t0) as f64 - mean_t;
+        cov += dt * (*a as f64 - mean_a);
+        var += dt * dt;
+    }
+    // Free space falling is the disk filling up.
+    let bytes_per_hour = if var > 0.0 { -cov / var * 3600.0 } else { 0.0 };
+    let span = stretch[stretch.len() - 1].0 - t0;
+    let days_until_full = (span >= GROWTH_MIN_SPAN_SECS && bytes_per_hour > 0.0)
+        .then(|| available as f64 / bytes_per_hour / 24.0);
+    Some(DiskGrowth {
+        mount_point: mount.to_string(),
+        total_space: series.total,
+        available_space: available,
+        samples: stretch.len(),
+        since: t0,
+        bytes_per_hour,
+        days_until_full,
+    })
+}
+
+#[tauri::command]
+pub fn get_disk_growth(mount: String, hours: Option<f64>) -> Result<serde_json::Value, String> {
+    let hours = hours.filter(|h| *h > 0.0).unwrap_or(GROWTH_ALERT_HOURS);
+    let since = chrono::Utc::now().timestamp() - (hours * 3600.0) as i64;
+    let history = lock_or_rebuild(&DISK_HISTORY, BTreeMap::new);
+    history
+        .get(&mount)
+        .and_then(|series| disk_growth(series, &mount, since))
+        .map(|growth| json!(growth))
+        .ok_or_else(|| format!("No disk history for {}; removable and read-only filesystems aren't tracked", mount))
+}
+
 #[tauri::command]
 pub fn get_resources() -> Result<serde_json::Value, String> {
-    let mut sys = get_system().lock().unwrap();
+    resources_with(get_thermal_info())
+}
+
+fn resources_with((mut temperatures, fans): (Vec<serde_json::Value>, Vec<serde_json::Value>)) -> Result<serde_json::Value, String> {
+    let mut sys = get_system();
     sys.refresh_cpu_all();
     sys.refresh_memory();
 
@@ -565,11 +1444,19 @@ pub fn get_resources() -> Result<serde_json::Value, String> {
     let cpu_count = sys.cpus().len().max(1) as f32;
     let cpu = cpu_total / cpu_count;
 
-    let per_cpu: Vec<_> = sys.cpus().iter().map(|c| json!({
-        "name": c.name(),
-        "usage": c.cpu_usage(),
-        "frequency": c.frequency(),
-    })).collect();
+    let topology = cpu_topology();
+    let per_cpu: Vec<_> = sys.cpus().iter().enumerate().map(|(i, c)| {
+        let id = c.name().trim_start_matches("cpu").parse().unwrap_or(i as u32);
+        let cpu = topology.cpu(id);
+        json!({
+            "name": c.name(),
+            "usage": c.cpu_usage(),
+            "frequency": c.frequency(),
+            "core_id": cpu.and_then(|c| c.core_id),
+            "package_id": cpu.and_then(|c| c.package_id),
+            "core_type": cpu.and_then(|c| c.core_type),
+        })
+    }).collect();
 
     let (load1, load5, load15) = get_load_average();
     let uptime = get_uptime_seconds();
@@ -581,45 +1468,190 @@ pub fn get_resources() -> Result<serde_json::Value, String> {
         "swap_used": sys.used_swap(),
     });
 
-    let mut disks = get_disks().lock().unwrap();
+    let mut disks = get_disks();
     disks.refresh_list();
     let disk_info: Vec<_> = disks
         .iter()
         .filter(|d| d.total_space() > 0)
         .map(|d| json!({
             "name": d.name().to_string_lossy(),
+            "display_name": display_name(&d.name().to_string_lossy()),
             "mount_point": d.mount_point().to_string_lossy(),
             "total_space": d.total_space(),
             "available_space": d.available_space(),
         }))
         .collect();
 
-    let gpu = get_gpu_info();
-    let (temperatures, fans) = get_thermal_info();
+    let (swap_devices, zswap) = get_swap_info();
+    let mut gpu = get_gpu_info();
+    apply_temperature_unit(&mut temperatures, &mut gpu, super::settings::temperature_unit());
     let network = get_network_stats();
     let disk_io = get_disk_io();
 
-    Ok(json!({
+    let mut resources = json!({
         "cpu": cpu,
         "cpu_count": sys.cpus().len(),
         "cpu_model": get_cpu_model(),
         "per_cpu": per_cpu,
+        "topology": topology,
         "load_avg": [load1, load5, load15],
         "uptime": uptime,
         "memory": memory,
+        "swap_devices": swap_devices,
+        "zswap": zswap,
         "disks": disk_info,
         "gpu": gpu,
         "temperatures": temperatures,
         "fans": fans,
         "network": network,
         "disk_io": disk_io,
-    }))
+    });
+    mark_resource_gaps(&mut resources);
+    Ok(resources)
+}
+
+#[cfg(target_os = "linux")]
+fn mark_resource_gaps(resources: &mut serde_json::Value) {
+    let caps = super::capabilities::capabilities();
+    let empty = |section: &str| resources[section].as_array().map_or(true, Vec::is_empty);
+    let thermal = empty("temperatures") && empty("fans") && !caps.readable("/sys/class/hwmon");
+    let gpu = empty("gpu") && !caps.readable("/sys/class/drm") && !caps.has_tool("nvidia-smi");
+    if thermal {
+        super::capabilities::mark_degraded(resources, "thermal", caps.reason("Temperatures and fans can't be read: /sys/class/hwmon isn't available"));
+    }
+    if gpu {
+        super::capabilities::mark_degraded(resources, "gpu", caps.reason("GPUs can't be listed: /sys/class/drm isn't available and nvidia-smi isn't installed"));
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn mark_resource_gaps(_resources: &mut serde_json::Value) {}
+
+pub fn start_resource_stream(app: tauri::AppHandle) {
+    super::tasks::spawn("resource-stream", move |task| {
+        let mut disks_recorded: Option<std::time::Instant> = None;
+        while !task.stopped() {
+            let interval = super::settings::live_refresh_interval();
+            std::thread::sleep(interval.unwrap_or(Duration::from_secs(1)));
+            let thermal = get_thermal_info();
+            let keep_ms = super::settings::history_retention().as_millis() as i64;
+            let readings = thermal_readings(&thermal.0, &thermal.1);
+            record_thermal(&mut lock_or_rebuild(&THERMAL_HISTORY, BTreeMap::new), readings, chrono::Utc::now().timestamp_millis(), keep_ms);
+            if disks_recorded.map_or(true, |at| at.elapsed() >= DISK_SAMPLE_INTERVAL) {
+                let readings = disk_readings();
+                record_disks(&mut lock_or_rebuild(&DISK_HISTORY, BTreeMap::new), readings, chrono::Utc::now().timestamp(), DISK_HISTORY_KEEP_SECS);
+                disks_recorded = Some(std::time::Instant::now());
+            }
+            if interval.is_none() || !app.webview_windows().values().any(|w| w.is_visible().unwrap_or(false)) {
+                continue;
+            }
+            if let Ok(resources) = resources_with(thermal) {
+                let _ = app.emit("resources-update", resources);
+            }
+        }
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_apply_temperature_unit() {
+        let mut temps = vec![json!({"label": "Package id 0", "celsius": 50.0})];
+        let mut gpus = json!([{"name": "GPU", "temperature": 40.0}, {"name": "iGPU", "temperature": null}]);
+        apply_temperature_unit(&mut temps, &mut gpus, TemperatureUnit::Fahrenheit);
+        assert_eq!(temps[0]["celsius"], 50.0, "celsius is kept");
+        assert_eq!(temps[0]["value"], 122.0);
+        assert_eq!(temps[0]["unit"], "°F");
+        assert_eq!(gpus[0]["temperature_value"], 104.0);
+        assert!(gpus[1]["temperature_value"].is_null());
+
+        let mut none = json!(null);
+        apply_temperature_unit(&mut [], &mut none, TemperatureUnit::Celsius);
+        assert!(none.is_null());
+    }
+
+    #[test]
+    fn test_thermal_history_follows_sensors_by_id() {
+        let temps = |usb: bool| {
+            let mut temps = vec![
+                json!({"label": "Core 0", "sensor": "coretemp", "device_id": "hwmon1", "device_name": "", "celsius": 50.0}),
+                json!({"label": "Package", "sensor": "coretemp", "device_id": "hwmon1", "device_name": "", "celsius": 62.5}),
+            ];
+            if usb {
+                temps.push(json!({"label": "Composite", "sensor": "drivetemp", "device_id": "hwmon4", "device_name": "", "celsius": 35.0}));
+            }
+            temps
+        };
+        let fans = [json!({"label": "CPU Fan", "sensor": "nct6775", "device_id": "hwmon2", "rpm": 1200})];
+        let readings = thermal_readings(&temps(true), &fans);
+        let summary: Vec<(&str, &str, f64)> = readings.iter().map(|r| (r.id.as_str(), r.label.as_str(), r.value)).collect();
+        assert_eq!(summary, vec![("temp:hwmon1", "coretemp", 62.5), ("temp:hwmon4", "drivetemp", 35.0), ("fan:hwmon2:CPU Fan", "CPU Fan", 1200.0)]);
+
+        let mut history = BTreeMap::new();
+        record_thermal(&mut history, thermal_readings(&temps(true), &fans), 0, 10_000);
+        record_thermal(&mut history, thermal_readings(&temps(false), &fans), 5_000, 10_000);
+        record_thermal(&mut history, thermal_readings(&temps(true), &fans), 8_000, 10_000);
+        assert_eq!(history["temp:hwmon4"].points, VecDeque::from([(0, 35.0), (8_000, 35.0)]), "an unplugged drive only leaves a gap");
+        assert_eq!(history["temp:hwmon1"].points.len(), 3);
+
+        record_thermal(&mut history, thermal_readings(&temps(false), &[]), 19_000, 10_000);
+        assert_eq!(history["temp:hwmon1"].points.len(), 1, "points past retention are dropped");
+        assert!(!history.contains_key("fan:hwmon2:CPU Fan"), "a series with nothing left is removed");
+        assert!(!history.contains_key("temp:hwmon4"));
+    }
+
+    #[test]
+    fn test_disk_growth_measures_from_last_cleanup() {
+        let hour = 3600;
+        let mut history = BTreeMap::new();
+        // 1 000 bytes used an hour, with 20 000 freed at hour 5.
+        for i in 0..10i64 {
+            let freed = if i >= 5 { 20_000 } else { 0 };
+            let readings = vec![("/".to_string(), 100_000, (50_000 - 1_000 * i + freed) as u64), ("/mnt/data".to_string(), 8_000, 4_000)];
+            record_disks(&mut history, readings, i * hour, DISK_HISTORY_KEEP_SECS);
+        }
+        let growth = disk_growth(&history["/"], "/", 0).unwrap();
+        assert_eq!(growth.since, 5 * hour, "the cleanup starts a new stretch");
+        assert_eq!(growth.samples, 5);
+        assert!((growth.bytes_per_hour - 1_000.0).abs() < 1e-6);
+        assert_eq!(growth.available_space, 61_000);
+        assert!((growth.days_until_full.unwrap() - 61.0 / 24.0).abs() < 1e-6);
+
+        let growth = disk_growth(&history["/"], "/", 2 * hour).unwrap();
+        assert_eq!(growth.since, 5 * hour);
+        let growth = disk_growth(&history["/"], "/", 9 * hour).unwrap();
+        assert_eq!(growth.days_until_full, None, "one sample can't be projected");
+        assert!(disk_growth(&history["/"], "/", 10 * hour).is_none());
+
+        let flat = disk_growth(&history["/mnt/data"], "/mnt/data", 0).unwrap();
+        assert_eq!((flat.bytes_per_hour, flat.days_until_full), (0.0, None));
+
+        record_disks(&mut history, vec![("/".to_string(), 100_000, 60_000)], 10 * hour, DISK_HISTORY_KEEP_SECS);
+        assert!(!history.contains_key("/mnt/data"), "an unmounted disk loses its history");
+        record_disks(&mut history, vec![("/".to_string(), 100_000, 60_000)], 10 * hour + DISK_HISTORY_KEEP_SECS, DISK_HISTORY_KEEP_SECS);
+        assert_eq!(history["/"].points.len(), 2, "old points are dropped");
+    }
+
+    #[test]
+    fn test_distro_family() {
+        assert_eq!(distro_family("ubuntu", "debian"), Some("debian"));
+        assert_eq!(distro_family("linuxmint", "ubuntu debian"), Some("debian"));
+        assert_eq!(distro_family("fedora", ""), Some("rhel"));
+        assert_eq!(distro_family("rocky", "\"rhel centos fedora\""), Some("rhel"));
+        assert_eq!(distro_family("opensuse-tumbleweed", "opensuse suse"), Some("suse"));
+        assert_eq!(distro_family("endeavouros", "arch"), Some("arch"));
+        assert_eq!(distro_family("nixos", ""), None);
+    }
+
+    #[test]
+    fn test_get_platform_capabilities() {
+        let caps = get_platform_capabilities();
+        assert_eq!(caps["os"], std::env::consts::OS);
+        assert!(caps["package_managers"].is_array(), "package_managers should be a list");
+    }
+
     #[test]
     fn test_get_os_info() {
         let result = get_os_info();
@@ -647,6 +1679,20 @@ mod tests {
         assert!(res["uptime"].as_u64().unwrap_or(0) > 0, "uptime should be > 0");
     }
 
+    #[test]
+    fn test_poisoned_lock_is_rebuilt() {
+        static COUNTER: Mutex<u32> = Mutex::new(0);
+        *lock_or_rebuild(&COUNTER, || 7) = 5;
+        let _ = std::thread::spawn(|| {
+            let _guard = COUNTER.lock().unwrap();
+            panic!("poison the counter");
+        })
+        .join();
+        assert!(COUNTER.is_poisoned());
+        assert_eq!(*lock_or_rebuild(&COUNTER, || 7), 7);
+        assert!(!COUNTER.is_poisoned());
+    }
+
     #[test]
     fn test_get_system_overview() {
         let result = get_system_overview();
@@ -675,4 +1721,94 @@ mod tests {
             platform
         );
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_pp_dpm() {
+        let sample = "0: 500Mhz\n1: 1200Mhz *\n2: 2100Mhz\n";
+        assert_eq!(parse_pp_dpm(sample), (Some(1200), Some(2100)));
+        assert_eq!(parse_pp_dpm(""), (None, None));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_drm_fdinfo() {
+        let sample = "pos:\t0\nflags:\t02100002\ndrm-driver:\tamdgpu\n\
+                      drm-pdev:\t0000:03:00.0\ndrm-client-id:\t42\n\
+                      drm-memory-vram:\t2048 KiB\ndrm-memory-gtt:\t512 KiB\n";
+        let client = parse_drm_fdinfo(sample).expect("should parse a DRM client");
+        assert_eq!(client.pdev, "0000:03:00.0");
+        assert_eq!(client.client_id, "42");
+        assert_eq!(client.vram_bytes, 2048 * 1024);
+        assert!(parse_drm_fdinfo("pos:\t0\nflags:\t02\n").is_none(), "non-DRM fds should be skipped");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cpu_topology_from_sysfs() {
+        let sys = std::env::temp_dir().join(format!("gantry-sysfs-{}", uuid::Uuid::new_v4()));
+        let write = |path: &str, content: &str| {
+            let path = sys.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        // Two P-cores with two threads each and two E-cores, as on a hybrid
+        // Intel laptop without the PMU lists.
+        for (cpu, core, max_freq) in [(0, 0, 4_700_000), (1, 0, 4_700_000), (2, 4, 4_600_000), (3, 4, 4_600_000), (4, 8, 3_500_000), (5, 9, 3_500_000)] {
+            write(&format!("devices/system/cpu/cpu{}/topology/core_id", cpu), &format!("{}\n", core));
+            write(&format!("devices/system/cpu/cpu{}/topology/physical_package_id", cpu), "0\n");
+            write(&format!("devices/system/cpu/cpu{}/cpufreq/cpuinfo_max_freq", cpu), &format!("{}\n", max_freq));
+        }
+        write("devices/system/cpu/cpu6/online", "0\n");
+        write("devices/system/cpu/cpufreq/boost", "1\n");
+
+        let topology = read_cpu_topology(&sys);
+        assert_eq!(topology.cpus.len(), 6, "offline CPUs and other entries are skipped");
+        assert_eq!((topology.sockets, topology.cores, topology.threads_per_core), (1, 4, 2));
+        assert_eq!((topology.performance_cores, topology.efficiency_cores), (Some(2), Some(2)));
+        assert_eq!(topology.cpu(2).unwrap().core_type, Some(CoreType::Performance), "a favoured core is not hybrid");
+        assert_eq!(topology.cpu(5).unwrap().core_type, Some(CoreType::Efficiency));
+
+        // The PMU lists win over frequencies.
+        write("devices/cpu_core/cpus", "0-3\n");
+        write("devices/cpu_atom/cpus", "4,5\n");
+        assert_eq!(read_cpu_topology(&sys).efficiency_cores, Some(2));
+        let _ = fs::remove_dir_all(&sys);
+
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), [0, 1, 2, 3, 8, 10, 11]);
+        let uniform = CpuTopology::from_cpus(
+            (0..4).map(|cpu| LogicalCpu { cpu, core_id: Some(cpu % 2), package_id: Some(cpu / 2), core_type: None }).collect(),
+        );
+        assert_eq!((uniform.sockets, uniform.cores_per_socket, uniform.threads_per_core), (2, 2, 1));
+        assert_eq!(uniform.performance_cores, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_swaps_and_zram() {
+        let swaps = "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n\
+                     /dev/zram0                              partition\t8388604\t\t1024\t\t100\n\
+                     /swap\\040file                           file\t\t2097148\t\t0\t\t-2\n";
+        let devices = parse_proc_swaps(swaps);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0]["path"], "/dev/zram0");
+        assert_eq!(devices[0]["size"], 8388604u64 * 1024);
+        assert_eq!(devices[0]["used"], 1024 * 1024);
+        assert_eq!(devices[1]["path"], "/swap file");
+        assert_eq!(devices[1]["priority"], -2);
+
+        let stats = parse_zram_stats("  4096000  1024000  1200000        0  1200000      120        0       12\n", Some("lzo lzo-rle [zstd]"))
+            .unwrap();
+        assert_eq!(stats["ratio"], 4.0);
+        assert_eq!(stats["mem_used_total"], 1_200_000);
+        assert_eq!(stats["algorithm"], "zstd");
+        assert_eq!(parse_zram_stats("0 0 0 0 0 0 0 0", None).unwrap()["ratio"], serde_json::Value::Null, "empty device");
+        assert!(parse_zram_stats("", None).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_get_gpu_details_out_of_range() {
+        assert!(get_gpu_details(usize::MAX).is_err());
+    }
 }
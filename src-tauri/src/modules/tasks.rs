@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+const CHILD_GRACE: Duration = Duration::from_secs(1);
+const SIGNAL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+struct Entry {
+    name: String,
+    stop: Arc<AtomicBool>,
+    groups: Vec<u32>,
+}
+
+static TASKS: Mutex<BTreeMap<u64, Entry>> = Mutex::new(BTreeMap::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+static SIGNALLED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) struct Task {
+    id: u64,
+    stop: Arc<AtomicBool>,
+}
+
+impl Task {
+    pub(crate) fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    // Nothing spawns a child from a task yet; a follower that runs `journalctl -f` or
+    // `udevadm monitor` registers its process group here so shutdown can end it.
+    #[allow(dead_code)]
+    pub(crate) fn watch_child(&self, pid: u32) {
+        if let Some(entry) = lock().get_mut(&self.id) {
+            entry.groups.push(pid);
+        }
+    }
+}
+
+impl Drop for Task {
+    fn drop(&mut self) {
+        lock().remove(&self.id);
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, BTreeMap<u64, Entry>> {
+    TASKS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) fn register(name: &str) -> Task {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let stop = Arc::new(AtomicBool::new(SHUT_DOWN.load(Ordering::Relaxed)));
+    lock().insert(id, Entry { name: name.to_string(), stop: stop.clone(), groups: Vec::new() });
+    Task { id, stop }
+}
+
+pub(crate) fn spawn<F>(name: &str, f: F) -> Arc<AtomicBool>
+where
+    F: FnOnce(Task) + Send + 'static,
+{
+    let task = register(name);
+    let stop = task.stop_flag();
+    std::thread::spawn(move || f(task));
+    stop
+}
+
+fn group_alive(pid: u32) -> bool {
+    unsafe { libc::kill(-(pid as i32), 0) == 0 }
+}
+
+fn stop_tasks() -> Vec<String> {
+    let entries: Vec<Entry> = std::mem::take(&mut *lock()).into_values().collect();
+    let mut groups = Vec::new();
+    for entry in &entries {
+        entry.stop.store(true, Ordering::Relaxed);
+        for &pid in &entry.groups {
+            unsafe { libc::kill(-(pid as i32), libc::SIGTERM) };
+            groups.push(pid);
+        }
+    }
+    let deadline = Instant::now() + CHILD_GRACE;
+    while groups.iter().any(|&pid| group_alive(pid)) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    for pid in groups {
+        if group_alive(pid) {
+            unsafe { libc::kill(-(pid as i32), libc::SIGKILL) };
+        }
+    }
+    entries.into_iter().map(|e| e.name).collect()
+}
+
+pub(crate) fn shutdown(app: &tauri::AppHandle) {
+    if SHUT_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let stopped = stop_tasks();
+    super::scripts::kill_running_scripts();
+    super::logging::stop_following();
+    super::metrics::stop();
+    super::admin::close_session();
+    if let Some(window) = app.get_webview_window("main") {
+        super::settings::save_window_state_now(&window);
+    }
+    let _ = super::logging::log("info", "app", &format!("Shutting down, stopped {} background tasks", stopped.len()));
+    super::logging::flush();
+    super::audit::flush();
+    super::instance::release();
+}
+
+extern "C" fn on_signal(_: libc::c_int) {
+    SIGNALLED.store(true, Ordering::SeqCst);
+}
+
+pub(crate) fn exit_on_signals(app: tauri::AppHandle) {
+    let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    unsafe {
+        libc::signal(libc::SIGTERM, handler);
+        libc::signal(libc::SIGINT, handler);
+    }
+    spawn("signals", move |task| {
+        while !task.stopped() {
+            if SIGNALLED.load(Ordering::SeqCst) {
+                app.exit(0);
+                return;
+            }
+            std::thread::sleep(SIGNAL_POLL_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn test_stop_tasks_ends_follower_children() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        // A follower like `journalctl -f`: reads its child until it exits.
+        let stop = spawn("fake-follower", move |task| {
+            let mut child = Command::new("sleep")
+                .arg("60")
+                .stdout(Stdio::piped())
+                .process_group(0)
+                .spawn()
+                .unwrap();
+            task.watch_child(child.id());
+            tx.send(child.id()).unwrap();
+            let mut out = String::new();
+            let _ = child.stdout.take().unwrap().read_to_string(&mut out);
+            tx.send(child.wait().unwrap().signal().unwrap_or(0) as u32).unwrap();
+        });
+        let pid = rx.recv().unwrap();
+
+        assert!(stop_tasks().contains(&"fake-follower".to_string()));
+        assert!(stop.load(Ordering::Relaxed));
+        let signal = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(signal, libc::SIGTERM as u32);
+        assert!(!group_alive(pid), "child is gone");
+    }
+}
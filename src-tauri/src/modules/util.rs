@@ -0,0 +1,63 @@
+use super::error::GantryError;
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Runs with LC_ALL=C, as callers parse the output.
+pub(crate) fn run_tool(program: &str, args: &[&str]) -> Result<String, GantryError> {
+    let output = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| GantryError::spawn(program, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(GantryError::from_output(program, &output))
+    }
+}
+
+pub(crate) struct PrivateTemp(PathBuf);
+
+impl PrivateTemp {
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for PrivateTemp {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Staging for files a privileged copy reads back. The directory is 0700 and the name is new, so no
+// other user can plant the file beforehand or swap it in between.
+pub(crate) fn private_temp(prefix: &str, content: &[u8]) -> Result<PrivateTemp, GantryError> {
+    let dir = super::appdata::config_path("tmp");
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    let path = dir.join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(&path)?;
+    let temp = PrivateTemp(path);
+    file.write_all(content)?;
+    Ok(temp)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn read_sysfs_string(path: &std::path::Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn find_brew() -> Option<PathBuf> {
+    ["/opt/homebrew/bin/brew", "/usr/local/bin/brew"]
+        .iter()
+        .map(PathBuf::from)
+        .find(|p| p.exists())
+}
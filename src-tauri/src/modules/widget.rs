@@ -0,0 +1,65 @@
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+
+const WIDGET_LABEL: &str = "monitor-widget";
+
+const WIDGET_WIDTH: f64 = 240.0;
+const WIDGET_HEIGHT: f64 = 150.0;
+
+static MAIN_CLOSED: AtomicBool = AtomicBool::new(false);
+
+// Async because creating a window from a sync command can deadlock.
+#[tauri::command]
+pub async fn open_monitor_widget(app: AppHandle) -> Result<serde_json::Value, String> {
+    if let Some(window) = app.get_webview_window(WIDGET_LABEL) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(json!({"open": true}));
+    }
+    let window = WebviewWindowBuilder::new(&app, WIDGET_LABEL, WebviewUrl::App("index.html?view=monitor-widget".into()))
+        .title("Gantry Monitor")
+        .inner_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Could not open the monitor widget: {}", e))?;
+    super::settings::restore_widget_position(&window);
+    super::settings::track_widget_position(&window);
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if !matches!(event, WindowEvent::Destroyed) || !MAIN_CLOSED.load(Ordering::Relaxed) {
+            return;
+        }
+        if handle.get_webview_window("main").is_some_and(|main| !main.is_visible().unwrap_or(true)) {
+            handle.exit(0);
+        }
+    });
+    Ok(json!({"open": true}))
+}
+
+// While the widget is open, closing the main window only hides it, so the widget keeps running and
+// a second launch or the shortcut can bring the window back. Closing the widget afterwards quits.
+pub fn hide_main_on_close(window: &WebviewWindow) {
+    let main = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::CloseRequested { api, .. } if main.app_handle().get_webview_window(WIDGET_LABEL).is_some() => {
+            api.prevent_close();
+            let _ = main.hide();
+            MAIN_CLOSED.store(true, Ordering::Relaxed);
+        }
+        WindowEvent::Focused(true) => MAIN_CLOSED.store(false, Ordering::Relaxed),
+        _ => {}
+    });
+}
+
+#[tauri::command]
+pub fn close_monitor_widget(app: AppHandle) -> Result<serde_json::Value, String> {
+    if let Some(window) = app.get_webview_window(WIDGET_LABEL) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(json!({"open": false}))
+}
@@ -1,7 +1,7 @@
 import { useState, useEffect, Suspense, lazy } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import './index.css'
 import Layout from './components/Layout'
+import CrashNotice from './components/CrashNotice'
 import Settings from './pages/Settings'
 import Devices from './pages/Devices'
 import Processes from './pages/Processes'
@@ -10,9 +10,10 @@ import StartupApps from './pages/StartupApps'
 import Resources from './pages/Resources'
 import Logs from './pages/Logs'
 import Scripts from './pages/Scripts'
-import { PageType, AppSettings, Platform } from './types'
+import Firewall from './pages/Firewall'
+import { PageType, AppSettings, Platform, ModuleName } from './types'
 import { applyTheme } from './utils/theme'
-import { DEFAULT_PAGE } from './constants'
+import { DEFAULT_PAGE, NAVIGATION_ITEMS } from './constants'
 import { ResourceMonitorContext, useResourceMonitorProvider } from './hooks/useResourceMonitor'
 import { PlatformContext } from './hooks/usePlatform'
 
@@ -22,6 +23,7 @@ function App() {
   const [currentPage, setCurrentPage] = useState<PageType>(DEFAULT_PAGE)
   const [themeLoaded, setThemeLoaded] = useState(false)
   const [platform, setPlatform] = useState<Platform>('linux')
+  const [visibleModules, setVisibleModules] = useState<Partial<Record<ModuleName, boolean>>>({})
   const monitorData = useResourceMonitorProvider()
 
   useEffect(() => {
@@ -33,6 +35,8 @@ function App() {
     try {
       const settings = await invoke<AppSettings>('get_settings')
       applyTheme(settings.theme)
+      setVisibleModules(settings.visible_modules)
+      setCurrentPage(settings.default_page)
     } catch (err) {
       const saved = localStorage.getItem('theme')
       applyTheme(saved || 'light')
@@ -41,6 +45,23 @@ function App() {
     }
   }
 
+  const loadModules = async () => {
+    try {
+      const settings = await invoke<AppSettings>('get_settings')
+      setVisibleModules(settings.visible_modules)
+    } catch (err) {
+      console.error('Failed to load modules:', err)
+    }
+  }
+
+  // Leave a page once its module is switched off.
+  useEffect(() => {
+    const module = NAVIGATION_ITEMS.find(item => item.id === currentPage)?.module
+    if (module && visibleModules[module] === false) {
+      setCurrentPage(DEFAULT_PAGE)
+    }
+  }, [currentPage, visibleModules])
+
   const loadPlatform = async () => {
     try {
       const p = await invoke<Platform>('get_platform')
@@ -52,7 +73,7 @@ function App() {
   const renderPage = () => {
     switch (currentPage) {
       case 'settings':
-        return <Settings />
+        return <Settings onModulesChange={loadModules} />
       case 'devices':
         return <Devices />
       case 'processes':
@@ -67,6 +88,8 @@ function App() {
         return <Logs />
       case 'scripts':
         return <Scripts />
+      case 'firewall':
+        return <Firewall />
       case 'services':
         return (
           <Suspense fallback={
@@ -103,7 +126,8 @@ function App() {
   return (
     <PlatformContext.Provider value={platform}>
       <ResourceMonitorContext.Provider value={monitorData}>
-        <Layout currentPage={currentPage} onPageChange={setCurrentPage}>
+        <Layout currentPage={currentPage} onPageChange={setCurrentPage} visibleModules={visibleModules}>
+          <CrashNotice />
           {renderPage()}
         </Layout>
       </ResourceMonitorContext.Provider>
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core'
 import { logger } from '../utils/logger'
+import { RepoHealthReport, StartupAppOptions } from '../types'
 
 export async function listAptRepos() {
   try {
@@ -23,9 +24,9 @@ export async function listStartupApps() {
   }
 }
 
-export async function addStartupApp(name: string, exec: string) {
+export async function addStartupApp(name: string, exec: string, options: StartupAppOptions = {}) {
   try {
-    const result = await invoke('add_startup_app', { name, exec })
+    const result = await invoke('add_startup_app', { name, exec, ...options })
     logger.debug('addStartupApp success')
     return result
   } catch (error) {
@@ -34,9 +35,9 @@ export async function addStartupApp(name: string, exec: string) {
   }
 }
 
-export async function editStartupApp(file: string, name: string, exec: string) {
+export async function editStartupApp(file: string, name: string, exec: string, options: StartupAppOptions = {}) {
   try {
-    const result = await invoke('edit_startup_app', { file, name, exec })
+    const result = await invoke('edit_startup_app', { file, name, exec, ...options })
     logger.debug('editStartupApp success')
     return result
   } catch (error) {
@@ -67,9 +68,9 @@ export async function toggleStartupApp(file: string, enabled: boolean) {
   }
 }
 
-export async function addAptRepo(repoLine: string) {
+export async function addAptRepo(repoLine: string, autoRefresh?: boolean, signedBy?: string, validate?: boolean, force?: boolean) {
   try {
-    const result = await invoke('add_apt_repo', { repoLine })
+    const result = await invoke('add_apt_repo', { repoLine, autoRefresh, signedBy, validate, force })
     logger.debug('addAptRepo success')
     return result
   } catch (error) {
@@ -88,3 +89,278 @@ export async function deleteAptRepo(id: string) {
     throw error
   }
 }
+
+export async function refreshPackageIndex() {
+  try {
+    const result = await invoke('refresh_package_index')
+    logger.debug('refreshPackageIndex success')
+    return result
+  } catch (error) {
+    logger.error('refreshPackageIndex failed', error)
+    throw error
+  }
+}
+
+export async function listPackages(query?: string, offset?: number, limit?: number) {
+  try {
+    const result = await invoke('list_packages', { query, offset, limit })
+    logger.debug('listPackages success')
+    return result
+  } catch (error) {
+    logger.error('listPackages failed', error)
+    throw error
+  }
+}
+
+export async function getPackageDetails(name: string) {
+  try {
+    const result = await invoke('get_package_details', { name })
+    logger.debug('getPackageDetails success')
+    return result
+  } catch (error) {
+    logger.error('getPackageDetails failed', error)
+    throw error
+  }
+}
+
+export async function listUpgradablePackages() {
+  try {
+    const result = await invoke('list_upgradable_packages')
+    logger.debug('listUpgradablePackages success')
+    return result
+  } catch (error) {
+    logger.error('listUpgradablePackages failed', error)
+    throw error
+  }
+}
+
+export async function upgradePackages(names?: string[]) {
+  try {
+    const result = await invoke('upgrade_packages', { names })
+    logger.debug('upgradePackages success')
+    return result
+  } catch (error) {
+    logger.error('upgradePackages failed', error)
+    throw error
+  }
+}
+
+export async function listAptKeys() {
+  try {
+    const result = await invoke('list_apt_keys')
+    logger.debug('listAptKeys success')
+    return result
+  } catch (error) {
+    logger.error('listAptKeys failed', error)
+    throw error
+  }
+}
+
+export async function addAptKey(source: string, keyringName: string) {
+  try {
+    const result = await invoke('add_apt_key', { source, keyringName })
+    logger.debug('addAptKey success')
+    return result
+  } catch (error) {
+    logger.error('addAptKey failed', error)
+    throw error
+  }
+}
+
+export async function deleteAptKey(path: string) {
+  try {
+    const result = await invoke('delete_apt_key', { path })
+    logger.debug('deleteAptKey success')
+    return result
+  } catch (error) {
+    logger.error('deleteAptKey failed', error)
+    throw error
+  }
+}
+
+export async function addAptRepoDeb822(types: string[], uris: string[], suites: string[], components: string[], signedBy?: string, enabled?: boolean, autoRefresh?: boolean, force?: boolean) {
+  try {
+    const result = await invoke('add_apt_repo_deb822', { types, uris, suites, components, signedBy, enabled, autoRefresh, force })
+    logger.debug('addAptRepoDeb822 success')
+    return result
+  } catch (error) {
+    logger.error('addAptRepoDeb822 failed', error)
+    throw error
+  }
+}
+
+export async function addPpa(name: string, autoRefresh?: boolean, force?: boolean) {
+  try {
+    const result = await invoke('add_ppa', { name, autoRefresh, force })
+    logger.debug('addPpa success')
+    return result
+  } catch (error) {
+    logger.error('addPpa failed', error)
+    throw error
+  }
+}
+
+export async function validateAptRepo(repoLine: string) {
+  try {
+    const result = await invoke('validate_apt_repo', { repoLine })
+    logger.debug('validateAptRepo success')
+    return result
+  } catch (error) {
+    logger.error('validateAptRepo failed', error)
+    throw error
+  }
+}
+
+export async function listRepoBackups() {
+  try {
+    const result = await invoke('list_repo_backups')
+    logger.debug('listRepoBackups success')
+    return result
+  } catch (error) {
+    logger.error('listRepoBackups failed', error)
+    throw error
+  }
+}
+
+export async function restoreRepoBackup(id: string) {
+  try {
+    const result = await invoke('restore_repo_backup', { id })
+    logger.debug('restoreRepoBackup success')
+    return result
+  } catch (error) {
+    logger.error('restoreRepoBackup failed', error)
+    throw error
+  }
+}
+
+export async function listCronJobs() {
+  try {
+    const result = await invoke('list_cron_jobs')
+    logger.debug('listCronJobs success')
+    return result
+  } catch (error) {
+    logger.error('listCronJobs failed', error)
+    throw error
+  }
+}
+
+export async function addCronJob(schedule: string, command: string, comment?: string) {
+  try {
+    const result = await invoke('add_cron_job', { schedule, command, comment })
+    logger.debug('addCronJob success')
+    return result
+  } catch (error) {
+    logger.error('addCronJob failed', error)
+    throw error
+  }
+}
+
+export async function editCronJob(id: number, schedule: string, command: string, comment?: string) {
+  try {
+    const result = await invoke('edit_cron_job', { id, schedule, command, comment })
+    logger.debug('editCronJob success')
+    return result
+  } catch (error) {
+    logger.error('editCronJob failed', error)
+    throw error
+  }
+}
+
+export async function deleteCronJob(id: number) {
+  try {
+    const result = await invoke('delete_cron_job', { id })
+    logger.debug('deleteCronJob success')
+    return result
+  } catch (error) {
+    logger.error('deleteCronJob failed', error)
+    throw error
+  }
+}
+
+export async function toggleCronJob(id: number, enabled: boolean) {
+  try {
+    const result = await invoke('toggle_cron_job', { id, enabled })
+    logger.debug('toggleCronJob success')
+    return result
+  } catch (error) {
+    logger.error('toggleCronJob failed', error)
+    throw error
+  }
+}
+
+export async function listInstalledApplications() {
+  try {
+    const result = await invoke('list_installed_applications')
+    logger.debug('listInstalledApplications success')
+    return result
+  } catch (error) {
+    logger.error('listInstalledApplications failed', error)
+    throw error
+  }
+}
+
+export async function listEnvironmentEntries() {
+  try {
+    const result = await invoke('list_environment_entries')
+    logger.debug('listEnvironmentEntries success')
+    return result
+  } catch (error) {
+    logger.error('listEnvironmentEntries failed', error)
+    throw error
+  }
+}
+
+export async function setEnvironmentEntry(scope: 'system' | 'user', key: string, value: string, file?: string) {
+  try {
+    const result = await invoke('set_environment_entry', { scope, key, value, file })
+    logger.debug('setEnvironmentEntry success')
+    return result
+  } catch (error) {
+    logger.error('setEnvironmentEntry failed', error)
+    throw error
+  }
+}
+
+export async function deleteEnvironmentEntry(scope: 'system' | 'user', key: string, file?: string) {
+  try {
+    const result = await invoke('delete_environment_entry', { scope, key, file })
+    logger.debug('deleteEnvironmentEntry success')
+    return result
+  } catch (error) {
+    logger.error('deleteEnvironmentEntry failed', error)
+    throw error
+  }
+}
+
+export async function repoHealthCheck(force?: boolean) {
+  try {
+    const result = await invoke<RepoHealthReport>('repo_health_check', { force })
+    logger.debug('repoHealthCheck success')
+    return result
+  } catch (error) {
+    logger.error('repoHealthCheck failed', error)
+    throw error
+  }
+}
+
+export async function getAutoUpdateConfig() {
+  try {
+    const result = await invoke('get_auto_update_config')
+    logger.debug('getAutoUpdateConfig success')
+    return result
+  } catch (error) {
+    logger.error('getAutoUpdateConfig failed', error)
+    throw error
+  }
+}
+
+export async function setAutoUpdates(enabled: boolean, withReboot: boolean) {
+  try {
+    const result = await invoke('set_auto_updates', { enabled, withReboot })
+    logger.debug('setAutoUpdates success')
+    return result
+  } catch (error) {
+    logger.error('setAutoUpdates failed', error)
+    throw error
+  }
+}
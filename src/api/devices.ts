@@ -44,3 +44,190 @@ export async function listPciDevices() {
     throw error
   }
 }
+
+export async function getUsbDeviceDetails(bus: string, device: string) {
+  try {
+    const result = await invoke('get_usb_device_details', { bus, device })
+    logger.debug('getUsbDeviceDetails success')
+    return result
+  } catch (error) {
+    logger.error('getUsbDeviceDetails failed', error)
+    throw error
+  }
+}
+
+export async function startInputTest(path: string) {
+  try {
+    const result = await invoke('start_input_test', { path })
+    logger.debug('startInputTest success')
+    return result
+  } catch (error) {
+    logger.error('startInputTest failed', error)
+    throw error
+  }
+}
+
+export async function stopInputTest() {
+  try {
+    const result = await invoke('stop_input_test')
+    logger.debug('stopInputTest success')
+    return result
+  } catch (error) {
+    logger.error('stopInputTest failed', error)
+    throw error
+  }
+}
+
+export async function listPrinters() {
+  try {
+    const result = await invoke('list_printers')
+    logger.debug('listPrinters success')
+    return result
+  } catch (error) {
+    logger.error('listPrinters failed', error)
+    throw error
+  }
+}
+
+export async function listPrintJobs() {
+  try {
+    const result = await invoke('list_print_jobs')
+    logger.debug('listPrintJobs success')
+    return result
+  } catch (error) {
+    logger.error('listPrintJobs failed', error)
+    throw error
+  }
+}
+
+export async function cancelPrintJob(id: string) {
+  try {
+    const result = await invoke('cancel_print_job', { id })
+    logger.debug('cancelPrintJob success')
+    return result
+  } catch (error) {
+    logger.error('cancelPrintJob failed', error)
+    throw error
+  }
+}
+
+export async function listVideoDevices() {
+  try {
+    const result = await invoke('list_video_devices')
+    logger.debug('listVideoDevices success')
+    return result
+  } catch (error) {
+    logger.error('listVideoDevices failed', error)
+    throw error
+  }
+}
+
+export async function getNvmeInfo(device: string) {
+  try {
+    const result = await invoke('get_nvme_info', { device })
+    logger.debug('getNvmeInfo success')
+    return result
+  } catch (error) {
+    logger.error('getNvmeInfo failed', error)
+    throw error
+  }
+}
+
+export async function ejectDevice(name: string) {
+  try {
+    const result = await invoke('eject_device', { name })
+    logger.debug('ejectDevice success')
+    return result
+  } catch (error) {
+    logger.error('ejectDevice failed', error)
+    throw error
+  }
+}
+
+export async function getNetworkDeviceDetails(name: string) {
+  try {
+    const result = await invoke('get_network_device_details', { name })
+    logger.debug('getNetworkDeviceDetails success')
+    return result
+  } catch (error) {
+    logger.error('getNetworkDeviceDetails failed', error)
+    throw error
+  }
+}
+
+export async function scanWifiNetworks(iface?: string) {
+  try {
+    const result = await invoke('scan_wifi_networks', { interface: iface })
+    logger.debug('scanWifiNetworks success')
+    return result
+  } catch (error) {
+    logger.error('scanWifiNetworks failed', error)
+    throw error
+  }
+}
+
+export async function getWifiStatus(iface: string) {
+  try {
+    const result = await invoke('get_wifi_status', { interface: iface })
+    logger.debug('getWifiStatus success')
+    return result
+  } catch (error) {
+    logger.error('getWifiStatus failed', error)
+    throw error
+  }
+}
+
+export async function listSerialDevices() {
+  try {
+    const result = await invoke('list_serial_devices')
+    logger.debug('listSerialDevices success')
+    return result
+  } catch (error) {
+    logger.error('listSerialDevices failed', error)
+    throw error
+  }
+}
+
+export async function listPowerSupplies() {
+  try {
+    const result = await invoke('list_power_supplies')
+    logger.debug('listPowerSupplies success')
+    return result
+  } catch (error) {
+    logger.error('listPowerSupplies failed', error)
+    throw error
+  }
+}
+
+export async function getFstabEntries() {
+  try {
+    const result = await invoke('get_fstab_entries')
+    logger.debug('getFstabEntries success')
+    return result
+  } catch (error) {
+    logger.error('getFstabEntries failed', error)
+    throw error
+  }
+}
+
+export async function listThunderboltDevices() {
+  try {
+    const result = await invoke('list_thunderbolt_devices')
+    logger.debug('listThunderboltDevices success')
+    return result
+  } catch (error) {
+    logger.error('listThunderboltDevices failed', error)
+    throw error
+  }
+}
+
+export async function authorizeThunderboltDevice(id: string) {
+  try {
+    const result = await invoke('authorize_thunderbolt_device', { id })
+    logger.debug('authorizeThunderboltDevice success')
+    return result
+  } catch (error) {
+    logger.error('authorizeThunderboltDevice failed', error)
+    throw error
+  }
+}
@@ -1,24 +1,61 @@
 import { invoke } from '@tauri-apps/api/core'
-import { logger } from '../utils/logger'
+import { logger, LogLevel } from '../utils/logger'
+import { FileLogEntry, LogFileContent, LogFileInfo, LogSearchOptions, LogSearchResult, LogTail } from '../types'
 
-export async function writeLog(message: string) {
+export async function writeLog(message: string, level: LogLevel = 'info') {
   try {
-    await invoke('write_log', { message })
+    await invoke('write_log', { message, level, source: 'frontend' })
   } catch (error) {
     logger.error('Failed to write to log file', error)
     throw error
   }
 }
 
-export async function readLogFile() {
+export async function readLogFile(includeRotated = false) {
   try {
-    return await invoke('read_log_file')
+    return await invoke<LogFileContent>('read_log_file', { includeRotated })
   } catch (error) {
     logger.error('Failed to read log file', error)
     throw error
   }
 }
 
+export async function readLogEntries(includeRotated = false) {
+  try {
+    return await invoke<FileLogEntry[]>('read_log_entries', { includeRotated })
+  } catch (error) {
+    logger.error('Failed to read log entries', error)
+    throw error
+  }
+}
+
+export async function readLogTail(lines: number, beforeCursor?: number | null) {
+  try {
+    return await invoke<LogTail>('read_log_tail', { lines, beforeCursor: beforeCursor ?? null })
+  } catch (error) {
+    logger.error('Failed to read log tail', error)
+    throw error
+  }
+}
+
+export async function getLogFileInfo() {
+  try {
+    return await invoke<LogFileInfo>('log_file_info')
+  } catch (error) {
+    logger.error('Failed to read log file info', error)
+    throw error
+  }
+}
+
+export async function searchLog(query: string, options: LogSearchOptions = {}) {
+  try {
+    return await invoke<LogSearchResult>('search_log', { query, ...options })
+  } catch (error) {
+    logger.error('Failed to search log', error)
+    throw error
+  }
+}
+
 export async function clearLogFile() {
   try {
     return await invoke('clear_log_file')
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core'
 import { logger } from '../utils/logger'
+import { PlatformCapabilities } from '../types'
 
 export async function getSystemOverview() {
   try {
@@ -33,3 +34,36 @@ export async function getResources() {
     throw error
   }
 }
+
+export async function getGpuDetails(index: number) {
+  try {
+    const result = await invoke('get_gpu_details', { index })
+    logger.debug('getGpuDetails success')
+    return result
+  } catch (error) {
+    logger.error('getGpuDetails failed', error)
+    throw error
+  }
+}
+
+export async function getTpmInfo() {
+  try {
+    const result = await invoke('get_tpm_info')
+    logger.debug('getTpmInfo success')
+    return result
+  } catch (error) {
+    logger.error('getTpmInfo failed', error)
+    throw error
+  }
+}
+
+export async function getPlatformCapabilities() {
+  try {
+    const result = await invoke<PlatformCapabilities>('get_platform_capabilities')
+    logger.debug('getPlatformCapabilities success')
+    return result
+  } catch (error) {
+    logger.error('getPlatformCapabilities failed', error)
+    throw error
+  }
+}
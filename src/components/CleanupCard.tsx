@@ -0,0 +1,135 @@
+import { useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Trash2, Lock } from 'lucide-react'
+import { errorMessage } from '../utils/errors'
+
+interface CleanupCandidate {
+  id: string
+  label: string
+  system: boolean
+  bytes: number | null
+  error: string | null
+}
+
+interface CleanupResult {
+  target: string
+  success: boolean
+  bytes_freed: number
+  error: unknown
+}
+
+const formatBytes = (bytes: number): string => {
+  if (bytes >= 1024 * 1024 * 1024) return (bytes / 1024 / 1024 / 1024).toFixed(1) + ' GB'
+  if (bytes >= 1024 * 1024) return (bytes / 1024 / 1024).toFixed(0) + ' MB'
+  if (bytes >= 1024) return (bytes / 1024).toFixed(0) + ' KB'
+  return bytes + ' B'
+}
+
+// Reclaimable space with one checkbox per cleanup; each runs on its own so
+// a denied prompt only skips that one.
+export default function CleanupCard() {
+  const [candidates, setCandidates] = useState<CleanupCandidate[] | null>(null)
+  const [selected, setSelected] = useState<Set<string>>(new Set())
+  const [results, setResults] = useState<Record<string, CleanupResult>>({})
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const scan = async () => {
+    setBusy(true)
+    setError(null)
+    try {
+      const found = await invoke<CleanupCandidate[]>('get_cleanup_candidates')
+      setCandidates(found)
+      setSelected(new Set(found.filter(c => !c.system && (c.bytes ?? 0) > 0).map(c => c.id)))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const clean = async () => {
+    setBusy(true)
+    setError(null)
+    try {
+      const { results } = await invoke<{ results: CleanupResult[] }>('run_cleanup', { targets: [...selected] })
+      setResults(Object.fromEntries(results.map(r => [r.target, r])))
+      setCandidates(await invoke<CleanupCandidate[]>('get_cleanup_candidates'))
+      setSelected(new Set())
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const toggle = (id: string) => {
+    setSelected(prev => {
+      const next = new Set(prev)
+      if (next.has(id)) next.delete(id)
+      else next.add(id)
+      return next
+    })
+  }
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+      <div className="flex items-center justify-between mb-4">
+        <div className="flex items-center gap-2">
+          <Trash2 size={18} className="text-amber-500" />
+          <h2 className="text-lg font-semibold text-gray-900 dark:text-gray-100">Cleanup</h2>
+        </div>
+        <div className="flex gap-2">
+          <button
+            onClick={scan}
+            disabled={busy}
+            className="px-3 py-1.5 text-sm bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 text-gray-900 dark:text-gray-100 rounded-lg disabled:opacity-50"
+          >
+            {candidates ? 'Rescan' : 'Scan'}
+          </button>
+          {candidates && (
+            <button
+              onClick={clean}
+              disabled={busy || selected.size === 0}
+              className="px-3 py-1.5 text-sm bg-blue-600 hover:bg-blue-700 text-white rounded-lg disabled:opacity-50"
+            >
+              Clean selected
+            </button>
+          )}
+        </div>
+      </div>
+      {error && <div className="text-sm text-red-500 mb-3">{error}</div>}
+      {!candidates ? (
+        <div className="text-sm text-gray-400">Scan to see how much space the trash, caches and old logs take up.</div>
+      ) : (
+        <div className="space-y-2">
+          {candidates.map(c => {
+            const result = results[c.id]
+            return (
+              <label key={c.id} className="flex items-center gap-3 text-sm cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={selected.has(c.id)}
+                  onChange={() => toggle(c.id)}
+                  disabled={busy || c.bytes === null}
+                />
+                <span className="flex-1 text-gray-900 dark:text-gray-100">
+                  {c.label}
+                  {c.system && <Lock size={12} className="inline ml-1.5 text-gray-400" aria-label="Needs administrator rights" />}
+                </span>
+                {result && (
+                  <span className={result.success ? 'text-green-600 dark:text-green-400' : 'text-red-500'}>
+                    {result.success ? `Freed ${formatBytes(result.bytes_freed)}` : errorMessage(result.error)}
+                  </span>
+                )}
+                <span className="w-20 text-right font-mono text-gray-600 dark:text-gray-400">
+                  {c.bytes !== null ? formatBytes(c.bytes) : <span title={c.error ?? undefined}>—</span>}
+                </span>
+              </label>
+            )
+          })}
+        </div>
+      )}
+    </div>
+  )
+}
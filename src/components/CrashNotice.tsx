@@ -0,0 +1,65 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { AlertTriangle, X } from 'lucide-react'
+
+interface CrashRecord {
+  ts: string
+  version: string
+  thread: string
+  message: string
+  location: string | null
+  backtrace: string
+}
+
+const bugReport = (crash: CrashRecord) =>
+  [
+    `Gantry ${crash.version} crashed at ${crash.ts}`,
+    `Thread '${crash.thread}' panicked at ${crash.location ?? 'unknown location'}: ${crash.message}`,
+    '',
+    '```',
+    crash.backtrace.trim(),
+    '```',
+  ].join('\n')
+
+// Offers the last panic as a bug report until it is dismissed.
+export default function CrashNotice() {
+  const [crash, setCrash] = useState<CrashRecord | null>(null)
+  const [copied, setCopied] = useState(false)
+
+  useEffect(() => {
+    invoke<CrashRecord | null>('get_last_crash')
+      .then(setCrash)
+      .catch(err => console.error('Failed to read last crash:', err))
+  }, [])
+
+  if (!crash) return null
+
+  const dismiss = async () => {
+    setCrash(null)
+    await invoke('clear_last_crash').catch(err => console.error('Failed to clear last crash:', err))
+  }
+
+  const copy = async () => {
+    await navigator.clipboard.writeText(bugReport(crash))
+    setCopied(true)
+  }
+
+  return (
+    <div className="mb-6 flex items-start gap-3 rounded-xl border border-amber-300 dark:border-amber-700 bg-amber-50 dark:bg-amber-900/30 p-4">
+      <AlertTriangle size={20} className="mt-0.5 shrink-0 text-amber-600 dark:text-amber-400" />
+      <div className="flex-1 min-w-0">
+        <div className="text-sm font-medium text-gray-900 dark:text-gray-100">Gantry crashed last time</div>
+        <div className="text-sm text-gray-600 dark:text-gray-300 truncate">{crash.message}</div>
+        <button
+          onClick={copy}
+          className="mt-2 px-3 py-1.5 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700"
+        >
+          {copied ? 'Copied' : 'Copy bug report'}
+        </button>
+      </div>
+      <button onClick={dismiss} className="text-gray-400 hover:text-gray-600 dark:hover:text-gray-200" title="Dismiss">
+        <X size={16} />
+      </button>
+    </div>
+  )
+}
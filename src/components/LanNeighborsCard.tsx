@@ -0,0 +1,103 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Network, RotateCw } from 'lucide-react'
+import CopyableText from './CopyableText'
+import { errorMessage } from '../utils/errors'
+
+interface Neighbor {
+  ip: string
+  mac: string
+  interface: string
+  state: string | null
+  hostname: string | null
+}
+
+interface PingResult {
+  transmitted: number
+  received: number
+  loss_percent: number | null
+  avg_ms: number | null
+}
+
+const pingSummary = (p: PingResult) =>
+  p.received === 0 ? 'unreachable' : `${p.avg_ms?.toFixed(1) ?? '?'} ms, ${p.loss_percent?.toFixed(0) ?? '?'}% loss`
+
+// Devices on the local networks, from the neighbor table; no scanning, so
+// only hosts this machine has talked to recently show up.
+export default function LanNeighborsCard() {
+  const [neighbors, setNeighbors] = useState<Neighbor[]>([])
+  const [pings, setPings] = useState<Record<string, PingResult | 'pending' | string>>({})
+  const [loading, setLoading] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const load = async () => {
+    setLoading(true)
+    setError(null)
+    try {
+      setNeighbors(await invoke<Neighbor[]>('list_lan_neighbors', { resolve: true }))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setLoading(false)
+    }
+  }
+
+  useEffect(() => { load() }, [])
+
+  const ping = async (ip: string) => {
+    setPings(prev => ({ ...prev, [ip]: 'pending' }))
+    try {
+      const result = await invoke<PingResult>('ping_host', { address: ip, count: 4 })
+      setPings(prev => ({ ...prev, [ip]: result }))
+    } catch (err) {
+      setPings(prev => ({ ...prev, [ip]: errorMessage(err) }))
+    }
+  }
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+      <div className="flex items-center gap-2 mb-4">
+        <Network size={18} className="text-blue-500" />
+        <h2 className="text-lg font-semibold text-gray-900 dark:text-gray-100">Devices on My Network</h2>
+        <button onClick={load} disabled={loading} className="ml-auto p-1 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300 disabled:opacity-50" title="Refresh">
+          <RotateCw size={16} className={loading ? 'animate-spin' : ''} />
+        </button>
+      </div>
+      {error && <div className="text-sm text-red-500 mb-3">{error}</div>}
+      {!loading && neighbors.length === 0 && !error && (
+        <div className="text-sm text-gray-400">No neighbors known yet.</div>
+      )}
+      <div className="space-y-1">
+        {neighbors.map(n => {
+          const result = pings[n.ip]
+          return (
+            <div key={`${n.interface}-${n.ip}`} className="flex items-center gap-3 text-sm py-1">
+              <div className="flex-1 min-w-0">
+                <CopyableText value={n.ip}>
+                  <span className="font-mono text-gray-900 dark:text-gray-100">{n.ip}</span>
+                </CopyableText>
+                {n.hostname && <span className="ml-2 text-gray-500 dark:text-gray-400">{n.hostname}</span>}
+              </div>
+              <CopyableText value={n.mac}>
+                <span className="font-mono text-xs text-gray-400">{n.mac}</span>
+              </CopyableText>
+              <span className="text-xs text-gray-400 w-16">{n.interface}</span>
+              {n.state && <span className="text-xs text-gray-400 w-20">{n.state.toLowerCase()}</span>}
+              <span className="text-xs text-gray-500 dark:text-gray-400 w-36 text-right">
+                {result === undefined ? (
+                  <button onClick={() => ping(n.ip)} className="text-blue-500 hover:text-blue-600">Ping</button>
+                ) : result === 'pending' ? (
+                  'Pinging…'
+                ) : typeof result === 'string' ? (
+                  <span className="text-red-500" title={result}>failed</span>
+                ) : (
+                  <button onClick={() => ping(n.ip)} title="Ping again">{pingSummary(result)}</button>
+                )}
+              </span>
+            </div>
+          )
+        })}
+      </div>
+    </div>
+  )
+}
@@ -1,18 +1,19 @@
 import React from 'react'
 import Sidebar from './Sidebar'
-import { PageType } from '../types'
+import { ModuleName, PageType } from '../types'
 
 interface LayoutProps {
   children: React.ReactNode
   currentPage: PageType
   onPageChange: (page: PageType) => void
+  visibleModules: Partial<Record<ModuleName, boolean>>
 }
 
-export default function Layout({ children, currentPage, onPageChange }: LayoutProps) {
+export default function Layout({ children, currentPage, onPageChange, visibleModules }: LayoutProps) {
   return (
     <div className="flex h-screen bg-gray-50 dark:bg-gray-900">
       <div className="w-64 bg-gray-900 dark:bg-gray-950 overflow-hidden flex flex-col">
-        <Sidebar currentPage={currentPage} onPageChange={onPageChange} />
+        <Sidebar currentPage={currentPage} onPageChange={onPageChange} visibleModules={visibleModules} />
       </div>
       <main className="flex-1 overflow-y-auto overflow-x-hidden bg-white dark:bg-gray-900">
         <div className="p-8">
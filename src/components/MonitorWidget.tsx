@@ -0,0 +1,69 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { X } from 'lucide-react'
+import Sparkline from './Sparkline'
+import { useResourceMonitorProvider } from '../hooks/useResourceMonitor'
+import { AppSettings } from '../types'
+import { applyTheme } from '../utils/theme'
+
+const HISTORY_LENGTH = 60
+
+const formatRate = (bytesPerSec: number): string => {
+  if (bytesPerSec >= 1024 * 1024) return (bytesPerSec / 1024 / 1024).toFixed(1) + ' MB/s'
+  if (bytesPerSec >= 1024) return (bytesPerSec / 1024).toFixed(0) + ' KB/s'
+  return bytesPerSec.toFixed(0) + ' B/s'
+}
+
+function Row({ label, value, data, color }: { label: string; value: string; data: number[]; color: string }) {
+  return (
+    <div className="flex items-center justify-between gap-2" data-tauri-drag-region>
+      <div className="w-20 pointer-events-none">
+        <div className="text-[10px] uppercase tracking-wide text-gray-500 dark:text-gray-400">{label}</div>
+        <div className="text-sm font-semibold text-gray-900 dark:text-gray-100">{value}</div>
+      </div>
+      <Sparkline data={data.slice(-HISTORY_LENGTH)} color={color} width={130} height={28} />
+    </div>
+  )
+}
+
+// Rendered in the always-on-top monitor-widget window instead of the app.
+export default function MonitorWidget() {
+  const { resources, cpuHistory, memoryHistory, networkRates } = useResourceMonitorProvider()
+  const [networkHistory, setNetworkHistory] = useState<number[]>([])
+
+  useEffect(() => {
+    invoke<AppSettings>('get_settings')
+      .then(settings => applyTheme(settings.theme))
+      .catch(() => applyTheme(localStorage.getItem('theme') || 'light'))
+  }, [])
+
+  const networkRate = networkRates
+    .filter(n => n.name !== 'lo')
+    .reduce((sum, n) => sum + n.rx + n.tx, 0)
+
+  useEffect(() => {
+    if (networkRates.length) {
+      setNetworkHistory(prev => [...prev, networkRate].slice(-HISTORY_LENGTH))
+    }
+  }, [networkRates])
+
+  const memoryPercent = resources?.memory ? (resources.memory.used / resources.memory.total) * 100 : 0
+
+  return (
+    <div
+      className="relative h-screen overflow-hidden select-none bg-white dark:bg-gray-900 border border-gray-200 dark:border-gray-700 rounded-lg px-3 py-2 space-y-1.5"
+      data-tauri-drag-region
+    >
+      <button
+        onClick={() => invoke('close_monitor_widget')}
+        className="absolute top-1 right-1 p-0.5 text-gray-400 hover:text-gray-700 dark:hover:text-gray-200"
+        title="Close"
+      >
+        <X size={12} />
+      </button>
+      <Row label="CPU" value={`${(resources?.cpu ?? 0).toFixed(0)}%`} data={cpuHistory} color="#3b82f6" />
+      <Row label="Memory" value={`${memoryPercent.toFixed(0)}%`} data={memoryHistory} color="#8b5cf6" />
+      <Row label="Network" value={formatRate(networkRate)} data={networkHistory} color="#10b981" />
+    </div>
+  )
+}
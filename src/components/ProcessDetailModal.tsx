@@ -0,0 +1,108 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Eye, EyeOff, X } from 'lucide-react'
+import CopyableText from './CopyableText'
+import { errorMessage } from '../utils/errors'
+
+interface ProcessDetail {
+  pid: number
+  parent_pid: number | null
+  name: string | null
+  state: string | null
+  uid: number | null
+  threads: number | null
+  cmdline: string[] | null
+  exe: string | null
+  cwd: string | null
+  environment: Record<string, string> | null
+  start_time: number | null
+  open_fds: number | null
+}
+
+// Shown for fields the backend couldn't read, usually another user's process.
+const unreadable = <span className="text-gray-400">not readable</span>
+
+function Field({ label, value }: { label: string; value: React.ReactNode }) {
+  return (
+    <div className="flex gap-3 py-1 text-sm">
+      <span className="w-28 shrink-0 text-gray-500 dark:text-gray-400">{label}</span>
+      <span className="min-w-0 break-all text-gray-900 dark:text-gray-100">{value ?? unreadable}</span>
+    </div>
+  )
+}
+
+// One process in full, opened from a PID in the process list.
+export default function ProcessDetailModal({ pid, onClose }: { pid: number; onClose: () => void }) {
+  const [detail, setDetail] = useState<ProcessDetail | null>(null)
+  const [revealEnv, setRevealEnv] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    invoke<ProcessDetail>('get_process_detail', { pid, redactEnv: !revealEnv })
+      .then(setDetail)
+      .catch(err => setError(errorMessage(err)))
+  }, [pid, revealEnv])
+
+  const copyable = (text: string | null, mono = true) =>
+    text === null ? null : <CopyableText value={text}><span className={mono ? 'font-mono text-xs' : ''}>{text}</span></CopyableText>
+
+  return (
+    <div className="fixed inset-0 bg-black/60 flex items-center justify-center z-50" onClick={onClose}>
+      <div
+        className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 w-full max-w-2xl mx-4 flex flex-col max-h-[80vh]"
+        onClick={e => e.stopPropagation()}
+      >
+        <div className="flex items-center justify-between p-4 border-b border-gray-200 dark:border-gray-700">
+          <h2 className="text-base font-bold text-gray-900 dark:text-gray-100">
+            {detail?.name ?? 'Process'} <span className="font-mono text-sm text-gray-400">{pid}</span>
+          </h2>
+          <button onClick={onClose} className="p-1.5 text-gray-400 hover:text-gray-600 dark:hover:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 rounded transition-colors">
+            <X size={16} />
+          </button>
+        </div>
+        <div className="p-4 overflow-y-auto">
+          {error && <div className="text-sm text-red-500">{error}</div>}
+          {detail && (
+            <>
+              <Field label="Command" value={detail.cmdline && copyable(detail.cmdline.join(' '))} />
+              <Field label="Executable" value={copyable(detail.exe)} />
+              <Field label="Working dir" value={copyable(detail.cwd)} />
+              <Field label="State" value={detail.state} />
+              <Field label="Parent PID" value={detail.parent_pid} />
+              <Field label="UID" value={detail.uid} />
+              <Field label="Threads" value={detail.threads} />
+              <Field label="Open files" value={detail.open_fds} />
+              <Field label="Started" value={detail.start_time && new Date(detail.start_time * 1000).toLocaleString()} />
+              <div className="flex items-center justify-between mt-4 mb-1">
+                <h3 className="text-sm font-semibold text-gray-900 dark:text-gray-100">Environment</h3>
+                {detail.environment && (
+                  <button
+                    onClick={() => setRevealEnv(v => !v)}
+                    className="flex items-center gap-1 text-xs text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
+                    title="Values of variables that look like secrets are hidden by default"
+                  >
+                    {revealEnv ? <EyeOff size={12} /> : <Eye size={12} />}
+                    {revealEnv ? 'Hide secrets' : 'Show secrets'}
+                  </button>
+                )}
+              </div>
+              {detail.environment ? (
+                <div className="font-mono text-xs space-y-0.5">
+                  {Object.entries(detail.environment).map(([name, value]) => (
+                    <div key={name} className="break-all">
+                      <span className="text-blue-600 dark:text-blue-400">{name}</span>
+                      <span className="text-gray-400">=</span>
+                      <span className="text-gray-700 dark:text-gray-300">{value}</span>
+                    </div>
+                  ))}
+                </div>
+              ) : (
+                <div className="text-sm text-gray-400">Not readable for this process</div>
+              )}
+            </>
+          )}
+        </div>
+      </div>
+    </div>
+  )
+}
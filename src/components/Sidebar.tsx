@@ -1,13 +1,14 @@
 import React from 'react'
-import { PageType } from '../types'
+import { ModuleName, PageType } from '../types'
 import { NAVIGATION_ITEMS, APP_NAME, APP_SUBTITLE, APP_VERSION } from '../constants'
 
 interface SidebarProps {
   currentPage: PageType
   onPageChange: (page: PageType) => void
+  visibleModules: Partial<Record<ModuleName, boolean>>
 }
 
-export default function Sidebar({ currentPage, onPageChange }: SidebarProps) {
+export default function Sidebar({ currentPage, onPageChange, visibleModules }: SidebarProps) {
   return (
     <aside className="w-64 h-full bg-gray-900 text-white flex flex-col border-r border-gray-800 shadow-lg">
       <div className="p-6 border-b border-gray-800">
@@ -16,7 +17,7 @@ export default function Sidebar({ currentPage, onPageChange }: SidebarProps) {
       </div>
 
       <nav className="flex-1 px-4 py-6 space-y-2">
-        {NAVIGATION_ITEMS.map(({ id, label, icon: Icon }) => (
+        {NAVIGATION_ITEMS.filter(({ module }) => !module || visibleModules[module] !== false).map(({ id, label, icon: Icon }) => (
           <button
             key={id}
             onClick={() => onPageChange(id)}
@@ -0,0 +1,185 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Camera } from 'lucide-react'
+import { errorMessage } from '../utils/errors'
+
+interface SnapshotInfo {
+  id: string
+  label: string
+  created: string
+}
+
+interface ServiceState {
+  name: string
+  user: boolean
+  active_state: string
+  sub_state: string
+  enabled: boolean
+}
+
+interface SnapshotDiff {
+  memory: { used_before: number | null; used_after: number | null; used_delta: number | null; swap_used_delta: number | null }
+  disks: { mount_point: string; free_before: number | null; free_after: number | null; free_delta: number | null }[]
+  processes: { new: { name: string; count: number; memory: number }[]; removed: { name: string; count: number; memory: number }[] }
+  services: { added: ServiceState[]; removed: ServiceState[]; changed: { name: string; user: boolean; before: ServiceState; after: ServiceState }[] } | null
+}
+
+const formatBytes = (bytes: number): string => {
+  const abs = Math.abs(bytes)
+  if (abs >= 1024 * 1024 * 1024) return (bytes / 1024 / 1024 / 1024).toFixed(1) + ' GB'
+  if (abs >= 1024 * 1024) return (bytes / 1024 / 1024).toFixed(0) + ' MB'
+  if (abs >= 1024) return (bytes / 1024).toFixed(0) + ' KB'
+  return bytes + ' B'
+}
+
+const formatDelta = (delta: number | null) =>
+  delta === null ? '—' : `${delta > 0 ? '+' : ''}${formatBytes(delta)}`
+
+const serviceState = (s: ServiceState) => `${s.active_state}/${s.sub_state}${s.enabled ? ', enabled' : ''}`
+
+const selectClass = 'px-2 py-1.5 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100'
+
+// Before/after snapshots of resources, processes and services; the diff is
+// worked out by the backend.
+export default function SnapshotsCard() {
+  const [snapshots, setSnapshots] = useState<SnapshotInfo[]>([])
+  const [label, setLabel] = useState('')
+  const [before, setBefore] = useState('')
+  const [after, setAfter] = useState('')
+  const [diff, setDiff] = useState<SnapshotDiff | null>(null)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const load = async () => {
+    const list = await invoke<SnapshotInfo[]>('list_resource_snapshots')
+    setSnapshots(list)
+    return list
+  }
+
+  useEffect(() => {
+    load().catch(err => setError(errorMessage(err)))
+  }, [])
+
+  const take = async () => {
+    setBusy(true)
+    setError(null)
+    try {
+      const taken = await invoke<SnapshotInfo>('take_resource_snapshot', { label: label.trim() || null })
+      setLabel('')
+      const list = await load()
+      setBefore(list[1]?.id ?? '')
+      setAfter(taken.id)
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const compare = async () => {
+    setBusy(true)
+    setError(null)
+    try {
+      setDiff(await invoke<SnapshotDiff>('compare_resource_snapshots', { idA: before, idB: after }))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const options = snapshots.map(s => (
+    <option key={s.id} value={s.id}>{s.label} ({new Date(s.created).toLocaleString()})</option>
+  ))
+  const disks = diff?.disks.filter(d => d.free_delta !== 0) ?? []
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+      <div className="flex items-center gap-2 mb-4">
+        <Camera size={18} className="text-blue-500" />
+        <h2 className="text-lg font-semibold text-gray-900 dark:text-gray-100">Snapshots</h2>
+      </div>
+      {error && <div className="text-sm text-red-500 mb-3">{error}</div>}
+      <div className="flex flex-wrap items-center gap-2 mb-3">
+        <input
+          value={label}
+          onChange={e => setLabel(e.target.value)}
+          placeholder="Label, e.g. before cleanup"
+          className={`${selectClass} w-64`}
+        />
+        <button
+          onClick={take}
+          disabled={busy}
+          className="px-3 py-1.5 text-sm bg-blue-600 hover:bg-blue-700 text-white rounded-lg disabled:opacity-50"
+        >
+          Take snapshot
+        </button>
+      </div>
+      {snapshots.length < 2 ? (
+        <div className="text-sm text-gray-400">Take a snapshot before and after a change to see what it did.</div>
+      ) : (
+        <div className="flex flex-wrap items-center gap-2 text-sm text-gray-600 dark:text-gray-300">
+          <select value={before} onChange={e => setBefore(e.target.value)} className={selectClass}>
+            <option value="">Before…</option>
+            {options}
+          </select>
+          <span>→</span>
+          <select value={after} onChange={e => setAfter(e.target.value)} className={selectClass}>
+            <option value="">After…</option>
+            {options}
+          </select>
+          <button
+            onClick={compare}
+            disabled={busy || !before || !after}
+            className="px-3 py-1.5 text-sm bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 text-gray-900 dark:text-gray-100 rounded-lg disabled:opacity-50"
+          >
+            Compare
+          </button>
+        </div>
+      )}
+      {diff && (
+        <div className="mt-4 space-y-3 text-sm text-gray-900 dark:text-gray-100">
+          <div>Memory used: <span className="font-mono">{formatDelta(diff.memory.used_delta)}</span></div>
+          {disks.length > 0 && (
+            <div>
+              <div className="font-medium mb-1">Free space</div>
+              {disks.map(d => (
+                <div key={d.mount_point} className="flex gap-3 font-mono text-gray-600 dark:text-gray-400">
+                  <span className="flex-1 truncate">{d.mount_point}</span>
+                  <span>{d.free_delta === null ? (d.free_after === null ? 'unmounted' : 'mounted') : formatDelta(d.free_delta)}</span>
+                </div>
+              ))}
+            </div>
+          )}
+          {(diff.processes.new.length > 0 || diff.processes.removed.length > 0) && (
+            <div>
+              <div className="font-medium mb-1">Processes</div>
+              {diff.processes.new.map(p => (
+                <div key={`+${p.name}`} className="text-green-600 dark:text-green-400">+ {p.name}{p.count > 1 ? ` ×${p.count}` : ''} ({formatBytes(p.memory)})</div>
+              ))}
+              {diff.processes.removed.map(p => (
+                <div key={`-${p.name}`} className="text-red-500">− {p.name}{p.count > 1 ? ` ×${p.count}` : ''}</div>
+              ))}
+            </div>
+          )}
+          {diff.services && (diff.services.added.length + diff.services.removed.length + diff.services.changed.length > 0) && (
+            <div>
+              <div className="font-medium mb-1">Services</div>
+              {diff.services.added.map(s => (
+                <div key={`+${s.name}${s.user}`} className="text-green-600 dark:text-green-400">+ {s.name} ({serviceState(s)})</div>
+              ))}
+              {diff.services.removed.map(s => (
+                <div key={`-${s.name}${s.user}`} className="text-red-500">− {s.name}</div>
+              ))}
+              {diff.services.changed.map(c => (
+                <div key={`~${c.name}${c.user}`} className="text-gray-600 dark:text-gray-400">
+                  {c.name}: {serviceState(c.before)} → {serviceState(c.after)}
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
@@ -0,0 +1,18 @@
+export default function Sparkline({ data, color, width = 140, height = 32 }: { data: number[]; color: string; width?: number; height?: number }) {
+  if (data.length < 2) return <div style={{ width, height }} />
+  const max = Math.max(...data, 1)
+  const points = data.map((v, i) => {
+    const x = (i / (data.length - 1)) * width
+    const y = height - (v / max) * (height - 2) - 1
+    return `${x},${y}`
+  }).join(' ')
+
+  const fillPoints = `0,${height} ${points} ${width},${height}`
+
+  return (
+    <svg width={width} height={height} className="shrink-0">
+      <polyline fill={color} fillOpacity="0.1" stroke="none" points={fillPoints} />
+      <polyline fill="none" stroke={color} strokeWidth="1.5" strokeLinejoin="round" points={points} />
+    </svg>
+  )
+}
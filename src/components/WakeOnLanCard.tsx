@@ -0,0 +1,105 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Power } from 'lucide-react'
+import { errorMessage } from '../utils/errors'
+
+interface WolStatus {
+  interface: string
+  supported: boolean | null
+  enabled: boolean | null
+  supported_modes: string[] | null
+  current_modes: string[] | null
+}
+
+const inputClass = 'px-2 py-1.5 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100'
+
+// Whether this machine's wired interfaces wake on a magic packet, and a
+// form to send one to another machine.
+export default function WakeOnLanCard({ interfaces }: { interfaces: string[] }) {
+  const [selected, setSelected] = useState('')
+  const [status, setStatus] = useState<WolStatus | null>(null)
+  const [mac, setMac] = useState('')
+  const [broadcast, setBroadcast] = useState('')
+  const [busy, setBusy] = useState(false)
+  const [message, setMessage] = useState<string | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    if (!selected && interfaces.length > 0) setSelected(interfaces[0])
+  }, [interfaces, selected])
+
+  useEffect(() => {
+    if (!selected) return
+    setStatus(null)
+    invoke<WolStatus>('get_wol_status', { interface: selected })
+      .then(setStatus)
+      .catch(err => setError(errorMessage(err)))
+  }, [selected])
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true)
+    setError(null)
+    setMessage(null)
+    try {
+      await action()
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const toggle = () => run(async () => {
+    setStatus(await invoke<WolStatus>('set_wol', { interface: selected, enabled: !status?.enabled }))
+  })
+
+  const send = () => run(async () => {
+    const sent = await invoke<{ target: string }>('send_wol_packet', { mac: mac.trim(), broadcastAddr: broadcast.trim() || null })
+    setMessage(`Magic packet sent to ${sent.target}`)
+  })
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+      <div className="flex items-center gap-2 mb-4">
+        <Power size={18} className="text-blue-500" />
+        <h2 className="text-lg font-semibold text-gray-900 dark:text-gray-100">Wake-on-LAN</h2>
+      </div>
+      {error && <div className="text-sm text-red-500 mb-3">{error}</div>}
+      {interfaces.length > 0 && (
+        <div className="flex flex-wrap items-center gap-2 mb-4 text-sm text-gray-600 dark:text-gray-300">
+          <select value={selected} onChange={e => setSelected(e.target.value)} className={inputClass}>
+            {interfaces.map(name => <option key={name} value={name}>{name}</option>)}
+          </select>
+          {status && (status.supported === null ? (
+            <span className="text-gray-400">Wake-on settings aren't readable for this interface</span>
+          ) : status.supported ? (
+            <>
+              <span>Wakes on magic packet: {status.enabled ? 'yes' : 'no'}</span>
+              <button
+                onClick={toggle}
+                disabled={busy}
+                className="px-3 py-1.5 text-sm bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 text-gray-900 dark:text-gray-100 rounded-lg disabled:opacity-50"
+              >
+                {status.enabled ? 'Disable' : 'Enable'}
+              </button>
+            </>
+          ) : (
+            <span className="text-gray-400">Doesn't support waking on a magic packet</span>
+          ))}
+        </div>
+      )}
+      <div className="flex flex-wrap items-center gap-2">
+        <input value={mac} onChange={e => setMac(e.target.value)} placeholder="MAC, e.g. 00:1a:2b:3c:4d:5e" className={`${inputClass} w-56 font-mono`} />
+        <input value={broadcast} onChange={e => setBroadcast(e.target.value)} placeholder="Broadcast (255.255.255.255)" className={`${inputClass} w-56`} />
+        <button
+          onClick={send}
+          disabled={busy || !mac.trim()}
+          className="px-3 py-1.5 text-sm bg-blue-600 hover:bg-blue-700 text-white rounded-lg disabled:opacity-50"
+        >
+          Wake
+        </button>
+      </div>
+      {message && <div className="text-sm text-green-600 dark:text-green-400 mt-2">{message}</div>}
+    </div>
+  )
+}
@@ -1,15 +1,16 @@
-import { Settings as SettingsIcon, Cpu, HardDrive, Server, Database, Terminal, FileText, ScrollText, Cog } from 'lucide-react'
+import { Settings as SettingsIcon, Cpu, HardDrive, Server, Database, Terminal, FileText, ScrollText, Cog, Shield } from 'lucide-react'
 import { MenuItem, PageType } from '../types'
 
 export const NAVIGATION_ITEMS: MenuItem[] = [
   { id: 'resources', label: 'Resources', icon: Cpu },
-  { id: 'devices', label: 'Devices', icon: HardDrive },
-  { id: 'processes', label: 'Processes', icon: Server },
-  { id: 'services', label: 'Services', icon: Cog },
-  { id: 'repositories', label: 'Repositories', icon: Database },
-  { id: 'startup', label: 'Startup Apps', icon: Terminal },
-  { id: 'scripts', label: 'Scripts', icon: ScrollText },
-  { id: 'logs', label: 'Logs', icon: FileText },
+  { id: 'devices', label: 'Devices', icon: HardDrive, module: 'devices' },
+  { id: 'processes', label: 'Processes', icon: Server, module: 'processes' },
+  { id: 'services', label: 'Services', icon: Cog, module: 'services' },
+  { id: 'firewall', label: 'Firewall', icon: Shield, module: 'firewall' },
+  { id: 'repositories', label: 'Repositories', icon: Database, module: 'repos' },
+  { id: 'startup', label: 'Startup Apps', icon: Terminal, module: 'startup' },
+  { id: 'scripts', label: 'Scripts', icon: ScrollText, module: 'scripts' },
+  { id: 'logs', label: 'Logs', icon: FileText, module: 'logs' },
   { id: 'settings', label: 'Settings', icon: SettingsIcon },
 ]
 
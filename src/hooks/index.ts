@@ -1,5 +1,6 @@
 import { useState, useEffect } from 'react'
 import { logger } from '../utils/logger'
+import { errorMessage } from '../utils/errors'
 
 export function useFetch<T>(
   fetchFn: () => Promise<T>,
@@ -18,7 +19,7 @@ export function useFetch<T>(
         setData(result)
       } catch (err) {
         logger.error('useFetch error', err)
-        setError(err instanceof Error ? err.message : String(err))
+        setError(errorMessage(err))
       } finally {
         setLoading(false)
       }
@@ -1,8 +1,11 @@
 import { createContext, useContext, useEffect, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { AppSettings } from '../types'
 
 interface DiskInfo {
   name: string
+  display_name?: string
   mount_point?: string
   total_space?: number
   available_space?: number
@@ -15,6 +18,8 @@ interface GpuInfo {
   memory_used: number | null
   memory_total: number | null
   temperature: number | null
+  temperature_value?: number | null
+  temperature_unit?: string
   fan_speed: number | null
 }
 
@@ -23,13 +28,18 @@ interface TempInfo {
   sensor: string
   device_id: string
   device_name: string
+  // Drive model and kernel name, for drive sensors only.
+  display_name: string | null
   celsius: number
+  value: number
+  unit: string
 }
 
 interface FanInfo {
   label: string
   sensor: string
   device_id: string
+  display_name: string | null
   rpm: number
 }
 
@@ -41,15 +51,50 @@ interface NetworkStat {
 
 interface DiskIoStat {
   name: string
+  display_name: string
   read_bytes: number
   write_bytes: number
   io_ms: number
 }
 
+export interface SwapDevice {
+  path: string
+  kind: string
+  size: number
+  used: number
+  priority: number
+  zram: {
+    orig_data_size: number
+    compr_data_size: number
+    mem_used_total: number
+    ratio: number | null
+    algorithm: string | null
+  } | null
+}
+
+export interface ZswapInfo {
+  compressor: string | null
+  max_pool_percent: number | null
+  pool_total_size: number | null
+  stored_pages: number | null
+}
+
 interface CpuCore {
   name: string
   usage: number
   frequency: number
+  core_id: number | null
+  package_id: number | null
+  core_type: 'performance' | 'efficiency' | null
+}
+
+export interface CpuTopology {
+  sockets: number
+  cores: number
+  cores_per_socket: number
+  threads_per_core: number
+  performance_cores: number | null
+  efficiency_cores: number | null
 }
 
 export interface NetworkRate {
@@ -62,6 +107,7 @@ export interface NetworkRate {
 
 export interface DiskIoRate {
   name: string
+  display_name: string
   read: number
   write: number
   utilization: number
@@ -72,6 +118,7 @@ export interface SystemResources {
   cpu_count: number
   cpu_model: string
   per_cpu: CpuCore[]
+  topology: CpuTopology
   load_avg: [number, number, number]
   uptime: number
   memory: {
@@ -80,12 +127,17 @@ export interface SystemResources {
     swap_total: number
     swap_used: number
   }
+  swap_devices: SwapDevice[]
+  zswap: ZswapInfo | null
   disks: DiskInfo[]
   gpu: GpuInfo[] | null
   temperatures: TempInfo[]
   fans: FanInfo[]
   network: NetworkStat[]
   disk_io: DiskIoStat[]
+  // Why the gpu or thermal section is missing, when what it reads is masked.
+  degraded?: boolean
+  degraded_reasons?: Record<string, string>
 }
 
 export interface ResourceMonitorData {
@@ -100,8 +152,6 @@ export interface ResourceMonitorData {
   diskIoHistory: Record<string, { read: number[]; write: number[] }>
 }
 
-const HISTORY_LENGTH = 300
-
 export const ResourceMonitorContext = createContext<ResourceMonitorData>({
   resources: null,
   loading: true,
@@ -130,19 +180,25 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
   const [diskIoHistory, setDiskIoHistory] = useState<Record<string, { read: number[]; write: number[] }>>({})
   const prevNetworkRef = useRef<NetworkStat[] | null>(null)
   const prevDiskIoRef = useRef<DiskIoStat[] | null>(null)
-  const intervalRef = useRef<number | null>(null)
 
   useEffect(() => {
-    const fetch = async () => {
+    // Rates are per second whatever the polling interval in settings.
+    let intervalMs = 1000
+    // Samples kept per graph, covering history_secs from settings.
+    let historyLength = 300
+    let cancelled = false
+
+    // Samples come from the backend's resources-update stream, which every
+    // window shares; the first one is fetched so the page isn't empty.
+    const apply = (data: SystemResources) => {
       try {
-        const data = await invoke<SystemResources>('get_resources')
         setResources(data)
 
-        setCpuHistory(prev => [...prev, data.cpu].slice(-HISTORY_LENGTH))
+        setCpuHistory(prev => [...prev, data.cpu].slice(-historyLength))
 
         if (data.memory) {
           const memPercent = (data.memory.used / data.memory.total) * 100
-          setMemoryHistory(prev => [...prev, memPercent].slice(-HISTORY_LENGTH))
+          setMemoryHistory(prev => [...prev, memPercent].slice(-historyLength))
         }
 
         if (data.gpu?.length) {
@@ -150,7 +206,7 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
             const next = { ...prev }
             data.gpu!.forEach((gpu, i) => {
               if (gpu.usage !== null) {
-                next[i] = [...(next[i] || []), gpu.usage!].slice(-HISTORY_LENGTH)
+                next[i] = [...(next[i] || []), gpu.usage!].slice(-historyLength)
               }
             })
             return next
@@ -164,8 +220,8 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
             const old = prevNetMap.get(curr.name)
             return {
               name: curr.name,
-              rx: old ? Math.max(0, curr.rx_bytes - old.rx_bytes) : 0,
-              tx: old ? Math.max(0, curr.tx_bytes - old.tx_bytes) : 0,
+              rx: old ? Math.max(0, curr.rx_bytes - old.rx_bytes) * 1000 / intervalMs : 0,
+              tx: old ? Math.max(0, curr.tx_bytes - old.tx_bytes) * 1000 / intervalMs : 0,
               totalRx: curr.rx_bytes,
               totalTx: curr.tx_bytes,
             }
@@ -176,8 +232,8 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
             rates.forEach(r => {
               if (!next[r.name]) next[r.name] = { rx: [], tx: [] }
               next[r.name] = {
-                rx: [...next[r.name].rx, r.rx].slice(-HISTORY_LENGTH),
-                tx: [...next[r.name].tx, r.tx].slice(-HISTORY_LENGTH),
+                rx: [...next[r.name].rx, r.rx].slice(-historyLength),
+                tx: [...next[r.name].tx, r.tx].slice(-historyLength),
               }
             })
             return next
@@ -200,9 +256,10 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
             const old = prevDiskMap.get(curr.name)
             return {
               name: curr.name,
-              read: old ? Math.max(0, curr.read_bytes - old.read_bytes) : 0,
-              write: old ? Math.max(0, curr.write_bytes - old.write_bytes) : 0,
-              utilization: old ? Math.min(100, Math.max(0, (curr.io_ms - old.io_ms) / 10)) : 0,
+              display_name: curr.display_name,
+              read: old ? Math.max(0, curr.read_bytes - old.read_bytes) * 1000 / intervalMs : 0,
+              write: old ? Math.max(0, curr.write_bytes - old.write_bytes) * 1000 / intervalMs : 0,
+              utilization: old ? Math.min(100, Math.max(0, (curr.io_ms - old.io_ms) * 100 / intervalMs)) : 0,
             }
           })
           setDiskIoRates(rates)
@@ -211,8 +268,8 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
             rates.forEach(r => {
               if (!next[r.name]) next[r.name] = { read: [], write: [] }
               next[r.name] = {
-                read: [...next[r.name].read, r.read].slice(-HISTORY_LENGTH),
-                write: [...next[r.name].write, r.write].slice(-HISTORY_LENGTH),
+                read: [...next[r.name].read, r.read].slice(-historyLength),
+                write: [...next[r.name].write, r.write].slice(-historyLength),
               }
             })
             return next
@@ -220,6 +277,7 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
         } else if (data.disk_io) {
           setDiskIoRates(data.disk_io.map(d => ({
             name: d.name,
+            display_name: d.display_name,
             read: 0,
             write: 0,
             utilization: 0,
@@ -227,15 +285,34 @@ export function useResourceMonitorProvider(): ResourceMonitorData {
         }
         prevDiskIoRef.current = data.disk_io
       } catch (err) {
-        console.error('Failed to fetch resources:', err)
+        console.error('Failed to apply resources:', err)
       } finally {
         setLoading(false)
       }
     }
 
-    fetch()
-    intervalRef.current = window.setInterval(fetch, 1000)
-    return () => { if (intervalRef.current) clearInterval(intervalRef.current) }
+    const unlisten = listen<SystemResources>('resources-update', event => {
+      if (!cancelled) apply(event.payload)
+    })
+
+    const start = async () => {
+      const settings = await invoke<AppSettings>('get_settings').catch(() => null)
+      intervalMs = settings?.refresh_interval_ms ?? 1000
+      historyLength = Math.max(2, Math.round((settings?.history_secs ?? 300) * 1000 / intervalMs))
+      try {
+        const data = await invoke<SystemResources>('get_resources')
+        if (!cancelled) apply(data)
+      } catch (err) {
+        console.error('Failed to fetch resources:', err)
+        setLoading(false)
+      }
+    }
+
+    start()
+    return () => {
+      cancelled = true
+      unlisten.then(stop => stop())
+    }
   }, [])
 
   return {
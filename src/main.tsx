@@ -1,9 +1,11 @@
 import React from 'react'
 import ReactDOM from 'react-dom/client'
 import App from './App'
+import MonitorWidget from './components/MonitorWidget'
+import './index.css'
 
 ReactDOM.createRoot(document.getElementById('root')!).render(
   <React.StrictMode>
-    <App />
+    {new URLSearchParams(window.location.search).get('view') === 'monitor-widget' ? <MonitorWidget /> : <App />}
   </React.StrictMode>,
 )
@@ -8,7 +8,11 @@ import {
 } from 'lucide-react'
 import CopyableText from '../components/CopyableText'
 import { getOsInfo } from '../api/system'
+import { getUsbDeviceDetails } from '../api/devices'
 import SystemReportModal from '../components/SystemReportModal'
+import WakeOnLanCard from '../components/WakeOnLanCard'
+import LanNeighborsCard from '../components/LanNeighborsCard'
+import { errorMessage } from '../utils/errors'
 
 interface BlockDevice {
   name: string
@@ -33,6 +37,29 @@ interface UsbDevice {
   device_type: string
 }
 
+interface UsbInterface {
+  number: string | null
+  class: string | null
+  subclass: string | null
+  protocol: string | null
+  driver: string | null
+}
+
+interface UsbDeviceDetails {
+  manufacturer: string | null
+  product: string | null
+  serial: string | null
+  speed_mbps?: string | null
+  speed?: string | null
+  max_power?: string | null
+  usb_version?: string | null
+  current_available?: string | null
+  current_required?: string | null
+  sysfs_path?: string
+  location_id?: string | null
+  interfaces: UsbInterface[]
+}
+
 interface NetworkDevice {
   name: string
   state: string
@@ -69,6 +96,7 @@ interface ProcessorInfo {
   sockets: number
   cores: number
   threads: number
+  topology: { performance_cores: number | null; efficiency_cores: number | null }
   cache: string
   family: string
   stepping: string
@@ -312,6 +340,9 @@ function PciDeviceRow({ dev }: { dev: PciDevice }) {
 }
 
 function UsbDeviceRow({ dev }: { dev: UsbDevice }) {
+  const [expanded, setExpanded] = useState(false)
+  const [details, setDetails] = useState<UsbDeviceDetails | null>(null)
+  const [error, setError] = useState<string | null>(null)
   const parts = dev.name.split(' ')
   const vendorEnd = parts.findIndex((_, i) => i > 0 && /^[A-Z]/.test(parts[i]) && !/^(Inc|Corp|Ltd|Co|LLC)/.test(parts[i]) && parts.slice(0, i).join(' ').includes(','))
   let vendor = ''
@@ -320,25 +351,103 @@ function UsbDeviceRow({ dev }: { dev: UsbDevice }) {
     vendor = cleanVendor(parts.slice(0, vendorEnd).join(' '))
     product = parts.slice(vendorEnd).join(' ')
   }
+
+  const toggle = async () => {
+    const next = !expanded
+    setExpanded(next)
+    if (!next || details) return
+    setError(null)
+    try {
+      setDetails(await (getUsbDeviceDetails(dev.bus, dev.device) as Promise<UsbDeviceDetails>))
+    } catch (err) {
+      setError(errorMessage(err))
+    }
+  }
+
+  const fields: [string, string | null | undefined][] = details ? [
+    ['Manufacturer', details.manufacturer],
+    ['Product', details.product],
+    ['Serial', details.serial],
+    ['Speed', details.speed_mbps ? `${details.speed_mbps} Mb/s` : details.speed],
+    ['USB version', details.usb_version],
+    ['Max power', details.max_power ?? details.current_required],
+    ['Path', details.sysfs_path ?? details.location_id],
+  ] : []
+
   return (
-    <div className="flex items-center gap-3 pl-16 pr-4 py-2.5 hover:bg-gray-50 dark:hover:bg-gray-700/30">
-      <div className="w-1.5 h-1.5 rounded-full bg-gray-300 dark:bg-gray-600 shrink-0" />
-      <div className="flex-1 min-w-0">
-        <CopyableText value={product}>
-          <div className="text-sm text-gray-900 dark:text-gray-100 truncate">{product}</div>
-        </CopyableText>
-        <div className="flex items-center gap-2 text-xs text-gray-400 mt-0.5">
-          {vendor && <span>{vendor}</span>}
-          <CopyableText value={`${dev.vendor_id}:${dev.product_id}`}>
-            <span className="font-mono">{dev.vendor_id}:{dev.product_id}</span>
+    <div>
+      <div
+        className="flex items-center gap-3 pl-12 pr-4 py-2.5 hover:bg-gray-50 dark:hover:bg-gray-700/30 cursor-pointer"
+        onClick={toggle}
+      >
+        {expanded ? <ChevronDown size={14} className="text-gray-400 shrink-0" /> : <ChevronRight size={14} className="text-gray-400 shrink-0" />}
+        <div className="flex-1 min-w-0">
+          <CopyableText value={product}>
+            <div className="text-sm text-gray-900 dark:text-gray-100 truncate">{product}</div>
           </CopyableText>
+          <div className="flex items-center gap-2 text-xs text-gray-400 mt-0.5">
+            {vendor && <span>{vendor}</span>}
+            <CopyableText value={`${dev.vendor_id}:${dev.product_id}`}>
+              <span className="font-mono">{dev.vendor_id}:{dev.product_id}</span>
+            </CopyableText>
+          </div>
         </div>
       </div>
+
+      {expanded && (
+        <div className="pl-[4.5rem] pr-4 pb-3 text-xs">
+          {error && <div className="text-red-500">{error}</div>}
+          {!error && !details && <div className="text-gray-400">Loading details…</div>}
+          {details && (
+            <>
+              <div className="grid grid-cols-[auto_1fr] gap-x-4 gap-y-1">
+                {fields.filter(([, value]) => value).map(([label, value]) => (
+                  <div key={label} className="contents">
+                    <span className="text-gray-400">{label}</span>
+                    <CopyableText value={value!}>
+                      <span className="font-mono text-gray-700 dark:text-gray-300 break-all">{value}</span>
+                    </CopyableText>
+                  </div>
+                ))}
+              </div>
+              {details.interfaces.length > 0 && (
+                <div className="mt-2 space-y-0.5">
+                  <div className="text-gray-400">Interfaces</div>
+                  {details.interfaces.map((iface, i) => (
+                    <div key={i} className="font-mono text-gray-600 dark:text-gray-400">
+                      #{iface.number ?? i} class {iface.class ?? '??'}/{iface.subclass ?? '??'}/{iface.protocol ?? '??'}
+                      {' '}{iface.driver ? `→ ${iface.driver}` : '(no driver)'}
+                    </div>
+                  ))}
+                </div>
+              )}
+            </>
+          )}
+        </div>
+      )}
     </div>
   )
 }
 
-function NetworkDeviceRow({ dev }: { dev: NetworkDevice }) {
+function NetworkDeviceRow({ dev, onChange }: { dev: NetworkDevice; onChange: (dev: NetworkDevice) => void }) {
+  const [mtu, setMtu] = useState<string | null>(null)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const isUp = dev.state === 'UP'
+
+  const change = async (command: string, args: Record<string, unknown>) => {
+    setBusy(true)
+    setError(null)
+    try {
+      onChange(await invoke<NetworkDevice>(command, { name: dev.name, ...args }))
+      setMtu(null)
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+    }
+  }
+
   return (
     <div className="flex items-center gap-3 pl-16 pr-4 py-2.5 hover:bg-gray-50 dark:hover:bg-gray-700/30">
       <span className={`w-2 h-2 rounded-full shrink-0 ${dev.state === 'UP' ? 'bg-green-500' : 'bg-gray-400'}`} />
@@ -362,9 +471,39 @@ function NetworkDeviceRow({ dev }: { dev: NetworkDevice }) {
               <span className="bg-blue-50 dark:bg-blue-900/20 text-blue-600 dark:text-blue-400 px-1.5 py-0.5 rounded">{ip}</span>
             </CopyableText>
           ))}
-          <span>MTU {dev.mtu}</span>
+          {mtu === null ? (
+            <button onClick={() => setMtu(dev.mtu)} className="hover:text-blue-500" title="Change MTU">MTU {dev.mtu}</button>
+          ) : (
+            <form
+              onSubmit={e => { e.preventDefault(); change('set_network_device_mtu', { mtu: Number(mtu) }) }}
+              className="flex items-center gap-1"
+            >
+              MTU
+              <input
+                type="number"
+                min={576}
+                max={9216}
+                value={mtu}
+                onChange={e => setMtu(e.target.value)}
+                autoFocus
+                className="w-20 px-1 py-0.5 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100"
+              />
+              <button type="submit" disabled={busy} className="text-blue-500 disabled:opacity-50">Set</button>
+              <button type="button" onClick={() => setMtu(null)}>Cancel</button>
+            </form>
+          )}
         </div>
+        {error && <div className="text-xs text-red-500 mt-0.5">{error}</div>}
       </div>
+      {dev.device_type !== 'Loopback' && (
+        <button
+          onClick={() => change('set_network_device_state', { up: !isUp })}
+          disabled={busy}
+          className="text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-600 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+        >
+          {isUp ? 'Bring down' : 'Bring up'}
+        </button>
+      )}
     </div>
   )
 }
@@ -389,6 +528,7 @@ function InputDeviceRow({ dev }: { dev: InputDevice }) {
 
 export default function Devices() {
   const [blockDevices, setBlockDevices] = useState<BlockDevice[]>([])
+  const [storageNotice, setStorageNotice] = useState<string | null>(null)
   const [usbDevices, setUsbDevices] = useState<UsbDevice[]>([])
   const [networkDevices, setNetworkDevices] = useState<NetworkDevice[]>([])
   const [pciDevices, setPciDevices] = useState<PciDevice[]>([])
@@ -410,7 +550,8 @@ export default function Devices() {
   const fetchAllDevices = async () => {
     try {
       const [devData, usbData, netData, pciData, inputData, cpuData, osData] = await Promise.all([
-        invoke<{ blockdevices?: BlockDevice[] }>('list_devices'),
+        invoke<{ blockdevices?: BlockDevice[]; degraded_reasons?: Record<string, string> }>('list_devices')
+          .catch(err => ({ blockdevices: [], degraded_reasons: { blockdevices: errorMessage(err) } })),
         invoke<UsbDevice[]>('list_usb_devices'),
         invoke<NetworkDevice[]>('list_network_devices'),
         invoke<PciDevice[]>('list_pci_devices'),
@@ -420,6 +561,7 @@ export default function Devices() {
       ])
 
       setBlockDevices(devData?.blockdevices || [])
+      setStorageNotice(devData?.degraded_reasons?.blockdevices ?? null)
       setUsbDevices(usbData || [])
       setNetworkDevices(netData || [])
       setPciDevices(pciData || [])
@@ -573,6 +715,9 @@ export default function Devices() {
             </CopyableText>
             <div className="flex flex-wrap gap-x-6 gap-y-1.5 mt-2 text-xs text-gray-500 dark:text-gray-400">
               <span>{processor.cores} cores / {processor.threads} threads</span>
+              {processor.topology?.performance_cores != null && (
+                <span>{processor.topology.performance_cores}P + {processor.topology.efficiency_cores}E</span>
+              )}
               {processor.sockets > 1 && <span>{processor.sockets} sockets</span>}
               {processor.cache && <span>Cache: {processor.cache}</span>}
             </div>
@@ -587,6 +732,10 @@ export default function Devices() {
         </DeviceSection>
       )}
 
+      {storageNotice && !searching && (
+        <div className="text-sm text-amber-600 dark:text-amber-400">{storageNotice}</div>
+      )}
+
       {filteredBlock.length > 0 && (
         <DeviceSection title="Storage" icon={<HardDrive size={18} />} count={filteredBlock.length} forceOpen={forceState}>
           {filteredBlock.map((device) => (
@@ -612,13 +761,24 @@ export default function Devices() {
           {networkGroups.map(([type, devices]) => (
             <DeviceSubGroup key={type} type={type} count={devices.length} forceOpen={forceState}>
               {devices.map((dev) => (
-                <NetworkDeviceRow key={dev.name} dev={dev} />
+                <NetworkDeviceRow
+                  key={dev.name}
+                  dev={dev}
+                  onChange={updated => setNetworkDevices(prev => prev.map(d => (d.name === updated.name ? updated : d)))}
+                />
               ))}
             </DeviceSubGroup>
           ))}
         </DeviceSection>
       )}
 
+      {!searching && (
+        <>
+          <LanNeighborsCard />
+          <WakeOnLanCard interfaces={networkDevices.filter(d => d.device_type === 'Ethernet').map(d => d.name)} />
+        </>
+      )}
+
       {filteredUsb.length > 0 && (
         <DeviceSection title="USB Devices" icon={<Usb size={18} />} count={filteredUsb.length} forceOpen={forceState}>
           {usbGroups.map(([type, devices]) => (
@@ -0,0 +1,254 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Plus, Trash2, RotateCw, Shield, Lock } from 'lucide-react'
+import { errorMessage } from '../utils/errors'
+
+interface FirewallRule {
+  id: string | null
+  action: string
+  direction: string
+  protocol: string
+  port: string | null
+  source: string | null
+  service: string | null
+}
+
+interface FirewallStatus {
+  backend: string | null
+  active: boolean
+  read_only: boolean
+  defaults: { incoming: string | null; outgoing: string | null }
+  rules: FirewallRule[]
+  rules_visible: boolean
+  note: string | null
+}
+
+const BACKEND_LABELS: Record<string, string> = {
+  ufw: 'ufw',
+  firewalld: 'firewalld',
+  nftables: 'nftables',
+  application_firewall: 'Application Firewall',
+}
+
+const actionColor = (action: string) =>
+  action === 'allow' || action === 'limit'
+    ? 'text-green-600 dark:text-green-400'
+    : 'text-red-600 dark:text-red-400'
+
+const selectClass = 'px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 text-sm'
+
+export default function Firewall() {
+  const [status, setStatus] = useState<FirewallStatus | null>(null)
+  const [isLoading, setIsLoading] = useState(true)
+  const [busy, setBusy] = useState(false)
+  // Set once the user asks to read rules that only root may list.
+  const [elevated, setElevated] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [deleteConfirm, setDeleteConfirm] = useState<string | null>(null)
+  const [action, setAction] = useState('allow')
+  const [direction, setDirection] = useState('in')
+  const [protocol, setProtocol] = useState('tcp')
+  const [port, setPort] = useState('')
+  const [source, setSource] = useState('')
+
+  useEffect(() => {
+    fetchStatus(false)
+  }, [])
+
+  const fetchStatus = async (asRoot: boolean) => {
+    try {
+      setError(null)
+      setBusy(true)
+      setStatus(await invoke<FirewallStatus>('get_firewall_status', { elevated: asRoot }))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setBusy(false)
+      setIsLoading(false)
+    }
+  }
+
+  const showRules = () => {
+    setElevated(true)
+    fetchStatus(true)
+  }
+
+  const handleAdd = async () => {
+    if (!port.trim()) return
+    try {
+      setError(null)
+      setBusy(true)
+      await invoke('add_firewall_rule', {
+        rule: { action, direction, protocol, port: port.trim(), source: source.trim() || null },
+      })
+      setPort('')
+      setSource('')
+      await fetchStatus(elevated)
+    } catch (err) {
+      setError(errorMessage(err))
+      setBusy(false)
+    }
+  }
+
+  const handleDelete = async (id: string) => {
+    try {
+      setError(null)
+      setBusy(true)
+      await invoke('delete_firewall_rule', { id })
+      setDeleteConfirm(null)
+      await fetchStatus(elevated)
+    } catch (err) {
+      setError(errorMessage(err))
+      setBusy(false)
+    }
+  }
+
+  if (isLoading) return <div className="p-4 text-gray-900 dark:text-gray-100">Loading firewall…</div>
+
+  const editable = !!status && !status.read_only && status.rules_visible
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold text-gray-900 dark:text-gray-100">Firewall</h1>
+        <div className="flex items-center gap-2">
+          {status && !status.rules_visible && status.backend && (
+            <button
+              onClick={showRules}
+              disabled={busy}
+              className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors disabled:opacity-50"
+            >
+              <Lock size={16} />
+              Show rules
+            </button>
+          )}
+          <button
+            onClick={() => fetchStatus(elevated)}
+            disabled={busy}
+            className="flex items-center gap-2 px-4 py-2 bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 text-gray-900 dark:text-gray-100 rounded-lg transition-colors disabled:opacity-50"
+          >
+            <RotateCw size={16} className={busy ? 'animate-spin' : ''} />
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {error && (
+        <div className="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+          <p className="text-sm text-red-800 dark:text-red-300">{error}</p>
+        </div>
+      )}
+
+      {status && (
+        <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+          <div className="flex items-center gap-3">
+            <Shield size={20} className={status.active ? 'text-green-500' : 'text-gray-400'} />
+            <div className="flex-1">
+              <div className="font-semibold text-gray-900 dark:text-gray-100">
+                {status.backend ? BACKEND_LABELS[status.backend] ?? status.backend : 'No firewall'}
+                <span className={`ml-2 text-sm font-normal ${status.active ? 'text-green-600 dark:text-green-400' : 'text-gray-500'}`}>
+                  {status.active ? 'active' : 'inactive'}
+                </span>
+                {status.read_only && status.backend && (
+                  <span className="ml-2 text-xs font-normal text-gray-500 dark:text-gray-400">read-only</span>
+                )}
+              </div>
+              {status.note && <div className="text-sm text-gray-500 dark:text-gray-400">{status.note}</div>}
+            </div>
+            <div className="text-sm text-gray-600 dark:text-gray-300 text-right">
+              <div>Incoming: <span className="font-medium">{status.defaults.incoming ?? 'unknown'}</span></div>
+              <div>Outgoing: <span className="font-medium">{status.defaults.outgoing ?? 'unknown'}</span></div>
+            </div>
+          </div>
+        </div>
+      )}
+
+      {editable && (
+        <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-4 flex flex-wrap items-center gap-2">
+          <select value={action} onChange={e => setAction(e.target.value)} className={selectClass}>
+            <option value="allow">Allow</option>
+            <option value="deny">Deny</option>
+            <option value="reject">Reject</option>
+          </select>
+          <select value={direction} onChange={e => setDirection(e.target.value)} className={selectClass}>
+            <option value="in">Incoming</option>
+            <option value="out">Outgoing</option>
+          </select>
+          <select value={protocol} onChange={e => setProtocol(e.target.value)} className={selectClass}>
+            <option value="tcp">TCP</option>
+            <option value="udp">UDP</option>
+            <option value="any">Any</option>
+          </select>
+          <input
+            value={port}
+            onChange={e => setPort(e.target.value)}
+            placeholder="Port or range, e.g. 22 or 8000-8100"
+            className={`${selectClass} w-64`}
+          />
+          <input
+            value={source}
+            onChange={e => setSource(e.target.value)}
+            placeholder="From (optional), e.g. 192.168.1.0/24"
+            className={`${selectClass} w-64`}
+          />
+          <button
+            onClick={handleAdd}
+            disabled={busy || !port.trim()}
+            className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white text-sm rounded-lg transition-colors disabled:opacity-50"
+          >
+            <Plus size={16} />
+            Add rule
+          </button>
+        </div>
+      )}
+
+      {status?.rules_visible && (
+        <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700">
+          {status.rules.length === 0 ? (
+            <div className="p-4 text-gray-600 dark:text-gray-400">No rules.</div>
+          ) : (
+            <div className="divide-y divide-gray-200 dark:divide-gray-700">
+              {status.rules.map((rule, i) => (
+                <div key={rule.id ?? i} className="flex items-center gap-4 px-4 py-3 text-sm">
+                  <span className={`w-16 font-semibold uppercase ${actionColor(rule.action)}`}>{rule.action}</span>
+                  <span className="w-12 text-gray-500 dark:text-gray-400">{rule.direction}</span>
+                  <span className="flex-1 font-mono text-gray-900 dark:text-gray-100">
+                    {rule.service ?? (rule.port ? `${rule.port}${rule.protocol !== 'any' ? `/${rule.protocol}` : ''}` : 'all ports')}
+                  </span>
+                  <span className="w-48 font-mono text-gray-600 dark:text-gray-400 truncate">{rule.source ?? 'anywhere'}</span>
+                  {editable && rule.id && (
+                    deleteConfirm === rule.id ? (
+                      <div className="flex items-center gap-1">
+                        <button
+                          onClick={() => handleDelete(rule.id!)}
+                          disabled={busy}
+                          className="px-2 py-1 bg-red-600 hover:bg-red-700 text-white text-xs rounded disabled:opacity-50"
+                        >
+                          Delete
+                        </button>
+                        <button
+                          onClick={() => setDeleteConfirm(null)}
+                          className="px-2 py-1 text-xs text-gray-600 dark:text-gray-300"
+                        >
+                          Cancel
+                        </button>
+                      </div>
+                    ) : (
+                      <button
+                        onClick={() => setDeleteConfirm(rule.id)}
+                        className="p-2 text-gray-400 hover:text-red-500 hover:bg-gray-100 dark:hover:bg-gray-600 rounded transition-colors"
+                        title="Delete rule"
+                      >
+                        <Trash2 size={16} />
+                      </button>
+                    )
+                  )}
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
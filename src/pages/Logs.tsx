@@ -1,8 +1,22 @@
 import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { logger } from '../utils/logger'
-import { readLogFile, clearLogFile } from '../api/logging'
+import { readLogFile, readLogTail, clearLogFile } from '../api/logging'
+import { FileLogEntry } from '../types'
 import { Trash2, RefreshCw, Download } from 'lucide-react'
 
+const TAIL_LINES = 500
+
+interface LogLineEvent {
+  line: string
+  entry: FileLogEntry | null
+}
+
+function formatFileEntry(entry: FileLogEntry): string {
+  return `[${entry.ts}] [${entry.level.toUpperCase()}] ${entry.message}`
+}
+
 interface LogEntry {
   timestamp: string
   level: 'info' | 'warn' | 'error' | 'debug'
@@ -15,7 +29,10 @@ type ViewMode = 'browser' | 'file'
 
 export default function Logs() {
   const [logs, setLogs] = useState<LogEntry[]>([])
-  const [fileLogs, setFileLogs] = useState<string>('')
+  const [fileLines, setFileLines] = useState<string[]>([])
+  const [fileCursor, setFileCursor] = useState<number | null>(null)
+  const [fileError, setFileError] = useState<string | null>(null)
+  const [fileExists, setFileExists] = useState(true)
   const [activeLogLevel, setActiveLogLevel] = useState<LogLevel>('all')
   const [viewMode, setViewMode] = useState<ViewMode>('file')
   const [isLoadingFileLogs, setIsLoadingFileLogs] = useState(false)
@@ -24,8 +41,15 @@ export default function Logs() {
     loadBrowserLogs()
     loadFileLogsFromDisk()
 
-    const autoRefreshInterval = setInterval(loadFileLogsFromDisk, 3000)
-    return () => clearInterval(autoRefreshInterval)
+    // New lines arrive as events instead of re-reading the file.
+    const unlisten = listen<LogLineEvent>('log-line', ({ payload }) => {
+      setFileLines(prev => [...prev, payload.line])
+    })
+    invoke('start_log_follow').catch(err => logger.warn('Failed to follow log file', err))
+    return () => {
+      unlisten.then(fn => fn())
+      invoke('stop_log_follow').catch(() => {})
+    }
   }, [])
 
   const loadBrowserLogs = () => {
@@ -35,15 +59,29 @@ export default function Logs() {
   const loadFileLogsFromDisk = async () => {
     setIsLoadingFileLogs(true)
     try {
-      const content = await readLogFile()
-      setFileLogs(String(content))
+      const tail = await readLogTail(TAIL_LINES)
+      setFileLines(tail.entries.map(formatFileEntry))
+      setFileCursor(tail.cursor)
+      setFileExists(tail.exists)
+      setFileError(null)
     } catch (err) {
-      setFileLogs(`Error reading log file: ${err}`)
+      setFileError(`Error reading log file: ${err}`)
     } finally {
       setIsLoadingFileLogs(false)
     }
   }
 
+  const loadOlderFileLogs = async () => {
+    if (fileCursor === null) return
+    try {
+      const tail = await readLogTail(TAIL_LINES, fileCursor)
+      setFileLines(prev => [...tail.entries.map(formatFileEntry), ...prev])
+      setFileCursor(tail.cursor)
+    } catch (err) {
+      setFileError(`Error reading log file: ${err}`)
+    }
+  }
+
   const refreshCurrentView = () => {
     if (viewMode === 'file') {
       loadFileLogsFromDisk()
@@ -58,12 +96,14 @@ export default function Logs() {
       setLogs([])
     } else {
       await clearLogFile()
-      setFileLogs('')
+      setFileLines([])
+      setFileCursor(null)
     }
   }
 
-  const downloadLogsAsFile = () => {
-    const blob = new Blob([fileLogs], { type: 'text/plain' })
+  const downloadLogsAsFile = async () => {
+    const { content } = await readLogFile(true)
+    const blob = new Blob([content], { type: 'text/plain' })
     const downloadLink = document.createElement('a')
     downloadLink.href = URL.createObjectURL(blob)
     downloadLink.download = `gantry-${new Date().toISOString().split('T')[0]}.log`
@@ -91,7 +131,7 @@ export default function Logs() {
     return logs.filter(log => activeLogLevel === 'all' || log.level === activeLogLevel)
   }
 
-  const fileLogLineCount = fileLogs.split('\n').length
+  const fileLogLineCount = fileLines.length
 
   return (
     <div className="space-y-4">
@@ -133,7 +173,7 @@ export default function Logs() {
               : 'border-transparent text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-gray-200'
           }`}
         >
-          File Logs ({fileLogLineCount} lines)
+          File Logs ({fileLogLineCount}{fileCursor !== null ? '+' : ''} lines)
         </button>
         <button
           onClick={() => setViewMode('browser')}
@@ -151,12 +191,21 @@ export default function Logs() {
         <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700">
           {isLoadingFileLogs ? (
             <div className="p-4 text-gray-900 dark:text-gray-100">Loading file logs…</div>
-          ) : fileLogs ? (
-            <pre className="p-4 text-xs overflow-auto max-h-96 bg-gray-50 dark:bg-gray-900 text-gray-900 dark:text-gray-100 font-mono whitespace-pre-wrap break-words">
-              {fileLogs}
-            </pre>
+          ) : fileError ? (
+            <div className="p-4 text-red-600 dark:text-red-400">{fileError}</div>
+          ) : fileLines.length > 0 ? (
+            <>
+              {fileCursor !== null && (
+                <button onClick={loadOlderFileLogs} className="w-full p-2 text-sm text-blue-600 dark:text-blue-400 hover:underline">
+                  Load older lines
+                </button>
+              )}
+              <pre className="p-4 text-xs overflow-auto max-h-96 bg-gray-50 dark:bg-gray-900 text-gray-900 dark:text-gray-100 font-mono whitespace-pre-wrap break-words">
+                {fileLines.join('\n')}
+              </pre>
+            </>
           ) : (
-            <div className="p-4 text-gray-600 dark:text-gray-400">No log file yet</div>
+            <div className="p-4 text-gray-600 dark:text-gray-400">{fileExists ? 'The log is empty' : 'No log file yet'}</div>
           )}
         </div>
       ) : (
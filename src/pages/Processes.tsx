@@ -1,8 +1,11 @@
-import { useEffect, useMemo, useState } from 'react'
+import { useEffect, useMemo, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { ChevronDown, ChevronRight, ChevronUp, Search, Trash2, X, Layers, RefreshCw } from 'lucide-react'
+import { ChevronDown, ChevronRight, ChevronUp, Search, Trash2, X, Layers, RefreshCw, Users } from 'lucide-react'
 import { useResourceMonitor } from '../hooks/useResourceMonitor'
 import Pagination from '../components/Pagination'
+import ProcessDetailModal from '../components/ProcessDetailModal'
+import { AppSettings, Polled } from '../types'
+import { errorMessage } from '../utils/errors'
 
 interface ProcessEntry {
   pid: number
@@ -12,18 +15,28 @@ interface ProcessEntry {
   cpu: number
   memory: number
   status: string
+  cpu_time_seconds: number | null
 }
 
 interface ProcessGroup {
   name: string
   icon: string
   total_cpu: number
+  total_cpu_time_seconds: number
   total_memory: number
   count: number
   main_pid: number
   processes: ProcessEntry[]
 }
 
+interface UserUsage {
+  uid: number | null
+  user: string
+  cpu: number
+  memory: number
+  count: number
+}
+
 const ITEMS_PER_PAGE = 50
 
 const formatMemory = (bytes: number): string => {
@@ -36,6 +49,16 @@ const formatCpu = (cpu: number): string => {
   return cpu.toFixed(1) + '%'
 }
 
+const formatCpuTime = (seconds: number | null): string => {
+  if (seconds === null) return '—'
+  const s = Math.floor(seconds)
+  const h = Math.floor(s / 3600)
+  const m = Math.floor((s % 3600) / 60)
+  if (h > 0) return `${h}h ${m.toString().padStart(2, '0')}m`
+  if (m > 0) return `${m}m ${(s % 60).toString().padStart(2, '0')}s`
+  return seconds.toFixed(1) + 's'
+}
+
 const memoryColor = (bytes: number, totalMemory: number): string => {
   if (totalMemory <= 0) return 'text-gray-500 dark:text-gray-400'
   const pct = (bytes / totalMemory) * 100
@@ -45,10 +68,11 @@ const memoryColor = (bytes: number, totalMemory: number): string => {
   return 'text-gray-500 dark:text-gray-400'
 }
 
-function ProcessGroupRow({ group, onKillGroup, onKillProcess, totalMemory }: {
+function ProcessGroupRow({ group, onKillGroup, onKillProcess, onShowDetail, totalMemory }: {
   group: ProcessGroup
   onKillGroup: (pids: number[]) => void
   onKillProcess: (pid: number) => void
+  onShowDetail: (pid: number) => void
   totalMemory: number
 }) {
   const [expanded, setExpanded] = useState(false)
@@ -80,6 +104,9 @@ function ProcessGroupRow({ group, onKillGroup, onKillProcess, totalMemory }: {
             {formatCpu(group.total_cpu)}
           </span>
         </td>
+        <td className="p-2 text-right text-sm text-gray-500 dark:text-gray-400">
+          {formatCpuTime(group.total_cpu_time_seconds)}
+        </td>
         <td className="p-2 text-right text-sm">
           <span className={memoryColor(group.total_memory, totalMemory)}>
             {formatMemory(group.total_memory)}
@@ -120,11 +147,18 @@ function ProcessGroupRow({ group, onKillGroup, onKillProcess, totalMemory }: {
           <td className="p-2" />
           <td className="p-2 pl-6 text-sm">
             <div className="flex items-center gap-2 text-gray-600 dark:text-gray-400">
-              <span className="font-mono text-xs text-gray-400 dark:text-gray-500">{proc.pid}</span>
+              <button
+                onClick={() => onShowDetail(proc.pid)}
+                className="font-mono text-xs text-gray-400 dark:text-gray-500 hover:text-blue-500 hover:underline"
+                title="Show details"
+              >
+                {proc.pid}
+              </button>
               <span className="truncate max-w-xs">{proc.name}</span>
             </div>
           </td>
           <td className="p-2 text-right text-xs text-gray-500 dark:text-gray-400">{formatCpu(proc.cpu)}</td>
+          <td className="p-2 text-right text-xs text-gray-500 dark:text-gray-400">{formatCpuTime(proc.cpu_time_seconds)}</td>
           <td className="p-2 text-right text-xs text-gray-500 dark:text-gray-400">{formatMemory(proc.memory)}</td>
           <td className="p-2 text-right">
             {confirmKill === proc.pid ? (
@@ -157,6 +191,11 @@ function ProcessGroupRow({ group, onKillGroup, onKillProcess, totalMemory }: {
   )
 }
 
+interface ProcessSort {
+  key: 'name' | 'cpu' | 'cpu_time' | 'memory'
+  dir: 'asc' | 'desc'
+}
+
 export default function Processes() {
   const { resources } = useResourceMonitor()
   const totalMemory = resources?.memory?.total ?? 0
@@ -164,25 +203,54 @@ export default function Processes() {
   const [loading, setLoading] = useState(true)
   const [searchQuery, setSearchQuery] = useState('')
   const [currentPage, setCurrentPage] = useState(1)
-  const [sortKey, setSortKey] = useState<'name' | 'cpu' | 'memory'>('memory')
-  const [sortDir, setSortDir] = useState<'asc' | 'desc'>('desc')
+  const [sortKey, setSortKey] = useState<ProcessSort['key']>('memory')
+  const [sortDir, setSortDir] = useState<ProcessSort['dir']>('desc')
   const [autoRefresh, setAutoRefresh] = useState(false)
+  const [refreshMs, setRefreshMs] = useState(5000)
   const [killError, setKillError] = useState<string | null>(null)
+  const [showUsers, setShowUsers] = useState(false)
+  const [userUsage, setUserUsage] = useState<UserUsage[]>([])
+  const generation = useRef(0)
+  const [detailPid, setDetailPid] = useState<number | null>(null)
 
   useEffect(() => {
     fetchProcesses()
+    invoke<AppSettings>('get_settings')
+      .then(settings => {
+        setRefreshMs(settings.processes_refresh_ms)
+        const sort = settings.page_preferences.processes?.sort as ProcessSort | undefined
+        if (sort) {
+          setSortKey(sort.key)
+          setSortDir(sort.dir)
+        }
+      })
+      .catch(() => {})
   }, [])
 
   useEffect(() => {
     if (!autoRefresh) return
-    const id = setInterval(fetchProcesses, 5000)
+    const id = setInterval(fetchProcesses, refreshMs)
     return () => clearInterval(id)
-  }, [autoRefresh])
+  }, [autoRefresh, refreshMs])
+
+  useEffect(() => {
+    if (showUsers) fetchUsage()
+  }, [showUsers])
+
+  const fetchUsage = async () => {
+    try {
+      setUserUsage(await invoke<UserUsage[]>('get_usage_by_user'))
+    } catch (err) {
+      console.error('Failed to load usage by user:', err)
+    }
+  }
 
   const fetchProcesses = async () => {
     try {
-      const data = await invoke<ProcessGroup[]>('list_processes')
-      setGroups(data || [])
+      const result = await invoke<Polled<ProcessGroup[]>>('list_processes', { ifChangedSince: generation.current })
+      generation.current = result.generation
+      if (!result.unchanged) setGroups(result.data || [])
+      if (showUsers) await fetchUsage()
     } catch (err) {
       console.error('Failed to load processes:', err)
     } finally {
@@ -195,7 +263,7 @@ export default function Processes() {
       await invoke('kill_process', { pid })
       await fetchProcesses()
     } catch (err) {
-      setKillError(`Failed to kill process: ${err}`)
+      setKillError(`Failed to kill process: ${errorMessage(err)}`)
     }
   }
 
@@ -204,13 +272,15 @@ export default function Processes() {
       await invoke('kill_process_group', { pids })
       await fetchProcesses()
     } catch (err) {
-      setKillError(`Failed to kill processes: ${err}`)
+      setKillError(`Failed to kill processes: ${errorMessage(err)}`)
     }
   }
 
-  const toggleSort = (key: 'name' | 'cpu' | 'memory') => {
-    if (sortKey === key) setSortDir(d => d === 'asc' ? 'desc' : 'asc')
-    else { setSortKey(key); setSortDir(key === 'name' ? 'asc' : 'desc') }
+  const toggleSort = (key: ProcessSort['key']) => {
+    const dir = sortKey === key ? (sortDir === 'asc' ? 'desc' : 'asc') : (key === 'name' ? 'asc' : 'desc')
+    setSortKey(key)
+    setSortDir(dir)
+    invoke('set_page_preference', { page: 'processes', key: 'sort', value: { key, dir } }).catch(() => {})
   }
 
   const filteredGroups = useMemo(() => {
@@ -222,6 +292,7 @@ export default function Processes() {
       const mul = sortDir === 'asc' ? 1 : -1
       if (sortKey === 'name') return mul * a.name.localeCompare(b.name)
       if (sortKey === 'cpu') return mul * (a.total_cpu - b.total_cpu)
+      if (sortKey === 'cpu_time') return mul * (a.total_cpu_time_seconds - b.total_cpu_time_seconds)
       return mul * (a.total_memory - b.total_memory)
     })
   }, [groups, searchQuery, sortKey, sortDir])
@@ -271,6 +342,17 @@ export default function Processes() {
             <span className="text-gray-300 dark:text-gray-600">|</span>
             <span>{totalProcessCount} total</span>
           </div>
+          <button
+            onClick={() => setShowUsers(v => !v)}
+            className={`flex items-center gap-1.5 px-3 py-1.5 text-sm rounded-lg transition-colors ${
+              showUsers
+                ? 'bg-blue-100 dark:bg-blue-900/30 text-blue-600 dark:text-blue-400'
+                : 'bg-gray-200 dark:bg-gray-700 text-gray-700 dark:text-gray-300 hover:bg-gray-300 dark:hover:bg-gray-600'
+            }`}
+          >
+            <Users size={13} />
+            By user
+          </button>
           <button
             onClick={() => setAutoRefresh(v => !v)}
             className={`flex items-center gap-1.5 px-3 py-1.5 text-sm rounded-lg transition-colors ${
@@ -291,6 +373,36 @@ export default function Processes() {
         </div>
       </div>
 
+      {showUsers && (
+        <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 overflow-hidden">
+          <table className="w-full text-sm">
+            <thead className="bg-gray-50 dark:bg-gray-700">
+              <tr>
+                <th className="p-2 text-left font-semibold text-gray-900 dark:text-gray-100">User</th>
+                <th className="p-2 text-right font-semibold text-gray-900 dark:text-gray-100 w-24">Processes</th>
+                <th className="p-2 text-right font-semibold text-gray-900 dark:text-gray-100 w-24">CPU</th>
+                <th className="p-2 text-right font-semibold text-gray-900 dark:text-gray-100 w-28">Memory</th>
+              </tr>
+            </thead>
+            <tbody>
+              {userUsage.map(u => (
+                <tr key={u.uid ?? 'unknown'} className="border-t border-gray-200 dark:border-gray-700">
+                  <td className="p-2 text-gray-900 dark:text-gray-100">
+                    {u.user}
+                    {u.uid !== null && <span className="ml-1.5 text-xs text-gray-400 font-mono">{u.uid}</span>}
+                  </td>
+                  <td className="p-2 text-right text-gray-500 dark:text-gray-400">{u.count}</td>
+                  <td className="p-2 text-right text-gray-500 dark:text-gray-400">{formatCpu(u.cpu)}</td>
+                  <td className="p-2 text-right">
+                    <span className={memoryColor(u.memory, totalMemory)}>{formatMemory(u.memory)}</span>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
       <div className="relative">
         <Search size={18} className="absolute left-3 top-1/2 -translate-y-1/2 text-gray-400" />
         <input
@@ -328,6 +440,12 @@ export default function Processes() {
                     {sortKey === 'cpu' && (sortDir === 'asc' ? <ChevronUp size={14} /> : <ChevronDown size={14} />)}
                   </div>
                 </th>
+                <th className="p-2 text-right text-sm font-semibold text-gray-900 dark:text-gray-100 w-28 cursor-pointer select-none hover:bg-gray-100 dark:hover:bg-gray-600 transition-colors" onClick={() => toggleSort('cpu_time')} title="CPU time used since each process started">
+                  <div className="flex items-center justify-end gap-1">
+                    CPU time
+                    {sortKey === 'cpu_time' && (sortDir === 'asc' ? <ChevronUp size={14} /> : <ChevronDown size={14} />)}
+                  </div>
+                </th>
                 <th className="p-2 text-right text-sm font-semibold text-gray-900 dark:text-gray-100 w-28 cursor-pointer select-none hover:bg-gray-100 dark:hover:bg-gray-600 transition-colors" onClick={() => toggleSort('memory')}>
                   <div className="flex items-center justify-end gap-1">
                     Memory
@@ -340,7 +458,7 @@ export default function Processes() {
             <tbody>
               {paginatedGroups.length === 0 ? (
                 <tr>
-                  <td colSpan={5} className="p-8 text-center text-gray-500 dark:text-gray-400">
+                  <td colSpan={6} className="p-8 text-center text-gray-500 dark:text-gray-400">
                     {searchQuery ? 'No processes match your search' : 'No processes found'}
                   </td>
                 </tr>
@@ -351,6 +469,7 @@ export default function Processes() {
                     group={group}
                     onKillGroup={handleKillGroup}
                     onKillProcess={handleKillProcess}
+                    onShowDetail={setDetailPid}
                     totalMemory={totalMemory}
                   />
                 ))
@@ -366,6 +485,8 @@ export default function Processes() {
           <Pagination currentPage={currentPage} totalPages={totalPages} onPageChange={setCurrentPage} />
         </div>
       )}
+
+      {detailPid !== null && <ProcessDetailModal pid={detailPid} onClose={() => setDetailPid(null)} />}
     </div>
   )
 }
@@ -1,8 +1,10 @@
 import { useEffect, useMemo, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { RotateCw, Plus, Trash2, Search } from 'lucide-react'
-import { addAptRepo, deleteAptRepo } from '../api/config'
+import { RotateCw, Plus, Trash2, Search, AlertTriangle } from 'lucide-react'
+import { addAptRepo, deleteAptRepo, repoHealthCheck } from '../api/config'
+import { RepoHealthFinding } from '../types'
 import { usePlatform } from '../hooks/usePlatform'
+import { errorMessage } from '../utils/errors'
 
 interface Repository {
   id: string
@@ -14,6 +16,13 @@ interface Repository {
   components: string
   enabled: boolean
   original_line: string
+  fingerprint?: string
+}
+
+interface RepoMutation {
+  repos?: Repository[] | null
+  duplicate?: boolean
+  existing_file?: string
 }
 
 export default function Repositories() {
@@ -28,62 +37,94 @@ export default function Repositories() {
   const [showAddModal, setShowAddModal] = useState(false)
   const [newRepoLine, setNewRepoLine] = useState('')
   const [deleteConfirm, setDeleteConfirm] = useState<string | null>(null)
+  const [duplicateOf, setDuplicateOf] = useState<string | null>(null)
+  const [health, setHealth] = useState<Record<string, RepoHealthFinding[]>>({})
 
   useEffect(() => {
     fetchAptRepositories()
   }, [])
 
-  const fetchAptRepositories = async () => {
+  const fetchAptRepositories = async (forceHealth?: boolean) => {
     try {
       setError(null)
       const data = await invoke<Repository[]>('list_apt_repos')
       setRepositories(data)
+      fetchHealth(forceHealth)
     } catch (err) {
       console.error('Failed to load repositories:', err)
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setIsLoading(false)
     }
   }
 
+  // Runs in the background; the checks probe every mirror and can be slow.
+  const fetchHealth = async (force?: boolean) => {
+    if (isMac) return
+    try {
+      const report = await repoHealthCheck(force)
+      setHealth(report.findings)
+    } catch (err) {
+      console.error('Failed to check repository health:', err)
+    }
+  }
+
+  // Mutations return the re-read list on Linux, or null when re-reading
+  // failed; ids shift when lines move, so never keep using the old ones.
+  const applyMutation = async (result: unknown) => {
+    const repos = (result as RepoMutation | null)?.repos
+    if (repos) {
+      setRepositories(repos)
+      fetchHealth()
+    } else {
+      await fetchAptRepositories()
+    }
+  }
+
   const toggleRepositoryEnabled = async (repo: Repository) => {
     setTogglingId(repo.id)
     setError(null)
     try {
-      await invoke('toggle_apt_repo', {
+      const result = await invoke('toggle_apt_repo', {
         id: repo.id,
         enabled: !repo.enabled,
       })
-      await fetchAptRepositories()
+      await applyMutation(result)
     } catch (err) {
       console.error('Failed to toggle repository:', err)
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setTogglingId(null)
     }
   }
 
-  const handleAdd = async () => {
+  const handleAdd = async (force = false) => {
     if (!newRepoLine.trim()) return
     try {
       setError(null)
-      await addAptRepo(newRepoLine.trim())
+      const result = await addAptRepo(newRepoLine.trim(), undefined, undefined, undefined, force)
+      const mutation = result as RepoMutation | null
+      if (mutation?.duplicate) {
+        setDuplicateOf(mutation.existing_file ?? '')
+        return
+      }
+      setDuplicateOf(null)
       setShowAddModal(false)
       setNewRepoLine('')
-      await fetchAptRepositories()
+      await applyMutation(result)
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     }
   }
 
   const handleDelete = async (id: string) => {
     try {
       setError(null)
-      await deleteAptRepo(id)
+      const result = await deleteAptRepo(id)
       setDeleteConfirm(null)
-      await fetchAptRepositories()
+      await applyMutation(result)
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     }
   }
 
@@ -109,14 +150,14 @@ export default function Repositories() {
         </h1>
         <div className="flex items-center gap-2">
           <button
-            onClick={() => { setNewRepoLine(''); setShowAddModal(true) }}
+            onClick={() => { setNewRepoLine(''); setDuplicateOf(null); setShowAddModal(true) }}
             className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors"
           >
             <Plus size={16} />
             Add
           </button>
           <button
-            onClick={() => fetchAptRepositories()}
+            onClick={() => fetchAptRepositories(true)}
             className="flex items-center gap-2 px-4 py-2 bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 text-gray-900 dark:text-gray-100 rounded-lg transition-colors"
           >
             <RotateCw size={16} />
@@ -196,6 +237,16 @@ export default function Repositories() {
                   )}
                 </div>
 
+                {health[repo.id]?.length > 0 && (
+                  <span
+                    title={health[repo.id].map((f) => f.message).join('\n')}
+                    className="flex items-center gap-1 text-xs font-semibold px-2 py-1 rounded whitespace-nowrap bg-amber-100 dark:bg-amber-900/30 text-amber-700 dark:text-amber-400"
+                  >
+                    <AlertTriangle size={12} />
+                    {health[repo.id].length === 1 ? health[repo.id][0].message : `${health[repo.id].length} issues`}
+                  </span>
+                )}
+
                 {!isMac && (
                   <span
                     className={`text-xs font-semibold px-2 py-1 rounded whitespace-nowrap ${
@@ -241,7 +292,7 @@ export default function Repositories() {
               <input
                 type="text"
                 value={newRepoLine}
-                onChange={e => setNewRepoLine(e.target.value)}
+                onChange={e => { setNewRepoLine(e.target.value); setDuplicateOf(null) }}
                 placeholder={isMac ? 'homebrew/cask-fonts' : 'deb http://example.com/repo focal main'}
                 className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
               />
@@ -250,6 +301,11 @@ export default function Repositories() {
                   ? 'Enter a Homebrew tap name, e.g. homebrew/cask-fonts or a GitHub repo owner/repo'
                   : <>Enter a complete APT repository line starting with <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">deb</code> or <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">deb-src</code></>}
               </p>
+              {duplicateOf !== null && (
+                <p className="text-sm text-amber-700 dark:text-amber-400">
+                  This repository is already configured in <span className="font-mono">{duplicateOf}</span>. Adding it again makes apt warn that it is configured multiple times.
+                </p>
+              )}
             </div>
             <div className="flex justify-end gap-3 mt-6">
               <button
@@ -259,11 +315,11 @@ export default function Repositories() {
                 Cancel
               </button>
               <button
-                onClick={handleAdd}
+                onClick={() => handleAdd(duplicateOf !== null)}
                 disabled={!newRepoLine.trim()}
                 className="px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed text-white rounded-lg transition-colors"
               >
-                {isMac ? 'Add Tap' : 'Add Repository'}
+                {duplicateOf !== null ? 'Add Anyway' : isMac ? 'Add Tap' : 'Add Repository'}
               </button>
             </div>
           </div>
@@ -1,9 +1,14 @@
-import { useMemo, useState } from 'react'
+import { useEffect, useMemo, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
 import SpeedometerGauge from '../components/SpeedometerGauge'
-import { Thermometer, Fan, ArrowDown, ArrowUp, HardDrive, ChevronDown, ChevronRight, Network, Clock, Activity, X, Cpu, Monitor, MemoryStick, Wifi, Info } from 'lucide-react'
+import { Thermometer, Fan, ArrowDown, ArrowUp, HardDrive, ChevronDown, ChevronRight, Network, Clock, Activity, X, Cpu, Monitor, MemoryStick, Wifi, Info, PictureInPicture2 } from 'lucide-react'
 import CopyableText from '../components/CopyableText'
+import Sparkline from '../components/Sparkline'
+import CleanupCard from '../components/CleanupCard'
+import SnapshotsCard from '../components/SnapshotsCard'
 import { useResourceMonitor } from '../hooks/useResourceMonitor'
 import type { SystemResources } from '../hooks/useResourceMonitor'
+import { errorMessage } from '../utils/errors'
 
 const formatBytes = (bytes: number): string => {
   if (bytes >= 1024 * 1024 * 1024) return (bytes / 1024 / 1024 / 1024).toFixed(1) + ' GB'
@@ -32,13 +37,19 @@ const tempColor = (celsius: number): string => {
   return 'text-green-500'
 }
 
+// Readings come converted to the unit chosen in settings; celsius still drives the colours.
+type Reading = { celsius: number; value: number; unit: string }
+const formatTemp = (t: Reading) => `${t.value}${t.unit}`
+const gpuTemp = (gpu: { temperature: number | null; temperature_value?: number | null; temperature_unit?: string }) =>
+  `${gpu.temperature_value ?? gpu.temperature}${gpu.temperature_unit ?? '°C'}`
+
 type SensorCategory = 'cpu' | 'gpu' | 'storage' | 'memory' | 'network' | 'other'
 
 type DeviceSensors = {
   deviceId: string
   deviceName: string
   sensor: string
-  temps: { label: string; celsius: number }[]
+  temps: ({ label: string } & Reading)[]
   fans: { label: string; rpm: number }[]
 }
 
@@ -151,6 +162,24 @@ const formatUptime = (seconds: number): string => {
   return `${mins}m`
 }
 
+type PerCpu = SystemResources['per_cpu'][number]
+
+// Threads of the same core next to each other, P-cores before E-cores.
+const sortedThreads = (cpus: PerCpu[]): PerCpu[] => {
+  const rank = (c: PerCpu) => [c.package_id ?? 0, c.core_type === 'efficiency' ? 1 : 0, c.core_id ?? -1]
+  return cpus
+    .map((c, i) => ({ c, i, r: rank(c) }))
+    .sort((a, b) => a.r[0] - b.r[0] || a.r[1] - b.r[1] || a.r[2] - b.r[2] || a.i - b.i)
+    .map(({ c }) => c)
+}
+
+const threadTitle = (c: PerCpu) => [
+  c.name,
+  c.core_id !== null && `core ${c.core_id}`,
+  c.package_id !== null && `socket ${c.package_id}`,
+  c.core_type && `${c.core_type} core`,
+].filter(Boolean).join(', ')
+
 function DetailModal({ title, onClose, children }: { title: string; onClose: () => void; children: React.ReactNode }) {
   return (
     <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50" onClick={onClose}>
@@ -223,25 +252,6 @@ function FullGraph({ data, color, unit = '%', height = 120 }: { data: number[];
   )
 }
 
-function Sparkline({ data, color, width = 140, height = 32 }: { data: number[]; color: string; width?: number; height?: number }) {
-  if (data.length < 2) return <div style={{ width, height }} />
-  const max = Math.max(...data, 1)
-  const points = data.map((v, i) => {
-    const x = (i / (data.length - 1)) * width
-    const y = height - (v / max) * (height - 2) - 1
-    return `${x},${y}`
-  }).join(' ')
-
-  const fillPoints = `0,${height} ${points} ${width},${height}`
-
-  return (
-    <svg width={width} height={height} className="shrink-0">
-      <polyline fill={color} fillOpacity="0.1" stroke="none" points={fillPoints} />
-      <polyline fill="none" stroke={color} strokeWidth="1.5" strokeLinejoin="round" points={points} />
-    </svg>
-  )
-}
-
 function buildGpuGraph(hist: number[], graphW: number, graphH: number) {
   const stepX = graphW / (hist.length - 1 || 1)
   const dataMin = Math.min(...hist)
@@ -306,7 +316,7 @@ function GpuSection({ resources, gpuHistory }: { resources: SystemResources | nu
             <div className="flex items-center gap-4 ml-3 shrink-0">
               {primary.gpu.temperature !== null && (
                 <div className="text-right">
-                  <div className={`text-lg font-bold font-mono ${tempColor(primary.gpu.temperature)}`}>{primary.gpu.temperature}°C</div>
+                  <div className={`text-lg font-bold font-mono ${tempColor(primary.gpu.temperature)}`}>{gpuTemp(primary.gpu)}</div>
                   <div className="text-[10px] text-gray-400">Temp</div>
                 </div>
               )}
@@ -384,7 +394,7 @@ function GpuSection({ resources, gpuHistory }: { resources: SystemResources | nu
               <div key={origIdx} className="flex items-center justify-between text-sm text-gray-500 dark:text-gray-400 py-1 mt-1">
                 <span className="truncate mr-3">{cleanGpuName(gpu.name)}</span>
                 <div className="flex items-center gap-3 shrink-0 text-xs font-mono">
-                  {gpu.temperature !== null && <span className={tempColor(gpu.temperature)}>{gpu.temperature}°C</span>}
+                  {gpu.temperature !== null && <span className={tempColor(gpu.temperature)}>{gpuTemp(gpu)}</span>}
                   {gpu.usage !== null && <span>{gpu.usage.toFixed(0)}%</span>}
                   {gpu.memory_total !== null && gpu.memory_total > 0 && (
                     <span>{formatBytes(gpu.memory_used || 0)} / {formatBytes(gpu.memory_total)}</span>
@@ -415,7 +425,7 @@ function GpuSection({ resources, gpuHistory }: { resources: SystemResources | nu
                     {gpu.temperature !== null && (
                       <div>
                         <div className="text-sm text-gray-400">Temperature</div>
-                        <div className={`text-lg font-bold ${tempColor(gpu.temperature)}`}>{gpu.temperature}°C</div>
+                        <div className={`text-lg font-bold ${tempColor(gpu.temperature)}`}>{gpuTemp(gpu)}</div>
                       </div>
                     )}
                     {gpu.memory_total !== null && gpu.memory_total > 0 && (
@@ -449,6 +459,56 @@ function GpuSection({ resources, gpuHistory }: { resources: SystemResources | nu
   )
 }
 
+interface ThermalSeries {
+  id: string
+  kind: 'temperature' | 'fan'
+  label: string
+  device_id: string
+  unit: string
+  points: [number, number][]
+}
+
+// Kept by the backend sampler, so the graphs are already filled in when the
+// page is opened again.
+function ThermalHistory({ resources }: { resources: SystemResources | null }) {
+  const [series, setSeries] = useState<ThermalSeries[]>([])
+
+  useEffect(() => {
+    invoke<ThermalSeries[]>('get_thermal_history', {})
+      .then(setSeries)
+      .catch(() => setSeries([]))
+  }, [resources])
+
+  if (series.length === 0) return null
+
+  return (
+    <div className="border-t border-gray-100 dark:border-gray-700 px-5 py-3">
+      <div className="text-xs font-medium text-gray-500 dark:text-gray-400 mb-2">History</div>
+      <div className="space-y-1.5">
+        {series.map(s => {
+          const values = s.points.map(([, v]) => v)
+          const latest = values[values.length - 1]
+          return (
+            <div key={s.id} className="flex items-center gap-3 text-sm">
+              <span className="w-40 text-gray-600 dark:text-gray-400 truncate flex items-center gap-1" title={s.device_id}>
+                {s.kind === 'fan' ? <Fan size={12} className="text-blue-400" /> : <Thermometer size={12} className="text-red-400" />}
+                {s.label}
+              </span>
+              <Sparkline data={values} color={s.kind === 'fan' ? '#3b82f6' : '#ef4444'} width={240} height={28} />
+              <span className="font-mono text-gray-900 dark:text-gray-100">
+                {s.kind === 'fan' ? `${latest} RPM` : `${latest}${s.unit}`}
+              </span>
+              <span className="text-xs font-mono text-gray-400">
+                {Math.min(...values)}–{Math.max(...values)}
+              </span>
+            </div>
+          )
+        })}
+      </div>
+    </div>
+  )
+}
+
 function ThermalSection({ resources }: { resources: SystemResources | null }) {
   const [thermalExpanded, setThermalExpanded] = useState(false)
   const [expandedCategories, setExpandedCategories] = useState<Record<string, boolean>>({})
@@ -462,10 +522,10 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
       const cat = getSensorCategory(t.sensor)
       let device = grouped[cat].find(d => d.deviceId === t.device_id)
       if (!device) {
-        device = { deviceId: t.device_id, deviceName: t.device_name || '', sensor: t.sensor, temps: [], fans: [] }
+        device = { deviceId: t.device_id, deviceName: t.display_name || t.device_name || '', sensor: t.sensor, temps: [], fans: [] }
         grouped[cat].push(device)
       }
-      device.temps.push({ label: cleanTempLabel(t.label, t.sensor, cat), celsius: t.celsius })
+      device.temps.push({ label: cleanTempLabel(t.label, t.sensor, cat), celsius: t.celsius, value: t.value, unit: t.unit })
     })
 
     resources?.fans?.filter(f => f.rpm > 0).forEach(f => {
@@ -487,13 +547,13 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
       return l.includes('package') || l.includes('tctl') || l.includes('tdie')
     })
 
-    const gpuTemp = resources?.gpu?.[0]?.temperature ?? null
+    const primaryGpu = resources?.gpu?.[0]?.temperature != null ? resources.gpu[0] : null
 
     const storageTemps = resources?.temperatures
       ?.filter(t => getSensorCategory(t.sensor) === 'storage' && t.label.toLowerCase() === 'composite')
       ?.sort((a, b) => a.device_id.localeCompare(b.device_id)) ?? []
 
-    return { grouped, activeCategories, cpuPackageTemp, gpuTemp, storageTemps }
+    return { grouped, activeCategories, cpuPackageTemp, primaryGpu, storageTemps }
   }, [resources?.temperatures, resources?.fans, resources?.gpu])
 
   const hasSensors = (resources?.temperatures?.length ?? 0) > 0 || (resources?.fans?.length ?? 0) > 0
@@ -515,8 +575,9 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
     setExpandedCategories(prev => ({ ...prev, [cat]: !prev[cat] }))
   }
 
-  const { grouped, activeCategories, cpuPackageTemp, gpuTemp, storageTemps } = sensorData
-  const maxStorageTemp = storageTemps.length > 0 ? Math.max(...storageTemps.map(t => t.celsius)) : 0
+  const { grouped, activeCategories, cpuPackageTemp, primaryGpu, storageTemps } = sensorData
+  const hottest = <T extends Reading>(temps: T[]) => temps.reduce((a, b) => (b.celsius > a.celsius ? b : a))
+  const maxStorageTemp = storageTemps.length > 0 ? hottest(storageTemps) : null
 
   return (
     <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 overflow-hidden">
@@ -531,17 +592,17 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
           <div className="flex gap-4 ml-auto text-sm">
             {cpuPackageTemp && (
               <span className={`font-mono font-medium ${tempColor(cpuPackageTemp.celsius)}`}>
-                CPU {cpuPackageTemp.celsius}°C
+                CPU {formatTemp(cpuPackageTemp)}
               </span>
             )}
-            {gpuTemp !== null && (
-              <span className={`font-mono font-medium ${tempColor(gpuTemp)}`}>
-                GPU {gpuTemp}°C
+            {primaryGpu && primaryGpu.temperature !== null && (
+              <span className={`font-mono font-medium ${tempColor(primaryGpu.temperature)}`}>
+                GPU {gpuTemp(primaryGpu)}
               </span>
             )}
-            {storageTemps.length > 0 && (
-              <span className={`font-mono font-medium ${tempColor(maxStorageTemp)}`}>
-                {storageTemps.length === 1 ? 'NVMe' : 'NVMe ↑'} {maxStorageTemp}°C
+            {maxStorageTemp && (
+              <span className={`font-mono font-medium ${tempColor(maxStorageTemp.celsius)}`}>
+                {storageTemps.length === 1 ? 'NVMe' : 'NVMe ↑'} {formatTemp(maxStorageTemp)}
               </span>
             )}
           </div>
@@ -581,8 +642,8 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
                   <span className="text-sm font-medium text-gray-900 dark:text-gray-100">{meta.label}</span>
                   <div className="flex gap-3 ml-auto text-xs font-mono">
                     {allTemps.length > 0 && (
-                      <span className={tempColor(Math.max(...allTemps.map(t => t.celsius)))}>
-                        {Math.max(...allTemps.map(t => t.celsius))}°C
+                      <span className={tempColor(hottest(allTemps).celsius)}>
+                        {formatTemp(hottest(allTemps))}
                       </span>
                     )}
                     {allFans.length > 0 && (
@@ -599,8 +660,8 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
                       allEmpty && d.temps.length === 1 && d.fans.length === 0 ? (
                         <div className="flex justify-between text-sm">
                           <span className="text-gray-500 dark:text-gray-400">{getDeviceLabel(cat, devices, 0)}</span>
-                          <CopyableText value={`${d.temps[0].celsius}°C`}>
-                            <span className={`font-mono font-medium ${tempColor(d.temps[0].celsius)}`}>{d.temps[0].celsius}°C</span>
+                          <CopyableText value={formatTemp(d.temps[0])}>
+                            <span className={`font-mono font-medium ${tempColor(d.temps[0].celsius)}`}>{formatTemp(d.temps[0])}</span>
                           </CopyableText>
                         </div>
                       ) : (
@@ -608,8 +669,8 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
                           {d.temps.map((temp, i) => (
                             <div key={`t${i}`} className="flex justify-between text-sm">
                               {temp.label && <span className="text-gray-600 dark:text-gray-400 truncate mr-2">{temp.label}</span>}
-                              <CopyableText value={`${temp.celsius}°C`}>
-                                <span className={`font-mono font-medium ${tempColor(temp.celsius)}`}>{temp.celsius}°C</span>
+                              <CopyableText value={formatTemp(temp)}>
+                                <span className={`font-mono font-medium ${tempColor(temp.celsius)}`}>{formatTemp(temp)}</span>
                               </CopyableText>
                             </div>
                           ))}
@@ -632,8 +693,8 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
                             return (
                               <div key={device.deviceId} className="flex justify-between text-sm">
                                 <span className="text-gray-600 dark:text-gray-400">{label}</span>
-                                <CopyableText value={`${device.temps[0].celsius}°C`}>
-                                  <span className={`font-mono font-medium ${tempColor(device.temps[0].celsius)}`}>{device.temps[0].celsius}°C</span>
+                                <CopyableText value={formatTemp(device.temps[0])}>
+                                  <span className={`font-mono font-medium ${tempColor(device.temps[0].celsius)}`}>{formatTemp(device.temps[0])}</span>
                                 </CopyableText>
                               </div>
                             )
@@ -645,8 +706,8 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
                                 {device.temps.map((temp, i) => (
                                   <div key={`t${i}`} className="flex justify-between text-sm">
                                     {temp.label && <span className="text-gray-600 dark:text-gray-400 truncate mr-2">{temp.label}</span>}
-                                    <CopyableText value={`${temp.celsius}°C`}>
-                                      <span className={`font-mono font-medium ${tempColor(temp.celsius)}`}>{temp.celsius}°C</span>
+                                    <CopyableText value={formatTemp(temp)}>
+                                      <span className={`font-mono font-medium ${tempColor(temp.celsius)}`}>{formatTemp(temp)}</span>
                                     </CopyableText>
                                   </div>
                                 ))}
@@ -669,6 +730,7 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
               </div>
             )
           })}
+          <ThermalHistory resources={resources} />
         </div>
       )}
       {thermalExpanded && !hasSensors && (
@@ -684,6 +746,77 @@ function ThermalSection({ resources }: { resources: SystemResources | null }) {
   )
 }
 
+interface TopTalker {
+  pid: number
+  name: string
+  rx_bps: number
+  tx_bps: number
+}
+
+interface TopTalkersResult {
+  estimated: boolean
+  note: string
+  interval_ms: number
+  talkers: TopTalker[]
+}
+
+function TopTalkers() {
+  const [result, setResult] = useState<TopTalkersResult | null>(null)
+  const [measuring, setMeasuring] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const measure = async () => {
+    setMeasuring(true)
+    setError(null)
+    try {
+      setResult(await invoke<TopTalkersResult>('get_network_top_talkers'))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setMeasuring(false)
+    }
+  }
+
+  return (
+    <div className="mt-5 pt-4 border-t border-gray-200 dark:border-gray-700">
+      <div className="flex items-center justify-between">
+        <span className="text-sm font-medium text-gray-900 dark:text-gray-100">Top talkers</span>
+        <button
+          onClick={measure}
+          disabled={measuring}
+          className="text-xs text-blue-600 dark:text-blue-400 hover:underline disabled:opacity-50"
+        >
+          {measuring ? 'Measuring...' : result ? 'Measure again' : 'Measure'}
+        </button>
+      </div>
+      {error && <div className="text-xs text-red-500 mt-2">{error}</div>}
+      {result && (
+        <div className="mt-2 space-y-1.5">
+          {result.talkers.length > 0 ? result.talkers.map(t => (
+            <div key={t.pid} className="flex items-center justify-between text-sm">
+              <div className="truncate">
+                <span className="text-gray-900 dark:text-gray-100">{t.name}</span>
+                <span className="ml-1.5 text-xs text-gray-400 font-mono">{t.pid}</span>
+              </div>
+              <div className="flex gap-3 font-mono shrink-0">
+                <span className="flex items-center gap-1 text-green-600 dark:text-green-400">
+                  <ArrowDown size={12} />{formatRate(t.rx_bps)}
+                </span>
+                <span className="flex items-center gap-1 text-blue-600 dark:text-blue-400">
+                  <ArrowUp size={12} />{formatRate(t.tx_bps)}
+                </span>
+              </div>
+            </div>
+          )) : (
+            <div className="text-sm text-gray-400">No network traffic during the sample</div>
+          )}
+          <div className="text-xs text-gray-400 pt-1">{result.note}</div>
+        </div>
+      )}
+    </div>
+  )
+}
+
 export default function Resources() {
   const {
     resources, loading, cpuHistory, memoryHistory, gpuHistory,
@@ -728,6 +861,14 @@ export default function Resources() {
       <div className="flex items-center justify-between">
         <h1 className="text-3xl font-bold text-gray-900 dark:text-gray-100">Resources</h1>
         <div className="flex items-center gap-4 text-sm text-gray-500 dark:text-gray-400">
+          <button
+            onClick={() => invoke('open_monitor_widget').catch(err => console.error('Failed to open monitor widget:', err))}
+            className="flex items-center gap-1.5 hover:text-blue-500 transition-colors"
+            title="Keep CPU, memory and network on screen in a small window"
+          >
+            <PictureInPicture2 size={14} />
+            Mini monitor
+          </button>
           {resources?.uptime !== undefined && (
             <span className="flex items-center gap-1.5">
               <Clock size={14} />
@@ -759,6 +900,12 @@ export default function Resources() {
         </div>
       </div>
 
+      {resources?.degraded_reasons && (
+        <div className="space-y-1 text-sm text-amber-600 dark:text-amber-400">
+          {Object.entries(resources.degraded_reasons).map(([section, reason]) => <div key={section}>{reason}</div>)}
+        </div>
+      )}
+
       <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
         <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
           <SpeedometerGauge value={resources?.cpu ?? 0} max={100} label="CPU Usage" unit="%" history={cpuHistory} color="#3b82f6" onClick={() => setDetailModal('cpu')} />
@@ -854,6 +1001,7 @@ export default function Resources() {
           ) : (
             <div className="text-sm text-gray-400">No active interfaces</div>
           )}
+          <TopTalkers />
         </div>
 
         <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-5">
@@ -867,15 +1015,16 @@ export default function Resources() {
                 const hist = diskIoHistory[disk.name]
                 const nvmeMatch = disk.name.match(/nvme(\d+)/)
                 const nvmeIdx = nvmeMatch ? parseInt(nvmeMatch[1]) : -1
-                const diskTemp = nvmeIdx >= 0 ? storageTemps[nvmeIdx] : undefined
+                const diskTemp = storageTemps.find(t => t.display_name === disk.display_name)
+                  ?? (nvmeIdx >= 0 ? storageTemps[nvmeIdx] : undefined)
                 return (
                   <div key={disk.name}>
                     <div className="flex items-center justify-between">
                       <div className="flex items-center gap-2">
-                        <span className="text-sm font-medium text-gray-900 dark:text-gray-100">{disk.name}</span>
+                        <span className="text-sm font-medium text-gray-900 dark:text-gray-100">{disk.display_name || disk.name}</span>
                         {diskTemp && (
                           <span className={`text-xs font-mono font-medium ${tempColor(diskTemp.celsius)}`}>
-                            {diskTemp.celsius}°C
+                            {formatTemp(diskTemp)}
                           </span>
                         )}
                       </div>
@@ -922,7 +1071,12 @@ export default function Resources() {
               return (
                 <div key={disk.name} className="space-y-2">
                   <div className="flex justify-between text-sm">
-                    <span className="font-medium text-gray-900 dark:text-gray-100">{disk.mount_point || disk.name}</span>
+                    <span className="font-medium text-gray-900 dark:text-gray-100">
+                      {disk.mount_point || disk.name}
+                      {disk.mount_point && disk.display_name && (
+                        <span className="ml-2 font-normal text-gray-500 dark:text-gray-400">{disk.display_name}</span>
+                      )}
+                    </span>
                     <span className="text-gray-500 dark:text-gray-400">{formatDiskSize(used)} / {formatDiskSize(total)}</span>
                   </div>
                   <div className="h-3 bg-gray-200 dark:bg-gray-700 rounded-full overflow-hidden">
@@ -936,6 +1090,10 @@ export default function Resources() {
         </div>
       )}
 
+      <CleanupCard />
+
+      <SnapshotsCard />
+
       {detailModal === 'cpu' && resources && (
         <DetailModal title="CPU Details" onClose={() => setDetailModal(null)}>
           <div className="space-y-6">
@@ -956,9 +1114,18 @@ export default function Resources() {
             {resources.per_cpu && resources.per_cpu.length > 0 && (
               <div>
                 <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mb-2">Per Thread</h3>
+                {resources.topology && (
+                  <div className="text-xs text-gray-500 dark:text-gray-400 mb-2">
+                    {resources.topology.sockets > 1 && `${resources.topology.sockets} sockets × `}
+                    {resources.topology.cores_per_socket} cores × {resources.topology.threads_per_core} threads
+                    {resources.topology.performance_cores !== null && (
+                      <> · {resources.topology.performance_cores} P-cores, {resources.topology.efficiency_cores} E-cores</>
+                    )}
+                  </div>
+                )}
                 <div className="grid grid-cols-8 gap-1">
-                  {resources.per_cpu.map((core, i) => (
-                    <div key={i} className="text-center">
+                  {sortedThreads(resources.per_cpu).map(core => (
+                    <div key={core.name} className="text-center" title={threadTitle(core)}>
                       <div
                         className="h-10 rounded text-xs flex items-end justify-center pb-0.5 font-mono"
                         style={{
@@ -967,7 +1134,10 @@ export default function Resources() {
                       >
                         {core.usage.toFixed(0)}%
                       </div>
-                      <div className="text-[9px] text-gray-400 mt-0.5">{core.frequency}MHz</div>
+                      <div className="text-[9px] text-gray-400 mt-0.5">
+                        {core.core_type === 'efficiency' && <span className="text-emerald-500 font-semibold">E </span>}
+                        {core.frequency}MHz
+                      </div>
                     </div>
                   ))}
                 </div>
@@ -1000,6 +1170,34 @@ export default function Resources() {
                 </div>
               )}
             </div>
+            {resources.swap_devices.length > 0 && (
+              <div>
+                <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mb-2">Swap Devices</h3>
+                <div className="space-y-1 text-sm">
+                  {resources.swap_devices.map(d => (
+                    <div key={d.path} className="flex gap-3 text-gray-600 dark:text-gray-400">
+                      <span className="flex-1 font-mono truncate text-gray-900 dark:text-gray-100">{d.path}</span>
+                      <span>{d.kind}, priority {d.priority}</span>
+                      <span className="font-mono">{formatBytes(d.used)} / {formatBytes(d.size)}</span>
+                      {d.zram && (
+                        <span className="font-mono" title={`${formatBytes(d.zram.orig_data_size)} stored in ${formatBytes(d.zram.compr_data_size)}`}>
+                          {d.zram.ratio !== null ? `${d.zram.ratio.toFixed(2)}×` : '—'}{d.zram.algorithm ? ` ${d.zram.algorithm}` : ''}
+                        </span>
+                      )}
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+            {resources.zswap && (
+              <div className="text-sm text-gray-600 dark:text-gray-400">
+                zswap{resources.zswap.compressor ? ` (${resources.zswap.compressor})` : ''}:{' '}
+                {resources.zswap.pool_total_size !== null
+                  ? `${formatBytes(resources.zswap.pool_total_size)} pool, ${resources.zswap.stored_pages ?? 0} pages stored`
+                  : 'pool stats need root'}
+                {resources.zswap.max_pool_percent !== null && `, max ${resources.zswap.max_pool_percent}% of RAM`}
+              </div>
+            )}
             <div>
               <h3 className="text-sm font-semibold text-gray-700 dark:text-gray-300 mb-2">Usage History (last 5 min)</h3>
               <FullGraph data={memoryHistory} color="#8b5cf6" />
@@ -1,18 +1,77 @@
-import { useEffect, useState } from 'react'
+import { useEffect, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { Plus, Play, Trash2, Shield, Terminal, X, Check, AlertCircle, Eye, Pencil, ChevronDown, ChevronUp } from 'lucide-react'
+import { listen } from '@tauri-apps/api/event'
+import { Plus, Play, Trash2, Shield, Terminal, X, Check, AlertCircle, Eye, Pencil, ChevronDown, ChevronUp, Square, History, Download, Upload, Bell, Star } from 'lucide-react'
+import { errorMessage } from '../utils/errors'
+
+type PromptKind = 'text' | 'password' | 'select' | 'boolean' | 'file' | 'number'
 
 interface ScriptPrompt {
   variable: string
   label: string
+  kind?: PromptKind
+  options?: string[]
+  default?: string
+  required?: boolean
+  raw?: boolean
 }
 
+const PROMPT_KINDS: PromptKind[] = ['text', 'password', 'select', 'boolean', 'file', 'number']
+
+const cleanPrompts = (prompts: ScriptPrompt[]): ScriptPrompt[] =>
+  prompts.map(p => ({
+    ...p,
+    options: p.kind === 'select' ? (p.options ?? []).map(o => o.trim()).filter(Boolean) : undefined,
+  }))
+
 interface CustomScript {
   id: string
   name: string
   command: string
   requires_sudo: boolean
   prompts: ScriptPrompt[]
+  category?: string | null
+  tags?: string[]
+  sort_order?: number
+  body?: string | null
+  interpreter?: string | null
+  on_success?: string | null
+  on_failure?: string | null
+  notify_on_completion?: boolean
+  is_favorite?: boolean
+  login_shell?: boolean
+  accepts_stdin?: boolean
+  skip_preflight?: boolean
+  last_run_at?: string | null
+  last_exit_code?: number | null
+}
+
+interface ScriptRunRecord {
+  started_at: string
+  duration_ms: number
+  success: boolean
+  exit_code: number | null
+  resolved_command: string
+  stdout: string
+  stderr: string
+  truncated: boolean
+}
+
+interface ScriptPreview {
+  resolved_command: string
+  interpreter: string
+  working_directory: string | null
+  elevated: boolean
+  elevation_method: string | null
+  body: string | null
+  env: string[]
+}
+
+interface ImportSummary {
+  imported: string[]
+  overwritten: string[]
+  renamed: { from: string; to: string }[]
+  skipped: string[]
 }
 
 interface ScriptResult {
@@ -28,6 +87,32 @@ interface ExecutionLog {
   command: string
   result: ScriptResult
   timestamp: Date
+  runId?: string
+  running?: boolean
+  elevation?: string | null
+}
+
+interface ScriptOutputEvent {
+  run_id: string
+  stream: 'stdout' | 'stderr'
+  line: string
+  truncated?: boolean
+}
+
+interface ScriptStepEvent {
+  run_id: string
+  step: number
+  script_id: string
+  name: string
+  resolved_command: string
+}
+
+interface ScriptFinishedEvent {
+  run_id: string
+  success: boolean
+  exit_code: number | null
+  signal: number | null
+  chain_error?: string | null
 }
 
 type ScriptForm = {
@@ -35,9 +120,26 @@ type ScriptForm = {
   command: string
   requires_sudo: boolean
   prompts: ScriptPrompt[]
+  category?: string | null
+  tags?: string[]
+  body?: string | null
+  interpreter?: string | null
+  on_success?: string | null
+  on_failure?: string | null
+  notify_on_completion?: boolean
+  login_shell?: boolean
+  accepts_stdin?: boolean
+  skip_preflight?: boolean
 }
 
-const emptyForm = (): ScriptForm => ({ name: '', command: '', requires_sudo: false, prompts: [] })
+// A script is runnable with either a one-line command or a multi-line body.
+const hasScriptContent = (f: ScriptForm) => f.command.trim() !== '' || (f.body ?? '').trim() !== ''
+
+// Runs ended by a signal report it the way a shell would, as 128 + signal.
+const finishedExitCode = (event: ScriptFinishedEvent) =>
+  event.exit_code ?? (event.signal !== null ? 128 + event.signal : null)
+
+const emptyForm = (): ScriptForm => ({ name: '', command: '', requires_sudo: false, prompts: [], category: '', tags: [] })
 
 export default function Scripts() {
   const [scripts, setScripts] = useState<CustomScript[]>([])
@@ -45,17 +147,81 @@ export default function Scripts() {
   const [showAddModal, setShowAddModal] = useState(false)
   const [editingScript, setEditingScript] = useState<CustomScript | null>(null)
   const [newScript, setNewScript] = useState<ScriptForm>(emptyForm())
-  const [runningScript, setRunningScript] = useState<string | null>(null)
+  const [activeRuns, setActiveRuns] = useState<Record<string, string>>({})
+  // Events can arrive before run_script_streaming resolves; hold them until the log entry exists.
+  const knownRuns = useRef(new Set<string>())
+  const earlyEvents = useRef<Record<string, { stdout: string; stderr: string; finished?: ScriptFinishedEvent }>>({})
   const [hoveredScript, setHoveredScript] = useState<string | null>(null)
   const [executionLogs, setExecutionLogs] = useState<ExecutionLog[]>([])
   const [terminalExpanded, setTerminalExpanded] = useState(true)
-  const [promptModal, setPromptModal] = useState<{ script: CustomScript; values: Record<string, string> } | null>(null)
+  const [promptModal, setPromptModal] = useState<{
+    script: CustomScript
+    values: Record<string, string>
+    stdin?: string
+    preview?: ScriptPreview
+    previewError?: string
+  } | null>(null)
   const [confirmDelete, setConfirmDelete] = useState<{ id: string; name: string } | null>(null)
+  const [historyScript, setHistoryScript] = useState<CustomScript | null>(null)
+  const [showImport, setShowImport] = useState(false)
+  const [notice, setNotice] = useState<{ text: string; error?: boolean } | null>(null)
+  const [categoryFilter, setCategoryFilter] = useState<string>('')
+  const [tagFilter, setTagFilter] = useState<string | null>(null)
+  const [dragging, setDragging] = useState<string | null>(null)
 
   useEffect(() => {
     fetchScripts()
   }, [])
 
+  useEffect(() => {
+    const updateRun = (runId: string, update: (log: ExecutionLog) => ExecutionLog) =>
+      setExecutionLogs(prev => prev.map(log => (log.runId === runId ? update(log) : log)))
+
+    const early = (runId: string) => (earlyEvents.current[runId] ??= { stdout: '', stderr: '' })
+
+    const appendOutput = (runId: string, stream: 'stdout' | 'stderr', text: string) => {
+      if (!knownRuns.current.has(runId)) {
+        early(runId)[stream] += text
+        return
+      }
+      updateRun(runId, log => ({
+        ...log,
+        result: { ...log.result, [stream]: log.result[stream] + text },
+      }))
+    }
+
+    const unlistenOutput = listen<ScriptOutputEvent>('script-output', ({ payload }) => {
+      appendOutput(payload.run_id, payload.stream, payload.line + (payload.truncated ? ' [line truncated]\n' : '\n'))
+    })
+    // Chained scripts continue in the same log entry.
+    const unlistenStep = listen<ScriptStepEvent>('script-step', ({ payload }) => {
+      appendOutput(payload.run_id, 'stdout', `==> ${payload.name}\n`)
+    })
+    const unlistenFinished = listen<ScriptFinishedEvent>('script-finished', ({ payload }) => {
+      if (!knownRuns.current.has(payload.run_id)) {
+        early(payload.run_id).finished = payload
+        return
+      }
+      updateRun(payload.run_id, log => ({
+        ...log,
+        running: false,
+        result: {
+          ...log.result,
+          stderr: log.result.stderr + (payload.chain_error ? payload.chain_error + '\n' : ''),
+          success: payload.success,
+          exit_code: finishedExitCode(payload),
+        },
+      }))
+      setActiveRuns(prev => Object.fromEntries(Object.entries(prev).filter(([, runId]) => runId !== payload.run_id)))
+      fetchScripts()
+    })
+    return () => {
+      unlistenOutput.then(unlisten => unlisten())
+      unlistenStep.then(unlisten => unlisten())
+      unlistenFinished.then(unlisten => unlisten())
+    }
+  }, [])
+
   const fetchScripts = async () => {
     try {
       const data = await invoke<CustomScript[]>('list_scripts')
@@ -68,14 +234,33 @@ export default function Scripts() {
   }
 
   const handleAddScript = async () => {
-    if (!newScript.name.trim() || !newScript.command.trim()) return
+    if (!newScript.name.trim() || !hasScriptContent(newScript)) return
     try {
-      await invoke('add_script', {
+      const created = await invoke<CustomScript>('add_script', {
         name: newScript.name,
         command: newScript.command,
         requiresSudo: newScript.requires_sudo,
-        prompts: newScript.prompts,
+        prompts: cleanPrompts(newScript.prompts),
+        category: newScript.category ?? '',
+        tags: newScript.tags ?? [],
+        body: newScript.body ?? '',
+        interpreter: newScript.interpreter ?? '',
       })
+      if (newScript.on_success || newScript.on_failure) {
+        await invoke('set_script_chain', { id: created.id, onSuccess: newScript.on_success ?? '', onFailure: newScript.on_failure ?? '' })
+      }
+      if (newScript.notify_on_completion) {
+        await invoke('set_script_notify', { id: created.id, enabled: true })
+      }
+      if (newScript.login_shell) {
+        await invoke('set_script_login_shell', { id: created.id, enabled: true })
+      }
+      if (newScript.accepts_stdin) {
+        await invoke('set_script_accepts_stdin', { id: created.id, enabled: true })
+      }
+      if (newScript.skip_preflight) {
+        await invoke('set_script_skip_preflight', { id: created.id, enabled: true })
+      }
       setNewScript(emptyForm())
       setShowAddModal(false)
       fetchScripts()
@@ -85,15 +270,28 @@ export default function Scripts() {
   }
 
   const handleUpdateScript = async () => {
-    if (!editingScript || !editingScript.name.trim() || !editingScript.command.trim()) return
+    if (!editingScript || !editingScript.name.trim() || !hasScriptContent(editingScript)) return
     try {
       await invoke('update_script', {
         id: editingScript.id,
         name: editingScript.name,
         command: editingScript.command,
         requiresSudo: editingScript.requires_sudo,
-        prompts: editingScript.prompts,
+        prompts: cleanPrompts(editingScript.prompts),
+        category: editingScript.category ?? '',
+        tags: editingScript.tags ?? [],
+        body: editingScript.body ?? '',
+        interpreter: editingScript.interpreter ?? '',
+      })
+      await invoke('set_script_chain', {
+        id: editingScript.id,
+        onSuccess: editingScript.on_success ?? '',
+        onFailure: editingScript.on_failure ?? '',
       })
+      await invoke('set_script_notify', { id: editingScript.id, enabled: !!editingScript.notify_on_completion })
+      await invoke('set_script_login_shell', { id: editingScript.id, enabled: !!editingScript.login_shell })
+      await invoke('set_script_accepts_stdin', { id: editingScript.id, enabled: !!editingScript.accepts_stdin })
+      await invoke('set_script_skip_preflight', { id: editingScript.id, enabled: !!editingScript.skip_preflight })
       setEditingScript(null)
       fetchScripts()
     } catch (err) {
@@ -101,6 +299,15 @@ export default function Scripts() {
     }
   }
 
+  const toggleFavorite = async (script: CustomScript) => {
+    try {
+      await invoke('set_script_favorite', { id: script.id, value: !script.is_favorite })
+      fetchScripts()
+    } catch (err) {
+      console.error('Failed to update favorite:', err)
+    }
+  }
+
   const handleRemoveScript = (id: string, name: string) => {
     setConfirmDelete({ id, name })
   }
@@ -108,35 +315,77 @@ export default function Scripts() {
   const confirmRemoveScript = async () => {
     if (!confirmDelete) return
     try {
-      await invoke('remove_script', { id: confirmDelete.id })
+      const { cleared_references } = await invoke<{ cleared_references: string[] }>('remove_script', { id: confirmDelete.id })
       setConfirmDelete(null)
+      if (cleared_references.length > 0) {
+        setNotice({ text: `Removed chain links to ${confirmDelete.name} from ${cleared_references.join(', ')}` })
+      }
       fetchScripts()
     } catch (err) {
       console.error('Failed to remove script:', err)
     }
   }
 
+  const loadPreview = async (script: CustomScript, values: Record<string, string>) => {
+    try {
+      const preview = await invoke<ScriptPreview>('preview_script', { id: script.id, args: values })
+      setPromptModal(prev => prev && prev.script.id === script.id ? { ...prev, preview, previewError: undefined } : prev)
+    } catch (err) {
+      setPromptModal(prev => prev && prev.script.id === script.id ? { ...prev, preview: undefined, previewError: errorMessage(err) } : prev)
+    }
+  }
+
+  // Admin scripts always go through the run dialog so the exact command can be checked first.
   const initiateRun = (script: CustomScript) => {
-    if (script.prompts.length > 0) {
+    if (script.prompts.length > 0 || script.requires_sudo || script.accepts_stdin) {
       const initial: Record<string, string> = {}
-      script.prompts.forEach(p => { initial[p.variable] = '' })
+      script.prompts.forEach(p => { initial[p.variable] = p.default ?? (p.kind === 'boolean' ? 'false' : '') })
       setPromptModal({ script, values: initial })
+      if (script.prompts.length === 0) loadPreview(script, initial)
     } else {
       executeScript(script, null)
     }
   }
 
-  const executeScript = async (script: CustomScript, args: Record<string, string> | null) => {
+  const executeScript = async (script: CustomScript, args: Record<string, string> | null, stdin?: string) => {
     setPromptModal(null)
-    setRunningScript(script.id)
     setTerminalExpanded(true)
     try {
-      const result = await invoke<ScriptResult>('run_script', { id: script.id, args })
+      const { run_id, resolved_command, elevation, preflight } = await invoke<{
+        run_id: string | null
+        resolved_command: string
+        elevation: string | null
+        preflight?: { missing: string[]; hint: string }
+      }>('run_script_streaming', { id: script.id, args, stdin: stdin ?? null })
+      if (preflight || !run_id) {
+        setExecutionLogs(prev => [{
+          scriptName: script.name,
+          command: resolved_command,
+          result: { success: false, stdout: '', stderr: (preflight?.hint ?? 'The script did not start') + '\n', exit_code: null },
+          timestamp: new Date(),
+          elevation,
+        }, ...prev].slice(0, 50))
+        return
+      }
+      knownRuns.current.add(run_id)
+      const buffered = earlyEvents.current[run_id] ?? { stdout: '', stderr: '' }
+      delete earlyEvents.current[run_id]
+      if (!buffered.finished) {
+        setActiveRuns(prev => ({ ...prev, [script.id]: run_id }))
+      }
       setExecutionLogs(prev => [{
         scriptName: script.name,
-        command: result.resolved_command ?? script.command,
-        result,
+        command: resolved_command,
+        result: {
+          success: buffered.finished?.success ?? false,
+          stdout: buffered.stdout,
+          stderr: buffered.stderr,
+          exit_code: buffered.finished ? finishedExitCode(buffered.finished) : null,
+        },
         timestamp: new Date(),
+        runId: run_id,
+        running: !buffered.finished,
+        elevation,
       }, ...prev].slice(0, 50))
     } catch (err) {
       setExecutionLogs(prev => [{
@@ -145,33 +394,100 @@ export default function Scripts() {
         result: {
           success: false,
           stdout: '',
-          stderr: err instanceof Error ? err.message : String(err),
+          stderr: errorMessage(err),
           exit_code: -1,
         },
         timestamp: new Date(),
       }, ...prev].slice(0, 50))
-    } finally {
-      setRunningScript(null)
+    }
+  }
+
+  const cancelRun = async (runId: string) => {
+    try {
+      await invoke('cancel_script_run', { runId })
+    } catch (err) {
+      setExecutionLogs(prev => prev.map(log => (log.runId === runId
+        ? { ...log, result: { ...log.result, stderr: log.result.stderr + errorMessage(err) + '\n' } }
+        : log)))
     }
   }
 
   const clearLogs = () => setExecutionLogs([])
 
+  const categories = [...new Set(scripts.map(s => s.category).filter((c): c is string => !!c))].sort()
+  const allTags = [...new Set(scripts.flatMap(s => s.tags ?? []))].sort()
+  const visibleScripts = scripts.filter(s =>
+    (!categoryFilter || s.category === categoryFilter) && (!tagFilter || (s.tags ?? []).includes(tagFilter)))
+
+  // Dropping a card before another moves it there and persists the full order.
+  const handleDrop = async (targetId: string) => {
+    if (!dragging || dragging === targetId) return
+    const ids = scripts.map(s => s.id).filter(id => id !== dragging)
+    ids.splice(ids.indexOf(targetId), 0, dragging)
+    setScripts(ids.map(id => scripts.find(s => s.id === id)!))
+    setDragging(null)
+    try {
+      await invoke('reorder_scripts', { idsInOrder: ids })
+    } catch (err) {
+      setNotice({ text: errorMessage(err), error: true })
+      fetchScripts()
+    }
+  }
+
+  const handleExport = async () => {
+    try {
+      const { path, count } = await invoke<{ path: string; count: number }>('export_scripts', {})
+      setNotice({ text: `Exported ${count} script${count === 1 ? '' : 's'} to ${path}` })
+    } catch (err) {
+      setNotice({ text: errorMessage(err), error: true })
+    }
+  }
+
   if (loading) return <div className="p-4 text-gray-900 dark:text-gray-100">Loading scripts...</div>
 
   return (
     <div className="space-y-6">
       <div className="flex items-center justify-between">
         <h1 className="text-3xl font-bold text-gray-900 dark:text-gray-100">Custom Scripts</h1>
-        <button
-          onClick={() => setShowAddModal(true)}
-          className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors"
-        >
-          <Plus size={20} />
-          Add Script
-        </button>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={() => setShowImport(true)}
+            className="flex items-center gap-2 px-3 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors"
+            title="Import scripts from a YAML bundle"
+          >
+            <Upload size={18} />
+            Import
+          </button>
+          <button
+            onClick={handleExport}
+            disabled={scripts.length === 0}
+            className="flex items-center gap-2 px-3 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 disabled:opacity-50 transition-colors"
+            title="Export all scripts to ~/Downloads"
+          >
+            <Download size={18} />
+            Export
+          </button>
+          <button
+            onClick={() => setShowAddModal(true)}
+            className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors"
+          >
+            <Plus size={20} />
+            Add Script
+          </button>
+        </div>
       </div>
 
+      {notice && (
+        <div className={`flex items-center justify-between px-4 py-2 rounded-lg text-sm ${
+          notice.error
+            ? 'bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-400'
+            : 'bg-green-50 dark:bg-green-900/20 text-green-700 dark:text-green-400'
+        }`}>
+          <span className="break-all">{notice.text}</span>
+          <button onClick={() => setNotice(null)} className="ml-3 opacity-70 hover:opacity-100"><X size={16} /></button>
+        </div>
+      )}
+
       {scripts.length === 0 ? (
         <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-8 text-center">
           <Terminal size={48} className="mx-auto text-gray-400 mb-4" />
@@ -188,14 +504,55 @@ export default function Scripts() {
           </button>
         </div>
       ) : (
+        <>
+        {(categories.length > 0 || allTags.length > 0) && (
+          <div className="flex flex-wrap items-center gap-2">
+            {categories.length > 0 && (
+              <select
+                value={categoryFilter}
+                onChange={(e) => setCategoryFilter(e.target.value)}
+                className="px-3 py-1.5 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-gray-100 text-sm"
+              >
+                <option value="">All categories</option>
+                {categories.map(c => <option key={c} value={c}>{c}</option>)}
+              </select>
+            )}
+            {allTags.map(tag => (
+              <button
+                key={tag}
+                onClick={() => setTagFilter(tagFilter === tag ? null : tag)}
+                className={`px-2.5 py-1 rounded-full text-xs transition-colors ${
+                  tagFilter === tag
+                    ? 'bg-blue-600 text-white'
+                    : 'bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300 hover:bg-gray-200 dark:hover:bg-gray-600'
+                }`}
+              >
+                #{tag}
+              </button>
+            ))}
+          </div>
+        )}
         <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
-          {scripts.map((script) => (
+          {visibleScripts.map((script) => (
             <div
               key={script.id}
-              className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-4 flex flex-col"
+              draggable
+              onDragStart={() => setDragging(script.id)}
+              onDragEnd={() => setDragging(null)}
+              onDragOver={(e) => e.preventDefault()}
+              onDrop={() => handleDrop(script.id)}
+              className={`bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-4 flex flex-col ${
+                dragging === script.id ? 'opacity-50' : ''
+              }`}
             >
               <div className="flex items-start justify-between mb-4">
                 <div className="flex items-center gap-2">
+                  {script.last_run_at && (
+                    <span
+                      className={`w-2 h-2 rounded-full ${script.last_exit_code === 0 ? 'bg-green-500' : 'bg-red-500'}`}
+                      title={`Last run ${new Date(script.last_run_at).toLocaleString()}, exit ${script.last_exit_code ?? '?'}`}
+                    />
+                  )}
                   <h3 className="font-semibold text-gray-900 dark:text-gray-100">{script.name}</h3>
                   {script.requires_sudo && (
                     <Shield size={16} className="text-amber-500" title="Requires admin privileges" />
@@ -212,8 +569,8 @@ export default function Scripts() {
                     </button>
                     {hoveredScript === script.id && (
                       <div className="absolute right-0 top-8 z-10 w-64 p-3 bg-gray-900 dark:bg-gray-700 text-white text-xs rounded-lg shadow-lg">
-                        <div className="text-gray-400 mb-1">Command:</div>
-                        <code className="block whitespace-pre-wrap break-all font-mono">{script.command}</code>
+                        <div className="text-gray-400 mb-1">{script.body ? 'Script body:' : 'Command:'}</div>
+                        <code className="block whitespace-pre-wrap break-all font-mono max-h-48 overflow-y-auto">{script.body || script.command}</code>
                         {script.prompts.length > 0 && (
                           <div className="mt-2 pt-2 border-t border-gray-700">
                             <div className="text-gray-400 mb-1">Prompts:</div>
@@ -226,6 +583,20 @@ export default function Scripts() {
                       </div>
                     )}
                   </div>
+                  <button
+                    onClick={() => toggleFavorite(script)}
+                    className={`p-1.5 transition-colors ${script.is_favorite ? 'text-amber-500' : 'text-gray-400 hover:text-amber-500'}`}
+                    title={script.is_favorite ? 'Remove from favorites' : 'Add to favorites'}
+                  >
+                    <Star size={16} fill={script.is_favorite ? 'currentColor' : 'none'} />
+                  </button>
+                  <button
+                    onClick={() => setHistoryScript(script)}
+                    className="p-1.5 text-gray-400 hover:text-blue-500 transition-colors"
+                    title="Run history"
+                  >
+                    <History size={16} />
+                  </button>
                   <button
                     onClick={() => setEditingScript({ ...script })}
                     className="p-1.5 text-gray-400 hover:text-blue-500 transition-colors"
@@ -243,19 +614,29 @@ export default function Scripts() {
                 </div>
               </div>
 
+              {(script.category || (script.tags ?? []).length > 0) && (
+                <div className="flex flex-wrap gap-1 -mt-2 mb-3 text-xs">
+                  {script.category && (
+                    <span className="px-2 py-0.5 rounded bg-blue-50 dark:bg-blue-900/30 text-blue-700 dark:text-blue-300">{script.category}</span>
+                  )}
+                  {(script.tags ?? []).map(tag => (
+                    <span key={tag} className="px-2 py-0.5 rounded bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300">#{tag}</span>
+                  ))}
+                </div>
+              )}
+
               <button
-                onClick={() => initiateRun(script)}
-                disabled={runningScript === script.id}
+                onClick={() => (activeRuns[script.id] ? cancelRun(activeRuns[script.id]) : initiateRun(script))}
                 className={`flex items-center justify-center gap-2 w-full py-2 rounded-lg transition-colors ${
-                  runningScript === script.id
-                    ? 'bg-gray-300 dark:bg-gray-600 cursor-not-allowed text-gray-600 dark:text-gray-300'
+                  activeRuns[script.id]
+                    ? 'bg-gray-300 dark:bg-gray-600 hover:bg-red-200 dark:hover:bg-red-900/40 text-gray-600 dark:text-gray-300'
                     : 'bg-green-600 hover:bg-green-700 text-white'
                 }`}
               >
-                {runningScript === script.id ? (
+                {activeRuns[script.id] ? (
                   <>
                     <div className="w-4 h-4 border-2 border-gray-600 dark:border-gray-300 border-t-transparent rounded-full animate-spin" />
-                    Running...
+                    Running… <Square size={14} /> Stop
                   </>
                 ) : (
                   <>
@@ -267,6 +648,7 @@ export default function Scripts() {
             </div>
           ))}
         </div>
+        </>
       )}
 
       {executionLogs.length > 0 && (
@@ -306,7 +688,12 @@ export default function Scripts() {
                   <div className="flex items-center gap-2 mb-2 ml-4">
                     <span className="text-gray-500 text-xs">[{log.timestamp.toLocaleTimeString()}]</span>
                     <span className="text-gray-500 text-xs">{log.scriptName}</span>
-                    {log.result.success ? (
+                    {log.elevation && (
+                      <span className="flex items-center gap-1 text-amber-400 text-xs"><Shield size={12} />{log.elevation}</span>
+                    )}
+                    {log.running ? (
+                      <span className="text-yellow-400 text-xs">running…</span>
+                    ) : log.result.success ? (
                       <span className="flex items-center gap-1 text-green-400 text-xs"><Check size={12} />exit 0</span>
                     ) : (
                       <span className="flex items-center gap-1 text-red-400 text-xs"><AlertCircle size={12} />exit {log.result.exit_code}</span>
@@ -331,6 +718,7 @@ export default function Scripts() {
           onConfirm={handleAddScript}
           onClose={() => { setShowAddModal(false); setNewScript(emptyForm()) }}
           confirmLabel="Add Script"
+          otherScripts={scripts}
         />
       )}
 
@@ -343,6 +731,7 @@ export default function Scripts() {
           onConfirm={handleUpdateScript}
           onClose={() => setEditingScript(null)}
           confirmLabel="Save Changes"
+          otherScripts={scripts.filter(s => s.id !== editingScript.id)}
         />
       )}
 
@@ -358,26 +747,99 @@ export default function Scripts() {
             </div>
 
             <div className="space-y-4">
-              {promptModal.script.prompts.map(p => (
-                <div key={p.variable}>
-                  <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">{p.label}</label>
-                  <input
-                    type="text"
-                    value={promptModal.values[p.variable] ?? ''}
-                    onChange={(e) => setPromptModal(prev => prev ? {
-                      ...prev,
-                      values: { ...prev.values, [p.variable]: e.target.value }
-                    } : null)}
-                    onKeyDown={(e) => {
-                      if (e.key === 'Enter') executeScript(promptModal.script, promptModal.values)
-                    }}
-                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono"
-                    autoFocus={promptModal.script.prompts[0].variable === p.variable}
+              {promptModal.script.prompts.map(p => {
+                const value = promptModal.values[p.variable] ?? ''
+                const setValue = (v: string) => setPromptModal(prev => prev ? {
+                  ...prev,
+                  values: { ...prev.values, [p.variable]: v },
+                  preview: undefined,
+                  previewError: undefined,
+                } : null)
+                const inputClass = 'w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono'
+
+                if (p.kind === 'boolean') {
+                  return (
+                    <label key={p.variable} className="flex items-center gap-3 text-sm text-gray-700 dark:text-gray-300">
+                      <input
+                        type="checkbox"
+                        checked={value === 'true'}
+                        onChange={(e) => setValue(e.target.checked ? 'true' : 'false')}
+                        className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                      />
+                      {p.label}
+                    </label>
+                  )
+                }
+                return (
+                  <div key={p.variable}>
+                    <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                      {p.label}{p.required && <span className="text-red-500 ml-0.5">*</span>}
+                    </label>
+                    {p.kind === 'select' ? (
+                      <select value={value} onChange={(e) => setValue(e.target.value)} className={inputClass}>
+                        {!p.required && <option value="">—</option>}
+                        {(p.options ?? []).map(o => <option key={o} value={o}>{o}</option>)}
+                      </select>
+                    ) : (
+                      <input
+                        type={p.kind === 'password' ? 'password' : p.kind === 'number' ? 'number' : 'text'}
+                        value={value}
+                        placeholder={p.kind === 'file' ? '/path/to/file' : undefined}
+                        onChange={(e) => setValue(e.target.value)}
+                        onKeyDown={(e) => {
+                          if (e.key === 'Enter') executeScript(promptModal.script, promptModal.values, promptModal.stdin)
+                        }}
+                        className={inputClass}
+                        autoFocus={promptModal.script.prompts[0].variable === p.variable}
+                      />
+                    )}
+                  </div>
+                )
+              })}
+
+              {promptModal.script.accepts_stdin && (
+                <div>
+                  <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Input (stdin)</label>
+                  <textarea
+                    value={promptModal.stdin ?? ''}
+                    onChange={(e) => setPromptModal(prev => prev ? { ...prev, stdin: e.target.value } : null)}
+                    rows={5}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
+                    autoFocus={promptModal.script.prompts.length === 0}
                   />
                 </div>
-              ))}
+              )}
+
+              {promptModal.preview && (
+                <div className="rounded-lg bg-gray-900 p-3 font-mono text-xs space-y-1">
+                  <div className="text-gray-300 whitespace-pre-wrap break-all">
+                    <span className="text-green-400 select-none">$ </span>{promptModal.preview.resolved_command}
+                  </div>
+                  <div className="text-gray-500">
+                    interpreter {promptModal.preview.interpreter}
+                    {promptModal.preview.working_directory && <> · in {promptModal.preview.working_directory}</>}
+                  </div>
+                  {promptModal.preview.env.length > 0 && (
+                    <div className="text-gray-500">env {promptModal.preview.env.join(', ')}</div>
+                  )}
+                  {promptModal.preview.elevated && (
+                    <div className="flex items-center gap-1 text-amber-400">
+                      <Shield size={12} />
+                      Runs as root via {promptModal.preview.elevation_method ?? 'an admin prompt'}
+                    </div>
+                  )}
+                </div>
+              )}
+              {promptModal.previewError && <p className="text-sm text-red-500">{promptModal.previewError}</p>}
 
               <div className="flex gap-3 pt-2">
+                <button
+                  onClick={() => loadPreview(promptModal.script, promptModal.values)}
+                  className="px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors"
+                  title="Show the exact command without running it"
+                >
+                  <Eye size={16} />
+                </button>
                 <button
                   onClick={() => setPromptModal(null)}
                   className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors"
@@ -385,7 +847,7 @@ export default function Scripts() {
                   Cancel
                 </button>
                 <button
-                  onClick={() => executeScript(promptModal.script, promptModal.values)}
+                  onClick={() => executeScript(promptModal.script, promptModal.values, promptModal.stdin)}
                   className="flex-1 flex items-center justify-center gap-2 px-4 py-2 bg-green-600 hover:bg-green-700 text-white rounded-lg transition-colors"
                 >
                   <Play size={16} />
@@ -397,6 +859,31 @@ export default function Scripts() {
         </div>
       )}
 
+      {showImport && (
+        <ImportScriptsModal
+          onClose={() => setShowImport(false)}
+          onImported={(summary) => {
+            setShowImport(false)
+            fetchScripts()
+            const parts = [
+              `${summary.imported.length} imported`,
+              summary.overwritten.length > 0 && `${summary.overwritten.length} overwritten`,
+              summary.renamed.length > 0 && `renamed ${summary.renamed.map(r => `${r.from} → ${r.to}`).join(', ')}`,
+              summary.skipped.length > 0 && `skipped ${summary.skipped.join(', ')} (already present)`,
+            ].filter(Boolean)
+            setNotice({ text: parts.join('; ') })
+          }}
+        />
+      )}
+
+      {historyScript && (
+        <ScriptHistoryModal
+          script={historyScript}
+          onClose={() => setHistoryScript(null)}
+          onCleared={fetchScripts}
+        />
+      )}
+
       {/* Delete Confirmation Modal */}
       {confirmDelete && (
         <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
@@ -405,6 +892,14 @@ export default function Scripts() {
             <p className="text-gray-600 dark:text-gray-400 mb-6">
               Are you sure you want to delete <span className="font-semibold text-gray-900 dark:text-gray-100">{confirmDelete.name}</span>? This cannot be undone.
             </p>
+            {(() => {
+              const linked = scripts.filter(s => s.on_success === confirmDelete.id || s.on_failure === confirmDelete.id)
+              return linked.length > 0 && (
+                <p className="text-sm text-amber-600 dark:text-amber-400 -mt-4 mb-6">
+                  {linked.map(s => s.name).join(', ')} {linked.length === 1 ? 'chains' : 'chain'} to this script; those links will be removed.
+                </p>
+              )
+            })()}
             <div className="flex gap-3">
               <button
                 onClick={() => setConfirmDelete(null)}
@@ -434,6 +929,7 @@ function ScriptFormModal({
   onConfirm,
   onClose,
   confirmLabel,
+  otherScripts,
 }: {
   title: string
   form: ScriptForm
@@ -441,10 +937,11 @@ function ScriptFormModal({
   onConfirm: () => void
   onClose: () => void
   confirmLabel: string
+  otherScripts: CustomScript[]
 }) {
-  const addPrompt = () => onChange({ ...form, prompts: [...form.prompts, { variable: '', label: '' }] })
+  const addPrompt = () => onChange({ ...form, prompts: [...form.prompts, { variable: '', label: '', kind: 'text' }] })
 
-  const updatePrompt = (index: number, field: keyof ScriptPrompt, value: string) => {
+  const updatePrompt = <K extends keyof ScriptPrompt>(index: number, field: K, value: ScriptPrompt[K]) => {
     const prompts = form.prompts.map((p, i) => i === index ? { ...p, [field]: value } : p)
     onChange({ ...form, prompts })
   }
@@ -453,7 +950,8 @@ function ScriptFormModal({
     onChange({ ...form, prompts: form.prompts.filter((_, i) => i !== index) })
   }
 
-  const valid = form.name.trim() !== '' && form.command.trim() !== ''
+  const valid = form.name.trim() !== '' && hasScriptContent(form)
+  const isBody = form.body != null
 
   return (
     <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
@@ -478,19 +976,85 @@ function ScriptFormModal({
           </div>
 
           <div>
-            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Command</label>
-            <textarea
-              value={form.command}
-              onChange={(e) => onChange({ ...form, command: e.target.value })}
-              placeholder={'e.g., shutdown -h +{delay}'}
-              rows={3}
-              className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
-            />
-            {form.prompts.length > 0 && (
-              <p className="text-xs text-gray-400 mt-1">Use <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">{'{variable}'}</code> in the command to insert prompt values.</p>
+            <div className="flex items-center justify-between mb-1">
+              <label className="text-sm font-medium text-gray-700 dark:text-gray-300">{isBody ? 'Script Body' : 'Command'}</label>
+              <div className="flex text-xs rounded-lg border border-gray-300 dark:border-gray-600 overflow-hidden">
+                {(['Command', 'Script body'] as const).map(mode => {
+                  const active = (mode === 'Script body') === isBody
+                  return (
+                    <button
+                      key={mode}
+                      type="button"
+                      onClick={() => onChange({ ...form, body: mode === 'Script body' ? (form.body ?? '') : null })}
+                      className={`px-2 py-1 ${active ? 'bg-blue-600 text-white' : 'text-gray-600 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700'}`}
+                    >
+                      {mode}
+                    </button>
+                  )
+                })}
+              </div>
+            </div>
+            {isBody ? (
+              <>
+                <textarea
+                  value={form.body ?? ''}
+                  onChange={(e) => onChange({ ...form, body: e.target.value })}
+                  placeholder={'#!/bin/bash\nset -e\necho "Backing up $1"'}
+                  rows={8}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
+                />
+                <input
+                  type="text"
+                  value={form.interpreter ?? ''}
+                  onChange={(e) => onChange({ ...form, interpreter: e.target.value })}
+                  placeholder="Interpreter (default: the #! line, or sh)"
+                  className="mt-2 w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
+                />
+                {form.prompts.length > 0 && (
+                  <p className="text-xs text-gray-400 mt-1">
+                    Prompt values are passed as <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">$1</code>, <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">$2</code>… in prompt order, and as environment variables named after each variable.
+                  </p>
+                )}
+              </>
+            ) : (
+              <>
+                <textarea
+                  value={form.command}
+                  onChange={(e) => onChange({ ...form, command: e.target.value })}
+                  placeholder={'e.g., shutdown -h +{delay}'}
+                  rows={3}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
+                />
+                {form.prompts.length > 0 && (
+                  <p className="text-xs text-gray-400 mt-1">Use <code className="bg-gray-100 dark:bg-gray-700 px-1 rounded">{'{variable}'}</code> in the command to insert prompt values.</p>
+                )}
+              </>
             )}
           </div>
 
+          <div className="flex gap-3">
+            <div className="flex-1">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Category</label>
+              <input
+                type="text"
+                value={form.category ?? ''}
+                onChange={(e) => onChange({ ...form, category: e.target.value })}
+                placeholder="e.g., Maintenance"
+                className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent text-sm"
+              />
+            </div>
+            <div className="flex-1">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Tags</label>
+              <input
+                type="text"
+                value={(form.tags ?? []).join(', ')}
+                onChange={(e) => onChange({ ...form, tags: e.target.value.split(',').map(t => t.trimStart()) })}
+                placeholder="Comma separated"
+                className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent text-sm"
+              />
+            </div>
+          </div>
+
           <div className="flex items-center gap-3">
             <input
               type="checkbox"
@@ -505,6 +1069,78 @@ function ScriptFormModal({
             </label>
           </div>
 
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="modal_notify_on_completion"
+              checked={!!form.notify_on_completion}
+              onChange={(e) => onChange({ ...form, notify_on_completion: e.target.checked })}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="modal_notify_on_completion" className="text-sm text-gray-700 dark:text-gray-300 flex items-center gap-2">
+              <Bell size={16} className="text-blue-500" />
+              Notify me when it finishes
+            </label>
+          </div>
+
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="modal_login_shell"
+              checked={!!form.login_shell}
+              onChange={(e) => onChange({ ...form, login_shell: e.target.checked })}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="modal_login_shell" className="text-sm text-gray-700 dark:text-gray-300 flex items-center gap-2">
+              <Terminal size={16} className="text-gray-500" />
+              Run in my login shell (same PATH as a terminal; ignored for non-shell interpreters)
+            </label>
+          </div>
+
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="modal_accepts_stdin"
+              checked={!!form.accepts_stdin}
+              onChange={(e) => onChange({ ...form, accepts_stdin: e.target.checked })}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="modal_accepts_stdin" className="text-sm text-gray-700 dark:text-gray-300">
+              Ask for input to pipe into the script (stdin)
+            </label>
+          </div>
+
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="modal_skip_preflight"
+              checked={!!form.skip_preflight}
+              onChange={(e) => onChange({ ...form, skip_preflight: e.target.checked })}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="modal_skip_preflight" className="text-sm text-gray-700 dark:text-gray-300">
+              Skip the missing-program check (for shell functions and aliases)
+            </label>
+          </div>
+
+          {otherScripts.length > 0 && (
+            <div className="flex gap-3">
+              {([['on_success', 'On success, run'], ['on_failure', 'On failure, run']] as const).map(([field, label]) => (
+                <div key={field} className="flex-1">
+                  <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">{label}</label>
+                  <select
+                    value={form[field] ?? ''}
+                    onChange={(e) => onChange({ ...form, [field]: e.target.value || null })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 text-sm"
+                  >
+                    <option value="">Nothing</option>
+                    {otherScripts.map(s => <option key={s.id} value={s.id}>{s.name}</option>)}
+                  </select>
+                </div>
+              ))}
+            </div>
+          )}
+
           <div>
             <div className="flex items-center justify-between mb-2">
               <label className="text-sm font-medium text-gray-700 dark:text-gray-300">Prompts</label>
@@ -523,7 +1159,8 @@ function ScriptFormModal({
               <div className="space-y-2">
                 {form.prompts.map((p, i) => (
                   <div key={i} className="flex gap-2 items-start">
-                    <div className="flex-1 flex gap-2">
+                    <div className="flex-1 space-y-2">
+                    <div className="flex gap-2">
                       <input
                         type="text"
                         value={p.variable}
@@ -539,6 +1176,52 @@ function ScriptFormModal({
                         className="flex-1 px-2 py-1.5 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 text-sm focus:ring-2 focus:ring-blue-500 focus:border-transparent"
                       />
                     </div>
+                    <div className="flex gap-2 items-center">
+                      <select
+                        value={p.kind ?? 'text'}
+                        onChange={(e) => updatePrompt(i, 'kind', e.target.value as PromptKind)}
+                        className="w-28 px-2 py-1.5 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 text-sm"
+                      >
+                        {PROMPT_KINDS.map(k => <option key={k} value={k}>{k}</option>)}
+                      </select>
+                      {p.kind === 'select' ? (
+                        <input
+                          type="text"
+                          value={(p.options ?? []).join(', ')}
+                          onChange={(e) => updatePrompt(i, 'options', e.target.value.split(',').map(o => o.trimStart()))}
+                          placeholder="Options, comma separated"
+                          className="flex-1 px-2 py-1.5 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 text-sm"
+                        />
+                      ) : p.kind !== 'password' && (
+                        <input
+                          type="text"
+                          value={p.default ?? ''}
+                          onChange={(e) => updatePrompt(i, 'default', e.target.value || undefined)}
+                          placeholder="Default"
+                          className="flex-1 px-2 py-1.5 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 text-sm"
+                        />
+                      )}
+                      <label className="flex items-center gap-1 text-xs text-gray-600 dark:text-gray-400 whitespace-nowrap">
+                        <input
+                          type="checkbox"
+                          checked={p.required ?? false}
+                          onChange={(e) => updatePrompt(i, 'required', e.target.checked)}
+                        />
+                        Required
+                      </label>
+                      <label
+                        className="flex items-center gap-1 text-xs text-gray-600 dark:text-gray-400 whitespace-nowrap"
+                        title="Insert the value unquoted so it can add flags or shell syntax"
+                      >
+                        <input
+                          type="checkbox"
+                          checked={p.raw ?? false}
+                          onChange={(e) => updatePrompt(i, 'raw', e.target.checked)}
+                        />
+                        Raw
+                      </label>
+                    </div>
+                    </div>
                     <button onClick={() => removePrompt(i)} className="p-1.5 text-gray-400 hover:text-red-500 transition-colors mt-0.5">
                       <X size={14} />
                     </button>
@@ -568,3 +1251,176 @@ function ScriptFormModal({
     </div>
   )
 }
+
+function ScriptHistoryModal({
+  script,
+  onClose,
+  onCleared,
+}: {
+  script: CustomScript
+  onClose: () => void
+  onCleared: () => void
+}) {
+  const [runs, setRuns] = useState<ScriptRunRecord[] | null>(null)
+  const [expanded, setExpanded] = useState<number | null>(0)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    invoke<ScriptRunRecord[]>('get_script_history', { id: script.id, limit: 20 })
+      .then(setRuns)
+      .catch(err => setError(errorMessage(err)))
+  }, [script.id])
+
+  const clearHistory = async () => {
+    try {
+      await invoke('clear_script_history', { id: script.id })
+      setRuns([])
+      onCleared()
+    } catch (err) {
+      setError(errorMessage(err))
+    }
+  }
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
+      <div className="bg-white dark:bg-gray-800 rounded-xl p-6 w-full max-w-2xl mx-4 shadow-xl max-h-[90vh] flex flex-col">
+        <div className="flex items-center justify-between mb-4">
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">History — {script.name}</h2>
+          <button onClick={onClose} className="p-1 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300">
+            <X size={20} />
+          </button>
+        </div>
+
+        {error && <p className="text-sm text-red-500 mb-3">{error}</p>}
+
+        <div className="flex-1 overflow-y-auto space-y-2">
+          {runs === null ? (
+            <p className="text-sm text-gray-500 dark:text-gray-400">Loading…</p>
+          ) : runs.length === 0 ? (
+            <p className="text-sm text-gray-500 dark:text-gray-400">This script hasn't been run yet.</p>
+          ) : (
+            runs.map((run, index) => (
+              <div key={index} className="border border-gray-200 dark:border-gray-700 rounded-lg">
+                <button
+                  onClick={() => setExpanded(expanded === index ? null : index)}
+                  className="w-full flex items-center gap-3 px-3 py-2 text-left text-sm"
+                >
+                  <span className={`w-2 h-2 rounded-full ${run.success ? 'bg-green-500' : 'bg-red-500'}`} />
+                  <span className="text-gray-900 dark:text-gray-100">{new Date(run.started_at).toLocaleString()}</span>
+                  <span className="text-gray-500 dark:text-gray-400">{(run.duration_ms / 1000).toFixed(1)}s</span>
+                  <span className="text-gray-500 dark:text-gray-400">exit {run.exit_code ?? '?'}</span>
+                  {expanded === index ? <ChevronUp size={14} className="ml-auto text-gray-400" /> : <ChevronDown size={14} className="ml-auto text-gray-400" />}
+                </button>
+                {expanded === index && (
+                  <div className="bg-gray-900 rounded-b-lg p-3 font-mono text-xs">
+                    <div className="text-gray-300 mb-2"><span className="text-green-400 select-none">$ </span>{run.resolved_command}</div>
+                    {run.truncated && <div className="text-gray-500 mb-1">… output truncated</div>}
+                    {run.stdout && <pre className="text-gray-300 whitespace-pre-wrap mb-1">{run.stdout}</pre>}
+                    {run.stderr && <pre className="text-red-400 whitespace-pre-wrap">{run.stderr}</pre>}
+                  </div>
+                )}
+              </div>
+            ))
+          )}
+        </div>
+
+        <div className="flex gap-3 pt-4">
+          <button
+            onClick={clearHistory}
+            disabled={!runs || runs.length === 0}
+            className="px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 disabled:opacity-50 transition-colors"
+          >
+            Clear History
+          </button>
+          <button
+            onClick={onClose}
+            className="flex-1 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors"
+          >
+            Close
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+function ImportScriptsModal({
+  onClose,
+  onImported,
+}: {
+  onClose: () => void
+  onImported: (summary: ImportSummary) => void
+}) {
+  const [path, setPath] = useState('')
+  const [overwrite, setOverwrite] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [importing, setImporting] = useState(false)
+
+  const handleImport = async () => {
+    setImporting(true)
+    setError(null)
+    try {
+      onImported(await invoke<ImportSummary>('import_scripts', { path: path.trim(), overwrite }))
+    } catch (err) {
+      setError(errorMessage(err))
+    } finally {
+      setImporting(false)
+    }
+  }
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
+      <div className="bg-white dark:bg-gray-800 rounded-xl p-6 w-full max-w-md mx-4 shadow-xl">
+        <div className="flex items-center justify-between mb-4">
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Import Scripts</h2>
+          <button onClick={onClose} className="p-1 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300">
+            <X size={20} />
+          </button>
+        </div>
+
+        <div className="space-y-4">
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Bundle file</label>
+            <input
+              type="text"
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+              placeholder="~/Downloads/gantry-scripts.yaml"
+              className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-900 text-gray-900 dark:text-gray-100 focus:ring-2 focus:ring-blue-500 focus:border-transparent font-mono text-sm"
+              autoFocus
+            />
+          </div>
+          <label className="flex items-start gap-3 text-sm text-gray-700 dark:text-gray-300">
+            <input
+              type="checkbox"
+              checked={overwrite}
+              onChange={(e) => setOverwrite(e.target.checked)}
+              className="mt-0.5 w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <span>
+              Overwrite scripts with the same name
+              <span className="block text-xs text-gray-500 dark:text-gray-400">Otherwise identical scripts are skipped and changed ones are imported under a new name.</span>
+            </span>
+          </label>
+          {error && <p className="text-sm text-red-500 break-all">{error}</p>}
+        </div>
+
+        <div className="flex gap-3 pt-6">
+          <button
+            onClick={onClose}
+            className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors"
+          >
+            Cancel
+          </button>
+          <button
+            onClick={handleImport}
+            disabled={!path.trim() || importing}
+            className="flex-1 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white rounded-lg transition-colors"
+          >
+            {importing ? 'Importing…' : 'Import'}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -3,6 +3,7 @@ import { invoke } from '@tauri-apps/api/core'
 import { Play, Square, RotateCw, Search, Power, PowerOff, ChevronUp, ChevronDown, User, Monitor, X } from 'lucide-react'
 import Pagination from '../components/Pagination'
 import { usePlatform } from '../hooks/usePlatform'
+import { errorMessage } from '../utils/errors'
 
 interface ServiceInfo {
   name: string
@@ -44,7 +45,7 @@ export default function Services() {
       setError(null)
     } catch (err) {
       console.error('Failed to load services:', err)
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setLoading(false)
     }
@@ -64,7 +65,7 @@ export default function Services() {
       }
       await fetchServices()
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setActionInProgress(null)
     }
@@ -1,13 +1,54 @@
 import { useState, useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { Palette } from 'lucide-react'
-import { AppSettings } from '../types'
+import { Palette, Terminal, RefreshCw, Archive, LayoutGrid, Power, ShieldCheck, Keyboard, Activity } from 'lucide-react'
+import { AppSettings, ModuleName, PageType } from '../types'
+import { NAVIGATION_ITEMS } from '../constants'
 import { applyTheme } from '../utils/theme'
+import { errorMessage } from '../utils/errors'
 
 type Theme = 'light' | 'dark'
+type TemperatureUnit = AppSettings['temperature_unit']
 
-export default function Settings() {
+const MODULE_LABELS: [ModuleName, string][] = [
+  ['processes', 'Processes'],
+  ['services', 'Services'],
+  ['firewall', 'Firewall'],
+  ['repos', 'Repositories'],
+  ['startup', 'Startup Apps'],
+  ['devices', 'Devices'],
+  ['scripts', 'Scripts'],
+  ['logs', 'Logs'],
+]
+
+interface SettingsProps {
+  onModulesChange?: () => void
+}
+
+export default function Settings({ onModulesChange }: SettingsProps) {
   const [theme, setTheme] = useState<Theme>('light')
+  const [temperatureUnit, setTemperatureUnit] = useState<TemperatureUnit>('celsius')
+  const [notifications, setNotifications] = useState(true)
+  const [enabledModules, setEnabledModules] = useState<AppSettings['enabled_modules']>({})
+  const [outputLimitKb, setOutputLimitKb] = useState(1024)
+  const [refresh, setRefresh] = useState({ auto_refresh_enabled: true, refresh_interval_ms: 1000, processes_refresh_ms: 5000, history_secs: 300 })
+  const [defaultPage, setDefaultPage] = useState<PageType>('resources')
+  const [launch, setLaunch] = useState({ start_at_login: false, start_minimized: false })
+  const [adminMinutes, setAdminMinutes] = useState(5)
+  const [adminSession, setAdminSession] = useState<{ active: boolean; expires_at: string | null }>({ active: false, expires_at: null })
+  const [adminError, setAdminError] = useState<string | null>(null)
+  const [shortcuts, setShortcuts] = useState<Record<string, string>>({})
+  const [favorites, setFavorites] = useState<{ id: string; name: string }[]>([])
+  const [shortcutErrors, setShortcutErrors] = useState<Record<string, string>>({})
+  const [metrics, setMetrics] = useState({ enabled: false, bind: '127.0.0.1', port: 9839 })
+  const [metricsStatus, setMetricsStatus] = useState<{ running: boolean; url: string | null }>({ running: false, url: null })
+  const [metricsError, setMetricsError] = useState<string | null>(null)
+  const [includeScripts, setIncludeScripts] = useState(true)
+  const [importPath, setImportPath] = useState('')
+  const [dataOptions, setDataOptions] = useState({ includeLogs: false, includeHistory: false })
+  const [dataPath, setDataPath] = useState('')
+  const [mergeData, setMergeData] = useState(true)
+  const [confirmReset, setConfirmReset] = useState(false)
+  const [notice, setNotice] = useState<{ text: string; error?: boolean } | null>(null)
   const [loading, setLoading] = useState(true)
 
   useEffect(() => {
@@ -20,6 +61,24 @@ export default function Settings() {
       const savedTheme = settings.theme === 'dark' ? 'dark' : 'light'
       setTheme(savedTheme)
       applyTheme(savedTheme)
+      setTemperatureUnit(settings.temperature_unit === 'fahrenheit' ? 'fahrenheit' : 'celsius')
+      setNotifications(settings.notifications_enabled)
+      setEnabledModules(settings.enabled_modules)
+      setOutputLimitKb(Math.round(settings.script_output_limit / 1024))
+      setDefaultPage(settings.default_page)
+      setLaunch({ start_at_login: settings.start_at_login, start_minimized: settings.start_minimized })
+      setAdminMinutes(settings.admin_session_minutes)
+      setAdminSession(await invoke('admin_session_status'))
+      setShortcuts(settings.global_shortcuts)
+      setMetrics({ enabled: settings.metrics_enabled, bind: settings.metrics_bind, port: settings.metrics_port })
+      setMetricsStatus(await invoke('get_metrics_endpoint'))
+      setFavorites(await invoke<{ id: string; name: string }[]>('list_favorite_scripts').catch(() => []))
+      setRefresh({
+        auto_refresh_enabled: settings.auto_refresh_enabled,
+        refresh_interval_ms: settings.refresh_interval_ms,
+        processes_refresh_ms: settings.processes_refresh_ms,
+        history_secs: settings.history_secs,
+      })
     } catch (err) {
       console.error('Failed to load settings:', err)
       const saved = localStorage.getItem('theme')
@@ -44,6 +103,204 @@ export default function Settings() {
     }
   }
 
+  const handleTemperatureUnitChange = async (unit: TemperatureUnit) => {
+    setTemperatureUnit(unit)
+    try {
+      await invoke('set_temperature_unit', { unit })
+    } catch (err) {
+      console.error('Failed to save temperature unit:', err)
+    }
+  }
+
+  const handleResetWindow = async () => {
+    try {
+      await invoke('reset_window_state')
+    } catch (err) {
+      console.error('Failed to reset window:', err)
+    }
+  }
+
+  const handleModuleChange = async (module: ModuleName, enabled: boolean) => {
+    setEnabledModules(prev => ({ ...prev, [module]: enabled }))
+    try {
+      await invoke('set_module_enabled', { module, enabled })
+      onModulesChange?.()
+    } catch (err) {
+      console.error('Failed to save module setting:', err)
+    }
+  }
+
+  const handleNotificationsChange = async (enabled: boolean) => {
+    setNotifications(enabled)
+    try {
+      await invoke('set_notifications_enabled', { enabled })
+    } catch (err) {
+      console.error('Failed to save notification setting:', err)
+    }
+  }
+
+  const handleDefaultPageChange = async (page: PageType) => {
+    setDefaultPage(page)
+    try {
+      await invoke('update_settings', { partial: { default_page: page } })
+    } catch (err) {
+      console.error('Failed to save default page:', err)
+    }
+  }
+
+  const handleStartAtLoginChange = async (enabled: boolean) => {
+    try {
+      await invoke('set_start_at_login', { enabled })
+      setLaunch(prev => ({ ...prev, start_at_login: enabled }))
+    } catch (err) {
+      setNotice({ text: `Failed to change login startup: ${err}`, error: true })
+    }
+  }
+
+  const handleStartMinimizedChange = async (enabled: boolean) => {
+    setLaunch(prev => ({ ...prev, start_minimized: enabled }))
+    try {
+      await invoke('set_start_minimized', { enabled })
+    } catch (err) {
+      console.error('Failed to save start minimized setting:', err)
+    }
+  }
+
+  const handleOutputLimitSave = async () => {
+    try {
+      await invoke('set_script_output_limit', { limit: Math.max(1, outputLimitKb) * 1024 })
+    } catch (err) {
+      console.error('Failed to save output limit:', err)
+    }
+  }
+
+  const handleAdminMinutesSave = async () => {
+    try {
+      const saved = await invoke<AppSettings>('update_settings', { partial: { admin_session_minutes: adminMinutes } })
+      setAdminMinutes(saved.admin_session_minutes)
+    } catch (err) {
+      console.error('Failed to save admin session length:', err)
+    }
+  }
+
+  const saveMetrics = async (changes: Partial<typeof metrics>) => {
+    const next = { ...metrics, ...changes }
+    setMetrics(next)
+    setMetricsError(null)
+    try {
+      setMetricsStatus(await invoke('set_metrics_endpoint', next))
+    } catch (err) {
+      setMetricsError(errorMessage(err))
+    }
+  }
+
+  // A shortcut another app holds isn't saved; the backend says why.
+  const saveShortcut = async (action: string) => {
+    const accelerator = shortcuts[action]?.trim() || null
+    setShortcutErrors(prev => ({ ...prev, [action]: '' }))
+    try {
+      const result = await invoke<{ accelerator: string | null; registered: boolean; conflict: string | null }>(
+        'set_global_shortcut', { action, accelerator },
+      )
+      if (result.conflict) {
+        setShortcutErrors(prev => ({ ...prev, [action]: result.conflict! }))
+      }
+      setShortcuts(prev => ({ ...prev, [action]: result.accelerator ?? '' }))
+    } catch (err) {
+      setShortcutErrors(prev => ({ ...prev, [action]: errorMessage(err) }))
+    }
+  }
+
+  const toggleAdminSession = async () => {
+    setAdminError(null)
+    try {
+      setAdminSession(await invoke(adminSession.active ? 'end_admin_session' : 'begin_admin_session'))
+    } catch (err) {
+      setAdminError(errorMessage(err))
+    }
+  }
+
+  // The backend clamps intervals, so show what it actually saved.
+  const saveRefresh = async (changes: Partial<typeof refresh>) => {
+    setRefresh(prev => ({ ...prev, ...changes }))
+    try {
+      const saved = await invoke<AppSettings>('update_settings', { partial: changes })
+      setRefresh({
+        auto_refresh_enabled: saved.auto_refresh_enabled,
+        refresh_interval_ms: saved.refresh_interval_ms,
+        processes_refresh_ms: saved.processes_refresh_ms,
+        history_secs: saved.history_secs,
+      })
+    } catch (err) {
+      console.error('Failed to save refresh settings:', err)
+    }
+  }
+
+  const handleExport = async () => {
+    try {
+      const { path } = await invoke<{ path: string }>('export_settings', { includeScripts })
+      setNotice({ text: `Exported settings${includeScripts ? ' and scripts' : ''} to ${path}` })
+    } catch (err) {
+      setNotice({ text: String(err), error: true })
+    }
+  }
+
+  const handleImport = async () => {
+    try {
+      const { scripts_imported, shortcut_failures } = await invoke<{ scripts_imported: number | null; shortcut_failures: { accelerator: string }[] }>(
+        'import_settings', { path: importPath.trim() },
+      )
+      const imported = scripts_imported === null ? 'Imported settings' : `Imported settings and ${scripts_imported} scripts`
+      setNotice(shortcut_failures.length
+        ? { text: `${imported}, but these shortcuts are in use elsewhere: ${shortcut_failures.map(f => f.accelerator).join(', ')}`, error: true }
+        : { text: imported })
+      setImportPath('')
+      await loadSettings()
+      onModulesChange?.()
+    } catch (err) {
+      setNotice({ text: String(err), error: true })
+    }
+  }
+
+  const handleExportData = async () => {
+    try {
+      const { path } = await invoke<{ path: string }>('export_app_data', dataOptions)
+      setNotice({ text: `Exported all Gantry data to ${path}` })
+    } catch (err) {
+      setNotice({ text: errorMessage(err), error: true })
+    }
+  }
+
+  const handleImportData = async () => {
+    try {
+      const { backup, shortcut_failures } = await invoke<{ backup: string; shortcut_failures: { accelerator: string }[] }>(
+        'import_app_data', { path: dataPath.trim(), merge: mergeData },
+      )
+      const imported = `${mergeData ? 'Merged' : 'Restored'} Gantry data; the previous data is saved in ${backup}`
+      setNotice(shortcut_failures.length
+        ? { text: `${imported}, but these shortcuts are in use elsewhere: ${shortcut_failures.map(f => f.accelerator).join(', ')}`, error: true }
+        : { text: imported })
+      setDataPath('')
+      await loadSettings()
+      onModulesChange?.()
+    } catch (err) {
+      setNotice({ text: errorMessage(err), error: true })
+    }
+  }
+
+  const handleReset = async () => {
+    setConfirmReset(false)
+    try {
+      await invoke('reset_settings')
+      localStorage.removeItem('theme')
+      setNotice({ text: 'Settings reset to defaults' })
+      await loadSettings()
+      onModulesChange?.()
+    } catch (err) {
+      setNotice({ text: String(err), error: true })
+    }
+  }
+
   if (loading) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -77,6 +334,421 @@ export default function Settings() {
             <option value="dark">Dark</option>
           </select>
         </div>
+        <div className="mt-4">
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Temperature unit
+          </label>
+          <select
+            value={temperatureUnit}
+            onChange={(e) => handleTemperatureUnitChange(e.target.value as TemperatureUnit)}
+            className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+          >
+            <option value="celsius">Celsius (°C)</option>
+            <option value="fahrenheit">Fahrenheit (°F)</option>
+          </select>
+        </div>
+        <button onClick={handleResetWindow} className="mt-4 text-sm text-blue-600 dark:text-blue-400 hover:underline">
+          Reset window size and position
+        </button>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <Power size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Launch</h2>
+        </div>
+        <div className="space-y-3">
+          <div>
+            <label htmlFor="default_page" className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+              Open on
+            </label>
+            <select
+              id="default_page"
+              value={defaultPage}
+              onChange={(e) => handleDefaultPageChange(e.target.value as PageType)}
+              className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+            >
+              {NAVIGATION_ITEMS.map(item => (
+                <option key={item.id} value={item.id}>{item.label}</option>
+              ))}
+            </select>
+          </div>
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="start_at_login"
+              checked={launch.start_at_login}
+              onChange={(e) => handleStartAtLoginChange(e.target.checked)}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="start_at_login" className="text-sm text-gray-700 dark:text-gray-300">
+              Start Gantry when I log in
+            </label>
+          </div>
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="start_minimized"
+              checked={launch.start_minimized}
+              onChange={(e) => handleStartMinimizedChange(e.target.checked)}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="start_minimized" className="text-sm text-gray-700 dark:text-gray-300">
+              Start minimized
+            </label>
+          </div>
+        </div>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <LayoutGrid size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Modules</h2>
+        </div>
+        <div className="grid grid-cols-2 gap-3">
+          {MODULE_LABELS.map(([module, label]) => (
+            <div key={module} className="flex items-center gap-3">
+              <input
+                type="checkbox"
+                id={`module_${module}`}
+                checked={enabledModules[module] !== false}
+                onChange={(e) => handleModuleChange(module, e.target.checked)}
+                className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+              />
+              <label htmlFor={`module_${module}`} className="text-sm text-gray-700 dark:text-gray-300">
+                {label}
+              </label>
+            </div>
+          ))}
+        </div>
+        <p className="text-xs text-gray-500 dark:text-gray-400 mt-3">
+          Pages for tools this machine doesn't have are hidden either way
+        </p>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <RefreshCw size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Refresh</h2>
+        </div>
+        <div className="space-y-4">
+          <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              id="auto_refresh_enabled"
+              checked={refresh.auto_refresh_enabled}
+              onChange={(e) => saveRefresh({ auto_refresh_enabled: e.target.checked })}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="auto_refresh_enabled" className="text-sm text-gray-700 dark:text-gray-300">
+              Refresh live views automatically
+            </label>
+          </div>
+          {([
+            ['refresh_interval_ms', 'Resources refresh interval (ms)', 250],
+            ['processes_refresh_ms', 'Processes refresh interval (ms)', 250],
+            ['history_secs', 'History graphs cover (seconds)', 60],
+          ] as const).map(([field, label, step]) => (
+            <div key={field}>
+              <label htmlFor={field} className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+                {label}
+              </label>
+              <input
+                type="number"
+                id={field}
+                min={step}
+                step={step}
+                value={refresh[field]}
+                onChange={(e) => setRefresh(prev => ({ ...prev, [field]: Number(e.target.value) }))}
+                onBlur={() => saveRefresh({ [field]: refresh[field] })}
+                className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+              />
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <Terminal size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Scripts</h2>
+        </div>
+        <div className="flex items-center gap-3">
+          <input
+            type="checkbox"
+            id="notifications_enabled"
+            checked={notifications}
+            onChange={(e) => handleNotificationsChange(e.target.checked)}
+            className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+          />
+          <label htmlFor="notifications_enabled" className="text-sm text-gray-700 dark:text-gray-300">
+            Show a desktop notification when a script set to notify finishes
+          </label>
+        </div>
+        <div className="mt-4">
+          <label htmlFor="script_output_limit" className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Script output kept per run (KB)
+          </label>
+          <input
+            type="number"
+            id="script_output_limit"
+            min={1}
+            value={outputLimitKb}
+            onChange={(e) => setOutputLimitKb(Number(e.target.value))}
+            onBlur={handleOutputLimitSave}
+            className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+          />
+          <p className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+            Longer output is cut; the full text is saved under ~/.gantry/script_output
+          </p>
+        </div>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <Keyboard size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Shortcuts</h2>
+        </div>
+        <p className="text-xs text-gray-500 dark:text-gray-400 mb-4">
+          Work from any application, e.g. CommandOrControl+Shift+G. Leave empty for none.
+        </p>
+        <div className="space-y-3">
+          {[{ action: 'toggle_window', label: 'Show or hide Gantry' }, ...favorites.map(f => ({ action: `script:${f.id}`, label: `Run ${f.name}` }))].map(({ action, label }) => (
+            <div key={action}>
+              <div className="flex items-center gap-3">
+                <label htmlFor={`shortcut_${action}`} className="w-48 text-sm text-gray-700 dark:text-gray-300 truncate">
+                  {label}
+                </label>
+                <input
+                  type="text"
+                  id={`shortcut_${action}`}
+                  value={shortcuts[action] ?? ''}
+                  onChange={(e) => setShortcuts(prev => ({ ...prev, [action]: e.target.value }))}
+                  onBlur={() => saveShortcut(action)}
+                  placeholder="None"
+                  className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900 font-mono text-sm"
+                />
+              </div>
+              {shortcutErrors[action] && (
+                <div className="mt-1 text-sm text-red-600 dark:text-red-400">{shortcutErrors[action]}</div>
+              )}
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <ShieldCheck size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Administrator</h2>
+        </div>
+        <div>
+          <label htmlFor="admin_session_minutes" className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Admin session length (minutes)
+          </label>
+          <input
+            type="number"
+            id="admin_session_minutes"
+            min={1}
+            max={60}
+            value={adminMinutes}
+            onChange={(e) => setAdminMinutes(Number(e.target.value))}
+            onBlur={handleAdminMinutesSave}
+            className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+          />
+          <p className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+            Service and repository changes during a session don't ask for your password again. Every action is logged to ~/.gantry/audit.log
+          </p>
+        </div>
+        <div className="mt-4 flex items-center gap-3">
+          <button
+            onClick={toggleAdminSession}
+            className="px-4 py-2 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700"
+          >
+            {adminSession.active ? 'End session' : 'Start session'}
+          </button>
+          <span className="text-sm text-gray-700 dark:text-gray-300">
+            {adminSession.active && adminSession.expires_at
+              ? `Active until ${new Date(adminSession.expires_at).toLocaleTimeString()}`
+              : 'No session'}
+          </span>
+        </div>
+        {adminError && <div className="mt-2 text-sm text-red-600 dark:text-red-400">{adminError}</div>}
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <Activity size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Metrics</h2>
+        </div>
+        <div className="flex items-center gap-3">
+          <input
+            type="checkbox"
+            id="metrics_enabled"
+            checked={metrics.enabled}
+            onChange={(e) => saveMetrics({ enabled: e.target.checked })}
+            className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+          />
+          <label htmlFor="metrics_enabled" className="text-sm text-gray-700 dark:text-gray-300">
+            Serve Prometheus metrics over HTTP
+          </label>
+        </div>
+        <div className="mt-4 grid grid-cols-2 gap-4">
+          <div>
+            <label htmlFor="metrics_bind" className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+              Bind address
+            </label>
+            <input
+              type="text"
+              id="metrics_bind"
+              value={metrics.bind}
+              onChange={(e) => setMetrics(prev => ({ ...prev, bind: e.target.value }))}
+              onBlur={() => saveMetrics({})}
+              className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+            />
+          </div>
+          <div>
+            <label htmlFor="metrics_port" className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+              Port
+            </label>
+            <input
+              type="number"
+              id="metrics_port"
+              min={1}
+              max={65535}
+              value={metrics.port}
+              onChange={(e) => setMetrics(prev => ({ ...prev, port: Number(e.target.value) }))}
+              onBlur={() => saveMetrics({})}
+              className="w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+            />
+          </div>
+        </div>
+        <p className="text-xs text-gray-500 dark:text-gray-400 mt-2">
+          {metricsStatus.running && metricsStatus.url ? `Serving ${metricsStatus.url}` : 'Not running'}. Any address other than 127.0.0.1 makes the metrics reachable from your network.
+        </p>
+        {metricsError && <div className="mt-2 text-sm text-red-600 dark:text-red-400">{metricsError}</div>}
+      </div>
+
+      <div className="bg-white dark:bg-gray-800 rounded-xl border border-gray-200 dark:border-gray-700 p-6">
+        <div className="flex items-center gap-3 mb-4">
+          <Archive size={24} className="text-blue-600 dark:text-blue-400" />
+          <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">Backup</h2>
+        </div>
+        {notice && (
+          <div className={`mb-4 text-sm ${notice.error ? 'text-red-600 dark:text-red-400' : 'text-green-600 dark:text-green-400'}`}>
+            {notice.text}
+          </div>
+        )}
+        <div className="space-y-4">
+          <div className="flex items-center gap-3">
+            <button
+              onClick={handleExport}
+              className="px-4 py-2 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700"
+            >
+              Export
+            </button>
+            <input
+              type="checkbox"
+              id="export_include_scripts"
+              checked={includeScripts}
+              onChange={(e) => setIncludeScripts(e.target.checked)}
+              className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+            />
+            <label htmlFor="export_include_scripts" className="text-sm text-gray-700 dark:text-gray-300">
+              Include scripts
+            </label>
+          </div>
+          <div className="flex gap-2">
+            <input
+              type="text"
+              value={importPath}
+              onChange={(e) => setImportPath(e.target.value)}
+              placeholder="~/Downloads/gantry-settings.yaml"
+              className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+            />
+            <button
+              onClick={handleImport}
+              disabled={!importPath.trim()}
+              className="px-4 py-2 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700 disabled:opacity-50"
+            >
+              Import
+            </button>
+          </div>
+          <div className="border-t border-gray-200 dark:border-gray-700 pt-4 space-y-3">
+            <p className="text-sm text-gray-600 dark:text-gray-400">
+              Everything in ~/.gantry (settings, scripts and favorites) as one .tar.gz archive.
+            </p>
+            <div className="flex items-center gap-3">
+              <button
+                onClick={handleExportData}
+                className="px-4 py-2 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700"
+              >
+                Export all data
+              </button>
+              <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+                <input
+                  type="checkbox"
+                  checked={dataOptions.includeLogs}
+                  onChange={(e) => setDataOptions({ ...dataOptions, includeLogs: e.target.checked })}
+                  className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                />
+                Include logs
+              </label>
+              <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+                <input
+                  type="checkbox"
+                  checked={dataOptions.includeHistory}
+                  onChange={(e) => setDataOptions({ ...dataOptions, includeHistory: e.target.checked })}
+                  className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                />
+                Include script history
+              </label>
+            </div>
+            <div className="flex items-center gap-2">
+              <input
+                type="text"
+                value={dataPath}
+                onChange={(e) => setDataPath(e.target.value)}
+                placeholder="~/Downloads/gantry-data.tar.gz"
+                className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent bg-white text-gray-900"
+              />
+              <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300" title="Keep existing scripts and history, adding only what's new">
+                <input
+                  type="checkbox"
+                  checked={mergeData}
+                  onChange={(e) => setMergeData(e.target.checked)}
+                  className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                />
+                Merge
+              </label>
+              <button
+                onClick={handleImportData}
+                disabled={!dataPath.trim()}
+                className="px-4 py-2 bg-blue-600 text-white text-sm rounded-lg hover:bg-blue-700 disabled:opacity-50"
+              >
+                Import
+              </button>
+            </div>
+          </div>
+          {confirmReset ? (
+            <div className="flex items-center gap-2">
+              <span className="text-sm text-gray-700 dark:text-gray-300">Reset every setting to its default?</span>
+              <button onClick={handleReset} className="px-3 py-1.5 bg-red-500 text-white text-sm rounded-lg hover:bg-red-600">
+                Reset
+              </button>
+              <button
+                onClick={() => setConfirmReset(false)}
+                className="px-3 py-1.5 bg-gray-300 dark:bg-gray-600 text-gray-700 dark:text-gray-300 text-sm rounded-lg"
+              >
+                Cancel
+              </button>
+            </div>
+          ) : (
+            <button onClick={() => setConfirmReset(true)} className="text-sm text-red-600 dark:text-red-400 hover:underline">
+              Reset to defaults
+            </button>
+          )}
+        </div>
       </div>
 
       <div className="bg-gray-50 dark:bg-gray-800/50 rounded-lg p-4 text-sm text-gray-600 dark:text-gray-400">
@@ -3,13 +3,17 @@ import { listStartupApps, addStartupApp, editStartupApp, deleteStartupApp, toggl
 import { Plus, Pencil, Trash2, RotateCw } from 'lucide-react'
 import Pagination from '../components/Pagination'
 import { usePlatform } from '../hooks/usePlatform'
+import { errorMessage } from '../utils/errors'
 
 interface StartupApp {
   name?: string
   file?: string
   exec?: string
+  argv?: string[] | null
   enabled?: boolean
   file_path?: string
+  scope?: 'system' | 'user' | 'user-override'
+  source?: 'autostart' | 'systemd-user'
 }
 
 export default function StartupApps() {
@@ -38,7 +42,7 @@ export default function StartupApps() {
       const data = await listStartupApps()
       setApps(Array.isArray(data) ? data : [])
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
       setApps([])
     } finally {
       setIsLoading(false)
@@ -55,7 +59,7 @@ export default function StartupApps() {
       setFormExec('')
       await fetchApps()
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     }
   }
 
@@ -69,7 +73,7 @@ export default function StartupApps() {
       setFormExec('')
       await fetchApps()
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     }
   }
 
@@ -80,7 +84,7 @@ export default function StartupApps() {
       setDeleteConfirm(null)
       await fetchApps()
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     }
   }
 
@@ -92,7 +96,7 @@ export default function StartupApps() {
       await toggleStartupApp(app.file, !app.enabled)
       await fetchApps()
     } catch (err) {
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setTogglingFile(null)
     }
@@ -162,17 +166,26 @@ export default function StartupApps() {
                 <div className="flex-1 min-w-0">
                   <div className={`font-semibold truncate ${app.enabled ? 'text-gray-900 dark:text-gray-100' : 'text-gray-400 dark:text-gray-500'}`}>
                     {app.name || app.file}
+                    {app.source === 'systemd-user' ? (
+                      <span className="ml-2 text-xs font-normal text-gray-500 dark:text-gray-400">systemd</span>
+                    ) : app.scope && app.scope !== 'user' && (
+                      <span className="ml-2 text-xs font-normal text-gray-500 dark:text-gray-400">
+                        {app.scope === 'system' ? 'system' : 'overridden'}
+                      </span>
+                    )}
                   </div>
                   <div className="text-sm text-gray-600 dark:text-gray-400 truncate">{app.exec}</div>
                 </div>
 
                 <div className="flex items-center gap-1">
-                  <button
-                    onClick={() => openEditModal(app)}
-                    className="p-2 text-gray-400 hover:text-blue-500 hover:bg-gray-100 dark:hover:bg-gray-600 rounded transition-colors"
-                  >
-                    <Pencil size={16} />
-                  </button>
+                  {app.source !== 'systemd-user' && (
+                    <button
+                      onClick={() => openEditModal(app)}
+                      className="p-2 text-gray-400 hover:text-blue-500 hover:bg-gray-100 dark:hover:bg-gray-600 rounded transition-colors"
+                    >
+                      <Pencil size={16} />
+                    </button>
+                  )}
                   <button
                     onClick={() => setDeleteConfirm(app.file || null)}
                     className="p-2 text-gray-400 hover:text-red-500 hover:bg-gray-100 dark:hover:bg-gray-600 rounded transition-colors"
@@ -1,9 +1,151 @@
-export type PageType = 'settings' | 'devices' | 'processes' | 'repositories' | 'startup' | 'resources' | 'logs' | 'scripts' | 'services'
+export type PageType = 'settings' | 'devices' | 'processes' | 'repositories' | 'startup' | 'resources' | 'logs' | 'scripts' | 'services' | 'firewall'
 
 export type Platform = 'linux' | 'macos' | 'windows'
 
+export type ModuleName = 'processes' | 'services' | 'repos' | 'startup' | 'devices' | 'scripts' | 'logs' | 'firewall'
+
+export type PackageManager = 'apt' | 'dnf' | 'pacman' | 'flatpak' | 'snap' | 'brew'
+
+// A list endpoint's answer when polled with ifChangedSince.
+export type Polled<T> =
+  | { unchanged: true; generation: number }
+  | { unchanged: false; generation: number; data: T }
+
+export interface PlatformCapabilities {
+  os: Platform
+  distro_id: string | null
+  distro_family: 'debian' | 'rhel' | 'arch' | 'suse' | null
+  package_managers: PackageManager[]
+  init_system: string
+  desktop_environment: string | null
+  container: string | null
+}
+
 export interface AppSettings {
+  version: number
   theme: string
+  repo_backup_keep: number
+  notifications_enabled: boolean
+  script_output_limit: number
+  refresh_interval_ms: number
+  processes_refresh_ms: number
+  auto_refresh_enabled: boolean
+  history_secs: number
+  temperature_unit: 'celsius' | 'fahrenheit'
+  window_state: WindowState | null
+  monitor_widget_position: { x: number; y: number } | null
+  enabled_modules: Partial<Record<ModuleName, boolean>>
+  alerts: AlertSettings
+  start_at_login: boolean
+  start_minimized: boolean
+  default_page: PageType
+  page_preferences: Partial<Record<PageType, Record<string, unknown>>>
+  log_max_bytes: number
+  log_keep: number
+  log_format: 'text' | 'json'
+  admin_session_minutes: number
+  global_shortcuts: Record<string, string>
+  metrics_enabled: boolean
+  metrics_bind: string
+  metrics_port: number
+  snapshots_keep: number
+  // Filled in by get_settings: enabled and usable on this machine.
+  visible_modules: Record<ModuleName, boolean>
+}
+
+export interface AlertSettings {
+  enabled: boolean
+  desktop_notifications: boolean
+  cpu_percent: number | null
+  cpu_sustained_secs: number
+  memory_percent: number | null
+  temperature_celsius: number | null
+  disk_free_percent: number | null
+  disk_free_percent_by_mount: Record<string, number>
+  disk_full_days: number | null
+  cooldown_secs: number
+}
+
+export interface SystemAlert {
+  key: string
+  kind: 'cpu' | 'memory' | 'temperature' | 'disk'
+  message: string
+  value: number
+  threshold: number
+  since: string
+}
+
+export interface FileLogEntry {
+  ts: string
+  level: 'debug' | 'info' | 'warn' | 'error'
+  message: string
+  source: string | null
+}
+
+export interface LogFileContent {
+  content: string
+  // False until the first entry is written; a cleared log still exists.
+  exists: boolean
+  size: number
+}
+
+export interface LogSearchOptions {
+  regex?: boolean
+  caseSensitive?: boolean
+  maxResults?: number
+  level?: FileLogEntry['level']
+}
+
+export interface LogSearchResult {
+  results: { file: string; line: number; offset: number; entry: FileLogEntry }[]
+  truncated: boolean
+}
+
+export interface LogTail {
+  entries: FileLogEntry[]
+  // Pass back as beforeCursor for older lines; null at the start of the file.
+  cursor: number | null
+  exists: boolean
+}
+
+export interface LogFileInfo {
+  path: string
+  exists: boolean
+  size: number
+  modified?: string | null
+  line_estimate: number
+  rotated: { path: string; size: number }[]
+}
+
+export interface WindowState {
+  width: number
+  height: number
+  x: number
+  y: number
+  maximized: boolean
+}
+
+export interface StartupAppOptions {
+  delaySeconds?: number
+  onlyShowIn?: string[]
+  comment?: string
+  terminal?: boolean
+  argv?: string[]
+  fromDesktopId?: string
+}
+
+export interface RepoHealthFinding {
+  kind: 'legacy_keyring' | 'release_missing' | 'duplicate'
+  message: string
+  key_fingerprint?: string
+  url?: string
+  status?: number
+  duplicate_of?: string[]
+}
+
+export interface RepoHealthReport {
+  findings: Record<string, RepoHealthFinding[]>
+  legacy_keyring: boolean
 }
 
 export interface Process {
@@ -69,4 +211,21 @@ export interface MenuItem {
   id: PageType
   label: string
   icon: React.ComponentType<{ size: number }>
+  module?: ModuleName
+}
+
+export type GantryErrorCode =
+  | 'permission_denied'
+  | 'not_found'
+  | 'tool_missing'
+  | 'parse_error'
+  | 'io'
+  | 'unsupported'
+  | 'cancelled'
+  | 'failed'
+
+export interface GantryError {
+  code: GantryErrorCode
+  message: string
+  details: { tool?: string } | null
 }
@@ -0,0 +1,16 @@
+import type { GantryError, GantryErrorCode } from '../types'
+
+export function isGantryError(err: unknown): err is GantryError {
+  return typeof err === 'object' && err !== null && 'code' in err && 'message' in err
+}
+
+// Commands reject with either a GantryError or, for modules not migrated yet, a plain string.
+export function errorMessage(err: unknown): string {
+  if (isGantryError(err)) return err.message
+  if (err instanceof Error) return err.message
+  return String(err)
+}
+
+export function errorCode(err: unknown): GantryErrorCode | null {
+  return isGantryError(err) ? err.code : null
+}
@@ -55,7 +55,7 @@ function logMessage(level: LogLevel, message: string, data?: unknown): void {
   saveLogs(logs)
 
   const dataStr = data !== undefined ? ` | ${safeStringify(data)}` : ''
-  const fileLogMessage = `[${level.toUpperCase()}] ${message}${dataStr}`
+  const fileLogMessage = `${message}${dataStr}`
 
   const consoleMethod = level === 'error' ? 'error' : level === 'warn' ? 'warn' : 'log'
   console[consoleMethod as 'log' | 'warn' | 'error'](
@@ -64,7 +64,7 @@ function logMessage(level: LogLevel, message: string, data?: unknown): void {
   )
 
   if (level === 'warn' || level === 'error') {
-    writeLogToFile(fileLogMessage).catch(() => {})
+    writeLogToFile(fileLogMessage, level).catch(() => {})
   }
 }
 